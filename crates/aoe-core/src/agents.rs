@@ -3,9 +3,61 @@
 //! All per-agent metadata lives here. Adding a new agent means adding one
 //! `AgentDef` entry to `AGENTS` and writing a status detection function.
 
+use serde::{Deserialize, Serialize};
+
 use crate::session::Status;
+use crate::tmux::output_parser::{self, OutputParser};
 use crate::tmux::status_detection;
 
+/// Model provider an agent talks to, for grouping sessions and capping how
+/// many run against one provider's rate limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    Anthropic,
+    OpenAI,
+    Google,
+    Mistral,
+    GitHub,
+    Cursor,
+    /// A model served on this machine or network, e.g. through Ollama
+    Local,
+    /// An agent that works with several providers and was not told which
+    Other,
+}
+
+impl Provider {
+    pub const ALL: [Provider; 8] = [
+        Provider::Anthropic,
+        Provider::OpenAI,
+        Provider::Google,
+        Provider::Mistral,
+        Provider::GitHub,
+        Provider::Cursor,
+        Provider::Local,
+        Provider::Other,
+    ];
+
+    /// Lowercase name, as used in config and on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            Provider::Anthropic => "anthropic",
+            Provider::OpenAI => "openai",
+            Provider::Google => "google",
+            Provider::Mistral => "mistral",
+            Provider::GitHub => "github",
+            Provider::Cursor => "cursor",
+            Provider::Local => "local",
+            Provider::Other => "other",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Provider> {
+        let name = name.trim().to_lowercase();
+        Provider::ALL.into_iter().find(|p| p.name() == name)
+    }
+}
+
 /// How to check whether an agent binary is installed on the host.
 pub enum DetectionMethod {
     /// Run `which <binary>` and check exit code.
@@ -58,14 +110,29 @@ pub struct AgentDef {
     /// CLI flag template for custom instruction injection.
     /// `{}` is replaced with the shell-escaped instruction text.
     pub instruction_flag: Option<&'static str>,
+    /// CLI argument template for an initial prompt on first launch.
+    /// `{}` is replaced with the shell-quoted prompt text.
+    pub prompt_flag: Option<&'static str>,
+    /// Template for naming a file in the initial prompt so the agent reads
+    /// it itself, e.g. `@{}`. `None` means attached files are inlined.
+    pub file_reference: Option<&'static str>,
+    /// Command that runs the agent's interactive login flow, used to
+    /// re-authenticate a session whose credentials expired. `None` means the
+    /// agent prompts for login when launched, so the binary itself is run.
+    pub login_command: Option<&'static str>,
     /// If true, `builder.rs` sets `instance.command = binary` for this agent.
     pub set_default_command: bool,
     /// If true, the agent can be launched directly on the host (non-sandboxed).
     pub supports_host_launch: bool,
     /// Status detection function pointer. Takes raw (non-lowercased) pane content.
     pub detect_status: fn(&str) -> Status,
+    /// Parser turning pane output into structured events for the preview.
+    /// `None` means the preview shows the raw pane.
+    pub parse_output: Option<OutputParser>,
     /// Environment variables always injected into the container for this agent.
     pub container_env: &'static [(&'static str, &'static str)],
+    /// Provider the agent talks to unless the session says otherwise.
+    pub provider: Provider,
     /// Hook configuration for file-based status detection. If set, AoE installs
     /// hooks into the agent's settings file so status is written to a file instead
     /// of being parsed from tmux pane content.
@@ -104,10 +171,15 @@ pub const AGENTS: &[AgentDef] = &[
         detection: DetectionMethod::Which("claude"),
         yolo: Some(YoloMode::CliFlag("--dangerously-skip-permissions")),
         instruction_flag: Some("--append-system-prompt {}"),
+        prompt_flag: Some("{}"),
+        file_reference: Some("@{}"),
+        login_command: Some("claude /login"),
         set_default_command: false,
         supports_host_launch: true,
         detect_status: status_detection::detect_claude_status,
+        parse_output: Some(output_parser::parse_claude_output),
         container_env: &[("CLAUDE_CONFIG_DIR", "/root/.claude")],
+        provider: Provider::Anthropic,
         hook_config: Some(AgentHookConfig {
             settings_rel_path: ".claude/settings.json",
             events: CLAUDE_CURSOR_HOOK_EVENTS,
@@ -120,10 +192,15 @@ pub const AGENTS: &[AgentDef] = &[
         detection: DetectionMethod::Which("opencode"),
         yolo: Some(YoloMode::EnvVar("OPENCODE_PERMISSION", r#"{"*":"allow"}"#)),
         instruction_flag: None,
+        prompt_flag: Some("--prompt {}"),
+        file_reference: None,
+        login_command: Some("opencode auth login"),
         set_default_command: true,
         supports_host_launch: false,
         detect_status: status_detection::detect_opencode_status,
+        parse_output: None,
         container_env: &[],
+        provider: Provider::Other,
         hook_config: None,
     },
     AgentDef {
@@ -133,10 +210,15 @@ pub const AGENTS: &[AgentDef] = &[
         detection: DetectionMethod::RunWithArg("vibe", "--version"),
         yolo: Some(YoloMode::CliFlag("--agent auto-approve")),
         instruction_flag: None,
+        prompt_flag: Some("{}"),
+        file_reference: None,
+        login_command: None,
         set_default_command: false,
         supports_host_launch: true,
         detect_status: status_detection::detect_vibe_status,
+        parse_output: None,
         container_env: &[],
+        provider: Provider::Mistral,
         hook_config: None,
     },
     AgentDef {
@@ -148,10 +230,15 @@ pub const AGENTS: &[AgentDef] = &[
             "--dangerously-bypass-approvals-and-sandbox",
         )),
         instruction_flag: Some("--config developer_instructions={}"),
+        prompt_flag: Some("{}"),
+        file_reference: None,
+        login_command: Some("codex login"),
         set_default_command: true,
         supports_host_launch: true,
         detect_status: status_detection::detect_codex_status,
+        parse_output: None,
         container_env: &[],
+        provider: Provider::OpenAI,
         hook_config: None,
     },
     AgentDef {
@@ -161,10 +248,15 @@ pub const AGENTS: &[AgentDef] = &[
         detection: DetectionMethod::Which("gemini"),
         yolo: Some(YoloMode::CliFlag("--approval-mode yolo")),
         instruction_flag: None,
+        prompt_flag: Some("--prompt-interactive {}"),
+        file_reference: Some("@{}"),
+        login_command: None,
         set_default_command: false,
         supports_host_launch: true,
         detect_status: status_detection::detect_gemini_status,
+        parse_output: None,
         container_env: &[],
+        provider: Provider::Google,
         hook_config: Some(AgentHookConfig {
             settings_rel_path: ".gemini/settings.json",
             events: &[
@@ -198,10 +290,15 @@ pub const AGENTS: &[AgentDef] = &[
         detection: DetectionMethod::Which("agent"),
         yolo: Some(YoloMode::CliFlag("--yolo")),
         instruction_flag: None,
+        prompt_flag: Some("{}"),
+        file_reference: None,
+        login_command: Some("agent login"),
         set_default_command: false,
         supports_host_launch: true,
         detect_status: status_detection::detect_cursor_status,
+        parse_output: None,
         container_env: &[("CURSOR_CONFIG_DIR", "/root/.cursor")],
+        provider: Provider::Cursor,
         hook_config: Some(AgentHookConfig {
             settings_rel_path: ".cursor/settings.json",
             events: CLAUDE_CURSOR_HOOK_EVENTS,
@@ -214,10 +311,15 @@ pub const AGENTS: &[AgentDef] = &[
         detection: DetectionMethod::Which("copilot"),
         yolo: Some(YoloMode::CliFlag("--yolo")),
         instruction_flag: None,
+        prompt_flag: Some("--interactive {}"),
+        file_reference: None,
+        login_command: None,
         set_default_command: false,
         supports_host_launch: true,
         detect_status: status_detection::detect_copilot_status,
+        parse_output: None,
         container_env: &[("COPILOT_CONFIG_DIR", "/root/.copilot")],
+        provider: Provider::GitHub,
         hook_config: None,
     },
     AgentDef {
@@ -228,10 +330,15 @@ pub const AGENTS: &[AgentDef] = &[
         // Pi runs in full YOLO mode by default (no approval gates), so no flag needed.
         yolo: Some(YoloMode::AlwaysYolo),
         instruction_flag: None,
+        prompt_flag: Some("{}"),
+        file_reference: None,
+        login_command: None,
         set_default_command: false,
         supports_host_launch: true,
         detect_status: status_detection::detect_pi_status,
+        parse_output: None,
         container_env: &[("PI_CODING_AGENT_DIR", "/root/.pi/agent")],
+        provider: Provider::Other,
         hook_config: None,
     },
 ];
@@ -351,6 +458,19 @@ mod tests {
         assert_eq!(name_from_settings_index(99), None);
     }
 
+    #[test]
+    fn test_prompt_flags_have_placeholder() {
+        for agent in AGENTS {
+            if let Some(flag) = agent.prompt_flag {
+                assert!(
+                    flag.contains("{}"),
+                    "Agent '{}' prompt_flag must contain a placeholder",
+                    agent.name
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_all_agents_have_yolo_support() {
         for agent in AGENTS {
@@ -16,6 +16,15 @@ impl Default for Docker {
     }
 }
 
+impl Docker {
+    /// Podman, driven through its Docker-compatible CLI.
+    pub fn podman() -> Self {
+        Self {
+            base: RuntimeBase::PODMAN,
+        }
+    }
+}
+
 impl ContainerRuntimeInterface for Docker {
     fn is_available(&self) -> bool {
         self.base.is_available()
@@ -6,7 +6,7 @@ pub(crate) mod runtime_base;
 
 use std::collections::HashMap;
 
-use crate::cli::truncate_id;
+use crate::session::truncate_id;
 use crate::session::{Config, ContainerRuntimeName};
 use apple_container::AppleContainer;
 pub use container_interface::{ContainerConfig, ContainerRuntimeInterface, VolumeMount};
@@ -32,6 +32,7 @@ pub fn runtime_binary() -> &'static str {
         match cfg.sandbox.container_runtime {
             ContainerRuntimeName::AppleContainer => "container",
             ContainerRuntimeName::Docker => "docker",
+            ContainerRuntimeName::Podman => "podman",
         }
     } else {
         "docker"
@@ -43,6 +44,7 @@ pub fn get_container_runtime() -> ContainerRuntime {
         match cfg.sandbox.container_runtime {
             ContainerRuntimeName::AppleContainer => AppleContainer::default().into(),
             ContainerRuntimeName::Docker => Docker::default().into(),
+            ContainerRuntimeName::Podman => Docker::podman().into(),
         }
     } else {
         ContainerRuntime::default()
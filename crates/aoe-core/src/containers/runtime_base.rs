@@ -36,6 +36,17 @@ impl RuntimeBase {
         supports_remove_volumes: true,
     };
 
+    /// Podman's CLI is Docker-compatible, so it only differs in the binary.
+    pub const PODMAN: Self = Self {
+        binary: "podman",
+        name: "Podman",
+        daemon_check_args: &["info"],
+        pull_prefix: &["pull"],
+        remove_subcommand: "rm",
+        supports_read_only_volumes: true,
+        supports_remove_volumes: true,
+    };
+
     pub const APPLE_CONTAINER: Self = Self {
         binary: "container",
         name: "Apple Container",
@@ -228,7 +239,7 @@ impl RuntimeBase {
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            if stderr.contains("No such container") {
+            if stderr.to_lowercase().contains("no such container") {
                 return Err(DockerError::ContainerNotFound(name.to_string()));
             }
             return Err(DockerError::StopFailed(stderr.to_string()));
@@ -253,7 +264,7 @@ impl RuntimeBase {
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            if stderr.contains("No such container") {
+            if stderr.to_lowercase().contains("no such container") {
                 return Err(DockerError::ContainerNotFound(name.to_string()));
             }
             return Err(DockerError::RemoveFailed(stderr.to_string()));
@@ -353,6 +364,13 @@ mod tests {
         assert_eq!(cmd, "container exec -it my-container my-agent");
     }
 
+    #[test]
+    fn test_exec_command_podman() {
+        let base = RuntimeBase::PODMAN;
+        let cmd = base.exec_command("my-container", Some("-e A=1"), "my-agent");
+        assert_eq!(cmd, "podman exec -it -e A=1 my-container my-agent");
+    }
+
     #[test]
     fn test_build_create_args_full_config() {
         let base = RuntimeBase::DOCKER;
@@ -0,0 +1,197 @@
+//! Cloning a repository for a new session
+//!
+//! A new session's path may be a git URL instead of a directory. The
+//! repository is cloned into `worktree.clone_dir`, in a directory named after
+//! it, and the session is then set up there as usual. A directory that
+//! already holds a clone of the same URL is reused, so a second session from
+//! the same URL does not clone again.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use super::error::{GitError, Result};
+
+/// Whether a session path is a git URL rather than a directory: a URL with
+/// a scheme git clones from, or scp-like `user@host:path`.
+pub fn is_clone_url(input: &str) -> bool {
+    let input = input.trim();
+    if ["https://", "http://", "ssh://", "git://", "file://"]
+        .iter()
+        .any(|scheme| input.starts_with(scheme))
+    {
+        return true;
+    }
+    match input.split_once(':') {
+        Some((host, path)) => host.contains('@') && !host.contains('/') && !path.is_empty(),
+        None => false,
+    }
+}
+
+/// The repository's name from its URL, e.g. `repo` for
+/// `git@github.com:org/repo.git`.
+pub fn repo_name(url: &str) -> Option<&str> {
+    let url = url.trim().trim_end_matches('/');
+    let last = url.rsplit(['/', ':']).next()?;
+    let name = last.strip_suffix(".git").unwrap_or(last);
+    (!name.is_empty()).then_some(name)
+}
+
+/// Where `url` is cloned within `clone_dir`, which may start with `~`.
+pub fn clone_path(url: &str, clone_dir: &str) -> Result<PathBuf> {
+    let name = repo_name(url).ok_or_else(|| {
+        GitError::CloneFailed(format!("Cannot tell the repository name from {}", url))
+    })?;
+    let clone_dir = match clone_dir.strip_prefix("~") {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => dirs::home_dir()
+            .unwrap_or_default()
+            .join(rest.trim_start_matches('/')),
+        _ => PathBuf::from(clone_dir),
+    };
+    Ok(clone_dir.join(name))
+}
+
+/// Clone `url` into `clone_dir` (only its latest commit if `shallow`), or
+/// reuse an earlier clone of it there, and return the repository's path.
+/// `progress` is called with each progress line git prints.
+pub fn ensure_clone(
+    url: &str,
+    clone_dir: &str,
+    shallow: bool,
+    mut progress: impl FnMut(&str),
+) -> Result<PathBuf> {
+    let url = url.trim();
+    let dest = clone_path(url, clone_dir)?;
+    if dest.exists() {
+        if origin_url(&dest).as_deref() == Some(url) {
+            return Ok(dest);
+        }
+        return Err(GitError::CloneFailed(format!(
+            "{} already exists and is not a clone of {}",
+            dest.display(),
+            url
+        )));
+    }
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut cmd = Command::new("git");
+    cmd.args(["clone", "--progress"]);
+    if shallow {
+        cmd.args(["--depth", "1"]);
+    }
+    // Never wait on a credential prompt nobody can see
+    let mut child = cmd
+        .arg("--")
+        .arg(url)
+        .arg(&dest)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Progress lines are redrawn in place with \r, so split on that too
+    let mut last_line = String::new();
+    if let Some(mut stderr) = child.stderr.take() {
+        let mut buf = Vec::new();
+        let mut byte = [0u8; 1];
+        while stderr.read(&mut byte)? == 1 {
+            if byte[0] != b'\r' && byte[0] != b'\n' {
+                buf.push(byte[0]);
+                continue;
+            }
+            let line = String::from_utf8_lossy(&buf).trim().to_string();
+            buf.clear();
+            if !line.is_empty() {
+                progress(&line);
+                last_line = line;
+            }
+        }
+    }
+
+    if !child.wait()?.success() {
+        let _ = std::fs::remove_dir_all(&dest);
+        return Err(GitError::CloneFailed(last_line));
+    }
+    Ok(dest)
+}
+
+fn origin_url(repo: &Path) -> Option<String> {
+    let repo = super::open_repo_at(repo).ok()?;
+    let remote = repo.find_remote("origin").ok()?;
+    remote.url().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_recognizes_clone_urls() {
+        assert!(is_clone_url("https://github.com/org/repo"));
+        assert!(is_clone_url("git@github.com:org/repo.git"));
+        assert!(is_clone_url(" ssh://git@host/org/repo.git "));
+        assert!(!is_clone_url("~/scm/repo"));
+        assert!(!is_clone_url("/tmp/with:colon"));
+        assert!(!is_clone_url("relative/dir"));
+
+        assert_eq!(repo_name("git@github.com:org/repo.git"), Some("repo"));
+        assert_eq!(
+            repo_name("https://gitlab.com/group/sub/tool/"),
+            Some("tool")
+        );
+        assert_eq!(
+            clone_path("https://github.com/org/repo", "/srv/src").unwrap(),
+            PathBuf::from("/srv/src/repo")
+        );
+    }
+
+    #[test]
+    fn test_clones_once_then_reuses() {
+        let dir = TempDir::new().unwrap();
+        let origin = dir.path().join("origin");
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .arg("-C")
+                .arg(&origin)
+                .args(args)
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false)
+        };
+        std::fs::create_dir_all(&origin).unwrap();
+        std::fs::write(origin.join("README.md"), "hello\n").unwrap();
+        if !(git(&["init"])
+            && git(&["add", "README.md"])
+            && git(&[
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "init",
+            ]))
+        {
+            return;
+        }
+
+        let url = format!("file://{}", origin.display());
+        let clone_dir = dir.path().join("clones");
+        let clone_dir = clone_dir.to_str().unwrap();
+        let mut lines = 0;
+        let path = ensure_clone(&url, clone_dir, true, |_| lines += 1).unwrap();
+        assert_eq!(path, dir.path().join("clones/origin"));
+        assert!(path.join("README.md").exists());
+        assert!(lines > 0);
+
+        let again = ensure_clone(&url, clone_dir, false, |_| panic!("cloned twice")).unwrap();
+        assert_eq!(again, path);
+
+        let other = format!("file://{}/", dir.path().join("elsewhere/origin").display());
+        assert!(ensure_clone(&other, clone_dir, false, |_| {}).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! Merge and rebase conflicts in a session's worktree
+//!
+//! An agent that merges or rebases its branch can stop on conflicts and sit
+//! waiting without saying so. The TUI checks worktree sessions alongside its
+//! merged-branch checks, flags the ones stuck on conflicts, and can send the
+//! agent `worktree.conflict_prompt` listing the conflicting files.
+
+use std::path::Path;
+
+use super::open_repo_at;
+
+/// Default `worktree.conflict_prompt`
+pub const DEFAULT_PROMPT: &str = "The {operation} on branch {branch} stopped with conflicts in: {files}. Resolve each conflict keeping the intent of both sides, make sure the code builds, then stage the files and continue the {operation}.";
+
+/// A merge, rebase, cherry-pick or revert stopped on conflicts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// "merge", "rebase", "cherry-pick" or "revert"
+    pub operation: &'static str,
+    /// Conflicting paths, relative to the worktree
+    pub files: Vec<String>,
+}
+
+impl Conflict {
+    /// e.g. "rebase conflicts in 3 files"
+    pub fn summary(&self) -> String {
+        format!(
+            "{} conflicts in {} file{}",
+            self.operation,
+            self.files.len(),
+            if self.files.len() == 1 { "" } else { "s" }
+        )
+    }
+
+    /// Fill in `template`'s `{operation}`, `{files}` (comma-separated) and
+    /// `{branch}` placeholders. The result is a single line so it is sent as
+    /// one message.
+    pub fn prompt(&self, template: &str, branch: &str) -> String {
+        template
+            .replace("{operation}", self.operation)
+            .replace("{files}", &self.files.join(", "))
+            .replace("{branch}", branch)
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// The conflicts the worktree at `path` is stopped on, if any. Conflicted
+/// files left behind without an operation in progress (e.g. by
+/// `git stash pop`) count as a merge.
+pub fn detect(path: &Path) -> Option<Conflict> {
+    let repo = open_repo_at(path).ok()?;
+    let index = repo.index().ok()?;
+    if !index.has_conflicts() {
+        return None;
+    }
+    let mut files: Vec<String> = index
+        .conflicts()
+        .ok()?
+        .filter_map(|c| {
+            let c = c.ok()?;
+            let entry = c.our.or(c.their).or(c.ancestor)?;
+            Some(String::from_utf8_lossy(&entry.path).into_owned())
+        })
+        .collect();
+    files.sort();
+    files.dedup();
+
+    use git2::RepositoryState as S;
+    let operation = match repo.state() {
+        S::Rebase | S::RebaseInteractive | S::RebaseMerge | S::ApplyMailboxOrRebase => "rebase",
+        S::CherryPick | S::CherryPickSequence => "cherry-pick",
+        S::Revert | S::RevertSequence => "revert",
+        _ => "merge",
+    };
+    Some(Conflict { operation, files })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::process::{Command, Output};
+
+    fn git(dir: &Path, args: &[&str]) -> Output {
+        Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .unwrap()
+    }
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        let out = git(dir, args);
+        assert!(
+            out.status.success(),
+            "git {:?}: {}",
+            args,
+            String::from_utf8_lossy(&out.stderr)
+        );
+    }
+
+    fn commit_file(dir: &Path, content: &str) {
+        fs::write(dir.join("a.txt"), content).unwrap();
+        run_git(dir, &["commit", "-q", "-am", content]);
+    }
+
+    #[test]
+    fn test_detect_merge_conflict() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path();
+        run_git(repo, &["init", "-q", "-b", "main"]);
+        run_git(repo, &["config", "user.name", "Test"]);
+        run_git(repo, &["config", "user.email", "test@example.com"]);
+        fs::write(repo.join("a.txt"), "base\n").unwrap();
+        run_git(repo, &["add", "a.txt"]);
+        run_git(repo, &["commit", "-q", "-m", "base"]);
+        run_git(repo, &["checkout", "-q", "-b", "feature"]);
+        commit_file(repo, "feature\n");
+        run_git(repo, &["checkout", "-q", "main"]);
+        commit_file(repo, "main\n");
+        assert_eq!(detect(repo), None);
+
+        assert!(!git(repo, &["merge", "-q", "feature"]).status.success());
+        let conflict = detect(repo).unwrap();
+        assert_eq!(conflict.operation, "merge");
+        assert_eq!(conflict.files, ["a.txt"]);
+        assert_eq!(conflict.summary(), "merge conflicts in 1 file");
+        assert_eq!(
+            conflict.prompt("Fix the {operation} on {branch}:\n{files}", "main"),
+            "Fix the merge on main: a.txt"
+        );
+    }
+}
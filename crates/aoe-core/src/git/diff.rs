@@ -141,9 +141,13 @@ pub fn compute_changed_files(repo_path: &Path, base_branch: &str) -> Result<Vec<
     })?;
 
     // Second pass: collect files
+    let sparse = super::sparse_excluded_paths(&repo);
     for delta in diff.deltas() {
         let status = match delta.status() {
             git2::Delta::Added => FileStatus::Added,
+            git2::Delta::Deleted if delta.old_file().path().is_some_and(|p| sparse.contains(p)) => {
+                continue
+            }
             git2::Delta::Deleted => FileStatus::Deleted,
             git2::Delta::Modified => FileStatus::Modified,
             git2::Delta::Renamed => FileStatus::Renamed,
@@ -489,6 +493,75 @@ pub fn list_branches(repo_path: &Path) -> Result<Vec<String>> {
     Ok(branches)
 }
 
+/// Branches, remote branches and tags a new worktree can start from, most
+/// recently committed first, as listed by `git for-each-ref`.
+pub fn list_refs(repo_path: &Path) -> Result<Vec<String>> {
+    let output = std::process::Command::new("git")
+        .args([
+            "for-each-ref",
+            "--sort=-committerdate",
+            "--format=%(refname)",
+            "refs/heads",
+            "refs/remotes",
+            "refs/tags",
+        ])
+        .current_dir(repo_path)
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(GitError::WorktreeCommandFailed(stderr));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        // Symbolic refs like origin/HEAD duplicate the branch they point to
+        .filter(|name| !name.ends_with("/HEAD"))
+        .filter_map(|name| {
+            ["refs/heads/", "refs/remotes/", "refs/tags/"]
+                .iter()
+                .find_map(|prefix| name.strip_prefix(prefix))
+        })
+        .map(str::to_string)
+        .collect())
+}
+
+/// A commit as offered when picking context for a new session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitSummary {
+    pub short_id: String,
+    pub subject: String,
+}
+
+impl CommitSummary {
+    /// One-line label for pickers: short hash and subject
+    pub fn label(&self) -> String {
+        format!("{} {}", self.short_id, self.subject)
+    }
+}
+
+/// The last `limit` commits reachable from HEAD, newest first.
+pub fn recent_commits(repo_path: &Path, limit: usize) -> Result<Vec<CommitSummary>> {
+    let repo = super::open_repo_at(repo_path)?;
+    let mut walk = repo.revwalk()?;
+    walk.push_head()?;
+
+    let mut commits = Vec::new();
+    for oid in walk.take(limit) {
+        let commit = repo.find_commit(oid?)?;
+        let short_id = commit
+            .as_object()
+            .short_id()?
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        commits.push(CommitSummary {
+            short_id,
+            subject: commit.summary().unwrap_or_default().to_string(),
+        });
+    }
+    Ok(commits)
+}
+
 /// Get the default branch name (main or master)
 pub fn get_default_branch(repo_path: &Path) -> Result<String> {
     let repo = super::open_repo_at(repo_path)?;
@@ -867,6 +940,33 @@ mod tests {
         assert!(!branches.is_empty());
     }
 
+    #[test]
+    fn test_list_refs_includes_tags() {
+        let (dir, repo) = setup_test_repo();
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("feature", &commit, false).unwrap();
+        repo.tag_lightweight("v1.0", commit.as_object(), false)
+            .unwrap();
+
+        let refs = list_refs(dir.path()).unwrap();
+        assert!(refs.contains(&"feature".to_string()));
+        assert!(refs.contains(&"v1.0".to_string()));
+        assert!(refs.iter().all(|r| !r.starts_with("refs/")));
+    }
+
+    #[test]
+    fn test_recent_commits_newest_first() {
+        let (dir, repo) = setup_test_repo();
+        commit_file(&repo, "a.txt", "a", "Add parser\n\nLonger body");
+        commit_file(&repo, "b.txt", "b", "Wire parser into preview");
+
+        let commits = recent_commits(dir.path(), 2).unwrap();
+        let subjects: Vec<&str> = commits.iter().map(|c| c.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["Wire parser into preview", "Add parser"]);
+        assert!(commits[0].label().ends_with(" Wire parser into preview"));
+        assert_eq!(recent_commits(dir.path(), 10).unwrap().len(), 3);
+    }
+
     #[test]
     fn test_get_default_branch() {
         let (dir, _repo) = setup_test_repo();
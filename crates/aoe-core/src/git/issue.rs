@@ -0,0 +1,373 @@
+//! Issue tracker integration for issue-driven sessions.
+//!
+//! Issues are fetched through the provider CLIs (`gh` for GitHub, `glab` for
+//! GitLab) so that authentication is handled by the tools the user already has
+//! configured. Nothing here talks to the provider APIs directly.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of characters of an issue title used in a session title.
+const MAX_TITLE_CHARS: usize = 48;
+
+/// Number of open issues offered by the TUI picker.
+const ISSUE_LIST_LIMIT: &str = "50";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueProvider {
+    GitHub,
+    GitLab,
+}
+
+impl IssueProvider {
+    /// The CLI binary used to talk to this provider.
+    pub fn cli(&self) -> &'static str {
+        match self {
+            IssueProvider::GitHub => "gh",
+            IssueProvider::GitLab => "glab",
+        }
+    }
+}
+
+/// A parsed reference to a single issue, e.g. `github.com/org/repo#123`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueRef {
+    pub provider: IssueProvider,
+    pub host: String,
+    /// Repository path on the host (`org/repo`, or `group/sub/project` on GitLab).
+    pub repo: String,
+    pub number: u64,
+}
+
+impl IssueRef {
+    /// Parse an issue URL.
+    ///
+    /// Accepts `https://<host>/<owner>/<repo>/issues/<n>` (GitHub and GitHub
+    /// Enterprise) and `https://<host>/<group>/<project>/-/issues/<n>` (GitLab).
+    pub fn parse(url: &str) -> Option<Self> {
+        let rest = url
+            .trim()
+            .strip_prefix("https://")
+            .or_else(|| url.trim().strip_prefix("http://"))?;
+        let rest = rest.split(['?', '#']).next().unwrap_or(rest);
+        let (host, path) = rest.split_once('/')?;
+        if host.is_empty() {
+            return None;
+        }
+        let path = path.trim_end_matches('/');
+
+        if let Some((repo, number)) = path.split_once("/-/issues/") {
+            let number = number.parse().ok()?;
+            if repo.split('/').filter(|s| !s.is_empty()).count() < 2 {
+                return None;
+            }
+            return Some(Self {
+                provider: IssueProvider::GitLab,
+                host: host.to_string(),
+                repo: repo.to_string(),
+                number,
+            });
+        }
+
+        let segments: Vec<&str> = path.split('/').collect();
+        match segments.as_slice() {
+            [owner, repo, "issues", number] if !owner.is_empty() && !repo.is_empty() => {
+                Some(Self {
+                    provider: if host.contains("gitlab") {
+                        IssueProvider::GitLab
+                    } else {
+                        IssueProvider::GitHub
+                    },
+                    host: host.to_string(),
+                    repo: format!("{}/{}", owner, repo),
+                    number: number.parse().ok()?,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Canonical web URL for the issue.
+    pub fn url(&self) -> String {
+        match self.provider {
+            IssueProvider::GitHub => {
+                format!("https://{}/{}/issues/{}", self.host, self.repo, self.number)
+            }
+            IssueProvider::GitLab => {
+                format!(
+                    "https://{}/{}/-/issues/{}",
+                    self.host, self.repo, self.number
+                )
+            }
+        }
+    }
+}
+
+/// An issue fetched from its tracker.
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub reference: IssueRef,
+    pub title: String,
+    pub body: String,
+    pub url: String,
+}
+
+impl Issue {
+    /// Session title derived from the issue, e.g. `#123 Fix login redirect`.
+    pub fn session_title(&self) -> String {
+        let title = self.title.trim();
+        let truncated: String = title.chars().take(MAX_TITLE_CHARS).collect();
+        let truncated = if truncated.len() < title.len() {
+            format!("{}...", truncated.trim_end())
+        } else {
+            truncated
+        };
+        format!("#{} {}", self.reference.number, truncated)
+    }
+
+    /// Prompt handed to the agent on first launch.
+    pub fn to_prompt(&self) -> String {
+        let mut prompt = format!(
+            "Work on issue #{}: {}\n\n{}",
+            self.reference.number,
+            self.title.trim(),
+            self.url
+        );
+        let body = self.body.trim();
+        if !body.is_empty() {
+            prompt.push_str("\n\n");
+            prompt.push_str(body);
+        }
+        prompt
+    }
+}
+
+/// Lightweight issue entry used by the TUI picker.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IssueSummary {
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+}
+
+impl IssueSummary {
+    /// Label shown in the picker list.
+    pub fn label(&self) -> String {
+        format!("#{} {}", self.number, self.title)
+    }
+}
+
+#[derive(Deserialize)]
+struct GhIssue {
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct GlabIssue {
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    web_url: String,
+}
+
+fn parse_issue_json(reference: &IssueRef, json: &str) -> Result<Issue> {
+    let (title, body, url) = match reference.provider {
+        IssueProvider::GitHub => {
+            let issue: GhIssue = serde_json::from_str(json).context("Invalid gh output")?;
+            (issue.title, issue.body, issue.url)
+        }
+        IssueProvider::GitLab => {
+            let issue: GlabIssue = serde_json::from_str(json).context("Invalid glab output")?;
+            (issue.title, issue.description, issue.web_url)
+        }
+    };
+    Ok(Issue {
+        reference: reference.clone(),
+        title,
+        body: body.unwrap_or_default(),
+        url,
+    })
+}
+
+fn run_provider_cli(provider: IssueProvider, cmd: &mut Command) -> Result<String> {
+    let output = cmd.output().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            anyhow::anyhow!(
+                "'{}' is not installed\nTip: Install it and run '{} auth login' to use issue links",
+                provider.cli(),
+                provider.cli()
+            )
+        } else {
+            anyhow::anyhow!("Failed to run {}: {}", provider.cli(), e)
+        }
+    })?;
+    if !output.status.success() {
+        bail!(
+            "{} failed: {}",
+            provider.cli(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Fetch an issue's title and body using the provider CLI.
+pub fn fetch_issue(reference: &IssueRef) -> Result<Issue> {
+    let mut cmd = Command::new(reference.provider.cli());
+    match reference.provider {
+        IssueProvider::GitHub => {
+            cmd.args([
+                "issue",
+                "view",
+                &reference.url(),
+                "--json",
+                "title,body,url",
+            ]);
+        }
+        IssueProvider::GitLab => {
+            cmd.args([
+                "issue",
+                "view",
+                &reference.number.to_string(),
+                "--repo",
+                &format!("https://{}/{}", reference.host, reference.repo),
+                "--output",
+                "json",
+            ]);
+        }
+    }
+    let json = run_provider_cli(reference.provider, &mut cmd)?;
+    parse_issue_json(reference, &json)
+}
+
+/// List open GitHub issues for the repository at `repo_path`.
+pub fn list_open_issues(repo_path: &Path) -> Result<Vec<IssueSummary>> {
+    let mut cmd = Command::new(IssueProvider::GitHub.cli());
+    cmd.args([
+        "issue",
+        "list",
+        "--state",
+        "open",
+        "--limit",
+        ISSUE_LIST_LIMIT,
+        "--json",
+        "number,title,url",
+    ])
+    .current_dir(repo_path);
+    let json = run_provider_cli(IssueProvider::GitHub, &mut cmd)?;
+    serde_json::from_str(&json).context("Invalid gh output")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_issue(title: &str, body: &str) -> Issue {
+        let reference = IssueRef::parse("https://github.com/org/repo/issues/123").unwrap();
+        Issue {
+            url: reference.url(),
+            reference,
+            title: title.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_github_url() {
+        let r = IssueRef::parse("https://github.com/org/repo/issues/123").unwrap();
+        assert_eq!(r.provider, IssueProvider::GitHub);
+        assert_eq!(r.host, "github.com");
+        assert_eq!(r.repo, "org/repo");
+        assert_eq!(r.number, 123);
+        assert_eq!(r.url(), "https://github.com/org/repo/issues/123");
+    }
+
+    #[test]
+    fn test_parse_github_url_with_fragment_and_query() {
+        let r = IssueRef::parse("https://github.com/org/repo/issues/7#issuecomment-1?x=1").unwrap();
+        assert_eq!(r.number, 7);
+        let r = IssueRef::parse("https://github.com/org/repo/issues/8/").unwrap();
+        assert_eq!(r.number, 8);
+    }
+
+    #[test]
+    fn test_parse_github_enterprise_url() {
+        let r = IssueRef::parse("https://git.example.com/team/app/issues/42").unwrap();
+        assert_eq!(r.provider, IssueProvider::GitHub);
+        assert_eq!(r.host, "git.example.com");
+    }
+
+    #[test]
+    fn test_parse_gitlab_url() {
+        let r = IssueRef::parse("https://gitlab.com/group/sub/project/-/issues/9").unwrap();
+        assert_eq!(r.provider, IssueProvider::GitLab);
+        assert_eq!(r.repo, "group/sub/project");
+        assert_eq!(r.number, 9);
+        assert_eq!(r.url(), "https://gitlab.com/group/sub/project/-/issues/9");
+    }
+
+    #[test]
+    fn test_parse_legacy_gitlab_url() {
+        let r = IssueRef::parse("https://gitlab.example.org/group/project/issues/3").unwrap();
+        assert_eq!(r.provider, IssueProvider::GitLab);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_urls() {
+        assert!(IssueRef::parse("github.com/org/repo/issues/1").is_none());
+        assert!(IssueRef::parse("https://github.com/org/repo/pull/1").is_none());
+        assert!(IssueRef::parse("https://github.com/org/repo/issues/abc").is_none());
+        assert!(IssueRef::parse("https://github.com/org/issues/1").is_none());
+        assert!(IssueRef::parse("https://gitlab.com/project/-/issues/1").is_none());
+        assert!(IssueRef::parse("").is_none());
+    }
+
+    #[test]
+    fn test_session_title_truncates() {
+        let issue = sample_issue("Short title", "");
+        assert_eq!(issue.session_title(), "#123 Short title");
+
+        let long = "a".repeat(80);
+        let title = sample_issue(&long, "").session_title();
+        assert!(title.ends_with("..."));
+        assert_eq!(title.len(), "#123 ".len() + MAX_TITLE_CHARS + 3);
+    }
+
+    #[test]
+    fn test_prompt_includes_title_url_and_body() {
+        let prompt = sample_issue("Fix login", "Steps to reproduce").to_prompt();
+        assert!(prompt.starts_with("Work on issue #123: Fix login"));
+        assert!(prompt.contains("https://github.com/org/repo/issues/123"));
+        assert!(prompt.ends_with("Steps to reproduce"));
+
+        let prompt = sample_issue("Fix login", "  ").to_prompt();
+        assert!(prompt.ends_with("issues/123"));
+    }
+
+    #[test]
+    fn test_parse_gh_json() {
+        let r = IssueRef::parse("https://github.com/org/repo/issues/5").unwrap();
+        let json =
+            r#"{"title":"Bug","body":"Details","url":"https://github.com/org/repo/issues/5"}"#;
+        let issue = parse_issue_json(&r, json).unwrap();
+        assert_eq!(issue.title, "Bug");
+        assert_eq!(issue.body, "Details");
+    }
+
+    #[test]
+    fn test_parse_glab_json() {
+        let r = IssueRef::parse("https://gitlab.com/g/p/-/issues/5").unwrap();
+        let json = r#"{"iid":5,"title":"Bug","description":null,"web_url":"https://gitlab.com/g/p/-/issues/5"}"#;
+        let issue = parse_issue_json(&r, json).unwrap();
+        assert_eq!(issue.title, "Bug");
+        assert_eq!(issue.body, "");
+        assert_eq!(issue.url, "https://gitlab.com/g/p/-/issues/5");
+    }
+}
@@ -0,0 +1,344 @@
+//! Merge queue for worktree session branches
+//!
+//! Lands several session branches on one base branch in turn: each branch is
+//! rebased onto the current base, optionally tested, and then fast-forwarded
+//! into the base, so the next branch rebases onto the result. A branch that
+//! conflicts or fails its tests is left as it was and the queue moves on.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::mpsc::Sender;
+
+/// Lines of test output kept for the report.
+const TEST_OUTPUT_TAIL: usize = 8;
+
+/// One session branch to land.
+#[derive(Debug, Clone)]
+pub struct MergeEntry {
+    pub session_id: String,
+    pub branch: String,
+    pub worktree_path: PathBuf,
+}
+
+/// Result of landing one branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeOutcome {
+    /// The base branch now includes this branch.
+    Merged,
+    /// Already contained in the base branch; nothing to do.
+    UpToDate,
+    /// Rebasing stopped on conflicts in these files; the rebase was aborted.
+    Conflict(Vec<String>),
+    /// The test command failed; last lines of its output. The branch was
+    /// reset to where it was before the rebase.
+    TestsFailed(String),
+    /// Not attempted (e.g. uncommitted changes in the worktree).
+    Skipped(String),
+    /// A git command failed for another reason.
+    Failed(String),
+}
+
+impl MergeOutcome {
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Merged | Self::UpToDate)
+    }
+
+    /// One-line description for lists and the session timeline.
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Merged => "merged".to_string(),
+            Self::UpToDate => "already up to date".to_string(),
+            Self::Conflict(files) => format!(
+                "conflicts in {} file{}",
+                files.len(),
+                if files.len() == 1 { "" } else { "s" }
+            ),
+            Self::TestsFailed(_) => "tests failed".to_string(),
+            Self::Skipped(reason) => format!("skipped: {}", reason),
+            Self::Failed(_) => "git error".to_string(),
+        }
+    }
+}
+
+/// Progress events sent while the queue runs, indexed by entry position.
+#[derive(Debug, Clone)]
+pub enum MergeProgress {
+    Stage(usize, &'static str),
+    Finished(usize, MergeOutcome),
+    Done,
+}
+
+fn git(dir: &Path, args: &[&str]) -> std::io::Result<Output> {
+    Command::new("git").arg("-C").arg(dir).args(args).output()
+}
+
+fn stderr_of(output: &Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if stderr.is_empty() {
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    } else {
+        stderr
+    }
+}
+
+fn tail(text: &str, lines: usize) -> String {
+    let all: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    all[all.len().saturating_sub(lines)..].join("\n")
+}
+
+/// Run the queue in order, reporting progress on `tx`.
+pub fn run(
+    repo_path: &Path,
+    base_branch: &str,
+    entries: &[MergeEntry],
+    test_command: Option<&str>,
+    tx: &Sender<MergeProgress>,
+) {
+    for (idx, entry) in entries.iter().enumerate() {
+        let outcome = land_branch(repo_path, base_branch, entry, test_command, |stage| {
+            let _ = tx.send(MergeProgress::Stage(idx, stage));
+        });
+        let _ = tx.send(MergeProgress::Finished(idx, outcome));
+    }
+    let _ = tx.send(MergeProgress::Done);
+}
+
+/// Rebase, test, and fast-forward one branch into the base branch.
+pub fn land_branch(
+    repo_path: &Path,
+    base_branch: &str,
+    entry: &MergeEntry,
+    test_command: Option<&str>,
+    stage: impl Fn(&'static str),
+) -> MergeOutcome {
+    let wt = entry.worktree_path.as_path();
+
+    match git(wt, &["status", "--porcelain"]) {
+        Ok(out) if out.status.success() => {
+            if !out.stdout.is_empty() {
+                return MergeOutcome::Skipped("uncommitted changes in the worktree".to_string());
+            }
+        }
+        Ok(out) => return MergeOutcome::Failed(stderr_of(&out)),
+        Err(e) => return MergeOutcome::Failed(e.to_string()),
+    }
+
+    if is_ancestor(wt, "HEAD", base_branch) {
+        return MergeOutcome::UpToDate;
+    }
+
+    // Where the branch was, so failed tests can put it back
+    let original_head = match git(wt, &["rev-parse", "HEAD"]) {
+        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout).trim().to_string(),
+        Ok(out) => return MergeOutcome::Failed(stderr_of(&out)),
+        Err(e) => return MergeOutcome::Failed(e.to_string()),
+    };
+
+    stage("rebasing");
+    match git(wt, &["rebase", base_branch]) {
+        Ok(out) if out.status.success() => {}
+        Ok(out) => {
+            let conflicts = git(wt, &["diff", "--name-only", "--diff-filter=U"])
+                .map(|o| {
+                    String::from_utf8_lossy(&o.stdout)
+                        .lines()
+                        .map(|l| l.to_string())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            let _ = git(wt, &["rebase", "--abort"]);
+            return if conflicts.is_empty() {
+                MergeOutcome::Failed(stderr_of(&out))
+            } else {
+                MergeOutcome::Conflict(conflicts)
+            };
+        }
+        Err(e) => return MergeOutcome::Failed(e.to_string()),
+    }
+
+    if let Some(cmd) = test_command.map(str::trim).filter(|c| !c.is_empty()) {
+        stage("testing");
+        let failure = match Command::new("sh")
+            .arg("-c")
+            .arg(cmd)
+            .current_dir(wt)
+            .output()
+        {
+            Ok(out) if out.status.success() => None,
+            Ok(out) => {
+                let combined = format!(
+                    "{}\n{}",
+                    String::from_utf8_lossy(&out.stdout),
+                    String::from_utf8_lossy(&out.stderr)
+                );
+                Some(tail(&combined, TEST_OUTPUT_TAIL))
+            }
+            Err(e) => Some(e.to_string()),
+        };
+        if let Some(output) = failure {
+            // Undo the rebase so the branch is left as it was
+            return match git(wt, &["reset", "--hard", "--quiet", &original_head]) {
+                Ok(out) if out.status.success() => MergeOutcome::TestsFailed(output),
+                Ok(out) => MergeOutcome::Failed(format!(
+                    "tests failed, then resetting the branch failed: {}",
+                    stderr_of(&out)
+                )),
+                Err(e) => MergeOutcome::Failed(format!(
+                    "tests failed, then resetting the branch failed: {}",
+                    e
+                )),
+            };
+        }
+    }
+
+    stage("merging");
+    let checked_out = git(repo_path, &["symbolic-ref", "--quiet", "--short", "HEAD"])
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+    let result = if checked_out.as_deref() == Some(base_branch) {
+        git(repo_path, &["merge", "--ff-only", &entry.branch])
+    } else {
+        // Fast-forward the base ref without touching any working tree
+        let refspec = format!("{}:{}", entry.branch, base_branch);
+        git(repo_path, &["fetch", ".", &refspec])
+    };
+    match result {
+        Ok(out) if out.status.success() => MergeOutcome::Merged,
+        Ok(out) => MergeOutcome::Failed(stderr_of(&out)),
+        Err(e) => MergeOutcome::Failed(e.to_string()),
+    }
+}
+
+fn is_ancestor(dir: &Path, commit: &str, of: &str) -> bool {
+    git(dir, &["merge-base", "--is-ancestor", commit, of]).is_ok_and(|o| o.status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        let out = git(dir, args).unwrap();
+        assert!(out.status.success(), "git {:?}: {}", args, stderr_of(&out));
+    }
+
+    fn commit_file(dir: &Path, name: &str, content: &str) {
+        fs::write(dir.join(name), content).unwrap();
+        run_git(dir, &["add", name]);
+        run_git(dir, &["commit", "-q", "-m", name]);
+    }
+
+    /// A repo on `main` with two worktree branches.
+    fn setup() -> (TempDir, PathBuf) {
+        let tmp = TempDir::new().unwrap();
+        let repo = tmp.path().join("repo");
+        fs::create_dir(&repo).unwrap();
+        run_git(&repo, &["init", "-q", "-b", "main"]);
+        run_git(&repo, &["config", "user.name", "Test"]);
+        run_git(&repo, &["config", "user.email", "test@example.com"]);
+        commit_file(&repo, "base.txt", "base\n");
+        for branch in ["one", "two"] {
+            let path = tmp.path().join(branch);
+            run_git(
+                &repo,
+                &[
+                    "worktree",
+                    "add",
+                    "-q",
+                    "-b",
+                    branch,
+                    path.to_str().unwrap(),
+                ],
+            );
+        }
+        (tmp, repo)
+    }
+
+    fn entry(tmp: &TempDir, branch: &str) -> MergeEntry {
+        MergeEntry {
+            session_id: branch.to_string(),
+            branch: branch.to_string(),
+            worktree_path: tmp.path().join(branch),
+        }
+    }
+
+    #[test]
+    fn test_lands_branches_in_turn() {
+        let (tmp, repo) = setup();
+        commit_file(&tmp.path().join("one"), "one.txt", "1\n");
+        commit_file(&tmp.path().join("two"), "two.txt", "2\n");
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        run(
+            &repo,
+            "main",
+            &[entry(&tmp, "one"), entry(&tmp, "two")],
+            Some("test -f base.txt"),
+            &tx,
+        );
+        let outcomes: Vec<MergeOutcome> = rx
+            .try_iter()
+            .filter_map(|p| match p {
+                MergeProgress::Finished(_, o) => Some(o),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(outcomes, vec![MergeOutcome::Merged, MergeOutcome::Merged]);
+        assert!(repo.join("one.txt").exists());
+        assert!(repo.join("two.txt").exists());
+    }
+
+    #[test]
+    fn test_conflict_is_reported_and_aborted() {
+        let (tmp, repo) = setup();
+        commit_file(&tmp.path().join("one"), "base.txt", "one\n");
+        commit_file(&tmp.path().join("two"), "base.txt", "two\n");
+
+        let outcome = land_branch(&repo, "main", &entry(&tmp, "one"), None, |_| {});
+        assert_eq!(outcome, MergeOutcome::Merged);
+        let outcome = land_branch(&repo, "main", &entry(&tmp, "two"), None, |_| {});
+        assert_eq!(
+            outcome,
+            MergeOutcome::Conflict(vec!["base.txt".to_string()])
+        );
+        // The rebase was aborted, leaving the branch as it was
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("two").join("base.txt")).unwrap(),
+            "two\n"
+        );
+    }
+
+    fn head_of(dir: &Path) -> String {
+        let out = git(dir, &["rev-parse", "HEAD"]).unwrap();
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn test_failed_tests_block_merge() {
+        let (tmp, repo) = setup();
+        commit_file(&tmp.path().join("one"), "one.txt", "1\n");
+        // Moves main on, so landing "one" has to rebase it
+        commit_file(&repo, "main.txt", "main\n");
+        let tip = head_of(&tmp.path().join("one"));
+        fs::write(tmp.path().join("two").join("dirty.txt"), "x").unwrap();
+
+        let outcome = land_branch(
+            &repo,
+            "main",
+            &entry(&tmp, "one"),
+            Some("echo boom; exit 1"),
+            |_| {},
+        );
+        assert_eq!(outcome, MergeOutcome::TestsFailed("boom".to_string()));
+        assert!(!repo.join("one.txt").exists());
+        // The rebase was undone, leaving the branch tip where it was
+        assert_eq!(head_of(&tmp.path().join("one")), tip);
+        assert!(!tmp.path().join("one").join("main.txt").exists());
+
+        let outcome = land_branch(&repo, "main", &entry(&tmp, "two"), None, |_| {});
+        assert!(matches!(outcome, MergeOutcome::Skipped(_)));
+    }
+}
@@ -0,0 +1,134 @@
+//! Detect worktree branches that have landed upstream
+//!
+//! A session's branch counts as merged when its tip was committed after the
+//! session started and is contained in the base branch (locally or on
+//! `origin`), or when `gh` reports the branch's pull request as merged. The
+//! second check also catches squash and rebase merges, which leave the
+//! branch's own commits out of the base.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+fn git(dir: &Path, args: &[&str]) -> Option<Output> {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()
+}
+
+fn succeeds(dir: &Path, args: &[&str]) -> bool {
+    git(dir, args).is_some_and(|o| o.status.success())
+}
+
+/// How `branch` was merged, e.g. "merged into main" or "PR #12 merged", or
+/// None if it has not been. `since` is when the session started; a branch
+/// with no commits after it has nothing to merge and is never reported.
+pub fn branch_merged(repo_path: &Path, branch: &str, since: DateTime<Utc>) -> Option<String> {
+    if let Some(pr) = merged_pull_request(repo_path, branch) {
+        return Some(format!("PR #{} merged", pr));
+    }
+
+    let tip_time = git(repo_path, &["log", "-1", "--format=%ct", branch])
+        .filter(|o| o.status.success())
+        .and_then(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .trim()
+                .parse::<i64>()
+                .ok()
+        })?;
+    if tip_time <= since.timestamp() {
+        return None;
+    }
+
+    let base = super::diff::get_default_branch(repo_path).ok()?;
+    if base == branch {
+        return None;
+    }
+    let remote_base = format!("refs/remotes/origin/{}", base);
+    let candidates = [base.as_str(), remote_base.as_str()];
+    candidates
+        .iter()
+        .filter(|base_ref| succeeds(repo_path, &["rev-parse", "--verify", "--quiet", base_ref]))
+        .any(|base_ref| {
+            succeeds(
+                repo_path,
+                &["merge-base", "--is-ancestor", branch, base_ref],
+            )
+        })
+        .then(|| format!("merged into {}", base))
+}
+
+#[derive(Deserialize)]
+struct PullRequest {
+    number: u64,
+    state: String,
+}
+
+/// Number of the merged pull request for `branch`, if `gh` is installed,
+/// authenticated, and knows of one.
+fn merged_pull_request(repo_path: &Path, branch: &str) -> Option<u64> {
+    let output = Command::new("gh")
+        .args(["pr", "view", branch, "--json", "number,state"])
+        .current_dir(repo_path)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let pr: PullRequest = serde_json::from_slice(&output.stdout).ok()?;
+    (pr.state == "MERGED").then_some(pr.number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        let out = git(dir, args).unwrap();
+        assert!(
+            out.status.success(),
+            "git {:?}: {}",
+            args,
+            String::from_utf8_lossy(&out.stderr)
+        );
+    }
+
+    fn commit_file(dir: &Path, name: &str) {
+        fs::write(dir.join(name), name).unwrap();
+        run_git(dir, &["add", name]);
+        run_git(dir, &["commit", "-q", "-m", name]);
+    }
+
+    #[test]
+    fn test_branch_merged_into_base() {
+        let tmp = TempDir::new().unwrap();
+        let repo = tmp.path();
+        run_git(repo, &["init", "-q", "-b", "main"]);
+        run_git(repo, &["config", "user.name", "Test"]);
+        run_git(repo, &["config", "user.email", "test@example.com"]);
+        commit_file(repo, "base.txt");
+        run_git(repo, &["branch", "feature"]);
+
+        let before = Utc::now() - Duration::hours(1);
+        // No commits on the branch yet: nothing to merge
+        assert_eq!(branch_merged(repo, "feature", Utc::now()), None);
+
+        run_git(repo, &["checkout", "-q", "feature"]);
+        commit_file(repo, "feature.txt");
+        run_git(repo, &["checkout", "-q", "main"]);
+        assert_eq!(branch_merged(repo, "feature", before), None);
+
+        run_git(repo, &["merge", "-q", "--ff-only", "feature"]);
+        assert_eq!(
+            branch_merged(repo, "feature", before),
+            Some("merged into main".to_string())
+        );
+        assert_eq!(branch_merged(repo, "missing", before), None);
+    }
+}
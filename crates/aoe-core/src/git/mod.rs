@@ -1,11 +1,18 @@
 // Git worktree operations module
 
+use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 
 pub mod cleanup;
+pub mod clone;
+pub mod conflicts;
 pub mod diff;
 pub mod error;
+pub mod issue;
+pub mod merge_queue;
+pub mod merged;
+pub mod pool;
 pub mod template;
 
 use error::{GitError, Result};
@@ -23,6 +30,19 @@ pub(crate) fn open_repo_at(path: &Path) -> std::result::Result<git2::Repository,
     )
 }
 
+/// Files left out of a sparse checkout. libgit2 does not know about sparse
+/// checkouts and reports them as deleted from the working tree.
+pub(crate) fn sparse_excluded_paths(repo: &git2::Repository) -> HashSet<PathBuf> {
+    let Ok(index) = repo.index() else {
+        return HashSet::new();
+    };
+    index
+        .iter()
+        .filter(|e| e.flags_extended & git2::IndexEntryExtendedFlag::SKIP_WORKTREE.bits() != 0)
+        .map(|e| PathBuf::from(String::from_utf8_lossy(&e.path).into_owned()))
+        .collect()
+}
+
 pub struct WorktreeEntry {
     pub path: PathBuf,
     pub branch: Option<String>,
@@ -31,6 +51,9 @@ pub struct WorktreeEntry {
 
 pub struct GitWorktree {
     pub repo_path: PathBuf,
+    /// Sparse-checkout patterns for worktrees created from here; empty
+    /// checks out everything
+    pub sparse_paths: Vec<String>,
 }
 
 impl GitWorktree {
@@ -38,7 +61,20 @@ impl GitWorktree {
         if !Self::is_git_repo(&repo_path) {
             return Err(GitError::NotAGitRepo);
         }
-        Ok(Self { repo_path })
+        Ok(Self {
+            repo_path,
+            sparse_paths: Vec::new(),
+        })
+    }
+
+    /// Check out only `patterns` in the worktrees this creates.
+    pub fn with_sparse_paths(mut self, patterns: Vec<String>) -> Self {
+        self.sparse_paths = patterns
+            .into_iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+        self
     }
 
     pub fn is_git_repo(path: &Path) -> bool {
@@ -185,12 +221,40 @@ impl GitWorktree {
             }
         }
 
+        self.add_worktree(branch, path)
+    }
+
+    /// Create a worktree on a new `branch` starting at `base`, which may be
+    /// a branch, remote branch, tag or commit.
+    pub fn create_worktree_from(&self, branch: &str, path: &Path, base: &str) -> Result<()> {
+        if path.exists() {
+            return Err(GitError::WorktreeAlreadyExists(path.to_path_buf()));
+        }
+        self.prune_worktrees()?;
+
+        let repo = open_repo_at(&self.repo_path)?;
+        let commit = repo
+            .revparse_single(base)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|_| GitError::BranchNotFound(base.to_string()))?;
+        repo.branch(branch, &commit, false)?;
+
+        self.add_worktree(branch, path)
+    }
+
+    /// Check out an existing `branch` into a new worktree at `path`.
+    fn add_worktree(&self, branch: &str, path: &Path) -> Result<()> {
         let path_str = path
             .to_str()
             .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid path"))?;
 
+        let mut args = vec!["worktree", "add"];
+        if !self.sparse_paths.is_empty() {
+            args.push("--no-checkout");
+        }
+        args.extend([path_str, branch]);
         let output = std::process::Command::new("git")
-            .args(["worktree", "add", path_str, branch])
+            .args(&args)
             .current_dir(&self.repo_path)
             .output()?;
 
@@ -199,6 +263,15 @@ impl GitWorktree {
             return Err(GitError::WorktreeCommandFailed(stderr));
         }
 
+        if !self.sparse_paths.is_empty() {
+            let checkout = Self::set_sparse_checkout(path, &self.sparse_paths)
+                .and_then(|_| Self::run_git(path, &["reset", "--quiet", "--hard"]));
+            if let Err(e) = checkout {
+                let _ = self.remove_worktree(path, true);
+                return Err(e);
+            }
+        }
+
         // Convert the .git file from absolute to relative path.
         // Git always writes absolute paths, but relative paths work better when
         // the repo is mounted at different locations (e.g., in Docker containers).
@@ -207,6 +280,41 @@ impl GitWorktree {
         Ok(())
     }
 
+    fn run_git(path: &Path, args: &[&str]) -> Result<()> {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(GitError::WorktreeCommandFailed(stderr));
+        }
+        Ok(())
+    }
+
+    /// Limit the checkout at `path` to `patterns`, or check out everything
+    /// again if there are none. Plain directories use cone mode, which git
+    /// matches fastest; globs and `!` negations need full patterns.
+    pub fn set_sparse_checkout(path: &Path, patterns: &[String]) -> Result<()> {
+        if patterns.is_empty() {
+            let output = std::process::Command::new("git")
+                .args(["config", "--get", "core.sparseCheckout"])
+                .current_dir(path)
+                .output()?;
+            if String::from_utf8_lossy(&output.stdout).trim() == "true" {
+                Self::run_git(path, &["sparse-checkout", "disable"])?;
+            }
+            return Ok(());
+        }
+        let cone = !patterns
+            .iter()
+            .any(|p| p.starts_with('!') || p.contains(['*', '?', '[']));
+        let mut args = vec!["sparse-checkout", "set"];
+        args.push(if cone { "--cone" } else { "--no-cone" });
+        args.extend(patterns.iter().map(String::as_str));
+        Self::run_git(path, &args)
+    }
+
     /// Prune stale worktree entries whose directories no longer exist on disk.
     pub fn prune_worktrees(&self) -> Result<()> {
         let output = std::process::Command::new("git")
@@ -231,7 +339,7 @@ impl GitWorktree {
     /// `gitdir: ../.bare/worktrees/name`
     ///
     /// Relative paths work when the repo is mounted at different locations.
-    fn convert_git_file_to_relative(worktree_path: &Path) -> Result<()> {
+    pub(crate) fn convert_git_file_to_relative(worktree_path: &Path) -> Result<()> {
         let git_file = worktree_path.join(".git");
         if !git_file.exists() || !git_file.is_file() {
             return Ok(()); // Not a worktree or already a directory
@@ -396,6 +504,58 @@ impl GitWorktree {
         resolve_template(template, &vars)
     }
 
+    /// Whether the working tree at `path` has modified, staged, or
+    /// untracked files. Ignored files don't count.
+    pub fn has_uncommitted_changes(path: &Path) -> Result<bool> {
+        let repo = open_repo_at(path)?;
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true).include_ignored(false);
+        let sparse = sparse_excluded_paths(&repo);
+        let dirty = repo.statuses(Some(&mut opts))?.iter().any(|entry| {
+            entry.status() != git2::Status::WT_DELETED
+                || !entry.path().is_some_and(|p| sparse.contains(Path::new(p)))
+        });
+        Ok(dirty)
+    }
+
+    /// Commit currently checked out at `path`.
+    pub fn head_commit(path: &Path) -> Result<String> {
+        let repo = open_repo_at(path)?;
+        let commit = repo.head()?.peel_to_commit()?;
+        Ok(commit.id().to_string())
+    }
+
+    /// Commit the worktree at `path` forked from: the merge base of its
+    /// HEAD and `base_branch`.
+    pub fn fork_point(path: &Path, base_branch: &str) -> Result<String> {
+        let repo = open_repo_at(path)?;
+        let head = repo.head()?.peel_to_commit()?;
+        let base = repo
+            .revparse_single(base_branch)
+            .and_then(|obj| obj.peel_to_commit())
+            .map_err(|_| GitError::BranchNotFound(base_branch.to_string()))?;
+        Ok(repo.merge_base(head.id(), base.id())?.to_string())
+    }
+
+    /// Move the branch checked out at `path` back to `commit`, discarding
+    /// later commits, local changes, and untracked files. Ignored files
+    /// are kept.
+    pub fn reset_to(path: &Path, commit: &str) -> Result<()> {
+        let repo = open_repo_at(path)?;
+        let target = repo.revparse_single(commit)?.peel_to_commit()?;
+        repo.reset(target.as_object(), git2::ResetType::Hard, None)?;
+
+        let output = std::process::Command::new("git")
+            .args(["clean", "-fd"])
+            .current_dir(path)
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(GitError::WorktreeCommandFailed(stderr));
+        }
+        Ok(())
+    }
+
     pub fn get_current_branch(path: &Path) -> Result<String> {
         let repo = open_repo_at(path)?;
         let head = repo.head()?;
@@ -443,6 +603,38 @@ mod tests {
         assert!(!GitWorktree::is_git_repo(dir.path()));
     }
 
+    #[test]
+    fn test_has_uncommitted_changes() {
+        let (_dir, repo) = setup_test_repo();
+        let repo_path = repo.path().parent().unwrap();
+        assert!(!GitWorktree::has_uncommitted_changes(repo_path).unwrap());
+
+        std::fs::write(repo_path.join("new.txt"), "x").unwrap();
+        assert!(GitWorktree::has_uncommitted_changes(repo_path).unwrap());
+    }
+
+    #[test]
+    fn test_reset_to_discards_commits_and_changes() {
+        let (_dir, repo) = setup_test_repo();
+        let repo_path = repo.path().parent().unwrap();
+        let base = GitWorktree::head_commit(repo_path).unwrap();
+
+        std::fs::write(repo_path.join("work.txt"), "x").unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("work.txt")).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Work", &tree, &[&parent])
+            .unwrap();
+        std::fs::write(repo_path.join("scratch.txt"), "y").unwrap();
+
+        GitWorktree::reset_to(repo_path, &base).unwrap();
+        assert_eq!(GitWorktree::head_commit(repo_path).unwrap(), base);
+        assert!(!repo_path.join("work.txt").exists());
+        assert!(!repo_path.join("scratch.txt").exists());
+    }
+
     #[test]
     fn test_find_main_repo_returns_repo_root() {
         let (_dir, repo) = setup_test_repo();
@@ -958,6 +1150,78 @@ mod tests {
             .is_ok());
     }
 
+    #[test]
+    fn test_create_worktree_from_tag() {
+        let (dir, repo) = setup_test_repo();
+        let repo_path = repo.path().parent().unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let first = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.tag_lightweight("v1.0", first.as_object(), false)
+            .unwrap();
+        let tree = first.tree().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Second", &tree, &[&first])
+            .unwrap();
+
+        let git_wt = GitWorktree::new(repo_path.to_path_buf()).unwrap();
+        let wt_path = dir.path().join("from-tag-wt");
+        git_wt
+            .create_worktree_from("from-tag", &wt_path, "v1.0")
+            .unwrap();
+        assert_eq!(
+            GitWorktree::head_commit(&wt_path).unwrap(),
+            first.id().to_string()
+        );
+
+        let err = git_wt
+            .create_worktree_from("bad", &dir.path().join("bad-wt"), "no-such-ref")
+            .unwrap_err();
+        assert!(matches!(err, GitError::BranchNotFound(_)));
+    }
+
+    #[test]
+    fn test_create_sparse_worktree() {
+        let (dir, repo) = setup_test_repo();
+        let repo_path = repo.path().parent().unwrap();
+        for file in ["services/api/main.rs", "services/web/app.js", "README.md"] {
+            let path = repo_path.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "x").unwrap();
+        }
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Files", &tree, &[&parent])
+            .unwrap();
+
+        let git_wt = GitWorktree::new(repo_path.to_path_buf())
+            .unwrap()
+            .with_sparse_paths(vec!["services/api".to_string(), " ".to_string()]);
+        assert_eq!(git_wt.sparse_paths, ["services/api"]);
+        let wt_path = dir.path().join("sparse-wt");
+        git_wt.create_worktree("sparse", &wt_path, true).unwrap();
+        assert!(wt_path.join("services/api/main.rs").exists());
+        assert!(!wt_path.join("services/web").exists());
+        // Cone mode keeps top-level files
+        assert!(wt_path.join("README.md").exists());
+        assert!(!GitWorktree::has_uncommitted_changes(&wt_path).unwrap());
+        assert!(diff::compute_changed_files(&wt_path, "sparse")
+            .unwrap()
+            .is_empty());
+
+        // A glob switches to full patterns
+        GitWorktree::set_sparse_checkout(&wt_path, &["*.js".to_string()]).unwrap();
+        assert!(wt_path.join("services/web/app.js").exists());
+        assert!(!wt_path.join("README.md").exists());
+
+        GitWorktree::set_sparse_checkout(&wt_path, &[]).unwrap();
+        assert!(wt_path.join("README.md").exists());
+        assert!(wt_path.join("services/api/main.rs").exists());
+    }
+
     #[test]
     fn test_regular_repo_full_builder_flow() {
         let (_dir, repo) = setup_test_repo();
@@ -0,0 +1,277 @@
+//! Pool of spare worktrees
+//!
+//! Creating a worktree checks out the whole tree, which in a large monorepo
+//! takes minutes. With `worktree.pool_size` set, aoe keeps that many spare
+//! worktrees per repository, detached at the repository's HEAD and locked
+//! with the reason "aoe pool" so `git worktree prune` leaves them alone. A
+//! new session claims one by unlocking it, moving it to the session's
+//! worktree path and checking out its branch there, which only rewrites the
+//! files that differ, with the session's sparse-checkout patterns applied
+//! first. `aoe worktree pool fill`, started in the background after each
+//! worktree session is created, tops the pool back up.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use super::error::{GitError, Result};
+use super::GitWorktree;
+use crate::session::config::WorktreeConfig;
+
+pub const LOCK_REASON: &str = "aoe pool";
+
+/// Spare worktrees are named `aoe-pool-<id>` in place of a branch
+const NAME_PREFIX: &str = "aoe-pool-";
+
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git").arg("-C").arg(dir).args(args).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(GitError::WorktreeCommandFailed(stderr));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn path_str(path: &Path) -> Result<&str> {
+    path.to_str().ok_or_else(|| {
+        GitError::IoError(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Invalid path",
+        ))
+    })
+}
+
+/// The path template new worktrees of `repo` use, as in session creation.
+pub fn template<'a>(repo: &GitWorktree, config: &'a WorktreeConfig) -> &'a str {
+    if GitWorktree::is_bare_repo(&repo.repo_path) {
+        &config.bare_repo_path_template
+    } else {
+        &config.path_template
+    }
+}
+
+/// Paths of the spare worktrees of `repo`.
+pub fn list(repo: &GitWorktree) -> Result<Vec<PathBuf>> {
+    let output = git(&repo.repo_path, &["worktree", "list", "--porcelain"])?;
+    let mut pooled = Vec::new();
+    for entry in output.split("\n\n") {
+        let mut path = None;
+        let mut locked = false;
+        for line in entry.lines() {
+            if let Some(p) = line.strip_prefix("worktree ") {
+                path = Some(PathBuf::from(p));
+            } else if line == format!("locked {}", LOCK_REASON) {
+                locked = true;
+            }
+        }
+        if let (Some(path), true) = (path, locked) {
+            pooled.push(path.canonicalize().unwrap_or(path));
+        }
+    }
+    Ok(pooled)
+}
+
+/// Create one spare worktree, detached at HEAD and limited to the
+/// repository's sparse-checkout patterns.
+pub fn add(repo: &GitWorktree, template: &str) -> Result<PathBuf> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let id = &id[..8];
+    let path = repo.compute_path(&format!("{}{}", NAME_PREFIX, id), template, id)?;
+    let mut args = vec![
+        "worktree",
+        "add",
+        "--quiet",
+        "--detach",
+        "--lock",
+        "--reason",
+        LOCK_REASON,
+    ];
+    if !repo.sparse_paths.is_empty() {
+        args.push("--no-checkout");
+    }
+    args.extend([path_str(&path)?, "HEAD"]);
+    git(&repo.repo_path, &args)?;
+    GitWorktree::convert_git_file_to_relative(&path)?;
+    if !repo.sparse_paths.is_empty() {
+        let checkout = GitWorktree::set_sparse_checkout(&path, &repo.sparse_paths)
+            .and_then(|_| git(&path, &["reset", "--quiet", "--hard"]).map(|_| ()));
+        if let Err(e) = checkout {
+            let _ = discard(repo, &path);
+            return Err(e);
+        }
+    }
+    Ok(path)
+}
+
+/// Remove a spare worktree, or one that was claimed but not set up.
+fn discard(repo: &GitWorktree, path: &Path) -> Result<()> {
+    let path = path_str(path)?;
+    let _ = git(&repo.repo_path, &["worktree", "unlock", path]);
+    git(&repo.repo_path, &["worktree", "remove", "--force", path])?;
+    Ok(())
+}
+
+/// Bring the pool of `repo` to `size` spare worktrees, all at the current
+/// HEAD. Returns how many were added.
+pub fn fill(repo: &GitWorktree, template: &str, size: usize) -> Result<usize> {
+    let head = git(&repo.repo_path, &["rev-parse", "HEAD"])?;
+    let pooled = list(repo)?;
+    for path in pooled.iter().skip(size) {
+        discard(repo, path)?;
+    }
+    for path in pooled.iter().take(size) {
+        if git(path, &["rev-parse", "HEAD"]).ok().as_deref() != Some(head.as_str()) {
+            git(path, &["checkout", "--quiet", "--detach", &head])?;
+        }
+    }
+
+    let mut added = 0;
+    while pooled.len() + added < size {
+        add(repo, template)?;
+        added += 1;
+    }
+    Ok(added)
+}
+
+/// Remove every spare worktree of `repo`. Returns how many there were.
+pub fn clear(repo: &GitWorktree) -> Result<usize> {
+    let pooled = list(repo)?;
+    for path in &pooled {
+        discard(repo, path)?;
+    }
+    Ok(pooled.len())
+}
+
+/// Take a spare worktree for a session, moving it to `path` and checking
+/// out `branch` there. `start` creates the branch from that commit; without
+/// it `branch` must already exist, locally or on a remote. Returns false,
+/// with nothing changed, when the pool is empty.
+pub fn claim(repo: &GitWorktree, path: &Path, branch: &str, start: Option<&str>) -> Result<bool> {
+    if path.exists() {
+        return Err(GitError::WorktreeAlreadyExists(path.to_path_buf()));
+    }
+    // Unlocking succeeds for only one of several sessions racing for the
+    // same worktree
+    let Some(spare) = list(repo)?.into_iter().find(|p| {
+        path_str(p)
+            .and_then(|p| git(&repo.repo_path, &["worktree", "unlock", p]))
+            .is_ok()
+    }) else {
+        return Ok(false);
+    };
+
+    let setup = || -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        git(
+            &repo.repo_path,
+            &["worktree", "move", path_str(&spare)?, path_str(path)?],
+        )?;
+        GitWorktree::convert_git_file_to_relative(path)?;
+        git(path, &["reset", "--quiet", "--hard"])?;
+        git(path, &["clean", "-fdq"])?;
+        GitWorktree::set_sparse_checkout(path, &repo.sparse_paths)?;
+        match start {
+            Some(start) => git(path, &["checkout", "--quiet", "-b", branch, start])?,
+            None => git(path, &["checkout", "--quiet", branch])?,
+        };
+        Ok(())
+    };
+    if let Err(e) = setup() {
+        let left = if path.exists() { path } else { &spare };
+        if let Err(e) = discard(repo, left) {
+            tracing::warn!("Failed to remove claimed pool worktree: {}", e);
+        }
+        return Err(e);
+    }
+    Ok(true)
+}
+
+/// Top up the pool of the repository at `repo_path` in the background.
+pub fn start_fill(repo_path: &Path, profile: &str) {
+    let Ok(aoe) = std::env::current_exe() else {
+        return;
+    };
+    let mut cmd = Command::new(aoe);
+    if !profile.is_empty() {
+        cmd.args(["--profile", profile]);
+    }
+    if let Err(e) = cmd
+        .args(["worktree", "pool", "fill"])
+        .arg(repo_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        tracing::warn!("Failed to start worktree pool fill: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_repo() -> (TempDir, GitWorktree) {
+        let dir = TempDir::new().unwrap();
+        let repo_path = dir.path().join("repo");
+        fs::create_dir(&repo_path).unwrap();
+        let run = |args: &[&str]| git(&repo_path, args).unwrap();
+        run(&["init", "-q"]);
+        fs::write(repo_path.join("a.txt"), "one").unwrap();
+        run(&["add", "a.txt"]);
+        run(&[
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-qm",
+            "init",
+        ]);
+        let repo = GitWorktree::new(repo_path.canonicalize().unwrap()).unwrap();
+        (dir, repo)
+    }
+
+    #[test]
+    fn test_fill_claim_and_clear() {
+        let (dir, repo) = setup_repo();
+        let template = "../worktrees/{branch}";
+        assert!(!claim(&repo, &dir.path().join("worktrees/x"), "x", Some("HEAD")).unwrap());
+
+        assert_eq!(fill(&repo, template, 2).unwrap(), 2);
+        assert_eq!(fill(&repo, template, 2).unwrap(), 0);
+        let pooled = list(&repo).unwrap();
+        assert_eq!(pooled.len(), 2);
+        assert!(pooled[0]
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with(NAME_PREFIX));
+
+        // A leftover change in a spare worktree does not reach the session
+        fs::write(pooled[0].join("a.txt"), "dirty").unwrap();
+        let target = dir.path().join("worktrees/feature");
+        assert!(claim(&repo, &target, "feature", Some("HEAD")).unwrap());
+        assert_eq!(list(&repo).unwrap().len(), 1);
+        assert_eq!(GitWorktree::get_current_branch(&target).unwrap(), "feature");
+        assert_eq!(fs::read_to_string(target.join("a.txt")).unwrap(), "one");
+        assert!(fs::read_to_string(target.join(".git"))
+            .unwrap()
+            .starts_with("gitdir: .."));
+
+        // A failed checkout gives the worktree up rather than the pool
+        let err = claim(&repo, &dir.path().join("worktrees/y"), "missing", None);
+        assert!(err.is_err());
+        assert!(list(&repo).unwrap().is_empty());
+        assert!(!dir.path().join("worktrees/y").exists());
+
+        fill(&repo, template, 3).unwrap();
+        assert_eq!(fill(&repo, template, 1).unwrap(), 0);
+        assert_eq!(list(&repo).unwrap().len(), 1);
+        assert_eq!(clear(&repo).unwrap(), 1);
+        assert!(list(&repo).unwrap().is_empty());
+    }
+}
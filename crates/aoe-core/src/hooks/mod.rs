@@ -6,6 +6,7 @@
 //!
 //! Hook events are agent-specific and defined in `AgentHookConfig::events`.
 
+pub mod shim;
 mod status_file;
 
 use std::path::Path;
@@ -0,0 +1,245 @@
+//! Out-of-band health pings for agents launched through `aoe shim`.
+//!
+//! With `session.agent_shim` enabled the agent runs as a child of the shim,
+//! which keeps a heartbeat for it (alive, and busy while its process tree is
+//! using CPU) and answers pings on `shim.sock` in the session's hook status
+//! directory. The protocol is one line each way: the client writes `ping`,
+//! the shim replies with the heartbeat as JSON. When the agent exits the shim
+//! records its exit code in `exit` next to the socket and exits with it.
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+use tokio::net::UnixListener;
+
+use super::hook_status_dir;
+use crate::process::ProcessTable;
+
+const SOCKET_FILE: &str = "shim.sock";
+const EXIT_FILE: &str = "exit";
+
+/// How often the shim refreshes its heartbeat
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long after its last CPU use the agent still counts as busy. Outside
+/// Linux `ps` may report whole CPU seconds, so a short window would flicker.
+const BUSY_WINDOW: Duration = Duration::from_secs(5);
+
+/// How long a ping waits for the shim to answer
+const PING_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// The shim's latest view of its agent
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Heartbeat {
+    pub pid: u32,
+    /// Whether the agent's process tree used CPU within the last few seconds
+    pub busy: bool,
+    /// CPU seconds the agent's process tree has used
+    pub cpu_secs: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShimHealth {
+    Alive(Heartbeat),
+    /// The agent exited with this code (128 + signal when killed)
+    Exited(i32),
+}
+
+fn socket_path(instance_id: &str) -> PathBuf {
+    hook_status_dir(instance_id).join(SOCKET_FILE)
+}
+
+fn exit_path(instance_id: &str) -> PathBuf {
+    hook_status_dir(instance_id).join(EXIT_FILE)
+}
+
+/// Wrap `cmd` so it runs under the shim for `instance_id`.
+pub fn wrap(instance_id: &str, cmd: &str) -> Result<String> {
+    let aoe = std::env::current_exe().context("Failed to locate the aoe binary for the shim")?;
+    Ok(format!(
+        "{} shim --id {} -- {}",
+        crate::session::shell_single_quote(&aoe.to_string_lossy()),
+        instance_id,
+        crate::session::shell_single_quote(cmd)
+    ))
+}
+
+/// Forget the exit code recorded for `instance_id`'s previous run.
+pub fn clear_exit(instance_id: &str) {
+    let _ = std::fs::remove_file(exit_path(instance_id));
+}
+
+/// Ask the shim of `instance_id` how its agent is doing. `None` when the
+/// session was not launched through a shim, or the shim itself was killed.
+pub fn ping(instance_id: &str) -> Option<ShimHealth> {
+    match ping_socket(instance_id) {
+        Ok(heartbeat) => Some(ShimHealth::Alive(heartbeat)),
+        Err(_) => std::fs::read_to_string(exit_path(instance_id))
+            .ok()
+            .and_then(|code| code.trim().parse().ok())
+            .map(ShimHealth::Exited),
+    }
+}
+
+fn ping_socket(instance_id: &str) -> Result<Heartbeat> {
+    let mut stream = UnixStream::connect(socket_path(instance_id))?;
+    stream.set_read_timeout(Some(PING_TIMEOUT))?;
+    stream.set_write_timeout(Some(PING_TIMEOUT))?;
+    stream.write_all(b"ping\n")?;
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    Ok(serde_json::from_str(&line)?)
+}
+
+/// Tracks when the agent's process tree last used CPU
+struct Activity {
+    cpu_secs: f64,
+    last_active: Instant,
+}
+
+impl Activity {
+    fn heartbeat(&self, pid: u32) -> Heartbeat {
+        Heartbeat {
+            pid,
+            busy: self.last_active.elapsed() < BUSY_WINDOW,
+            cpu_secs: self.cpu_secs,
+        }
+    }
+
+    /// Compares with the last sample rather than the most seen: the tree's
+    /// total drops whenever a busy child process exits.
+    fn record(&mut self, cpu_secs: f64) {
+        if cpu_secs > self.cpu_secs {
+            self.last_active = Instant::now();
+        }
+        self.cpu_secs = cpu_secs;
+    }
+}
+
+/// Run `command` through `sh -c` for `instance_id`, serving pings until it
+/// exits. Returns the agent's exit code.
+pub async fn run(instance_id: &str, command: &str) -> Result<i32> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let dir = hook_status_dir(instance_id);
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let socket = socket_path(instance_id);
+    let _ = std::fs::remove_file(&socket);
+    clear_exit(instance_id);
+    let listener = UnixListener::bind(&socket)
+        .with_context(|| format!("Failed to listen on {}", socket.display()))?;
+
+    // Ctrl-C and Ctrl-\ reach the whole foreground process group; they are
+    // for the agent. A handler (unlike ignoring) is reset for the child.
+    let _interrupt = signal(SignalKind::interrupt())?;
+    let _quit = signal(SignalKind::quit())?;
+
+    let mut child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .spawn()
+        .context("Failed to start the agent")?;
+    let pid = child.id().unwrap_or_default();
+    let activity = Arc::new(Mutex::new(Activity {
+        cpu_secs: 0.0,
+        last_active: Instant::now(),
+    }));
+
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    let status = loop {
+        tokio::select! {
+            status = child.wait() => break status?,
+            Ok((stream, _)) = listener.accept() => {
+                let activity = activity.clone();
+                tokio::spawn(async move {
+                    let (read, mut write) = stream.into_split();
+                    let mut lines = tokio::io::BufReader::new(read).lines();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        if line.trim() != "ping" {
+                            continue;
+                        }
+                        let beat = activity.lock().unwrap().heartbeat(pid);
+                        let Ok(mut reply) = serde_json::to_string(&beat) else {
+                            break;
+                        };
+                        reply.push('\n');
+                        if write.write_all(reply.as_bytes()).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            _ = heartbeat.tick() => {
+                let table = tokio::task::spawn_blocking(ProcessTable::snapshot).await?;
+                if let Some(table) = table {
+                    activity.lock().unwrap().record(table.tree_cpu_time(pid));
+                }
+            }
+        }
+    };
+
+    let code = exit_code(status);
+    std::fs::write(exit_path(instance_id), code.to_string())?;
+    let _ = std::fs::remove_file(&socket);
+    Ok(code)
+}
+
+fn exit_code(status: std::process::ExitStatus) -> i32 {
+    use std::os::unix::process::ExitStatusExt;
+    status
+        .code()
+        .or_else(|| status.signal().map(|signal| 128 + signal))
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shim_answers_pings_then_records_exit_code() {
+        let id = "test_shim_ping";
+        let _ = std::fs::remove_dir_all(hook_status_dir(id));
+        assert_eq!(ping(id), None);
+
+        let shim = tokio::spawn(async move { run(id, "sleep 1; exit 3").await });
+        let mut health = None;
+        for _ in 0..50 {
+            health = tokio::task::spawn_blocking(move || ping(id)).await.unwrap();
+            if health.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        match health {
+            Some(ShimHealth::Alive(beat)) => assert!(beat.pid > 0),
+            other => panic!("expected a heartbeat, got {:?}", other),
+        }
+
+        assert_eq!(shim.await.unwrap().unwrap(), 3);
+        assert_eq!(ping(id), Some(ShimHealth::Exited(3)));
+        crate::hooks::cleanup_hook_status_dir(id);
+    }
+
+    #[test]
+    fn test_busy_until_cpu_use_stops() {
+        let mut activity = Activity {
+            cpu_secs: 2.0,
+            last_active: Instant::now() - BUSY_WINDOW,
+        };
+        assert!(!activity.heartbeat(1).busy);
+        activity.record(2.0);
+        assert!(!activity.heartbeat(1).busy);
+        // A finished child takes its CPU time with it
+        activity.record(1.0);
+        assert!(!activity.heartbeat(1).busy);
+        activity.record(2.0);
+        assert!(activity.heartbeat(1).busy);
+    }
+}
@@ -0,0 +1,65 @@
+//! Session management for Agent of Empires
+//!
+//! `aoe-core` is everything `aoe` does to manage agent sessions, without
+//! its CLI or TUI, so other Rust tools can create, inspect, and drive the
+//! same sessions `aoe` shows. It reads and writes the same profiles and
+//! config, so sessions made through it appear in `aoe` and the other way
+//! round.
+//!
+//! The main pieces:
+//!
+//! - **Store**: [`Storage`] loads and saves a profile's sessions
+//!   ([`Instance`]) and groups ([`GroupTree`]). Where they are kept is up
+//!   to a [`SessionStore`]; [`JsonFileStore`] is the one `aoe` uses.
+//! - **Multiplexer**: each session's agent runs in a terminal multiplexer
+//!   session, reached through [`Multiplexer`]. [`tmux::Session`] is the
+//!   tmux implementation, and [`Instance::tmux_session`] gives the one a
+//!   session runs in.
+//! - **Agent registry**: [`agents::AGENTS`] lists the supported agents
+//!   ([`AgentDef`]): how to launch them, detect them, and tell their
+//!   status. [`get_agent`] looks one up by name.
+//!
+//! ```no_run
+//! use aoe_core::{Instance, Multiplexer, Storage};
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let storage = Storage::new("default")?;
+//! let (mut instances, groups) = storage.load_with_groups()?;
+//!
+//! let mut inst = Instance::new("fix login", "/home/me/src/app");
+//! inst.tool = "claude".to_string();
+//! inst.start()?;
+//! instances.push(inst);
+//! storage.save_with_groups(&instances, &aoe_core::GroupTree::new_with_groups(&instances, &groups))?;
+//!
+//! for inst in &instances {
+//!     let session = inst.tmux_session()?;
+//!     if session.exists() {
+//!         println!("{}: {}", inst.title, session.capture(5)?);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The functions here run `tmux`, `git`, and the container runtime as
+//! needed, so those have to be installed for the parts that use them.
+
+pub mod agents;
+pub mod containers;
+pub mod git;
+pub mod hooks;
+pub mod migrations;
+pub mod multiplexer;
+pub mod notifications;
+pub mod process;
+pub mod session;
+pub mod sound;
+pub mod tmux;
+pub mod tray;
+
+pub use agents::{get_agent, AgentDef};
+pub use multiplexer::Multiplexer;
+pub use session::{
+    Config, Group, GroupTree, Instance, JsonFileStore, SessionStore, Status, Storage,
+};
@@ -0,0 +1,33 @@
+//! The terminal multiplexer agent sessions run in
+//!
+//! Every agent runs in a session of a terminal multiplexer, which keeps it
+//! alive while nothing is attached and lets aoe read its screen and type
+//! into it. aoe uses tmux (`tmux::Session`); `Multiplexer` is the part of it
+//! that managing one agent's session needs, for code that embeds aoe-core
+//! and wants to work with sessions without going through tmux directly.
+
+use anyhow::Result;
+
+/// One agent's multiplexer session
+pub trait Multiplexer {
+    /// The session's name in the multiplexer
+    fn name(&self) -> &str;
+
+    fn exists(&self) -> bool;
+
+    /// Start the session in `working_dir`, running `command` or else a
+    /// shell. Does nothing if it already exists.
+    fn create(&self, working_dir: &str, command: Option<&str>) -> Result<()>;
+
+    /// End the session and the processes running in it
+    fn kill(&self) -> Result<()>;
+
+    /// The last `lines` lines of the session's screen, with ANSI escapes
+    fn capture(&self, lines: usize) -> Result<String>;
+
+    /// Type `text` into the session, followed by Enter
+    fn send_keys(&self, text: &str) -> Result<()>;
+
+    /// Attach the current terminal to the session until it detaches
+    fn attach(&self) -> Result<()>;
+}
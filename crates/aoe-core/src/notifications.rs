@@ -0,0 +1,498 @@
+//! Notifications routed to channels by configurable rules
+//!
+//! Each rule is a string of conditions and the channels to use when they
+//! all hold, for example:
+//!
+//! ```toml
+//! [notifications]
+//! webhook_url = "https://hooks.example.com/aoe"
+//! rules = [
+//!     "error tag:prod -> webhook",
+//!     "hours:09:00-18:00 days:mon-fri -> desktop",
+//!     "* -> none",
+//! ]
+//! ```
+//!
+//! The first matching rule decides where an event goes; events no rule
+//! matches are not sent anywhere.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use chrono::{Datelike, Local, NaiveDateTime, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::session::digest::DigestDelivery;
+use crate::session::Status;
+
+/// Session events that can be notified
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Running,
+    Waiting,
+    Idle,
+    /// The session entered the error state, its health check started
+    /// failing, or its agent needs to log in or hit a usage limit
+    Error,
+}
+
+impl Event {
+    const ALL: [Event; 4] = [Event::Running, Event::Waiting, Event::Idle, Event::Error];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Event::Running => "running",
+            Event::Waiting => "waiting",
+            Event::Idle => "idle",
+            Event::Error => "error",
+        }
+    }
+
+    /// The event for a session entering `status`, if it is one to notify
+    pub fn for_status(status: Status) -> Option<Self> {
+        match status {
+            Status::Running => Some(Event::Running),
+            Status::Waiting => Some(Event::Waiting),
+            Status::Idle => Some(Event::Idle),
+            Status::Error => Some(Event::Error),
+            Status::Starting | Status::Stopped | Status::Deleting | Status::Unknown => None,
+        }
+    }
+}
+
+/// Where a notification is delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    /// Desktop notification (notify-send on Linux, osascript on macOS)
+    Desktop,
+    /// JSON POST to `notifications.webhook_url`
+    Webhook,
+    /// Terminal bell
+    Bell,
+    /// Drop the event; ends matching so later rules don't catch it
+    None,
+}
+
+impl Channel {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "desktop" => Some(Channel::Desktop),
+            "webhook" => Some(Channel::Webhook),
+            "bell" => Some(Channel::Bell),
+            "none" => Some(Channel::None),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    /// Routing rules, checked in order (see the module docs for the syntax)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<String>,
+
+    /// URL the `webhook` channel posts to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+
+    /// Show a tray icon counting the sessions that need attention while the
+    /// TUI runs (see `crate::tray`)
+    #[serde(default)]
+    pub tray: bool,
+
+    /// Command run when the tray icon is clicked; detected from the
+    /// terminal if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tray_focus_command: Option<String>,
+
+    /// Deliver a weekly digest of finished sessions (see
+    /// `crate::session::digest`)
+    #[serde(default)]
+    pub digest: DigestDelivery,
+
+    /// File the `file` digest is written to, `{week}` replaced by the week's
+    /// Monday; `digests/{week}.md` in the app directory if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest_path: Option<String>,
+}
+
+/// Profile override for notification config (all fields optional, None = inherit)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationConfigOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tray: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tray_focus_command: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<DigestDelivery>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest_path: Option<String>,
+}
+
+/// Apply notification config overrides from a profile
+pub fn apply_notification_overrides(
+    target: &mut NotificationConfig,
+    source: &NotificationConfigOverride,
+) {
+    if let Some(ref rules) = source.rules {
+        target.rules = rules.clone();
+    }
+    if source.webhook_url.is_some() {
+        target.webhook_url = source.webhook_url.clone();
+    }
+    if let Some(tray) = source.tray {
+        target.tray = tray;
+    }
+    if source.tray_focus_command.is_some() {
+        target.tray_focus_command = source.tray_focus_command.clone();
+    }
+    if let Some(digest) = source.digest {
+        target.digest = digest;
+    }
+    if source.digest_path.is_some() {
+        target.digest_path = source.digest_path.clone();
+    }
+}
+
+/// A parsed routing rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    /// Events the rule applies to; empty for all
+    events: Vec<Event>,
+    /// The session must have one of these tags; empty for any session
+    tags: Vec<String>,
+    /// Local time window; the end may be past midnight, e.g. 22:00-06:00
+    hours: Option<(NaiveTime, NaiveTime)>,
+    /// Days of the week the rule applies on; empty for every day
+    days: Vec<Weekday>,
+    channels: Vec<Channel>,
+}
+
+impl Rule {
+    /// Parse `"<conditions> -> <channels>"`. Conditions are event names
+    /// (or `*` for any), `tag:NAME`, `hours:HH:MM-HH:MM`, and
+    /// `days:mon-fri` or `days:sat,sun`. Channels are comma-separated.
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let (conditions, channels) = rule
+            .split_once("->")
+            .ok_or_else(|| format!("'{}' is missing '->' before its channels", rule))?;
+
+        let channels = channels
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|c| !c.is_empty())
+            .map(|c| {
+                Channel::parse(&c.to_lowercase()).ok_or_else(|| {
+                    format!(
+                        "unknown channel '{}' (use desktop, webhook, bell, or none)",
+                        c
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if channels.is_empty() {
+            return Err(format!("'{}' has no channels after '->'", rule));
+        }
+
+        let mut parsed = Rule {
+            events: Vec::new(),
+            tags: Vec::new(),
+            hours: None,
+            days: Vec::new(),
+            channels,
+        };
+        for token in conditions.split_whitespace() {
+            let token = token.to_lowercase();
+            if token == "*" || token == "all" {
+                continue;
+            } else if let Some(tag) = token.strip_prefix("tag:") {
+                parsed.tags.push(tag.trim_start_matches('#').to_string());
+            } else if let Some(hours) = token.strip_prefix("hours:") {
+                parsed.hours = Some(parse_hours(hours)?);
+            } else if let Some(days) = token.strip_prefix("days:") {
+                parsed.days = parse_days(days)?;
+            } else {
+                let name = token.strip_suffix('s').unwrap_or(&token);
+                let event = Event::ALL
+                    .into_iter()
+                    .find(|e| e.name() == token || e.name() == name)
+                    .ok_or_else(|| {
+                        format!(
+                            "unknown condition '{}' (use running, waiting, idle, error, tag:, hours:, or days:)",
+                            token
+                        )
+                    })?;
+                parsed.events.push(event);
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn matches(&self, event: Event, tags: &[String], now: NaiveDateTime) -> bool {
+        if !self.events.is_empty() && !self.events.contains(&event) {
+            return false;
+        }
+        if !self.tags.is_empty() && !self.tags.iter().any(|t| tags.contains(t)) {
+            return false;
+        }
+        if !self.days.is_empty() && !self.days.contains(&now.weekday()) {
+            return false;
+        }
+        if let Some((start, end)) = self.hours {
+            let time = now.time();
+            let inside = if start <= end {
+                time >= start && time < end
+            } else {
+                time >= start || time < end
+            };
+            if !inside {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn parse_hours(hours: &str) -> Result<(NaiveTime, NaiveTime), String> {
+    let invalid = || format!("invalid hours '{}' (expected HH:MM-HH:MM)", hours);
+    let (start, end) = hours.split_once('-').ok_or_else(invalid)?;
+    let start = NaiveTime::parse_from_str(start, "%H:%M").map_err(|_| invalid())?;
+    let end = NaiveTime::parse_from_str(end, "%H:%M").map_err(|_| invalid())?;
+    Ok((start, end))
+}
+
+fn parse_days(days: &str) -> Result<Vec<Weekday>, String> {
+    let day = |name: &str| {
+        name.parse::<Weekday>()
+            .map_err(|_| format!("invalid day '{}' (use mon, tue, ..., sun)", name))
+    };
+    let mut parsed = Vec::new();
+    for part in days.split(',').filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((from, to)) => {
+                let (mut current, to) = (day(from)?, day(to)?);
+                parsed.push(current);
+                while current != to {
+                    current = current.succ();
+                    parsed.push(current);
+                }
+            }
+            None => parsed.push(day(part)?),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Parse every rule, returning the first error.
+pub fn parse_rules(rules: &[String]) -> Result<Vec<Rule>, String> {
+    rules.iter().map(|r| Rule::parse(r)).collect()
+}
+
+/// Channels for `event` on a session with `tags` at `now`, from the first
+/// matching rule. Empty when no rule matches or it routes to `none`.
+pub fn route(rules: &[Rule], event: Event, tags: &[String], now: NaiveDateTime) -> Vec<Channel> {
+    rules
+        .iter()
+        .find(|r| r.matches(event, tags, now))
+        .map(|r| {
+            r.channels
+                .iter()
+                .copied()
+                .filter(|c| *c != Channel::None)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A session event to deliver
+#[derive(Debug, Clone, Serialize)]
+pub struct Notification {
+    #[serde(serialize_with = "serialize_event")]
+    pub event: Event,
+    pub session_id: String,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub message: String,
+}
+
+fn serialize_event<S: serde::Serializer>(event: &Event, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(event.name())
+}
+
+/// Deliver `notification` to the channels the configured rules route it
+/// to. Invalid rules are logged and skipped. Delivery runs in the
+/// background and never blocks the caller.
+pub fn notify(config: &NotificationConfig, notification: Notification) {
+    if config.rules.is_empty() {
+        return;
+    }
+    let rules: Vec<Rule> = config
+        .rules
+        .iter()
+        .filter_map(|r| {
+            Rule::parse(r)
+                .map_err(|e| tracing::warn!("Ignoring notification rule: {}", e))
+                .ok()
+        })
+        .collect();
+    let now = Local::now().naive_local();
+    for channel in route(&rules, notification.event, &notification.tags, now) {
+        match channel {
+            Channel::Desktop => send_desktop(&notification),
+            Channel::Webhook => match &config.webhook_url {
+                Some(url) => send_webhook(url.clone(), notification.clone()),
+                None => tracing::warn!("Notification routed to webhook but no webhook_url is set"),
+            },
+            Channel::Bell => {
+                let mut stdout = std::io::stdout();
+                let _ = stdout.write_all(b"\x07");
+                let _ = stdout.flush();
+            }
+            Channel::None => {}
+        }
+    }
+}
+
+fn send_desktop(notification: &Notification) {
+    let title = format!("aoe: {}", notification.title);
+    let mut cmd = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {} with title {}",
+            applescript_string(&notification.message),
+            applescript_string(&title)
+        );
+        let mut cmd = Command::new("osascript");
+        cmd.args(["-e", &script]);
+        cmd
+    } else {
+        let mut cmd = Command::new("notify-send");
+        cmd.args(["--app-name=aoe", &title, &notification.message]);
+        cmd
+    };
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    match cmd.spawn() {
+        // Reap the child off the UI thread
+        Ok(mut child) => {
+            std::thread::spawn(move || child.wait());
+        }
+        Err(e) => tracing::warn!("Failed to show desktop notification: {}", e),
+    }
+}
+
+fn applescript_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn send_webhook(url: String, notification: Notification) {
+    std::thread::spawn(move || {
+        if let Err(e) = post_json(&url, &notification) {
+            tracing::warn!("Failed to send notification webhook: {}", e);
+        }
+    });
+}
+
+/// POST `body` to `url` as JSON and wait for the response. Starts its own
+/// runtime, so it must not be called from an async runtime's thread.
+pub(crate) fn post_json(url: &str, body: &impl Serialize) -> anyhow::Result<()> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(async {
+        reqwest::Client::builder()
+            .user_agent("agent-of-empires")
+            .timeout(std::time::Duration::from_secs(5))
+            .build()?
+            .post(url)
+            .json(body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        // 2026-06-01 is a Monday
+        NaiveDate::from_ymd_opt(2026, 6, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn rules(rules: &[&str]) -> Vec<Rule> {
+        parse_rules(&rules.iter().map(|r| r.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn test_first_matching_rule_routes() {
+        let rules = rules(&[
+            "errors tag:prod -> webhook, bell",
+            "hours:09:00-18:00 days:mon-fri -> desktop",
+            "* -> none",
+        ]);
+        let prod = vec!["prod".to_string()];
+
+        assert_eq!(
+            route(&rules, Event::Error, &prod, at(6, 23)),
+            vec![Channel::Webhook, Channel::Bell]
+        );
+        assert_eq!(
+            route(&rules, Event::Waiting, &prod, at(1, 10)),
+            vec![Channel::Desktop]
+        );
+        // Outside work hours, and on a Saturday
+        assert!(route(&rules, Event::Waiting, &prod, at(1, 20)).is_empty());
+        assert!(route(&rules, Event::Error, &[], at(6, 10)).is_empty());
+    }
+
+    #[test]
+    fn test_hours_can_wrap_midnight() {
+        let rules = rules(&["hours:22:00-06:00 -> bell"]);
+        assert_eq!(
+            route(&rules, Event::Idle, &[], at(1, 23)),
+            vec![Channel::Bell]
+        );
+        assert_eq!(
+            route(&rules, Event::Idle, &[], at(1, 3)),
+            vec![Channel::Bell]
+        );
+        assert!(route(&rules, Event::Idle, &[], at(1, 12)).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        assert!(Rule::parse("error webhook").is_err());
+        assert!(Rule::parse("error -> pager").is_err());
+        assert!(Rule::parse("finished -> desktop").is_err());
+        assert!(Rule::parse("hours:9-5 -> desktop").is_err());
+        assert!(Rule::parse("days:someday -> desktop").is_err());
+        assert!(Rule::parse("error ->").is_err());
+    }
+
+    #[test]
+    fn test_parse_days_range_wraps_week() {
+        assert_eq!(
+            parse_days("fri-mon").unwrap(),
+            vec![Weekday::Fri, Weekday::Sat, Weekday::Sun, Weekday::Mon]
+        );
+        assert_eq!(
+            parse_days("sat,sun").unwrap(),
+            vec![Weekday::Sat, Weekday::Sun]
+        );
+    }
+}
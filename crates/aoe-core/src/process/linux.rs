@@ -4,7 +4,7 @@ use std::fs;
 use std::path::Path;
 
 use nix::sys::signal::{kill, Signal};
-use nix::unistd::Pid;
+use nix::unistd::{sysconf, Pid, SysconfVar};
 use tracing::debug;
 
 /// Kill a process and all its descendants
@@ -131,6 +131,37 @@ fn find_process_in_group(pgrp: u32) -> Option<u32> {
     None
 }
 
+/// Every process's pid, parent pid, and CPU seconds used (user plus
+/// system time from /proc/[pid]/stat), at clock-tick resolution
+pub fn cpu_times() -> Option<Vec<(u32, u32, f64)>> {
+    let ticks = sysconf(SysconfVar::CLK_TCK)
+        .ok()
+        .flatten()
+        .filter(|t| *t > 0)? as f64;
+    let mut entries = Vec::new();
+    for entry in fs::read_dir("/proc").ok()?.flatten() {
+        let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+        let Ok(content) = fs::read_to_string(entry.path().join("stat")) else {
+            continue;
+        };
+        if let Some((ppid, cpu_ticks)) = parse_cpu_ticks(&content) {
+            entries.push((pid, ppid, cpu_ticks as f64 / ticks));
+        }
+    }
+    Some(entries)
+}
+
+/// Parent pid and user plus system clock ticks from a /proc/[pid]/stat line
+fn parse_cpu_ticks(content: &str) -> Option<(u32, i64)> {
+    // Fields: ppid(3) ... utime(13) stime(14)
+    let ppid = parse_stat_field(content, 3)?;
+    let utime = parse_stat_field(content, 13)?;
+    let stime = parse_stat_field(content, 14)?;
+    Some((ppid as u32, utime + stime))
+}
+
 /// Parse a specific field from /proc/[pid]/stat
 /// Fields are space-separated but comm (field 2) can contain spaces and is in parens
 fn parse_stat_field(content: &str, field_idx: usize) -> Option<i64> {
@@ -159,4 +190,19 @@ mod tests {
         assert_eq!(parse_stat_field(stat, 4), Some(1234)); // pgrp
         assert_eq!(parse_stat_field(stat, 7), Some(1234)); // tpgid
     }
+
+    #[test]
+    fn test_parse_cpu_ticks() {
+        let stat = "4321 (node (agent)) S 4300 4321 4300 34816 4321 4194560 \
+                    9520 0 0 0 1234 56 0 0 20 0 11 0 123456";
+        assert_eq!(parse_cpu_ticks(stat), Some((4300, 1290)));
+        assert_eq!(parse_cpu_ticks("4321 (sh) S 1"), None);
+    }
+
+    #[test]
+    fn test_cpu_times_include_this_process() {
+        let entries = cpu_times().expect("/proc is readable");
+        let me = std::process::id();
+        assert!(entries.iter().any(|&(pid, _, cpu)| pid == me && cpu >= 0.0));
+    }
 }
@@ -0,0 +1,282 @@
+//! Process utilities for tmux session management
+
+use std::collections::HashMap;
+use std::process::Command;
+
+use nix::sys::signal::{kill, Signal};
+use nix::unistd::Pid;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+#[cfg(target_os = "macos")]
+mod macos;
+
+/// Get the PID of the shell process running in a tmux pane
+pub fn get_pane_pid(session_name: &str) -> Option<u32> {
+    // Use `^.0` to target the first window's first pane regardless of
+    // base-index or which pane is active, so we always query the agent's
+    // pane even when the user has created additional tmux windows or split
+    // panes.  See #435, #488.
+    let target = format!("{session_name}:^.0");
+    let output = Command::new("tmux")
+        .args(["display-message", "-t", &target, "-p", "#{pane_pid}"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// Get the foreground process group leader PID for a given shell PID
+/// This finds the actual process that has the terminal foreground
+pub fn get_foreground_pid(shell_pid: u32) -> Option<u32> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::get_foreground_pid(shell_pid)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::get_foreground_pid(shell_pid)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        let _ = shell_pid;
+        None
+    }
+}
+
+/// Kill a process and all its descendants
+/// Sends SIGTERM first, then SIGKILL to any survivors
+pub fn kill_process_tree(pid: u32) {
+    #[cfg(target_os = "linux")]
+    {
+        linux::kill_process_tree(pid);
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::kill_process_tree(pid);
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        let _ = pid;
+        // No-op on unsupported platforms, fall back to tmux kill-session only
+    }
+}
+
+/// Snapshot of every process's parent and cumulative CPU time: read from
+/// `/proc` at clock-tick resolution on Linux, taken with `ps` elsewhere.
+pub struct ProcessTable {
+    /// (pid, parent pid, CPU seconds used so far)
+    entries: Vec<(u32, u32, f64)>,
+}
+
+impl ProcessTable {
+    pub fn snapshot() -> Option<Self> {
+        #[cfg(target_os = "linux")]
+        if let Some(entries) = linux::cpu_times() {
+            return Some(Self { entries });
+        }
+
+        let output = Command::new("ps")
+            .args(["-A", "-o", "pid=,ppid=,time="])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(Self::parse(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn parse(ps_output: &str) -> Self {
+        let entries = ps_output
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let pid = fields.next()?.parse().ok()?;
+                let ppid = fields.next()?.parse().ok()?;
+                let cpu = parse_cpu_time(fields.next()?)?;
+                Some((pid, ppid, cpu))
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Total CPU seconds used by `pid` and all of its descendants.
+    pub fn tree_cpu_time(&self, pid: u32) -> f64 {
+        let mut total = 0.0;
+        let mut pending = vec![pid];
+        while let Some(current) = pending.pop() {
+            for &(child, parent, cpu) in &self.entries {
+                if child == current {
+                    total += cpu;
+                }
+                if parent == current && child != current {
+                    pending.push(child);
+                }
+            }
+        }
+        total
+    }
+}
+
+/// One process of a session's process tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessNode {
+    pub pid: u32,
+    pub ppid: u32,
+    /// Levels below the tree's root, which is 0
+    pub depth: usize,
+    /// `ps` state code, e.g. "S", "R+" or "Z"
+    pub state: String,
+    /// Recent CPU usage in percent of one core
+    pub cpu: f32,
+    /// Command line, or the process name in brackets when it has none
+    pub command: String,
+}
+
+impl ProcessNode {
+    /// Exited but not yet reaped by its parent
+    pub fn is_zombie(&self) -> bool {
+        self.state.starts_with('Z')
+    }
+
+    /// Stopped by a signal, e.g. SIGSTOP or Ctrl+Z
+    pub fn is_stopped(&self) -> bool {
+        self.state.starts_with('T')
+    }
+}
+
+/// `pid` and all of its descendants, depth first with siblings in pid
+/// order. Empty if `pid` is not running or `ps` fails.
+pub fn process_tree(pid: u32) -> Vec<ProcessNode> {
+    let Ok(output) = Command::new("ps")
+        .args(["-A", "-o", "pid=,ppid=,pcpu=,stat=,args="])
+        .output()
+    else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    parse_process_tree(&String::from_utf8_lossy(&output.stdout), pid)
+}
+
+fn parse_process_tree(ps_output: &str, root: u32) -> Vec<ProcessNode> {
+    let mut nodes: HashMap<u32, ProcessNode> = HashMap::new();
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for line in ps_output.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(pid), Some(ppid), Some(cpu), Some(state)) = (
+            fields.next().and_then(|f| f.parse().ok()),
+            fields.next().and_then(|f| f.parse().ok()),
+            fields.next().and_then(|f| f.parse().ok()),
+            fields.next(),
+        ) else {
+            continue;
+        };
+        if pid != ppid {
+            children.entry(ppid).or_default().push(pid);
+        }
+        nodes.insert(
+            pid,
+            ProcessNode {
+                pid,
+                ppid,
+                depth: 0,
+                state: state.to_string(),
+                cpu,
+                command: fields.collect::<Vec<_>>().join(" "),
+            },
+        );
+    }
+
+    let mut tree = Vec::new();
+    let mut pending = vec![(root, 0)];
+    while let Some((pid, depth)) = pending.pop() {
+        let Some(mut node) = nodes.remove(&pid) else {
+            continue;
+        };
+        node.depth = depth;
+        tree.push(node);
+        if let Some(kids) = children.get_mut(&pid) {
+            kids.sort_unstable();
+            pending.extend(kids.iter().rev().map(|&kid| (kid, depth + 1)));
+        }
+    }
+    tree
+}
+
+/// Send `signal` to a single process.
+pub fn send_signal(pid: u32, signal: Signal) -> anyhow::Result<()> {
+    kill(Pid::from_raw(pid as i32), signal)
+        .map_err(|e| anyhow::anyhow!("Failed to send {} to {}: {}", signal, pid, e))
+}
+
+/// Parse `ps` CPU time: `[DD-][HH:]MM:SS[.ss]`.
+fn parse_cpu_time(value: &str) -> Option<f64> {
+    let (days, clock) = match value.split_once('-') {
+        Some((days, clock)) => (days.parse::<f64>().ok()?, clock),
+        None => (0.0, value),
+    };
+    let mut seconds = 0.0;
+    for part in clock.split(':') {
+        seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
+    }
+    Some(days * 86_400.0 + seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpu_time() {
+        assert_eq!(parse_cpu_time("00:01:05"), Some(65.0));
+        assert_eq!(parse_cpu_time("1-02:00:00"), Some(93_600.0));
+        assert_eq!(parse_cpu_time("0:02.50"), Some(2.5));
+        assert_eq!(parse_cpu_time("n/a"), None);
+    }
+
+    #[test]
+    fn test_tree_cpu_time_sums_descendants() {
+        let table = ProcessTable::parse(
+            "    1     0 00:10:00
+  100     1 00:00:02
+  101   100 00:00:30
+  102   101 00:01:00
+  200     1 00:05:00
+",
+        );
+        assert_eq!(table.tree_cpu_time(100), 92.0);
+        assert_eq!(table.tree_cpu_time(102), 60.0);
+        assert_eq!(table.tree_cpu_time(999), 0.0);
+    }
+
+    #[test]
+    fn test_process_tree_walks_descendants_in_order() {
+        let tree = parse_process_tree(
+            "    1     0  0.0 Ss   /sbin/init
+  100     1  0.5 Ss   -zsh
+  103   100 99.0 R+   cargo test --workspace
+  101   100  0.0 Z    [node] <defunct>
+  102   101  1.5 S    sleep 30
+  200     1  0.0 S    unrelated
+",
+            100,
+        );
+        let summary: Vec<(u32, usize)> = tree.iter().map(|n| (n.pid, n.depth)).collect();
+        assert_eq!(summary, vec![(100, 0), (101, 1), (102, 2), (103, 1)]);
+        assert!(tree[1].is_zombie());
+        assert_eq!(tree[3].command, "cargo test --workspace");
+        assert_eq!(tree[3].cpu, 99.0);
+        assert!(parse_process_tree("", 100).is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+//! Files and diffs attached to a session's first prompt
+//!
+//! Attachments are resolved against the project directory when the session
+//! is created. Agents that read files named in their prompt (Claude's
+//! `@path`, for example) get a reference; otherwise file contents are
+//! inlined. Inlined text is capped per attachment and in total, with a
+//! marker where it was cut.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+/// Most bytes inlined from one attachment
+pub const MAX_ATTACHMENT_BYTES: usize = 16 * 1024;
+
+/// Most bytes inlined from all attachments together
+pub const MAX_TOTAL_BYTES: usize = 64 * 1024;
+
+/// How many files the picker offers
+pub const MAX_LISTED_FILES: usize = 5000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Attachment {
+    /// A file, relative to the project directory
+    File(String),
+    /// Uncommitted changes to tracked files (`git diff HEAD`)
+    GitDiff,
+}
+
+impl Attachment {
+    pub fn label(&self) -> String {
+        match self {
+            Attachment::File(path) => path.clone(),
+            Attachment::GitDiff => "git diff (uncommitted changes)".to_string(),
+        }
+    }
+}
+
+/// Files under `dir` that git does not ignore, relative to it, for the
+/// attachment picker.
+pub fn list_files(dir: &Path) -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["ls-files", "--cached", "--others", "--exclude-standard"])
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git ls-files")?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .take(MAX_LISTED_FILES)
+        .map(String::from)
+        .collect())
+}
+
+/// Prompt text for `attachments`, or None if there are none. Files are
+/// referenced with `file_reference` (a template where `{}` is the path)
+/// when the agent has one, and inlined otherwise.
+pub fn render(
+    attachments: &[Attachment],
+    dir: &Path,
+    file_reference: Option<&str>,
+) -> Result<Option<String>> {
+    if attachments.is_empty() {
+        return Ok(None);
+    }
+    let mut budget = MAX_TOTAL_BYTES;
+    let mut references = Vec::new();
+    let mut blocks = Vec::new();
+    for attachment in attachments {
+        let (name, content) = match attachment {
+            Attachment::File(path) => {
+                if let Some(template) = file_reference {
+                    references.push(template.replace("{}", path));
+                    continue;
+                }
+                let bytes = std::fs::read(dir.join(path))
+                    .with_context(|| format!("Failed to read attachment {}", path))?;
+                let content = if bytes.contains(&0) {
+                    "[binary file omitted]".to_string()
+                } else {
+                    String::from_utf8_lossy(&bytes).into_owned()
+                };
+                (format!("file {}", path), content)
+            }
+            Attachment::GitDiff => ("git diff".to_string(), git_diff(dir)?),
+        };
+        if budget == 0 {
+            blocks.push(format!(
+                "[{} omitted: attachments exceed {} KB]",
+                name,
+                MAX_TOTAL_BYTES / 1024
+            ));
+            continue;
+        }
+        let limit = MAX_ATTACHMENT_BYTES.min(budget);
+        let content = truncate(&content, limit);
+        budget = budget.saturating_sub(content.len());
+        blocks.push(format!(
+            "--- {} ---\n{}\n--- end {} ---",
+            name, content, name
+        ));
+    }
+
+    let mut prompt = String::new();
+    if !references.is_empty() {
+        prompt.push_str("Attached files: ");
+        prompt.push_str(&references.join(" "));
+    }
+    for block in blocks {
+        if !prompt.is_empty() {
+            prompt.push_str("\n\n");
+        }
+        prompt.push_str(&block);
+    }
+    Ok(Some(prompt))
+}
+
+fn git_diff(dir: &Path) -> Result<String> {
+    let output = Command::new("git")
+        .args(["diff", "--no-color", "HEAD"])
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git diff")?;
+    if !output.status.success() {
+        bail!(
+            "git diff failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let diff = String::from_utf8_lossy(&output.stdout).into_owned();
+    Ok(if diff.trim().is_empty() {
+        "[no uncommitted changes]".to_string()
+    } else {
+        diff
+    })
+}
+
+/// `content` cut to at most `limit` bytes on a line or char boundary, with
+/// a marker saying how much was dropped.
+fn truncate(content: &str, limit: usize) -> String {
+    if content.len() <= limit {
+        return content.trim_end().to_string();
+    }
+    let mut end = limit;
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    if let Some(newline) = content[..end].rfind('\n') {
+        end = newline;
+    }
+    format!(
+        "{}\n[... truncated {} bytes ...]",
+        &content[..end],
+        content.len() - end
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_render_inlines_and_truncates() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("small.txt"), "hello\n").unwrap();
+        let big = "line of text\n".repeat(MAX_ATTACHMENT_BYTES / 10);
+        std::fs::write(dir.path().join("big.txt"), &big).unwrap();
+
+        let prompt = render(
+            &[
+                Attachment::File("small.txt".into()),
+                Attachment::File("big.txt".into()),
+            ],
+            dir.path(),
+            None,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(prompt.starts_with("--- file small.txt ---\nhello\n--- end file small.txt ---"));
+        assert!(prompt.contains("[... truncated "));
+        assert!(prompt.len() < MAX_ATTACHMENT_BYTES + 200);
+    }
+
+    #[test]
+    fn test_render_uses_file_reference() {
+        let dir = TempDir::new().unwrap();
+        let prompt = render(
+            &[Attachment::File("src/main.rs".into())],
+            dir.path(),
+            Some("@{}"),
+        )
+        .unwrap();
+        assert_eq!(prompt.as_deref(), Some("Attached files: @src/main.rs"));
+        assert_eq!(render(&[], dir.path(), None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_total_budget_omits_later_attachments() {
+        let dir = TempDir::new().unwrap();
+        let files: Vec<Attachment> = (0..6)
+            .map(|i| {
+                let name = format!("f{}.txt", i);
+                std::fs::write(dir.path().join(&name), "x".repeat(MAX_ATTACHMENT_BYTES)).unwrap();
+                Attachment::File(name)
+            })
+            .collect();
+        let prompt = render(&files, dir.path(), None).unwrap().unwrap();
+        assert!(prompt.contains("[file f5.txt omitted: attachments exceed 64 KB]"));
+    }
+}
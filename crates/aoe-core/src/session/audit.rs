@@ -0,0 +1,260 @@
+//! Append-only audit log of mutating operations
+//!
+//! Every action that changes something (sessions created, started, stopped,
+//! removed, or restored, prompts sent, hooks run, config saved) is appended
+//! to `audit.jsonl` in the app directory with a timestamp and whether it came
+//! from the TUI, the CLI, or the `aoe ipc` API. Unlike session timelines the
+//! log spans all profiles and is never trimmed or rewritten. Recording is
+//! best-effort: a failed write is logged and never interrupts the operation.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{get_app_dir, Instance};
+
+/// Where an action was started from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    Tui,
+    Cli,
+    Api,
+}
+
+impl Source {
+    pub fn label(self) -> &'static str {
+        match self {
+            Source::Tui => "tui",
+            Source::Cli => "cli",
+            Source::Api => "api",
+        }
+    }
+}
+
+static SOURCE: AtomicU8 = AtomicU8::new(Source::Cli as u8);
+
+/// Set the source recorded for this process's actions. Called once at startup.
+pub fn set_source(source: Source) {
+    SOURCE.store(source as u8, Ordering::Relaxed);
+}
+
+fn current_source() -> Source {
+    match SOURCE.load(Ordering::Relaxed) {
+        s if s == Source::Tui as u8 => Source::Tui,
+        s if s == Source::Api as u8 => Source::Api,
+        _ => Source::Cli,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Created,
+    Started,
+    Stopped,
+    Removed,
+    Restored,
+    Purged,
+    Prompt,
+    Hook,
+    Config,
+}
+
+impl Action {
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::Created => "created",
+            Action::Started => "started",
+            Action::Stopped => "stopped",
+            Action::Removed => "removed",
+            Action::Restored => "restored",
+            Action::Purged => "purged",
+            Action::Prompt => "prompt",
+            Action::Hook => "hook",
+            Action::Config => "config",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub at: DateTime<Utc>,
+    pub source: Source,
+    pub action: Action,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub detail: String,
+}
+
+impl AuditEntry {
+    /// Whether `query` appears (case-insensitively) in any field.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        [
+            self.action.label(),
+            self.source.label(),
+            self.session_id.as_deref().unwrap_or_default(),
+            self.title.as_deref().unwrap_or_default(),
+            &self.detail,
+        ]
+        .iter()
+        .any(|field| field.to_lowercase().contains(&query))
+    }
+}
+
+pub fn audit_log_path() -> Result<PathBuf> {
+    Ok(get_app_dir()?.join("audit.jsonl"))
+}
+
+/// Append an action on a session.
+pub fn record_session(action: Action, instance: &Instance, detail: impl AsRef<str>) {
+    record(
+        action,
+        Some(&instance.id),
+        Some(&instance.title),
+        detail.as_ref(),
+    );
+}
+
+/// Append an action. `session_id` and `title` identify the session it
+/// applies to, if any.
+pub fn record(action: Action, session_id: Option<&str>, title: Option<&str>, detail: &str) {
+    let entry = AuditEntry {
+        at: Utc::now(),
+        source: current_source(),
+        action,
+        session_id: session_id.map(str::to_string),
+        title: title.map(str::to_string),
+        detail: super::timeline::summarize(detail),
+    };
+    if let Err(e) = append(&entry) {
+        tracing::warn!("Failed to write audit log entry: {}", e);
+    }
+}
+
+fn append(entry: &AuditEntry) -> Result<()> {
+    let path = audit_log_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// Every entry, oldest first. Unparseable lines are skipped.
+pub fn load() -> Result<Vec<AuditEntry>> {
+    let path = audit_log_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(fs::read_to_string(&path)?
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Record a saved config file, naming the settings that changed. Nothing is
+/// recorded if the file's content is unchanged. `app_state` is skipped since
+/// the TUI writes it itself (list width, seen dialogs).
+pub fn record_config_change(what: &str, old: Option<&str>, new: &str) {
+    let parse = |s: &str| {
+        let mut table = s.parse::<toml::Table>().unwrap_or_default();
+        table.remove("app_state");
+        table
+    };
+    let old = old.map(parse).unwrap_or_default();
+    let new = parse(new);
+    let mut changed = Vec::new();
+    changed_keys("", &old, &new, &mut changed);
+    if !changed.is_empty() {
+        record(
+            Action::Config,
+            None,
+            None,
+            &format!("{}: {}", what, changed.join(", ")),
+        );
+    }
+}
+
+/// Dotted paths of keys added, removed, or changed between two tables.
+fn changed_keys(prefix: &str, old: &toml::Table, new: &toml::Table, out: &mut Vec<String>) {
+    let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match (old.get(key), new.get(key)) {
+            (Some(toml::Value::Table(a)), Some(toml::Value::Table(b))) => {
+                changed_keys(&path, a, b, out)
+            }
+            (Some(toml::Value::Table(a)), None) => changed_keys(&path, a, &toml::Table::new(), out),
+            (None, Some(toml::Value::Table(b))) => changed_keys(&path, &toml::Table::new(), b, out),
+            (a, b) if a != b => out.push(path),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_changed_keys() {
+        let old: toml::Table = "a = 1\n[session]\nnice = 0\nlocks = []\n".parse().unwrap();
+        let new: toml::Table = "a = 1\nb = true\n[session]\nnice = 10\nlocks = []\n"
+            .parse()
+            .unwrap();
+        let mut changed = Vec::new();
+        changed_keys("", &old, &new, &mut changed);
+        assert_eq!(changed, vec!["b", "session.nice"]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_record_and_search() -> Result<()> {
+        let temp = tempdir()?;
+        std::env::set_var("HOME", temp.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.path().join(".config"));
+
+        let inst = Instance::new("fix-login", "/tmp/project");
+        record_session(Action::Created, &inst, "Created with claude");
+        set_source(Source::Api);
+        record_session(Action::Prompt, &inst, "run the\ntests");
+        set_source(Source::Cli);
+        record_config_change("global config", Some("[theme]\nname = \"a\"\n"), "");
+        record_config_change("global config", Some("x = 1\n"), "x = 1\n");
+        record_config_change(
+            "global config",
+            Some("x = 1\n"),
+            "x = 1\n[app_state]\nhome_list_width = 40\n",
+        );
+
+        let entries = load()?;
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].source, Source::Cli);
+        assert_eq!(entries[1].source, Source::Api);
+        assert_eq!(entries[1].detail, "run the tests");
+        assert_eq!(entries[2].detail, "global config: theme.name");
+        assert!(entries[1].matches("FIX-LOGIN"));
+        assert!(entries[1].matches("api"));
+        assert!(!entries[2].matches("fix-login"));
+        Ok(())
+    }
+}
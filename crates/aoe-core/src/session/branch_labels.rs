@@ -0,0 +1,134 @@
+//! Session labels from branch naming conventions
+//!
+//! Branch names often say what kind of work they hold and which ticket it is
+//! for, as in `feat/ABC-123-login-form`. When a worktree session is created,
+//! `worktree.branch_tag_pattern` pulls a tag from its branch (the `tag`
+//! capture group, or the whole match) and `worktree.branch_ticket_pattern` a
+//! ticket ID (the `ticket` group, or the whole match), which
+//! `worktree.ticket_url_template` turns into a link. Both end up among the
+//! session's tags, so `tag:fix` or `tag:abc-123` filter on them.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::config::WorktreeConfig;
+
+pub const DEFAULT_TAG_PATTERN: &str =
+    r"^(?P<tag>feat|feature|fix|bugfix|hotfix|chore|docs|refactor|perf|test)/";
+pub const DEFAULT_TICKET_PATTERN: &str = r"[A-Z][A-Z0-9]+-\d+";
+
+/// A ticket a session's branch refers to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ticket {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// What a branch name says about its session
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BranchLabels {
+    /// Lowercased, like `#tags` from titles and notes
+    pub tags: Vec<String>,
+    pub ticket: Option<Ticket>,
+}
+
+/// Check a branch pattern setting; empty turns it off
+pub fn validate_pattern(pattern: &str) -> Result<(), String> {
+    if pattern.trim().is_empty() {
+        return Ok(());
+    }
+    Regex::new(pattern)
+        .map(|_| ())
+        .map_err(|e| format!("Invalid regular expression: {}", e))
+}
+
+/// The `group` capture of `pattern` in `text`, or the whole match
+fn capture(pattern: &str, group: &str, text: &str) -> Option<String> {
+    if pattern.trim().is_empty() {
+        return None;
+    }
+    let re = match Regex::new(pattern) {
+        Ok(re) => re,
+        Err(e) => {
+            tracing::warn!("Ignoring branch pattern '{}': {}", pattern, e);
+            return None;
+        }
+    };
+    let caps = re.captures(text)?;
+    caps.name(group)
+        .or_else(|| caps.get(0))
+        .map(|m| m.as_str().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+pub fn parse(branch: &str, config: &WorktreeConfig) -> BranchLabels {
+    let mut tags = Vec::new();
+    if let Some(tag) = capture(&config.branch_tag_pattern, "tag", branch) {
+        tags.push(tag.to_lowercase());
+    }
+
+    let ticket = capture(&config.branch_ticket_pattern, "ticket", branch).map(|id| {
+        let template = config.ticket_url_template.trim();
+        Ticket {
+            url: (!template.is_empty()).then(|| template.replace("{ticket}", &id)),
+            id,
+        }
+    });
+    if let Some(ticket) = &ticket {
+        let tag = ticket.id.to_lowercase();
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    BranchLabels { tags, ticket }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_default_conventions() {
+        let config = WorktreeConfig::default();
+
+        let labels = parse("feat/ABC-123-login-form", &config);
+        assert_eq!(labels.tags, vec!["feat", "abc-123"]);
+        assert_eq!(
+            labels.ticket,
+            Some(Ticket {
+                id: "ABC-123".to_string(),
+                url: None,
+            })
+        );
+
+        assert_eq!(parse("fix/typo", &config).tags, vec!["fix"]);
+        assert_eq!(parse("wip-add-2fa", &config), BranchLabels::default());
+    }
+
+    #[test]
+    fn test_parse_custom_patterns() {
+        let config = WorktreeConfig {
+            branch_tag_pattern: r"^(?P<tag>[a-z]+)-".to_string(),
+            branch_ticket_pattern: r"gh-(?P<ticket>\d+)".to_string(),
+            ticket_url_template: "https://github.com/org/repo/issues/{ticket}".to_string(),
+            ..Default::default()
+        };
+        let labels = parse("spike-gh-42-cache", &config);
+        assert_eq!(labels.tags, vec!["spike", "42"]);
+        assert_eq!(
+            labels.ticket.unwrap().url.as_deref(),
+            Some("https://github.com/org/repo/issues/42")
+        );
+
+        let off = WorktreeConfig {
+            branch_tag_pattern: String::new(),
+            branch_ticket_pattern: "(".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(parse("feat/ABC-1", &off), BranchLabels::default());
+        assert!(validate_pattern("(").is_err());
+        assert!(validate_pattern("").is_ok());
+    }
+}
@@ -0,0 +1,305 @@
+//! Daily and weekly spend budgets
+//!
+//! Spend is estimated from the costs agents report in their panes: the
+//! costs recorded in `stats.jsonl` for removed sessions that ended inside
+//! the budget window, plus the last reported cost of sessions still alive.
+//! A day starts at local midnight and a week on Monday at local midnight.
+//! Budgets are set per profile with `daily_budget` and `weekly_budget`.
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveTime, TimeZone, Utc};
+
+use super::config::{BudgetAction, SessionConfig};
+use super::stats::{HistoryQuery, SessionRecord};
+use super::DEFAULT_PROFILE;
+
+/// The period a budget covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    Day,
+    Week,
+}
+
+impl Window {
+    pub fn name(self) -> &'static str {
+        match self {
+            Window::Day => "daily",
+            Window::Week => "weekly",
+        }
+    }
+
+    /// When the window containing `now` started
+    pub fn start(self, now: DateTime<Local>) -> DateTime<Utc> {
+        let mut date = now.date_naive();
+        if self == Window::Week {
+            date -= Duration::days(date.weekday().num_days_from_monday() as i64);
+        }
+        let midnight = date.and_time(NaiveTime::MIN);
+        Local
+            .from_local_datetime(&midnight)
+            .earliest()
+            .map_or_else(|| midnight.and_utc(), |t| t.with_timezone(&Utc))
+    }
+
+    /// When the window containing `now` ends
+    pub fn reset(self, now: DateTime<Local>) -> DateTime<Utc> {
+        let days = match self {
+            Window::Day => 1,
+            Window::Week => 7,
+        };
+        self.start(now) + Duration::days(days)
+    }
+}
+
+/// Spend against one budget
+#[derive(Debug, Clone, PartialEq)]
+pub struct Usage {
+    pub window: Window,
+    pub spent: f64,
+    pub limit: u64,
+}
+
+impl Usage {
+    /// Share of the budget spent, in percent
+    pub fn percent(&self) -> f64 {
+        self.spent / self.limit as f64 * 100.0
+    }
+
+    /// e.g. "daily budget: $8.20 of $10"
+    pub fn describe(&self) -> String {
+        format!(
+            "{} budget: ${:.2} of ${}",
+            self.window.name(),
+            self.spent,
+            self.limit
+        )
+    }
+}
+
+/// Where a profile stands against its budgets
+#[derive(Debug, Clone, PartialEq)]
+pub enum BudgetState {
+    /// No budget set, or all below the warning threshold
+    Ok,
+    /// A budget crossed the warning threshold
+    Warning(Usage),
+    /// A budget is used up
+    Exceeded(Usage),
+}
+
+impl BudgetState {
+    pub fn usage(&self) -> Option<&Usage> {
+        match self {
+            BudgetState::Ok => None,
+            BudgetState::Warning(usage) | BudgetState::Exceeded(usage) => Some(usage),
+        }
+    }
+
+    /// Ok < Warning < Exceeded
+    pub fn severity(&self) -> u8 {
+        match self {
+            BudgetState::Ok => 0,
+            BudgetState::Warning(_) => 1,
+            BudgetState::Exceeded(_) => 2,
+        }
+    }
+}
+
+/// Spend for `profile` in `window`: recorded costs of sessions that ended
+/// inside it plus `live_cost` from sessions still running.
+pub fn spent(
+    records: &[SessionRecord],
+    profile: &str,
+    window: Window,
+    now: DateTime<Local>,
+    live_cost: f64,
+) -> f64 {
+    let start = window.start(now);
+    let recorded: f64 = records
+        .iter()
+        .filter(|r| r.ended_at >= start && record_profile(r) == profile)
+        .filter_map(|r| r.cost)
+        .sum();
+    recorded + live_cost
+}
+
+/// Records written before profiles were tracked count toward the default
+fn record_profile(record: &SessionRecord) -> &str {
+    if record.profile.is_empty() {
+        DEFAULT_PROFILE
+    } else {
+        &record.profile
+    }
+}
+
+/// Check `profile`'s budgets, reporting the worst one
+pub fn check(
+    config: &SessionConfig,
+    records: &[SessionRecord],
+    profile: &str,
+    now: DateTime<Local>,
+    live_cost: f64,
+) -> BudgetState {
+    [
+        (Window::Day, config.daily_budget),
+        (Window::Week, config.weekly_budget),
+    ]
+    .into_iter()
+    .filter(|(_, limit)| *limit > 0)
+    .map(|(window, limit)| {
+        let usage = Usage {
+            window,
+            spent: spent(records, profile, window, now, live_cost),
+            limit,
+        };
+        if usage.spent >= limit as f64 {
+            BudgetState::Exceeded(usage)
+        } else if usage.percent() >= config.budget_warn_percent as f64 {
+            BudgetState::Warning(usage)
+        } else {
+            BudgetState::Ok
+        }
+    })
+    .max_by_key(BudgetState::severity)
+    .unwrap_or(BudgetState::Ok)
+}
+
+/// A profile's budget standing and what it is set to do when used up
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetCheck {
+    pub state: BudgetState,
+    pub action: BudgetAction,
+}
+
+impl BudgetCheck {
+    /// Why new sessions are refused, if a budget is used up and set to block
+    pub fn block_reason(&self) -> Option<String> {
+        match &self.state {
+            BudgetState::Exceeded(usage) if self.action == BudgetAction::Block => {
+                let reset = usage.window.reset(Local::now()).with_timezone(&Local);
+                Some(format!(
+                    "{} used up, new sessions are blocked until {}",
+                    usage.describe(),
+                    reset.format("%a %H:%M")
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Check `profile`'s budgets against the stats log and the given live cost
+pub fn check_profile(profile: &str, live_cost: f64) -> anyhow::Result<BudgetCheck> {
+    let config = super::resolve_config(profile)?;
+    let action = config.session.budget_action;
+    if config.session.daily_budget == 0 && config.session.weekly_budget == 0 {
+        return Ok(BudgetCheck {
+            state: BudgetState::Ok,
+            action,
+        });
+    }
+    // Eight days covers the current local week whatever the time zone
+    let records = super::stats::query(&HistoryQuery {
+        since: Some(Utc::now() - Duration::days(8)),
+        profile: Some(profile.to_string()),
+        ..Default::default()
+    })?;
+    Ok(BudgetCheck {
+        state: check(&config.session, &records, profile, Local::now(), live_cost),
+        action,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::stats::Outcome;
+
+    fn record(profile: &str, ended_at: DateTime<Utc>, cost: f64) -> SessionRecord {
+        SessionRecord {
+            ended_at,
+            session_id: "id".to_string(),
+            title: "t".to_string(),
+            project: "/p".to_string(),
+            tool: "claude".to_string(),
+            profile: profile.to_string(),
+            duration_secs: 60,
+            cost: Some(cost),
+            outcome: Outcome::Completed,
+        }
+    }
+
+    fn budgets(daily: u64, weekly: u64) -> SessionConfig {
+        SessionConfig {
+            daily_budget: daily,
+            weekly_budget: weekly,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_windows_start_at_local_midnight() {
+        // Thursday
+        let now = Local.with_ymd_and_hms(2026, 3, 12, 15, 30, 0).unwrap();
+        let day = Window::Day.start(now).with_timezone(&Local);
+        assert_eq!(day.to_string()[..19], *"2026-03-12 00:00:00");
+        let week = Window::Week.start(now).with_timezone(&Local);
+        assert_eq!(week.to_string()[..19], *"2026-03-09 00:00:00");
+        let reset = Window::Week.reset(now).with_timezone(&Local);
+        assert_eq!(reset.date_naive().to_string(), "2026-03-16");
+    }
+
+    #[test]
+    fn test_check_counts_window_profile_and_live_cost() {
+        let now = Local.with_ymd_and_hms(2026, 3, 12, 15, 30, 0).unwrap();
+        let today = (now - Duration::hours(1)).with_timezone(&Utc);
+        let monday = (now - Duration::days(3)).with_timezone(&Utc);
+        let last_week = (now - Duration::days(8)).with_timezone(&Utc);
+        let records = vec![
+            record("default", today, 3.0),
+            record("", today, 1.0),
+            record("work", today, 50.0),
+            record("default", monday, 10.0),
+            record("default", last_week, 100.0),
+        ];
+
+        assert_eq!(spent(&records, "default", Window::Day, now, 0.5), 4.5);
+        assert_eq!(spent(&records, "default", Window::Week, now, 0.0), 14.0);
+
+        assert_eq!(
+            check(&budgets(0, 0), &records, "default", now, 0.0),
+            BudgetState::Ok
+        );
+        assert_eq!(
+            check(&budgets(10, 0), &records, "default", now, 0.0),
+            BudgetState::Ok
+        );
+        assert!(matches!(
+            check(&budgets(5, 0), &records, "default", now, 0.0),
+            BudgetState::Warning(Usage {
+                window: Window::Day,
+                ..
+            })
+        ));
+        // The used-up weekly budget outranks the daily warning
+        let state = check(&budgets(5, 14), &records, "default", now, 0.0);
+        assert!(matches!(
+            state,
+            BudgetState::Exceeded(Usage {
+                window: Window::Week,
+                ..
+            })
+        ));
+        assert_eq!(
+            state.usage().unwrap().describe(),
+            "weekly budget: $14.00 of $14"
+        );
+
+        let mut check = BudgetCheck {
+            state,
+            action: BudgetAction::Warn,
+        };
+        assert!(check.block_reason().is_none());
+        check.action = BudgetAction::Block;
+        assert!(check.block_reason().unwrap().contains("blocked until"));
+    }
+}
@@ -3,16 +3,19 @@
 //! This module provides shared logic for building new session instances,
 //! used by both synchronous (TUI operations) and asynchronous (background poller) code paths.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Result};
 use chrono::Utc;
 
 use crate::containers::{self, ContainerRuntimeInterface};
-use crate::git::GitWorktree;
+use crate::git::{self, GitWorktree};
 
+use super::journal;
+use super::timeline;
 use super::{
-    civilizations, Config, Instance, SandboxInfo, WorkspaceInfo, WorkspaceRepo, WorktreeInfo,
+    civilizations, Config, Instance, IssueInfo, SandboxInfo, WorkspaceInfo, WorkspaceRepo,
+    WorktreeInfo,
 };
 
 /// Parameters for creating a new session instance.
@@ -24,6 +27,9 @@ pub struct InstanceParams {
     pub tool: String,
     pub worktree_branch: Option<String>,
     pub create_new_branch: bool,
+    /// Ref a new branch starts from, HEAD when unset. Not used for
+    /// multi-repo workspaces, where each repo branches from its own HEAD.
+    pub base_ref: Option<String>,
     pub sandbox: bool,
     /// The sandbox image to use. Required when sandbox is true.
     pub sandbox_image: String,
@@ -37,6 +43,10 @@ pub struct InstanceParams {
     pub command_override: String,
     /// Additional repository paths for multi-repo workspace mode
     pub extra_repo_paths: Vec<String>,
+    /// Issue the session is created from, if any
+    pub issue_info: Option<IssueInfo>,
+    /// Prompt passed to the agent on first launch
+    pub initial_prompt: Option<String>,
 }
 
 /// Result of building an instance, tracking what was created for cleanup purposes.
@@ -176,6 +186,84 @@ pub fn create_workspace(
     })
 }
 
+/// Resolve `worktree.repos` entries against the project's repo root.
+///
+/// Entries may be sibling repositories or submodules of the project. A
+/// submodule that was never initialized has no repository to branch from,
+/// so it is reported rather than skipped.
+pub fn configured_workspace_repos(project_path: &Path, repos: &[String]) -> Result<Vec<PathBuf>> {
+    if repos.is_empty() {
+        return Ok(Vec::new());
+    }
+    let root = GitWorktree::find_main_repo(project_path)?;
+    repos
+        .iter()
+        .map(|entry| {
+            let path = root.join(entry);
+            if !path.is_dir() {
+                bail!(
+                    "worktree.repos entry '{}' not found at {}",
+                    entry,
+                    path.display()
+                );
+            }
+            if !GitWorktree::is_git_repo(&path) {
+                bail!(
+                    "worktree.repos entry '{}' is not a git repository\n\
+                     Tip: For a submodule, run `git submodule update --init` first",
+                    entry
+                );
+            }
+            Ok(path.canonicalize().unwrap_or(path))
+        })
+        .collect()
+}
+
+/// Create the worktree for a new session at `path`: a new `branch` from
+/// `base` (HEAD when unset) if `create_branch` or `base` is given, else a
+/// checkout of the existing `branch`. With `pool_size` set, a spare worktree
+/// from the pool is used if there is one, and the pool is topped up in the
+/// background afterwards.
+pub fn create_session_worktree(
+    git_wt: &GitWorktree,
+    path: &Path,
+    branch: &str,
+    create_branch: bool,
+    base: Option<&str>,
+    pool_size: usize,
+    profile: &str,
+) -> Result<()> {
+    if pool_size == 0 {
+        match base {
+            Some(base) => git_wt.create_worktree_from(branch, path, base)?,
+            None => git_wt.create_worktree(branch, path, create_branch)?,
+        }
+        return Ok(());
+    }
+
+    let start = match base {
+        Some(base) => Some(base.to_string()),
+        None if create_branch => GitWorktree::head_commit(&git_wt.repo_path).ok(),
+        None => None,
+    };
+    let claimed = if start.is_some() || !create_branch {
+        git::pool::claim(git_wt, path, branch, start.as_deref()).unwrap_or_else(|e| {
+            tracing::warn!("Failed to use a pooled worktree: {}", e);
+            false
+        })
+    } else {
+        false
+    };
+    let result = match base {
+        _ if claimed => Ok(()),
+        Some(base) => git_wt.create_worktree_from(branch, path, base),
+        None => git_wt.create_worktree(branch, path, create_branch),
+    };
+    // Also replaces a spare that was claimed but could not be used
+    git::pool::start_fill(&git_wt.repo_path, profile);
+    Ok(result?)
+}
+
 /// Build an instance with all setup (worktree resolution, sandbox config).
 ///
 /// This does NOT start the instance or create Docker containers - that happens
@@ -201,6 +289,14 @@ pub fn build_instance(
         Config::default()
     });
 
+    // Checked before any worktree exists that would need cleaning up
+    let proxy = super::repo_config::resolve_config_with_repo(profile, Path::new(&params.path))
+        .map(|c| super::proxy::Proxy::from_config(&c.session))
+        .unwrap_or_else(|_| super::proxy::Proxy::from_config(&config.session));
+    if let Some(proxy) = &proxy {
+        proxy.check()?;
+    }
+
     let mut final_path = PathBuf::from(&params.path)
         .canonicalize()
         .map(|p| p.to_string_lossy().to_string())
@@ -212,12 +308,21 @@ pub fn build_instance(
     let mut created_workspace_worktrees: Vec<CreatedWorktree> = Vec::new();
 
     if let Some(branch) = &params.worktree_branch {
-        if !params.extra_repo_paths.is_empty() {
+        // Repos picked for this session replace the configured ones
+        let extra_paths: Vec<PathBuf> = if params.extra_repo_paths.is_empty() {
+            let repos =
+                super::repo_config::resolve_config_with_repo(profile, Path::new(&params.path))
+                    .map(|c| c.worktree.repos)
+                    .unwrap_or_else(|_| config.worktree.repos.clone());
+            configured_workspace_repos(Path::new(&params.path), &repos)?
+        } else {
+            params.extra_repo_paths.iter().map(PathBuf::from).collect()
+        };
+
+        if !extra_paths.is_empty() {
             let primary_path = PathBuf::from(&params.path)
                 .canonicalize()
                 .unwrap_or_else(|_| PathBuf::from(&params.path));
-            let extra_paths: Vec<PathBuf> =
-                params.extra_repo_paths.iter().map(PathBuf::from).collect();
 
             let ws_result = create_workspace(
                 &primary_path,
@@ -240,7 +345,12 @@ pub fn build_instance(
             let main_repo_path = main_repo_path_raw
                 .canonicalize()
                 .unwrap_or(main_repo_path_raw);
-            let git_wt = GitWorktree::new(main_repo_path.clone())?;
+            let repo_worktree = super::repo_config::resolve_config_with_repo(profile, &path)
+                .map(|c| c.worktree)
+                .unwrap_or_else(|_| config.worktree.clone());
+            let git_wt = GitWorktree::new(main_repo_path.clone())?
+                .with_sparse_paths(repo_worktree.sparse_paths);
+            let pool_size = repo_worktree.pool_size;
 
             // Choose appropriate template based on repo type (bare vs regular)
             // Use main_repo_path (not path) to correctly detect bare repos when running from a worktree
@@ -263,12 +373,21 @@ pub fn build_instance(
                         main_repo_path: main_repo_path.to_string_lossy().to_string(),
                         managed_by_aoe: false,
                         created_at: Utc::now(),
+                        base_commit: None,
                     });
                 } else {
                     let session_id = uuid::Uuid::new_v4().to_string();
                     let worktree_path = git_wt.compute_path(branch, template, &session_id[..8])?;
 
-                    git_wt.create_worktree(branch, &worktree_path, false)?;
+                    create_session_worktree(
+                        &git_wt,
+                        &worktree_path,
+                        branch,
+                        false,
+                        None,
+                        pool_size,
+                        profile,
+                    )?;
 
                     final_path = worktree_path.to_string_lossy().to_string();
                     created_worktree = Some(CreatedWorktree {
@@ -280,6 +399,7 @@ pub fn build_instance(
                         main_repo_path: main_repo_path.to_string_lossy().to_string(),
                         managed_by_aoe: true,
                         created_at: Utc::now(),
+                        base_commit: GitWorktree::head_commit(Path::new(&final_path)).ok(),
                     });
                 }
             } else {
@@ -290,7 +410,15 @@ pub fn build_instance(
                     bail!("Worktree already exists at {}", worktree_path.display());
                 }
 
-                git_wt.create_worktree(branch, &worktree_path, true)?;
+                create_session_worktree(
+                    &git_wt,
+                    &worktree_path,
+                    branch,
+                    true,
+                    params.base_ref.as_deref(),
+                    pool_size,
+                    profile,
+                )?;
 
                 final_path = worktree_path.to_string_lossy().to_string();
                 created_worktree = Some(CreatedWorktree {
@@ -302,6 +430,7 @@ pub fn build_instance(
                     main_repo_path: main_repo_path.to_string_lossy().to_string(),
                     managed_by_aoe: true,
                     created_at: Utc::now(),
+                    base_commit: GitWorktree::head_commit(Path::new(&final_path)).ok(),
                 });
             }
         }
@@ -334,6 +463,32 @@ pub fn build_instance(
     instance.worktree_info = worktree_info;
     instance.workspace_info = workspace_info;
     instance.yolo_mode = params.yolo_mode;
+    instance.issue_info = params.issue_info;
+    instance.original_prompt = params.initial_prompt.clone();
+    instance.initial_prompt = params.initial_prompt;
+    let repo_config =
+        super::repo_config::resolve_config_with_repo(profile, std::path::Path::new(&final_path))
+            .unwrap_or_else(|_| config.clone());
+    instance.host_sandbox = super::host_sandbox::HostSandbox::from_config(&repo_config.sandbox);
+    instance.resource_limits =
+        super::resource_limits::ResourceLimits::from_config(&repo_config.session);
+    instance.proxy = proxy;
+    instance.locks = super::locks::normalize(repo_config.session.locks);
+    let branch = instance
+        .worktree_info
+        .as_ref()
+        .map(|wt| wt.branch.as_str())
+        .or(instance
+            .workspace_info
+            .as_ref()
+            .map(|ws| ws.branch.as_str()));
+    if let Some(branch) = branch {
+        let labels = super::branch_labels::parse(branch, &repo_config.worktree);
+        instance.labels = labels.tags;
+        instance.ticket = labels.ticket;
+    }
+    instance.fallback_tool = repo_config.session.fallback_tool;
+    instance.icon = repo_config.session.icon.filter(|icon| !icon.is_empty());
 
     // Apply agent_command_override and agent_extra_args from resolved config.
     // Per-session values from params take priority over config.
@@ -368,6 +523,9 @@ pub fn build_instance(
         });
     }
 
+    timeline::record_created(&instance);
+    journal::record_created(profile, &instance);
+
     Ok(BuildResult {
         instance,
         created_worktree,
@@ -386,6 +544,8 @@ pub fn cleanup_instance(
     created_worktree: Option<&CreatedWorktree>,
     created_workspace_worktrees: &[CreatedWorktree],
 ) {
+    timeline::remove(&instance.id);
+
     if let Some(wt) = created_worktree {
         if let Ok(git_wt) = GitWorktree::new(wt.main_repo_path.clone()) {
             if let Err(e) = git_wt.remove_worktree(&wt.path, false) {
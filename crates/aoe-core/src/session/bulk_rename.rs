@@ -0,0 +1,203 @@
+//! Renaming many sessions at once
+//!
+//! `aoe rename --match 'old-*' --replace 'new-*'` and the TUI's bulk rename
+//! dialog rename every session whose title matches a simple pattern: `*`
+//! matches any run of characters and `?` a single one, and each wildcard in
+//! the replacement is filled with what the wildcard in the same position
+//! matched. The whole batch is checked before anything changes, and if a
+//! tmux session cannot be renamed the ones already renamed are put back, so
+//! either every session gets its new name or none does.
+
+use anyhow::{bail, Result};
+use regex::Regex;
+
+use super::Instance;
+
+/// A `--match` pattern and its `--replace` template
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    regex: Regex,
+    replace: String,
+}
+
+fn wildcards(pattern: &str) -> usize {
+    pattern.chars().filter(|c| matches!(c, '*' | '?')).count()
+}
+
+impl Pattern {
+    pub fn new(pattern: &str, replace: &str) -> Result<Self, String> {
+        if pattern.is_empty() {
+            return Err("The match pattern is empty".to_string());
+        }
+        if wildcards(replace) > wildcards(pattern) {
+            return Err(format!(
+                "The replacement has more wildcards than the pattern ({} > {})",
+                wildcards(replace),
+                wildcards(pattern)
+            ));
+        }
+        let mut source = String::from("^");
+        for c in pattern.chars() {
+            match c {
+                '*' => source.push_str("(.*)"),
+                '?' => source.push_str("(.)"),
+                c => source.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        source.push('$');
+        let regex = Regex::new(&source).map_err(|e| e.to_string())?;
+        Ok(Self {
+            regex,
+            replace: replace.to_string(),
+        })
+    }
+
+    /// `title` renamed, or `None` if the pattern doesn't match it.
+    pub fn apply(&self, title: &str) -> Option<String> {
+        let captures = self.regex.captures(title)?;
+        let mut groups = captures.iter().skip(1).flatten();
+        let renamed: String = self
+            .replace
+            .chars()
+            .map(|c| match c {
+                '*' | '?' => groups.next().map(|m| m.as_str()).unwrap_or("").to_string(),
+                c => c.to_string(),
+            })
+            .collect();
+        Some(renamed.trim().to_string())
+    }
+}
+
+/// One session's rename
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rename {
+    pub id: String,
+    pub profile: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// The renames `pattern` makes to `instances`, in list order. Sessions whose
+/// title would not change are left out. Fails if a title would become empty
+/// or two sessions in one profile would end up with the same title.
+pub fn plan(pattern: &Pattern, instances: &[Instance]) -> Result<Vec<Rename>, String> {
+    let renames: Vec<Rename> = instances
+        .iter()
+        .filter_map(|inst| {
+            let to = pattern.apply(&inst.title)?;
+            (to != inst.title).then(|| Rename {
+                id: inst.id.clone(),
+                profile: inst.source_profile.clone(),
+                from: inst.title.clone(),
+                to,
+            })
+        })
+        .collect();
+
+    for (i, rename) in renames.iter().enumerate() {
+        if rename.to.is_empty() {
+            return Err(format!("'{}' would be renamed to nothing", rename.from));
+        }
+        if let Some(other) = renames[..i]
+            .iter()
+            .find(|r| r.profile == rename.profile && r.to == rename.to)
+        {
+            return Err(format!(
+                "'{}' and '{}' would both be renamed to '{}'",
+                other.from, rename.from, rename.to
+            ));
+        }
+        let taken = instances.iter().find(|inst| {
+            inst.source_profile == rename.profile
+                && inst.title == rename.to
+                && !renames.iter().any(|r| r.id == inst.id)
+        });
+        if let Some(existing) = taken {
+            return Err(format!(
+                "'{}' would take the title of session '{}'",
+                rename.from, existing.title
+            ));
+        }
+    }
+    Ok(renames)
+}
+
+/// Rename the tmux sessions of every planned session, then their titles in
+/// `instances`. If a tmux rename fails, those already done are undone and
+/// `instances` is left unchanged. The caller saves `instances`.
+pub fn apply(instances: &mut [Instance], renames: &[Rename]) -> Result<()> {
+    let mut done: Vec<(String, String)> = Vec::new();
+    let mut failure = None;
+    'renames: for rename in renames {
+        let names = crate::tmux::session_names(&rename.id, &rename.from)
+            .into_iter()
+            .zip(crate::tmux::session_names(&rename.id, &rename.to));
+        for (from, to) in names {
+            if from == to {
+                continue;
+            }
+            match crate::tmux::rename_session(&from, &to) {
+                Ok(true) => done.push((from, to)),
+                Ok(false) => {}
+                Err(e) => {
+                    failure = Some(e);
+                    break 'renames;
+                }
+            }
+        }
+    }
+    if let Some(e) = failure {
+        for (from, to) in done.iter().rev() {
+            if let Err(undo) = crate::tmux::rename_session(to, from) {
+                tracing::warn!("Failed to restore tmux session {}: {}", from, undo);
+            }
+        }
+        crate::tmux::refresh_session_cache();
+        bail!("{}; no sessions were renamed", e);
+    }
+    if !done.is_empty() {
+        crate::tmux::refresh_session_cache();
+    }
+
+    for rename in renames {
+        if let Some(inst) = instances.iter_mut().find(|i| i.id == rename.id) {
+            inst.title = rename.to.clone();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(title: &str) -> Instance {
+        Instance::new(title, "/tmp/project")
+    }
+
+    #[test]
+    fn test_plan_renames_matching_titles() {
+        let pattern = Pattern::new("old-*", "new-*").unwrap();
+        assert_eq!(pattern.apply("old-api").as_deref(), Some("new-api"));
+        assert_eq!(pattern.apply("bold-api"), None);
+        let versioned = Pattern::new("*-v?", "*_v?").unwrap();
+        assert_eq!(versioned.apply("api-v2").as_deref(), Some("api_v2"));
+        assert!(Pattern::new("old", "new-*").is_err());
+
+        let instances = vec![session("old-api"), session("web"), session("old-db")];
+        let renames = plan(&pattern, &instances).unwrap();
+        let titles: Vec<(&str, &str)> = renames
+            .iter()
+            .map(|r| (r.from.as_str(), r.to.as_str()))
+            .collect();
+        assert_eq!(titles, [("old-api", "new-api"), ("old-db", "new-db")]);
+
+        let taken = vec![session("old-api"), session("new-api")];
+        assert!(plan(&pattern, &taken).unwrap_err().contains("would take"));
+        let merged = vec![session("api-1"), session("api-2")];
+        let collapse = Pattern::new("api-?", "api").unwrap();
+        assert!(plan(&collapse, &merged)
+            .unwrap_err()
+            .contains("would both be renamed"));
+    }
+}
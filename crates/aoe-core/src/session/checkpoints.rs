@@ -0,0 +1,333 @@
+//! Named checkpoints within a session
+//!
+//! A checkpoint marks a milestone in a long session: the commit checked out
+//! in the session's directory, a snapshot of its uncommitted changes, how far
+//! the agent's output had got, and an optional note. Snapshots are commits
+//! built from a scratch index, so making one leaves the branch, index, and
+//! stash alone, and they are pinned under `refs/aoe/checkpoints/` so
+//! `git gc` keeps them. Rolling back restores the commit and the snapshot,
+//! after saving the current state as another checkpoint so the rollback can
+//! itself be undone.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::timeline::{self, EventKind};
+use super::Instance;
+use crate::git::GitWorktree;
+
+const REF_PREFIX: &str = "refs/aoe/checkpoints";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub name: String,
+    pub at: DateTime<Utc>,
+    /// HEAD when the checkpoint was made; unset outside a git repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Commit holding the uncommitted changes, untracked files included;
+    /// unset when the tree was clean
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<String>,
+    /// Lines of agent output, scrollback included, at the checkpoint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript_line: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl Checkpoint {
+    /// Short description of the git state, e.g. "a1b2c3d + changes".
+    pub fn git_label(&self) -> String {
+        match &self.commit {
+            Some(commit) => {
+                let short: String = commit.chars().take(7).collect();
+                if self.snapshot.is_some() {
+                    format!("{} + changes", short)
+                } else {
+                    short
+                }
+            }
+            None => "no git state".to_string(),
+        }
+    }
+}
+
+fn git(dir: &Path, args: &[&str], index: Option<&Path>) -> Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.arg("-C").arg(dir).args(args);
+    if let Some(index) = index {
+        cmd.env("GIT_INDEX_FILE", index);
+    }
+    let output = cmd.output().context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Commit the working tree of `dir` as a child of HEAD without touching the
+/// real index. Returns None if nothing differs from HEAD.
+fn snapshot(dir: &Path, name: &str) -> Result<Option<String>> {
+    let index = std::env::temp_dir().join(format!("aoe-checkpoint-{}.index", uuid::Uuid::new_v4()));
+    let tree = git(dir, &["read-tree", "HEAD"], Some(&index))
+        .and_then(|_| git(dir, &["add", "-A"], Some(&index)))
+        .and_then(|_| git(dir, &["write-tree"], Some(&index)));
+    let _ = std::fs::remove_file(&index);
+    let tree = tree?;
+
+    if tree == git(dir, &["rev-parse", "HEAD^{tree}"], None)? {
+        return Ok(None);
+    }
+    let message = format!("aoe checkpoint: {}", name);
+    let commit = git(
+        dir,
+        &[
+            "-c",
+            "user.name=aoe",
+            "-c",
+            "user.email=aoe@localhost",
+            "commit-tree",
+            &tree,
+            "-p",
+            "HEAD",
+            "-m",
+            &message,
+        ],
+        None,
+    )?;
+    Ok(Some(commit))
+}
+
+fn ref_name(instance: &Instance, checkpoint: &Checkpoint) -> String {
+    format!(
+        "{}/{}/{}",
+        REF_PREFIX,
+        instance.id,
+        checkpoint.at.timestamp_micros()
+    )
+}
+
+pub fn find<'a>(instance: &'a Instance, name: &str) -> Option<&'a Checkpoint> {
+    instance.checkpoints.iter().find(|c| c.name == name)
+}
+
+/// `base`, or `base (2)`, `base (3)`, ... if a checkpoint already has it.
+fn unique_name(instance: &Instance, base: &str) -> String {
+    let mut name = base.to_string();
+    let mut n = 2;
+    while find(instance, &name).is_some() {
+        name = format!("{} ({})", base, n);
+        n += 1;
+    }
+    name
+}
+
+/// Record a checkpoint of the session's current state.
+pub fn create(instance: &mut Instance, name: &str, note: Option<&str>) -> Result<Checkpoint> {
+    let name = name.trim();
+    if name.is_empty() {
+        bail!("Checkpoint name cannot be empty");
+    }
+    if find(instance, name).is_some() {
+        bail!("Session already has a checkpoint named '{}'", name);
+    }
+
+    let dir = Path::new(&instance.project_path);
+    let (commit, snapshot) = match GitWorktree::head_commit(dir) {
+        Ok(commit) => (Some(commit), snapshot(dir, name)?),
+        Err(_) => (None, None),
+    };
+    let transcript_line = crate::tmux::Session::new(&instance.id, &instance.title)
+        .ok()
+        .and_then(|s| s.output_line_count());
+
+    let checkpoint = Checkpoint {
+        name: name.to_string(),
+        at: Utc::now(),
+        commit,
+        snapshot,
+        transcript_line,
+        note: note
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(str::to_string),
+    };
+    if let Some(target) = checkpoint.snapshot.as_ref().or(checkpoint.commit.as_ref()) {
+        git(
+            dir,
+            &["update-ref", &ref_name(instance, &checkpoint), target],
+            None,
+        )?;
+    }
+
+    timeline::record(
+        &instance.id,
+        EventKind::Git,
+        format!("Checkpoint '{}' at {}", name, checkpoint.git_label()),
+    );
+    instance.checkpoints.push(checkpoint.clone());
+    Ok(checkpoint)
+}
+
+/// Restore the session's directory to checkpoint `name`. The current state
+/// is saved first as a "before rollback" checkpoint, which is returned.
+/// The agent keeps running and keeps its conversation.
+pub fn rollback(instance: &mut Instance, name: &str) -> Result<Checkpoint> {
+    let target = find(instance, name)
+        .cloned()
+        .with_context(|| format!("No checkpoint named '{}'", name))?;
+    let Some(commit) = &target.commit else {
+        bail!("Checkpoint '{}' has no git state to roll back to", name);
+    };
+
+    let saved_name = unique_name(instance, &format!("before rollback to {}", name));
+    let saved = create(instance, &saved_name, None)?;
+
+    let dir = Path::new(&instance.project_path);
+    GitWorktree::reset_to(dir, commit)?;
+    if let Some(snapshot) = &target.snapshot {
+        git(dir, &["read-tree", "-u", "--reset", snapshot], None)?;
+        git(dir, &["reset", "-q"], None)?;
+    }
+
+    timeline::record(
+        &instance.id,
+        EventKind::Git,
+        format!("Rolled back to checkpoint '{}'", name),
+    );
+    Ok(saved)
+}
+
+/// Forget checkpoint `name` and release its snapshot.
+pub fn remove(instance: &mut Instance, name: &str) -> Result<()> {
+    let Some(pos) = instance.checkpoints.iter().position(|c| c.name == name) else {
+        bail!("No checkpoint named '{}'", name);
+    };
+    let checkpoint = instance.checkpoints.remove(pos);
+    if checkpoint.commit.is_some() {
+        let dir = Path::new(&instance.project_path);
+        if let Err(e) = git(
+            dir,
+            &["update-ref", "-d", &ref_name(instance, &checkpoint)],
+            None,
+        ) {
+            tracing::warn!("Failed to delete checkpoint ref: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// The agent's output since the checkpoint, or None if the session is not
+/// running or the checkpoint has no transcript position.
+pub fn transcript_since(instance: &Instance, checkpoint: &Checkpoint) -> Option<String> {
+    let line = checkpoint.transcript_line?;
+    let session = crate::tmux::Session::new(&instance.id, &instance.title).ok()?;
+    if !session.exists() {
+        return None;
+    }
+    session.capture_from(line).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// A repository with one commit, plus a temporary home for the timeline.
+    fn setup_repo() -> (TempDir, TempDir) {
+        let home = TempDir::new().unwrap();
+        std::env::set_var("HOME", home.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", home.path().join(".config"));
+
+        let dir = TempDir::new().unwrap();
+        let run = |args: &[&str]| git(dir.path(), args, None).unwrap();
+        run(&["init", "-q"]);
+        fs::write(dir.path().join("a.txt"), "one").unwrap();
+        run(&["add", "a.txt"]);
+        run(&[
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-qm",
+            "init",
+        ]);
+        (home, dir)
+    }
+
+    #[test]
+    #[serial]
+    fn test_create_and_rollback_restores_changes() {
+        let (_home, dir) = setup_repo();
+        let path = dir.path();
+        let mut inst = Instance::new("cp", path.to_str().unwrap());
+
+        fs::write(path.join("a.txt"), "two").unwrap();
+        fs::write(path.join("new.txt"), "untracked").unwrap();
+        let cp = create(&mut inst, "halfway", Some("tests pass")).unwrap();
+        assert!(cp.snapshot.is_some());
+        assert_eq!(cp.note.as_deref(), Some("tests pass"));
+        // The real index is untouched
+        assert!(git(path, &["diff", "--cached", "--name-only"], None)
+            .unwrap()
+            .is_empty());
+        assert!(create(&mut inst, "halfway", None).is_err());
+
+        fs::write(path.join("a.txt"), "three").unwrap();
+        fs::remove_file(path.join("new.txt")).unwrap();
+        fs::write(path.join("later.txt"), "x").unwrap();
+
+        let saved = rollback(&mut inst, "halfway").unwrap();
+        assert_eq!(saved.name, "before rollback to halfway");
+        assert_eq!(fs::read_to_string(path.join("a.txt")).unwrap(), "two");
+        assert_eq!(
+            fs::read_to_string(path.join("new.txt")).unwrap(),
+            "untracked"
+        );
+        assert!(!path.join("later.txt").exists());
+        assert_eq!(
+            git(path, &["rev-parse", "HEAD"], None).unwrap(),
+            cp.commit.clone().unwrap()
+        );
+
+        // The safety checkpoint undoes the rollback
+        rollback(&mut inst, "before rollback to halfway").unwrap();
+        assert_eq!(fs::read_to_string(path.join("a.txt")).unwrap(), "three");
+        assert!(path.join("later.txt").exists());
+        assert!(!path.join("new.txt").exists());
+
+        let saved = rollback(&mut inst, "halfway").unwrap();
+        assert_eq!(saved.name, "before rollback to halfway (2)");
+        assert_eq!(inst.checkpoints.len(), 4);
+    }
+
+    #[test]
+    #[serial]
+    fn test_clean_tree_and_remove() {
+        let (_home, dir) = setup_repo();
+        let mut inst = Instance::new("cp", dir.path().to_str().unwrap());
+        let cp = create(&mut inst, "start", None).unwrap();
+        assert_eq!(cp.snapshot, None);
+        assert_eq!(cp.git_label().len(), 7);
+
+        let refs = || git(dir.path(), &["for-each-ref", REF_PREFIX], None).unwrap();
+        assert!(!refs().is_empty());
+        remove(&mut inst, "start").unwrap();
+        assert!(inst.checkpoints.is_empty());
+        assert!(refs().is_empty());
+        assert!(remove(&mut inst, "start").is_err());
+    }
+}
@@ -0,0 +1,228 @@
+//! Claude Code conversations started outside aoe
+//!
+//! Claude Code keeps one transcript per conversation in
+//! `<claude dir>/projects/<encoded cwd>/<session id>.jsonl`, where the
+//! directory name is the working directory with every character other than
+//! a letter or digit replaced by `-`. Reading those lets the new session
+//! dialog offer earlier conversations in a directory for `claude --resume`.
+
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+/// Most conversations offered for one directory, newest first
+pub const LIST_LIMIT: usize = 30;
+
+/// A conversation Claude Code can resume
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaudeConversation {
+    /// Session id passed to `claude --resume`
+    pub id: String,
+    /// Claude's summary of the conversation, or its first prompt
+    pub title: String,
+    pub modified: DateTime<Utc>,
+    /// Prompts the user sent
+    pub prompts: usize,
+}
+
+/// Claude Code's config directory: `claude.config_dir` from the aoe
+/// config, then `CLAUDE_CONFIG_DIR`, then `~/.claude`.
+pub fn claude_dir() -> Option<PathBuf> {
+    super::config::get_claude_config_dir()
+        .or_else(|| {
+            std::env::var_os("CLAUDE_CONFIG_DIR")
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+        })
+        .or_else(|| dirs::home_dir().map(|home| home.join(".claude")))
+}
+
+/// Claude Code's name for the transcript directory of `project_path`
+pub fn encode_project_path(project_path: &Path) -> String {
+    project_path
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+fn transcripts_dir(claude_dir: &Path, project_path: &Path) -> PathBuf {
+    let path = project_path
+        .canonicalize()
+        .unwrap_or_else(|_| project_path.to_path_buf());
+    claude_dir.join("projects").join(encode_project_path(&path))
+}
+
+fn transcripts(claude_dir: &Path, project_path: &Path) -> Vec<(PathBuf, DateTime<Utc>)> {
+    let Ok(entries) = fs::read_dir(transcripts_dir(claude_dir, project_path)) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(PathBuf, DateTime<Utc>)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified.into()))
+        })
+        .collect();
+    files.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+    files
+}
+
+/// Number of conversations Claude Code has on record for `project_path`.
+/// Only lists the directory, so it is cheap enough to call as the path is
+/// typed.
+pub fn count(project_path: &Path) -> usize {
+    claude_dir().map_or(0, |dir| transcripts(&dir, project_path).len())
+}
+
+/// Conversations for `project_path`, newest first, at most [`LIST_LIMIT`].
+pub fn list(project_path: &Path) -> Vec<ClaudeConversation> {
+    claude_dir().map_or_else(Vec::new, |dir| list_in(&dir, project_path))
+}
+
+pub fn list_in(claude_dir: &Path, project_path: &Path) -> Vec<ClaudeConversation> {
+    transcripts(claude_dir, project_path)
+        .into_iter()
+        .filter_map(|(path, modified)| parse_transcript(&path, modified))
+        .take(LIST_LIMIT)
+        .collect()
+}
+
+/// Read a transcript. Conversations without a prompt from the user (such
+/// as ones that were opened and closed) are skipped.
+fn parse_transcript(path: &Path, modified: DateTime<Utc>) -> Option<ClaudeConversation> {
+    let id = path.file_stem()?.to_string_lossy().to_string();
+    let reader = BufReader::new(File::open(path).ok()?);
+    let mut summary = None;
+    let mut first_prompt = None;
+    let mut prompts = 0;
+    for line in reader.lines().map_while(Result::ok) {
+        // Skip assistant and tool lines without parsing them
+        if !line.contains("\"type\":\"user\"") && !line.contains("\"type\":\"summary\"") {
+            continue;
+        }
+        let Ok(entry) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        match entry.get("type").and_then(Value::as_str) {
+            Some("summary") => {
+                if let Some(text) = entry.get("summary").and_then(Value::as_str) {
+                    summary = Some(text.trim().to_string());
+                }
+            }
+            Some("user") => {
+                if let Some(text) = prompt_text(&entry) {
+                    prompts += 1;
+                    first_prompt.get_or_insert(text);
+                }
+            }
+            _ => {}
+        }
+    }
+    let title = summary
+        .filter(|s| !s.is_empty())
+        .or(first_prompt)?
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    Some(ClaudeConversation {
+        id,
+        title,
+        modified,
+        prompts,
+    })
+}
+
+/// Text the user typed, skipping tool results and the messages Claude Code
+/// adds itself (slash command echoes, caveats).
+fn prompt_text(entry: &Value) -> Option<String> {
+    if entry.get("isMeta").and_then(Value::as_bool) == Some(true) {
+        return None;
+    }
+    let content = entry.get("message")?.get("content")?;
+    let text = match content {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter(|part| part.get("type").and_then(Value::as_str) == Some("text"))
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => return None,
+    };
+    let text = text.trim();
+    if text.is_empty() || text.starts_with('<') || text.starts_with("Caveat:") {
+        return None;
+    }
+    Some(text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_project_path() {
+        assert_eq!(
+            encode_project_path(Path::new("/Users/me/src/my.app")),
+            "-Users-me-src-my-app"
+        );
+    }
+
+    #[test]
+    fn test_list_in_reads_titles_and_skips_empty() {
+        let claude = tempfile::tempdir().unwrap();
+        let project = tempfile::tempdir().unwrap();
+        let dir = transcripts_dir(claude.path(), project.path());
+        fs::create_dir_all(&dir).unwrap();
+
+        let user = |content: &str| {
+            format!(
+                r#"{{"type":"user","message":{{"role":"user","content":{}}}}}"#,
+                content
+            )
+        };
+        fs::write(
+            dir.join("aaa.jsonl"),
+            [
+                user(r#""<command-name>/clear</command-name>""#),
+                user(r#""Fix the flaky login test\nIt fails on CI""#),
+                r#"{"type":"assistant","message":{"role":"assistant","content":"ok"}}"#.to_string(),
+                user(r#"[{"type":"tool_result","content":"done"}]"#),
+                user(r#"[{"type":"text","text":"Also update the docs"}]"#),
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::write(
+            dir.join("bbb.jsonl"),
+            [
+                r#"{"type":"summary","summary":"Parser rewrite","leafUuid":"x"}"#.to_string(),
+                user(r#""Rewrite the parser""#),
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        fs::write(dir.join("ccc.jsonl"), user(r#""<local-command-stdout>""#)).unwrap();
+
+        let conversations = list_in(claude.path(), project.path());
+        let summary: Vec<(&str, &str, usize)> = conversations
+            .iter()
+            .map(|c| (c.id.as_str(), c.title.as_str(), c.prompts))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("bbb", "Parser rewrite", 1),
+                ("aaa", "Fix the flaky login test", 2)
+            ]
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Named session commands (`[commands]` in `.aoe/config.toml`)
+//!
+//! A repo or a shared template can name the commands people run over and
+//! over while reviewing an agent's work:
+//!
+//! ```toml
+//! [commands]
+//! test = "cargo test"
+//! lint = "cargo clippy -- -D warnings"
+//! ```
+//!
+//! The TUI offers them as one-key actions for the selected session. A
+//! command runs in the session's companion tmux session, in the session's
+//! directory (inside the container for sandboxed sessions), and whether it
+//! passed is recorded in the session timeline.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::timeline::{self, EventKind};
+
+/// A command offered on the session's command menu
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedCommand {
+    /// Key that runs it from the menu
+    pub key: char,
+    pub name: String,
+    pub run: String,
+}
+
+/// Commands defined for the project at `project_path`, including those from
+/// the templates its repo config extends. Empty when there is no repo
+/// config or it fails to load.
+pub fn for_project(project_path: &Path) -> Vec<NamedCommand> {
+    match super::repo_config::load_repo_config(project_path) {
+        Ok(Some(config)) => assign_keys(&config.commands),
+        Ok(None) => Vec::new(),
+        Err(e) => {
+            tracing::warn!(
+                "Failed to load commands for {}: {}",
+                project_path.display(),
+                e
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// Give each command a key: the first letter or digit of its name that is
+/// still free, then 1-9. Commands with blank names or command lines, and
+/// any left over once keys run out, are dropped.
+pub fn assign_keys(commands: &BTreeMap<String, String>) -> Vec<NamedCommand> {
+    let mut taken: Vec<char> = Vec::new();
+    let mut assigned = Vec::new();
+    for (name, run) in commands {
+        let (name, run) = (name.trim(), run.trim());
+        if name.is_empty() || run.is_empty() {
+            continue;
+        }
+        let key = name
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .chain('1'..='9')
+            .find(|c| !taken.contains(c));
+        let Some(key) = key else { continue };
+        taken.push(key);
+        assigned.push(NamedCommand {
+            key,
+            name: name.to_string(),
+            run: run.to_string(),
+        });
+    }
+    assigned
+}
+
+/// One-line list of commands and their keys for the session details.
+pub fn summary(commands: &[NamedCommand]) -> Option<String> {
+    if commands.is_empty() {
+        return None;
+    }
+    Some(
+        commands
+            .iter()
+            .map(|c| format!("{} {}", c.key, c.name))
+            .collect::<Vec<_>>()
+            .join("  "),
+    )
+}
+
+/// Record a finished command. `exit_code` is `None` when the companion
+/// session was closed before the command finished.
+pub fn record_result(session_id: &str, name: &str, exit_code: Option<i32>) {
+    timeline::record(
+        session_id,
+        EventKind::Command,
+        result_message(name, exit_code),
+    );
+}
+
+pub fn result_message(name: &str, exit_code: Option<i32>) -> String {
+    match exit_code {
+        Some(0) => format!("{} passed", name),
+        Some(code) => format!("{} failed (exit code {})", name, code),
+        None => format!("{} stopped before finishing", name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assign_keys_prefers_name_letters() {
+        let commands: BTreeMap<String, String> = [
+            ("lint", "cargo clippy"),
+            ("lint-docs", "cargo doc"),
+            ("test", "cargo test"),
+            ("blank", "  "),
+        ]
+        .into_iter()
+        .map(|(n, r)| (n.to_string(), r.to_string()))
+        .collect();
+
+        let assigned = assign_keys(&commands);
+        let keys: Vec<(char, &str)> = assigned.iter().map(|c| (c.key, c.name.as_str())).collect();
+        assert_eq!(keys, vec![('l', "lint"), ('i', "lint-docs"), ('t', "test")]);
+        assert_eq!(
+            summary(&assigned).as_deref(),
+            Some("l lint  i lint-docs  t test")
+        );
+        assert_eq!(summary(&[]), None);
+    }
+
+    #[test]
+    fn test_result_message() {
+        assert_eq!(result_message("test", Some(0)), "test passed");
+        assert_eq!(result_message("lint", Some(2)), "lint failed (exit code 2)");
+        assert_eq!(
+            result_message("test", None),
+            "test stopped before finishing"
+        );
+    }
+}
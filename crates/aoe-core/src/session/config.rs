@@ -22,6 +22,9 @@ pub struct Config {
     #[serde(default)]
     pub updates: UpdatesConfig,
 
+    #[serde(default)]
+    pub polling: PollingConfig,
+
     #[serde(default)]
     pub worktree: WorktreeConfig,
 
@@ -43,6 +46,15 @@ pub struct Config {
     #[serde(default)]
     pub sound: crate::sound::SoundConfig,
 
+    #[serde(default)]
+    pub notifications: crate::notifications::NotificationConfig,
+
+    #[serde(default)]
+    pub share: ShareConfig,
+
+    #[serde(default)]
+    pub storage: StorageConfig,
+
     #[serde(default)]
     pub app_state: AppStateConfig,
 }
@@ -107,18 +119,37 @@ pub struct AppStateConfig {
     #[serde(default)]
     pub has_acknowledged_agent_hooks: bool,
 
+    #[serde(default)]
+    pub has_seen_nested_tmux_notice: bool,
+
+    /// Monday of the last week whose digest was delivered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_digest_week: Option<chrono::NaiveDate>,
+
+    /// Sort order from before it was saved per profile with the rest of the
+    /// TUI state; still used when a profile has no saved state.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sort_order: Option<SortOrder>,
 }
 
 /// Session-related configuration defaults
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionConfig {
     /// Default coding tool for new sessions (claude, opencode, vibe, codex)
     /// If not set or tool is unavailable, falls back to first available tool
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_tool: Option<String>,
 
+    /// Agent a session switches to when its own agent fails to start or
+    /// reports a hard usage limit (e.g. "codex" for a claude session)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_tool: Option<String>,
+
+    /// Icon (an emoji or nerd-font glyph) new sessions start with, shown
+    /// before their title in the list and as their tmux window name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+
     /// Enable YOLO mode by default for new sessions (skip permission prompts)
     #[serde(default)]
     pub yolo_mode_default: bool,
@@ -130,6 +161,173 @@ pub struct SessionConfig {
     /// Per-agent command override replacing the binary entirely (e.g., claude = "happy cli claude")
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub agent_command_override: HashMap<String, String>,
+
+    /// Days a removed session stays in the trash before it is purged.
+    /// 0 disables the trash and deletes sessions immediately.
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u64,
+
+    /// Maximum number of sessions kept in the trash (oldest purged first)
+    #[serde(default = "default_trash_max_entries")]
+    pub trash_max_entries: u64,
+
+    /// Named locks new sessions hold (e.g. "db-migrations"). Sessions sharing
+    /// a lock run one at a time; the others queue until it is released.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locks: Vec<String>,
+
+    /// Patterns (regular expressions, case-insensitive) that prompts are
+    /// checked against before sending, e.g. "force.push" or a production
+    /// hostname. A match asks for confirmation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prompt_guardrails: Vec<String>,
+
+    /// Niceness (0-19) host sessions run at. Higher values leave more CPU
+    /// to other programs; 0 leaves scheduling alone.
+    #[serde(default)]
+    pub nice: u8,
+
+    /// IO scheduling priority for host sessions
+    #[serde(default)]
+    pub io_priority: IoPriority,
+
+    /// Memory cap for a host session's processes (Linux, e.g. "4G")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<String>,
+
+    /// CPU cap for a host session's processes in CPUs (Linux, e.g. "2")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_limit: Option<String>,
+
+    /// Proxy the agent's network traffic goes through, e.g.
+    /// "http://proxy.corp:3128" or "socks5://127.0.0.1:1080"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+
+    /// Comma-separated hosts the agent reaches without the proxy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<String>,
+
+    /// Estimated spend allowed per day, in whole dollars (0 for no limit).
+    /// The day resets at local midnight.
+    #[serde(default)]
+    pub daily_budget: u64,
+
+    /// Estimated spend allowed per week, in whole dollars (0 for no limit).
+    /// The week resets on Monday at local midnight.
+    #[serde(default)]
+    pub weekly_budget: u64,
+
+    /// Share of a budget, in percent, at which a warning is shown
+    #[serde(default = "default_budget_warn_percent")]
+    pub budget_warn_percent: u64,
+
+    /// What happens once a budget is used up
+    #[serde(default)]
+    pub budget_action: BudgetAction,
+
+    /// When each agent's API key or token expires (e.g. claude =
+    /// "2026-03-31"), as a date (local midnight) or an RFC 3339 time
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub credential_expiry: HashMap<String, String>,
+
+    /// Days before a credential expires that the TUI starts warning
+    #[serde(default = "default_credential_warn_days")]
+    pub credential_warn_days: u64,
+
+    /// Shell command run once when a credential enters its warning window,
+    /// e.g. a script that rotates the key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_refresh_command: Option<String>,
+
+    /// Launch host agents through `aoe shim`, which reports whether the
+    /// agent is busy or has exited without reading its pane
+    #[serde(default)]
+    pub agent_shim: bool,
+
+    /// Start host agents with the project's direnv (`.envrc`) or mise
+    /// environment, when it has one
+    #[serde(default)]
+    pub project_env: bool,
+
+    /// Most sessions of each provider active at once (e.g. anthropic = 3),
+    /// to stay inside its rate limits. Providers not listed are unlimited
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub provider_limits: HashMap<String, usize>,
+}
+
+/// What happens once a budget is used up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetAction {
+    /// Only warn
+    #[default]
+    Warn,
+    /// Also refuse to create sessions and hold queued ones until the
+    /// budget window resets
+    Block,
+}
+
+/// IO scheduling priority for a session's processes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum IoPriority {
+    /// Leave IO scheduling alone
+    #[default]
+    Normal,
+    /// Lowest best-effort priority
+    Low,
+    /// Only use the disk when nothing else does
+    Idle,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            default_tool: None,
+            fallback_tool: None,
+            icon: None,
+            yolo_mode_default: false,
+            agent_extra_args: HashMap::new(),
+            agent_command_override: HashMap::new(),
+            trash_retention_days: default_trash_retention_days(),
+            trash_max_entries: default_trash_max_entries(),
+            locks: Vec::new(),
+            prompt_guardrails: Vec::new(),
+            nice: 0,
+            io_priority: IoPriority::default(),
+            memory_limit: None,
+            cpu_limit: None,
+            proxy: None,
+            no_proxy: None,
+            daily_budget: 0,
+            weekly_budget: 0,
+            budget_warn_percent: default_budget_warn_percent(),
+            budget_action: BudgetAction::default(),
+            credential_expiry: HashMap::new(),
+            credential_warn_days: default_credential_warn_days(),
+            credential_refresh_command: None,
+            agent_shim: false,
+            project_env: false,
+            provider_limits: HashMap::new(),
+        }
+    }
+}
+
+fn default_trash_retention_days() -> u64 {
+    7
+}
+
+fn default_budget_warn_percent() -> u64 {
+    80
+}
+
+fn default_credential_warn_days() -> u64 {
+    7
+}
+
+fn default_trash_max_entries() -> u64 {
+    50
 }
 
 /// Diff view configuration
@@ -158,14 +356,170 @@ fn default_context_lines() -> usize {
     3
 }
 
+/// Where `aoe share --upload` sends a bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ShareDestination {
+    /// Bundles are only written locally
+    #[default]
+    None,
+    /// A GitHub gist, created with `gh`
+    Gist,
+    /// An S3 bucket, uploaded with the `aws` CLI and shared as a presigned link
+    S3,
+}
+
+/// Where session metadata and stats history are kept (global only)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageConfig {
+    #[serde(default)]
+    pub backend: StorageBackend,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// `sessions.json` and `groups.json` per profile, `stats.jsonl` for history
+    #[default]
+    Json,
+    /// One `aoe.db` for every profile, indexed for search and stats queries.
+    /// Needs aoe built with the `sqlite` feature.
+    Sqlite,
+}
+
+/// `aoe share` configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShareConfig {
+    #[serde(default)]
+    pub destination: ShareDestination,
+
+    /// Bucket and optional key prefix for S3 uploads, e.g. "my-bucket/aoe"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub s3_bucket: Option<String>,
+
+    /// Hours a presigned S3 link stays valid (at most 168)
+    #[serde(default = "default_s3_link_hours")]
+    pub s3_link_hours: u64,
+
+    /// Create public gists instead of secret ones
+    #[serde(default)]
+    pub gist_public: bool,
+}
+
+impl Default for ShareConfig {
+    fn default() -> Self {
+        Self {
+            destination: ShareDestination::default(),
+            s3_bucket: None,
+            s3_link_hours: default_s3_link_hours(),
+            gist_public: false,
+        }
+    }
+}
+
+fn default_s3_link_hours() -> u64 {
+    168
+}
+
 fn default_profile() -> String {
     "default".to_string()
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThemeConfig {
     #[serde(default)]
     pub name: String,
+
+    /// Template for session rows in the list, e.g. "{status} {name:20} {age}".
+    /// Unset uses the built-in layout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_format: Option<String>,
+
+    /// Status palette for color vision deficiencies
+    #[serde(default)]
+    pub color_vision: ColorVision,
+
+    /// Clickable links for paths and URLs in the preview
+    #[serde(default)]
+    pub hyperlinks: HyperlinkMode,
+
+    /// Underline words missing from the dictionary in prompt editors
+    #[serde(default = "default_true")]
+    pub spellcheck: bool,
+
+    /// Terminal width below which the preview pane is hidden
+    #[serde(default = "default_preview_min_width")]
+    pub preview_min_width: u16,
+
+    /// Terminal width below which session rows leave out branch names and
+    /// tags
+    #[serde(default = "default_row_details_min_width")]
+    pub row_details_min_width: u16,
+
+    /// Terminal window and tab title while the TUI runs, e.g.
+    /// "aoe - {summary}". Empty leaves the title alone.
+    #[serde(default = "default_terminal_title")]
+    pub terminal_title: String,
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            row_format: None,
+            color_vision: ColorVision::default(),
+            hyperlinks: HyperlinkMode::default(),
+            spellcheck: true,
+            preview_min_width: default_preview_min_width(),
+            row_details_min_width: default_row_details_min_width(),
+            terminal_title: default_terminal_title(),
+        }
+    }
+}
+
+impl ThemeConfig {
+    /// `preview_min_width` and `row_details_min_width`
+    pub fn breakpoints(&self) -> (u16, u16) {
+        (self.preview_min_width, self.row_details_min_width)
+    }
+}
+
+fn default_preview_min_width() -> u16 {
+    60
+}
+
+fn default_row_details_min_width() -> u16 {
+    80
+}
+
+fn default_terminal_title() -> String {
+    "aoe - {summary}".to_string()
+}
+
+/// Whether paths and URLs in the preview are emitted as OSC 8 hyperlinks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HyperlinkMode {
+    /// Only on terminals known to support them, and not inside tmux
+    #[default]
+    Auto,
+    Enabled,
+    Disabled,
+}
+
+/// Color vision the status palette is tuned for. Statuses also keep their
+/// own glyphs, so none is told apart by color alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorVision {
+    #[default]
+    Normal,
+    /// Red-green, reduced green sensitivity (the most common)
+    Deuteranopia,
+    /// Red-green, reduced red sensitivity
+    Protanopia,
+    /// Blue-yellow
+    Tritanopia,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -187,6 +541,15 @@ pub struct UpdatesConfig {
 
     #[serde(default = "default_true")]
     pub notify_in_cli: bool,
+
+    /// Also check installed agent CLIs for newer releases
+    #[serde(default = "default_true")]
+    pub check_agents: bool,
+
+    /// Command that upgrades each agent (e.g. claude = "brew upgrade
+    /// claude-code"), replacing the built-in npm command
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub agent_upgrade_commands: HashMap<String, String>,
 }
 
 impl Default for UpdatesConfig {
@@ -196,6 +559,8 @@ impl Default for UpdatesConfig {
             auto_update: false,
             check_interval_hours: 24,
             notify_in_cli: true,
+            check_agents: true,
+            agent_upgrade_commands: HashMap::new(),
         }
     }
 }
@@ -208,6 +573,51 @@ fn default_check_interval() -> u64 {
     24
 }
 
+/// How often each data source is polled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PollingConfig {
+    /// Fastest interval between tmux pane captures for session status, in
+    /// milliseconds. Quiet sessions back off to 8 times this, and the
+    /// selected session's preview is captured twice as often.
+    #[serde(default = "default_pane_capture_ms")]
+    pub pane_capture_ms: u64,
+
+    /// Seconds between git checks for merged worktree branches
+    #[serde(default = "default_git_check_secs")]
+    pub git_check_secs: u64,
+
+    /// Seconds between CPU and cost samples in `aoe top`
+    #[serde(default = "default_resource_usage_secs")]
+    pub resource_usage_secs: u64,
+
+    /// Poll everything less often while the terminal is unfocused
+    #[serde(default)]
+    pub eco_mode: bool,
+}
+
+impl Default for PollingConfig {
+    fn default() -> Self {
+        Self {
+            pane_capture_ms: default_pane_capture_ms(),
+            git_check_secs: default_git_check_secs(),
+            resource_usage_secs: default_resource_usage_secs(),
+            eco_mode: false,
+        }
+    }
+}
+
+fn default_pane_capture_ms() -> u64 {
+    500
+}
+
+fn default_git_check_secs() -> u64 {
+    300
+}
+
+fn default_resource_usage_secs() -> u64 {
+    2
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorktreeConfig {
     #[serde(default)]
@@ -236,6 +646,81 @@ pub struct WorktreeConfig {
     /// Supports {branch} and {session-id} placeholders.
     #[serde(default = "default_workspace_template")]
     pub workspace_path_template: String,
+
+    /// Other repositories every worktree session spans, relative to the
+    /// project's repo root (e.g. "../shared" or a submodule like
+    /// "vendor/proto"). Each gets a worktree on the session branch in a
+    /// shared workspace directory.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub repos: Vec<String>,
+
+    /// Sparse-checkout patterns for new worktrees, so a session in a large
+    /// monorepo only checks out what it needs (e.g. "services/api"). Plain
+    /// directories use cone mode; any glob or `!` pattern switches to
+    /// gitignore-style patterns. Empty checks out everything.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sparse_paths: Vec<String>,
+
+    /// Command the merge queue runs in each rebased worktree before merging
+    /// it (e.g. "cargo test"). Unset means branches are merged untested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge_test_command: Option<String>,
+
+    /// Mirror each worktree session's notes to
+    /// `docs/agent-sessions/<name>.md` in its worktree.
+    #[serde(default)]
+    pub sync_notes: bool,
+
+    /// What to do when a worktree session's branch is found merged upstream
+    #[serde(default)]
+    pub on_merged: MergedSessionAction,
+
+    /// Regex pulling a tag from new sessions' branch names (the `tag` group,
+    /// or the whole match). Empty turns it off.
+    #[serde(default = "default_branch_tag_pattern")]
+    pub branch_tag_pattern: String,
+
+    /// Regex pulling a ticket ID from new sessions' branch names (the
+    /// `ticket` group, or the whole match). Empty turns it off.
+    #[serde(default = "default_branch_ticket_pattern")]
+    pub branch_ticket_pattern: String,
+
+    /// Link to a ticket, with `{ticket}` replaced by its ID
+    /// (e.g. "https://linear.app/acme/issue/{ticket}").
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub ticket_url_template: String,
+
+    /// Spare worktrees kept ready per repository, which new worktree
+    /// sessions claim instead of creating their own. 0 turns the pool off.
+    #[serde(default)]
+    pub pool_size: usize,
+
+    /// Directory that git URLs given as a new session's path are cloned into
+    #[serde(default = "default_clone_dir")]
+    pub clone_dir: String,
+
+    /// Clone only the latest commit of those repositories (`--depth 1`)
+    #[serde(default)]
+    pub shallow_clone: bool,
+
+    /// Prompt sent to a session's agent to resolve merge or rebase
+    /// conflicts in its worktree. `{operation}`, `{files}` and `{branch}`
+    /// are filled in.
+    #[serde(default = "default_conflict_prompt")]
+    pub conflict_prompt: String,
+}
+
+/// Handling of worktree sessions whose branch has been merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MergedSessionAction {
+    /// Ask before archiving the session and removing its worktree
+    #[default]
+    Prompt,
+    /// Archive the session and remove its worktree without asking
+    Archive,
+    /// Don't check for merged branches
+    Off,
 }
 
 impl Default for WorktreeConfig {
@@ -248,10 +733,30 @@ impl Default for WorktreeConfig {
             show_branch_in_tui: true,
             delete_branch_on_cleanup: false,
             workspace_path_template: default_workspace_template(),
+            repos: Vec::new(),
+            sparse_paths: Vec::new(),
+            merge_test_command: None,
+            sync_notes: false,
+            on_merged: MergedSessionAction::default(),
+            branch_tag_pattern: default_branch_tag_pattern(),
+            branch_ticket_pattern: default_branch_ticket_pattern(),
+            ticket_url_template: String::new(),
+            pool_size: 0,
+            clone_dir: default_clone_dir(),
+            shallow_clone: false,
+            conflict_prompt: default_conflict_prompt(),
         }
     }
 }
 
+fn default_conflict_prompt() -> String {
+    crate::git::conflicts::DEFAULT_PROMPT.to_string()
+}
+
+fn default_clone_dir() -> String {
+    "~/src".to_string()
+}
+
 fn default_worktree_template() -> String {
     "../{repo-name}-worktrees/{branch}".to_string()
 }
@@ -264,6 +769,14 @@ fn default_workspace_template() -> String {
     "../{branch}-workspace-{session-id}".to_string()
 }
 
+fn default_branch_tag_pattern() -> String {
+    super::branch_labels::DEFAULT_TAG_PATTERN.to_string()
+}
+
+fn default_branch_ticket_pattern() -> String {
+    super::branch_labels::DEFAULT_TICKET_PATTERN.to_string()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SandboxConfig {
     #[serde(default)]
@@ -306,9 +819,18 @@ pub struct SandboxConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub custom_instruction: Option<String>,
 
-    /// Container runtime to use for sandboxing (docker or apple_container)
+    /// Container runtime to use for sandboxing (docker, apple_container or podman)
     #[serde(default)]
     pub container_runtime: ContainerRuntimeName,
+
+    /// Restrict sessions that run on the host with bubblewrap (Linux) or
+    /// sandbox-exec (macOS). Does not apply to container sessions.
+    #[serde(default)]
+    pub host_profile: HostSandboxProfile,
+
+    /// Block network access for host-sandboxed sessions
+    #[serde(default)]
+    pub host_block_network: bool,
 }
 
 /// Container runtime options for sandboxing
@@ -318,6 +840,20 @@ pub enum ContainerRuntimeName {
     AppleContainer,
     #[default]
     Docker,
+    Podman,
+}
+
+/// How tightly a host (non-container) session is sandboxed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HostSandboxProfile {
+    /// No restrictions
+    #[default]
+    Off,
+    /// Read-only filesystem except the session directory, temp and agent config
+    Workdir,
+    /// Like workdir, and the rest of the home directory is hidden
+    Isolated,
 }
 
 impl Default for SandboxConfig {
@@ -336,6 +872,8 @@ impl Default for SandboxConfig {
             mount_ssh: false,
             custom_instruction: None,
             container_runtime: ContainerRuntimeName::default(),
+            host_profile: HostSandboxProfile::default(),
+            host_block_network: false,
         }
     }
 }
@@ -383,6 +921,32 @@ pub enum TmuxMouseMode {
     Disabled,
 }
 
+/// How the window of an aoe session is sized while clients are attached.
+/// Whatever the policy, it returns to the size it was created with once the
+/// last client detaches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TmuxWindowSize {
+    /// Follow the most recently active client (tmux's default)
+    #[default]
+    Latest,
+    /// Follow the largest attached client
+    Largest,
+    /// Keep the size the session was created with
+    Manual,
+}
+
+impl TmuxWindowSize {
+    /// The tmux `window-size` option value
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TmuxWindowSize::Latest => "latest",
+            TmuxWindowSize::Largest => "largest",
+            TmuxWindowSize::Manual => "manual",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TmuxConfig {
     #[serde(default)]
@@ -391,6 +955,10 @@ pub struct TmuxConfig {
     /// Mouse support mode (auto, enabled, disabled)
     #[serde(default)]
     pub mouse: TmuxMouseMode,
+
+    /// Window size policy for attached clients (latest, largest, manual)
+    #[serde(default)]
+    pub window_size: TmuxWindowSize,
 }
 
 impl Default for TmuxConfig {
@@ -398,6 +966,7 @@ impl Default for TmuxConfig {
         Self {
             status_bar: TmuxStatusBarMode::Auto,
             mouse: TmuxMouseMode::Auto,
+            window_size: TmuxWindowSize::Latest,
         }
     }
 }
@@ -441,6 +1010,11 @@ pub fn should_apply_tmux_mouse() -> Option<bool> {
     }
 }
 
+/// Window size policy for new aoe sessions.
+pub fn tmux_window_size() -> TmuxWindowSize {
+    Config::load().unwrap_or_default().tmux.window_size
+}
+
 fn config_path() -> Result<PathBuf> {
     Ok(get_app_dir()?.join("config.toml"))
 }
@@ -469,7 +1043,9 @@ pub fn load_config() -> Result<Option<Config>> {
 pub fn save_config(config: &Config) -> Result<()> {
     let path = config_path()?;
     let content = toml::to_string_pretty(config)?;
-    fs::write(&path, content)?;
+    let previous = fs::read_to_string(&path).ok();
+    fs::write(&path, &content)?;
+    super::audit::record_config_change("global config", previous.as_deref(), &content);
     Ok(())
 }
 
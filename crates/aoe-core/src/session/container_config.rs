@@ -159,6 +159,21 @@ const AGENT_CONFIG_MOUNTS: &[AgentConfigMount] = &[
     },
 ];
 
+/// Paths relative to home where an agent keeps its config and credentials on
+/// the host.
+pub(super) fn host_agent_paths(tool: &str) -> Vec<&'static str> {
+    let mut paths: Vec<&'static str> = AGENT_CONFIG_MOUNTS
+        .iter()
+        .filter(|m| m.tool_name == tool)
+        .map(|m| m.host_rel)
+        .collect();
+    // Claude Code keeps account and onboarding state next to ~/.claude
+    if tool == "claude" {
+        paths.push(".claude.json");
+    }
+    paths
+}
+
 /// Sync host agent config into the shared sandbox directory. Copies top-level files
 /// and `copy_dirs` from the host (always overwritten on refresh). Seed files are
 /// write-once: only created if they don't already exist, so container-accumulated
@@ -0,0 +1,180 @@
+//! Credential expiry warnings
+//!
+//! Scoped API keys and tokens often expire on a known date. Recording it in
+//! `session.credential_expiry` (`agent = date`, per profile) lets aoe warn
+//! before an agent starts failing mid-run: the TUI shows a banner counting
+//! down to the soonest expiry within `session.credential_warn_days`, and
+//! `session.credential_refresh_command` runs once when an expiry enters that
+//! window, so a script can rotate the key. Dates are `YYYY-MM-DD`, meaning
+//! the start of that day in local time, or RFC 3339 times.
+
+use std::collections::HashMap;
+use std::process::{Command, Stdio};
+
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+
+use super::config::SessionConfig;
+
+/// An agent credential inside its warning window, or already expired.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Expiring {
+    pub agent: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Expiring {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+
+    /// e.g. "claude credentials expire in 2d 4h"
+    pub fn describe(&self, now: DateTime<Utc>) -> String {
+        if self.is_expired(now) {
+            format!(
+                "{} credentials expired {} ago",
+                self.agent,
+                format_span(now - self.expires_at)
+            )
+        } else {
+            format!(
+                "{} credentials expire in {}",
+                self.agent,
+                format_span(self.expires_at - now)
+            )
+        }
+    }
+}
+
+fn format_span(span: chrono::Duration) -> String {
+    let minutes = span.num_minutes().max(1);
+    let (days, hours) = (minutes / (24 * 60), minutes / 60 % 24);
+    match (days, hours) {
+        (0, 0) => format!("{}m", minutes),
+        (0, h) => format!("{}h {}m", h, minutes % 60),
+        (d, 0) => format!("{}d", d),
+        (d, h) => format!("{}d {}h", d, h),
+    }
+}
+
+/// Parse an expiry: `YYYY-MM-DD` (start of that day, local time) or an
+/// RFC 3339 time.
+pub fn parse_expiry(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+    let midnight = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()?
+        .and_hms_opt(0, 0, 0)?;
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Check `agent=date` entries as edited in the settings.
+pub fn validate(entries: &[String]) -> Result<(), String> {
+    for entry in entries {
+        let Some((agent, date)) = entry.split_once('=') else {
+            return Err(format!("Expected agent=date, got \"{}\"", entry));
+        };
+        if agent.trim().is_empty() {
+            return Err(format!("Missing agent in \"{}\"", entry));
+        }
+        if parse_expiry(date).is_none() {
+            return Err(format!(
+                "Invalid date \"{}\" (use YYYY-MM-DD or an RFC 3339 time)",
+                date.trim()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Credentials in `expiries` (agent to date) that expire within
+/// `warn_days` of `now` or already have, soonest first. Unparseable dates
+/// are skipped.
+pub fn expiring(
+    expiries: &HashMap<String, String>,
+    warn_days: u64,
+    now: DateTime<Utc>,
+) -> Vec<Expiring> {
+    let horizon = now + chrono::Duration::days(warn_days.min(36500) as i64);
+    let mut found: Vec<Expiring> = expiries
+        .iter()
+        .filter_map(|(agent, date)| {
+            Some(Expiring {
+                agent: agent.trim().to_string(),
+                expires_at: parse_expiry(date)?,
+            })
+        })
+        .filter(|e| e.expires_at <= horizon)
+        .collect();
+    found.sort_by(|a, b| (a.expires_at, &a.agent).cmp(&(b.expires_at, &b.agent)));
+    found
+}
+
+/// Run `session.credential_refresh_command` for `expiring` in the
+/// background, with `AOE_PROFILE`, `AOE_AGENT` and `AOE_CREDENTIAL_EXPIRES`
+/// (RFC 3339) set. Does nothing if no command is configured.
+pub fn run_refresh(config: &SessionConfig, profile: &str, expiring: &Expiring) {
+    let Some(command) = config
+        .credential_refresh_command
+        .as_deref()
+        .filter(|c| !c.trim().is_empty())
+    else {
+        return;
+    };
+    let child = Command::new("sh")
+        .args(["-c", command])
+        .env("AOE_PROFILE", profile)
+        .env("AOE_AGENT", &expiring.agent)
+        .env("AOE_CREDENTIAL_EXPIRES", expiring.expires_at.to_rfc3339())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match child {
+        Ok(mut child) => {
+            let agent = expiring.agent.clone();
+            std::thread::spawn(move || match child.wait() {
+                Ok(status) if !status.success() => {
+                    tracing::warn!("Credential refresh for {} exited with {}", agent, status)
+                }
+                Err(e) => tracing::warn!("Credential refresh for {} failed: {}", agent, e),
+                Ok(_) => {}
+            });
+        }
+        Err(e) => tracing::warn!("Failed to run credential refresh command: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiring_within_window() {
+        let now = parse_expiry("2026-03-10T12:00:00Z").unwrap();
+        let expiries = HashMap::from([
+            ("claude".to_string(), "2026-03-12T18:30:00Z".to_string()),
+            ("codex".to_string(), "2026-03-09T12:00:00+00:00".to_string()),
+            ("gemini".to_string(), "2026-06-01".to_string()),
+            ("vibe".to_string(), "someday".to_string()),
+        ]);
+
+        let found = expiring(&expiries, 7, now);
+        let agents: Vec<&str> = found.iter().map(|e| e.agent.as_str()).collect();
+        assert_eq!(agents, ["codex", "claude"]);
+        assert!(found[0].is_expired(now));
+        assert_eq!(found[0].describe(now), "codex credentials expired 1d ago");
+        assert_eq!(found[1].describe(now), "claude credentials expire in 2d 6h");
+        assert!(expiring(&expiries, 0, now)
+            .iter()
+            .all(|e| e.is_expired(now)));
+
+        assert!(validate(&["claude=2026-03-12".to_string()]).is_ok());
+        assert!(validate(&["claude=tomorrow".to_string()]).is_err());
+        assert!(validate(&["2026-03-12".to_string()]).is_err());
+    }
+}
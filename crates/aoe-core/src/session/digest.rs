@@ -0,0 +1,333 @@
+//! Weekly digest of finished sessions
+//!
+//! Summarizes one week (Monday to Sunday) of the session history `aoe stats`
+//! reads: how many sessions finished, how many succeeded or were merged,
+//! what they cost, and the projects that cost the most, next to the week
+//! before. With `notifications.digest` set, the TUI delivers last week's
+//! digest on startup once per week, posted to `notifications.webhook_url` or
+//! written as a Markdown file. `aoe stats --digest --send` delivers it on
+//! demand, e.g. from cron.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::stats::{self, HistoryQuery, SessionRecord, Summary};
+use super::{get_app_dir, load_config, save_config};
+use crate::notifications::{self, NotificationConfig};
+
+/// Projects listed in a digest
+const TOP_PROJECTS: usize = 5;
+
+/// How the weekly digest is delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestDelivery {
+    /// No digest
+    #[default]
+    Off,
+    /// JSON POST to `notifications.webhook_url`
+    Webhook,
+    /// Markdown file at `notifications.digest_path`
+    File,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectSummary {
+    pub project: String,
+    #[serde(flatten)]
+    pub summary: Summary,
+}
+
+/// One week's digest, as posted to the webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct Digest {
+    /// Always "digest", to tell it apart from session notifications
+    pub event: &'static str,
+    /// Monday of the week covered
+    pub week_of: NaiveDate,
+    #[serde(flatten)]
+    pub summary: Summary,
+    pub success_rate: Option<f64>,
+    /// The week before, for comparison
+    pub previous: Summary,
+    /// Projects that cost the most, then ran the most sessions
+    pub projects: Vec<ProjectSummary>,
+    /// The digest as Markdown, as the file delivery writes it
+    pub text: String,
+}
+
+/// Monday of the last full week before `now`
+pub fn last_week(now: DateTime<Utc>) -> NaiveDate {
+    stats::week_start(now) - Duration::weeks(1)
+}
+
+/// The records `build` needs for the week starting `week_of`: that week
+/// and the one before it
+pub fn history_query(week_of: NaiveDate) -> HistoryQuery {
+    HistoryQuery {
+        since: Some(
+            (week_of - Duration::weeks(1))
+                .and_time(NaiveTime::MIN)
+                .and_utc(),
+        ),
+        ..Default::default()
+    }
+}
+
+/// Summarize the week starting `week_of` from `records`.
+pub fn build(records: &[SessionRecord], week_of: NaiveDate) -> Digest {
+    let noon = week_of
+        .and_hms_opt(12, 0, 0)
+        .expect("noon is a valid time")
+        .and_utc();
+    let mut weeks = stats::weekly(records, 2, noon);
+    let (_, summary) = weeks.pop().unwrap_or_default();
+    let (_, previous) = weeks.pop().unwrap_or_default();
+
+    let mut projects: Vec<ProjectSummary> = stats::by_project(
+        records
+            .iter()
+            .filter(|r| stats::week_start(r.ended_at) == week_of),
+    )
+    .into_iter()
+    .map(|(project, summary)| ProjectSummary { project, summary })
+    .collect();
+    projects.sort_by(|a, b| {
+        b.summary
+            .cost
+            .total_cmp(&a.summary.cost)
+            .then(b.summary.sessions.cmp(&a.summary.sessions))
+    });
+    projects.truncate(TOP_PROJECTS);
+
+    let mut digest = Digest {
+        event: "digest",
+        week_of,
+        success_rate: summary.success_rate(),
+        summary,
+        previous,
+        projects,
+        text: String::new(),
+    };
+    digest.text = render(&digest);
+    digest
+}
+
+fn render(digest: &Digest) -> String {
+    let mut text = format!(
+        "# Agent of Empires digest: week of {}\n\n",
+        digest.week_of.format("%Y-%m-%d")
+    );
+    let s = &digest.summary;
+    if s.sessions == 0 {
+        text.push_str("No sessions finished this week.\n");
+        return text;
+    }
+    text.push_str(&format!(
+        "{} sessions finished: {} succeeded ({}), {} merged, ${:.2} spent over {} of agent time.\n",
+        s.sessions,
+        s.succeeded,
+        percent(s.success_rate()),
+        s.merged,
+        s.cost,
+        hours(s.duration_secs)
+    ));
+    let p = &digest.previous;
+    if p.sessions > 0 {
+        text.push_str(&format!(
+            "The week before: {} sessions, {} succeeded, ${:.2} spent.\n",
+            p.sessions,
+            percent(p.success_rate()),
+            p.cost
+        ));
+    }
+    text.push_str("\n| Project | Sessions | Success | Merged | Spend |\n");
+    text.push_str("|---------|---------:|--------:|-------:|------:|\n");
+    for project in &digest.projects {
+        text.push_str(&format!(
+            "| {} | {} | {} | {} | ${:.2} |\n",
+            project.project,
+            project.summary.sessions,
+            percent(project.summary.success_rate()),
+            project.summary.merged,
+            project.summary.cost
+        ));
+    }
+    text
+}
+
+fn percent(rate: Option<f64>) -> String {
+    rate.map(|r| format!("{:.0}%", r * 100.0))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn hours(secs: i64) -> String {
+    format!("{}h {:02}m", secs / 3600, secs % 3600 / 60)
+}
+
+/// Where the file delivery writes the digest for `week_of`
+pub fn digest_path(config: &NotificationConfig, week_of: NaiveDate) -> Result<PathBuf> {
+    let week = week_of.format("%Y-%m-%d").to_string();
+    match config
+        .digest_path
+        .as_deref()
+        .filter(|p| !p.trim().is_empty())
+    {
+        Some(template) => {
+            let path = template.replace("{week}", &week);
+            Ok(match path.strip_prefix("~/") {
+                Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+                None => PathBuf::from(path),
+            })
+        }
+        None => Ok(get_app_dir()?.join("digests").join(format!("{}.md", week))),
+    }
+}
+
+/// Deliver `digest` as `notifications.digest` says, returning where it went.
+pub fn deliver(config: &NotificationConfig, digest: &Digest) -> Result<String> {
+    match config.digest {
+        DigestDelivery::Off => bail!(
+            "No digest delivery configured\n\
+             Tip: Set notifications.digest to \"webhook\" or \"file\" in the settings"
+        ),
+        DigestDelivery::Webhook => {
+            let Some(url) = config
+                .webhook_url
+                .as_deref()
+                .filter(|u| !u.trim().is_empty())
+            else {
+                bail!(
+                    "notifications.digest is \"webhook\" but notifications.webhook_url is not set"
+                );
+            };
+            // Off the caller's thread, which may be inside an async runtime
+            std::thread::scope(|s| {
+                s.spawn(|| notifications::post_json(url, digest))
+                    .join()
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("webhook thread panicked")))
+            })?;
+            Ok("Posted to notifications.webhook_url".to_string())
+        }
+        DigestDelivery::File => {
+            let path = digest_path(config, digest.week_of)?;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, &digest.text)?;
+            Ok(format!("Wrote {}", path.display()))
+        }
+    }
+}
+
+/// Deliver last week's digest on a background thread, unless the digest is
+/// off or that week's was already handled. Weeks in which no session
+/// finished are skipped.
+pub fn deliver_if_due(config: &NotificationConfig) {
+    if config.digest == DigestDelivery::Off {
+        return;
+    }
+    let week_of = last_week(Utc::now());
+    let mut app_config = match load_config() {
+        Ok(app_config) => app_config.unwrap_or_default(),
+        Err(e) => {
+            tracing::warn!("Failed to check the weekly digest: {}", e);
+            return;
+        }
+    };
+    if app_config
+        .app_state
+        .last_digest_week
+        .is_some_and(|sent| sent >= week_of)
+    {
+        return;
+    }
+    // Recorded first, so a second aoe starting meanwhile doesn't send it too
+    app_config.app_state.last_digest_week = Some(week_of);
+    if let Err(e) = save_config(&app_config) {
+        tracing::warn!("Failed to record the weekly digest: {}", e);
+        return;
+    }
+
+    let config = config.clone();
+    std::thread::spawn(move || {
+        let records = stats::query(&history_query(week_of)).unwrap_or_default();
+        let digest = build(&records, week_of);
+        if digest.summary.sessions == 0 {
+            return;
+        }
+        match deliver(&config, &digest) {
+            Ok(done) => tracing::info!("Weekly digest delivered: {}", done),
+            Err(e) => tracing::warn!("Failed to deliver the weekly digest: {}", e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::stats::Outcome;
+
+    fn record(project: &str, ended_at: &str, cost: f64, outcome: Outcome) -> SessionRecord {
+        SessionRecord {
+            ended_at: ended_at.parse().unwrap(),
+            session_id: "id".to_string(),
+            title: "t".to_string(),
+            project: project.to_string(),
+            tool: "claude".to_string(),
+            profile: "default".to_string(),
+            duration_secs: 1800,
+            cost: Some(cost),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_build_covers_one_week_and_compares() {
+        let records = vec![
+            record("/old", "2026-03-04T08:00:00Z", 1.0, Outcome::Completed),
+            record("/api", "2026-03-09T08:00:00Z", 2.0, Outcome::Merged),
+            record("/api", "2026-03-15T20:00:00Z", 1.0, Outcome::Failed),
+            record("/web", "2026-03-10T08:00:00Z", 4.5, Outcome::Completed),
+            record("/next", "2026-03-16T08:00:00Z", 9.0, Outcome::Completed),
+        ];
+        let week_of = last_week("2026-03-18T12:00:00Z".parse().unwrap());
+        assert_eq!(week_of, NaiveDate::from_ymd_opt(2026, 3, 9).unwrap());
+
+        let digest = build(&records, week_of);
+        assert_eq!(digest.summary.sessions, 3);
+        assert_eq!(digest.summary.merged, 1);
+        assert_eq!(digest.summary.cost, 7.5);
+        assert_eq!(digest.previous.sessions, 1);
+        let projects: Vec<&str> = digest.projects.iter().map(|p| p.project.as_str()).collect();
+        assert_eq!(projects, vec!["/web", "/api"]);
+        assert!(digest.text.contains(
+            "3 sessions finished: 2 succeeded (67%), 1 merged, $7.50 spent over 1h 30m of agent time."
+        ));
+        assert!(digest.text.contains("| /web | 1 | 100% | 0 | $4.50 |"));
+
+        let json = serde_json::to_value(&digest).unwrap();
+        assert_eq!(json["event"], "digest");
+        assert_eq!(json["week_of"], "2026-03-09");
+        assert_eq!(json["sessions"], 3);
+
+        let empty = build(&[], week_of);
+        assert!(empty.text.contains("No sessions finished this week."));
+    }
+
+    #[test]
+    fn test_digest_path_template() {
+        let week_of = NaiveDate::from_ymd_opt(2026, 3, 9).unwrap();
+        let config = NotificationConfig {
+            digest_path: Some("/tmp/aoe/{week}.md".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            digest_path(&config, week_of).unwrap(),
+            PathBuf::from("/tmp/aoe/2026-03-09.md")
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Detection of new sessions that repeat a recent session's prompt
+//!
+//! Starting two agents on the same task in the same project doubles the API
+//! spend for one result. Before a session with a prompt is created, the TUI
+//! looks for a recent session in the same repository whose first prompt is
+//! nearly identical and offers to switch to it instead.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+
+use super::Instance;
+use crate::git::GitWorktree;
+
+/// Word-level similarity at or above which two prompts count as duplicates
+pub const SIMILARITY_THRESHOLD: f32 = 0.9;
+
+/// How far back to look for a session with the same prompt
+pub const RECENT_HOURS: i64 = 24;
+
+/// A recent session whose prompt matches the one being submitted
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicatePrompt {
+    pub session_id: String,
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    /// Word-level similarity of the two prompts, from 0.0 to 1.0
+    pub similarity: f32,
+}
+
+/// Similarity of two prompts from 0.0 to 1.0, ignoring case, punctuation
+/// and whitespace.
+pub fn similarity(a: &str, b: &str) -> f32 {
+    let a = normalize(a);
+    let b = normalize(b);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    similar::TextDiff::from_words(a.as_str(), b.as_str()).ratio()
+}
+
+fn normalize(prompt: &str) -> String {
+    prompt
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The repository a path belongs to, so a worktree and its main checkout
+/// count as the same project.
+fn project_root(path: &Path) -> PathBuf {
+    let root = GitWorktree::find_main_repo(path).unwrap_or_else(|_| path.to_path_buf());
+    root.canonicalize().unwrap_or(root)
+}
+
+fn instance_root(inst: &Instance) -> PathBuf {
+    match &inst.worktree_info {
+        Some(wt) => project_root(Path::new(&wt.main_repo_path)),
+        None => project_root(Path::new(&inst.project_path)),
+    }
+}
+
+/// The most similar session created in the last [`RECENT_HOURS`] in the
+/// same project as `project_path` whose prompt matches `prompt`, if any.
+pub fn find_duplicate(
+    instances: &[Instance],
+    project_path: &str,
+    prompt: &str,
+    now: DateTime<Utc>,
+) -> Option<DuplicatePrompt> {
+    let since = now - Duration::hours(RECENT_HOURS);
+    let mut candidates: Vec<DuplicatePrompt> = instances
+        .iter()
+        .filter(|inst| inst.created_at >= since)
+        .filter_map(|inst| {
+            let previous = inst.original_prompt.as_deref()?;
+            let score = similarity(prompt, previous);
+            (score >= SIMILARITY_THRESHOLD).then(|| DuplicatePrompt {
+                session_id: inst.id.clone(),
+                title: inst.title.clone(),
+                created_at: inst.created_at,
+                similarity: score,
+            })
+        })
+        .collect();
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let root = project_root(Path::new(project_path));
+    candidates.retain(|candidate| {
+        instances
+            .iter()
+            .find(|inst| inst.id == candidate.session_id)
+            .is_some_and(|inst| instance_root(inst) == root)
+    });
+    candidates.into_iter().max_by(|a, b| {
+        a.similarity
+            .total_cmp(&b.similarity)
+            .then(a.created_at.cmp(&b.created_at))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similarity_ignores_case_and_punctuation() {
+        assert_eq!(similarity("Fix the login bug.", "fix the  login bug"), 1.0);
+        assert!(
+            similarity(
+                "Fix the login bug in auth.rs",
+                "Fix the login bug in auth.ts"
+            ) >= 0.8
+        );
+        assert!(similarity("Fix the login bug", "Add dark mode to settings") < 0.5);
+        assert_eq!(similarity("", "anything"), 0.0);
+    }
+
+    #[test]
+    fn test_find_duplicate_in_same_project_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        let path = dir.path().to_str().unwrap();
+        let now = Utc::now();
+
+        let mut same = Instance::new("first", path);
+        same.original_prompt = Some("Fix the flaky login test".to_string());
+        let mut elsewhere = Instance::new("elsewhere", other.path().to_str().unwrap());
+        elsewhere.original_prompt = same.original_prompt.clone();
+        let mut stale = Instance::new("stale", path);
+        stale.original_prompt = same.original_prompt.clone();
+        stale.created_at = now - Duration::hours(RECENT_HOURS + 1);
+
+        let instances = vec![elsewhere, stale, same.clone()];
+        let found = find_duplicate(&instances, path, "fix the flaky login test!", now).unwrap();
+        assert_eq!(found.session_id, same.id);
+
+        assert!(find_duplicate(&instances, path, "Write release notes", now).is_none());
+        assert!(find_duplicate(&instances[..2], path, "Fix the flaky login test", now).is_none());
+    }
+}
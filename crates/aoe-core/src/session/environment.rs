@@ -56,6 +56,14 @@ pub(crate) fn shell_escape(val: &str) -> String {
     format!("\"{}\"", escaped)
 }
 
+/// Single-quote a value so it reaches the program as one literal argument.
+/// Unlike `shell_escape`, newlines are preserved, which matters for multi-line
+/// prompts. The result may be nested inside `wrap_command_ignore_suspend`,
+/// which re-escapes the embedded single quotes.
+pub(crate) fn shell_single_quote(val: &str) -> String {
+    format!("'{}'", val.replace('\'', "'\\''"))
+}
+
 /// Resolve an environment value. If the value starts with `$`, read the
 /// named variable from the host environment (use `$$` to escape a literal `$`).
 /// Otherwise return the literal value.
@@ -219,6 +227,13 @@ pub(crate) fn build_docker_env_args(sandbox: &SandboxInfo) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_shell_single_quote() {
+        assert_eq!(shell_single_quote("hello"), "'hello'");
+        assert_eq!(shell_single_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_single_quote("a\nb $HOME"), "'a\nb $HOME'");
+    }
+
     #[test]
     fn test_shell_escape_simple() {
         assert_eq!(shell_escape("hello"), "\"hello\"");
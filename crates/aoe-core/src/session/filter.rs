@@ -0,0 +1,239 @@
+//! Session filter expressions
+//!
+//! A filter is a space-separated list of terms that must all match, such as
+//! `status:waiting tag:bugfix project:api`. A term is `key:value`, where the
+//! value may list alternatives (`status:waiting,error`), or a bare word
+//! matched against the title. A leading `-` negates a term.
+//!
+//! Tags are the `#words` in a session's title or notes.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Instance, Status};
+
+/// Filter keys, as typed before the colon.
+pub const FILTER_KEYS: &[&str] = &[
+    "status", "tag", "project", "group", "tool", "branch", "profile",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Status,
+    Tag,
+    Project,
+    Group,
+    Tool,
+    Branch,
+    Profile,
+    Title,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Term {
+    key: Key,
+    /// Lowercased alternatives; any one matching is enough
+    values: Vec<String>,
+    negate: bool,
+}
+
+/// A parsed filter expression.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionFilter {
+    terms: Vec<Term>,
+}
+
+/// A filter saved under a name for quick reuse.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedFilter {
+    pub name: String,
+    pub query: String,
+}
+
+impl SessionFilter {
+    /// Parse a filter expression. Unknown keys and statuses are errors, so
+    /// a typo doesn't silently hide every session.
+    pub fn parse(query: &str) -> Result<Self, String> {
+        let mut terms = Vec::new();
+        for word in query.split_whitespace() {
+            let (negate, word) = match word.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => (true, rest),
+                _ => (false, word),
+            };
+            let (key, value) = match word.split_once(':') {
+                Some((key, value)) => (parse_key(key)?, value),
+                None => (Key::Title, word),
+            };
+            let values: Vec<String> = value
+                .split(',')
+                .map(|v| v.trim().trim_start_matches('#').to_lowercase())
+                .filter(|v| !v.is_empty())
+                .collect();
+            if values.is_empty() {
+                return Err(format!("Missing value for '{}'", word));
+            }
+            if key == Key::Status {
+                if let Some(bad) = values.iter().find(|v| parse_status(v).is_none()) {
+                    return Err(format!("Unknown status '{}'", bad));
+                }
+            }
+            terms.push(Term {
+                key,
+                values,
+                negate,
+            });
+        }
+        Ok(Self { terms })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn matches(&self, inst: &Instance) -> bool {
+        self.terms
+            .iter()
+            .all(|term| term.values.iter().any(|v| term_matches(term.key, v, inst)) != term.negate)
+    }
+}
+
+fn parse_key(key: &str) -> Result<Key, String> {
+    Ok(match key.to_lowercase().as_str() {
+        "status" | "s" => Key::Status,
+        "tag" | "t" => Key::Tag,
+        "project" | "repo" => Key::Project,
+        "group" | "g" => Key::Group,
+        "tool" | "agent" => Key::Tool,
+        "branch" | "b" => Key::Branch,
+        "profile" => Key::Profile,
+        "title" => Key::Title,
+        other => {
+            return Err(format!(
+                "Unknown filter key '{}' (use {})",
+                other,
+                FILTER_KEYS.join(", ")
+            ))
+        }
+    })
+}
+
+/// Status names as shown in the UI, plus `done` for idle.
+fn parse_status(value: &str) -> Option<Status> {
+    Some(match value {
+        "running" => Status::Running,
+        "waiting" => Status::Waiting,
+        "idle" | "done" => Status::Idle,
+        "error" => Status::Error,
+        "stopped" => Status::Stopped,
+        "starting" => Status::Starting,
+        "unknown" => Status::Unknown,
+        "deleting" => Status::Deleting,
+        _ => return None,
+    })
+}
+
+fn term_matches(key: Key, value: &str, inst: &Instance) -> bool {
+    let contains = |text: &str| text.to_lowercase().contains(value);
+    match key {
+        Key::Status => parse_status(value) == Some(inst.status),
+        Key::Tag => tags(inst).iter().any(|t| t == value),
+        Key::Project => {
+            let name = |path: &str| {
+                Path::new(path)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            };
+            contains(&name(&inst.project_path))
+                || inst
+                    .worktree_info
+                    .as_ref()
+                    .is_some_and(|wt| contains(&name(&wt.main_repo_path)))
+                || inst
+                    .workspace_info
+                    .as_ref()
+                    .is_some_and(|ws| ws.repos.iter().any(|r| contains(&r.name)))
+        }
+        Key::Group => contains(&inst.group_path),
+        Key::Tool => contains(&inst.tool),
+        Key::Branch => inst
+            .worktree_info
+            .as_ref()
+            .map(|wt| &wt.branch)
+            .or(inst.workspace_info.as_ref().map(|ws| &ws.branch))
+            .is_some_and(|b| contains(b)),
+        Key::Profile => contains(&inst.source_profile),
+        Key::Title => contains(&inst.title),
+    }
+}
+
+/// The session's `#tags`, lowercased, from its title and notes, followed by
+/// the labels parsed from its branch name.
+pub fn tags(inst: &Instance) -> Vec<String> {
+    let notes = inst.notes.as_deref().unwrap_or("");
+    let mut tags: Vec<String> = Vec::new();
+    let words = inst
+        .title
+        .split_whitespace()
+        .chain(notes.split_whitespace())
+        .filter_map(|word| word.strip_prefix('#'))
+        .map(|tag| {
+            tag.trim_end_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|tag| !tag.is_empty());
+    for tag in words.chain(inst.labels.iter().cloned()) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(title: &str, status: Status) -> Instance {
+        let mut inst = Instance::new(title, "/src/api");
+        inst.status = status;
+        inst
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(SessionFilter::parse("colour:red").is_err());
+        assert!(SessionFilter::parse("status:sleeping").is_err());
+        assert!(SessionFilter::parse("status:").is_err());
+        assert!(SessionFilter::parse("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_terms_combine() {
+        let mut fix = session("Fix login #bugfix", Status::Waiting);
+        fix.group_path = "backend/auth".to_string();
+        let mut docs = session("Update docs", Status::Idle);
+        docs.notes = Some("Part of the #Docs push.".to_string());
+
+        let filter = SessionFilter::parse("status:waiting tag:bugfix project:api").unwrap();
+        assert!(filter.matches(&fix));
+        assert!(!filter.matches(&docs));
+
+        let filter = SessionFilter::parse("status:waiting,done -group:backend").unwrap();
+        assert!(!filter.matches(&fix));
+        assert!(filter.matches(&docs));
+
+        assert!(SessionFilter::parse("tag:#docs").unwrap().matches(&docs));
+        assert!(SessionFilter::parse("LOGIN").unwrap().matches(&fix));
+    }
+
+    #[test]
+    fn test_tags() {
+        let mut inst = session("#ui tweak", Status::Idle);
+        inst.notes = Some("see #perf, #UI".to_string());
+        assert_eq!(tags(&inst), vec!["ui", "perf"]);
+        inst.labels = vec!["fix".to_string(), "perf".to_string()];
+        assert_eq!(tags(&inst), vec!["ui", "perf", "fix"]);
+    }
+}
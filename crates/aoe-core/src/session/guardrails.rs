@@ -0,0 +1,133 @@
+//! Prompt guardrails
+//!
+//! `session.prompt_guardrails` lists patterns that prompts are checked
+//! against before they are sent to an agent, such as "force.push" or a
+//! production hostname. A match does not block the prompt outright: the TUI
+//! asks for confirmation and `aoe send` requires `--force`. Sending anyway is
+//! recorded on the session's timeline so overrides can be audited later.
+
+use regex::{Regex, RegexBuilder};
+
+use super::timeline;
+
+/// Compiled guardrail patterns.
+#[derive(Debug, Clone, Default)]
+pub struct Guardrails {
+    patterns: Vec<(String, Regex)>,
+}
+
+impl Guardrails {
+    /// Compile patterns case-insensitively. A pattern that is not a valid
+    /// regular expression is matched as literal text instead, so a typo
+    /// never silently disables a guardrail.
+    pub fn new(patterns: &[String]) -> Self {
+        let patterns = patterns
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                let regex = RegexBuilder::new(p)
+                    .case_insensitive(true)
+                    .build()
+                    .unwrap_or_else(|e| {
+                        tracing::warn!(
+                            "Invalid prompt guardrail {:?}, matching literally: {}",
+                            p,
+                            e
+                        );
+                        RegexBuilder::new(&regex::escape(p))
+                            .case_insensitive(true)
+                            .build()
+                            .expect("escaped pattern is valid")
+                    });
+                (p.to_string(), regex)
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    /// Guardrails for prompts sent to sessions in `project_path`, including
+    /// any patterns from the repo's `.aoe/config.toml`.
+    pub fn for_project(profile: &str, project_path: &str) -> Self {
+        match super::repo_config::resolve_config_with_repo(
+            profile,
+            std::path::Path::new(project_path),
+        ) {
+            Ok(config) => Self::new(&config.session.prompt_guardrails),
+            Err(e) => {
+                tracing::warn!("Failed to load config for prompt guardrails: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// The patterns `prompt` matches, in configured order.
+    pub fn check(&self, prompt: &str) -> Vec<String> {
+        self.patterns
+            .iter()
+            .filter(|(_, regex)| regex.is_match(prompt))
+            .map(|(pattern, _)| pattern.clone())
+            .collect()
+    }
+}
+
+/// Human-readable list of matched patterns, e.g. `"force.push", "prod-db"`.
+pub fn describe(matched: &[String]) -> String {
+    matched
+        .iter()
+        .map(|p| format!("\"{}\"", p))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Record that a prompt was sent despite matching guardrails.
+pub fn record_override(session_id: &str, matched: &[String]) {
+    timeline::record(
+        session_id,
+        timeline::EventKind::Guardrail,
+        format!("Sent prompt despite guardrail {}", describe(matched)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guardrails(patterns: &[&str]) -> Guardrails {
+        Guardrails::new(&patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_check_matches_case_insensitively() {
+        let g = guardrails(&["force.push", r"prod\.example\.com", "  "]);
+        assert_eq!(
+            g.check("Please FORCE PUSH the branch"),
+            vec!["force.push".to_string()]
+        );
+        assert_eq!(
+            g.check("deploy to prod.example.com then git force-push"),
+            vec!["force.push".to_string(), r"prod\.example\.com".to_string()]
+        );
+        assert!(g.check("run the tests").is_empty());
+    }
+
+    #[test]
+    fn test_invalid_pattern_matches_literally() {
+        let g = guardrails(&["rm -rf (/"]);
+        assert!(!g.is_empty());
+        assert_eq!(g.check("then rm -rf (/ please").len(), 1);
+        assert!(g.check("rm -rf /").is_empty());
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            describe(&["a".to_string(), "b.c".to_string()]),
+            "\"a\", \"b.c\""
+        );
+    }
+}
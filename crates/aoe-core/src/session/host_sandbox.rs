@@ -0,0 +1,298 @@
+//! Restricted sandbox for sessions that run on the host
+//!
+//! `sandbox.host_profile` wraps the agent command in bubblewrap (Linux) or
+//! sandbox-exec (macOS). The agent can read the system as usual but only
+//! write to the session directory, the temp directory, and its own config
+//! directories. The `isolated` profile also hides the rest of the home
+//! directory, and `sandbox.host_block_network` cuts off network access.
+//! Container sessions (`aoe add --sandbox`) are isolated by the container
+//! and never wrapped.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use super::config::{HostSandboxProfile, SandboxConfig};
+use super::environment::shell_escape;
+use super::Instance;
+
+/// Entries under home that stay readable in the isolated profile, so login
+/// shells and user-installed tool managers keep working.
+const ISOLATED_HOME_READ_PATHS: &[&str] = &[
+    ".profile",
+    ".bashrc",
+    ".bash_profile",
+    ".zshrc",
+    ".zprofile",
+    ".zshenv",
+    ".config/fish",
+    ".gitconfig",
+    ".local/bin",
+    ".nvm",
+    ".cargo/bin",
+    ".bun/bin",
+    ".volta",
+    ".asdf",
+];
+
+/// The host sandbox a session runs under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostSandbox {
+    pub profile: HostSandboxProfile,
+    #[serde(default)]
+    pub block_network: bool,
+}
+
+impl HostSandbox {
+    /// The sandbox new sessions get from config, or None when it is off.
+    pub fn from_config(config: &SandboxConfig) -> Option<Self> {
+        Self::new(config.host_profile, config.host_block_network)
+    }
+
+    pub fn new(profile: HostSandboxProfile, block_network: bool) -> Option<Self> {
+        (profile != HostSandboxProfile::Off).then_some(Self {
+            profile,
+            block_network,
+        })
+    }
+
+    /// Short description, e.g. "workdir" or "isolated, no network".
+    pub fn label(&self) -> String {
+        let profile = match self.profile {
+            HostSandboxProfile::Off => "off",
+            HostSandboxProfile::Workdir => "workdir",
+            HostSandboxProfile::Isolated => "isolated",
+        };
+        if self.block_network {
+            format!("{}, no network", profile)
+        } else {
+            profile.to_string()
+        }
+    }
+
+    /// Wrap a tmux session command so it runs inside the sandbox. Fails if
+    /// the platform's sandbox tool is not installed.
+    pub fn wrap(&self, inst: &Instance, cmd: &str) -> Result<String> {
+        let Some(home) = dirs::home_dir() else {
+            bail!("Cannot determine the home directory for the host sandbox");
+        };
+        let writable = writable_paths(inst, &home);
+
+        if cfg!(target_os = "linux") {
+            if !command_exists("bwrap") {
+                bail!(
+                    "Host sandbox requires bubblewrap (bwrap), which was not found in PATH. \
+                     Install it or set sandbox.host_profile = \"off\""
+                );
+            }
+            Ok(bwrap_command(
+                self,
+                &home,
+                &writable,
+                Path::new(&inst.project_path),
+                cmd,
+            ))
+        } else if cfg!(target_os = "macos") {
+            if !command_exists("sandbox-exec") {
+                bail!("Host sandbox requires sandbox-exec, which was not found in PATH");
+            }
+            Ok(sandbox_exec_command(self, &home, &writable, cmd))
+        } else {
+            bail!("Host sandbox is only supported on Linux and macOS");
+        }
+    }
+}
+
+/// Paths the sandboxed agent may write to: the session directory, the git
+/// repositories worktrees point back to, temp directories, and the agent's
+/// config and credentials.
+fn writable_paths(inst: &Instance, home: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from(&inst.project_path)];
+    if let Some(wt) = &inst.worktree_info {
+        paths.push(PathBuf::from(&wt.main_repo_path));
+    }
+    if let Some(ws) = &inst.workspace_info {
+        paths.extend(ws.repos.iter().map(|r| PathBuf::from(&r.main_repo_path)));
+    }
+    paths.push(PathBuf::from("/tmp"));
+    paths.push(std::env::temp_dir());
+    paths.extend(
+        super::container_config::host_agent_paths(&inst.tool)
+            .into_iter()
+            .map(|rel| home.join(rel)),
+    );
+
+    let mut unique: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        // Resolve symlinks (e.g. /tmp -> /private/tmp on macOS); the sandbox
+        // tools match on real paths.
+        let path = path.canonicalize().unwrap_or(path);
+        if !unique.contains(&path) {
+            unique.push(path);
+        }
+    }
+    unique
+}
+
+fn command_exists(cmd: &str) -> bool {
+    Command::new("which")
+        .arg(cmd)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+fn quote(path: &Path) -> String {
+    shell_escape(&path.to_string_lossy())
+}
+
+/// bubblewrap invocation: the root filesystem is mounted read-only and the
+/// writable paths are bound back on top of it.
+fn bwrap_command(
+    sandbox: &HostSandbox,
+    home: &Path,
+    writable: &[PathBuf],
+    workdir: &Path,
+    cmd: &str,
+) -> String {
+    let mut args = vec![
+        "bwrap".to_string(),
+        "--ro-bind / /".to_string(),
+        "--dev-bind /dev /dev".to_string(),
+    ];
+    if sandbox.profile == HostSandboxProfile::Isolated {
+        args.push(format!("--tmpfs {}", quote(home)));
+        for rel in ISOLATED_HOME_READ_PATHS {
+            let path = quote(&home.join(rel));
+            args.push(format!("--ro-bind-try {} {}", path, path));
+        }
+    }
+    for path in writable {
+        let path = quote(path);
+        args.push(format!("--bind-try {} {}", path, path));
+    }
+    if sandbox.block_network {
+        args.push("--unshare-net".to_string());
+    }
+    args.push("--die-with-parent".to_string());
+    args.push(format!("--chdir {}", quote(workdir)));
+    args.push("--".to_string());
+    args.push(cmd.to_string());
+    args.join(" ")
+}
+
+/// sandbox-exec invocation with an inline SBPL profile. Later rules take
+/// precedence, so the allow lists come after the blanket denies.
+fn sandbox_exec_command(
+    sandbox: &HostSandbox,
+    home: &Path,
+    writable: &[PathBuf],
+    cmd: &str,
+) -> String {
+    let subpath = |p: &Path| format!("(subpath \"{}\")", p.to_string_lossy());
+    let writable_rules: Vec<String> = writable.iter().map(|p| subpath(p)).collect();
+
+    let mut rules = vec![
+        "(version 1)".to_string(),
+        "(allow default)".to_string(),
+        "(deny file-write*)".to_string(),
+        format!(
+            "(allow file-write* (literal \"/dev/null\") (regex #\"^/dev/tty\") \
+             (subpath \"/private/var/folders\") {})",
+            writable_rules.join(" ")
+        ),
+    ];
+    if sandbox.profile == HostSandboxProfile::Isolated {
+        let readable: Vec<String> = ISOLATED_HOME_READ_PATHS
+            .iter()
+            .map(|rel| subpath(&home.join(rel)))
+            .chain(writable_rules.iter().cloned())
+            .collect();
+        rules.push(format!("(deny file-read* {})", subpath(home)));
+        rules.push(format!(
+            "(allow file-read* (literal \"{}\") {})",
+            home.to_string_lossy(),
+            readable.join(" ")
+        ));
+    }
+    if sandbox.block_network {
+        rules.push("(deny network-outbound (remote ip))".to_string());
+    }
+
+    format!("sandbox-exec -p {} {}", shell_escape(&rules.join(" ")), cmd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox(profile: HostSandboxProfile, block_network: bool) -> HostSandbox {
+        HostSandbox::new(profile, block_network).unwrap()
+    }
+
+    #[test]
+    fn test_from_config() {
+        let mut config = SandboxConfig::default();
+        assert_eq!(HostSandbox::from_config(&config), None);
+
+        config.host_profile = HostSandboxProfile::Isolated;
+        config.host_block_network = true;
+        let sb = HostSandbox::from_config(&config).unwrap();
+        assert_eq!(sb.label(), "isolated, no network");
+    }
+
+    #[test]
+    fn test_bwrap_workdir() {
+        let writable = vec![PathBuf::from("/work/proj"), PathBuf::from("/tmp")];
+        let cmd = bwrap_command(
+            &sandbox(HostSandboxProfile::Workdir, false),
+            Path::new("/home/me"),
+            &writable,
+            Path::new("/work/proj"),
+            "bash -lc 'claude'",
+        );
+        assert!(cmd.starts_with("bwrap --ro-bind / / --dev-bind /dev /dev "));
+        assert!(cmd.contains("--bind-try \"/work/proj\" \"/work/proj\""));
+        assert!(cmd.contains("--bind-try \"/tmp\" \"/tmp\""));
+        assert!(!cmd.contains("--tmpfs"));
+        assert!(!cmd.contains("--unshare-net"));
+        assert!(cmd.ends_with("--chdir \"/work/proj\" -- bash -lc 'claude'"));
+    }
+
+    #[test]
+    fn test_bwrap_isolated_without_network() {
+        let writable = vec![PathBuf::from("/home/me/proj")];
+        let cmd = bwrap_command(
+            &sandbox(HostSandboxProfile::Isolated, true),
+            Path::new("/home/me"),
+            &writable,
+            Path::new("/home/me/proj"),
+            "sh",
+        );
+        let tmpfs = cmd.find("--tmpfs \"/home/me\"").unwrap();
+        let bind = cmd.find("--bind-try \"/home/me/proj\"").unwrap();
+        // The project is bound after home is hidden, so it stays visible
+        assert!(tmpfs < bind);
+        assert!(cmd.contains("--ro-bind-try \"/home/me/.zshrc\" \"/home/me/.zshrc\""));
+        assert!(cmd.contains("--unshare-net"));
+    }
+
+    #[test]
+    fn test_sandbox_exec_profile() {
+        let writable = vec![PathBuf::from("/Users/me/proj")];
+        let cmd = sandbox_exec_command(
+            &sandbox(HostSandboxProfile::Workdir, true),
+            Path::new("/Users/me"),
+            &writable,
+            "zsh -lc 'codex'",
+        );
+        assert!(cmd.starts_with("sandbox-exec -p \"(version 1) (allow default) (deny file-write*)"));
+        assert!(cmd.contains("(subpath \\\"/Users/me/proj\\\")"));
+        assert!(cmd.contains("(deny network-outbound (remote ip))"));
+        assert!(!cmd.contains("deny file-read*"));
+        assert!(cmd.ends_with(" zsh -lc 'codex'"));
+    }
+}
@@ -0,0 +1,234 @@
+//! Attention inbox
+//!
+//! Every time a session starts waiting for input, fails, or finishes a turn,
+//! an item is added to `inbox.json` in the app directory. Items are kept in
+//! the order they arrived and stay pending until marked handled, so when
+//! several sessions ping at once none of them gets lost in the session list.
+//! Handled items are kept for reference and are the first to go once the
+//! inbox is full.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{get_app_dir, Status};
+
+/// Items kept in the inbox, pending or handled.
+const MAX_ITEMS: usize = 200;
+
+/// Why a session needs the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Reason {
+    Waiting,
+    Error,
+    Finished,
+}
+
+impl Reason {
+    pub fn label(self) -> &'static str {
+        match self {
+            Reason::Waiting => "waiting",
+            Reason::Error => "error",
+            Reason::Finished => "finished",
+        }
+    }
+
+    /// The reason a status change needs the user, if it does. Finishing is
+    /// a running session going idle.
+    pub fn for_transition(old: Status, new: Status) -> Option<Self> {
+        match (old, new) {
+            (_, Status::Waiting) => Some(Reason::Waiting),
+            (_, Status::Error) => Some(Reason::Error),
+            (Status::Running, Status::Idle) => Some(Reason::Finished),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InboxItem {
+    pub at: DateTime<Utc>,
+    pub session_id: String,
+    /// Title when the item arrived, shown even after the session is removed
+    pub title: String,
+    pub reason: Reason,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handled_at: Option<DateTime<Utc>>,
+}
+
+impl InboxItem {
+    pub fn new(session_id: &str, title: &str, reason: Reason, message: &str) -> Self {
+        Self {
+            at: Utc::now(),
+            session_id: session_id.to_string(),
+            title: title.to_string(),
+            reason,
+            message: super::timeline::summarize(message),
+            handled_at: None,
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.handled_at.is_none()
+    }
+}
+
+/// Attention items, oldest first
+#[derive(Debug, Default)]
+pub struct Inbox {
+    pub items: Vec<InboxItem>,
+    /// Whether `save` writes to disk; only for an inbox from `load`
+    persistent: bool,
+}
+
+fn inbox_path() -> Result<PathBuf> {
+    Ok(get_app_dir()?.join("inbox.json"))
+}
+
+impl Inbox {
+    /// Load the inbox. A missing or unreadable file gives an empty one.
+    pub fn load() -> Self {
+        let items = inbox_path()
+            .and_then(|path| Ok(fs::read_to_string(path)?))
+            .ok()
+            .and_then(|content| match serde_json::from_str(&content) {
+                Ok(items) => Some(items),
+                Err(e) => {
+                    tracing::warn!("Ignoring unreadable inbox: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        Self {
+            items,
+            persistent: true,
+        }
+    }
+
+    /// Write the inbox back. Best-effort, like the timeline.
+    pub fn save(&self) {
+        if !self.persistent {
+            return;
+        }
+        let result = inbox_path().and_then(|path| {
+            fs::write(&path, serde_json::to_string_pretty(&self.items)?)?;
+            Ok(())
+        });
+        if let Err(e) = result {
+            tracing::warn!("Failed to save inbox: {}", e);
+        }
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.items.iter().filter(|i| i.is_pending()).count()
+    }
+
+    /// Add an item. A pending item for the same session and reason is
+    /// replaced, so a session flapping between states takes one row, at the
+    /// position of its latest ping.
+    pub fn push(&mut self, item: InboxItem) {
+        self.items.retain(|i| {
+            !(i.is_pending() && i.session_id == item.session_id && i.reason == item.reason)
+        });
+        self.items.push(item);
+
+        let mut excess = self.items.len().saturating_sub(MAX_ITEMS);
+        self.items.retain(|i| {
+            let drop = excess > 0 && !i.is_pending();
+            excess -= usize::from(drop);
+            !drop
+        });
+        let excess = self.items.len().saturating_sub(MAX_ITEMS);
+        self.items.drain(..excess);
+    }
+
+    /// Replace the message of the session's pending item for `reason`, e.g.
+    /// with the agent's final message once it is known.
+    pub fn describe(&mut self, session_id: &str, reason: Reason, message: &str) -> bool {
+        let item = self
+            .items
+            .iter_mut()
+            .rev()
+            .find(|i| i.is_pending() && i.session_id == session_id && i.reason == reason);
+        match item {
+            Some(item) => {
+                item.message = super::timeline::summarize(message);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Mark the item at `index` handled, or pending again if it already is.
+    pub fn toggle_handled(&mut self, index: usize) {
+        if let Some(item) = self.items.get_mut(index) {
+            item.handled_at = match item.handled_at {
+                Some(_) => None,
+                None => Some(Utc::now()),
+            };
+        }
+    }
+
+    pub fn mark_all_handled(&mut self) {
+        let now = Utc::now();
+        for item in self.items.iter_mut().filter(|i| i.is_pending()) {
+            item.handled_at = Some(now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reason_for_transition() {
+        use Status::*;
+        assert_eq!(
+            Reason::for_transition(Running, Waiting),
+            Some(Reason::Waiting)
+        );
+        assert_eq!(Reason::for_transition(Starting, Error), Some(Reason::Error));
+        assert_eq!(
+            Reason::for_transition(Running, Idle),
+            Some(Reason::Finished)
+        );
+        assert_eq!(Reason::for_transition(Waiting, Idle), None);
+        assert_eq!(Reason::for_transition(Idle, Running), None);
+    }
+
+    #[test]
+    fn test_push_replaces_pending_and_trims_handled_first() {
+        let mut inbox = Inbox::default();
+        inbox.push(InboxItem::new("a", "A", Reason::Waiting, "first"));
+        inbox.push(InboxItem::new("b", "B", Reason::Error, "boom"));
+        inbox.push(InboxItem::new("a", "A", Reason::Waiting, "again"));
+        let order: Vec<_> = inbox.items.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(order, ["boom", "again"]);
+
+        // A handled item is history, not replaced by a new ping
+        inbox.toggle_handled(1);
+        inbox.push(InboxItem::new("a", "A", Reason::Waiting, "third"));
+        assert_eq!(inbox.items.len(), 3);
+        assert_eq!(inbox.pending_count(), 2);
+
+        inbox.mark_all_handled();
+        assert_eq!(inbox.pending_count(), 0);
+        inbox.push(InboxItem::new("c", "C", Reason::Finished, "done"));
+        for n in 1..MAX_ITEMS {
+            inbox.push(InboxItem::new(&n.to_string(), "N", Reason::Waiting, ""));
+        }
+        assert_eq!(inbox.pending_count(), MAX_ITEMS);
+        assert_eq!(inbox.items[0].session_id, "c");
+
+        // Once only pending items are left, the oldest goes
+        inbox.push(InboxItem::new("d", "D", Reason::Waiting, ""));
+        assert_eq!(inbox.items.len(), MAX_ITEMS);
+        assert_eq!(inbox.items[0].session_id, "1");
+    }
+}
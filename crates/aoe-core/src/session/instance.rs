@@ -8,10 +8,12 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::containers::{self, ContainerRuntimeInterface, DockerContainer};
+use crate::git::issue::IssueProvider;
+use crate::hooks::shim::ShimHealth;
 use crate::tmux;
 
 use super::container_config;
-use super::environment::{build_docker_env_args, shell_escape};
+use super::environment::{build_docker_env_args, shell_escape, shell_single_quote};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TerminalInfo {
@@ -35,12 +37,56 @@ pub enum Status {
     Deleting,
 }
 
+/// Column a session is shown in on the board view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BoardLane {
+    Queued,
+    Running,
+    Waiting,
+    Done,
+    Archived,
+}
+
+impl BoardLane {
+    pub const ALL: [BoardLane; 5] = [
+        BoardLane::Queued,
+        BoardLane::Running,
+        BoardLane::Waiting,
+        BoardLane::Done,
+        BoardLane::Archived,
+    ];
+
+    /// Lane a session lands in when the user has not moved it.
+    pub fn for_status(status: Status) -> Self {
+        match status {
+            Status::Starting | Status::Stopped => BoardLane::Queued,
+            Status::Running => BoardLane::Running,
+            Status::Waiting | Status::Error => BoardLane::Waiting,
+            Status::Idle | Status::Unknown | Status::Deleting => BoardLane::Done,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BoardLane::Queued => "Queued",
+            BoardLane::Running => "Running",
+            BoardLane::Waiting => "Waiting",
+            BoardLane::Done => "Done",
+            BoardLane::Archived => "Archived",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorktreeInfo {
     pub branch: String,
     pub main_repo_path: String,
     pub managed_by_aoe: bool,
     pub created_at: DateTime<Utc>,
+    /// Commit the branch started from, which retrying resets it to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_commit: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,6 +131,15 @@ pub struct SandboxInfo {
     pub custom_instruction: Option<String>,
 }
 
+/// Link between a session and the tracker issue it was created from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueInfo {
+    pub provider: IssueProvider,
+    pub url: String,
+    pub number: u64,
+    pub title: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Instance {
     pub id: String,
@@ -100,6 +155,10 @@ pub struct Instance {
     pub extra_args: String,
     #[serde(default)]
     pub tool: String,
+    /// Provider the agent talks to, when it differs from the agent's usual
+    /// one (e.g. a local model). See `provider()`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<crate::agents::Provider>,
     #[serde(default)]
     pub yolo_mode: bool,
     #[serde(default)]
@@ -124,6 +183,94 @@ pub struct Instance {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub terminal_info: Option<TerminalInfo>,
 
+    // Issue the session was created from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue_info: Option<IssueInfo>,
+
+    /// Emoji or nerd-font glyph shown before the title in the list and used
+    /// in the tmux window name (`session.icon` for new sessions).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+
+    /// Tags parsed from the session's branch name (see `session::branch_labels`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+
+    /// Ticket the session's branch name refers to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<super::branch_labels::Ticket>,
+
+    /// Prompt passed to the agent on its first launch, cleared once consumed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_prompt: Option<String>,
+
+    /// Prompt the latest attempt was launched with, kept after launch so a
+    /// retry can offer it for editing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_prompt: Option<String>,
+
+    /// Named locks this session holds while active. Sessions sharing a lock
+    /// run one at a time.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locks: Vec<String>,
+
+    /// Host sandbox the agent runs under when not in a container.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_sandbox: Option<super::host_sandbox::HostSandbox>,
+
+    /// CPU, IO, and memory limits the agent runs under when not in a container.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_limits: Option<super::resource_limits::ResourceLimits>,
+
+    /// Proxy the agent's network traffic goes through.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<super::proxy::Proxy>,
+
+    /// Free-form notes about the session's task, kept with the session and
+    /// optionally mirrored into the worktree (see `session::notes`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
+    /// Named milestones the session can be rolled back to, oldest first
+    /// (see `session::checkpoints`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checkpoints: Vec<super::checkpoints::Checkpoint>,
+
+    /// Board lane the user moved this session to. Unset means the lane
+    /// follows the session status.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board_lane: Option<BoardLane>,
+
+    /// The agent's last message, captured when it last finished work
+    /// (Running to Idle). See `output_parser::final_message`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_summary: Option<String>,
+
+    /// Agent to switch to if this one fails to start or hits a usage limit
+    /// (`session.fallback_tool`). Cleared once used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_tool: Option<String>,
+
+    /// The agent this session was created with, if it has since failed over
+    /// to its fallback.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed_over_from: Option<String>,
+
+    /// Who created the session, as `user@host` (see `session::ownership`).
+    /// Unset for sessions from before owners were recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+
+    /// For an orchestrator session (see `session::orchestrator`), the profile
+    /// whose sessions it drives.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orchestrator: Option<String>,
+
+    /// The direnv or mise environment the agent was last started with (see
+    /// `session::project_env`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_env: Option<super::project_env::ProjectEnv>,
+
     /// Runtime-only: which profile this instance was loaded from. Not persisted to disk.
     #[serde(default, skip_serializing)]
     pub source_profile: String,
@@ -135,6 +282,15 @@ pub struct Instance {
     pub last_start_time: Option<std::time::Instant>,
     #[serde(skip)]
     pub last_error: Option<String>,
+    /// The agent's output shows expired or missing credentials.
+    #[serde(skip)]
+    pub auth_required: bool,
+    /// The agent's output shows it stopped at a usage limit or quota.
+    #[serde(skip)]
+    pub quota_exceeded: bool,
+    /// The tool-permission prompt the agent is waiting on, if any.
+    #[serde(skip)]
+    pub permission_prompt: Option<tmux::permission_prompt::PermissionPrompt>,
 }
 
 impl Instance {
@@ -148,6 +304,7 @@ impl Instance {
             command: String::new(),
             extra_args: String::new(),
             tool: "claude".to_string(),
+            provider: None,
             yolo_mode: false,
             status: Status::Idle,
             created_at: Utc::now(),
@@ -156,10 +313,136 @@ impl Instance {
             workspace_info: None,
             sandbox_info: None,
             terminal_info: None,
+            issue_info: None,
+            icon: None,
+            labels: Vec::new(),
+            ticket: None,
+            initial_prompt: None,
+            original_prompt: None,
+            locks: Vec::new(),
+            host_sandbox: None,
+            resource_limits: None,
+            proxy: None,
+            notes: None,
+            checkpoints: Vec::new(),
+            board_lane: None,
+            final_summary: None,
+            fallback_tool: None,
+            failed_over_from: None,
+            owner: Some(super::ownership::current().to_string()),
+            orchestrator: None,
+            project_env: None,
             source_profile: String::new(),
             last_error_check: None,
             last_start_time: None,
             last_error: None,
+            auth_required: false,
+            quota_exceeded: false,
+            permission_prompt: None,
+        }
+    }
+
+    /// Switch to the fallback agent, returning the agent it replaces. Returns
+    /// `None` if there is no fallback or the session already failed over.
+    /// Command and extra args belong to the old agent, so they are reset.
+    pub fn fail_over(&mut self) -> Option<String> {
+        let fallback = self.fallback_tool.take()?;
+        if fallback == self.tool || self.failed_over_from.is_some() {
+            return None;
+        }
+        let from = std::mem::replace(&mut self.tool, fallback);
+        self.command = crate::agents::get_agent(&self.tool)
+            .filter(|a| a.set_default_command)
+            .map(|a| a.binary.to_string())
+            .unwrap_or_default();
+        self.extra_args.clear();
+        self.auth_required = false;
+        self.quota_exceeded = false;
+        self.permission_prompt = None;
+        self.failed_over_from = Some(from.clone());
+        Some(from)
+    }
+
+    /// Prepare another attempt at the task: kill the agent, move each
+    /// worktree aoe manages back to the commit it started from, and queue
+    /// `prompt` for the next launch. Worktrees without a recorded base
+    /// commit go back to where they forked from the default branch.
+    pub fn reset_for_retry(&mut self, prompt: Option<String>) -> Result<()> {
+        use crate::git::{diff, GitWorktree};
+
+        self.kill()?;
+        let reset = |path: &str, main_repo: &str, base: Option<&str>| -> Result<()> {
+            let path = Path::new(path);
+            let base = match base {
+                Some(base) => base.to_string(),
+                None => {
+                    let default_branch = diff::get_default_branch(Path::new(main_repo))?;
+                    GitWorktree::fork_point(path, &default_branch)?
+                }
+            };
+            GitWorktree::reset_to(path, &base)?;
+            Ok(())
+        };
+        if let Some(ws) = &self.workspace_info {
+            for repo in ws.repos.iter().filter(|r| r.managed_by_aoe) {
+                reset(&repo.worktree_path, &repo.main_repo_path, None)
+                    .map_err(|e| anyhow::anyhow!("{}: {}", repo.name, e))?;
+            }
+        } else if let Some(wt) = self.worktree_info.as_ref().filter(|wt| wt.managed_by_aoe) {
+            reset(
+                &self.project_path,
+                &wt.main_repo_path,
+                wt.base_commit.as_deref(),
+            )?;
+        }
+
+        self.initial_prompt = prompt.filter(|p| !p.trim().is_empty());
+        if self.initial_prompt.is_some() {
+            self.original_prompt = self.initial_prompt.clone();
+        }
+        self.status = Status::Idle;
+        self.last_error = None;
+        self.auth_required = false;
+        self.quota_exceeded = false;
+        self.permission_prompt = None;
+        Ok(())
+    }
+
+    /// The board lane this session is shown in.
+    pub fn lane(&self) -> BoardLane {
+        self.board_lane
+            .unwrap_or_else(|| BoardLane::for_status(self.status))
+    }
+
+    /// Move the session to `lane` on the board. Moving it back to the lane
+    /// its status implies clears the pin so it follows the status again.
+    pub fn set_lane(&mut self, lane: BoardLane) {
+        self.board_lane = if lane == BoardLane::for_status(self.status) {
+            None
+        } else {
+            Some(lane)
+        };
+    }
+
+    /// Worktrees aoe manages for this session that have uncommitted
+    /// changes, by name (the repo name in a multi-repo workspace, else the
+    /// branch).
+    pub fn dirty_worktrees(&self) -> Vec<String> {
+        let dirty = |path: &str| {
+            crate::git::GitWorktree::has_uncommitted_changes(Path::new(path)).unwrap_or(false)
+        };
+        if let Some(ws) = &self.workspace_info {
+            ws.repos
+                .iter()
+                .filter(|r| r.managed_by_aoe && dirty(&r.worktree_path))
+                .map(|r| r.name.clone())
+                .collect()
+        } else {
+            self.worktree_info
+                .iter()
+                .filter(|wt| wt.managed_by_aoe && dirty(&self.project_path))
+                .map(|wt| wt.branch.clone())
+                .collect()
         }
     }
 
@@ -191,6 +474,20 @@ impl Instance {
             .unwrap_or(true)
     }
 
+    /// First 8 characters of the ID, which is enough to name the session
+    /// on the command line and is shown where the full ID would not fit.
+    pub fn short_id(&self) -> &str {
+        super::truncate_id(&self.id, 8)
+    }
+
+    /// Provider the session's agent talks to: the one set on the session,
+    /// else the agent's usual one.
+    pub fn provider(&self) -> crate::agents::Provider {
+        self.provider
+            .or_else(|| crate::agents::get_agent(&self.tool).map(|a| a.provider))
+            .unwrap_or(crate::agents::Provider::Other)
+    }
+
     pub fn expects_shell(&self) -> bool {
         crate::tmux::utils::is_shell_command(self.get_tool_command())
     }
@@ -205,6 +502,14 @@ impl Instance {
         }
     }
 
+    /// The title with the session's icon in front, as shown in lists
+    pub fn display_title(&self) -> std::borrow::Cow<'_, str> {
+        match &self.icon {
+            Some(icon) => format!("{} {}", icon, self.title).into(),
+            None => self.title.as_str().into(),
+        }
+    }
+
     pub fn tmux_session(&self) -> Result<tmux::Session> {
         tmux::Session::new(&self.id, &self.title)
     }
@@ -213,6 +518,89 @@ impl Instance {
         tmux::TerminalSession::new(&self.id, &self.title)
     }
 
+    pub fn login_tmux_session(&self) -> Result<tmux::LoginSession> {
+        tmux::LoginSession::new(&self.id, &self.title)
+    }
+
+    /// Open a temporary tmux session running the agent's login flow (inside
+    /// the container for sandboxed sessions). The session ends when the
+    /// login command exits and the user presses Enter.
+    pub fn start_login_with_size(
+        &mut self,
+        size: Option<(u16, u16)>,
+    ) -> Result<tmux::LoginSession> {
+        let login = crate::agents::get_agent(&self.tool)
+            .and_then(|a| a.login_command)
+            .unwrap_or_else(|| self.get_tool_command())
+            .to_string();
+        let cmd = if self.is_sandboxed() {
+            let container = self.get_container_for_instance()?;
+            container.exec_command(Some(&format!("-w {}", self.container_workdir())), &login)
+        } else {
+            login
+        };
+        let cmd = format!(
+            "{}; printf '\\nPress Enter to return to Agent of Empires...'; read _",
+            cmd
+        );
+
+        let session = self.login_tmux_session()?;
+        session.kill()?;
+        session.create_with_size(&self.project_path, &cmd, size)?;
+        Ok(session)
+    }
+
+    pub fn command_tmux_session(&self) -> Result<tmux::CommandSession> {
+        tmux::CommandSession::new(&self.id, &self.title)
+    }
+
+    /// Run one of the session's named commands in its companion tmux session
+    /// (inside the container for sandboxed sessions), replacing whatever ran
+    /// there before. The pane stays open after the command exits.
+    pub fn start_command_with_size(
+        &mut self,
+        command: &str,
+        size: Option<(u16, u16)>,
+    ) -> Result<tmux::CommandSession> {
+        let cmd = self.companion_command(command)?;
+        let session = self.command_tmux_session()?;
+        session.kill()?;
+        session.create_with_size(&self.project_path, &cmd, size)?;
+        Ok(session)
+    }
+
+    pub fn watch_tmux_session(&self) -> Result<tmux::CommandSession> {
+        tmux::CommandSession::new_watch(&self.id, &self.title)
+    }
+
+    /// Start the session's test watcher in its own companion tmux session,
+    /// unless it is already there.
+    pub fn start_test_watch_with_size(
+        &mut self,
+        command: &str,
+        size: Option<(u16, u16)>,
+    ) -> Result<tmux::CommandSession> {
+        let session = self.watch_tmux_session()?;
+        if !session.exists() {
+            let cmd = self.companion_command(command)?;
+            session.create_with_size(&self.project_path, &cmd, size)?;
+        }
+        Ok(session)
+    }
+
+    /// `command` as run in a companion session: as is on the host, or
+    /// through `docker exec` in the session's directory when sandboxed
+    fn companion_command(&mut self, command: &str) -> Result<String> {
+        if !self.is_sandboxed() {
+            return Ok(command.to_string());
+        }
+        let container = self.get_container_for_instance()?;
+        Ok(container.exec_command(
+            Some(&format!("-w {}", self.container_workdir())),
+            &format!("sh -c {}", super::environment::shell_single_quote(command)),
+        ))
+    }
+
     pub fn has_terminal(&self) -> bool {
         self.terminal_info
             .as_ref()
@@ -358,6 +746,26 @@ impl Instance {
             return Ok(());
         }
 
+        // tmux would silently fall back to another directory, and a missing
+        // agent binary leaves a pane that dies on launch, so check both first.
+        if !Path::new(&self.project_path).is_dir() {
+            anyhow::bail!("Project path no longer exists: {}", self.project_path);
+        }
+        let agent = crate::agents::get_agent(&self.tool);
+        if let Some(a) = agent.filter(|a| {
+            !self.is_sandboxed()
+                && a.supports_host_launch
+                && (self.command.is_empty() || self.command == a.binary)
+        }) {
+            if !crate::tmux::is_agent_available(a) {
+                anyhow::bail!(
+                    "{} is not installed: `{}` was not found in PATH",
+                    a.name,
+                    a.binary
+                );
+            }
+        }
+
         // Resolve on_launch hooks from the full config chain (global > profile > repo).
         // Repo hooks go through trust verification; global/profile hooks are implicitly trusted.
         let on_launch_hooks = if skip_on_launch {
@@ -387,7 +795,6 @@ impl Instance {
         };
 
         // Install status-detection hooks for agents that support them
-        let agent = crate::agents::get_agent(&self.tool);
         if let Some(hook_cfg) = agent.and_then(|a| a.hook_config.as_ref()) {
             if self.is_sandboxed() {
                 // For sandboxed sessions, hooks are installed via build_container_config
@@ -402,17 +809,22 @@ impl Instance {
             }
         }
 
+        // Initial prompt (e.g. from a linked issue) is only passed on first launch
+        let prompt_arg = self.initial_prompt_arg(agent);
+
         let cmd = if self.is_sandboxed() {
             let container = self.get_container_for_instance()?;
             // Run on_launch hooks inside the container
             if let Some(ref hook_cmds) = on_launch_hooks {
                 if let Some(ref sandbox) = self.sandbox_info {
                     let workdir = self.container_workdir();
-                    if let Err(e) = super::repo_config::execute_hooks_in_container(
+                    let result = super::repo_config::execute_hooks_in_container(
                         hook_cmds,
                         &sandbox.container_name,
                         &workdir,
-                    ) {
+                    );
+                    super::timeline::record_hooks(&self.id, "on_launch", hook_cmds, &result);
+                    if let Err(e) = result {
                         tracing::warn!("on_launch hook failed in container: {}", e);
                     }
                 }
@@ -448,8 +860,14 @@ impl Instance {
                     }
                 }
             }
+            if let Some(ref arg) = prompt_arg {
+                tool_cmd = format!("{} {}", tool_cmd, arg);
+            }
 
             let mut env_args = build_docker_env_args(sandbox);
+            if let Some(proxy) = &self.proxy {
+                env_args = format!("{} {}", env_args, proxy.docker_env_args());
+            }
             // Pass AOE_INSTANCE_ID into the container
             env_args = format!("{} -e AOE_INSTANCE_ID={}", env_args, self.id);
             let env_part = format!("{} ", env_args);
@@ -459,19 +877,22 @@ impl Instance {
         } else {
             // Run on_launch hooks on host for non-sandboxed sessions
             if let Some(ref hook_cmds) = on_launch_hooks {
-                if let Err(e) =
-                    super::repo_config::execute_hooks(hook_cmds, Path::new(&self.project_path))
-                {
+                let result =
+                    super::repo_config::execute_hooks(hook_cmds, Path::new(&self.project_path));
+                super::timeline::record_hooks(&self.id, "on_launch", hook_cmds, &result);
+                if let Err(e) = result {
                     tracing::warn!("on_launch hook failed: {}", e);
                 }
             }
 
             // Prepend AOE_INSTANCE_ID env var if this agent supports hooks
-            let env_prefix = if agent.and_then(|a| a.hook_config.as_ref()).is_some() {
+            let mut env_prefix = if agent.and_then(|a| a.hook_config.as_ref()).is_some() {
                 format!("AOE_INSTANCE_ID={} ", self.id)
             } else {
                 String::new()
             };
+            env_prefix.push_str(&super::orchestrator::env_prefix(self));
+            let instruction_arg = super::orchestrator::instruction_arg(self, agent);
 
             if self.command.is_empty() {
                 crate::agents::get_agent(&self.tool)
@@ -494,6 +915,12 @@ impl Instance {
                                 }
                             }
                         }
+                        if let Some(ref arg) = instruction_arg {
+                            cmd = format!("{} {}", cmd, arg);
+                        }
+                        if let Some(ref arg) = prompt_arg {
+                            cmd = format!("{} {}", cmd, arg);
+                        }
                         wrap_command_ignore_suspend(&format!("{}{}", env_prefix, cmd))
                     })
             } else {
@@ -514,6 +941,12 @@ impl Instance {
                         }
                     }
                 }
+                if let Some(ref arg) = instruction_arg {
+                    cmd = format!("{} {}", cmd, arg);
+                }
+                if let Some(ref arg) = prompt_arg {
+                    cmd = format!("{} {}", cmd, arg);
+                }
                 Some(wrap_command_ignore_suspend(&format!(
                     "{}{}",
                     env_prefix, cmd
@@ -521,21 +954,136 @@ impl Instance {
             }
         };
 
+        let cmd = match self.proxy.as_ref().filter(|_| !self.is_sandboxed()) {
+            Some(proxy) => {
+                let inner = cmd.unwrap_or_else(super::environment::user_posix_shell);
+                Some(proxy.wrap(&inner))
+            }
+            None => cmd,
+        };
+
+        // Outside the proxy, so aoe's own settings win over the project's
+        let project_env = if self.is_sandboxed() {
+            None
+        } else {
+            self.load_project_env()
+        };
+        self.project_env = project_env.as_ref().map(|env| env.summary());
+        let cmd = match &project_env {
+            Some(env) => {
+                let inner = cmd.unwrap_or_else(super::environment::user_posix_shell);
+                Some(env.wrap(&inner))
+            }
+            None => cmd,
+        };
+
+        let cmd = match self.host_sandbox.filter(|_| !self.is_sandboxed()) {
+            Some(host_sandbox) => {
+                let inner = cmd.unwrap_or_else(super::environment::user_posix_shell);
+                Some(host_sandbox.wrap(self, &inner)?)
+            }
+            None => cmd,
+        };
+
+        let cmd = match self
+            .resource_limits
+            .as_ref()
+            .filter(|_| !self.is_sandboxed())
+        {
+            Some(limits) => {
+                let inner = cmd.unwrap_or_else(super::environment::user_posix_shell);
+                Some(limits.wrap(&inner)?)
+            }
+            None => cmd,
+        };
+
+        // Outermost, so the shim sees every process the agent starts
+        let cmd = match cmd {
+            Some(inner) if !self.is_sandboxed() && self.agent_shim_enabled() => {
+                Some(crate::hooks::shim::wrap(&self.id, &inner)?)
+            }
+            cmd => cmd,
+        };
+        // An exit left by an earlier run would read as this one's
+        crate::hooks::shim::clear_exit(&self.id);
+
         tracing::debug!("container cmd: {}", cmd.as_ref().map_or("none", |v| v));
         session.create_with_size(&self.project_path, cmd.as_deref(), size)?;
+        if let Some(env) = &project_env {
+            env.apply_to_tmux(&tmux::Session::generate_name(&self.id, &self.title));
+        }
 
         // Apply all configured tmux options (status bar, mouse, etc.)
         self.apply_tmux_options();
 
         self.status = Status::Starting;
         self.last_start_time = Some(std::time::Instant::now());
+        if prompt_arg.is_some() {
+            if let Some(prompt) = self.initial_prompt.take() {
+                super::timeline::record_prompt(self, &prompt);
+            }
+        }
+        super::audit::record_session(super::audit::Action::Started, self, &self.tool);
 
         Ok(())
     }
 
+    /// Session config from this session's profile and repo.
+    fn session_config(&self) -> Option<super::config::SessionConfig> {
+        let profile = if self.source_profile.is_empty() {
+            super::config::resolve_default_profile()
+        } else {
+            self.source_profile.clone()
+        };
+        super::repo_config::resolve_config_with_repo(&profile, Path::new(&self.project_path))
+            .ok()
+            .map(|c| c.session)
+    }
+
+    /// Whether `session.agent_shim` is on for this session's profile and repo.
+    fn agent_shim_enabled(&self) -> bool {
+        self.session_config().is_some_and(|c| c.agent_shim)
+    }
+
+    /// The project's direnv or mise environment, if `session.project_env`
+    /// is on and the project has one.
+    fn load_project_env(&self) -> Option<super::project_env::LoadedEnv> {
+        if !self.session_config().is_some_and(|c| c.project_env) {
+            return None;
+        }
+        match super::project_env::LoadedEnv::load(Path::new(&self.project_path)) {
+            Ok(env) => env,
+            Err(e) => {
+                tracing::warn!("Skipping project environment: {:#}", e);
+                None
+            }
+        }
+    }
+
+    /// Build the agent argument carrying `initial_prompt`, if the agent accepts one.
+    fn initial_prompt_arg(&self, agent: Option<&crate::agents::AgentDef>) -> Option<String> {
+        let prompt = self
+            .initial_prompt
+            .as_deref()
+            .filter(|p| !p.trim().is_empty())?;
+        let flag_template = agent.and_then(|a| a.prompt_flag)?;
+        Some(flag_template.replace("{}", &shell_single_quote(prompt)))
+    }
+
     fn apply_tmux_options(&self) {
         let name = tmux::Session::generate_name(&self.id, &self.title);
         self.apply_session_tmux_options(&name, &self.title);
+        self.apply_window_name();
+    }
+
+    /// Name the agent's tmux window after the session's icon and title, or
+    /// with no icon, hand naming back to tmux (the running command).
+    pub fn apply_window_name(&self) {
+        let name = tmux::Session::generate_name(&self.id, &self.title);
+        let window_name = self.icon.as_ref().map(|_| self.display_title());
+        if let Err(e) = tmux::utils::set_window_name(&name, window_name.as_deref()) {
+            tracing::debug!("Failed to name tmux window: {}", e);
+        }
     }
 
     fn apply_terminal_tmux_options(&self) {
@@ -614,9 +1162,18 @@ impl Instance {
     }
 
     pub fn kill(&self) -> Result<()> {
+        // The test watcher only runs alongside the agent
+        if let Ok(watch) = self.watch_tmux_session() {
+            let _ = watch.kill();
+        }
         let session = self.tmux_session()?;
         if session.exists() {
             session.kill()?;
+            super::audit::record_session(
+                super::audit::Action::Stopped,
+                self,
+                "tmux session killed",
+            );
         }
         Ok(())
     }
@@ -689,13 +1246,43 @@ impl Instance {
                 hook_status
             };
             self.last_error = None;
+            self.update_agent_failures(&session, None);
             return;
         }
 
+        // Then the heartbeat of the shim the agent was launched through
+        if let Some(health) = crate::hooks::shim::ping(&self.id) {
+            tracing::trace!("shim status detection '{}': {:?}", self.title, health);
+            match health {
+                ShimHealth::Exited(code) => {
+                    self.status = Status::Error;
+                    self.last_error = Some(format!("Agent exited with code {}", code));
+                    self.last_error_check = Some(std::time::Instant::now());
+                    return;
+                }
+                // CPU use confirms the agent is working even when its pane
+                // looks idle, unless the pane shows it asking for input
+                ShimHealth::Alive(heartbeat) if heartbeat.busy => {
+                    let (status, content) = match session.detect_status_with_content(&self.tool) {
+                        Ok((Status::Waiting, content)) => (Status::Waiting, Some(content)),
+                        Ok((_, content)) => (Status::Running, Some(content)),
+                        Err(_) => (Status::Running, None),
+                    };
+                    self.status = status;
+                    self.last_error = None;
+                    self.update_agent_failures(&session, content);
+                    return;
+                }
+                // A quiet heartbeat proves nothing: an agent streaming from or
+                // waiting on its model barely uses CPU. The pane decides.
+                ShimHealth::Alive(_) => {}
+            }
+        }
+
         // Fall back to tmux pane content detection
-        let detected = match session.detect_status(&self.tool) {
-            Ok(status) => status,
-            Err(_) => Status::Idle,
+        let (detected, content) = match session.detect_status_with_content(&self.tool) {
+            Ok((status, content)) => (status, Some(content)),
+            Err(_) => (Status::Idle, None),
         };
         tracing::trace!(
             "status detection '{}' (tool={}, custom_cmd={}): {:?}",
@@ -719,6 +1306,26 @@ impl Instance {
 
         // Clear stale error now that the session is healthy
         self.last_error = None;
+        self.update_agent_failures(&session, content);
+    }
+
+    /// Check the pane for a credentials or usage limit failure, and a
+    /// waiting agent's for a permission prompt. A busy agent is assumed to
+    /// be fine, which also clears the flags after a re-login. `captured` is
+    /// pane content status detection already read, used instead of
+    /// capturing the pane again.
+    fn update_agent_failures(&mut self, session: &tmux::Session, captured: Option<String>) {
+        let content = match self.status {
+            Status::Running | Status::Starting => None,
+            _ => captured.or_else(|| session.capture_pane(30).ok()),
+        };
+        let content = content.as_deref().unwrap_or("");
+        self.auth_required = tmux::status_detection::detect_auth_required(content);
+        self.quota_exceeded = tmux::status_detection::detect_quota_exceeded(content);
+        self.permission_prompt = match self.status {
+            Status::Waiting => tmux::permission_prompt::detect(content),
+            _ => None,
+        };
     }
 
     pub fn capture_output_with_size(
@@ -785,6 +1392,46 @@ mod tests {
         assert!(inst.is_sub_session());
     }
 
+    #[test]
+    fn test_board_lane_follows_status_until_pinned() {
+        let mut inst = Instance::new("test", "/tmp/test");
+        assert_eq!(inst.lane(), BoardLane::Done);
+        inst.status = Status::Waiting;
+        assert_eq!(inst.lane(), BoardLane::Waiting);
+
+        inst.set_lane(BoardLane::Archived);
+        assert_eq!(inst.board_lane, Some(BoardLane::Archived));
+        inst.status = Status::Running;
+        assert_eq!(inst.lane(), BoardLane::Archived);
+
+        // Moving back to the status lane unpins
+        inst.set_lane(BoardLane::Running);
+        assert_eq!(inst.board_lane, None);
+
+        let json = serde_json::to_string(&inst).unwrap();
+        assert!(!json.contains("board_lane"));
+    }
+
+    #[test]
+    fn test_fail_over_switches_agent_once() {
+        let mut inst = Instance::new("test", "/tmp/test");
+        inst.command = "happy cli claude".to_string();
+        inst.extra_args = "--verbose".to_string();
+        assert_eq!(inst.fail_over(), None);
+
+        inst.fallback_tool = Some("codex".to_string());
+        assert_eq!(inst.fail_over().as_deref(), Some("claude"));
+        assert_eq!(inst.tool, "codex");
+        assert_eq!(inst.command, "codex");
+        assert!(inst.extra_args.is_empty());
+        assert_eq!(inst.failed_over_from.as_deref(), Some("claude"));
+
+        // No failing back and forth
+        inst.fallback_tool = Some("claude".to_string());
+        assert_eq!(inst.fail_over(), None);
+        assert_eq!(inst.tool, "codex");
+    }
+
     #[test]
     fn test_all_agents_have_yolo_support() {
         for agent in crate::agents::AGENTS {
@@ -958,6 +1605,7 @@ mod tests {
             main_repo_path: "/home/user/repo".to_string(),
             managed_by_aoe: true,
             created_at: Utc::now(),
+            base_commit: None,
         };
 
         let json = serde_json::to_string(&info).unwrap();
@@ -1029,6 +1677,7 @@ mod tests {
         inst.last_error_check = Some(std::time::Instant::now());
         inst.last_start_time = Some(std::time::Instant::now());
         inst.last_error = Some("test error".to_string());
+        inst.auth_required = true;
 
         let json = serde_json::to_string(&inst).unwrap();
 
@@ -1036,6 +1685,7 @@ mod tests {
         assert!(!json.contains("last_error_check"));
         assert!(!json.contains("last_start_time"));
         assert!(!json.contains("last_error"));
+        assert!(!json.contains("auth_required"));
     }
 
     #[test]
@@ -1046,6 +1696,7 @@ mod tests {
             main_repo_path: "/tmp/main".to_string(),
             managed_by_aoe: true,
             created_at: Utc::now(),
+            base_commit: None,
         });
 
         let json = serde_json::to_string(&inst).unwrap();
@@ -1057,6 +1708,58 @@ mod tests {
         assert!(wt.managed_by_aoe);
     }
 
+    #[test]
+    fn test_instance_with_issue_info() {
+        let mut inst = Instance::new("#12 Fix login", "/tmp/repo");
+        inst.issue_info = Some(IssueInfo {
+            provider: IssueProvider::GitHub,
+            url: "https://github.com/org/repo/issues/12".to_string(),
+            number: 12,
+            title: "Fix login".to_string(),
+        });
+        inst.initial_prompt = Some("Work on issue #12".to_string());
+
+        let json = serde_json::to_string(&inst).unwrap();
+        assert!(json.contains("\"provider\":\"github\""));
+        let deserialized: Instance = serde_json::from_str(&json).unwrap();
+
+        let issue = deserialized.issue_info.unwrap();
+        assert_eq!(issue.number, 12);
+        assert_eq!(issue.url, "https://github.com/org/repo/issues/12");
+        assert_eq!(
+            deserialized.initial_prompt.as_deref(),
+            Some("Work on issue #12")
+        );
+
+        let plain = serde_json::to_string(&Instance::new("Test", "/tmp/test")).unwrap();
+        assert!(!plain.contains("issue_info"));
+        assert!(!plain.contains("initial_prompt"));
+    }
+
+    #[test]
+    fn test_initial_prompt_arg() {
+        let mut inst = Instance::new("Test", "/tmp/test");
+        let claude = crate::agents::get_agent("claude");
+        assert_eq!(inst.initial_prompt_arg(claude), None);
+
+        inst.initial_prompt = Some("fix it's\nbroken".to_string());
+        assert_eq!(
+            inst.initial_prompt_arg(claude).as_deref(),
+            Some("'fix it'\\''s\nbroken'")
+        );
+
+        let opencode = crate::agents::get_agent("opencode");
+        assert!(inst
+            .initial_prompt_arg(opencode)
+            .unwrap()
+            .starts_with("--prompt '"));
+
+        assert_eq!(inst.initial_prompt_arg(None), None);
+
+        inst.initial_prompt = Some("   ".to_string());
+        assert_eq!(inst.initial_prompt_arg(claude), None);
+    }
+
     // Test generate_id function properties
     #[test]
     fn test_generate_id_uniqueness() {
@@ -0,0 +1,142 @@
+//! Session state journal for external dashboards
+//!
+//! Each profile keeps `journal.jsonl` in its directory with one compact JSON
+//! record per session state change: created, every status transition, and
+//! removed. Dashboards and scripts can tail or chart it without running aoe.
+//! The schema is versioned by the `v` field and only grows by new optional
+//! fields within a version; see the "Session Journal" section of the
+//! workflow guide. Status transitions are recorded as the TUI observes them,
+//! and as `aoe session start` and `stop` make them. Recording is best-effort
+//! and the file is never trimmed.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{get_profile_dir, Instance, Status};
+
+/// Version written to the `v` field of every record.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// State `to` holds when a session is removed.
+pub const REMOVED: &str = "removed";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalRecord {
+    pub v: u32,
+    pub at: DateTime<Utc>,
+    pub id: String,
+    pub title: String,
+    pub tool: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub group: String,
+    /// Previous state; absent on the record for a new session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// A session status (`idle`, `starting`, `running`, `waiting`,
+    /// `stopped`, `error`, ...) or `removed`.
+    pub to: String,
+}
+
+pub fn journal_path(profile: &str) -> Result<PathBuf> {
+    Ok(get_profile_dir(profile)?.join("journal.jsonl"))
+}
+
+fn state(status: Status) -> String {
+    format!("{:?}", status).to_lowercase()
+}
+
+/// Record a new session, in the status it was created with.
+pub fn record_created(profile: &str, instance: &Instance) {
+    append(profile, instance, None, state(instance.status));
+}
+
+/// Record a status transition.
+pub fn record_transition(profile: &str, instance: &Instance, from: Status, to: Status) {
+    if from != to {
+        append(profile, instance, Some(state(from)), state(to));
+    }
+}
+
+/// Record a removed session.
+pub fn record_removed(profile: &str, instance: &Instance) {
+    append(
+        profile,
+        instance,
+        Some(state(instance.status)),
+        REMOVED.to_string(),
+    );
+}
+
+fn append(profile: &str, instance: &Instance, from: Option<String>, to: String) {
+    let record = JournalRecord {
+        v: SCHEMA_VERSION,
+        at: Utc::now(),
+        id: instance.id.clone(),
+        title: instance.title.clone(),
+        tool: instance.tool.clone(),
+        group: instance.group_path.clone(),
+        from,
+        to,
+    };
+    if let Err(e) = try_append(profile, &record) {
+        tracing::warn!("Failed to write session journal record: {}", e);
+    }
+}
+
+fn try_append(profile: &str, record: &JournalRecord) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(journal_path(profile)?)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    #[test]
+    #[serial]
+    fn test_records_session_lifecycle() -> Result<()> {
+        let temp = tempdir()?;
+        std::env::set_var("HOME", temp.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.path().join(".config"));
+
+        let mut inst = Instance::new("fix-login", "/tmp/project");
+        inst.group_path = "work".to_string();
+        record_created("default", &inst);
+        record_transition("default", &inst, Status::Idle, Status::Idle);
+        record_transition("default", &inst, Status::Running, Status::Waiting);
+        inst.status = Status::Stopped;
+        record_removed("default", &inst);
+
+        let content = std::fs::read_to_string(journal_path("default")?)?;
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with(r#"{"v":1,"at":""#), "{}", lines[0]);
+        assert!(!lines[0].contains("\"from\""));
+        assert!(lines[0].ends_with(r#""group":"work","to":"idle"}"#));
+
+        let records: Vec<JournalRecord> = lines
+            .iter()
+            .map(|l| serde_json::from_str(l))
+            .collect::<std::result::Result<_, _>>()?;
+        assert_eq!(records[1].from.as_deref(), Some("running"));
+        assert_eq!(records[1].to, "waiting");
+        assert_eq!(records[2].from.as_deref(), Some("stopped"));
+        assert_eq!(records[2].to, REMOVED);
+        assert!(records
+            .iter()
+            .all(|r| r.id == inst.id && r.tool == "claude"));
+        Ok(())
+    }
+}
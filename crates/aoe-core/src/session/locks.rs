@@ -0,0 +1,180 @@
+//! Named session locks
+//!
+//! Sessions can declare named locks (`session.locks` in config, or
+//! `aoe add --lock`). A lock is held by a session while it is starting,
+//! running, or waiting for input; other sessions sharing that lock are queued
+//! until it is released. Locks are global across profiles.
+
+use anyhow::Result;
+
+use super::{list_profiles, Instance, Status, Storage};
+
+/// A lock that keeps a session from starting, and the session holding it.
+#[derive(Debug, Clone, Copy)]
+pub struct LockConflict<'a> {
+    pub lock: &'a str,
+    pub holder: &'a Instance,
+}
+
+/// Who holds one of a session's locks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockOwner {
+    /// The session itself holds it.
+    This,
+    /// Another session (by title) holds it.
+    Other(String),
+    Free,
+}
+
+/// Trim, drop empty names, and remove duplicates (keeping the first).
+pub fn normalize(locks: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for lock in locks {
+        let lock = lock.trim().to_string();
+        if !lock.is_empty() && !out.contains(&lock) {
+            out.push(lock);
+        }
+    }
+    out
+}
+
+/// Whether a session in this status holds its locks.
+pub fn holds_locks(status: Status) -> bool {
+    matches!(status, Status::Starting | Status::Running | Status::Waiting)
+}
+
+/// The first lock `candidate` shares with another session that currently
+/// holds it, if any.
+pub fn conflict<'a>(
+    candidate: &'a Instance,
+    instances: &'a [Instance],
+) -> Option<LockConflict<'a>> {
+    candidate.locks.iter().find_map(|lock| {
+        instances
+            .iter()
+            .find(|other| {
+                other.id != candidate.id && holds_locks(other.status) && other.locks.contains(lock)
+            })
+            .map(|holder| LockConflict {
+                lock: lock.as_str(),
+                holder,
+            })
+    })
+}
+
+/// Ownership of each of `candidate`'s locks, in declaration order.
+pub fn owners(candidate: &Instance, instances: &[Instance]) -> Vec<(String, LockOwner)> {
+    candidate
+        .locks
+        .iter()
+        .map(|lock| {
+            let owner = if holds_locks(candidate.status) {
+                LockOwner::This
+            } else {
+                instances
+                    .iter()
+                    .find(|other| {
+                        other.id != candidate.id
+                            && holds_locks(other.status)
+                            && other.locks.contains(lock)
+                    })
+                    .map(|holder| LockOwner::Other(holder.title.clone()))
+                    .unwrap_or(LockOwner::Free)
+            };
+            (lock.clone(), owner)
+        })
+        .collect()
+}
+
+/// Load sessions from every profile with fresh statuses, for lock checks
+/// outside the TUI.
+pub fn load_all_with_status() -> Result<Vec<Instance>> {
+    crate::tmux::refresh_session_cache();
+    let mut all = Vec::new();
+    for profile in list_profiles()? {
+        let storage = Storage::new(&profile)?;
+        for mut inst in storage.load()? {
+            if inst.locks.is_empty() {
+                continue;
+            }
+            inst.update_status();
+            all.push(inst);
+        }
+    }
+    Ok(all)
+}
+
+/// Fail with a readable message if `candidate` is blocked by a held lock.
+pub fn ensure_can_start(candidate: &Instance) -> Result<()> {
+    if candidate.locks.is_empty() {
+        return Ok(());
+    }
+    let all = load_all_with_status()?;
+    if let Some(c) = conflict(candidate, &all) {
+        anyhow::bail!(
+            "Lock '{}' is held by session \"{}\". Start \"{}\" after it stops or goes idle, \
+             or start it from the TUI to queue it",
+            c.lock,
+            c.holder.title,
+            candidate.title
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(title: &str, status: Status, locks: &[&str]) -> Instance {
+        let mut inst = Instance::new(title, "/tmp/project");
+        inst.status = status;
+        inst.locks = locks.iter().map(|l| l.to_string()).collect();
+        inst
+    }
+
+    #[test]
+    fn test_normalize_trims_and_dedupes() {
+        let locks = normalize(vec![
+            " db ".to_string(),
+            "".to_string(),
+            "db".to_string(),
+            "ports".to_string(),
+        ]);
+        assert_eq!(locks, vec!["db", "ports"]);
+    }
+
+    #[test]
+    fn test_conflict_only_with_active_holders() {
+        let candidate = session("b", Status::Stopped, &["db"]);
+        let idle = session("a", Status::Idle, &["db"]);
+        let unrelated = session("c", Status::Running, &["ports"]);
+        let instances = vec![idle, unrelated, candidate.clone()];
+        assert!(conflict(&candidate, &instances).is_none());
+
+        let running = session("a", Status::Running, &["db"]);
+        let instances = vec![running, candidate.clone()];
+        let c = conflict(&candidate, &instances).unwrap();
+        assert_eq!(c.lock, "db");
+        assert_eq!(c.holder.title, "a");
+    }
+
+    #[test]
+    fn test_owners() {
+        let candidate = session("b", Status::Stopped, &["db", "ports"]);
+        let instances = vec![session("a", Status::Waiting, &["db"]), candidate.clone()];
+        assert_eq!(
+            owners(&candidate, &instances),
+            vec![
+                ("db".to_string(), LockOwner::Other("a".to_string())),
+                ("ports".to_string(), LockOwner::Free),
+            ]
+        );
+
+        let running = session("b", Status::Running, &["db"]);
+        assert_eq!(
+            owners(&running, &[]),
+            vec![("db".to_string(), LockOwner::This)]
+        );
+    }
+}
@@ -1,48 +1,100 @@
 //! Session management module
 
+pub mod attachments;
+pub mod audit;
+pub mod branch_labels;
+pub mod budget;
 pub mod builder;
+pub mod bulk_rename;
+pub mod checkpoints;
 pub mod civilizations;
+pub mod claude_history;
+pub mod commands;
 pub mod config;
 mod container_config;
+pub mod credentials;
+pub mod digest;
+pub mod duplicates;
 mod environment;
+pub mod filter;
 mod groups;
+pub mod guardrails;
+pub mod host_sandbox;
+pub mod inbox;
 mod instance;
+pub mod journal;
+pub mod locks;
+pub mod notes;
+pub mod orchestrator;
+pub mod ownership;
+pub mod profile_bundle;
 pub mod profile_config;
+pub mod project_env;
+pub mod providers;
+pub mod proxy;
+pub mod relocate;
 pub mod repo_config;
+pub mod resource_limits;
+pub mod share;
+#[cfg(feature = "sqlite")]
+mod sqlite_store;
+pub mod stats;
 mod storage;
+pub mod template_sources;
+pub mod test_watch;
+pub mod timeline;
+pub mod transcript;
+pub mod trash;
 
 pub use crate::sound::{SoundConfig, SoundConfigOverride};
 pub use config::{
-    get_claude_config_dir, get_update_settings, load_config, save_config, ClaudeConfig, Config,
-    ContainerRuntimeName, DefaultTerminalMode, SandboxConfig, SessionConfig, ThemeConfig,
-    TmuxMouseMode, TmuxStatusBarMode, UpdatesConfig, WorktreeConfig,
+    get_claude_config_dir, get_update_settings, load_config, save_config, BudgetAction,
+    ClaudeConfig, ColorVision, Config, ContainerRuntimeName, DefaultTerminalMode,
+    HostSandboxProfile, HyperlinkMode, IoPriority, MergedSessionAction, PollingConfig,
+    SandboxConfig, SessionConfig, ShareConfig, ShareDestination, StorageBackend, StorageConfig,
+    ThemeConfig, TmuxMouseMode, TmuxStatusBarMode, TmuxWindowSize, UpdatesConfig, WorktreeConfig,
 };
-pub(crate) use environment::user_shell;
 pub use environment::validate_env_entry;
+pub(crate) use environment::{shell_single_quote, user_shell};
 pub use groups::{flatten_tree, flatten_tree_all_profiles, Group, GroupTree, Item};
 pub use instance::{
-    Instance, SandboxInfo, Status, TerminalInfo, WorkspaceInfo, WorkspaceRepo, WorktreeInfo,
+    BoardLane, Instance, IssueInfo, SandboxInfo, Status, TerminalInfo, WorkspaceInfo,
+    WorkspaceRepo, WorktreeInfo,
 };
 pub use profile_config::{
     load_profile_config, merge_configs, resolve_config, save_profile_config,
-    validate_check_interval, validate_memory_limit, validate_path_exists, validate_volume_format,
-    ClaudeConfigOverride, HooksConfigOverride, ProfileConfig, SandboxConfigOverride,
-    SessionConfigOverride, ThemeConfigOverride, TmuxConfigOverride, UpdatesConfigOverride,
-    WorktreeConfigOverride,
+    validate_check_interval, validate_health_check_interval, validate_icon, validate_memory_limit,
+    validate_pane_capture_interval, validate_path_exists, validate_poll_interval,
+    validate_volume_format, ClaudeConfigOverride, HooksConfigOverride, PollingConfigOverride,
+    ProfileConfig, SandboxConfigOverride, SessionConfigOverride, ShareConfigOverride,
+    ThemeConfigOverride, TmuxConfigOverride, UpdatesConfigOverride, WorktreeConfigOverride,
 };
 pub use repo_config::{
     check_hook_trust, execute_hooks, execute_hooks_in_container, load_repo_config,
-    merge_repo_config, profile_to_repo_config, repo_config_to_profile, resolve_config_with_repo,
-    save_repo_config, trust_repo, HookTrustStatus, HooksConfig, RepoConfig,
+    load_repo_config_file, merge_repo_config, profile_to_repo_config, repo_config_to_profile,
+    resolve_config_with_repo, save_repo_config, trust_repo, HookTrustStatus, HooksConfig,
+    RepoConfig,
 };
-pub use storage::Storage;
+#[cfg(feature = "sqlite")]
+pub use sqlite_store::SqliteStore;
+pub use storage::{JsonFileStore, SessionQuery, SessionStore, Storage};
 
 use anyhow::Result;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
 pub const DEFAULT_PROFILE: &str = "default";
 
+static APP_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `dir` as the app directory for the rest of the process instead of
+/// the user's, so nothing reads or writes their sessions and config. Only
+/// the first call has an effect.
+pub fn override_app_dir(dir: PathBuf) {
+    let _ = APP_DIR_OVERRIDE.set(dir);
+}
+
 pub fn get_app_dir() -> Result<PathBuf> {
     let dir = get_app_dir_path()?;
     if !dir.exists() {
@@ -52,6 +104,10 @@ pub fn get_app_dir() -> Result<PathBuf> {
 }
 
 fn get_app_dir_path() -> Result<PathBuf> {
+    if let Some(dir) = APP_DIR_OVERRIDE.get() {
+        return Ok(dir.clone());
+    }
+
     #[cfg(target_os = "linux")]
     let dir = dirs::config_dir()
         .ok_or_else(|| anyhow::anyhow!("Cannot find config directory"))?
@@ -65,6 +121,48 @@ fn get_app_dir_path() -> Result<PathBuf> {
     Ok(dir)
 }
 
+/// Where `aoe ipc` listens for a profile unless `--socket` is given.
+pub fn ipc_socket_path(profile: &str) -> Result<PathBuf> {
+    Ok(get_app_dir()?.join(format!("ipc-{}.sock", profile)))
+}
+
+/// The first `max_len` bytes of a session id, as used in tmux and
+/// container names.
+pub fn truncate_id(id: &str, max_len: usize) -> &str {
+    if id.len() > max_len {
+        &id[..max_len]
+    } else {
+        id
+    }
+}
+
+/// Indices of the sessions `identifier` could name, from the first of
+/// these that matches anything: the session with that full ID, the sessions
+/// with that title, the sessions whose ID starts with it, the sessions in
+/// that project path. Empty when nothing matches or the identifier is
+/// blank.
+pub fn match_sessions(identifier: &str, instances: &[Instance]) -> Vec<usize> {
+    let identifier = identifier.trim();
+    if identifier.is_empty() {
+        return Vec::new();
+    }
+    let tiers: [&dyn Fn(&Instance) -> bool; 4] = [
+        &|i| i.id == identifier,
+        &|i| i.title == identifier,
+        &|i| i.id.starts_with(identifier),
+        &|i| i.project_path == identifier,
+    ];
+    for matches_tier in tiers {
+        let found: Vec<usize> = (0..instances.len())
+            .filter(|&i| matches_tier(&instances[i]))
+            .collect();
+        if !found.is_empty() {
+            return found;
+        }
+    }
+    Vec::new()
+}
+
 pub fn get_profile_dir(profile: &str) -> Result<PathBuf> {
     let base = get_app_dir()?;
     let profile_name = if profile.is_empty() {
@@ -132,6 +230,8 @@ pub fn delete_profile(name: &str) -> Result<()> {
         anyhow::bail!("Profile '{}' does not exist", name);
     }
 
+    // First, so a failure leaves the profile whole
+    storage::delete_profile_data(name)?;
     fs::remove_dir_all(&profile_dir)?;
     Ok(())
 }
@@ -156,6 +256,10 @@ pub fn rename_profile(old_name: &str, new_name: &str) -> Result<()> {
     }
 
     fs::rename(&old_dir, &new_dir)?;
+    if let Err(e) = storage::rename_profile_data(old_name, new_name) {
+        let _ = fs::rename(&new_dir, &old_dir);
+        return Err(e);
+    }
 
     // Update default profile if the renamed profile was the default
     if let Some(config) = load_config()? {
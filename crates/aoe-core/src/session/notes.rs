@@ -0,0 +1,188 @@
+//! Session notes mirrored into the repository
+//!
+//! A session's notes live on the instance. With `worktree.sync_notes`
+//! enabled they are also written to `docs/agent-sessions/<name>.md` inside
+//! the session's worktree, so the context is committed with the work and
+//! travels with the pull request. Only worktree sessions are mirrored; a
+//! session working directly in the main checkout would leave the file
+//! behind on whatever branch happens to be checked out.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+use super::Instance;
+
+/// Directory, relative to the worktree root, that holds the notes files.
+pub const NOTES_DIR: &str = "docs/agent-sessions";
+
+/// File name for a session's notes: the title lowercased, with runs of
+/// anything other than letters and digits collapsed to a dash.
+pub fn file_name(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "session.md".to_string()
+    } else {
+        format!("{}.md", slug)
+    }
+}
+
+/// Where the session's notes are mirrored, or None for sessions without a
+/// worktree.
+pub fn repo_notes_path(inst: &Instance) -> Option<PathBuf> {
+    inst.worktree_info.as_ref()?;
+    Some(
+        Path::new(&inst.project_path)
+            .join(NOTES_DIR)
+            .join(file_name(&inst.title)),
+    )
+}
+
+/// Markdown document for the session: a short header with the agent,
+/// branch, and linked issue, followed by the notes.
+pub fn render(inst: &Instance) -> String {
+    let mut out = format!("# {}\n\n", inst.title);
+    out.push_str(&format!("- Agent: {}\n", inst.tool));
+    if let Some(wt) = &inst.worktree_info {
+        out.push_str(&format!("- Branch: `{}`\n", wt.branch));
+    }
+    if let Some(issue) = &inst.issue_info {
+        out.push_str(&format!(
+            "- Issue: [#{} {}]({})\n",
+            issue.number, issue.title, issue.url
+        ));
+    }
+    out.push_str(&format!(
+        "- Started: {}\n",
+        inst.created_at.format("%Y-%m-%d")
+    ));
+
+    out.push_str("\n## Notes\n\n");
+    out.push_str(inst.notes.as_deref().unwrap_or("").trim());
+    out.push('\n');
+    out
+}
+
+/// Write the session's notes file. Returns the path written, or None when
+/// the session has no worktree or no notes.
+pub fn sync(inst: &Instance) -> Result<Option<PathBuf>> {
+    let Some(path) = repo_notes_path(inst) else {
+        return Ok(None);
+    };
+    if inst.notes.as_deref().map_or(true, |n| n.trim().is_empty()) {
+        return Ok(None);
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, render(inst))?;
+    Ok(Some(path))
+}
+
+/// Mirror the notes if `worktree.sync_notes` is enabled for the session's
+/// project. Failures are logged; notes are always kept on the session.
+pub fn sync_if_enabled(inst: &Instance, profile: &str) -> Option<PathBuf> {
+    let enabled =
+        super::repo_config::resolve_config_with_repo(profile, Path::new(&inst.project_path))
+            .map(|c| c.worktree.sync_notes)
+            .unwrap_or(false);
+    if !enabled {
+        return None;
+    }
+    match sync(inst) {
+        Ok(path) => path,
+        Err(e) => {
+            tracing::warn!("Failed to write notes for {}: {}", inst.title, e);
+            None
+        }
+    }
+}
+
+/// Open `current` in `editor` and return the edited text.
+pub fn edit_notes(editor: &str, session_id: &str, current: &str) -> Result<String> {
+    let path = std::env::temp_dir().join(format!("aoe-notes-{}.md", session_id));
+    fs::write(&path, current)?;
+    let status = Command::new(editor).arg(&path).status();
+    let edited = fs::read_to_string(&path);
+    let _ = fs::remove_file(&path);
+
+    let status = status.with_context(|| format!("Failed to run editor '{}'", editor))?;
+    if !status.success() {
+        bail!("Editor '{}' exited with {}", editor, status);
+    }
+    Ok(edited?)
+}
+
+/// Normalize edited notes: surrounding whitespace is dropped and empty
+/// notes are removed.
+pub fn normalize(text: &str) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::WorktreeInfo;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    fn worktree_instance(path: &Path) -> Instance {
+        let mut inst = Instance::new("Fix Login: OAuth flow!", path.to_str().unwrap());
+        inst.worktree_info = Some(WorktreeInfo {
+            branch: "fix/login".to_string(),
+            main_repo_path: "/repo".to_string(),
+            managed_by_aoe: true,
+            created_at: Utc::now(),
+            base_commit: None,
+        });
+        inst
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(
+            file_name("Fix Login: OAuth flow!"),
+            "fix-login-oauth-flow.md"
+        );
+        assert_eq!(file_name("  --- "), "session.md");
+    }
+
+    #[test]
+    fn test_sync_writes_worktree_file() {
+        let tmp = TempDir::new().unwrap();
+        let mut inst = worktree_instance(tmp.path());
+        assert_eq!(sync(&inst).unwrap(), None);
+
+        inst.notes = Some("Token refresh races with logout.\n".to_string());
+        let path = sync(&inst).unwrap().unwrap();
+        assert_eq!(
+            path,
+            tmp.path()
+                .join("docs/agent-sessions/fix-login-oauth-flow.md")
+        );
+        let content = fs::read_to_string(path).unwrap();
+        assert!(content.starts_with("# Fix Login: OAuth flow!\n"));
+        assert!(content.contains("- Branch: `fix/login`\n"));
+        assert!(content.ends_with("## Notes\n\nToken refresh races with logout.\n"));
+    }
+
+    #[test]
+    fn test_sync_skips_sessions_without_worktree() {
+        let tmp = TempDir::new().unwrap();
+        let mut inst = Instance::new("plain", tmp.path().to_str().unwrap());
+        inst.notes = Some("notes".to_string());
+        assert_eq!(sync(&inst).unwrap(), None);
+        assert!(!tmp.path().join(NOTES_DIR).exists());
+    }
+}
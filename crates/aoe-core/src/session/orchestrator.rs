@@ -0,0 +1,129 @@
+//! Orchestrator sessions
+//!
+//! An orchestrator is a session whose agent splits a task into pieces and
+//! hands each to a worker session, driving the workers through aoe itself.
+//! On launch its agent gets instructions describing the `aoe` commands for
+//! that, and an environment that points those commands at the orchestrator's
+//! profile: `AGENT_OF_EMPIRES_PROFILE`, `AOE_ORCHESTRATOR_ID` (so workers can
+//! be created as its sub-sessions) and `AOE_IPC_SOCKET` (for agents that
+//! prefer the `aoe ipc` protocol). Agents without a system instruction flag
+//! get the instructions ahead of their first prompt instead.
+
+use anyhow::{bail, Result};
+
+use super::environment::shell_single_quote;
+use super::Instance;
+
+/// Make `inst` an orchestrator over the sessions in `profile`.
+pub fn apply(inst: &mut Instance, profile: &str) -> Result<()> {
+    if inst.is_sub_session() {
+        bail!("An orchestrator cannot be a sub-session; its workers are its sub-sessions");
+    }
+    if inst.sandbox_info.is_some() {
+        bail!("An orchestrator cannot run in a Docker sandbox, where aoe is not available");
+    }
+    let agent = crate::agents::get_agent(&inst.tool);
+    if agent.is_some_and(|a| a.instruction_flag.is_some()) {
+        inst.orchestrator = Some(profile.to_string());
+        return Ok(());
+    }
+    if !agent.is_some_and(|a| a.prompt_flag.is_some()) {
+        bail!(
+            "'{}' accepts neither instructions nor an initial prompt, so it cannot be told how to orchestrate",
+            inst.tool
+        );
+    }
+    inst.orchestrator = Some(profile.to_string());
+    let prompt = match inst.initial_prompt.take() {
+        Some(task) => format!("{}\n\nYour task:\n{}", instructions(inst), task),
+        None => instructions(inst),
+    };
+    inst.initial_prompt = Some(prompt);
+    inst.original_prompt = inst.initial_prompt.clone();
+    Ok(())
+}
+
+/// What the orchestrator's agent is told about driving workers.
+pub fn instructions(inst: &Instance) -> String {
+    format!(
+        "You are an orchestrator session in Agent of Empires (aoe), session ID {id}. \
+Break the work you are given into independent pieces and delegate each one to a \
+worker session: another coding agent that aoe runs in tmux. Coordinate and review \
+the workers' results rather than doing the work yourself. Drive workers only through \
+the `aoe` command, which already targets the right profile:
+
+- Create a worker: aoe add <project dir> -P {id} -t <title> -w <branch> -b --prompt '<task>'
+  (-w/-b give it its own git worktree; use --prompt - to read the task from stdin)
+- Start it: aoe session start <title>
+- List workers and their status: aoe list --json
+- Block until a worker finishes or needs input: aoe wait <title> --timeout <secs>
+  (exit codes: 0 done, 2 waiting for input, 3 error, 4 stopped or removed, 124 timeout)
+- Read a worker's recent output: aoe session capture <title> -n 200 --strip-ansi
+- Answer or follow up with a worker: aoe send <title> '<message>'
+- Stop or remove a finished worker: aoe session stop <title>, aoe remove <title>
+
+Never attach to a worker (no `aoe add -l`, no tmux attach): it would take over your terminal. \
+Run `aoe <command> --help` for other options.",
+        id = inst.id
+    )
+}
+
+/// Shell assignments prefixed to the orchestrator's agent command.
+pub fn env_prefix(inst: &Instance) -> String {
+    let Some(profile) = inst.orchestrator.as_deref() else {
+        return String::new();
+    };
+    let mut prefix = format!(
+        "AGENT_OF_EMPIRES_PROFILE={} AOE_ORCHESTRATOR_ID={} ",
+        shell_single_quote(profile),
+        inst.id
+    );
+    if let Ok(socket) = super::ipc_socket_path(profile) {
+        prefix.push_str(&format!(
+            "AOE_IPC_SOCKET={} ",
+            shell_single_quote(&socket.to_string_lossy())
+        ));
+    }
+    prefix
+}
+
+/// The agent flag carrying the instructions, for agents that take one.
+pub fn instruction_arg(inst: &Instance, agent: Option<&crate::agents::AgentDef>) -> Option<String> {
+    inst.orchestrator.as_ref()?;
+    let flag_template = agent.and_then(|a| a.instruction_flag)?;
+    Some(flag_template.replace("{}", &shell_single_quote(&instructions(inst))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orchestrator_wiring() {
+        let mut inst = Instance::new("conductor", "/tmp/project");
+        assert_eq!(env_prefix(&inst), "");
+
+        apply(&mut inst, "work").unwrap();
+        assert_eq!(inst.orchestrator.as_deref(), Some("work"));
+        assert!(inst.initial_prompt.is_none());
+        let prefix = env_prefix(&inst);
+        assert!(prefix.starts_with("AGENT_OF_EMPIRES_PROFILE='work' AOE_ORCHESTRATOR_ID="));
+        assert!(prefix.contains(&inst.id));
+        let claude = crate::agents::get_agent("claude");
+        let arg = instruction_arg(&inst, claude).unwrap();
+        assert!(arg.starts_with("--append-system-prompt '"));
+        assert!(arg.contains(&format!("-P {}", inst.id)));
+
+        let mut gemini = Instance::new("gemini-conductor", "/tmp/project");
+        gemini.tool = "gemini".to_string();
+        gemini.initial_prompt = Some("Port the CLI to clap 4".to_string());
+        apply(&mut gemini, "default").unwrap();
+        let prompt = gemini.initial_prompt.as_deref().unwrap();
+        assert!(prompt.starts_with("You are an orchestrator"));
+        assert!(prompt.ends_with("Your task:\nPort the CLI to clap 4"));
+
+        let mut worker = Instance::new("worker", "/tmp/project");
+        worker.parent_session_id = Some(inst.id.clone());
+        assert!(apply(&mut worker, "work").is_err());
+    }
+}
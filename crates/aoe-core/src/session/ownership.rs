@@ -0,0 +1,118 @@
+//! Session ownership
+//!
+//! Each session records who created it as `user@host`. On a shared machine,
+//! or with session data synced between machines, commands that change or
+//! remove a session owned by someone else refuse to run unless given
+//! `--steal`, which makes the current user the owner. Sessions created
+//! before owners were recorded belong to nobody and anyone can change them.
+
+use std::sync::OnceLock;
+
+use anyhow::{bail, Result};
+
+use super::{timeline, GroupTree, Instance, Storage};
+
+/// The current user as `user@host`.
+pub fn current() -> &'static str {
+    static CURRENT: OnceLock<String> = OnceLock::new();
+    CURRENT.get_or_init(|| {
+        let user = ["USER", "LOGNAME"]
+            .iter()
+            .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+            .unwrap_or_else(|| "unknown".to_string());
+        let host = nix::unistd::gethostname()
+            .ok()
+            .and_then(|h| h.into_string().ok())
+            .filter(|h| !h.is_empty())
+            .unwrap_or_else(|| "localhost".to_string());
+        format!("{}@{}", user, host)
+    })
+}
+
+/// The owner of `inst`, if it is someone other than the current user.
+pub fn foreign_owner(inst: &Instance) -> Option<&str> {
+    inst.owner.as_deref().filter(|owner| *owner != current())
+}
+
+/// Make sure the current user may change `inst`. With `steal`, a session
+/// owned elsewhere is taken over (the caller saves it); without, it is an
+/// error.
+pub fn claim(inst: &mut Instance, steal: bool) -> Result<()> {
+    let Some(owner) = foreign_owner(inst).map(str::to_string) else {
+        return Ok(());
+    };
+    if !steal {
+        bail!(
+            "Session '{}' is owned by {}. Pass --steal to take it over.",
+            inst.title,
+            owner
+        );
+    }
+    take(inst, &owner);
+    Ok(())
+}
+
+/// `claim` for commands that otherwise only read sessions: loads session
+/// `id` from `storage` and saves it if it was stolen.
+pub fn claim_stored(storage: &Storage, id: &str, steal: bool) -> Result<()> {
+    let (mut instances, groups) = storage.load_with_groups()?;
+    let Some(inst) = instances.iter_mut().find(|i| i.id == id) else {
+        return Ok(());
+    };
+    let before = inst.owner.clone();
+    claim(inst, steal)?;
+    if inst.owner != before {
+        let group_tree = GroupTree::new_with_groups(&instances, &groups);
+        storage.save_with_groups(&instances, &group_tree)?;
+    }
+    Ok(())
+}
+
+/// Make the current user the owner of `inst`.
+pub fn steal(inst: &mut Instance) {
+    if let Some(owner) = foreign_owner(inst).map(str::to_string) {
+        take(inst, &owner);
+    }
+}
+
+fn take(inst: &mut Instance, from: &str) {
+    timeline::record(
+        &inst.id,
+        timeline::EventKind::Command,
+        format!("ownership taken from {} by {}", from, current()),
+    );
+    inst.owner = Some(current().to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    #[test]
+    #[serial]
+    fn test_claim_requires_steal_for_foreign_sessions() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("HOME", temp.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.path().join(".config"));
+
+        let mut inst = Instance::new("shared", "/tmp/shared");
+        assert_eq!(inst.owner.as_deref(), Some(current()));
+        assert!(claim(&mut inst, false).is_ok());
+
+        inst.owner = None;
+        assert!(claim(&mut inst, false).is_ok());
+
+        inst.owner = Some("colleague@devbox".to_string());
+        let err = claim(&mut inst, false).unwrap_err().to_string();
+        assert!(err.contains("colleague@devbox"), "{}", err);
+        assert_eq!(inst.owner.as_deref(), Some("colleague@devbox"));
+
+        claim(&mut inst, true).unwrap();
+        assert_eq!(inst.owner.as_deref(), Some(current()));
+        let events = timeline::load(&inst.id).unwrap();
+        assert!(events[0].message.contains("taken from colleague@devbox"));
+    }
+}
@@ -0,0 +1,320 @@
+//! Shareable profile bundles
+//!
+//! A bundle is a single TOML document holding a profile's config overrides
+//! and the repo config templates, plus a small header, so a team can check a
+//! standard setup into a repo or pass it around and load it with `aoe
+//! profile import`. Sessions and groups are machine-specific and are never
+//! included.
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::profile_config::{
+    get_profile_config_path, load_profile_config, save_profile_config, ProfileConfig,
+};
+use super::repo_config::{is_valid_template_name, templates_dir};
+use super::{create_profile, list_profiles};
+
+/// Bumped when the bundle layout changes in a way older versions can't read.
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileBundle {
+    pub format_version: u32,
+    /// aoe version that wrote the bundle, for troubleshooting only.
+    #[serde(default)]
+    pub aoe_version: String,
+    /// Name of the exported profile; used as the import name by default.
+    pub profile: String,
+    #[serde(default)]
+    pub config: ProfileConfig,
+    /// Repo config templates by name, as the contents of their files.
+    /// Templates are shared by every profile, so all of them are included.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub templates: BTreeMap<String, String>,
+}
+
+impl ProfileBundle {
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let bundle: ProfileBundle = toml::from_str(content).context("Invalid profile bundle")?;
+        if bundle.format_version > BUNDLE_FORMAT_VERSION {
+            bail!(
+                "Bundle format version {} is newer than this aoe supports ({})\nTip: Upgrade aoe to import this bundle",
+                bundle.format_version,
+                BUNDLE_FORMAT_VERSION
+            );
+        }
+        Ok(bundle)
+    }
+
+    /// Hook commands carried by the bundle, including the health check.
+    /// Importing a bundle means trusting these to run, so callers should
+    /// surface them.
+    pub fn hook_commands(&self) -> Vec<&str> {
+        self.config
+            .hooks
+            .as_ref()
+            .map(|h| {
+                h.on_create
+                    .iter()
+                    .chain(h.on_launch.iter())
+                    .flatten()
+                    .chain(h.health_check.iter())
+                    .chain(h.test_watch.iter())
+                    .map(String::as_str)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Build a bundle from an existing profile.
+pub fn export_profile(name: &str) -> Result<ProfileBundle> {
+    if !list_profiles()?.iter().any(|p| p == name) {
+        bail!("Profile '{}' does not exist", name);
+    }
+    Ok(ProfileBundle {
+        format_version: BUNDLE_FORMAT_VERSION,
+        aoe_version: env!("CARGO_PKG_VERSION").to_string(),
+        profile: name.to_string(),
+        config: load_profile_config(name)?,
+        templates: load_templates()?,
+    })
+}
+
+fn load_templates() -> Result<BTreeMap<String, String>> {
+    let mut templates = BTreeMap::new();
+    let Ok(entries) = fs::read_dir(templates_dir()?) else {
+        return Ok(templates);
+    };
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if path.extension().map_or(true, |ext| ext != "toml") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        templates.insert(name.to_string(), content);
+    }
+    Ok(templates)
+}
+
+/// Write a bundle's config into profile `name`, creating the profile if
+/// needed, and install its templates. An existing profile with its own config
+/// file, or a template that exists with different contents, is only replaced
+/// when `force` is set; nothing is written unless everything can be.
+pub fn import_profile(bundle: &ProfileBundle, name: &str, force: bool) -> Result<()> {
+    let dir = templates_dir()?;
+    let mut changed_templates = Vec::new();
+    for (template, content) in &bundle.templates {
+        if !is_valid_template_name(template) {
+            bail!("Invalid template name in bundle: '{}'", template);
+        }
+        let path = dir.join(format!("{}.toml", template));
+        if fs::read_to_string(&path).ok().as_ref() != Some(content) {
+            changed_templates.push((template.as_str(), path, content));
+        }
+    }
+
+    let exists = list_profiles()?.iter().any(|p| p == name);
+    if !force {
+        if exists && get_profile_config_path(name)?.exists() {
+            bail!(
+                "Profile '{}' already has a config\nTip: Use --force to overwrite it, or --name to import under a different name",
+                name
+            );
+        }
+        let conflicts: Vec<&str> = changed_templates
+            .iter()
+            .filter(|(_, path, _)| path.exists())
+            .map(|(template, _, _)| *template)
+            .collect();
+        if !conflicts.is_empty() {
+            bail!(
+                "Templates differ from the bundle's: {}\nTip: Use --force to overwrite them",
+                conflicts.join(", ")
+            );
+        }
+    }
+
+    if !exists {
+        create_profile(name)?;
+    }
+    save_profile_config(name, &bundle.config)?;
+    if !changed_templates.is_empty() {
+        fs::create_dir_all(&dir)?;
+    }
+    for (_, path, content) in changed_templates {
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::profile_config::{HooksConfigOverride, ThemeConfigOverride};
+    use serial_test::serial;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    fn setup_test_home(temp: &Path) {
+        std::env::set_var("HOME", temp);
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.join(".config"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_export_import_roundtrip() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        create_profile("team")?;
+        let config = ProfileConfig {
+            theme: Some(ThemeConfigOverride {
+                name: Some("dracula".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        save_profile_config("team", &config)?;
+
+        let toml = export_profile("team")?.to_toml()?;
+        let bundle = ProfileBundle::from_toml(&toml)?;
+        assert_eq!(bundle.profile, "team");
+
+        import_profile(&bundle, "copy", false)?;
+        let imported = load_profile_config("copy")?;
+        assert_eq!(
+            imported.theme.and_then(|t| t.name).as_deref(),
+            Some("dracula")
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_export_missing_profile_fails() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+        assert!(export_profile("nope").is_err());
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_import_requires_force_to_overwrite() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        create_profile("team")?;
+        save_profile_config("team", &ProfileConfig::default())?;
+        let bundle = export_profile("team")?;
+
+        assert!(import_profile(&bundle, "team", false).is_err());
+        import_profile(&bundle, "team", true)?;
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_templates_travel_with_the_bundle() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        create_profile("team")?;
+        let dir = templates_dir()?;
+        fs::create_dir_all(&dir)?;
+        fs::write(
+            dir.join("base-rust.toml"),
+            "[session]\ndefault_tool = \"codex\"\n",
+        )?;
+        fs::write(dir.join("notes.txt"), "not a template")?;
+        let bundle = ProfileBundle::from_toml(&export_profile("team")?.to_toml()?)?;
+        assert_eq!(
+            bundle.templates.keys().collect::<Vec<_>>(),
+            vec!["base-rust"]
+        );
+
+        // Restored where missing; identical ones don't count as conflicts
+        fs::remove_file(dir.join("base-rust.toml"))?;
+        import_profile(&bundle, "copy", false)?;
+        assert_eq!(
+            fs::read_to_string(dir.join("base-rust.toml"))?,
+            bundle.templates["base-rust"]
+        );
+        import_profile(&bundle, "other", false)?;
+
+        // A changed template needs --force, and nothing is written without it
+        fs::write(dir.join("base-rust.toml"), "# mine\n")?;
+        let err = import_profile(&bundle, "third", false).unwrap_err();
+        assert!(err.to_string().contains("base-rust"));
+        assert!(!list_profiles()?.contains(&"third".to_string()));
+        assert_eq!(fs::read_to_string(dir.join("base-rust.toml"))?, "# mine\n");
+        import_profile(&bundle, "third", true)?;
+        assert_eq!(
+            fs::read_to_string(dir.join("base-rust.toml"))?,
+            bundle.templates["base-rust"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_rejects_template_paths() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+        let bundle = ProfileBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            aoe_version: String::new(),
+            profile: "x".to_string(),
+            config: ProfileConfig::default(),
+            templates: BTreeMap::from([("../escape".to_string(), String::new())]),
+        };
+        assert!(import_profile(&bundle, "x", true).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_newer_format() {
+        let toml = format!(
+            "format_version = {}\nprofile = \"x\"\n",
+            BUNDLE_FORMAT_VERSION + 1
+        );
+        assert!(ProfileBundle::from_toml(&toml).is_err());
+        assert!(ProfileBundle::from_toml("not toml [").is_err());
+    }
+
+    #[test]
+    fn test_hook_commands() {
+        let bundle = ProfileBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            aoe_version: String::new(),
+            profile: "x".to_string(),
+            templates: BTreeMap::new(),
+            config: ProfileConfig {
+                hooks: Some(HooksConfigOverride {
+                    on_create: Some(vec!["npm install".to_string()]),
+                    on_launch: Some(vec!["echo hi".to_string()]),
+                    health_check: Some("cargo check".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        };
+        assert_eq!(
+            bundle.hook_commands(),
+            vec!["npm install", "echo hi", "cargo check"]
+        );
+    }
+}
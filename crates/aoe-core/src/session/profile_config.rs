@@ -9,7 +9,9 @@ use std::collections::HashMap;
 use std::fs;
 
 use super::config::{
-    Config, ContainerRuntimeName, DefaultTerminalMode, TmuxMouseMode, TmuxStatusBarMode,
+    BudgetAction, ColorVision, Config, ContainerRuntimeName, DefaultTerminalMode,
+    HostSandboxProfile, HyperlinkMode, IoPriority, MergedSessionAction, ShareDestination,
+    TmuxMouseMode, TmuxStatusBarMode, TmuxWindowSize,
 };
 use super::get_profile_dir;
 
@@ -25,6 +27,9 @@ pub struct ProfileConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub updates: Option<UpdatesConfigOverride>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub polling: Option<PollingConfigOverride>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub worktree: Option<WorktreeConfigOverride>,
 
@@ -42,12 +47,39 @@ pub struct ProfileConfig {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sound: Option<crate::sound::SoundConfigOverride>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notifications: Option<crate::notifications::NotificationConfigOverride>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub share: Option<ShareConfigOverride>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ThemeConfigOverride {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_format: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_vision: Option<ColorVision>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hyperlinks: Option<HyperlinkMode>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spellcheck: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_min_width: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_details_min_width: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal_title: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -69,6 +101,42 @@ pub struct UpdatesConfigOverride {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub notify_in_cli: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_agents: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_upgrade_commands: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PollingConfigOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pane_capture_ms: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_check_secs: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_usage_secs: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eco_mode: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShareConfigOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination: Option<ShareDestination>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub s3_bucket: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub s3_link_hours: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gist_public: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -93,6 +161,42 @@ pub struct WorktreeConfigOverride {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub workspace_path_template: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repos: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sparse_paths: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge_test_command: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync_notes: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_merged: Option<MergedSessionAction>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_tag_pattern: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_ticket_pattern: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket_url_template: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_size: Option<usize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clone_dir: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shallow_clone: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conflict_prompt: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -135,6 +239,12 @@ pub struct SandboxConfigOverride {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub container_runtime: Option<ContainerRuntimeName>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_profile: Option<HostSandboxProfile>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_block_network: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -144,6 +254,9 @@ pub struct TmuxConfigOverride {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mouse: Option<TmuxMouseMode>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_size: Option<TmuxWindowSize>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -151,6 +264,12 @@ pub struct SessionConfigOverride {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_tool: Option<String>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_tool: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub yolo_mode_default: Option<bool>,
 
@@ -159,6 +278,66 @@ pub struct SessionConfigOverride {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub agent_command_override: Option<HashMap<String, String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trash_retention_days: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trash_max_entries: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locks: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_guardrails: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nice: Option<u8>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub io_priority: Option<IoPriority>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_limit: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_budget: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weekly_budget: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_warn_percent: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_action: Option<BudgetAction>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_expiry: Option<HashMap<String, String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_warn_days: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_refresh_command: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_shim: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_env: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_limits: Option<HashMap<String, usize>>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -168,6 +347,15 @@ pub struct HooksConfigOverride {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_launch: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_check_interval_secs: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_watch: Option<String>,
 }
 
 /// Load profile-specific config. Returns empty config if file doesn't exist.
@@ -188,7 +376,13 @@ pub fn load_profile_config(profile: &str) -> Result<ProfileConfig> {
 pub fn save_profile_config(profile: &str, config: &ProfileConfig) -> Result<()> {
     let path = get_profile_config_path(profile)?;
     let content = toml::to_string_pretty(config)?;
-    fs::write(&path, content)?;
+    let previous = fs::read_to_string(&path).ok();
+    fs::write(&path, &content)?;
+    super::audit::record_config_change(
+        &format!("profile '{}' config", profile),
+        previous.as_deref(),
+        &content,
+    );
     Ok(())
 }
 
@@ -202,12 +396,15 @@ pub fn profile_has_overrides(config: &ProfileConfig) -> bool {
     config.theme.is_some()
         || config.claude.is_some()
         || config.updates.is_some()
+        || config.polling.is_some()
         || config.worktree.is_some()
         || config.sandbox.is_some()
         || config.tmux.is_some()
         || config.session.is_some()
         || config.hooks.is_some()
         || config.sound.is_some()
+        || config.notifications.is_some()
+        || config.share.is_some()
 }
 
 /// Load effective config for a profile (global + profile overrides merged)
@@ -261,6 +458,12 @@ pub fn apply_sandbox_overrides(
     if let Some(container_runtime) = source.container_runtime {
         target.container_runtime = container_runtime;
     }
+    if let Some(host_profile) = source.host_profile {
+        target.host_profile = host_profile;
+    }
+    if let Some(host_block_network) = source.host_block_network {
+        target.host_block_network = host_block_network;
+    }
 }
 
 /// Apply worktree config overrides to a target config.
@@ -289,6 +492,42 @@ pub fn apply_worktree_overrides(
     if let Some(ref workspace_path_template) = source.workspace_path_template {
         target.workspace_path_template = workspace_path_template.clone();
     }
+    if let Some(ref repos) = source.repos {
+        target.repos = repos.clone();
+    }
+    if let Some(ref merge_test_command) = source.merge_test_command {
+        target.merge_test_command = Some(merge_test_command.clone());
+    }
+    if let Some(sync_notes) = source.sync_notes {
+        target.sync_notes = sync_notes;
+    }
+    if let Some(on_merged) = source.on_merged {
+        target.on_merged = on_merged;
+    }
+    if let Some(ref branch_tag_pattern) = source.branch_tag_pattern {
+        target.branch_tag_pattern = branch_tag_pattern.clone();
+    }
+    if let Some(ref branch_ticket_pattern) = source.branch_ticket_pattern {
+        target.branch_ticket_pattern = branch_ticket_pattern.clone();
+    }
+    if let Some(ref ticket_url_template) = source.ticket_url_template {
+        target.ticket_url_template = ticket_url_template.clone();
+    }
+    if let Some(ref sparse_paths) = source.sparse_paths {
+        target.sparse_paths = sparse_paths.clone();
+    }
+    if let Some(pool_size) = source.pool_size {
+        target.pool_size = pool_size;
+    }
+    if let Some(ref clone_dir) = source.clone_dir {
+        target.clone_dir = clone_dir.clone();
+    }
+    if let Some(shallow_clone) = source.shallow_clone {
+        target.shallow_clone = shallow_clone;
+    }
+    if let Some(ref conflict_prompt) = source.conflict_prompt {
+        target.conflict_prompt = conflict_prompt.clone();
+    }
 }
 
 /// Apply hooks config overrides to a target config.
@@ -302,6 +541,15 @@ pub fn apply_hooks_overrides(
     if let Some(ref on_launch) = source.on_launch {
         target.on_launch = on_launch.clone();
     }
+    if source.health_check.is_some() {
+        target.health_check = source.health_check.clone();
+    }
+    if let Some(secs) = source.health_check_interval_secs {
+        target.health_check_interval_secs = secs;
+    }
+    if source.test_watch.is_some() {
+        target.test_watch = source.test_watch.clone();
+    }
 }
 
 /// Apply session config overrides to a target config.
@@ -312,6 +560,12 @@ pub fn apply_session_overrides(
     if source.default_tool.is_some() {
         target.default_tool = source.default_tool.clone();
     }
+    if source.fallback_tool.is_some() {
+        target.fallback_tool = source.fallback_tool.clone();
+    }
+    if source.icon.is_some() {
+        target.icon = source.icon.clone();
+    }
     if let Some(yolo_mode_default) = source.yolo_mode_default {
         target.yolo_mode_default = yolo_mode_default;
     }
@@ -321,6 +575,66 @@ pub fn apply_session_overrides(
     if let Some(ref overrides) = source.agent_command_override {
         target.agent_command_override = overrides.clone();
     }
+    if let Some(days) = source.trash_retention_days {
+        target.trash_retention_days = days;
+    }
+    if let Some(max) = source.trash_max_entries {
+        target.trash_max_entries = max;
+    }
+    if let Some(ref locks) = source.locks {
+        target.locks = locks.clone();
+    }
+    if let Some(ref guardrails) = source.prompt_guardrails {
+        target.prompt_guardrails = guardrails.clone();
+    }
+    if let Some(nice) = source.nice {
+        target.nice = nice;
+    }
+    if let Some(io_priority) = source.io_priority {
+        target.io_priority = io_priority;
+    }
+    if source.memory_limit.is_some() {
+        target.memory_limit = source.memory_limit.clone();
+    }
+    if source.cpu_limit.is_some() {
+        target.cpu_limit = source.cpu_limit.clone();
+    }
+    if source.proxy.is_some() {
+        target.proxy = source.proxy.clone();
+    }
+    if source.no_proxy.is_some() {
+        target.no_proxy = source.no_proxy.clone();
+    }
+    if let Some(budget) = source.daily_budget {
+        target.daily_budget = budget;
+    }
+    if let Some(budget) = source.weekly_budget {
+        target.weekly_budget = budget;
+    }
+    if let Some(percent) = source.budget_warn_percent {
+        target.budget_warn_percent = percent;
+    }
+    if let Some(action) = source.budget_action {
+        target.budget_action = action;
+    }
+    if let Some(ref expiry) = source.credential_expiry {
+        target.credential_expiry = expiry.clone();
+    }
+    if let Some(ref limits) = source.provider_limits {
+        target.provider_limits = limits.clone();
+    }
+    if let Some(days) = source.credential_warn_days {
+        target.credential_warn_days = days;
+    }
+    if source.credential_refresh_command.is_some() {
+        target.credential_refresh_command = source.credential_refresh_command.clone();
+    }
+    if let Some(agent_shim) = source.agent_shim {
+        target.agent_shim = agent_shim;
+    }
+    if let Some(project_env) = source.project_env {
+        target.project_env = project_env;
+    }
 }
 
 /// Apply tmux config overrides to a target config.
@@ -331,6 +645,47 @@ pub fn apply_tmux_overrides(target: &mut super::config::TmuxConfig, source: &Tmu
     if let Some(mouse) = source.mouse {
         target.mouse = mouse;
     }
+    if let Some(window_size) = source.window_size {
+        target.window_size = window_size;
+    }
+}
+
+/// Apply share config overrides to a target config.
+pub fn apply_share_overrides(
+    target: &mut super::config::ShareConfig,
+    source: &ShareConfigOverride,
+) {
+    if let Some(destination) = source.destination {
+        target.destination = destination;
+    }
+    if source.s3_bucket.is_some() {
+        target.s3_bucket = source.s3_bucket.clone();
+    }
+    if let Some(hours) = source.s3_link_hours {
+        target.s3_link_hours = hours;
+    }
+    if let Some(public) = source.gist_public {
+        target.gist_public = public;
+    }
+}
+
+/// Apply polling config overrides to a target config.
+pub fn apply_polling_overrides(
+    target: &mut super::config::PollingConfig,
+    source: &PollingConfigOverride,
+) {
+    if let Some(ms) = source.pane_capture_ms {
+        target.pane_capture_ms = ms;
+    }
+    if let Some(secs) = source.git_check_secs {
+        target.git_check_secs = secs;
+    }
+    if let Some(secs) = source.resource_usage_secs {
+        target.resource_usage_secs = secs;
+    }
+    if let Some(eco_mode) = source.eco_mode {
+        target.eco_mode = eco_mode;
+    }
 }
 
 /// Merge profile overrides into global config
@@ -339,6 +694,27 @@ pub fn merge_configs(mut global: Config, profile: &ProfileConfig) -> Config {
         if let Some(ref name) = theme_override.name {
             global.theme.name = name.clone();
         }
+        if theme_override.row_format.is_some() {
+            global.theme.row_format = theme_override.row_format.clone();
+        }
+        if let Some(vision) = theme_override.color_vision {
+            global.theme.color_vision = vision;
+        }
+        if let Some(mode) = theme_override.hyperlinks {
+            global.theme.hyperlinks = mode;
+        }
+        if let Some(spellcheck) = theme_override.spellcheck {
+            global.theme.spellcheck = spellcheck;
+        }
+        if let Some(width) = theme_override.preview_min_width {
+            global.theme.preview_min_width = width;
+        }
+        if let Some(width) = theme_override.row_details_min_width {
+            global.theme.row_details_min_width = width;
+        }
+        if let Some(ref title) = theme_override.terminal_title {
+            global.theme.terminal_title = title.clone();
+        }
     }
 
     if let Some(ref claude_override) = profile.claude {
@@ -360,6 +736,16 @@ pub fn merge_configs(mut global: Config, profile: &ProfileConfig) -> Config {
         if let Some(notify_in_cli) = updates_override.notify_in_cli {
             global.updates.notify_in_cli = notify_in_cli;
         }
+        if let Some(check_agents) = updates_override.check_agents {
+            global.updates.check_agents = check_agents;
+        }
+        if let Some(ref commands) = updates_override.agent_upgrade_commands {
+            global.updates.agent_upgrade_commands = commands.clone();
+        }
+    }
+
+    if let Some(ref polling_override) = profile.polling {
+        apply_polling_overrides(&mut global.polling, polling_override);
     }
 
     if let Some(ref worktree_override) = profile.worktree {
@@ -386,9 +772,39 @@ pub fn merge_configs(mut global: Config, profile: &ProfileConfig) -> Config {
         crate::sound::apply_sound_overrides(&mut global.sound, sound_override);
     }
 
+    if let Some(ref notifications_override) = profile.notifications {
+        crate::notifications::apply_notification_overrides(
+            &mut global.notifications,
+            notifications_override,
+        );
+    }
+
+    if let Some(ref share_override) = profile.share {
+        apply_share_overrides(&mut global.share, share_override);
+    }
+
     global
 }
 
+/// Validate a session icon: one emoji or glyph, which may be a sequence
+/// such as a flag or a skin-toned emoji, but no text or whitespace
+pub fn validate_icon(icon: &str) -> Result<(), String> {
+    if icon.is_empty() {
+        return Ok(());
+    }
+    if icon.chars().any(char::is_whitespace) {
+        return Err("Icon cannot contain whitespace".to_string());
+    }
+    if icon.chars().count() > MAX_ICON_CHARS {
+        return Err("Icon must be a single emoji or glyph".to_string());
+    }
+    Ok(())
+}
+
+/// Enough for emoji built from several code points (ZWJ sequences, skin
+/// tones, flags) while ruling out words
+const MAX_ICON_CHARS: usize = 7;
+
 /// Validate a path exists (for config_dir validation)
 pub fn validate_path_exists(path: &str) -> Result<(), String> {
     if path.is_empty() {
@@ -453,6 +869,40 @@ pub fn validate_check_interval(hours: u64) -> Result<(), String> {
     }
 }
 
+/// Pane captures faster than this cost more CPU than they save in latency
+pub const MIN_PANE_CAPTURE_MS: u64 = 100;
+
+pub fn validate_pane_capture_interval(ms: u64) -> Result<(), String> {
+    if ms < MIN_PANE_CAPTURE_MS {
+        Err(format!(
+            "Pane capture interval must be at least {} ms",
+            MIN_PANE_CAPTURE_MS
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn validate_poll_interval(secs: u64) -> Result<(), String> {
+    if secs == 0 {
+        Err("Polling interval must be greater than 0".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+pub fn validate_health_check_interval(secs: u64) -> Result<(), String> {
+    let min = super::repo_config::MIN_HEALTH_CHECK_INTERVAL_SECS;
+    if secs < min {
+        Err(format!(
+            "Health check interval must be at least {} seconds",
+            min
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -549,6 +999,25 @@ mod tests {
         assert!(merged.worktree.enabled);
     }
 
+    #[test]
+    fn test_merge_polling_overrides() {
+        let profile = ProfileConfig {
+            polling: Some(PollingConfigOverride {
+                pane_capture_ms: Some(1000),
+                eco_mode: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let merged = merge_configs(Config::default(), &profile);
+        assert_eq!(merged.polling.pane_capture_ms, 1000);
+        assert!(merged.polling.eco_mode);
+        assert_eq!(merged.polling.git_check_secs, 300);
+        assert!(profile_has_overrides(&profile));
+        assert!(validate_pane_capture_interval(50).is_err());
+        assert!(validate_poll_interval(0).is_err());
+    }
+
     #[test]
     fn test_profile_has_overrides() {
         let empty = ProfileConfig::default();
@@ -557,6 +1026,7 @@ mod tests {
         let with_override = ProfileConfig {
             theme: Some(ThemeConfigOverride {
                 name: Some("dark".to_string()),
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -573,6 +1043,17 @@ mod tests {
         assert!(validate_volume_format("/host:").is_err());
     }
 
+    #[test]
+    fn test_validate_icon() {
+        assert!(validate_icon("").is_ok());
+        assert!(validate_icon("🚀").is_ok());
+        assert!(validate_icon("\u{f121}").is_ok());
+        // Family emoji: four people joined by zero-width joiners
+        assert!(validate_icon("👨\u{200d}👩\u{200d}👧\u{200d}👦").is_ok());
+        assert!(validate_icon("🚀 x").is_err());
+        assert!(validate_icon("frontend").is_err());
+    }
+
     #[test]
     fn test_validate_memory_limit() {
         assert!(validate_memory_limit("").is_ok());
@@ -616,6 +1097,7 @@ mod tests {
             tmux: Some(TmuxConfigOverride {
                 status_bar: Some(TmuxStatusBarMode::Enabled),
                 mouse: None,
+                window_size: None,
             }),
             ..Default::default()
         };
@@ -707,6 +1189,7 @@ mod tests {
             tmux: Some(TmuxConfigOverride {
                 status_bar: Some(TmuxStatusBarMode::Enabled),
                 mouse: Some(TmuxMouseMode::Enabled),
+                window_size: Some(TmuxWindowSize::Largest),
             }),
             ..Default::default()
         };
@@ -714,6 +1197,7 @@ mod tests {
         let serialized = toml::to_string_pretty(&config).unwrap();
         assert!(serialized.contains("[tmux]"));
         assert!(serialized.contains(r#"mouse = "enabled""#));
+        assert!(serialized.contains(r#"window_size = "largest""#));
 
         let deserialized: ProfileConfig = toml::from_str(&serialized).unwrap();
         assert_eq!(
@@ -728,6 +1212,7 @@ mod tests {
         let profile = ProfileConfig {
             theme: Some(ThemeConfigOverride {
                 name: Some("tokyo-night".to_string()),
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -0,0 +1,217 @@
+//! A project's direnv or mise environment for its session
+//!
+//! With `session.project_env` enabled, a host session whose project has an
+//! `.envrc` (direnv) or a `mise.toml` / `.mise.toml` (mise) starts its agent
+//! with that environment, as if it had been launched from a shell with the
+//! direnv or mise hook. The variables are also set on the tmux session, so
+//! windows opened in it later see them too. The tools' own trust checks
+//! apply: an `.envrc` that was never `direnv allow`ed is skipped with a
+//! warning.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::environment::shell_single_quote;
+
+/// Variables direnv keeps its own state in
+const DIRENV_STATE_PREFIX: &str = "DIRENV_";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvTool {
+    Direnv,
+    Mise,
+}
+
+impl EnvTool {
+    /// The tool whose config `dir` has. direnv wins when both are present,
+    /// since an `.envrc` usually activates mise itself.
+    pub fn detect(dir: &Path) -> Option<Self> {
+        if dir.join(".envrc").is_file() {
+            Some(Self::Direnv)
+        } else if dir.join("mise.toml").is_file() || dir.join(".mise.toml").is_file() {
+            Some(Self::Mise)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Direnv => "direnv",
+            Self::Mise => "mise",
+        }
+    }
+
+    fn export_command(self, dir: &Path) -> Command {
+        let mut command = match self {
+            Self::Direnv => {
+                let mut command = Command::new("direnv");
+                command.args(["export", "json"]);
+                // Export everything even if aoe itself runs with the project's
+                // environment loaded; direnv would only report changes.
+                for key in ["DIRENV_DIR", "DIRENV_FILE", "DIRENV_DIFF", "DIRENV_WATCHES"] {
+                    command.env_remove(key);
+                }
+                command
+            }
+            Self::Mise => {
+                let mut command = Command::new("mise");
+                command.args(["env", "--json"]);
+                command
+            }
+        };
+        command.current_dir(dir);
+        command
+    }
+}
+
+/// What was applied to a session, kept so the TUI can show it. The values
+/// themselves are not stored, since `.envrc` files often hold secrets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectEnv {
+    pub tool: EnvTool,
+    /// Number of variables set or unset
+    pub vars: usize,
+}
+
+impl ProjectEnv {
+    pub fn label(&self) -> String {
+        format!(
+            "{} ({} var{})",
+            self.tool.name(),
+            self.vars,
+            if self.vars == 1 { "" } else { "s" }
+        )
+    }
+}
+
+/// A project's environment, as variables to set (`Some`) or unset (`None`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadedEnv {
+    pub tool: EnvTool,
+    pub vars: BTreeMap<String, Option<String>>,
+}
+
+impl LoadedEnv {
+    /// Export the environment of the project at `dir`. `Ok(None)` when it
+    /// has neither an `.envrc` nor a mise config.
+    pub fn load(dir: &Path) -> Result<Option<Self>> {
+        let Some(tool) = EnvTool::detect(dir) else {
+            return Ok(None);
+        };
+        let output = tool
+            .export_command(dir)
+            .output()
+            .with_context(|| format!("Failed to run {}; is it installed?", tool.name()))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!(
+                "{} could not load the project environment: {}",
+                tool.name(),
+                stderr.trim()
+            );
+        }
+        let vars = parse_export(&String::from_utf8_lossy(&output.stdout))
+            .with_context(|| format!("Unexpected output from {}", tool.name()))?;
+        Ok(Some(Self { tool, vars }))
+    }
+
+    pub fn summary(&self) -> ProjectEnv {
+        ProjectEnv {
+            tool: self.tool,
+            vars: self
+                .vars
+                .keys()
+                .filter(|key| !key.starts_with(DIRENV_STATE_PREFIX))
+                .count(),
+        }
+    }
+
+    /// Wrap a tmux session command so it runs with the environment applied.
+    pub fn wrap(&self, cmd: &str) -> String {
+        // env takes options only before the first assignment
+        let unset = self
+            .vars
+            .iter()
+            .filter(|(_, value)| value.is_none())
+            .map(|(key, _)| format!("-u {}", key));
+        let set = self.vars.iter().filter_map(|(key, value)| {
+            value
+                .as_ref()
+                .map(|value| format!("{}={}", key, shell_single_quote(value)))
+        });
+        let args: Vec<String> = unset.chain(set).collect();
+        if args.is_empty() {
+            return cmd.to_string();
+        }
+        format!("env {} {}", args.join(" "), cmd)
+    }
+
+    /// Set the variables on tmux session `name`, for windows opened later.
+    pub fn apply_to_tmux(&self, name: &str) {
+        for (key, value) in &self.vars {
+            let mut args = vec!["set-environment", "-t", name];
+            match value {
+                Some(value) => args.extend([key.as_str(), value.as_str()]),
+                None => args.extend(["-r", key.as_str()]),
+            }
+            if let Err(e) = Command::new("tmux").args(&args).output() {
+                tracing::warn!("Failed to set {} on tmux session {}: {}", key, name, e);
+            }
+        }
+    }
+}
+
+/// `direnv export json` and `mise env --json` both print an object of
+/// variable names to values; direnv uses `null` for variables to unset and
+/// prints nothing at all when there is nothing to change.
+fn parse_export(output: &str) -> Result<BTreeMap<String, Option<String>>> {
+    if output.trim().is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    Ok(serde_json::from_str(output)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_prefers_direnv() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert_eq!(EnvTool::detect(tmp.path()), None);
+        std::fs::write(tmp.path().join(".mise.toml"), "[env]\n").unwrap();
+        assert_eq!(EnvTool::detect(tmp.path()), Some(EnvTool::Mise));
+        std::fs::write(tmp.path().join(".envrc"), "use mise\n").unwrap();
+        assert_eq!(EnvTool::detect(tmp.path()), Some(EnvTool::Direnv));
+    }
+
+    #[test]
+    fn test_export_wraps_command_and_counts_vars() {
+        let vars = parse_export(
+            r#"{"DATABASE_URL":"postgres://localhost/app dev","OLD":null,"DIRENV_DIR":"-/app"}"#,
+        )
+        .unwrap();
+        let env = LoadedEnv {
+            tool: EnvTool::Direnv,
+            vars,
+        };
+        assert_eq!(
+            env.wrap("claude"),
+            "env -u OLD DATABASE_URL='postgres://localhost/app dev' DIRENV_DIR='-/app' claude"
+        );
+        assert_eq!(env.summary().label(), "direnv (2 vars)");
+
+        let empty = LoadedEnv {
+            tool: EnvTool::Direnv,
+            vars: parse_export("").unwrap(),
+        };
+        assert_eq!(empty.wrap("claude"), "claude");
+        assert!(parse_export("export FOO=bar").is_err());
+    }
+}
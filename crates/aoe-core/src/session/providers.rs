@@ -0,0 +1,175 @@
+//! Per-provider session caps
+//!
+//! Every session's agent talks to a model provider: Claude Code to
+//! Anthropic, Codex to OpenAI, and so on (see `Instance::provider`).
+//! `session.provider_limits` (`provider = count`) caps how many sessions of
+//! one provider may be active at once, to stay inside its rate limits. A
+//! session counts while it is starting, running, or waiting for input. The
+//! TUI queues a session over its provider's cap until another one finishes,
+//! and `aoe session start` refuses it. Like locks, caps count sessions in
+//! every profile.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use super::config::SessionConfig;
+use super::{list_profiles, locks, Instance, Storage};
+use crate::agents::Provider;
+
+/// A provider at its cap, keeping another of its sessions from starting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitReached {
+    pub provider: Provider,
+    pub limit: usize,
+}
+
+/// Caps from `session.provider_limits`, skipping unknown providers.
+pub fn limits(config: &SessionConfig) -> HashMap<Provider, usize> {
+    config
+        .provider_limits
+        .iter()
+        .filter_map(|(name, limit)| Some((Provider::from_name(name)?, *limit)))
+        .collect()
+}
+
+/// Check `provider=count` entries as edited in the settings.
+pub fn validate(entries: &[String]) -> Result<(), String> {
+    for entry in entries {
+        let Some((provider, limit)) = entry.split_once('=') else {
+            return Err(format!("Expected provider=count, got \"{}\"", entry));
+        };
+        if Provider::from_name(provider).is_none() {
+            let known: Vec<&str> = Provider::ALL.iter().map(|p| p.name()).collect();
+            return Err(format!(
+                "Unknown provider \"{}\" (one of {})",
+                provider.trim(),
+                known.join(", ")
+            ));
+        }
+        if limit.trim().parse::<usize>().is_err() {
+            return Err(format!("\"{}\" is not a number of sessions", limit.trim()));
+        }
+    }
+    Ok(())
+}
+
+/// Active sessions per provider, in `Provider` order, leaving out providers
+/// with none.
+pub fn active_counts(instances: &[Instance]) -> Vec<(Provider, usize)> {
+    let mut counts: Vec<(Provider, usize)> = Provider::ALL.iter().map(|p| (*p, 0)).collect();
+    for inst in instances.iter().filter(|i| locks::holds_locks(i.status)) {
+        if let Some(count) = counts.iter_mut().find(|(p, _)| *p == inst.provider()) {
+            count.1 += 1;
+        }
+    }
+    counts.retain(|(_, count)| *count > 0);
+    counts
+}
+
+/// The cap `candidate` would go over by starting, if any.
+pub fn limit_reached(
+    candidate: &Instance,
+    instances: &[Instance],
+    limits: &HashMap<Provider, usize>,
+) -> Option<LimitReached> {
+    let provider = candidate.provider();
+    let limit = *limits.get(&provider)?;
+    let active = instances
+        .iter()
+        .filter(|other| {
+            other.id != candidate.id
+                && locks::holds_locks(other.status)
+                && other.provider() == provider
+        })
+        .count();
+    (active >= limit).then_some(LimitReached { provider, limit })
+}
+
+/// Fail with a readable message if `candidate`'s provider is at its cap in
+/// `config`.
+pub fn ensure_can_start(candidate: &Instance, config: &SessionConfig) -> Result<()> {
+    let limits = limits(config);
+    if !limits.contains_key(&candidate.provider()) {
+        return Ok(());
+    }
+    crate::tmux::refresh_session_cache();
+    let mut all = Vec::new();
+    for profile in list_profiles()? {
+        for mut inst in Storage::new(&profile)?.load()? {
+            inst.update_status();
+            all.push(inst);
+        }
+    }
+    if let Some(reached) = limit_reached(candidate, &all, &limits) {
+        anyhow::bail!(
+            "{} sessions are at their limit of {}. Start \"{}\" after one stops or goes idle, \
+             or start it from the TUI to queue it\n\
+             Tip: Raise session.provider_limits.{} in the settings",
+            reached.provider.name(),
+            reached.limit,
+            candidate.title,
+            reached.provider.name()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::Status;
+
+    fn session(title: &str, tool: &str, status: Status) -> Instance {
+        let mut inst = Instance::new(title, "/tmp/project");
+        inst.tool = tool.to_string();
+        inst.status = status;
+        inst
+    }
+
+    #[test]
+    fn test_limit_counts_active_sessions_of_the_provider() {
+        let limits = HashMap::from([(Provider::Anthropic, 2)]);
+        let candidate = session("c", "claude", Status::Stopped);
+        let mut instances = vec![
+            session("a", "claude", Status::Running),
+            session("b", "claude", Status::Idle),
+            session("x", "codex", Status::Running),
+            candidate.clone(),
+        ];
+        assert!(limit_reached(&candidate, &instances, &limits).is_none());
+
+        instances[1].status = Status::Waiting;
+        assert_eq!(
+            limit_reached(&candidate, &instances, &limits),
+            Some(LimitReached {
+                provider: Provider::Anthropic,
+                limit: 2
+            })
+        );
+
+        // Told it runs against a local model, it no longer counts as Anthropic
+        let mut local = candidate.clone();
+        local.provider = Some(Provider::Local);
+        assert!(limit_reached(&local, &instances, &limits).is_none());
+
+        assert_eq!(
+            active_counts(&instances),
+            vec![(Provider::Anthropic, 2), (Provider::OpenAI, 1)]
+        );
+    }
+
+    #[test]
+    fn test_validate_and_parse_limits() {
+        assert!(validate(&["anthropic=3".to_string(), "OpenAI = 1".to_string()]).is_ok());
+        assert!(validate(&["anthropic".to_string()]).is_err());
+        assert!(validate(&["acme=3".to_string()]).is_err());
+        assert!(validate(&["local=many".to_string()]).is_err());
+
+        let config = SessionConfig {
+            provider_limits: HashMap::from([("openai".to_string(), 1), ("acme".to_string(), 3)]),
+            ..Default::default()
+        };
+        assert_eq!(limits(&config), HashMap::from([(Provider::OpenAI, 1)]));
+    }
+}
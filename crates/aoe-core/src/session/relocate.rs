@@ -0,0 +1,250 @@
+//! Moving aoe's data into this platform's location (`aoe config migrate`)
+//!
+//! Linux keeps config, profiles, and session data under
+//! `$XDG_CONFIG_HOME/agent-of-empires`, macOS under `~/.agent-of-empires`.
+//! A directory copied from another machine, or left behind by an older aoe,
+//! sits in the other place and is silently ignored. Relocating moves its
+//! contents into the right place, merging directory by directory: entries
+//! that already exist there are reported as conflicts and left alone.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// Where aoe data for another platform (or an older aoe) would live on this
+/// machine. Never the current app directory.
+pub fn other_app_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    #[cfg(target_os = "linux")]
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join(".agent-of-empires"));
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        // dirs::config_dir() is ~/Library/Application Support on macOS, not
+        // where a Linux setup would put it
+        let config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".config")));
+        if let Some(config_home) = config_home {
+            dirs.push(config_home.join("agent-of-empires"));
+        }
+    }
+
+    dirs
+}
+
+/// A file or directory that does not exist in the target yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Plan {
+    pub source: PathBuf,
+    pub target: PathBuf,
+    pub moves: Vec<Move>,
+    /// Files present in both places; the target's copy is kept
+    pub conflicts: Vec<PathBuf>,
+}
+
+impl Plan {
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty() && self.conflicts.is_empty()
+    }
+}
+
+/// What moving `source` into `target` would do. `None` when there is
+/// nothing at `source`, or it already is (or links to) `target`.
+pub fn plan(source: &Path, target: &Path) -> Result<Option<Plan>> {
+    if !source.is_dir() {
+        return Ok(None);
+    }
+    if let (Ok(a), Ok(b)) = (source.canonicalize(), target.canonicalize()) {
+        if a == b {
+            return Ok(None);
+        }
+    }
+    let mut plan = Plan {
+        source: source.to_path_buf(),
+        target: target.to_path_buf(),
+        ..Plan::default()
+    };
+    merge(source, target, &mut plan)?;
+    Ok(Some(plan))
+}
+
+fn merge(source: &Path, target: &Path, plan: &mut Plan) -> Result<()> {
+    let mut entries = fs::read_dir(source)
+        .with_context(|| format!("Failed to read {}", source.display()))?
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let from = entry.path();
+        let to = target.join(entry.file_name());
+        let is_dir = entry.file_type()?.is_dir();
+        if to.symlink_metadata().is_err() {
+            plan.moves.push(Move { from, to });
+        } else if is_dir && to.is_dir() {
+            merge(&from, &to, plan)?;
+        } else {
+            plan.conflicts.push(from);
+        }
+    }
+    Ok(())
+}
+
+/// Carry out `plan`. Once `source` is empty it is removed, or with
+/// `symlink` replaced by a link to the target so anything still pointing
+/// at the old place keeps working. Returns whether `source` was cleared.
+pub fn apply(plan: &Plan, symlink: bool) -> Result<bool> {
+    fs::create_dir_all(&plan.target)?;
+    for Move { from, to } in &plan.moves {
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        move_entry(from, to)
+            .with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))?;
+    }
+    if !plan.conflicts.is_empty() {
+        return Ok(false);
+    }
+
+    remove_empty_dirs(&plan.source)?;
+    if plan.source.exists() {
+        return Ok(false);
+    }
+    if symlink {
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&plan.target, &plan.source).with_context(|| {
+            format!(
+                "Failed to link {} to the new location",
+                plan.source.display()
+            )
+        })?;
+    }
+    Ok(true)
+}
+
+/// A rename, or a copy and delete when the two places are on different
+/// filesystems
+fn move_entry(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    if from.is_dir() {
+        copy_dir(from, to)?;
+        fs::remove_dir_all(from)?;
+    } else {
+        fs::copy(from, to)?;
+        fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let dest = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &dest)?;
+        } else {
+            fs::copy(entry.path(), &dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove `dir` and its subdirectories if they hold no files
+fn remove_empty_dirs(dir: &Path) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            remove_empty_dirs(&entry.path())?;
+        }
+    }
+    if fs::read_dir(dir)?.next().is_none() {
+        fs::remove_dir(dir)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_relocate_merges_profiles_and_keeps_conflicts() {
+        let tmp = TempDir::new().unwrap();
+        let old = tmp.path().join("old");
+        let new = tmp.path().join("new");
+        write(&old.join("config.toml"), "old");
+        write(&old.join("profiles/work/sessions.json"), "work");
+        write(&old.join("profiles/default/sessions.json"), "old default");
+        write(&new.join("profiles/default/sessions.json"), "new default");
+
+        let plan = plan(&old, &new).unwrap().unwrap();
+        assert_eq!(
+            plan.moves,
+            vec![
+                Move {
+                    from: old.join("config.toml"),
+                    to: new.join("config.toml"),
+                },
+                Move {
+                    from: old.join("profiles/work"),
+                    to: new.join("profiles/work"),
+                },
+            ]
+        );
+        assert_eq!(
+            plan.conflicts,
+            vec![old.join("profiles/default/sessions.json")]
+        );
+
+        assert!(!apply(&plan, true).unwrap());
+        assert_eq!(
+            fs::read_to_string(new.join("profiles/work/sessions.json")).unwrap(),
+            "work"
+        );
+        assert_eq!(
+            fs::read_to_string(new.join("profiles/default/sessions.json")).unwrap(),
+            "new default"
+        );
+        // The conflicting file stays put, so the old directory does too
+        assert!(old.join("profiles/default/sessions.json").exists());
+        assert!(!old.join("config.toml").exists());
+    }
+
+    #[test]
+    fn test_relocate_links_old_location_once_empty() {
+        let tmp = TempDir::new().unwrap();
+        let old = tmp.path().join("old");
+        let new = tmp.path().join("new");
+        write(&old.join("profiles/default/sessions.json"), "[]");
+
+        let plan = plan(&old, &new).unwrap().unwrap();
+        assert!(apply(&plan, true).unwrap());
+        assert!(old.symlink_metadata().unwrap().file_type().is_symlink());
+        assert!(new.join("profiles/default/sessions.json").exists());
+
+        // Following the link leads back to the target: nothing left to do
+        assert_eq!(super::plan(&old, &new).unwrap(), None);
+        assert_eq!(
+            super::plan(&tmp.path().join("missing"), &new).unwrap(),
+            None
+        );
+    }
+}
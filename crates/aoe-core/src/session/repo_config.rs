@@ -7,6 +7,7 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc;
@@ -29,6 +30,10 @@ use super::profile_config::{
 /// Repository-level configuration loaded from `.aoe/config.toml`.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RepoConfig {
+    /// Shared template(s) from `<app_dir>/templates/` this config builds on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<Extends>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub hooks: Option<HooksConfig>,
 
@@ -49,6 +54,32 @@ pub struct RepoConfig {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sound: Option<crate::sound::SoundConfigOverride>,
+
+    /// Named commands offered as one-key actions on a session, keyed by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub commands: BTreeMap<String, String>,
+
+    /// Directory names the new session dialog's path completion skips
+    /// inside this repo (e.g. build output and dependency folders).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub completion_ignores: Vec<String>,
+}
+
+/// One template name or a list of them, applied in order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Extends {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Extends {
+    pub fn names(&self) -> &[String] {
+        match self {
+            Extends::One(name) => std::slice::from_ref(name),
+            Extends::Many(names) => names,
+        }
+    }
 }
 
 /// Hook commands to run at various lifecycle points.
@@ -58,7 +89,11 @@ pub struct RepoConfig {
 /// - `on_launch`: failures are logged as warnings but do not prevent the session
 ///   from starting, since blocking an existing session on a transient hook failure
 ///   would be disruptive.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// - `health_check`: a non-zero exit marks the session unhealthy in the list;
+///   the session itself is left alone.
+/// - `test_watch`: runs alongside the session for as long as it is alive; its
+///   pass/fail counts are shown in the list, and nothing fails if it does.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HooksConfig {
     /// Commands run once when a session is first created.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -67,36 +102,206 @@ pub struct HooksConfig {
     /// Commands run every time a session starts (failures are non-fatal).
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub on_launch: Vec<String>,
+
+    /// Probe command run periodically in the session's directory while the
+    /// session is alive (e.g. `curl -fs localhost:3000/health`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<String>,
+
+    /// Seconds between health check runs.
+    #[serde(
+        default = "default_health_check_interval",
+        skip_serializing_if = "is_default_health_check_interval"
+    )]
+    pub health_check_interval_secs: u64,
+
+    /// Test watcher kept running in a companion tmux session while the
+    /// session is alive (e.g. `cargo watch -c -x test`). The pass/fail counts
+    /// it prints are shown on the session's row.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_watch: Option<String>,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            on_create: Vec::new(),
+            on_launch: Vec::new(),
+            health_check: None,
+            health_check_interval_secs: default_health_check_interval(),
+            test_watch: None,
+        }
+    }
+}
+
+pub const DEFAULT_HEALTH_CHECK_INTERVAL_SECS: u64 = 60;
+
+fn default_health_check_interval() -> u64 {
+    DEFAULT_HEALTH_CHECK_INTERVAL_SECS
+}
+
+fn is_default_health_check_interval(secs: &u64) -> bool {
+    *secs == DEFAULT_HEALTH_CHECK_INTERVAL_SECS
 }
 
 impl HooksConfig {
     pub fn is_empty(&self) -> bool {
-        self.on_create.is_empty() && self.on_launch.is_empty()
+        self.on_create.is_empty()
+            && self.on_launch.is_empty()
+            && self.health_check.is_none()
+            && self.test_watch.is_none()
     }
 }
 
 /// Path to the repo config file relative to the project root.
-const REPO_CONFIG_PATH: &str = ".aoe/config.toml";
+pub const REPO_CONFIG_PATH: &str = ".aoe/config.toml";
 
-/// Load repo config from `<project_path>/.aoe/config.toml`.
+/// Load repo config from `<project_path>/.aoe/config.toml`, with any
+/// templates it `extends` layered underneath.
 /// Returns `None` if the file doesn't exist.
 pub fn load_repo_config(project_path: &Path) -> Result<Option<RepoConfig>> {
     let config_path = project_path.join(REPO_CONFIG_PATH);
-    if !config_path.exists() {
+    let Some(table) = read_config_table(&config_path)? else {
+        return Ok(None);
+    };
+
+    let table = resolve_extends(table, &mut Vec::new())
+        .with_context(|| format!("Failed to resolve templates for {}", config_path.display()))?;
+    let config: RepoConfig = toml::Value::Table(table)
+        .try_into()
+        .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+
+    Ok(Some(config))
+}
+
+/// Load `<project_path>/.aoe/config.toml` as written, without applying the
+/// templates it extends. Used by the settings TUI so saving doesn't copy
+/// template values into the repo file.
+pub fn load_repo_config_file(project_path: &Path) -> Result<Option<RepoConfig>> {
+    let config_path = project_path.join(REPO_CONFIG_PATH);
+    let Some(table) = read_config_table(&config_path)? else {
+        return Ok(None);
+    };
+
+    let config: RepoConfig = toml::Value::Table(table)
+        .try_into()
+        .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+
+    Ok(Some(config))
+}
+
+/// Parse repo config text, layering in the templates it `extends`, as
+/// `load_repo_config` would.
+pub fn parse_repo_config(content: &str) -> Result<RepoConfig> {
+    let table = content.parse::<toml::Table>()?;
+    let table = resolve_extends(table, &mut Vec::new())?;
+    Ok(toml::Value::Table(table).try_into()?)
+}
+
+fn read_config_table(path: &Path) -> Result<Option<toml::Table>> {
+    if !path.exists() {
         return Ok(None);
     }
 
-    let content = fs::read_to_string(&config_path)
-        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
 
     if content.trim().is_empty() {
         return Ok(None);
     }
 
-    let config: RepoConfig = toml::from_str(&content)
-        .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+    let table = content
+        .parse::<toml::Table>()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
 
-    Ok(Some(config))
+    Ok(Some(table))
+}
+
+/// Directory holding shared repo config templates.
+pub fn templates_dir() -> Result<PathBuf> {
+    Ok(super::get_app_dir()?.join("templates"))
+}
+
+/// Whether `name` can name a template: a plain file stem, not a path.
+pub fn is_valid_template_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['/', '\\']) && !name.starts_with('.')
+}
+
+/// Lists that accumulate across `extends` (parent entries first) instead of
+/// being replaced by the child's.
+const MERGED_LISTS: &[(&str, &str)] = &[
+    ("hooks", "on_create"),
+    ("hooks", "on_launch"),
+    ("sandbox", "environment"),
+    ("worktree", "repos"),
+];
+
+/// Layer the templates named by `table`'s `extends` key underneath it,
+/// recursively. `chain` holds the templates currently being resolved so a
+/// cycle is reported instead of recursing forever.
+fn resolve_extends(table: toml::Table, chain: &mut Vec<String>) -> Result<toml::Table> {
+    let names = match table.get("extends") {
+        None => return Ok(table),
+        Some(value) => Extends::deserialize(value.clone())
+            .context("`extends` must be a template name or a list of names")?,
+    };
+
+    let mut merged = toml::Table::new();
+    for name in names.names() {
+        if !is_valid_template_name(name) {
+            anyhow::bail!("Invalid template name '{}'", name);
+        }
+        if chain.contains(name) {
+            chain.push(name.clone());
+            anyhow::bail!("Template cycle: {}", chain.join(" -> "));
+        }
+
+        let path = templates_dir()?.join(format!("{}.toml", name));
+        if !path.exists() {
+            anyhow::bail!("Template '{}' not found at {}", name, path.display());
+        }
+        let parent = read_config_table(&path)?.unwrap_or_default();
+
+        chain.push(name.clone());
+        let parent = resolve_extends(parent, chain)?;
+        chain.pop();
+        merged = layer_tables(merged, parent);
+    }
+
+    Ok(layer_tables(merged, table))
+}
+
+/// Apply `child` on top of `base`: sections merge key by key, the child's
+/// value wins, and lists in `MERGED_LISTS` are concatenated without
+/// duplicates.
+fn layer_tables(mut base: toml::Table, child: toml::Table) -> toml::Table {
+    for (section, value) in child {
+        match (base.get_mut(&section), value) {
+            (Some(toml::Value::Table(base_section)), toml::Value::Table(child_section)) => {
+                for (key, value) in child_section {
+                    let merge_list = MERGED_LISTS.contains(&(section.as_str(), key.as_str()));
+                    match (base_section.get_mut(&key), value) {
+                        (Some(toml::Value::Array(items)), toml::Value::Array(extra))
+                            if merge_list =>
+                        {
+                            for item in extra {
+                                if !items.contains(&item) {
+                                    items.push(item);
+                                }
+                            }
+                        }
+                        (_, value) => {
+                            base_section.insert(key, value);
+                        }
+                    }
+                }
+            }
+            (_, value) => {
+                base.insert(section, value);
+            }
+        }
+    }
+    base
 }
 
 /// Save repo config to `<project_path>/.aoe/config.toml`.
@@ -144,6 +349,13 @@ pub fn merge_repo_config(mut config: Config, repo: &RepoConfig) -> Config {
         if !hooks.on_launch.is_empty() {
             config.hooks.on_launch = hooks.on_launch.clone();
         }
+        if hooks.health_check.is_some() {
+            config.hooks.health_check = hooks.health_check.clone();
+            config.hooks.health_check_interval_secs = hooks.health_check_interval_secs;
+        }
+        if hooks.test_watch.is_some() {
+            config.hooks.test_watch = hooks.test_watch.clone();
+        }
     }
 
     if let Some(ref updates_override) = repo.updates {
@@ -159,6 +371,12 @@ pub fn merge_repo_config(mut config: Config, repo: &RepoConfig) -> Config {
         if let Some(notify_in_cli) = updates_override.notify_in_cli {
             config.updates.notify_in_cli = notify_in_cli;
         }
+        if let Some(check_agents) = updates_override.check_agents {
+            config.updates.check_agents = check_agents;
+        }
+        if let Some(ref commands) = updates_override.agent_upgrade_commands {
+            config.updates.agent_upgrade_commands = commands.clone();
+        }
     }
 
     if let Some(ref tmux_override) = repo.tmux {
@@ -194,6 +412,12 @@ pub fn repo_config_to_profile(repo: &RepoConfig) -> ProfileConfig {
             } else {
                 Some(h.on_launch.clone())
             },
+            health_check: h.health_check.clone(),
+            health_check_interval_secs: (!is_default_health_check_interval(
+                &h.health_check_interval_secs,
+            ))
+            .then_some(h.health_check_interval_secs),
+            test_watch: h.test_watch.clone(),
         }),
         ..Default::default()
     }
@@ -202,9 +426,15 @@ pub fn repo_config_to_profile(repo: &RepoConfig) -> ProfileConfig {
 /// Convert a ProfileConfig back into a RepoConfig after TUI editing.
 pub fn profile_to_repo_config(profile: &ProfileConfig) -> RepoConfig {
     RepoConfig {
+        extends: None,
         hooks: profile.hooks.as_ref().map(|h| HooksConfig {
             on_create: h.on_create.clone().unwrap_or_default(),
             on_launch: h.on_launch.clone().unwrap_or_default(),
+            health_check: h.health_check.clone(),
+            health_check_interval_secs: h
+                .health_check_interval_secs
+                .unwrap_or(DEFAULT_HEALTH_CHECK_INTERVAL_SECS),
+            test_watch: h.test_watch.clone(),
         }),
         session: profile.session.clone(),
         sandbox: profile.sandbox.clone(),
@@ -212,6 +442,8 @@ pub fn profile_to_repo_config(profile: &ProfileConfig) -> RepoConfig {
         updates: profile.updates.clone(),
         tmux: profile.tmux.clone(),
         sound: profile.sound.clone(),
+        commands: BTreeMap::new(),
+        completion_ignores: Vec::new(),
     }
 }
 
@@ -257,6 +489,16 @@ pub fn compute_hooks_hash(hooks: &HooksConfig) -> String {
         hasher.update(cmd.as_bytes());
         hasher.update(b"\n");
     }
+    if let Some(cmd) = &hooks.health_check {
+        hasher.update(b"health_check:");
+        hasher.update(cmd.as_bytes());
+        hasher.update(b"\n");
+    }
+    if let Some(cmd) = &hooks.test_watch {
+        hasher.update(b"test_watch:");
+        hasher.update(cmd.as_bytes());
+        hasher.update(b"\n");
+    }
     format!("{:x}", hasher.finalize())
 }
 
@@ -587,19 +829,143 @@ pub fn execute_hooks_in_container_streamed(
     )
 }
 
+// ---------------------------------------------------------------------------
+// Health checks
+// ---------------------------------------------------------------------------
+
+/// Shortest allowed interval between health check runs, so a typo like
+/// `health_check_interval_secs = 0` can't spin a probe in a tight loop.
+pub const MIN_HEALTH_CHECK_INTERVAL_SECS: u64 = 5;
+
+/// Resolve the health check for a session: global+profile config, overridden
+/// by the repo's probe only when the repo hooks are trusted. Returns the
+/// command and its interval in seconds.
+pub fn resolve_health_check(profile: &str, project_path: &Path) -> Option<(String, u64)> {
+    let mut hooks = super::profile_config::resolve_config(profile)
+        .map(|c| c.hooks)
+        .unwrap_or_default();
+
+    if let Ok(HookTrustStatus::Trusted(repo_hooks)) = check_hook_trust(project_path) {
+        if repo_hooks.health_check.is_some() {
+            hooks.health_check = repo_hooks.health_check;
+            hooks.health_check_interval_secs = repo_hooks.health_check_interval_secs;
+        }
+    }
+
+    let interval = hooks
+        .health_check_interval_secs
+        .max(MIN_HEALTH_CHECK_INTERVAL_SECS);
+    hooks
+        .health_check
+        .filter(|cmd| !cmd.trim().is_empty())
+        .map(|cmd| (cmd, interval))
+}
+
+/// Resolve the test watcher for a session: global+profile config, overridden
+/// by the repo's watcher only when the repo hooks are trusted.
+pub fn resolve_test_watch(profile: &str, project_path: &Path) -> Option<String> {
+    let mut hooks = super::profile_config::resolve_config(profile)
+        .map(|c| c.hooks)
+        .unwrap_or_default();
+
+    if let Ok(HookTrustStatus::Trusted(repo_hooks)) = check_hook_trust(project_path) {
+        if repo_hooks.test_watch.is_some() {
+            hooks.test_watch = repo_hooks.test_watch;
+        }
+    }
+
+    hooks.test_watch.filter(|cmd| !cmd.trim().is_empty())
+}
+
+/// Run a health check command in the given directory. Fails on a non-zero
+/// exit or when the probe outlives `timeout`, with the last line of output
+/// as detail.
+pub fn run_health_check(
+    cmd: &str,
+    project_path: &Path,
+    timeout: std::time::Duration,
+) -> Result<()> {
+    use std::io::Read;
+
+    let mut child = build_hook_command(cmd, &HookTarget::Local { project_path }, true)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to execute health check: {}", cmd))?;
+
+    // Drain output on a separate thread so a chatty probe can't block on a
+    // full pipe while we wait for it.
+    let stdout = child.stdout.take();
+    let reader = std::thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut stdout) = stdout {
+            let _ = stdout.read_to_string(&mut output);
+        }
+        output
+    });
+
+    let started = std::time::Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    };
+
+    let output = reader.join().unwrap_or_default();
+    let last_line = output
+        .lines()
+        .rev()
+        .find(|l| !l.trim().is_empty())
+        .unwrap_or("")
+        .trim();
+
+    match status {
+        Some(status) if status.success() => Ok(()),
+        Some(status) if last_line.is_empty() => {
+            anyhow::bail!("exit code {}", status.code().unwrap_or(-1))
+        }
+        Some(status) => anyhow::bail!("exit code {}: {}", status.code().unwrap_or(-1), last_line),
+        None => anyhow::bail!("timed out after {}s", timeout.as_secs()),
+    }
+}
+
 /// Template content for `aoe init`.
 pub const INIT_TEMPLATE: &str = r#"# Agent of Empires - Repository Configuration
 # This file configures aoe behavior for this repository.
 # See: https://github.com/njbrake/agent-of-empires
 
+# Build on shared templates from ~/.agent-of-empires/templates/<name>.toml
+# extends = "base-rust"
+
+# Directories path completion skips inside this repo
+# completion_ignores = ["target", "node_modules"]
+
 # [hooks]
 # Commands run once when a session is first created
 # on_create = ["npm install", "cp .env.example .env"]
 # Commands run every time a session starts
 # on_launch = ["npm install"]
+# Probe run periodically while the session is alive; shown green/red in the list
+# health_check = "curl -fs localhost:3000/health"
+# health_check_interval_secs = 60
+# Test watcher kept running beside the session; its pass/fail counts show in the list
+# test_watch = "cargo watch -c -x test"
 
 # [session]
 # default_tool = "claude"
+# Agent to switch to if the default fails to start or hits a usage limit
+# fallback_tool = "codex"
+# Emoji or glyph shown before the title in the list and tmux window name
+# icon = "🚀"
+# Sessions sharing a lock run one at a time
+# locks = ["db-migrations"]
 
 # [sandbox]
 # enabled_by_default = true
@@ -607,9 +973,22 @@ pub const INIT_TEMPLATE: &str = r#"# Agent of Empires - Repository Configuration
 # List fields below replace (not append to) global settings when set:
 # environment = ["NODE_ENV", "DATABASE_URL"]
 # volume_ignores = ["node_modules", ".next"]
+# Restrict host (non-container) sessions: "off", "workdir" or "isolated"
+# host_profile = "workdir"
+# host_block_network = false
 
 # [worktree]
 # enabled = true
+# merge_test_command = "cargo test"
+# Other repos (or submodules) each worktree session also gets a worktree of:
+# repos = ["../shared", "vendor/proto"]
+# Only check out these paths in new worktrees:
+# sparse_paths = ["services/api", "libs/common"]
+
+# Named commands, run from the TUI with ! and the command's key
+# [commands]
+# test = "cargo test"
+# lint = "cargo clippy -- -D warnings"
 
 # [updates]
 # check_enabled = false
@@ -632,11 +1011,76 @@ mod tests {
         assert!(hooks.is_empty());
     }
 
+    #[test]
+    fn test_health_check_alone_is_not_empty() {
+        let hooks = HooksConfig {
+            health_check: Some("cargo check".to_string()),
+            ..Default::default()
+        };
+        assert!(!hooks.is_empty());
+    }
+
+    #[test]
+    fn test_compute_hooks_hash_unchanged_without_health_check() {
+        // Existing trust entries must stay valid for repos without a probe.
+        let hooks = HooksConfig {
+            on_create: vec!["npm install".to_string()],
+            ..Default::default()
+        };
+        let with_interval = HooksConfig {
+            health_check_interval_secs: 10,
+            ..hooks.clone()
+        };
+        assert_eq!(
+            compute_hooks_hash(&hooks),
+            compute_hooks_hash(&with_interval)
+        );
+        let with_probe = HooksConfig {
+            health_check: Some("true".to_string()),
+            ..hooks.clone()
+        };
+        assert_ne!(compute_hooks_hash(&hooks), compute_hooks_hash(&with_probe));
+        let with_watch = HooksConfig {
+            test_watch: Some("cargo watch -x test".to_string()),
+            ..hooks.clone()
+        };
+        assert_ne!(compute_hooks_hash(&hooks), compute_hooks_hash(&with_watch));
+    }
+
+    #[test]
+    fn test_health_check_interval_default_roundtrip() {
+        let hooks: HooksConfig = toml::from_str("health_check = \"true\"").unwrap();
+        assert_eq!(
+            hooks.health_check_interval_secs,
+            DEFAULT_HEALTH_CHECK_INTERVAL_SECS
+        );
+        let serialized = toml::to_string(&hooks).unwrap();
+        assert!(!serialized.contains("health_check_interval_secs"));
+    }
+
+    #[test]
+    fn test_run_health_check_outcomes() {
+        let dir = tempfile::tempdir().unwrap();
+        let timeout = std::time::Duration::from_secs(5);
+        assert!(run_health_check("true", dir.path(), timeout).is_ok());
+
+        let err = run_health_check("echo booting; echo not ready; exit 3", dir.path(), timeout)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "exit code 3: not ready");
+
+        let err = run_health_check("sleep 5", dir.path(), std::time::Duration::from_millis(200))
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("timed out"), "{}", err);
+    }
+
     #[test]
     fn test_hooks_config_not_empty() {
         let hooks = HooksConfig {
             on_create: vec!["npm install".to_string()],
             on_launch: vec![],
+            ..Default::default()
         };
         assert!(!hooks.is_empty());
     }
@@ -646,6 +1090,7 @@ mod tests {
         let hooks = HooksConfig {
             on_create: vec!["npm install".to_string()],
             on_launch: vec!["echo hello".to_string()],
+            ..Default::default()
         };
         let hash1 = compute_hooks_hash(&hooks);
         let hash2 = compute_hooks_hash(&hooks);
@@ -657,10 +1102,12 @@ mod tests {
         let hooks1 = HooksConfig {
             on_create: vec!["npm install".to_string()],
             on_launch: vec![],
+            ..Default::default()
         };
         let hooks2 = HooksConfig {
             on_create: vec!["yarn install".to_string()],
             on_launch: vec![],
+            ..Default::default()
         };
         assert_ne!(compute_hooks_hash(&hooks1), compute_hooks_hash(&hooks2));
     }
@@ -670,10 +1117,12 @@ mod tests {
         let hooks1 = HooksConfig {
             on_create: vec!["echo hello".to_string()],
             on_launch: vec![],
+            ..Default::default()
         };
         let hooks2 = HooksConfig {
             on_create: vec![],
             on_launch: vec!["echo hello".to_string()],
+            ..Default::default()
         };
         assert_ne!(compute_hooks_hash(&hooks1), compute_hooks_hash(&hooks2));
     }
@@ -894,4 +1343,105 @@ mod tests {
         assert!(merged.sandbox.auto_cleanup);
         assert!(merged.worktree.auto_cleanup);
     }
+
+    #[test]
+    fn test_layer_tables_merges_lists_and_overrides_scalars() {
+        let base: toml::Table = r#"
+[hooks]
+on_create = ["cargo fetch"]
+health_check = "true"
+[sandbox]
+environment = ["RUST_LOG"]
+default_image = "base"
+"#
+        .parse()
+        .unwrap();
+        let child: toml::Table = r#"
+[hooks]
+on_create = ["cargo fetch", "make setup"]
+[sandbox]
+environment = ["DATABASE_URL"]
+default_image = "child"
+extra_volumes = ["/data:/data"]
+"#
+        .parse()
+        .unwrap();
+
+        let config: RepoConfig = toml::Value::Table(layer_tables(base, child))
+            .try_into()
+            .unwrap();
+        let hooks = config.hooks.unwrap();
+        assert_eq!(hooks.on_create, vec!["cargo fetch", "make setup"]);
+        assert_eq!(hooks.health_check.as_deref(), Some("true"));
+        let sandbox = config.sandbox.unwrap();
+        assert_eq!(
+            sandbox.environment,
+            Some(vec!["RUST_LOG".to_string(), "DATABASE_URL".to_string()])
+        );
+        assert_eq!(sandbox.default_image.as_deref(), Some("child"));
+        assert_eq!(sandbox.extra_volumes, Some(vec!["/data:/data".to_string()]));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_load_repo_config_with_extends() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        std::env::set_var("HOME", temp.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.path().join(".config"));
+
+        let templates = templates_dir()?;
+        fs::create_dir_all(&templates)?;
+        fs::write(
+            templates.join("org.toml"),
+            "[hooks]\non_launch = [\"git fetch\"]\n[session]\ndefault_tool = \"claude\"\n",
+        )?;
+        fs::write(
+            templates.join("base-rust.toml"),
+            "extends = \"org\"\n[hooks]\non_launch = [\"cargo fetch\"]\n[commands]\ntest = \"cargo test\"\n",
+        )?;
+        fs::write(
+            templates.join("docker.toml"),
+            "[sandbox]\nenabled_by_default = true\n",
+        )?;
+
+        let project = temp.path().join("project");
+        fs::create_dir_all(project.join(".aoe"))?;
+        fs::write(
+            project.join(REPO_CONFIG_PATH),
+            "extends = [\"base-rust\", \"docker\"]\n[session]\ndefault_tool = \"codex\"\n[commands]\nlint = \"cargo clippy\"\n",
+        )?;
+
+        let config = load_repo_config(&project)?.unwrap();
+        assert_eq!(
+            config.commands.keys().collect::<Vec<_>>(),
+            vec!["lint", "test"]
+        );
+        assert_eq!(
+            config.hooks.unwrap().on_launch,
+            vec!["git fetch", "cargo fetch"]
+        );
+        assert_eq!(
+            config.session.unwrap().default_tool.as_deref(),
+            Some("codex")
+        );
+        assert_eq!(config.sandbox.unwrap().enabled_by_default, Some(true));
+
+        // The raw file keeps only what the repo itself sets
+        let raw = load_repo_config_file(&project)?.unwrap();
+        assert!(raw.hooks.is_none());
+        assert_eq!(
+            raw.extends.map(|e| e.names().to_vec()),
+            Some(vec!["base-rust".to_string(), "docker".to_string()])
+        );
+
+        fs::write(templates.join("org.toml"), "extends = \"base-rust\"\n")?;
+        let err = format!("{:#}", load_repo_config(&project).unwrap_err());
+        assert!(err.contains("base-rust -> org -> base-rust"), "{}", err);
+
+        fs::write(project.join(REPO_CONFIG_PATH), "extends = \"missing\"\n")?;
+        let err = format!("{:#}", load_repo_config(&project).unwrap_err());
+        assert!(err.contains("Template 'missing' not found"), "{}", err);
+        Ok(())
+    }
 }
@@ -0,0 +1,196 @@
+//! CPU, IO, and memory limits for sessions that run on the host
+//!
+//! `session.nice` and `session.io_priority` run the agent under `nice` and
+//! `ionice` (`taskpolicy` on macOS), so a runaway build yields to the rest
+//! of the machine. On Linux, `session.memory_limit` and `session.cpu_limit`
+//! put the session's whole process tree in its own cgroup through
+//! `systemd-run --user --scope`. Container sessions are limited by the
+//! container (`sandbox.cpu_limit`, `sandbox.memory_limit`) instead.
+
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use super::config::{IoPriority, SessionConfig};
+use super::environment::shell_escape;
+
+/// Resource limits a session runs under.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    #[serde(default)]
+    pub nice: u8,
+    #[serde(default)]
+    pub io_priority: IoPriority,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_limit: Option<String>,
+}
+
+impl ResourceLimits {
+    /// The limits new sessions get from config, or None when none are set.
+    pub fn from_config(config: &SessionConfig) -> Option<Self> {
+        let limits = Self {
+            nice: config.nice.min(19),
+            io_priority: config.io_priority,
+            memory_limit: non_empty(&config.memory_limit),
+            cpu_limit: non_empty(&config.cpu_limit),
+        };
+        (limits != Self::default()).then_some(limits)
+    }
+
+    /// Short description, e.g. "nice 10, io idle, 4G memory, 2 CPUs".
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        if self.nice > 0 {
+            parts.push(format!("nice {}", self.nice));
+        }
+        match self.io_priority {
+            IoPriority::Normal => {}
+            IoPriority::Low => parts.push("io low".to_string()),
+            IoPriority::Idle => parts.push("io idle".to_string()),
+        }
+        if let Some(memory) = &self.memory_limit {
+            parts.push(format!("{} memory", memory));
+        }
+        if let Some(cpus) = &self.cpu_limit {
+            parts.push(format!("{} CPUs", cpus));
+        }
+        parts.join(", ")
+    }
+
+    /// Wrap a tmux session command so it runs under the limits. Fails if a
+    /// cgroup limit is set but `systemd-run` is not available.
+    pub fn wrap(&self, cmd: &str) -> Result<String> {
+        let has_cgroup_limits = self.memory_limit.is_some() || self.cpu_limit.is_some();
+        if has_cgroup_limits && cfg!(target_os = "linux") && !command_exists("systemd-run") {
+            bail!(
+                "Session memory and CPU limits require systemd-run, which was not found in PATH. \
+                 Unset session.memory_limit and session.cpu_limit to start without them"
+            );
+        }
+        if has_cgroup_limits && !cfg!(target_os = "linux") {
+            tracing::warn!("session.memory_limit and session.cpu_limit are only applied on Linux");
+        }
+        wrap_command(self, cmd, cfg!(target_os = "linux"))
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+fn command_exists(cmd: &str) -> bool {
+    Command::new("which")
+        .arg(cmd)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// systemd's CPUQuota for a limit in CPUs: "2" is "200%".
+fn cpu_quota(cpus: &str) -> Result<String> {
+    match cpus.parse::<f64>() {
+        Ok(n) if n > 0.0 => Ok(format!("{}%", (n * 100.0).round() as u64)),
+        _ => bail!(
+            "Invalid session.cpu_limit \"{}\": expected a number of CPUs such as \"2\" or \"0.5\"",
+            cpus
+        ),
+    }
+}
+
+/// The wrapped command. `linux` selects `systemd-run` and `ionice` over
+/// macOS's `taskpolicy`.
+fn wrap_command(limits: &ResourceLimits, cmd: &str, linux: bool) -> Result<String> {
+    let mut args: Vec<String> = Vec::new();
+
+    if linux && (limits.memory_limit.is_some() || limits.cpu_limit.is_some()) {
+        args.push("systemd-run --user --scope --quiet --collect".to_string());
+        if let Some(memory) = &limits.memory_limit {
+            args.push(format!(
+                "-p {}",
+                shell_escape(&format!("MemoryMax={}", memory))
+            ));
+        }
+        if let Some(cpus) = &limits.cpu_limit {
+            args.push(format!("-p CPUQuota={}", cpu_quota(cpus)?));
+        }
+        args.push("--".to_string());
+    }
+    if limits.nice > 0 {
+        args.push(format!("nice -n {}", limits.nice));
+    }
+    match (limits.io_priority, linux) {
+        (IoPriority::Normal, _) => {}
+        (IoPriority::Low, true) => args.push("ionice -c 2 -n 7".to_string()),
+        (IoPriority::Idle, true) => args.push("ionice -c 3".to_string()),
+        (IoPriority::Low, false) => args.push("taskpolicy -d utility".to_string()),
+        (IoPriority::Idle, false) => args.push("taskpolicy -d throttle".to_string()),
+    }
+
+    args.push(cmd.to_string());
+    Ok(args.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_config() {
+        let mut config = SessionConfig::default();
+        assert_eq!(ResourceLimits::from_config(&config), None);
+
+        config.nice = 30;
+        config.io_priority = IoPriority::Idle;
+        config.memory_limit = Some(" 4G ".to_string());
+        config.cpu_limit = Some(String::new());
+        let limits = ResourceLimits::from_config(&config).unwrap();
+        assert_eq!(limits.nice, 19);
+        assert_eq!(limits.cpu_limit, None);
+        assert_eq!(limits.label(), "nice 19, io idle, 4G memory");
+    }
+
+    #[test]
+    fn test_wrap_linux() {
+        let limits = ResourceLimits {
+            nice: 10,
+            io_priority: IoPriority::Low,
+            memory_limit: Some("4G".to_string()),
+            cpu_limit: Some("1.5".to_string()),
+        };
+        assert_eq!(
+            wrap_command(&limits, "bash -lc 'claude'", true).unwrap(),
+            "systemd-run --user --scope --quiet --collect -p \"MemoryMax=4G\" -p CPUQuota=150% \
+             -- nice -n 10 ionice -c 2 -n 7 bash -lc 'claude'"
+        );
+    }
+
+    #[test]
+    fn test_wrap_macos_skips_cgroups() {
+        let limits = ResourceLimits {
+            io_priority: IoPriority::Idle,
+            memory_limit: Some("4G".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            wrap_command(&limits, "zsh -lc 'codex'", false).unwrap(),
+            "taskpolicy -d throttle zsh -lc 'codex'"
+        );
+    }
+
+    #[test]
+    fn test_invalid_cpu_limit() {
+        let limits = ResourceLimits {
+            cpu_limit: Some("lots".to_string()),
+            ..Default::default()
+        };
+        assert!(wrap_command(&limits, "sh", true).is_err());
+    }
+}
@@ -0,0 +1,435 @@
+//! Read-only share bundles for `aoe share`
+//!
+//! A bundle is one self-contained HTML page holding a session's metadata,
+//! activity timeline, diff against the commit it started from, and the
+//! transcript from its pane, meant to be linked from a code review. Bundles
+//! are written to `shares/` in the app directory and can be uploaded to a
+//! GitHub gist (with `gh`) or an S3 bucket (with the `aws` CLI).
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, Utc};
+
+use super::config::{ShareConfig, ShareDestination};
+use super::timeline::{self, TimelineEvent};
+use super::{get_app_dir, Instance};
+
+/// Longest presigned S3 link AWS allows, in hours
+const MAX_S3_LINK_HOURS: u64 = 168;
+
+/// Everything that goes into a share bundle
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    pub title: String,
+    pub tool: String,
+    pub project: String,
+    pub branch: Option<String>,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    pub prompt: Option<String>,
+    pub final_summary: Option<String>,
+    pub notes: Option<String>,
+    /// Cost the agent last reported, in dollars
+    pub cost: Option<f64>,
+    pub timeline: Vec<TimelineEvent>,
+    /// Diff of the session's changes; `None` outside a git repository
+    pub diff: Option<String>,
+    /// Pane output; `None` when the session is not running
+    pub transcript: Option<String>,
+}
+
+/// Gather a bundle for `instance`, keeping the last `lines` lines of its pane
+pub fn collect(instance: &Instance, lines: usize) -> Bundle {
+    let content = instance
+        .tmux_session()
+        .ok()
+        .filter(|s| s.exists())
+        .and_then(|s| s.capture_pane(lines).ok())
+        .map(|content| crate::tmux::utils::strip_ansi(&content));
+    Bundle {
+        title: instance.title.clone(),
+        tool: instance.tool.clone(),
+        project: instance
+            .worktree_info
+            .as_ref()
+            .map(|wt| wt.main_repo_path.clone())
+            .unwrap_or_else(|| instance.project_path.clone()),
+        branch: instance.worktree_info.as_ref().map(|wt| wt.branch.clone()),
+        status: format!("{:?}", instance.status).to_lowercase(),
+        created_at: instance.created_at,
+        generated_at: Utc::now(),
+        prompt: instance.original_prompt.clone(),
+        final_summary: instance.final_summary.clone(),
+        notes: instance.notes.clone().filter(|n| !n.trim().is_empty()),
+        cost: content
+            .as_deref()
+            .and_then(crate::tmux::output_parser::reported_cost),
+        timeline: timeline::load(&instance.id).unwrap_or_default(),
+        diff: diff_of(instance),
+        transcript: content.map(|c| c.trim_end().to_string()),
+    }
+}
+
+/// Changes in the session's working tree since the commit it started from,
+/// or since it branched off the default branch.
+fn diff_of(instance: &Instance) -> Option<String> {
+    let dir = Path::new(&instance.project_path);
+    let base = match instance
+        .worktree_info
+        .as_ref()
+        .and_then(|wt| wt.base_commit.clone())
+    {
+        Some(commit) => commit,
+        None => {
+            let default = crate::git::diff::get_default_branch(dir).ok()?;
+            git(dir, &["merge-base", "HEAD", &default])?
+                .trim()
+                .to_string()
+        }
+    };
+    git(dir, &["diff", "--no-color", &base])
+}
+
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn local_time(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M %Z")
+        .to_string()
+}
+
+const STYLE: &str = "\
+body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;max-width:1100px;\
+margin:2em auto;padding:0 1em;color:#1f2328;background:#fff}\
+h1{margin-bottom:.2em}h2{border-bottom:1px solid #d0d7de;padding-bottom:.3em;margin-top:2em}\
+table.meta td{padding:2px 12px 2px 0;vertical-align:top}table.meta td:first-child{color:#656d76}\
+pre{background:#f6f8fa;border:1px solid #d0d7de;border-radius:6px;padding:12px;overflow-x:auto;\
+font-size:12px;line-height:1.45}\
+.add{color:#116329;background:#dafbe1}.del{color:#82071e;background:#ffebe9}\
+.hunk{color:#0550ae}.file{font-weight:bold}.muted{color:#656d76}\
+table.timeline td{padding:2px 12px 2px 0;font-size:13px;vertical-align:top}";
+
+/// Render `bundle` as a standalone HTML page
+pub fn render_html(bundle: &Bundle) -> String {
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{} - Agent of Empires</title>\n<style>{}</style>\n</head>\n<body>\n\
+         <h1>{}</h1>\n<p class=\"muted\">Shared from Agent of Empires on {}</p>\n",
+        escape(&bundle.title),
+        STYLE,
+        escape(&bundle.title),
+        local_time(bundle.generated_at),
+    );
+
+    html.push_str("<table class=\"meta\">\n");
+    let mut row = |label: &str, value: &str| {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td></tr>",
+            label,
+            escape(value)
+        );
+    };
+    row("Agent", &bundle.tool);
+    row("Project", &bundle.project);
+    if let Some(branch) = &bundle.branch {
+        row("Branch", branch);
+    }
+    row("Status", &bundle.status);
+    row("Created", &local_time(bundle.created_at));
+    if let Some(cost) = bundle.cost {
+        row("Cost", &format!("${:.2}", cost));
+    }
+    html.push_str("</table>\n");
+
+    for (heading, text) in [
+        ("Prompt", &bundle.prompt),
+        ("Result", &bundle.final_summary),
+        ("Notes", &bundle.notes),
+    ] {
+        if let Some(text) = text {
+            let _ = write!(html, "<h2>{}</h2>\n<pre>{}</pre>\n", heading, escape(text));
+        }
+    }
+
+    if !bundle.timeline.is_empty() {
+        html.push_str("<h2>Timeline</h2>\n<table class=\"timeline\">\n");
+        for event in &bundle.timeline {
+            let _ = writeln!(
+                html,
+                "<tr><td class=\"muted\">{}</td><td>{}</td><td>{}</td></tr>",
+                local_time(event.at),
+                event.kind.label(),
+                escape(&event.message)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("<h2>Changes</h2>\n");
+    match bundle.diff.as_deref() {
+        None => html.push_str("<p class=\"muted\">Not a git repository.</p>\n"),
+        Some(diff) if diff.trim().is_empty() => {
+            html.push_str("<p class=\"muted\">No changes.</p>\n")
+        }
+        Some(diff) => {
+            html.push_str("<pre>");
+            for line in diff.lines() {
+                let class = if line.starts_with("diff --git") {
+                    Some("file")
+                } else if line.starts_with("+++") || line.starts_with("---") {
+                    Some("muted")
+                } else if line.starts_with("@@") {
+                    Some("hunk")
+                } else if line.starts_with('+') {
+                    Some("add")
+                } else if line.starts_with('-') {
+                    Some("del")
+                } else {
+                    None
+                };
+                match class {
+                    Some(class) => {
+                        let _ = writeln!(html, "<span class=\"{}\">{}</span>", class, escape(line));
+                    }
+                    None => {
+                        let _ = writeln!(html, "{}", escape(line));
+                    }
+                }
+            }
+            html.push_str("</pre>\n");
+        }
+    }
+
+    html.push_str("<h2>Transcript</h2>\n");
+    match bundle.transcript.as_deref() {
+        Some(transcript) if !transcript.is_empty() => {
+            let _ = writeln!(html, "<pre>{}</pre>", escape(transcript));
+        }
+        _ => html.push_str("<p class=\"muted\">The session was not running.</p>\n"),
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// File name for a bundle, e.g. `fix-login-20260312-153000.html`
+pub fn file_name(bundle: &Bundle) -> String {
+    format!(
+        "{}-{}.html",
+        crate::tmux::utils::sanitize_session_name(&bundle.title),
+        bundle.generated_at.format("%Y%m%d-%H%M%S")
+    )
+}
+
+/// Write `bundle` to `output`, or to `shares/` in the app directory.
+/// Returns the path written.
+pub fn write(bundle: &Bundle, output: Option<&Path>) -> Result<PathBuf> {
+    let path = match output {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let dir = get_app_dir()?.join("shares");
+            std::fs::create_dir_all(&dir)?;
+            dir.join(file_name(bundle))
+        }
+    };
+    std::fs::write(&path, render_html(bundle))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Upload the bundle at `path` to the configured destination and return a
+/// URL for it.
+pub fn upload(path: &Path, title: &str, config: &ShareConfig) -> Result<String> {
+    match config.destination {
+        ShareDestination::None => bail!(
+            "No upload destination configured\n\
+             Tip: Set share.destination to \"gist\" or \"s3\" in the settings"
+        ),
+        ShareDestination::Gist => upload_gist(path, title, config.gist_public),
+        ShareDestination::S3 => {
+            let Some(bucket) = config.s3_bucket.as_deref().filter(|b| !b.trim().is_empty()) else {
+                bail!("share.destination is \"s3\" but share.s3_bucket is not set");
+            };
+            upload_s3(path, bucket, config.s3_link_hours)
+        }
+    }
+}
+
+fn upload_gist(path: &Path, title: &str, public: bool) -> Result<String> {
+    let mut cmd = Command::new("gh");
+    cmd.args(["gist", "create", "--desc"])
+        .arg(format!("{} (shared from Agent of Empires)", title));
+    if public {
+        cmd.arg("--public");
+    }
+    let output = cmd
+        .arg(path)
+        .output()
+        .context("Failed to run gh. Is the GitHub CLI installed?")?;
+    url_from(output, "gh gist create")
+}
+
+/// `s3_bucket` is a bucket name with an optional key prefix
+fn s3_uri(bucket: &str, name: &str) -> String {
+    let bucket = bucket.trim().trim_start_matches("s3://").trim_matches('/');
+    format!("s3://{}/{}", bucket, name)
+}
+
+fn upload_s3(path: &Path, bucket: &str, link_hours: u64) -> Result<String> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "share.html".to_string());
+    let uri = s3_uri(bucket, &name);
+    let output = Command::new("aws")
+        .args(["s3", "cp", "--content-type", "text/html"])
+        .arg(path)
+        .arg(&uri)
+        .output()
+        .context("Failed to run aws. Is the AWS CLI installed?")?;
+    if !output.status.success() {
+        bail!(
+            "aws s3 cp failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let expires = link_hours.clamp(1, MAX_S3_LINK_HOURS) * 3600;
+    let output = Command::new("aws")
+        .args(["s3", "presign", &uri, "--expires-in", &expires.to_string()])
+        .output()
+        .context("Failed to run aws s3 presign")?;
+    url_from(output, "aws s3 presign")
+}
+
+/// The last line of a successful command's output, which both `gh` and
+/// `aws` use for the URL
+fn url_from(output: std::process::Output, command: &str) -> Result<String> {
+    if !output.status.success() {
+        bail!(
+            "{} failed: {}",
+            command,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .rfind(|line| line.starts_with("http"))
+        .map(str::to_string)
+        .with_context(|| format!("{} printed no URL", command))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::timeline::EventKind;
+
+    fn bundle() -> Bundle {
+        let at: DateTime<Utc> = "2026-03-12T15:30:00Z".parse().unwrap();
+        Bundle {
+            title: "fix <login>".to_string(),
+            tool: "claude".to_string(),
+            project: "/src/app".to_string(),
+            branch: Some("fix/login".to_string()),
+            status: "idle".to_string(),
+            created_at: at,
+            generated_at: at,
+            prompt: Some("Fix the \"login\" redirect & tests".to_string()),
+            final_summary: None,
+            notes: None,
+            cost: Some(1.5),
+            timeline: vec![TimelineEvent {
+                at,
+                kind: EventKind::Created,
+                message: "created with claude".to_string(),
+            }],
+            diff: Some(
+                "diff --git a/x b/x\n--- a/x\n+++ b/x\n@@ -1 +1 @@\n-old\n+<new>\n".to_string(),
+            ),
+            transcript: None,
+        }
+    }
+
+    #[test]
+    fn test_render_escapes_and_marks_diff_lines() {
+        let html = render_html(&bundle());
+        assert!(html.contains("<h1>fix &lt;login&gt;</h1>"));
+        assert!(html.contains("Fix the &quot;login&quot; redirect &amp; tests"));
+        assert!(html.contains("<span class=\"add\">+&lt;new&gt;</span>"));
+        assert!(html.contains("<span class=\"del\">-old</span>"));
+        assert!(html.contains("<span class=\"hunk\">@@ -1 +1 @@</span>"));
+        assert!(html.contains("<td>$1.50</td>"));
+        assert!(html.contains("created with claude"));
+        assert!(html.contains("The session was not running."));
+        assert!(!html.contains("<h2>Result</h2>"));
+        assert_eq!(file_name(&bundle()), "fix__login_-20260312-153000.html");
+    }
+
+    #[test]
+    fn test_s3_uri_and_url_parsing() {
+        assert_eq!(s3_uri("bucket", "a.html"), "s3://bucket/a.html");
+        assert_eq!(
+            s3_uri("s3://bucket/aoe/", "a.html"),
+            "s3://bucket/aoe/a.html"
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            let output = std::process::Output {
+                status: std::process::ExitStatus::from_raw(0),
+                stdout: b"- Creating gist\nhttps://gist.github.com/abc\n".to_vec(),
+                stderr: Vec::new(),
+            };
+            assert_eq!(
+                url_from(output, "gh").unwrap(),
+                "https://gist.github.com/abc"
+            );
+        }
+    }
+
+    #[test]
+    fn test_upload_needs_a_destination() {
+        let config = ShareConfig::default();
+        let err = upload(Path::new("x.html"), "t", &config).unwrap_err();
+        assert!(err.to_string().contains("No upload destination"));
+
+        let config = ShareConfig {
+            destination: ShareDestination::S3,
+            ..Default::default()
+        };
+        let err = upload(Path::new("x.html"), "t", &config).unwrap_err();
+        assert!(err.to_string().contains("s3_bucket"));
+    }
+}
@@ -0,0 +1,470 @@
+//! SQLite session store (`sqlite` feature)
+//!
+//! With `storage.backend = "sqlite"` every profile's sessions and groups
+//! live in `aoe.db` in the app directory. Each session is stored as its JSON
+//! next to the columns queries filter on, so the schema does not have to
+//! follow every new `Instance` field. A profile's `sessions.json` and
+//! `groups.json` are imported the first time it is opened.
+//!
+//! The database also indexes `stats.jsonl`, which stays the record of
+//! finished sessions: a history query first reads whatever was appended
+//! since the last one, so `aoe stats` does not re-parse the whole log.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+
+use super::stats::{stats_path, HistoryQuery, SessionRecord};
+use super::storage::{search_text, JsonFileStore, SessionQuery, SessionStore};
+use super::{get_app_dir, get_profile_dir, Group, Instance};
+
+const DB_FILE: &str = "aoe.db";
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS sessions (
+    profile TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    id TEXT NOT NULL,
+    tool TEXT NOT NULL,
+    search TEXT NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS sessions_by_profile ON sessions (profile, position);
+CREATE INDEX IF NOT EXISTS sessions_by_tool ON sessions (profile, tool);
+CREATE TABLE IF NOT EXISTS groups (
+    profile TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS history (
+    ended_at INTEGER NOT NULL,
+    profile TEXT NOT NULL,
+    project TEXT NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS history_by_time ON history (ended_at);
+CREATE TABLE IF NOT EXISTS meta (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+";
+
+/// `meta` key holding how many bytes of `stats.jsonl` are indexed
+const HISTORY_OFFSET: &str = "history_offset";
+
+fn db_path() -> Result<PathBuf> {
+    Ok(get_app_dir()?.join(DB_FILE))
+}
+
+fn connect() -> Result<Connection> {
+    let path = db_path()?;
+    let conn =
+        Connection::open(&path).with_context(|| format!("Failed to open {}", path.display()))?;
+    // The TUI, CLI commands, and hooks all open the file at once
+    conn.busy_timeout(Duration::from_secs(5))?;
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    conn.execute_batch(SCHEMA)?;
+    Ok(conn)
+}
+
+fn meta(conn: &Connection, key: &str) -> Result<Option<String>> {
+    Ok(conn
+        .query_row("SELECT value FROM meta WHERE key = ?1", [key], |row| {
+            row.get(0)
+        })
+        .optional()?)
+}
+
+fn set_meta(conn: &Connection, key: &str, value: &str) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO meta (key, value) VALUES (?1, ?2)",
+        [key, value],
+    )?;
+    Ok(())
+}
+
+/// Deserialize the JSON `data` column of every row `sql` returns
+fn load_rows<T: serde::de::DeserializeOwned>(
+    conn: &Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<T>> {
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(params, |row| row.get::<_, String>(0))?;
+    rows.map(|data| Ok(serde_json::from_str(&data?)?)).collect()
+}
+
+/// `meta` key recording that `profile`'s JSON files were imported
+fn imported_key(profile: &str) -> String {
+    format!("imported:{}", profile)
+}
+
+/// One profile's sessions and groups in `aoe.db`
+pub struct SqliteStore {
+    profile: String,
+    conn: Mutex<Connection>,
+}
+
+impl SqliteStore {
+    /// Open `profile`'s part of the database, importing its JSON files the
+    /// first time.
+    pub fn open(profile: &str) -> Result<Self> {
+        let store = Self {
+            profile: profile.to_string(),
+            conn: Mutex::new(connect()?),
+        };
+        store.import_json()?;
+        Ok(store)
+    }
+
+    fn import_json(&self) -> Result<()> {
+        let key = imported_key(&self.profile);
+        if meta(&self.conn.lock().unwrap(), &key)?.is_some() {
+            return Ok(());
+        }
+        let json = JsonFileStore::new(&get_profile_dir(&self.profile)?);
+        self.save_sessions(&json.load_sessions()?)?;
+        self.save_groups(&json.load_groups()?)?;
+        set_meta(
+            &self.conn.lock().unwrap(),
+            &key,
+            &chrono::Utc::now().to_rfc3339(),
+        )
+    }
+}
+
+impl SessionStore for SqliteStore {
+    fn load_sessions(&self) -> Result<Vec<Instance>> {
+        self.query_sessions(&SessionQuery::default())
+    }
+
+    fn load_groups(&self) -> Result<Vec<Group>> {
+        load_rows(
+            &self.conn.lock().unwrap(),
+            "SELECT data FROM groups WHERE profile = ?1 ORDER BY position",
+            [&self.profile],
+        )
+    }
+
+    fn save_sessions(&self, instances: &[Instance]) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM sessions WHERE profile = ?1", [&self.profile])?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO sessions (profile, position, id, tool, search, data) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for (position, inst) in instances.iter().enumerate() {
+                insert.execute(params![
+                    self.profile,
+                    position as i64,
+                    inst.id,
+                    inst.tool,
+                    search_text(inst),
+                    serde_json::to_string(inst)?
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn save_groups(&self, groups: &[Group]) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM groups WHERE profile = ?1", [&self.profile])?;
+        {
+            let mut insert =
+                tx.prepare("INSERT INTO groups (profile, position, data) VALUES (?1, ?2, ?3)")?;
+            for (position, group) in groups.iter().enumerate() {
+                insert.execute(params![
+                    self.profile,
+                    position as i64,
+                    serde_json::to_string(group)?
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn query_sessions(&self, query: &SessionQuery) -> Result<Vec<Instance>> {
+        load_rows(
+            &self.conn.lock().unwrap(),
+            "SELECT data FROM sessions WHERE profile = ?1 \
+             AND (?2 IS NULL OR instr(search, ?2) > 0) \
+             AND (?3 IS NULL OR tool = ?3) \
+             ORDER BY position",
+            params![
+                self.profile,
+                query.text.as_deref().map(str::to_lowercase),
+                query.tool
+            ],
+        )
+    }
+}
+
+/// Move profile `old`'s sessions and groups to `new`, replacing anything
+/// left under that name.
+pub fn rename_profile(old: &str, new: &str) -> Result<()> {
+    let mut conn = connect()?;
+    let tx = conn.transaction()?;
+    for table in ["sessions", "groups"] {
+        tx.execute(&format!("DELETE FROM {} WHERE profile = ?1", table), [new])?;
+        tx.execute(
+            &format!("UPDATE {} SET profile = ?2 WHERE profile = ?1", table),
+            [old, new],
+        )?;
+    }
+    tx.execute("DELETE FROM meta WHERE key = ?1", [imported_key(new)])?;
+    tx.execute(
+        "UPDATE meta SET key = ?2 WHERE key = ?1",
+        [imported_key(old), imported_key(new)],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Forget profile `name`'s sessions and groups, and that its JSON files
+/// were imported.
+pub fn delete_profile(name: &str) -> Result<()> {
+    let mut conn = connect()?;
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM sessions WHERE profile = ?1", [name])?;
+    tx.execute("DELETE FROM groups WHERE profile = ?1", [name])?;
+    tx.execute("DELETE FROM meta WHERE key = ?1", [imported_key(name)])?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Records of finished sessions matching `query`, oldest first.
+pub fn query_records(query: &HistoryQuery) -> Result<Vec<SessionRecord>> {
+    let mut conn = connect()?;
+    sync_history(&mut conn)?;
+    load_rows(
+        &conn,
+        "SELECT data FROM history \
+         WHERE (?1 IS NULL OR ended_at >= ?1) \
+         AND (?2 IS NULL OR instr(project, ?2) > 0) \
+         AND (?3 IS NULL OR profile = ?3) \
+         ORDER BY rowid",
+        params![
+            query.since.map(|since| since.timestamp()),
+            query.project,
+            query.profile
+        ],
+    )
+}
+
+/// Index the lines appended to `stats.jsonl` since the last sync. A log
+/// shorter than what was indexed has been rewritten, so it is indexed again
+/// from the start.
+fn sync_history(conn: &mut Connection) -> Result<()> {
+    let Ok(mut file) = File::open(stats_path()?) else {
+        return Ok(());
+    };
+    let len = file.metadata()?.len();
+
+    // Immediate, so two processes syncing at once don't both insert
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let mut offset: u64 = meta(&tx, HISTORY_OFFSET)?
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0);
+    if len == offset {
+        return Ok(());
+    }
+    if len < offset {
+        tx.execute("DELETE FROM history", [])?;
+        offset = 0;
+    }
+
+    file.seek(SeekFrom::Start(offset))?;
+    let mut appended = String::new();
+    file.read_to_string(&mut appended)?;
+    // A line still being written has no newline yet; it waits for next time
+    let complete = appended.rfind('\n').map_or(0, |end| end + 1);
+    {
+        let mut insert = tx.prepare(
+            "INSERT INTO history (ended_at, profile, project, data) VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for line in appended[..complete].lines() {
+            let Ok(record) = serde_json::from_str::<SessionRecord>(line) else {
+                continue;
+            };
+            insert.execute(params![
+                record.ended_at.timestamp(),
+                record.profile,
+                record.project,
+                line
+            ])?;
+        }
+    }
+    set_meta(&tx, HISTORY_OFFSET, &(offset + complete as u64).to_string())?;
+    tx.commit()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::stats::Outcome;
+    use chrono::{Duration as ChronoDuration, Utc};
+    use serial_test::serial;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn setup_test_home(temp: &std::path::Path) {
+        std::env::set_var("HOME", temp);
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.join(".config"));
+    }
+
+    fn record(title: &str, project: &str, days_ago: i64) -> SessionRecord {
+        SessionRecord {
+            ended_at: Utc::now() - ChronoDuration::days(days_ago),
+            session_id: title.to_string(),
+            title: title.to_string(),
+            project: project.to_string(),
+            tool: "claude".to_string(),
+            profile: "default".to_string(),
+            duration_secs: 60,
+            cost: None,
+            outcome: Outcome::Completed,
+        }
+    }
+
+    fn append_line(record: &SessionRecord) {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(stats_path().unwrap())
+            .unwrap();
+        writeln!(file, "{}", serde_json::to_string(record).unwrap()).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_imports_json_then_saves_and_queries() {
+        let temp = TempDir::new().unwrap();
+        setup_test_home(temp.path());
+
+        let mut api = Instance::new("Fix API", "/src/api");
+        api.tool = "codex".to_string();
+        JsonFileStore::new(&get_profile_dir("default").unwrap())
+            .save_sessions(&[api.clone(), Instance::new("web", "/src/web")])
+            .unwrap();
+
+        let store = SqliteStore::open("default").unwrap();
+        let titles = |query: &SessionQuery| -> Vec<String> {
+            store
+                .query_sessions(query)
+                .unwrap()
+                .into_iter()
+                .map(|i| i.title)
+                .collect()
+        };
+        assert_eq!(titles(&SessionQuery::default()), vec!["Fix API", "web"]);
+        let query = SessionQuery {
+            text: Some("fix".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(titles(&query), vec!["Fix API"]);
+        let query = SessionQuery {
+            text: Some("SRC".to_string()),
+            tool: Some("claude".to_string()),
+        };
+        assert_eq!(titles(&query), vec!["web"]);
+
+        // Saving replaces the profile's sessions; a second open doesn't
+        // import the JSON files again
+        store.save_sessions(&[api]).unwrap();
+        let reopened = SqliteStore::open("default").unwrap();
+        assert_eq!(reopened.load_sessions().unwrap().len(), 1);
+        assert!(SqliteStore::open("other")
+            .unwrap()
+            .load_sessions()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_profile_rename_and_delete_move_the_rows() {
+        use crate::session::{
+            create_profile, delete_profile, rename_profile, save_config, Config, Storage,
+            StorageBackend,
+        };
+
+        let temp = TempDir::new().unwrap();
+        setup_test_home(temp.path());
+        let mut config = Config::default();
+        config.storage.backend = StorageBackend::Sqlite;
+        save_config(&config).unwrap();
+
+        // The JSON file is imported once, then goes stale
+        create_profile("team").unwrap();
+        JsonFileStore::new(&get_profile_dir("team").unwrap())
+            .save_sessions(&[Instance::new("stale", "/src/old")])
+            .unwrap();
+        let team = Storage::new("team").unwrap();
+        team.save(&[Instance::new("current", "/src/api")]).unwrap();
+
+        let titles = |profile: &str| -> Vec<String> {
+            Storage::new(profile)
+                .unwrap()
+                .load()
+                .unwrap()
+                .into_iter()
+                .map(|i| i.title)
+                .collect()
+        };
+        rename_profile("team", "crew").unwrap();
+        assert_eq!(titles("crew"), vec!["current"]);
+
+        delete_profile("crew").unwrap();
+        create_profile("crew").unwrap();
+        assert!(titles("crew").is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_history_follows_the_stats_log() {
+        let temp = TempDir::new().unwrap();
+        setup_test_home(temp.path());
+
+        append_line(&record("old", "/src/api", 30));
+        append_line(&record("new", "/src/web", 1));
+        let titles = |query: &HistoryQuery| -> Vec<String> {
+            query_records(query)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.title)
+                .collect()
+        };
+        assert_eq!(titles(&HistoryQuery::default()), vec!["old", "new"]);
+        let recent = HistoryQuery {
+            since: Some(Utc::now() - ChronoDuration::days(7)),
+            ..Default::default()
+        };
+        assert_eq!(titles(&recent), vec!["new"]);
+
+        // Only what was appended since is read
+        append_line(&record("newer", "/src/api", 0));
+        let api = HistoryQuery {
+            project: Some("api".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(titles(&api), vec!["old", "newer"]);
+
+        // A rewritten log is indexed from scratch
+        std::fs::remove_file(stats_path().unwrap()).unwrap();
+        append_line(&record("only", "/src/api", 0));
+        assert_eq!(titles(&HistoryQuery::default()), vec!["only"]);
+    }
+}
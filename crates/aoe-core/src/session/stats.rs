@@ -0,0 +1,333 @@
+//! History of finished sessions for `aoe stats`
+//!
+//! When a session is removed, a record of how it went (how long it ran,
+//! the cost the agent last reported, whether its branch was merged or it
+//! ended in an error, and which agent ran it) is appended to `stats.jsonl`
+//! in the app directory. Like the audit log it spans all profiles and is
+//! never trimmed. Recording is best-effort and never blocks a removal.
+//!
+//! `query` reads the log back filtered. With the SQLite backend the log is
+//! also indexed in `aoe.db`, so the filtering happens there.
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{get_app_dir, Instance, Status};
+
+/// Pane lines searched for the agent's final cost report
+const COST_SCAN_LINES: usize = 200;
+
+/// How a session ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    /// Its branch landed in the base branch
+    Merged,
+    /// Removed without an error
+    Completed,
+    /// Removed while in the error state
+    Failed,
+}
+
+impl Outcome {
+    pub fn is_success(self) -> bool {
+        self != Outcome::Failed
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRecord {
+    pub ended_at: DateTime<Utc>,
+    pub session_id: String,
+    pub title: String,
+    /// Main repository the session worked in
+    pub project: String,
+    pub tool: String,
+    /// Profile the session belonged to
+    #[serde(default)]
+    pub profile: String,
+    pub duration_secs: i64,
+    /// Cost the agent last reported, in dollars
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+    pub outcome: Outcome,
+}
+
+pub fn stats_path() -> Result<PathBuf> {
+    Ok(get_app_dir()?.join("stats.jsonl"))
+}
+
+/// Record a session that is being removed. Call before its tmux session is
+/// killed so the agent's last cost report can still be read.
+pub fn record_removed(instance: &Instance, profile: &str) {
+    let cost = pane_cost(instance);
+    let record = SessionRecord {
+        ended_at: Utc::now(),
+        session_id: instance.id.clone(),
+        title: instance.title.clone(),
+        project: project_of(instance),
+        tool: instance.tool.clone(),
+        profile: profile.to_string(),
+        duration_secs: (Utc::now() - instance.created_at).num_seconds().max(0),
+        cost,
+        outcome: outcome_of(instance),
+    };
+    if let Err(e) = append(&record) {
+        tracing::warn!("Failed to write session stats: {}", e);
+    }
+}
+
+/// Cost the session's agent last reported in its pane, if it is running
+pub fn pane_cost(instance: &Instance) -> Option<f64> {
+    instance
+        .tmux_session()
+        .ok()
+        .filter(|s| s.exists())
+        .and_then(|s| s.capture_pane(COST_SCAN_LINES).ok())
+        .and_then(|content| crate::tmux::output_parser::reported_cost(&content))
+}
+
+fn project_of(instance: &Instance) -> String {
+    instance
+        .worktree_info
+        .as_ref()
+        .map(|wt| wt.main_repo_path.clone())
+        .unwrap_or_else(|| instance.project_path.clone())
+}
+
+fn outcome_of(instance: &Instance) -> Outcome {
+    if instance.status == Status::Error {
+        return Outcome::Failed;
+    }
+    let merged = instance.worktree_info.as_ref().is_some_and(|wt| {
+        crate::git::merged::branch_merged(
+            Path::new(&wt.main_repo_path),
+            &wt.branch,
+            instance.created_at,
+        )
+        .is_some()
+    });
+    if merged {
+        Outcome::Merged
+    } else {
+        Outcome::Completed
+    }
+}
+
+fn append(record: &SessionRecord) -> Result<()> {
+    let path = stats_path()?;
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
+/// Which records `query` returns. Unset fields match everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistoryQuery {
+    /// Sessions that ended at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Text in the project path
+    pub project: Option<String>,
+    pub profile: Option<String>,
+}
+
+impl HistoryQuery {
+    pub fn matches(&self, record: &SessionRecord) -> bool {
+        self.since.map_or(true, |since| record.ended_at >= since)
+            && self
+                .project
+                .as_deref()
+                .map_or(true, |project| record.project.contains(project))
+            && self
+                .profile
+                .as_deref()
+                .map_or(true, |profile| record.profile == profile)
+    }
+}
+
+/// Records matching `query`, oldest first
+pub fn query(query: &HistoryQuery) -> Result<Vec<SessionRecord>> {
+    #[cfg(feature = "sqlite")]
+    if super::storage::backend() == super::StorageBackend::Sqlite {
+        return super::sqlite_store::query_records(query);
+    }
+    Ok(load()?.into_iter().filter(|r| query.matches(r)).collect())
+}
+
+/// Every record, oldest first. Unparseable lines are skipped.
+pub fn load() -> Result<Vec<SessionRecord>> {
+    let path = stats_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(fs::read_to_string(&path)?
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Totals for a set of records
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Summary {
+    pub sessions: usize,
+    pub succeeded: usize,
+    pub merged: usize,
+    pub duration_secs: i64,
+    pub cost: f64,
+}
+
+impl Summary {
+    fn add(&mut self, record: &SessionRecord) {
+        self.sessions += 1;
+        self.succeeded += record.outcome.is_success() as usize;
+        self.merged += (record.outcome == Outcome::Merged) as usize;
+        self.duration_secs += record.duration_secs;
+        self.cost += record.cost.unwrap_or(0.0);
+    }
+
+    /// Share of sessions that did not fail, from 0.0 to 1.0
+    pub fn success_rate(&self) -> Option<f64> {
+        (self.sessions > 0).then(|| self.succeeded as f64 / self.sessions as f64)
+    }
+}
+
+/// Monday of the week `at` falls in
+pub fn week_start(at: DateTime<Utc>) -> NaiveDate {
+    let date = at.date_naive();
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// One summary per week for the `weeks` weeks up to and including the one
+/// containing `now`, oldest first. Weeks without sessions are included.
+pub fn weekly(
+    records: &[SessionRecord],
+    weeks: usize,
+    now: DateTime<Utc>,
+) -> Vec<(NaiveDate, Summary)> {
+    let current = week_start(now);
+    let mut result: Vec<(NaiveDate, Summary)> = (0..weeks)
+        .rev()
+        .map(|ago| (current - Duration::weeks(ago as i64), Summary::default()))
+        .collect();
+    for record in records {
+        let week = week_start(record.ended_at);
+        if let Some((_, summary)) = result.iter_mut().find(|(start, _)| *start == week) {
+            summary.add(record);
+        }
+    }
+    result
+}
+
+/// Totals per project, keyed by project path
+pub fn by_project<'a>(
+    records: impl IntoIterator<Item = &'a SessionRecord>,
+) -> BTreeMap<String, Summary> {
+    let mut projects: BTreeMap<String, Summary> = BTreeMap::new();
+    for record in records {
+        projects
+            .entry(record.project.clone())
+            .or_default()
+            .add(record);
+    }
+    projects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    fn record(
+        project: &str,
+        ended_at: DateTime<Utc>,
+        cost: Option<f64>,
+        outcome: Outcome,
+    ) -> SessionRecord {
+        SessionRecord {
+            ended_at,
+            session_id: "id".to_string(),
+            title: "t".to_string(),
+            project: project.to_string(),
+            tool: "claude".to_string(),
+            profile: "default".to_string(),
+            duration_secs: 600,
+            cost,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_weekly_buckets_by_monday() {
+        let now: DateTime<Utc> = "2026-03-12T12:00:00Z".parse().unwrap(); // Thursday
+        let records = vec![
+            record(
+                "/a",
+                "2026-03-09T08:00:00Z".parse().unwrap(),
+                Some(1.5),
+                Outcome::Merged,
+            ),
+            record(
+                "/a",
+                "2026-03-11T08:00:00Z".parse().unwrap(),
+                None,
+                Outcome::Failed,
+            ),
+            record(
+                "/b",
+                "2026-03-01T08:00:00Z".parse().unwrap(),
+                Some(2.0),
+                Outcome::Completed,
+            ),
+            record(
+                "/b",
+                "2025-12-01T08:00:00Z".parse().unwrap(),
+                Some(9.0),
+                Outcome::Completed,
+            ),
+        ];
+        let weeks = weekly(&records, 3, now);
+        let starts: Vec<String> = weeks.iter().map(|(d, _)| d.to_string()).collect();
+        assert_eq!(starts, vec!["2026-02-23", "2026-03-02", "2026-03-09"]);
+        assert_eq!(weeks[0].1.sessions, 1);
+        assert_eq!(weeks[1].1.sessions, 0);
+        assert_eq!(weeks[2].1.sessions, 2);
+        assert_eq!(weeks[2].1.success_rate(), Some(0.5));
+        assert_eq!(weeks[2].1.cost, 1.5);
+        assert_eq!(weeks[1].1.success_rate(), None);
+
+        let projects = by_project(&records);
+        assert_eq!(projects["/b"].cost, 11.0);
+        assert_eq!(projects["/a"].merged, 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_record_removed_and_load() -> Result<()> {
+        let temp = tempdir()?;
+        std::env::set_var("HOME", temp.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.path().join(".config"));
+
+        let mut inst = Instance::new("fix-login", "/tmp/project");
+        inst.tool = "claude".to_string();
+        inst.status = Status::Error;
+        inst.created_at = Utc::now() - Duration::minutes(30);
+        record_removed(&inst, "work");
+
+        let records = load()?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].project, "/tmp/project");
+        assert_eq!(records[0].profile, "work");
+        assert_eq!(records[0].outcome, Outcome::Failed);
+        assert!(records[0].duration_secs >= 30 * 60);
+        assert_eq!(records[0].cost, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,573 @@
+//! Session storage
+//!
+//! `Storage` is what the rest of the app loads and saves a profile's
+//! sessions and groups through. Where they actually live is up to a
+//! `SessionStore`, picked by `storage.backend`: the default, `JsonFileStore`,
+//! keeps them in `sessions.json` and `groups.json` in the profile directory;
+//! `SqliteStore` (with the `sqlite` feature) keeps every profile in one
+//! indexed database.
+
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use super::config::{Config, StorageBackend};
+use super::{get_profile_dir, Group, GroupTree, Instance, DEFAULT_PROFILE};
+
+const SESSIONS_FILE: &str = "sessions.json";
+const GROUPS_FILE: &str = "groups.json";
+
+/// Which sessions `SessionStore::query_sessions` returns. Unset fields
+/// match everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionQuery {
+    /// Text in the title, project path, or branch, ignoring case
+    pub text: Option<String>,
+    /// Agent the session runs, e.g. "claude"
+    pub tool: Option<String>,
+}
+
+impl SessionQuery {
+    pub fn matches(&self, instance: &Instance) -> bool {
+        let text_matches = self.text.as_deref().map_or(true, |text| {
+            search_text(instance).contains(&text.to_lowercase())
+        });
+        let tool_matches = self
+            .tool
+            .as_deref()
+            .map_or(true, |tool| instance.tool == tool);
+        text_matches && tool_matches
+    }
+}
+
+/// What `SessionQuery::text` is matched against, lowercased
+pub(crate) fn search_text(instance: &Instance) -> String {
+    let branch = instance
+        .worktree_info
+        .as_ref()
+        .map(|wt| wt.branch.as_str())
+        .unwrap_or_default();
+    format!("{}\n{}\n{}", instance.title, instance.project_path, branch).to_lowercase()
+}
+
+/// Persistence for one profile's session metadata
+pub trait SessionStore: Send + Sync {
+    fn load_sessions(&self) -> Result<Vec<Instance>>;
+    fn load_groups(&self) -> Result<Vec<Group>>;
+    fn save_sessions(&self, instances: &[Instance]) -> Result<()>;
+    fn save_groups(&self, groups: &[Group]) -> Result<()>;
+
+    /// Sessions matching `query`, in saved order. By default every session
+    /// is loaded and filtered in memory; stores that index their sessions
+    /// answer it themselves.
+    fn query_sessions(&self, query: &SessionQuery) -> Result<Vec<Instance>> {
+        Ok(self
+            .load_sessions()?
+            .into_iter()
+            .filter(|i| query.matches(i))
+            .collect())
+    }
+}
+
+/// The configured `storage.backend`. An unreadable config means the default,
+/// as it does everywhere else the global config is read.
+pub fn backend() -> StorageBackend {
+    Config::load()
+        .map(|c| c.storage.backend)
+        .unwrap_or_default()
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn sqlite_unavailable() -> anyhow::Error {
+    anyhow::anyhow!(
+        "storage.backend is \"sqlite\", but this aoe was built without the `sqlite` feature\n\
+         Tip: Set storage.backend = \"json\" in the global config, or build aoe \
+         with `cargo build --release --features sqlite`"
+    )
+}
+
+/// Move profile `old`'s sessions and groups to `new` when renaming it. The
+/// JSON files move along with the profile directory; other stores keep them
+/// elsewhere.
+#[cfg_attr(not(feature = "sqlite"), allow(unused_variables))]
+pub(crate) fn rename_profile_data(old: &str, new: &str) -> Result<()> {
+    match backend() {
+        StorageBackend::Json => Ok(()),
+        #[cfg(feature = "sqlite")]
+        StorageBackend::Sqlite => super::sqlite_store::rename_profile(old, new),
+        #[cfg(not(feature = "sqlite"))]
+        StorageBackend::Sqlite => Err(sqlite_unavailable()),
+    }
+}
+
+/// Drop profile `name`'s sessions and groups when deleting it, so a profile
+/// created later under the same name starts empty.
+#[cfg_attr(not(feature = "sqlite"), allow(unused_variables))]
+pub(crate) fn delete_profile_data(name: &str) -> Result<()> {
+    match backend() {
+        StorageBackend::Json => Ok(()),
+        #[cfg(feature = "sqlite")]
+        StorageBackend::Sqlite => super::sqlite_store::delete_profile(name),
+        #[cfg(not(feature = "sqlite"))]
+        StorageBackend::Sqlite => Err(sqlite_unavailable()),
+    }
+}
+
+/// Sessions and groups as pretty-printed JSON files, with a backup of the
+/// previous sessions file kept on every save
+pub struct JsonFileStore {
+    sessions_path: PathBuf,
+    groups_path: PathBuf,
+}
+
+impl JsonFileStore {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            sessions_path: dir.join(SESSIONS_FILE),
+            groups_path: dir.join(GROUPS_FILE),
+        }
+    }
+}
+
+/// Parse a JSON array file, treating a missing or blank file as empty
+fn read_json_list<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Vec<T>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path)?;
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(serde_json::from_str(&content)?)
+}
+
+impl SessionStore for JsonFileStore {
+    fn load_sessions(&self) -> Result<Vec<Instance>> {
+        read_json_list(&self.sessions_path)
+    }
+
+    fn load_groups(&self) -> Result<Vec<Group>> {
+        read_json_list(&self.groups_path)
+    }
+
+    fn save_sessions(&self, instances: &[Instance]) -> Result<()> {
+        // Create backup
+        if self.sessions_path.exists() {
+            let backup_path = self.sessions_path.with_extension("json.bak");
+            if let Err(e) = fs::copy(&self.sessions_path, &backup_path) {
+                warn!("Failed to create backup: {}", e);
+            }
+        }
+
+        let content = serde_json::to_string_pretty(instances)?;
+        fs::write(&self.sessions_path, content)?;
+        Ok(())
+    }
+
+    fn save_groups(&self, groups: &[Group]) -> Result<()> {
+        let content = serde_json::to_string_pretty(groups)?;
+        fs::write(&self.groups_path, content)?;
+        Ok(())
+    }
+}
+
+pub struct Storage {
+    profile: String,
+    store: Box<dyn SessionStore>,
+}
+
+impl Storage {
+    pub fn new(profile: &str) -> Result<Self> {
+        let profile_name = if profile.is_empty() {
+            DEFAULT_PROFILE.to_string()
+        } else {
+            profile.to_string()
+        };
+
+        let store: Box<dyn SessionStore> = match backend() {
+            StorageBackend::Json => Box::new(JsonFileStore::new(&get_profile_dir(&profile_name)?)),
+            #[cfg(feature = "sqlite")]
+            StorageBackend::Sqlite => Box::new(super::SqliteStore::open(&profile_name)?),
+            #[cfg(not(feature = "sqlite"))]
+            StorageBackend::Sqlite => return Err(sqlite_unavailable()),
+        };
+        Ok(Self::with_store(&profile_name, store))
+    }
+
+    /// Storage for `profile` backed by another store
+    pub fn with_store(profile: &str, store: Box<dyn SessionStore>) -> Self {
+        Self {
+            profile: profile.to_string(),
+            store,
+        }
+    }
+
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    pub fn load(&self) -> Result<Vec<Instance>> {
+        self.store.load_sessions()
+    }
+
+    pub fn load_with_groups(&self) -> Result<(Vec<Instance>, Vec<Group>)> {
+        Ok((self.store.load_sessions()?, self.store.load_groups()?))
+    }
+
+    /// Sessions matching `query`, in saved order.
+    pub fn query(&self, query: &SessionQuery) -> Result<Vec<Instance>> {
+        self.store.query_sessions(query)
+    }
+
+    pub fn save(&self, instances: &[Instance]) -> Result<()> {
+        self.store.save_sessions(instances)
+    }
+
+    pub fn save_with_groups(&self, instances: &[Instance], group_tree: &GroupTree) -> Result<()> {
+        self.save(instances)?;
+        self.store.save_groups(&group_tree.get_all_groups())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    fn sessions_path(storage: &Storage) -> PathBuf {
+        get_profile_dir(storage.profile())
+            .unwrap()
+            .join(SESSIONS_FILE)
+    }
+
+    fn setup_test_home(temp: &std::path::Path) {
+        std::env::set_var("HOME", temp);
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.join(".config"));
+    }
+
+    #[test]
+    fn test_storage_roundtrip() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let storage = Storage::new("test-profile")?;
+
+        let instances = vec![
+            Instance::new("test1", "/tmp/test1"),
+            Instance::new("test2", "/tmp/test2"),
+        ];
+
+        storage.save(&instances)?;
+        let loaded = storage.load()?;
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].title, "test1");
+        assert_eq!(loaded[1].title, "test2");
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_new_with_empty_profile() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let storage = Storage::new("")?;
+        assert_eq!(storage.profile(), "default");
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_new_with_custom_profile() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let storage = Storage::new("custom-profile")?;
+        assert_eq!(storage.profile(), "custom-profile");
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_load_nonexistent_file() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let storage = Storage::new("test-empty")?;
+        let loaded = storage.load()?;
+
+        assert!(loaded.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_load_empty_file() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let storage = Storage::new("test-empty-file")?;
+
+        // Create empty file
+        fs::create_dir_all(sessions_path(&storage).parent().unwrap())?;
+        fs::write(sessions_path(&storage), "")?;
+
+        let loaded = storage.load()?;
+        assert!(loaded.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_load_whitespace_only_file() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let storage = Storage::new("test-whitespace")?;
+
+        fs::create_dir_all(sessions_path(&storage).parent().unwrap())?;
+        fs::write(sessions_path(&storage), "   \n  \t  ")?;
+
+        let loaded = storage.load()?;
+        assert!(loaded.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_save_creates_backup() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let storage = Storage::new("test-backup")?;
+
+        // First save
+        let instances = vec![Instance::new("test1", "/tmp/test1")];
+        storage.save(&instances)?;
+
+        // Second save (should create backup)
+        let instances2 = vec![Instance::new("test2", "/tmp/test2")];
+        storage.save(&instances2)?;
+
+        // Check backup exists
+        let backup_path = sessions_path(&storage).with_extension("json.bak");
+        assert!(backup_path.exists());
+
+        // Backup should contain first save content
+        let backup_content = fs::read_to_string(&backup_path)?;
+        assert!(backup_content.contains("test1"));
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_save_empty_array() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let storage = Storage::new("test-empty-save")?;
+        storage.save(&[])?;
+
+        let content = fs::read_to_string(sessions_path(&storage))?;
+        assert_eq!(content.trim(), "[]");
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_load_with_groups_no_groups_file() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let storage = Storage::new("test-no-groups")?;
+
+        let instances = vec![Instance::new("test", "/tmp/test")];
+        storage.save(&instances)?;
+
+        let (loaded_instances, loaded_groups) = storage.load_with_groups()?;
+        assert_eq!(loaded_instances.len(), 1);
+        assert!(loaded_groups.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_save_and_load_with_groups() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let storage = Storage::new("test-with-groups")?;
+
+        let mut instances = vec![Instance::new("test", "/tmp/test")];
+        instances[0].group_path = "work/projects".to_string();
+
+        let groups = vec![Group::new("projects", "work/projects")];
+        let group_tree = GroupTree::new_with_groups(&instances, &groups);
+
+        storage.save_with_groups(&instances, &group_tree)?;
+
+        let (loaded_instances, loaded_groups) = storage.load_with_groups()?;
+        assert_eq!(loaded_instances.len(), 1);
+        assert_eq!(loaded_instances[0].group_path, "work/projects");
+        assert!(!loaded_groups.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_load_invalid_json() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let storage = Storage::new("test-invalid")?;
+
+        fs::create_dir_all(sessions_path(&storage).parent().unwrap())?;
+        fs::write(sessions_path(&storage), "{ invalid json }")?;
+
+        let result = storage.load();
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_preserves_instance_fields() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let storage = Storage::new("test-fields")?;
+
+        let mut instance = Instance::new("Test Project", "/home/user/project");
+        instance.tool = "opencode".to_string();
+        instance.command = "opencode --config test".to_string();
+        instance.group_path = "work/clients".to_string();
+
+        storage.save(&[instance.clone()])?;
+        let loaded = storage.load()?;
+
+        assert_eq!(loaded.len(), 1);
+        let loaded_instance = &loaded[0];
+        assert_eq!(loaded_instance.title, "Test Project");
+        assert_eq!(loaded_instance.project_path, "/home/user/project");
+        assert_eq!(loaded_instance.tool, "opencode");
+        assert_eq!(loaded_instance.command, "opencode --config test");
+        assert_eq!(loaded_instance.group_path, "work/clients");
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_profile_accessor() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        // Verify profiles are correctly named
+        let storage1 = Storage::new("profile-alpha")?;
+        let storage2 = Storage::new("profile-beta")?;
+
+        assert_eq!(storage1.profile(), "profile-alpha");
+        assert_eq!(storage2.profile(), "profile-beta");
+
+        // Verify they use different paths (implying isolation)
+        assert_ne!(sessions_path(&storage1), sessions_path(&storage2));
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_groups_file_empty() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let storage = Storage::new("test-empty-groups")?;
+
+        // Save sessions
+        storage.save(&[Instance::new("test", "/tmp/test")])?;
+
+        // Create empty groups file
+        let groups_path = sessions_path(&storage).with_file_name("groups.json");
+        fs::write(&groups_path, "   ")?;
+
+        let (instances, groups) = storage.load_with_groups()?;
+        assert_eq!(instances.len(), 1);
+        assert!(groups.is_empty());
+        Ok(())
+    }
+
+    #[derive(Default)]
+    struct MemoryStore {
+        sessions: std::sync::Mutex<Vec<Instance>>,
+        groups: std::sync::Mutex<Vec<Group>>,
+    }
+
+    impl SessionStore for MemoryStore {
+        fn load_sessions(&self) -> Result<Vec<Instance>> {
+            Ok(self.sessions.lock().unwrap().clone())
+        }
+        fn load_groups(&self) -> Result<Vec<Group>> {
+            Ok(self.groups.lock().unwrap().clone())
+        }
+        fn save_sessions(&self, instances: &[Instance]) -> Result<()> {
+            *self.sessions.lock().unwrap() = instances.to_vec();
+            Ok(())
+        }
+        fn save_groups(&self, groups: &[Group]) -> Result<()> {
+            *self.groups.lock().unwrap() = groups.to_vec();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_storage_with_custom_store() -> Result<()> {
+        let storage = Storage::with_store("mem", Box::<MemoryStore>::default());
+        let mut inst = Instance::new("test", "/tmp/test");
+        inst.group_path = "work".to_string();
+        let tree = GroupTree::new_with_groups(std::slice::from_ref(&inst), &[]);
+        storage.save_with_groups(&[inst], &tree)?;
+
+        let (instances, groups) = storage.load_with_groups()?;
+        assert_eq!(storage.profile(), "mem");
+        assert_eq!(instances[0].title, "test");
+        assert_eq!(groups[0].path, "work");
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_query_filters_in_memory() -> Result<()> {
+        let storage = Storage::with_store("mem", Box::<MemoryStore>::default());
+        let mut api = Instance::new("Fix API", "/src/api");
+        api.tool = "codex".to_string();
+        let mut web = Instance::new("web", "/src/web");
+        web.tool = "claude".to_string();
+        storage.save(&[api, web])?;
+
+        let titles = |query: SessionQuery| -> Result<Vec<String>> {
+            Ok(storage
+                .query(&query)?
+                .into_iter()
+                .map(|i| i.title)
+                .collect())
+        };
+        assert_eq!(titles(SessionQuery::default())?, vec!["Fix API", "web"]);
+        assert_eq!(
+            titles(SessionQuery {
+                text: Some("fix".to_string()),
+                tool: None,
+            })?,
+            vec!["Fix API"]
+        );
+        assert_eq!(
+            titles(SessionQuery {
+                text: Some("SRC".to_string()),
+                tool: Some("claude".to_string()),
+            })?,
+            vec!["web"]
+        );
+        Ok(())
+    }
+}
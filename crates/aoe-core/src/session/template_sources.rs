@@ -0,0 +1,543 @@
+//! Templates fetched from a git repo or URL
+//!
+//! `aoe template add` registers a source of shared templates (see
+//! `repo_config::templates_dir`): a git repository, given as a clone URL or
+//! as `github:owner/repo` / `gitlab:group/repo`, or the URL of a single
+//! `.toml` file. A repository's templates are the `.toml` files in its
+//! `templates/` directory, or at its root if it has none. Repositories are
+//! cached under `template-cache/<name>` in the app dir and may be pinned to
+//! a branch, tag, or commit; `aoe template update` fetches again and
+//! re-installs. Sources are recorded in `template_sources.json` with the
+//! templates each one installed, so a source never overwrites a local
+//! template or another source's, and templates dropped upstream are removed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::get_app_dir;
+use super::repo_config::{is_valid_template_name, templates_dir};
+use crate::git::clone::{is_clone_url, repo_name};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceKind {
+    /// A git repository of templates
+    Git,
+    /// A single template file fetched over HTTP
+    File,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateSource {
+    pub name: String,
+    /// The source as given to `aoe template add`
+    pub source: String,
+    /// What is fetched, with `github:`-style shorthands expanded
+    pub url: String,
+    pub kind: SourceKind,
+    /// Branch, tag, or commit the source is pinned to; the remote's default
+    /// branch if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    /// Commit the templates were last installed from (git sources only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Templates installed from this source
+    #[serde(default)]
+    pub templates: Vec<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The first characters of a commit hash, as git shows it.
+pub fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(7)]
+}
+
+fn sources_path() -> Result<PathBuf> {
+    Ok(get_app_dir()?.join("template_sources.json"))
+}
+
+fn cache_dir(name: &str) -> Result<PathBuf> {
+    Ok(get_app_dir()?.join("template-cache").join(name))
+}
+
+/// Registered template sources, in the order they were added.
+pub fn load() -> Result<Vec<TemplateSource>> {
+    let path = sources_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn save(sources: &[TemplateSource]) -> Result<()> {
+    fs::write(sources_path()?, serde_json::to_string_pretty(sources)?)?;
+    Ok(())
+}
+
+/// What to fetch for a source given to `aoe template add`.
+pub fn resolve(source: &str) -> Result<(SourceKind, String)> {
+    let source = source.trim();
+    for (prefix, host) in [
+        ("github:", "https://github.com/"),
+        ("gitlab:", "https://gitlab.com/"),
+    ] {
+        if let Some(path) = source.strip_prefix(prefix) {
+            let path = path.trim_matches('/');
+            let path = path.strip_suffix(".git").unwrap_or(path);
+            if path.split('/').count() < 2 || path.split('/').any(str::is_empty) {
+                bail!("Expected {}owner/repo, got \"{}\"", prefix, source);
+            }
+            return Ok((SourceKind::Git, format!("{}{}.git", host, path)));
+        }
+    }
+    if (source.starts_with("https://") || source.starts_with("http://"))
+        && source.ends_with(".toml")
+    {
+        return Ok((SourceKind::File, source.to_string()));
+    }
+    if is_clone_url(source) {
+        return Ok((SourceKind::Git, source.to_string()));
+    }
+    bail!(
+        "\"{}\" is not a git URL, github:owner/repo, or the URL of a .toml file",
+        source
+    )
+}
+
+/// The name a source gets without `--name`: the repository's name, or the
+/// file's name without `.toml`.
+fn default_name(kind: SourceKind, url: &str) -> Option<String> {
+    match kind {
+        SourceKind::Git => repo_name(url).map(str::to_string),
+        SourceKind::File => url
+            .rsplit('/')
+            .next()
+            .and_then(|file| file.strip_suffix(".toml"))
+            .map(str::to_string),
+    }
+}
+
+/// Register `source`, optionally pinned to `rev`, and install its templates.
+pub async fn add(
+    source: &str,
+    rev: Option<String>,
+    name: Option<String>,
+) -> Result<TemplateSource> {
+    let (kind, url) = resolve(source)?;
+    if kind == SourceKind::File && rev.is_some() {
+        bail!("--rev only applies to git repositories");
+    }
+    let name = match name {
+        Some(name) => name,
+        None => default_name(kind, &url)
+            .ok_or_else(|| anyhow!("Cannot tell a name for {}; pass --name", source))?,
+    };
+    if !is_valid_template_name(&name) {
+        bail!("Invalid source name '{}'", name);
+    }
+
+    let mut sources = load()?;
+    if sources.iter().any(|s| s.name == name) {
+        bail!(
+            "Template source '{}' is already added\n\
+             Tip: Run `aoe template update {}` to fetch it again, or pass --name",
+            name,
+            name
+        );
+    }
+
+    let mut added = TemplateSource {
+        name,
+        source: source.trim().to_string(),
+        url,
+        kind,
+        rev,
+        commit: None,
+        templates: Vec::new(),
+        updated_at: Utc::now(),
+    };
+    if let Err(e) = refresh(&mut added, &sources).await {
+        let cache = cache_dir(&added.name)?;
+        if cache.exists() {
+            let _ = fs::remove_dir_all(cache);
+        }
+        return Err(e);
+    }
+    sources.push(added.clone());
+    save(&sources)?;
+    Ok(added)
+}
+
+/// Fetch the source `name` (every source if `None`) again and re-install its
+/// templates, first re-pinning it to `rev` if given. Returns each source as
+/// it was before and after.
+pub async fn update(
+    name: Option<&str>,
+    rev: Option<String>,
+) -> Result<Vec<(TemplateSource, TemplateSource)>> {
+    let mut sources = load()?;
+    match name {
+        Some(name) if !sources.iter().any(|s| s.name == name) => {
+            bail!("Template source '{}' not found", name)
+        }
+        None if rev.is_some() => bail!("--rev needs the name of the source to pin"),
+        _ => {}
+    }
+
+    let mut updated = Vec::new();
+    for i in 0..sources.len() {
+        if name.is_some_and(|name| name != sources[i].name) {
+            continue;
+        }
+        let before = sources[i].clone();
+        let mut after = before.clone();
+        if let Some(rev) = &rev {
+            if after.kind == SourceKind::File {
+                bail!("--rev only applies to git repositories");
+            }
+            after.rev = Some(rev.clone());
+        }
+        refresh(&mut after, &sources)
+            .await
+            .with_context(|| format!("Failed to update '{}'", before.name))?;
+        sources[i] = after.clone();
+        save(&sources)?;
+        updated.push((before, after));
+    }
+    Ok(updated)
+}
+
+/// Unregister the source `name` and delete the templates it installed.
+pub fn remove(name: &str) -> Result<TemplateSource> {
+    let mut sources = load()?;
+    let index = sources
+        .iter()
+        .position(|s| s.name == name)
+        .ok_or_else(|| anyhow!("Template source '{}' not found", name))?;
+    let removed = sources.remove(index);
+
+    let dir = templates_dir()?;
+    for template in &removed.templates {
+        let _ = fs::remove_file(dir.join(format!("{}.toml", template)));
+    }
+    let cache = cache_dir(name)?;
+    if cache.exists() {
+        fs::remove_dir_all(&cache)?;
+    }
+    save(&sources)?;
+    Ok(removed)
+}
+
+/// Fetch `source` and install its templates, checking them against the
+/// templates the other sources in `all` own.
+async fn refresh(source: &mut TemplateSource, all: &[TemplateSource]) -> Result<()> {
+    let files = match source.kind {
+        SourceKind::Git => {
+            let checkout = cache_dir(&source.name)?;
+            let commit = checkout_rev(&source.url, &checkout, source.rev.as_deref())?;
+            source.commit = Some(commit);
+            read_templates(&checkout)?
+        }
+        SourceKind::File => vec![(source.name.clone(), download(&source.url).await?)],
+    };
+    install(source, files, all)?;
+    source.updated_at = Utc::now();
+    Ok(())
+}
+
+fn git(repo: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Clone `url` into `checkout`, or fetch into an earlier clone there, and
+/// check out `rev` (the remote's default branch if unset). Returns the
+/// commit checked out.
+fn checkout_rev(url: &str, checkout: &Path, rev: Option<&str>) -> Result<String> {
+    if checkout.join(".git").exists() {
+        git(
+            checkout,
+            &["fetch", "--quiet", "--tags", "--force", "--prune", "origin"],
+        )?;
+    } else {
+        if let Some(parent) = checkout.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let output = Command::new("git")
+            .args(["clone", "--quiet", "--", url])
+            .arg(checkout)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .stdin(Stdio::null())
+            .output()
+            .context("Failed to run git")?;
+        if !output.status.success() {
+            bail!(
+                "Failed to clone {}: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+    }
+
+    // A branch name is looked up on the remote first, so a pin to a branch
+    // follows it on update rather than sticking to the local copy
+    let candidates = match rev {
+        Some(rev) => vec![format!("origin/{}", rev), rev.to_string()],
+        None => vec!["origin/HEAD".to_string()],
+    };
+    let commit = candidates
+        .iter()
+        .find_map(|candidate| {
+            let spec = format!("{}^{{commit}}", candidate);
+            git(checkout, &["rev-parse", "--verify", "--quiet", &spec]).ok()
+        })
+        .ok_or_else(|| anyhow!("Revision '{}' not found in {}", rev.unwrap_or("HEAD"), url))?;
+    git(
+        checkout,
+        &["checkout", "--quiet", "--force", "--detach", &commit],
+    )?;
+    Ok(commit)
+}
+
+/// The templates in a checked-out repository, by name.
+fn read_templates(checkout: &Path) -> Result<Vec<(String, String)>> {
+    let nested = checkout.join("templates");
+    let dir = if nested.is_dir() {
+        nested
+    } else {
+        checkout.to_path_buf()
+    };
+
+    let mut templates = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if !path.is_file() || path.extension().map_or(true, |ext| ext != "toml") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if is_valid_template_name(name) {
+            templates.push((name.to_string(), fs::read_to_string(&path)?));
+        }
+    }
+    if templates.is_empty() {
+        bail!("The repository has no .toml templates in templates/ or at its root");
+    }
+    templates.sort();
+    Ok(templates)
+}
+
+async fn download(url: &str) -> Result<String> {
+    let client = reqwest::Client::builder()
+        .user_agent("agent-of-empires")
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch {}", url))?;
+    if !response.status().is_success() {
+        bail!("Failed to fetch {}: {}", url, response.status());
+    }
+    Ok(response.text().await?)
+}
+
+/// Write `files` into the templates directory for `source` and delete the
+/// ones it installed before that are gone now.
+fn install(
+    source: &mut TemplateSource,
+    files: Vec<(String, String)>,
+    all: &[TemplateSource],
+) -> Result<()> {
+    let dir = templates_dir()?;
+    for (template, content) in &files {
+        content
+            .parse::<toml::Table>()
+            .with_context(|| format!("Template '{}' is not valid TOML", template))?;
+        if let Some(other) = all
+            .iter()
+            .find(|s| s.name != source.name && s.templates.contains(template))
+        {
+            bail!(
+                "Template '{}' is already installed from '{}'",
+                template,
+                other.name
+            );
+        }
+        if dir.join(format!("{}.toml", template)).exists() && !source.templates.contains(template) {
+            bail!(
+                "A local template '{}' already exists in {}\n\
+                 Tip: Rename or remove it first",
+                template,
+                dir.display()
+            );
+        }
+    }
+
+    fs::create_dir_all(&dir)?;
+    let origin = match &source.commit {
+        Some(commit) => format!("{} at {}", source.source, short_commit(commit)),
+        None => source.source.clone(),
+    };
+    for (template, content) in &files {
+        let header = format!(
+            "# Installed from {}; `aoe template update` replaces local edits.\n",
+            origin
+        );
+        fs::write(dir.join(format!("{}.toml", template)), header + content)?;
+    }
+
+    let installed: Vec<String> = files.into_iter().map(|(template, _)| template).collect();
+    for stale in source.templates.iter().filter(|t| !installed.contains(t)) {
+        let _ = fs::remove_file(dir.join(format!("{}.toml", stale)));
+    }
+    source.templates = installed;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    fn setup_test_home(temp: &Path) {
+        std::env::set_var("HOME", temp);
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.join(".config"));
+    }
+
+    #[test]
+    fn test_resolve_sources() {
+        assert_eq!(
+            resolve("github:org/aoe-templates").unwrap(),
+            (
+                SourceKind::Git,
+                "https://github.com/org/aoe-templates.git".to_string()
+            )
+        );
+        assert_eq!(
+            resolve("gitlab:group/sub/templates.git").unwrap().1,
+            "https://gitlab.com/group/sub/templates.git"
+        );
+        assert!(resolve("github:org").is_err());
+        assert_eq!(
+            resolve("https://example.com/org/base.toml").unwrap().0,
+            SourceKind::File
+        );
+        assert_eq!(
+            resolve("git@github.com:org/templates.git").unwrap().0,
+            SourceKind::Git
+        );
+        assert!(resolve("./templates").is_err());
+
+        assert_eq!(
+            default_name(SourceKind::File, "https://example.com/org/base.toml"),
+            Some("base".to_string())
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_add_pin_update_and_remove() {
+        let temp = TempDir::new().unwrap();
+        setup_test_home(temp.path());
+
+        let origin = temp.path().join("aoe-templates");
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .arg("-C")
+                .arg(&origin)
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false)
+        };
+        fs::create_dir_all(origin.join("templates")).unwrap();
+        fs::write(
+            origin.join("templates/base.toml"),
+            "[session]\ndefault_tool = \"claude\"\n",
+        )
+        .unwrap();
+        if !(git(&["init"])
+            && git(&["add", "."])
+            && git(&["commit", "-m", "v1"])
+            && git(&["tag", "v1"]))
+        {
+            return;
+        }
+
+        let dir = templates_dir().unwrap();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("local.toml"), "").unwrap();
+
+        let url = format!("file://{}", origin.display());
+        let added = add(&url, None, None).await.unwrap();
+        assert_eq!(added.name, "aoe-templates");
+        assert_eq!(added.templates, vec!["base"]);
+        let base = fs::read_to_string(dir.join("base.toml")).unwrap();
+        assert!(base.contains("default_tool = \"claude\""));
+        assert!(add(&url, None, None).await.is_err());
+
+        // Upstream changes base and adds docker; a local template of the
+        // same name is never overwritten
+        fs::write(
+            origin.join("templates/base.toml"),
+            "[session]\ndefault_tool = \"codex\"\n",
+        )
+        .unwrap();
+        fs::write(origin.join("templates/docker.toml"), "[sandbox]\n").unwrap();
+        assert!(git(&["add", "."]) && git(&["commit", "-m", "v2"]));
+        fs::write(dir.join("docker.toml"), "").unwrap();
+        assert!(update(None, None).await.is_err());
+        fs::remove_file(dir.join("docker.toml")).unwrap();
+
+        let updated = update(None, None).await.unwrap();
+        let (before, after) = &updated[0];
+        assert_ne!(before.commit, after.commit);
+        assert_eq!(after.templates, vec!["base", "docker"]);
+        assert!(fs::read_to_string(dir.join("base.toml"))
+            .unwrap()
+            .contains("default_tool = \"codex\""));
+
+        // Pinning back to the tag drops the template v1 did not have
+        update(Some("aoe-templates"), Some("v1".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(load().unwrap()[0].rev.as_deref(), Some("v1"));
+        assert!(!dir.join("docker.toml").exists());
+        assert!(fs::read_to_string(dir.join("base.toml"))
+            .unwrap()
+            .contains("default_tool = \"claude\""));
+
+        let removed = remove("aoe-templates").unwrap();
+        assert_eq!(removed.templates, vec!["base"]);
+        assert!(!dir.join("base.toml").exists());
+        assert!(dir.join("local.toml").exists());
+        assert!(load().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+//! Test watcher results
+//!
+//! `hooks.test_watch` names a test watcher (e.g. `cargo watch -c -x test`)
+//! that runs in a companion tmux session while the session is alive. The TUI
+//! reads what its pane shows and folds the pass/fail counts into the
+//! session's row, so build health is visible without attaching.
+//!
+//! Counts come from the summary lines common runners print: cargo's
+//! `test result:` lines (summed, since each test binary prints one), and
+//! otherwise the last line with counts, as printed by pytest, jest, vitest
+//! or mocha. Watchers that clear the screen before each run (`cargo watch
+//! -c`) give the counts of the latest run only.
+
+use std::fmt;
+
+/// Passed and failed tests in the watcher's latest run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TestCounts {
+    pub passed: u64,
+    pub failed: u64,
+}
+
+impl fmt::Display for TestCounts {
+    /// e.g. "✓ 212 / ✗ 3", or "✓ 212" when nothing failed
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.failed == 0 {
+            write!(f, "✓ {}", self.passed)
+        } else {
+            write!(f, "✓ {} / ✗ {}", self.passed, self.failed)
+        }
+    }
+}
+
+/// Counts on one summary line: `N passed`/`N passing` and
+/// `N failed`/`N failing`, whichever appear.
+fn line_counts(line: &str) -> (Option<u64>, Option<u64>) {
+    let words: Vec<&str> = line
+        .split(|c: char| c.is_whitespace() || ",;|()=:".contains(c))
+        .filter(|w| !w.is_empty())
+        .collect();
+    let (mut passed, mut failed) = (None, None);
+    for pair in words.windows(2) {
+        let Ok(n) = pair[0].parse::<u64>() else {
+            continue;
+        };
+        let word = pair[1].to_ascii_lowercase();
+        if word.starts_with("pass") {
+            passed = Some(n);
+        } else if word.starts_with("fail") {
+            failed = Some(n);
+        }
+    }
+    (passed, failed)
+}
+
+/// The counts the watcher's screen shows, if it shows any.
+pub fn parse(screen: &str) -> Option<TestCounts> {
+    let lines: Vec<&str> = screen.lines().collect();
+
+    let cargo: Vec<_> = lines
+        .iter()
+        .filter(|l| l.trim_start().starts_with("test result:"))
+        .map(|l| line_counts(l))
+        .collect();
+    if !cargo.is_empty() {
+        return Some(
+            cargo
+                .iter()
+                .fold(TestCounts::default(), |sum, (p, f)| TestCounts {
+                    passed: sum.passed + p.unwrap_or(0),
+                    failed: sum.failed + f.unwrap_or(0),
+                }),
+        );
+    }
+
+    // mocha prints passing and failing on lines of their own
+    let last = lines.iter().rposition(|l| line_counts(l) != (None, None))?;
+    let (mut passed, mut failed) = line_counts(lines[last]);
+    for line in lines[last.saturating_sub(2)..last].iter().rev() {
+        let (p, f) = line_counts(line);
+        passed = passed.or(p);
+        failed = failed.or(f);
+    }
+    Some(TestCounts {
+        passed: passed.unwrap_or(0),
+        failed: failed.unwrap_or(0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_runner_summaries() {
+        let cargo = "running 210 tests\n\
+test result: FAILED. 207 passed; 3 failed; 0 ignored; 0 measured\n\
+\n\
+test result: ok. 5 passed; 0 failed; 1 ignored; 0 measured\n\
+[Finished running. Exit status: 101]\n";
+        let counts = parse(cargo).unwrap();
+        assert_eq!(
+            counts,
+            TestCounts {
+                passed: 212,
+                failed: 3
+            }
+        );
+        assert_eq!(counts.to_string(), "✓ 212 / ✗ 3");
+
+        let pytest = "tests/test_api.py ..F.\n===== 1 failed, 40 passed in 1.20s =====\n";
+        assert_eq!(parse(pytest).unwrap().to_string(), "✓ 40 / ✗ 1");
+        let jest = "Tests:       2 failed, 98 passed, 100 total\nTime: 3.1 s\n";
+        assert_eq!(parse(jest).unwrap().to_string(), "✓ 98 / ✗ 2");
+        let vitest = " Test Files  4 passed (4)\n      Tests  31 passed (31)\n";
+        assert_eq!(parse(vitest).unwrap().to_string(), "✓ 31");
+        let mocha = "  12 passing (30ms)\n  1 failing\n\n  1) adds numbers\n";
+        assert_eq!(parse(mocha).unwrap().to_string(), "✓ 12 / ✗ 1");
+
+        assert_eq!(
+            parse("[Running 'cargo test']\n   Compiling app v0.1.0\n"),
+            None
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! Per-session activity timeline
+//!
+//! Notable events (status changes, prompts sent, hooks run, git operations,
+//! guardrail overrides, named command results) are appended to `timelines/<session-id>.jsonl` in the
+//! app directory so the TUI can show what happened while nobody was watching.
+//! Recording is best-effort: a failed write is logged and never interrupts
+//! the operation being recorded.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{get_app_dir, Instance, Status};
+
+/// Once a log grows past this size it is rewritten to keep only the newest
+/// half of its events.
+const MAX_LOG_BYTES: u64 = 256 * 1024;
+
+/// Longest message stored for a single event. Prompts in particular can be
+/// arbitrarily long; the timeline only needs enough to recognise them.
+const MAX_MESSAGE_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventKind {
+    Created,
+    Status,
+    Prompt,
+    Hook,
+    Git,
+    Guardrail,
+    Command,
+}
+
+impl EventKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            EventKind::Created => "created",
+            EventKind::Status => "status",
+            EventKind::Prompt => "prompt",
+            EventKind::Hook => "hook",
+            EventKind::Git => "git",
+            EventKind::Guardrail => "guardrail",
+            EventKind::Command => "command",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEvent {
+    pub at: DateTime<Utc>,
+    pub kind: EventKind,
+    pub message: String,
+}
+
+fn timelines_dir() -> Result<PathBuf> {
+    Ok(get_app_dir()?.join("timelines"))
+}
+
+pub(super) fn timeline_path(session_id: &str) -> Result<PathBuf> {
+    Ok(timelines_dir()?.join(format!("{}.jsonl", session_id)))
+}
+
+/// Collapse to a single line and cap the length.
+pub(super) fn summarize(message: &str) -> String {
+    let flat = message.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() > MAX_MESSAGE_CHARS {
+        let truncated: String = flat.chars().take(MAX_MESSAGE_CHARS).collect();
+        format!("{}...", truncated.trim_end())
+    } else {
+        flat
+    }
+}
+
+/// Append an event to a session's timeline.
+pub fn record(session_id: &str, kind: EventKind, message: impl AsRef<str>) {
+    if let Err(e) = try_record(session_id, kind, message.as_ref()) {
+        tracing::warn!("Failed to record timeline event for {}: {}", session_id, e);
+    }
+}
+
+fn try_record(session_id: &str, kind: EventKind, message: &str) -> Result<()> {
+    let dir = timelines_dir()?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.jsonl", session_id));
+
+    let event = TimelineEvent {
+        at: Utc::now(),
+        kind,
+        message: summarize(message),
+    };
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(&event)?)?;
+
+    if file.metadata()?.len() > MAX_LOG_BYTES {
+        let events = load(session_id)?;
+        let keep = &events[events.len() / 2..];
+        let mut content = String::new();
+        for event in keep {
+            content.push_str(&serde_json::to_string(event)?);
+            content.push('\n');
+        }
+        fs::write(&path, content)?;
+    }
+    Ok(())
+}
+
+/// Record a new session and the git setup done for it, also in the audit
+/// log.
+pub fn record_created(instance: &Instance) {
+    super::audit::record_session(
+        super::audit::Action::Created,
+        instance,
+        format!("{} in {}", instance.tool, instance.project_path),
+    );
+    record(
+        &instance.id,
+        EventKind::Created,
+        format!(
+            "Created with {} in {}",
+            instance.tool, instance.project_path
+        ),
+    );
+    if let Some(ws) = &instance.workspace_info {
+        record(
+            &instance.id,
+            EventKind::Git,
+            format!(
+                "Created workspace with {} repos on branch {}",
+                ws.repos.len(),
+                ws.branch
+            ),
+        );
+    } else if let Some(wt) = &instance.worktree_info {
+        let message = if wt.managed_by_aoe {
+            format!("Created worktree on branch {}", wt.branch)
+        } else {
+            format!("Using existing worktree on branch {}", wt.branch)
+        };
+        record(&instance.id, EventKind::Git, message);
+    }
+}
+
+pub fn record_status(session_id: &str, old: Status, new: Status) {
+    record(
+        session_id,
+        EventKind::Status,
+        format!("{:?} -> {:?}", old, new),
+    );
+}
+
+/// Record the outcome of a hook phase (`on_create`, `on_launch`), also in
+/// the audit log.
+pub fn record_hooks(session_id: &str, phase: &str, commands: &[String], result: &Result<()>) {
+    let message = match result {
+        Ok(()) => format!("{}: {}", phase, commands.join("; ")),
+        Err(e) => format!("{} failed: {:#}", phase, e),
+    };
+    super::audit::record(super::audit::Action::Hook, Some(session_id), None, &message);
+    record(session_id, EventKind::Hook, message);
+}
+
+/// Record a prompt sent to a session, also in the audit log.
+pub fn record_prompt(instance: &Instance, prompt: &str) {
+    super::audit::record_session(super::audit::Action::Prompt, instance, prompt);
+    record(&instance.id, EventKind::Prompt, prompt);
+}
+
+/// Load a session's timeline, oldest first. Unparseable lines (e.g. a
+/// partially written final line) are skipped.
+pub fn load(session_id: &str) -> Result<Vec<TimelineEvent>> {
+    let path = timeline_path(session_id)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Delete a session's timeline. Called when the session itself is removed.
+pub fn remove(session_id: &str) {
+    if let Ok(path) = timeline_path(session_id) {
+        if path.exists() {
+            if let Err(e) = fs::remove_file(&path) {
+                tracing::warn!("Failed to remove timeline for {}: {}", session_id, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    fn setup_test_home(temp: &Path) {
+        std::env::set_var("HOME", temp);
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.join(".config"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_record_and_load() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        assert!(load("abc")?.is_empty());
+        record("abc", EventKind::Created, "Session created");
+        record("abc", EventKind::Status, "idle -> running");
+        record("other", EventKind::Git, "Created worktree");
+
+        let events = load("abc")?;
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, EventKind::Created);
+        assert_eq!(events[1].message, "idle -> running");
+
+        remove("abc");
+        assert!(load("abc")?.is_empty());
+        assert_eq!(load("other")?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_log_is_trimmed() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        // Each event is well over 128 bytes, so this overflows the cap
+        let message = "x".repeat(MAX_MESSAGE_CHARS);
+        let written = (MAX_LOG_BYTES / 128) as usize;
+        for _ in 0..written {
+            record("big", EventKind::Prompt, &message);
+        }
+        let size = timeline_path("big")?.metadata()?.len();
+        assert!(size <= MAX_LOG_BYTES);
+        assert!(load("big")?.len() < written);
+        Ok(())
+    }
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(summarize("fix the\n  login bug"), "fix the login bug");
+        let long = "a".repeat(MAX_MESSAGE_CHARS + 10);
+        assert!(summarize(&long).ends_with("..."));
+        assert_eq!(summarize(&long).chars().count(), MAX_MESSAGE_CHARS + 3);
+    }
+}
@@ -0,0 +1,106 @@
+//! Transcript files for `aoe session capture --output`
+//!
+//! A transcript is the session's pane output as plain text, preceded by TOML
+//! front-matter between `+++` lines (the form Hugo and Zola read) describing
+//! the session. Tools that index a directory of transcripts, from ripgrep to
+//! static site generators, can then filter and group them by session, path,
+//! agent, or tag without a separate index file.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::Instance;
+
+const DELIMITER: &str = "+++";
+
+#[derive(Debug, Serialize)]
+struct FrontMatter<'a> {
+    id: &'a str,
+    title: &'a str,
+    path: &'a str,
+    agent: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    group: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    branch: Option<&'a str>,
+    tags: &'a [String],
+    created: DateTime<Utc>,
+    captured: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompt: Option<&'a str>,
+}
+
+/// `instance`'s metadata as front-matter, ending with the closing delimiter
+/// and a blank line.
+pub fn front_matter(instance: &Instance, captured: DateTime<Utc>) -> Result<String> {
+    let matter = FrontMatter {
+        id: &instance.id,
+        title: &instance.title,
+        path: &instance.project_path,
+        agent: &instance.tool,
+        group: &instance.group_path,
+        branch: instance
+            .worktree_info
+            .as_ref()
+            .map(|wt| wt.branch.as_str())
+            .or_else(|| instance.workspace_info.as_ref().map(|w| w.branch.as_str())),
+        tags: &instance.labels,
+        created: instance.created_at,
+        captured,
+        prompt: instance
+            .original_prompt
+            .as_deref()
+            .filter(|p| !p.trim().is_empty()),
+    };
+    let toml = toml::to_string_pretty(&matter).context("Failed to serialize front-matter")?;
+    Ok(format!("{}\n{}{}\n\n", DELIMITER, toml, DELIMITER))
+}
+
+/// Write `content` (plain text, without ANSI codes) to `path` as a
+/// transcript of `instance`.
+pub fn write(instance: &Instance, content: &str, path: &Path) -> Result<()> {
+    let mut transcript = front_matter(instance, Utc::now())?;
+    transcript.push_str(content.trim_end());
+    transcript.push('\n');
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    std::fs::write(path, transcript)
+        .with_context(|| format!("Failed to write transcript {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcript_starts_with_parseable_front_matter() {
+        let mut inst = Instance::new("fix login", "/src/app");
+        inst.tool = "claude".to_string();
+        inst.labels = vec!["feat".to_string(), "abc-123".to_string()];
+        inst.original_prompt = Some("Fix the \"login\" bug\nand add a test".to_string());
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("out/fix-login.md");
+        write(&inst, "> done\n\n", &path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+
+        let rest = text.strip_prefix("+++\n").unwrap();
+        let (matter, body) = rest.split_once("\n+++\n\n").unwrap();
+        assert_eq!(body, "> done\n");
+        let parsed: toml::Table = toml::from_str(matter).unwrap();
+        assert_eq!(parsed["id"].as_str(), Some(inst.id.as_str()));
+        assert_eq!(parsed["path"].as_str(), Some("/src/app"));
+        assert_eq!(parsed["agent"].as_str(), Some("claude"));
+        assert_eq!(parsed["tags"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            parsed["prompt"].as_str(),
+            Some("Fix the \"login\" bug\nand add a test")
+        );
+        assert!(!parsed.contains_key("branch"));
+    }
+}
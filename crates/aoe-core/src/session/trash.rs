@@ -0,0 +1,450 @@
+//! Soft-deleted sessions
+//!
+//! Removing a session moves its metadata and activity timeline into
+//! `profiles/<profile>/trash/` instead of discarding them, so a mistaken
+//! delete can be undone with `aoe trash restore`. The trash is pruned by age
+//! and count (`session.trash_retention_days`, `session.trash_max_entries`)
+//! every time something new is added to it. Removal may already have
+//! deleted the session's worktree and container; restoring recreates the
+//! worktree from its branch when it can and says what it had to change.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    audit, get_profile_dir, journal, match_sessions, timeline, GroupTree, Instance, Status, Storage,
+};
+use crate::containers::DockerContainer;
+use crate::git::GitWorktree;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub deleted_at: DateTime<Utc>,
+    pub instance: Instance,
+}
+
+/// A session put back from the trash
+pub struct Restored {
+    pub instance: Instance,
+    /// What changed because removal had cleaned up its worktree or container
+    pub notes: Vec<String>,
+}
+
+fn trash_dir(profile: &str) -> Result<PathBuf> {
+    Ok(get_profile_dir(profile)?.join("trash"))
+}
+
+fn entry_path(dir: &std::path::Path, session_id: &str) -> PathBuf {
+    dir.join(format!("{}.json", session_id))
+}
+
+fn timeline_copy_path(dir: &std::path::Path, session_id: &str) -> PathBuf {
+    dir.join(format!("{}.timeline.jsonl", session_id))
+}
+
+/// Move a removed session into the trash. When the trash is disabled
+/// (`trash_retention_days = 0`) the session's timeline is deleted instead.
+/// Returns true if the session was kept.
+pub fn move_to_trash(profile: &str, instance: &Instance) -> Result<bool> {
+    let config = super::resolve_config(profile)
+        .map(|c| c.session)
+        .unwrap_or_default();
+    journal::record_removed(profile, instance);
+    if config.trash_retention_days == 0 {
+        audit::record_session(audit::Action::Removed, instance, "deleted");
+        timeline::remove(&instance.id);
+        return Ok(false);
+    }
+    audit::record_session(audit::Action::Removed, instance, "moved to trash");
+
+    let dir = trash_dir(profile)?;
+    fs::create_dir_all(&dir)?;
+
+    let mut instance = instance.clone();
+    instance.status = Status::Stopped;
+    instance.last_error = None;
+    let entry = TrashEntry {
+        deleted_at: Utc::now(),
+        instance,
+    };
+    fs::write(
+        entry_path(&dir, &entry.instance.id),
+        serde_json::to_string_pretty(&entry)?,
+    )?;
+
+    let timeline = timeline::timeline_path(&entry.instance.id)?;
+    if timeline.exists() {
+        fs::rename(&timeline, timeline_copy_path(&dir, &entry.instance.id))
+            .context("Failed to move timeline to trash")?;
+    }
+
+    prune(
+        profile,
+        config.trash_retention_days,
+        config.trash_max_entries as usize,
+    )?;
+    Ok(true)
+}
+
+/// All trashed sessions, newest first. Unreadable entries are skipped.
+pub fn list(profile: &str) -> Result<Vec<TrashEntry>> {
+    let dir = trash_dir(profile)?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for file in fs::read_dir(&dir)? {
+        let path = file?.path();
+        let is_entry = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.ends_with(".json"));
+        if !is_entry {
+            continue;
+        }
+        match fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|c| Ok(serde_json::from_str::<TrashEntry>(&c)?))
+        {
+            Ok(entry) => entries.push(entry),
+            Err(e) => tracing::warn!("Skipping trash entry {}: {}", path.display(), e),
+        }
+    }
+    entries.sort_by_key(|e| std::cmp::Reverse(e.deleted_at));
+    Ok(entries)
+}
+
+/// Find a trashed session by ID, unique ID prefix, or title. An identifier
+/// matching more than one entry is an error rather than a guess, since a
+/// purge cannot be undone.
+pub fn find(profile: &str, identifier: &str) -> Result<TrashEntry> {
+    if identifier.trim().is_empty() {
+        bail!("No session given");
+    }
+    let mut entries = list(profile)?;
+    let instances: Vec<Instance> = entries.iter().map(|e| e.instance.clone()).collect();
+    let matches = match_sessions(identifier, &instances);
+    match matches.as_slice() {
+        [] => bail!("Session not found in trash: {}", identifier),
+        [index] => Ok(entries.swap_remove(*index)),
+        _ => {
+            let candidates: Vec<String> = matches
+                .iter()
+                .map(|&i| format!("{} ({})", instances[i].short_id(), instances[i].title))
+                .collect();
+            bail!(
+                "'{}' matches more than one session in the trash: {}\n\
+                 Tip: Use the session's ID, shown by `aoe trash list`",
+                identifier.trim(),
+                candidates.join(", ")
+            )
+        }
+    }
+}
+
+/// Put a trashed session back into the profile's session list (stopped) and
+/// restore its timeline.
+pub fn restore(profile: &str, identifier: &str) -> Result<Restored> {
+    let entry = find(profile, identifier)?;
+    let mut instance = entry.instance;
+
+    let storage = Storage::new(profile)?;
+    let (mut instances, groups) = storage.load_with_groups()?;
+    if instances.iter().any(|i| i.id == instance.id) {
+        bail!("A session with ID {} already exists", instance.id);
+    }
+    let notes = repair_cleaned_up(&mut instance);
+    instances.push(instance.clone());
+    let group_tree = GroupTree::new_with_groups(&instances, &groups);
+    storage.save_with_groups(&instances, &group_tree)?;
+
+    let dir = trash_dir(profile)?;
+    let saved_timeline = timeline_copy_path(&dir, &instance.id);
+    if saved_timeline.exists() {
+        let target = timeline::timeline_path(&instance.id)?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(&saved_timeline, &target)?;
+    }
+    fs::remove_file(entry_path(&dir, &instance.id))?;
+
+    audit::record_session(audit::Action::Restored, &instance, "restored from trash");
+    Ok(Restored { instance, notes })
+}
+
+/// Point a restored session at what removal left behind. A deleted worktree
+/// is recreated from its branch, or the session moves to the main repo if
+/// the branch is gone too; a deleted container is forgotten, so starting
+/// the session creates a new one.
+fn repair_cleaned_up(instance: &mut Instance) -> Vec<String> {
+    let mut notes = Vec::new();
+
+    if let Some(wt) = instance.worktree_info.clone() {
+        let path = PathBuf::from(&instance.project_path);
+        if !path.exists() {
+            let recreated = GitWorktree::new(PathBuf::from(&wt.main_repo_path))
+                .and_then(|git| git.create_worktree(&wt.branch, &path, false));
+            match recreated {
+                Ok(()) => notes.push(format!("Recreated its worktree on branch '{}'", wt.branch)),
+                Err(e) => {
+                    tracing::warn!("Cannot recreate worktree for '{}': {}", instance.title, e);
+                    instance.worktree_info = None;
+                    instance.project_path = wt.main_repo_path.clone();
+                    notes.push(format!(
+                        "Its worktree and branch '{}' were deleted; it now runs in {}",
+                        wt.branch, wt.main_repo_path
+                    ));
+                }
+            }
+        }
+    }
+
+    let id = instance.id.clone();
+    if let Some(sandbox) = instance.sandbox_info.as_mut() {
+        // An unreachable container runtime proves nothing, so keep the record
+        let gone = sandbox.container_id.is_some()
+            && !DockerContainer::from_session_id(&id)
+                .exists()
+                .unwrap_or(true);
+        if gone {
+            sandbox.container_id = None;
+            sandbox.created_at = None;
+            notes.push("Its container was deleted; a new one is created when it starts".into());
+        }
+    }
+
+    notes
+}
+
+/// Permanently delete one trashed session.
+pub fn purge(profile: &str, identifier: &str) -> Result<Instance> {
+    let entry = find(profile, identifier)?;
+    remove_entry(&trash_dir(profile)?, &entry.instance.id)?;
+    audit::record_session(audit::Action::Purged, &entry.instance, "purged from trash");
+    Ok(entry.instance)
+}
+
+/// Permanently delete everything in the trash. Returns the number purged.
+pub fn purge_all(profile: &str) -> Result<usize> {
+    let dir = trash_dir(profile)?;
+    let entries = list(profile)?;
+    for entry in &entries {
+        remove_entry(&dir, &entry.instance.id)?;
+        audit::record_session(audit::Action::Purged, &entry.instance, "purged from trash");
+    }
+    Ok(entries.len())
+}
+
+/// Drop entries older than `retention_days`, then the oldest entries beyond
+/// `max_entries` (0 means no count limit). Returns the number purged.
+pub fn prune(profile: &str, retention_days: u64, max_entries: usize) -> Result<usize> {
+    let dir = trash_dir(profile)?;
+    let cutoff = Utc::now() - Duration::days(retention_days.min(36500) as i64);
+    let mut purged = 0;
+    for (i, entry) in list(profile)?.iter().enumerate() {
+        let over_count = max_entries > 0 && i >= max_entries;
+        if over_count || entry.deleted_at < cutoff {
+            remove_entry(&dir, &entry.instance.id)?;
+            audit::record_session(audit::Action::Purged, &entry.instance, "expired from trash");
+            purged += 1;
+        }
+    }
+    Ok(purged)
+}
+
+fn remove_entry(dir: &std::path::Path, session_id: &str) -> Result<()> {
+    let timeline = timeline_copy_path(dir, session_id);
+    if timeline.exists() {
+        fs::remove_file(timeline)?;
+    }
+    let path = entry_path(dir, session_id);
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    fn setup_test_home(temp: &std::path::Path) {
+        std::env::set_var("HOME", temp);
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.join(".config"));
+    }
+
+    fn trashed(title: &str) -> Instance {
+        let inst = Instance::new(title, "/tmp/project");
+        timeline::record(&inst.id, timeline::EventKind::Prompt, "hello");
+        move_to_trash("default", &inst).unwrap();
+        inst
+    }
+
+    #[test]
+    #[serial]
+    fn test_move_to_trash_and_restore() {
+        let temp = TempDir::new().unwrap();
+        setup_test_home(temp.path());
+
+        let inst = trashed("fix-bug");
+        assert!(timeline::load(&inst.id).unwrap().is_empty());
+        let entries = list("default").unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].instance.title, "fix-bug");
+
+        let restored = restore("default", "fix-bug").unwrap().instance;
+        assert_eq!(restored.id, inst.id);
+        assert_eq!(restored.status, Status::Stopped);
+        assert!(list("default").unwrap().is_empty());
+        assert_eq!(timeline::load(&inst.id).unwrap().len(), 1);
+
+        let sessions = Storage::new("default").unwrap().load().unwrap();
+        assert!(sessions.iter().any(|i| i.id == inst.id));
+        assert!(restore("default", "fix-bug").is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_find_refuses_ambiguous_identifiers() {
+        let temp = TempDir::new().unwrap();
+        setup_test_home(temp.path());
+
+        for (id, title) in [("abc10000", "one"), ("abc20000", "two")] {
+            let mut inst = Instance::new(title, "/tmp/project");
+            inst.id = id.to_string();
+            move_to_trash("default", &inst).unwrap();
+        }
+
+        let err = purge("default", "abc").unwrap_err().to_string();
+        assert!(err.contains("abc10000 (one)"), "{}", err);
+        assert!(err.contains("abc20000 (two)"), "{}", err);
+        assert!(purge("default", "").is_err());
+        assert_eq!(list("default").unwrap().len(), 2);
+
+        assert_eq!(purge("default", "abc2").unwrap().title, "two");
+        assert_eq!(find("default", "one").unwrap().instance.id, "abc10000");
+    }
+
+    #[test]
+    #[serial]
+    fn test_restore_recreates_deleted_worktree() {
+        let temp = TempDir::new().unwrap();
+        setup_test_home(temp.path());
+
+        let repo = temp.path().join("repo");
+        fs::create_dir_all(&repo).unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .arg("-C")
+                .arg(&repo)
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false)
+        };
+        if !(git(&["init"])
+            && git(&["commit", "--allow-empty", "-m", "init"])
+            && git(&["branch", "feature"]))
+        {
+            return;
+        }
+
+        let trashed_worktree = |title: &str, branch: &str| {
+            let mut inst = Instance::new(title, &temp.path().join(title).to_string_lossy());
+            inst.worktree_info = Some(crate::session::WorktreeInfo {
+                branch: branch.to_string(),
+                main_repo_path: repo.to_string_lossy().to_string(),
+                managed_by_aoe: true,
+                created_at: Utc::now(),
+                base_commit: None,
+            });
+            move_to_trash("default", &inst).unwrap();
+            inst
+        };
+
+        let kept = trashed_worktree("kept", "feature");
+        let restored = restore("default", "kept").unwrap();
+        assert_eq!(restored.notes.len(), 1);
+        assert!(std::path::Path::new(&kept.project_path).exists());
+        assert!(restored.instance.worktree_info.is_some());
+
+        trashed_worktree("lost", "deleted-branch");
+        let restored = restore("default", "lost").unwrap();
+        assert!(restored.notes[0].contains("deleted-branch"));
+        assert!(restored.instance.worktree_info.is_none());
+        assert_eq!(restored.instance.project_path, repo.to_string_lossy());
+    }
+
+    #[test]
+    #[serial]
+    fn test_prune_by_count_keeps_newest() {
+        let temp = TempDir::new().unwrap();
+        setup_test_home(temp.path());
+
+        trashed("a");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        trashed("b");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        trashed("c");
+
+        assert_eq!(prune("default", 7, 2).unwrap(), 1);
+        let titles: Vec<_> = list("default")
+            .unwrap()
+            .into_iter()
+            .map(|e| e.instance.title)
+            .collect();
+        assert_eq!(titles, vec!["c", "b"]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_prune_by_age_and_purge() {
+        let temp = TempDir::new().unwrap();
+        setup_test_home(temp.path());
+
+        let old = trashed("old");
+        trashed("new");
+
+        // Backdate one entry past the retention window
+        let dir = trash_dir("default").unwrap();
+        let mut entry = find("default", "old").unwrap();
+        entry.deleted_at = Utc::now() - Duration::days(10);
+        fs::write(
+            entry_path(&dir, &old.id),
+            serde_json::to_string(&entry).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(prune("default", 7, 0).unwrap(), 1);
+        assert!(!timeline_copy_path(&dir, &old.id).exists());
+        assert_eq!(purge("default", "new").unwrap().title, "new");
+        assert_eq!(purge_all("default").unwrap(), 0);
+    }
+
+    #[test]
+    #[serial]
+    fn test_trash_disabled_deletes_timeline() {
+        let temp = TempDir::new().unwrap();
+        setup_test_home(temp.path());
+
+        let mut config = crate::session::Config::default();
+        config.session.trash_retention_days = 0;
+        crate::session::save_config(&config).unwrap();
+
+        let inst = Instance::new("gone", "/tmp/project");
+        timeline::record(&inst.id, timeline::EventKind::Prompt, "hello");
+        assert!(!move_to_trash("default", &inst).unwrap());
+        assert!(timeline::load(&inst.id).unwrap().is_empty());
+        assert!(list("default").unwrap().is_empty());
+    }
+}
@@ -57,6 +57,11 @@ pub struct SoundConfig {
     /// Sound to play when a session enters error state
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_error: Option<String>,
+
+    /// Sound to play when an agent finishes its work (running to idle);
+    /// `on_idle` is used if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_finished: Option<String>,
 }
 
 /// Profile override for sound config (all fields optional, None = inherit)
@@ -82,6 +87,9 @@ pub struct SoundConfigOverride {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_error: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_finished: Option<String>,
 }
 
 /// List of bundled sound files available for download
@@ -276,7 +284,7 @@ fn get_audio_command(path: &str) -> Result<(&'static str, Vec<&str>), std::io::E
 }
 
 /// Check if a command exists in PATH
-fn which_command(cmd: &str) -> Result<(), std::io::Error> {
+pub(crate) fn which_command(cmd: &str) -> Result<(), std::io::Error> {
     std::process::Command::new("which")
         .arg(cmd)
         .stdout(std::process::Stdio::null())
@@ -384,6 +392,9 @@ pub fn play_for_transition(old: Status, new: Status, config: &SoundConfig) {
         Status::Starting => config.on_start.as_deref(),
         Status::Running => config.on_running.as_deref(),
         Status::Waiting => config.on_waiting.as_deref(),
+        Status::Idle if old == Status::Running && config.on_finished.is_some() => {
+            config.on_finished.as_deref()
+        }
         Status::Idle => config.on_idle.as_deref(),
         Status::Error => config.on_error.as_deref(),
         Status::Unknown => return,
@@ -396,6 +407,17 @@ pub fn play_for_transition(old: Status, new: Status, config: &SoundConfig) {
     }
 }
 
+/// Play the error sound when a session's health check starts failing or its
+/// agent needs to log in again
+pub fn play_for_failure(config: &SoundConfig) {
+    if !config.enabled {
+        return;
+    }
+    if let Some(name) = resolve_sound_name(config.on_error.as_deref(), config) {
+        play_sound(&name);
+    }
+}
+
 /// Apply sound config overrides from a profile
 pub fn apply_sound_overrides(target: &mut SoundConfig, source: &SoundConfigOverride) {
     if let Some(enabled) = source.enabled {
@@ -419,6 +441,9 @@ pub fn apply_sound_overrides(target: &mut SoundConfig, source: &SoundConfigOverr
     if source.on_error.is_some() {
         target.on_error = source.on_error.clone();
     }
+    if source.on_finished.is_some() {
+        target.on_finished = source.on_finished.clone();
+    }
 }
 
 #[cfg(test)]
@@ -1,15 +1,23 @@
 //! tmux integration module
 
+pub mod nested;
+pub mod options;
+pub mod output_parser;
+pub mod permission_prompt;
 mod session;
 pub mod status_bar;
 pub(crate) mod status_detection;
 mod terminal_session;
-pub(crate) mod utils;
+pub mod utils;
+pub mod version;
+pub mod window_size;
 
 pub use session::Session;
 pub use status_bar::{get_session_info_for_current, get_status_for_current_session};
 pub use status_detection::detect_status_from_content;
-pub use terminal_session::{ContainerTerminalSession, TerminalSession};
+pub use terminal_session::{
+    CommandSession, ContainerTerminalSession, LoginSession, TerminalSession,
+};
 
 use std::collections::HashMap;
 use std::process::Command;
@@ -19,6 +27,9 @@ use std::time::{Duration, Instant};
 pub const SESSION_PREFIX: &str = "aoe_";
 pub const TERMINAL_PREFIX: &str = "aoe_term_";
 pub const CONTAINER_TERMINAL_PREFIX: &str = "aoe_cterm_";
+pub const LOGIN_PREFIX: &str = "aoe_login_";
+pub const COMMAND_PREFIX: &str = "aoe_cmd_";
+pub const WATCH_PREFIX: &str = "aoe_watch_";
 
 static SESSION_CACHE: RwLock<SessionCache> = RwLock::new(SessionCache {
     data: None,
@@ -75,6 +86,48 @@ pub fn session_exists_from_cache(name: &str) -> Option<bool> {
     cache.data.as_ref().map(|m| m.contains_key(name))
 }
 
+/// Unix time of the session's last activity, from the session cache.
+pub fn session_activity_from_cache(name: &str) -> Option<i64> {
+    let cache = SESSION_CACHE.read().ok()?;
+    cache.data.as_ref()?.get(name).copied()
+}
+
+/// Names of every long-lived tmux session aoe may keep for session `id`
+/// titled `title`: the agent, host and container terminals, named commands
+/// and the test watcher.
+pub fn session_names(id: &str, title: &str) -> Vec<String> {
+    vec![
+        Session::generate_name(id, title),
+        TerminalSession::generate_name(id, title),
+        ContainerTerminalSession::generate_name(id, title),
+        CommandSession::generate_name(id, title),
+        CommandSession::watch_name(id, title),
+    ]
+}
+
+/// Rename tmux session `from` to `to`. Returns false if `from` doesn't exist.
+pub fn rename_session(from: &str, to: &str) -> anyhow::Result<bool> {
+    let exists = Command::new("tmux")
+        .args(["has-session", "-t", from])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+    if !exists {
+        return Ok(false);
+    }
+    let output = Command::new("tmux")
+        .args(["rename-session", "-t", from, to])
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to rename tmux session {}: {}",
+            from,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(true)
+}
+
 pub fn get_current_session_name() -> Option<String> {
     let output = Command::new("tmux")
         .args(["display-message", "-p", "#{session_name}"])
@@ -94,7 +147,8 @@ pub fn is_tmux_available() -> bool {
     Command::new("tmux").arg("-V").output().is_ok()
 }
 
-fn is_agent_available(agent: &crate::agents::AgentDef) -> bool {
+/// Whether `agent`'s binary can be found on the host.
+pub fn is_agent_available(agent: &crate::agents::AgentDef) -> bool {
     use crate::agents::DetectionMethod;
     match &agent.detection {
         DetectionMethod::Which(binary) => {
@@ -140,15 +194,35 @@ pub struct AvailableTools {
 }
 
 impl AvailableTools {
+    /// Check every agent at once: a missing one falls back to a login
+    /// shell, which can take a while with version managers installed.
     pub fn detect() -> Self {
+        let found: Vec<bool> = std::thread::scope(|s| {
+            let checks: Vec<_> = crate::agents::AGENTS
+                .iter()
+                .map(|a| s.spawn(move || is_agent_available(a)))
+                .collect();
+            checks
+                .into_iter()
+                .map(|check| check.join().unwrap_or(false))
+                .collect()
+        });
         let available = crate::agents::AGENTS
             .iter()
-            .filter(|a| is_agent_available(a))
-            .map(|a| a.name)
+            .zip(found)
+            .filter(|(_, found)| *found)
+            .map(|(a, _)| a.name)
             .collect();
         Self { available }
     }
 
+    /// Every supported agent, for when none has to actually run (`aoe --demo`)
+    pub fn all() -> Self {
+        Self {
+            available: crate::agents::AGENTS.iter().map(|a| a.name).collect(),
+        }
+    }
+
     pub fn any_available(&self) -> bool {
         !self.available.is_empty()
     }
@@ -157,7 +231,7 @@ impl AvailableTools {
         self.available.clone()
     }
 
-    #[cfg(test)]
+    /// Exactly `tools`, whatever is installed, for tests
     pub fn with_tools(tools: &[&'static str]) -> Self {
         Self {
             available: tools.to_vec(),
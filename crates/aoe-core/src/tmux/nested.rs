@@ -0,0 +1,91 @@
+//! Running aoe inside tmux
+//!
+//! Started from a tmux pane, aoe shares the tmux server with the user's own
+//! sessions. Attaching then switches the user's client to the agent's
+//! session instead of nesting a second client in the pane, and the user's
+//! prefix is the one tmux acts on. The hint in an aoe session's status bar
+//! names that prefix and, for a client that was switched there, says how to
+//! go back to aoe: detaching would take the whole client, aoe included, off
+//! the screen.
+
+use std::process::Command;
+
+/// Whether aoe runs in a pane of an attached tmux client. `TMUX` alone is
+/// not enough: it is inherited by terminals opened from tmux (e.g. with
+/// `open -a Terminal`), which are not part of any client.
+pub fn is_nested() -> bool {
+    std::env::var_os("TMUX").is_some() && current_client().is_some()
+}
+
+fn current_client() -> Option<String> {
+    let output = Command::new("tmux")
+        .args(["display-message", "-p", "#{client_name}"])
+        .output()
+        .ok()?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !name.is_empty()).then_some(name)
+}
+
+/// The command showing session `name` in this terminal: `switch-client`
+/// when nested, else `attach-session`.
+pub fn attach_command(name: &str) -> Command {
+    let verb = if is_nested() {
+        "switch-client"
+    } else {
+        "attach-session"
+    };
+    let mut command = Command::new("tmux");
+    command.args([verb, "-t", name]);
+    command
+}
+
+/// The server's prefix key for hints, e.g. `Ctrl+b`
+pub fn prefix_label() -> String {
+    let prefix = Command::new("tmux")
+        .args(["show-options", "-gv", "prefix"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|p| !p.is_empty() && p != "None");
+    key_label(prefix.as_deref().unwrap_or("C-b"))
+}
+
+/// `C-a` as `Ctrl+a`, `M-a` as `Alt+a`; other keys as tmux names them
+fn key_label(key: &str) -> String {
+    if let Some(rest) = key.strip_prefix("C-") {
+        format!("Ctrl+{}", key_label(rest))
+    } else if let Some(rest) = key.strip_prefix("M-") {
+        format!("Alt+{}", key_label(rest))
+    } else if let Some(rest) = key.strip_prefix("S-") {
+        format!("Shift+{}", key_label(rest))
+    } else {
+        key.to_string()
+    }
+}
+
+/// Status bar format for the key hint: going back with `prefix L` for a
+/// client that came from another session (a nested aoe switched it there),
+/// detaching with `prefix d` otherwise.
+pub fn key_hint_format(prefix: &str) -> String {
+    format!(
+        "#{{?client_last_session,{p} L#[fg=colour240] to return to aoe,{p} d#[fg=colour240] to detach}}",
+        p = prefix
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_hint_names_prefix() {
+        assert_eq!(key_label("C-b"), "Ctrl+b");
+        assert_eq!(key_label("C-M-a"), "Ctrl+Alt+a");
+        assert_eq!(key_label("`"), "`");
+        assert_eq!(
+            key_hint_format("Ctrl+a"),
+            "#{?client_last_session,Ctrl+a L#[fg=colour240] to return to aoe,Ctrl+a d#[fg=colour240] to detach}"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! tmux options aoe depends on, and checks for user configs that conflict
+//!
+//! Some global tmux settings break aoe: `destroy-unattached on` kills the
+//! detached sessions aoe creates, `alternate-screen off` leaves agent UIs
+//! smeared across the scrollback status detection reads, and `status off`
+//! hides the detach hint. aoe sets the options it can per session when
+//! creating one, so the user's global config is left untouched.
+
+use std::process::Command;
+
+/// Scope a tmux option is set at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    Server,
+    Session,
+    Window,
+}
+
+/// An option aoe needs a particular value for
+struct Requirement {
+    option: &'static str,
+    scope: Scope,
+    /// Whether `value` is acceptable
+    accepts: fn(&str) -> bool,
+    /// Value applied per session, or recommended for server options
+    recommended: &'static str,
+    /// What goes wrong with another value
+    impact: &'static str,
+}
+
+/// escape-time above this delays Esc reaching agents noticeably
+const MAX_ESCAPE_TIME_MS: u64 = 50;
+
+const REQUIREMENTS: &[Requirement] = &[
+    Requirement {
+        option: "destroy-unattached",
+        scope: Scope::Session,
+        accepts: |v| v == "off",
+        recommended: "off",
+        impact: "aoe sessions are destroyed as soon as they are detached",
+    },
+    Requirement {
+        option: "status",
+        scope: Scope::Session,
+        accepts: |v| v != "off",
+        recommended: "on",
+        impact: "the status bar with aoe's detach hint is hidden",
+    },
+    Requirement {
+        option: "alternate-screen",
+        scope: Scope::Window,
+        accepts: |v| v == "on",
+        recommended: "on",
+        impact: "full-screen agent output spills into the scrollback and confuses status detection",
+    },
+    Requirement {
+        option: "escape-time",
+        scope: Scope::Server,
+        accepts: |v| v.parse::<u64>().is_ok_and(|ms| ms <= MAX_ESCAPE_TIME_MS),
+        recommended: "10",
+        impact: "Esc and Alt keys reach agents late or are misread",
+    },
+];
+
+/// A user tmux option whose value conflicts with what aoe needs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionConflict {
+    pub option: &'static str,
+    pub value: String,
+    pub recommended: &'static str,
+    pub impact: &'static str,
+    /// aoe sets the option on its own sessions, so only other tmux
+    /// sessions see the user's value
+    pub overridden: bool,
+}
+
+/// Append `; set-option ...` commands for the per-session options aoe
+/// needs to an in-flight tmux argument list, so they are applied atomically
+/// with session creation and scoped to `target` rather than set globally.
+pub fn append_session_option_args(args: &mut Vec<String>, target: &str) {
+    for req in REQUIREMENTS {
+        let command = match req.scope {
+            Scope::Session => "set-option",
+            Scope::Window => "set-window-option",
+            Scope::Server => continue,
+        };
+        args.extend([
+            ";".to_string(),
+            command.to_string(),
+            "-t".to_string(),
+            target.to_string(),
+            req.option.to_string(),
+            req.recommended.to_string(),
+        ]);
+    }
+}
+
+/// Read the user's global tmux options and report those that conflict
+/// with aoe. Starts the tmux server if needed so the user's config is
+/// loaded. Options tmux doesn't know are skipped.
+pub fn check_user_options() -> Vec<OptionConflict> {
+    let values = REQUIREMENTS
+        .iter()
+        .filter_map(|req| {
+            let flags = match req.scope {
+                Scope::Server => "-sv",
+                Scope::Session => "-gv",
+                Scope::Window => "-gwv",
+            };
+            let output = Command::new("tmux")
+                .args(["start-server", ";", "show-options", flags, req.option])
+                .output()
+                .ok()
+                .filter(|o| o.status.success())?;
+            let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            Some((req.option, value))
+        })
+        .collect::<Vec<_>>();
+    conflicts(&values)
+}
+
+fn conflicts(values: &[(&str, String)]) -> Vec<OptionConflict> {
+    REQUIREMENTS
+        .iter()
+        .filter_map(|req| {
+            let (_, value) = values.iter().find(|(option, _)| *option == req.option)?;
+            (!(req.accepts)(value)).then(|| OptionConflict {
+                option: req.option,
+                value: value.clone(),
+                recommended: req.recommended,
+                impact: req.impact,
+                overridden: req.scope != Scope::Server,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflicts_flag_breaking_values() {
+        let values = [
+            ("destroy-unattached", "on".to_string()),
+            ("status", "2".to_string()),
+            ("alternate-screen", "off".to_string()),
+            ("escape-time", "500".to_string()),
+        ];
+        let found: Vec<(&str, bool)> = conflicts(&values)
+            .iter()
+            .map(|c| (c.option, c.overridden))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("destroy-unattached", true),
+                ("alternate-screen", true),
+                ("escape-time", false),
+            ]
+        );
+
+        let sane = [
+            ("status", "on".to_string()),
+            ("escape-time", "10".to_string()),
+        ];
+        assert!(conflicts(&sane).is_empty());
+    }
+
+    #[test]
+    fn test_session_option_args_skip_server_options() {
+        let mut args = vec!["new-session".to_string()];
+        append_session_option_args(&mut args, "aoe_x");
+        let joined = args.join(" ");
+        assert!(joined.contains("; set-option -t aoe_x destroy-unattached off"));
+        assert!(joined.contains("; set-window-option -t aoe_x alternate-screen on"));
+        assert!(!joined.contains("escape-time"));
+    }
+}
@@ -0,0 +1,525 @@
+//! Structured events parsed from agent pane output
+//!
+//! Each agent can register a parser (`AgentDef::parse_output`) that turns
+//! captured pane text into tool calls, file edits, and test runs, so the
+//! preview can list what the agent did instead of showing raw text. Agents
+//! without a parser, and output a parser doesn't recognise, fall back to the
+//! raw pane view.
+
+use serde_json::Value;
+
+use super::utils::strip_ansi;
+
+/// Parser signature: raw pane content (may contain ANSI codes) in, events
+/// out, oldest first.
+pub type OutputParser = fn(&str) -> Vec<OutputEvent>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputEvent {
+    /// Any tool invocation without a more specific kind (read, search, ...)
+    ToolCall { tool: String, detail: String },
+    /// A file was created or modified
+    FileEdit { tool: String, path: String },
+    /// A test command ran; `passed` is None while unknown
+    TestRun {
+        command: String,
+        passed: Option<bool>,
+    },
+}
+
+/// Tools that modify the file named in their argument.
+const EDIT_TOOLS: &[&str] = &["Edit", "MultiEdit", "Update", "Write", "NotebookEdit"];
+
+/// The parser for a session: the agent's own, or one matched by the custom
+/// command's binary (agents such as aider run as custom commands).
+pub fn parser_for(tool: &str, command: &str) -> Option<OutputParser> {
+    if let Some(parser) = crate::agents::get_agent(tool).and_then(|a| a.parse_output) {
+        return Some(parser);
+    }
+    let binary = command.split_whitespace().next().unwrap_or("");
+    match binary.rsplit('/').next() {
+        Some("aider") => Some(parse_aider_output),
+        _ => None,
+    }
+}
+
+/// Heuristic for shell commands that run a test suite.
+fn is_test_command(command: &str) -> bool {
+    command.split_whitespace().any(|word| {
+        let word = word.to_lowercase();
+        word.contains("test") || word == "rspec" || word == "jest" || word == "vitest"
+    })
+}
+
+/// Pass/fail from a test command's output, if it says.
+fn test_result(output: &str) -> Option<bool> {
+    let lower = output.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+        .filter(|w| !w.is_empty())
+        .collect();
+    // Sum of counts like "3 failed" or "12 passed"
+    let count = |label: &str| -> u64 {
+        words
+            .windows(2)
+            .filter(|w| w[1].starts_with(label))
+            .filter_map(|w| w[0].parse::<u64>().ok())
+            .sum()
+    };
+
+    if count("fail") > 0
+        || output.contains("FAIL")
+        || lower.contains("test result: failed")
+        || lower.contains("exit code")
+    {
+        Some(false)
+    } else if count("passed") > 0 || lower.contains("test result: ok") {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+fn event_for_call(tool: &str, arg: &str) -> OutputEvent {
+    if EDIT_TOOLS.contains(&tool) {
+        OutputEvent::FileEdit {
+            tool: tool.to_string(),
+            path: arg.to_string(),
+        }
+    } else if tool == "Bash" && is_test_command(arg) {
+        OutputEvent::TestRun {
+            command: arg.to_string(),
+            passed: None,
+        }
+    } else {
+        OutputEvent::ToolCall {
+            tool: tool.to_string(),
+            detail: arg.to_string(),
+        }
+    }
+}
+
+/// Claude Code output. Understands both the interactive transcript
+/// (`⏺ Bash(cargo test)` followed by `⎿` result lines) and the
+/// `--output-format stream-json` event stream.
+pub fn parse_claude_output(raw: &str) -> Vec<OutputEvent> {
+    let content = strip_ansi(raw);
+    let mut events = Vec::new();
+    // Index of the last test run, so its result lines can settle it
+    let mut pending_test: Option<usize> = None;
+    let mut result_text = String::new();
+    // stream-json test runs by tool_use id
+    let mut json_tests: Vec<(String, usize)> = Vec::new();
+
+    let settle = |events: &mut Vec<OutputEvent>, pending: &mut Option<usize>, text: &str| {
+        if let Some(idx) = pending.take() {
+            if let Some(OutputEvent::TestRun { passed, .. }) = events.get_mut(idx) {
+                *passed = test_result(text);
+            }
+        }
+    };
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('{') {
+            parse_claude_json_line(trimmed, &mut events, &mut json_tests);
+            continue;
+        }
+
+        if let Some(call) = trimmed.strip_prefix('⏺') {
+            settle(&mut events, &mut pending_test, &result_text);
+            result_text.clear();
+            if let Some((tool, arg)) = split_call(call.trim()) {
+                let event = event_for_call(tool, arg);
+                if matches!(event, OutputEvent::TestRun { .. }) {
+                    pending_test = Some(events.len());
+                }
+                events.push(event);
+            }
+        } else if pending_test.is_some() {
+            result_text.push_str(trimmed);
+            result_text.push('\n');
+        }
+    }
+    settle(&mut events, &mut pending_test, &result_text);
+    events
+}
+
+/// Split `Tool(argument)` into its parts. Plain assistant text after the
+/// bullet is not a call and yields None.
+fn split_call(call: &str) -> Option<(&str, &str)> {
+    let open = call.find('(')?;
+    let tool = &call[..open];
+    let starts_upper = tool.chars().next().is_some_and(|c| c.is_ascii_uppercase());
+    if !starts_upper || !tool.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    let arg = call[open + 1..]
+        .strip_suffix(')')
+        .unwrap_or(&call[open + 1..]);
+    Some((tool, arg.trim()))
+}
+
+/// One line of Claude's stream-json output: tool uses from assistant
+/// messages, and tool results that settle an earlier test run.
+fn parse_claude_json_line(
+    line: &str,
+    events: &mut Vec<OutputEvent>,
+    tests: &mut Vec<(String, usize)>,
+) {
+    let Ok(value) = serde_json::from_str::<Value>(line) else {
+        return;
+    };
+    let Some(content) = value.pointer("/message/content").and_then(Value::as_array) else {
+        return;
+    };
+
+    for block in content {
+        match block.get("type").and_then(Value::as_str) {
+            Some("tool_use") => {
+                let tool = block.get("name").and_then(Value::as_str).unwrap_or("");
+                let input = block.get("input");
+                let arg = ["file_path", "command", "pattern", "path", "url"]
+                    .iter()
+                    .find_map(|key| input.and_then(|i| i.get(key)).and_then(Value::as_str))
+                    .unwrap_or("");
+                let event = event_for_call(tool, arg);
+                if matches!(event, OutputEvent::TestRun { .. }) {
+                    let id = block.get("id").and_then(Value::as_str).unwrap_or("");
+                    tests.push((id.to_string(), events.len()));
+                }
+                events.push(event);
+            }
+            Some("tool_result") => {
+                let is_error = block
+                    .get("is_error")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                let id = block.get("tool_use_id").and_then(Value::as_str);
+                let idx = tests
+                    .iter()
+                    .find(|(test_id, _)| Some(test_id.as_str()) == id)
+                    .map(|(_, idx)| *idx);
+                if let Some(OutputEvent::TestRun { passed, .. }) =
+                    idx.and_then(|i| events.get_mut(i))
+                {
+                    *passed = Some(!is_error);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Aider output: applied edits, shell commands it runs, and commits.
+pub fn parse_aider_output(raw: &str) -> Vec<OutputEvent> {
+    let content = strip_ansi(raw);
+    let mut events: Vec<OutputEvent> = Vec::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if let Some(path) = trimmed.strip_prefix("Applied edit to ") {
+            events.push(OutputEvent::FileEdit {
+                tool: "Edit".to_string(),
+                path: path.trim().to_string(),
+            });
+        } else if let Some(command) = trimmed.strip_prefix("Running ") {
+            let command = command.trim().to_string();
+            events.push(if is_test_command(&command) {
+                OutputEvent::TestRun {
+                    command,
+                    passed: None,
+                }
+            } else {
+                OutputEvent::ToolCall {
+                    tool: "Run".to_string(),
+                    detail: command,
+                }
+            });
+        } else if let Some(rest) = trimmed.strip_prefix("Commit ") {
+            events.push(OutputEvent::ToolCall {
+                tool: "Commit".to_string(),
+                detail: rest.trim().to_string(),
+            });
+        } else if let Some(OutputEvent::TestRun { passed, .. }) = events.last_mut() {
+            if passed.is_none() && !trimmed.is_empty() {
+                *passed = test_result(trimmed);
+            }
+        }
+    }
+    events
+}
+
+/// The latest session cost the agent printed, in dollars: the last line
+/// mentioning "cost" with a dollar amount, such as Claude's `/cost` summary
+/// ("Total cost: $0.42") or a status line showing the cost.
+pub fn reported_cost(raw: &str) -> Option<f64> {
+    let content = strip_ansi(raw);
+    content.lines().rev().find_map(|line| {
+        if !line.to_lowercase().contains("cost") {
+            return None;
+        }
+        let amount = &line[line.find('$')? + 1..];
+        let end = amount
+            .find(|c: char| !c.is_ascii_digit() && c != '.' && c != ',')
+            .unwrap_or(amount.len());
+        amount[..end].replace(',', "").parse().ok()
+    })
+}
+
+/// Longest final message kept, in characters.
+const MAX_FINAL_MESSAGE_CHARS: usize = 1000;
+
+/// The agent's last message in Claude's output: the final `result` of a
+/// stream-json run, or the last plain-text `⏺` block of the interactive
+/// transcript (tool calls are skipped). Paragraphs are joined with newlines
+/// and the text is capped at `MAX_FINAL_MESSAGE_CHARS`.
+pub fn final_message(raw: &str) -> Option<String> {
+    let content = strip_ansi(raw);
+    let mut last: Option<Vec<String>> = None;
+    // Paragraphs of the text block being read, if any
+    let mut current: Option<Vec<String>> = None;
+    let mut json_result: Option<String> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('{') {
+            if let Some(text) = claude_json_text(trimmed) {
+                json_result = Some(text);
+            }
+            continue;
+        }
+
+        if let Some(text) = trimmed.strip_prefix('⏺') {
+            last = current.take().or(last);
+            let text = text.trim();
+            if split_call(text).is_none() && !text.is_empty() {
+                current = Some(vec![text.to_string()]);
+            }
+        } else if let Some(paragraphs) = &mut current {
+            if trimmed.is_empty() {
+                if paragraphs.last().is_some_and(|p| !p.is_empty()) {
+                    paragraphs.push(String::new());
+                }
+            } else if line.starts_with(char::is_whitespace) && !trimmed.starts_with('⎿') {
+                let paragraph = paragraphs.last_mut().expect("block starts non-empty");
+                if !paragraph.is_empty() {
+                    paragraph.push(' ');
+                }
+                paragraph.push_str(trimmed);
+            } else {
+                // Unindented text is the prompt box or a status line
+                last = current.take();
+            }
+        }
+    }
+
+    let text = match json_result {
+        Some(text) => text,
+        None => {
+            let paragraphs = current.or(last)?;
+            let paragraphs: Vec<&str> = paragraphs
+                .iter()
+                .map(String::as_str)
+                .filter(|p| !p.is_empty())
+                .collect();
+            paragraphs.join("\n")
+        }
+    };
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(match text.char_indices().nth(MAX_FINAL_MESSAGE_CHARS) {
+        Some((end, _)) => format!("{}…", text[..end].trim_end()),
+        None => text.to_string(),
+    })
+}
+
+/// Text of a stream-json line: the run's final `result`, or an assistant
+/// message's text blocks.
+fn claude_json_text(line: &str) -> Option<String> {
+    let value = serde_json::from_str::<Value>(line).ok()?;
+    if let Some(result) = value.get("result").and_then(Value::as_str) {
+        return Some(result.to_string());
+    }
+    let text: Vec<&str> = value
+        .pointer("/message/content")
+        .and_then(Value::as_array)?
+        .iter()
+        .filter(|block| block.get("type").and_then(Value::as_str) == Some("text"))
+        .filter_map(|block| block.get("text").and_then(Value::as_str))
+        .collect();
+    (!text.is_empty()).then(|| text.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claude_transcript() {
+        let output = "\
+⏺ I'll look at the failing test first.
+
+⏺ Read(src/lib.rs)
+  ⎿  Read 120 lines
+
+⏺ \x1b[1mUpdate\x1b[0m(src/lib.rs)
+  ⎿  Updated src/lib.rs with 2 additions
+
+⏺ Bash(cargo test)
+  ⎿  running 12 tests
+     test result: ok. 12 passed; 0 failed
+";
+        assert_eq!(
+            parse_claude_output(output),
+            vec![
+                OutputEvent::ToolCall {
+                    tool: "Read".to_string(),
+                    detail: "src/lib.rs".to_string()
+                },
+                OutputEvent::FileEdit {
+                    tool: "Update".to_string(),
+                    path: "src/lib.rs".to_string()
+                },
+                OutputEvent::TestRun {
+                    command: "cargo test".to_string(),
+                    passed: Some(true),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_claude_stream_json() {
+        let output = r#"{"type":"system","subtype":"init"}
+{"type":"assistant","message":{"content":[{"type":"tool_use","id":"1","name":"Write","input":{"file_path":"/p/a.rs","content":"x"}}]}}
+{"type":"assistant","message":{"content":[{"type":"tool_use","id":"2","name":"Bash","input":{"command":"npm test"}}]}}
+{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"2","is_error":true}]}}
+not json"#;
+        assert_eq!(
+            parse_claude_output(output),
+            vec![
+                OutputEvent::FileEdit {
+                    tool: "Write".to_string(),
+                    path: "/p/a.rs".to_string()
+                },
+                OutputEvent::TestRun {
+                    command: "npm test".to_string(),
+                    passed: Some(false)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_aider_output() {
+        let output = "\
+src/app.py
+<<<<<<< SEARCH
+=======
+>>>>>>> REPLACE
+Applied edit to src/app.py
+Commit 1a2b3c4 fix: handle empty input
+Running pytest -q
+3 passed in 0.12s
+";
+        assert_eq!(
+            parse_aider_output(output),
+            vec![
+                OutputEvent::FileEdit {
+                    tool: "Edit".to_string(),
+                    path: "src/app.py".to_string()
+                },
+                OutputEvent::ToolCall {
+                    tool: "Commit".to_string(),
+                    detail: "1a2b3c4 fix: handle empty input".to_string()
+                },
+                OutputEvent::TestRun {
+                    command: "pytest -q".to_string(),
+                    passed: Some(true)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_test_result() {
+        assert_eq!(
+            test_result("test result: ok. 12 passed; 0 failed"),
+            Some(true)
+        );
+        assert_eq!(test_result("Tests: 1 failed, 4 passed"), Some(false));
+        assert_eq!(test_result("Error: Exit code 101"), Some(false));
+        assert_eq!(test_result("Compiling foo v0.1.0"), None);
+    }
+
+    #[test]
+    fn test_parser_for() {
+        assert!(parser_for("claude", "").is_some());
+        assert!(parser_for("opencode", "").is_none());
+        assert!(parser_for("opencode", "/usr/local/bin/aider --model x").is_some());
+    }
+
+    #[test]
+    fn test_reported_cost() {
+        let output = "\
+> /cost
+  ⎿  Total cost:            $0.0123
+     Total duration (API):  1m 2.3s
+
+Model: opus | Cost: \x1b[33m$1,204.50\x1b[0m | ctx 40%
+";
+        assert_eq!(reported_cost(output), Some(1204.5));
+        assert_eq!(reported_cost("Total cost: $0.0123"), Some(0.0123));
+        assert_eq!(reported_cost("Paid $5 for lunch"), None);
+        assert_eq!(reported_cost("cost: unknown"), None);
+    }
+
+    #[test]
+    fn test_final_message_transcript() {
+        let output = "\
+⏺ I'll run the tests.
+
+⏺ Bash(cargo test)
+  ⎿  test result: ok. 12 passed; 0 failed
+
+⏺ All 12 tests pass. I fixed the off-by-one in
+  \x1b[1mparse_range\x1b[0m and added a regression test.
+
+  No other files changed.
+
+╭──────────────────────────────╮
+│ >                            │
+╰──────────────────────────────╯
+";
+        assert_eq!(
+            final_message(output).as_deref(),
+            Some(
+                "All 12 tests pass. I fixed the off-by-one in parse_range and added a \
+                 regression test.\nNo other files changed."
+            )
+        );
+        assert_eq!(
+            final_message("⏺ Read(src/lib.rs)\n  ⎿  Read 3 lines\n"),
+            None
+        );
+        assert_eq!(final_message(""), None);
+    }
+
+    #[test]
+    fn test_final_message_stream_json() {
+        let output = r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Working on it"}]}}
+{"type":"result","subtype":"success","result":"Done: renamed the module."}"#;
+        assert_eq!(
+            final_message(output).as_deref(),
+            Some("Done: renamed the module.")
+        );
+
+        let long = format!("⏺ {}", "word ".repeat(400));
+        let message = final_message(&long).unwrap();
+        assert!(message.ends_with('…'));
+        assert!(message.chars().count() <= MAX_FINAL_MESSAGE_CHARS + 1);
+    }
+}
@@ -0,0 +1,313 @@
+//! Tool-permission prompts
+//!
+//! Agents that ask before running a tool pause on a prompt: a numbered menu
+//! under a question ("Do you want to proceed?" with "1. Yes", "2. Yes, and
+//! don't ask again ...", "3. No ...") or a `(y/n)` question. `detect` finds
+//! one at the bottom of a pane and works out which keys answer it, so the
+//! TUI can allow or deny without attaching.
+
+use anyhow::{bail, Result};
+
+use super::utils::strip_ansi;
+use super::Session;
+
+/// Non-empty pane lines searched for a prompt
+const TAIL_LINES: usize = 15;
+
+/// Lines above a menu searched for its question
+const QUESTION_LINES: usize = 6;
+
+/// Lines above the question kept with a prompt; they name the tool call
+const CONTEXT_LINES: usize = 8;
+
+/// Non-empty lines a menu may be followed by (key hints, a box border), so
+/// one the agent has moved past is not picked up
+const MAX_FOOTER_LINES: usize = 3;
+
+/// Borders, cursors and bullets agents draw around menus and questions
+const DECORATION: &[char] = &[
+    '❯', '›', '>', '●', '○', '▌', '│', '┃', '╭', '╮', '╰', '╯', '─', '*', ' ',
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionChoice {
+    Allow,
+    /// Allow, and don't ask again for the same kind of tool call
+    AlwaysAllow,
+    Deny,
+}
+
+impl PermissionChoice {
+    pub fn label(self) -> &'static str {
+        match self {
+            PermissionChoice::Allow => "allow",
+            PermissionChoice::AlwaysAllow => "always allow",
+            PermissionChoice::Deny => "deny",
+        }
+    }
+}
+
+/// A tool-permission prompt an agent is paused on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionPrompt {
+    /// The question asked, e.g. "Do you want to proceed?"
+    pub question: String,
+    /// The non-empty lines above the question, which name the tool call.
+    /// Questions are generic, so these tell one prompt from the next.
+    pub context: Vec<String>,
+    /// Keys that pick each choice the prompt offers, in menu order
+    pub options: Vec<(PermissionChoice, String)>,
+    /// Whether the keys have to be followed by Enter, as for `(y/n)`
+    pub enter: bool,
+}
+
+impl PermissionPrompt {
+    /// The keys that answer with `choice`, if the prompt offers it
+    pub fn key(&self, choice: PermissionChoice) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(c, _)| *c == choice)
+            .map(|(_, key)| key.as_str())
+    }
+}
+
+/// The permission prompt at the bottom of `raw_content`, if there is one.
+pub fn detect(raw_content: &str) -> Option<PermissionPrompt> {
+    let lines: Vec<String> = raw_content
+        .lines()
+        .map(|l| strip_ansi(l).trim_end().to_string())
+        .filter(|l| !l.trim().is_empty())
+        .collect();
+    let skip = lines.len().saturating_sub(TAIL_LINES);
+    let tail = &lines[skip..];
+    let (mut prompt, question_index) = detect_menu(tail).or_else(|| detect_yes_no(tail))?;
+    let question_index = skip + question_index;
+    prompt.context = lines[question_index.saturating_sub(CONTEXT_LINES)..question_index].to_vec();
+    Some(prompt)
+}
+
+/// Answer the prompt `session` shows with `choice`. The pane is read again
+/// first, so nothing is typed into an agent that has moved on, including to
+/// another tool call asking the same question.
+pub fn answer(
+    session: &Session,
+    expected: &PermissionPrompt,
+    choice: PermissionChoice,
+) -> Result<()> {
+    let current = detect(&session.capture_pane(30)?);
+    let Some(prompt) =
+        current.filter(|p| p.question == expected.question && p.context == expected.context)
+    else {
+        bail!("The agent is no longer showing that prompt");
+    };
+    let Some(key) = prompt.key(choice) else {
+        bail!("This prompt has no \"{}\" option", choice.label());
+    };
+    session.send_answer(key, prompt.enter)
+}
+
+fn undecorated(line: &str) -> &str {
+    line.trim_matches(|c| DECORATION.contains(&c))
+}
+
+/// A numbered menu line: its number and label
+fn menu_option(line: &str) -> Option<(usize, &str)> {
+    let line = undecorated(line);
+    let digits = line.find(|c: char| !c.is_ascii_digit())?;
+    let number = line[..digits].parse().ok()?;
+    let label = line[digits..]
+        .strip_prefix(". ")
+        .or_else(|| line[digits..].strip_prefix(") "))?;
+    Some((number, label.trim()))
+}
+
+fn classify(label: &str) -> Option<PermissionChoice> {
+    let label = label.to_lowercase();
+    let first = label
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or("");
+    match first {
+        "no" | "deny" | "reject" | "decline" | "cancel" => Some(PermissionChoice::Deny),
+        "yes" | "allow" | "approve" | "accept" | "proceed" | "run" => {
+            let always = [
+                "always",
+                "don't ask",
+                "don’t ask",
+                "dont ask",
+                "do not ask",
+                "this session",
+            ]
+            .iter()
+            .any(|p| label.contains(p));
+            Some(if always {
+                PermissionChoice::AlwaysAllow
+            } else {
+                PermissionChoice::Allow
+            })
+        }
+        _ => None,
+    }
+}
+
+/// A menu prompt in `tail`, and the index of its question line
+fn detect_menu(tail: &[String]) -> Option<(PermissionPrompt, usize)> {
+    let last = tail.iter().rposition(|l| menu_option(l).is_some())?;
+    if tail.len() - 1 - last > MAX_FOOTER_LINES {
+        return None;
+    }
+
+    // Walk up the menu to option 1, allowing one wrapped line per option
+    let (mut number, label) = menu_option(&tail[last])?;
+    let mut labels = vec![(number, label)];
+    let mut first = last;
+    while number > 1 {
+        let above = first.saturating_sub(2)..first;
+        let (index, option) = above
+            .rev()
+            .find_map(|i| menu_option(&tail[i]).map(|o| (i, o)))?;
+        if option.0 != number - 1 {
+            return None;
+        }
+        number = option.0;
+        labels.push(option);
+        first = index;
+    }
+    labels.reverse();
+
+    let mut options = Vec::new();
+    for (number, label) in labels {
+        if let Some(choice) = classify(label) {
+            if !options.iter().any(|(c, _)| *c == choice) {
+                options.push((choice, number.to_string()));
+            }
+        }
+    }
+    let has = |choice| options.iter().any(|(c, _)| *c == choice);
+    if !has(PermissionChoice::Allow) || !has(PermissionChoice::Deny) {
+        return None;
+    }
+
+    let start = first.saturating_sub(QUESTION_LINES);
+    let (index, question) = tail[start..first]
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, l)| (start + i, undecorated(l)))
+        .find(|(_, l)| l.ends_with('?'))?;
+    let prompt = PermissionPrompt {
+        question: question.to_string(),
+        context: Vec::new(),
+        options,
+        enter: false,
+    };
+    Some((prompt, index))
+}
+
+/// A `(y/n)` question on the last line of `tail`, and that line's index
+fn detect_yes_no(tail: &[String]) -> Option<(PermissionPrompt, usize)> {
+    let line = undecorated(tail.last()?);
+    let lower = line.to_lowercase();
+    if !["(y/n)", "[y/n]", "(yes/no)", "[yes/no]"]
+        .iter()
+        .any(|p| lower.contains(p))
+    {
+        return None;
+    }
+    let prompt = PermissionPrompt {
+        question: line.to_string(),
+        context: Vec::new(),
+        options: vec![
+            (PermissionChoice::Allow, "y".to_string()),
+            (PermissionChoice::Deny, "n".to_string()),
+        ],
+        enter: true,
+    };
+    Some((prompt, tail.len() - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_numbered_menu() {
+        let pane = "\
+⏺ Bash(cargo test)
+╭──────────────────────────────────────────────╮
+│ Bash command                                 │
+│   cargo test                                 │
+│ Do you want to proceed?                      │
+│ ❯ 1. Yes                                     │
+│   2. Yes, and don't ask again for cargo test │
+│   3. No, and tell Claude what to do          │
+│      differently (esc)                       │
+╰──────────────────────────────────────────────╯
+";
+        let prompt = detect(pane).expect("prompt");
+        assert_eq!(prompt.question, "Do you want to proceed?");
+        assert_eq!(
+            prompt.context.last().map(|l| l.trim()),
+            Some("│   cargo test                                 │")
+        );
+        assert_eq!(prompt.context[0], "⏺ Bash(cargo test)");
+        assert_eq!(prompt.key(PermissionChoice::Allow), Some("1"));
+        assert_eq!(prompt.key(PermissionChoice::AlwaysAllow), Some("2"));
+        assert_eq!(prompt.key(PermissionChoice::Deny), Some("3"));
+        assert!(!prompt.enter);
+
+        let gemini = "\
+Allow execution of: 'npm test'?
+● 1. Yes, allow once
+  2. Yes, allow always
+  3. No, suggest changes (esc)
+";
+        let prompt = detect(gemini).expect("prompt");
+        assert_eq!(prompt.question, "Allow execution of: 'npm test'?");
+        assert_eq!(prompt.key(PermissionChoice::AlwaysAllow), Some("2"));
+    }
+
+    #[test]
+    fn test_context_tells_calls_with_the_same_question_apart() {
+        let prompt_for = |command: &str| {
+            detect(&format!(
+                "⏺ Bash({})\nDo you want to proceed?\n❯ 1. Yes\n  2. No\n",
+                command
+            ))
+            .expect("prompt")
+        };
+        let tests = prompt_for("cargo test");
+        let cleanup = prompt_for("rm -rf target");
+        assert_eq!(tests.question, cleanup.question);
+        assert_ne!(tests, cleanup);
+        assert_eq!(tests, prompt_for("cargo test"));
+    }
+
+    #[test]
+    fn test_detect_yes_no() {
+        let prompt = detect("Running rm -rf build\nContinue? [y/N]\n").expect("prompt");
+        assert_eq!(prompt.context, vec!["Running rm -rf build"]);
+        assert_eq!(prompt.key(PermissionChoice::Allow), Some("y"));
+        assert_eq!(prompt.key(PermissionChoice::Deny), Some("n"));
+        assert_eq!(prompt.key(PermissionChoice::AlwaysAllow), None);
+        assert!(prompt.enter);
+    }
+
+    #[test]
+    fn test_ignores_other_lists_and_old_prompts() {
+        // A numbered list in the agent's answer has no yes/no options
+        assert!(detect("Next steps?\n1. Add tests\n2. Update docs\n> ").is_none());
+
+        // A prompt already answered has scrolled up past the footer allowance
+        let answered = "\
+Do you want to proceed?
+❯ 1. Yes
+  2. No
+⏺ Bash(cargo test)
+  running 12 tests
+  test result: ok
+  all done
+> ";
+        assert!(detect(answered).is_none());
+    }
+}
@@ -2,18 +2,32 @@
 
 use anyhow::{bail, Result};
 use std::process::Command;
+use std::time::{Duration, Instant};
 
 use super::{
     refresh_session_cache, session_exists_from_cache,
     utils::{
         append_pane_base_index_args, append_remain_on_exit_args, is_pane_dead,
-        is_pane_running_shell,
+        is_pane_running_shell, strip_ansi,
     },
     SESSION_PREFIX,
 };
-use crate::cli::truncate_id;
+use crate::multiplexer::Multiplexer;
 use crate::process;
-use crate::session::Status;
+use crate::session::{truncate_id, Status};
+
+const BRACKETED_PASTE_START: &str = "\x1b[200~";
+const BRACKETED_PASTE_END: &str = "\x1b[201~";
+
+/// Largest piece of a paste handed to tmux at once
+const PASTE_CHUNK_BYTES: usize = 1024;
+/// Pause between paste chunks so the agent's input loop keeps up
+const PASTE_CHUNK_DELAY: Duration = Duration::from_millis(20);
+/// How long to wait for the agent to show pasted text before giving up
+const PASTE_ECHO_TIMEOUT: Duration = Duration::from_secs(5);
+const PASTE_ECHO_POLL: Duration = Duration::from_millis(100);
+/// Leading characters of the pasted text's last line looked for in the pane
+const PASTE_ECHO_SNIPPET_CHARS: usize = 20;
 
 pub struct Session {
     name: String,
@@ -31,6 +45,13 @@ impl Session {
         format!("{}{}_{}", SESSION_PREFIX, safe_title, truncate_id(id, 8))
     }
 
+    /// Whether the tmux session `name` belongs to the aoe session `id`.
+    /// Only the ID at the end of the name is compared, so this holds even
+    /// if the title in the name predates a rename.
+    pub fn name_matches_id(name: &str, id: &str) -> bool {
+        name.starts_with(SESSION_PREFIX) && name.ends_with(&format!("_{}", truncate_id(id, 8)))
+    }
+
     pub fn exists(&self) -> bool {
         if let Some(exists) = session_exists_from_cache(&self.name) {
             return exists;
@@ -56,10 +77,12 @@ impl Session {
         if self.exists() {
             return Ok(());
         }
+        super::version::check_supported()?;
 
         let mut args = build_create_args(&self.name, working_dir, command, size);
         append_remain_on_exit_args(&mut args, &self.name);
         append_pane_base_index_args(&mut args, &self.name);
+        super::options::append_session_option_args(&mut args, &self.name);
 
         let output = Command::new("tmux").args(&args).output()?;
 
@@ -138,34 +161,31 @@ impl Session {
             bail!("Session does not exist: {}", self.name);
         }
 
-        if std::env::var("TMUX").is_ok() {
-            let status = Command::new("tmux")
-                .args(["switch-client", "-t", &self.name])
-                .status()?;
-
-            if !status.success() {
-                // Fall back to attach-session if switch-client fails.
-                // This handles cases where TMUX env var is inherited but we're
-                // not actually inside a tmux client (e.g., terminal spawned
-                // from within tmux via `open -a Terminal`).
-                let status = Command::new("tmux")
-                    .args(["attach-session", "-t", &self.name])
-                    .status()?;
+        let status = super::nested::attach_command(&self.name).status()?;
+        if !status.success() {
+            bail!("Failed to attach to tmux session");
+        }
 
-                if !status.success() {
-                    bail!("Failed to attach to tmux session");
-                }
-            }
-        } else {
-            let status = Command::new("tmux")
-                .args(["attach-session", "-t", &self.name])
-                .status()?;
+        Ok(())
+    }
 
-            if !status.success() {
-                bail!("Failed to attach to tmux session");
-            }
+    /// Attach as a read-only client, which shows the session but ignores
+    /// keys other than detaching. Inside tmux this opens a nested client,
+    /// since switching the current client would leave it read-only; detach
+    /// it by pressing the prefix key twice, then `d`.
+    pub fn attach_read_only(&self) -> Result<()> {
+        if !self.exists() {
+            bail!("Session does not exist: {}", self.name);
         }
 
+        let status = Command::new("tmux")
+            .args(["attach-session", "-r", "-t", &self.name])
+            .env_remove("TMUX")
+            .status()?;
+
+        if !status.success() {
+            bail!("Failed to attach to tmux session");
+        }
         Ok(())
     }
 
@@ -205,6 +225,62 @@ impl Session {
         }
     }
 
+    /// Scrollback size and cursor row of the agent pane.
+    fn pane_position(&self) -> Option<(usize, usize)> {
+        let target = format!("{}:^.0", self.name);
+        let output = Command::new("tmux")
+            .args([
+                "display-message",
+                "-t",
+                &target,
+                "-p",
+                "#{history_size} #{cursor_y}",
+            ])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let text = String::from_utf8_lossy(&output.stdout);
+        let (history, cursor) = text.trim().split_once(' ')?;
+        Some((history.parse().ok()?, cursor.parse().ok()?))
+    }
+
+    /// Lines of output in the agent pane so far, scrollback included, up to
+    /// the cursor. A position for `capture_from`.
+    pub fn output_line_count(&self) -> Option<usize> {
+        let (history, cursor) = self.pane_position()?;
+        Some(history + cursor)
+    }
+
+    /// Capture the agent pane from line `line`, as counted by
+    /// `output_line_count`, to the end. Lines that have since scrolled out
+    /// of the history limit are lost.
+    pub fn capture_from(&self, line: usize) -> Result<String> {
+        if !self.exists() {
+            return Ok(String::new());
+        }
+        let history = self.pane_position().map_or(0, |(history, _)| history);
+        let start = line as i64 - history as i64;
+        let target = format!("{}:^.0", self.name);
+        let output = Command::new("tmux")
+            .args([
+                "capture-pane",
+                "-t",
+                &target,
+                "-p",
+                "-S",
+                &start.to_string(),
+            ])
+            .output()?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            Ok(String::new())
+        }
+    }
+
     pub fn get_pane_pid(&self) -> Option<u32> {
         process::get_pane_pid(&self.name)
     }
@@ -215,11 +291,16 @@ impl Session {
     }
 
     pub fn detect_status(&self, tool: &str) -> Result<Status> {
+        Ok(self.detect_status_with_content(tool)?.0)
+    }
+
+    /// Like `detect_status`, also returning the pane content the status was
+    /// read from, so callers can check it for more without capturing again.
+    pub fn detect_status_with_content(&self, tool: &str) -> Result<(Status, String)> {
         let content = self.capture_pane(50)?;
         let fg_pid = self.get_foreground_pid();
-        Ok(super::status_detection::detect_status_from_content(
-            &content, tool, fg_pid,
-        ))
+        let status = super::status_detection::detect_status_from_content(&content, tool, fg_pid);
+        Ok((status, content))
     }
 
     /// Send literal text to the session's first window pane, followed by Enter.
@@ -229,32 +310,156 @@ impl Session {
             bail!("Session does not exist: {}", self.name);
         }
 
-        let target = format!("{}:^.0", self.name);
-
         for line in text.lines() {
-            let output = Command::new("tmux")
-                .args(["send-keys", "-t", &target, "-l", line])
-                .output()?;
+            self.send_literal(line)?;
+            self.send_enter()?;
+        }
 
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                bail!("Failed to send keys: {}", stderr);
-            }
+        Ok(())
+    }
+
+    /// Paste `text` into the session as one bracketed paste, then press Enter.
+    ///
+    /// Unlike `send_keys`, newlines stay part of a single message instead of
+    /// submitting each line. The text goes out in small chunks so the agent's
+    /// input handling keeps up with large payloads, and Enter is only sent
+    /// once the pane shows the paste arrived.
+    pub fn paste_text(&self, text: &str) -> Result<()> {
+        if !self.exists() {
+            bail!("Session does not exist: {}", self.name);
+        }
 
-            let output = Command::new("tmux")
-                .args(["send-keys", "-t", &target, "Enter"])
-                .output()?;
+        let before = self.capture_pane(0)?;
+        self.send_literal(BRACKETED_PASTE_START)?;
+        for chunk in paste_chunks(text, PASTE_CHUNK_BYTES) {
+            self.send_literal(chunk)?;
+            std::thread::sleep(PASTE_CHUNK_DELAY);
+        }
+        self.send_literal(BRACKETED_PASTE_END)?;
 
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                bail!("Failed to send Enter: {}", stderr);
+        let deadline = Instant::now() + PASTE_ECHO_TIMEOUT;
+        loop {
+            std::thread::sleep(PASTE_ECHO_POLL);
+            if paste_echoed(&before, &self.capture_pane(0)?, text) {
+                break;
+            }
+            if Instant::now() >= deadline {
+                bail!("The agent did not show the pasted text; Enter was not sent");
             }
         }
+        self.send_enter()
+    }
 
+    /// Type `keys` as they are, pressing Enter only if `enter`, e.g. to
+    /// pick an option in a menu the agent shows.
+    pub fn send_answer(&self, keys: &str, enter: bool) -> Result<()> {
+        if !self.exists() {
+            bail!("Session does not exist: {}", self.name);
+        }
+        self.send_literal(keys)?;
+        if enter {
+            self.send_enter()?;
+        }
+        Ok(())
+    }
+
+    fn send_literal(&self, text: &str) -> Result<()> {
+        let target = format!("{}:^.0", self.name);
+        let output = Command::new("tmux")
+            .args(["send-keys", "-t", &target, "-l", text])
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("Failed to send keys: {}", stderr);
+        }
+        Ok(())
+    }
+
+    fn send_enter(&self) -> Result<()> {
+        let target = format!("{}:^.0", self.name);
+        let output = Command::new("tmux")
+            .args(["send-keys", "-t", &target, "Enter"])
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("Failed to send Enter: {}", stderr);
+        }
         Ok(())
     }
 }
 
+impl Multiplexer for Session {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn exists(&self) -> bool {
+        Session::exists(self)
+    }
+
+    fn create(&self, working_dir: &str, command: Option<&str>) -> Result<()> {
+        Session::create(self, working_dir, command)
+    }
+
+    fn kill(&self) -> Result<()> {
+        Session::kill(self)
+    }
+
+    fn capture(&self, lines: usize) -> Result<String> {
+        self.capture_pane(lines)
+    }
+
+    fn send_keys(&self, text: &str) -> Result<()> {
+        Session::send_keys(self, text)
+    }
+
+    fn attach(&self) -> Result<()> {
+        Session::attach(self)
+    }
+}
+
+/// Split `text` into pieces of at most `max_bytes`, breaking after newlines
+/// where possible and never inside a UTF-8 character.
+fn paste_chunks(text: &str, max_bytes: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while rest.len() > max_bytes {
+        let mut end = max_bytes;
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        if let Some(newline) = rest[..end].rfind('\n') {
+            end = newline + 1;
+        }
+        chunks.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+/// Whether the pane changed to show a paste of `text`: either its last line,
+/// or the placeholder agents such as Claude show for long pastes.
+fn paste_echoed(before: &str, after: &str, text: &str) -> bool {
+    if before == after {
+        return false;
+    }
+    let after = strip_ansi(after);
+    if after.contains("[Pasted text") {
+        return true;
+    }
+    let Some(last_line) = text.lines().rev().map(str::trim).find(|l| !l.is_empty()) else {
+        return true;
+    };
+    // Wide lines wrap in the pane, so only look for their start
+    let snippet: String = last_line.chars().take(PASTE_ECHO_SNIPPET_CHARS).collect();
+    after.contains(&snippet)
+}
+
 fn sanitize_session_name(name: &str) -> String {
     name.chars()
         .map(|c| {
@@ -795,6 +1000,13 @@ mod tests {
         assert!(name.starts_with(SESSION_PREFIX));
         assert!(name.contains("My_Project"));
         assert!(name.contains("abc123de"));
+        assert!(Session::name_matches_id(&name, "abc123def456"));
+        // Renamed since the tmux session was created
+        assert!(Session::name_matches_id(
+            "aoe_Old_Title_abc123de",
+            "abc123def456"
+        ));
+        assert!(!Session::name_matches_id(&name, "fff123def456"));
     }
 
     #[test]
@@ -919,4 +1131,34 @@ mod tests {
             .args(["kill-session", "-t", &session_name])
             .output();
     }
+
+    #[test]
+    fn test_paste_chunks() {
+        assert_eq!(paste_chunks("", 8), Vec::<&str>::new());
+        assert_eq!(paste_chunks("short", 8), vec!["short"]);
+        assert_eq!(
+            paste_chunks("one\ntwo\nthree\n", 9),
+            vec!["one\ntwo\n", "three\n"]
+        );
+        // No newline to break at: split at the limit, on a char boundary
+        assert_eq!(paste_chunks("ééééé", 5), vec!["éé", "éé", "é"]);
+    }
+
+    #[test]
+    fn test_paste_echoed() {
+        let before = "> ";
+        let text = "Refactor the parser.\n\nKeep the public API.\n";
+        assert!(!paste_echoed(before, before, text));
+        assert!(paste_echoed(
+            before,
+            "> Refactor the parser.\n\n  Keep the public API.",
+            text
+        ));
+        assert!(paste_echoed(
+            before,
+            "> \x1b[2m[Pasted text #1 +3 lines]\x1b[0m",
+            text
+        ));
+        assert!(!paste_echoed(before, "> Refactor the", text));
+    }
 }
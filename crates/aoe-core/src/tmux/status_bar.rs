@@ -52,12 +52,16 @@ pub fn apply_status_bar(
 
     // Dark background with light text - matches aoe phosphor theme
     set_session_option(session_name, "status-style", "bg=colour235,fg=colour252")?;
+    let key_hint = super::nested::key_hint_format(&super::nested::prefix_label());
     set_session_option(
         session_name,
         "status-left",
-        " #[fg=colour46,bold]#S#[fg=colour252,nobold] │ #[fg=colour245]Ctrl+b d#[fg=colour240] to detach ",
+        &format!(
+            " #[fg=colour46,bold]#S#[fg=colour252,nobold] │ #[fg=colour245]{} ",
+            key_hint
+        ),
     )?;
-    set_session_option(session_name, "status-left-length", "50")?;
+    set_session_option(session_name, "status-left-length", "60")?;
 
     Ok(())
 }
@@ -85,14 +89,17 @@ pub fn apply_mouse_option(session_name: &str, enabled: bool) -> Result<()> {
 }
 
 /// Apply all configured tmux options to a session.
-/// This is a unified entry point that applies status bar styling and mouse settings.
+/// This is a unified entry point that applies status bar styling, mouse
+/// settings, and the window size policy.
 pub fn apply_all_tmux_options(
     session_name: &str,
     title: &str,
     branch: Option<&str>,
     sandbox: Option<&SandboxDisplay>,
 ) {
-    use crate::session::config::{should_apply_tmux_mouse, should_apply_tmux_status_bar};
+    use crate::session::config::{
+        should_apply_tmux_mouse, should_apply_tmux_status_bar, tmux_window_size,
+    };
 
     if should_apply_tmux_status_bar() {
         if let Err(e) = apply_status_bar(session_name, title, branch, sandbox) {
@@ -105,6 +112,10 @@ pub fn apply_all_tmux_options(
             tracing::debug!("Failed to apply tmux mouse option: {}", e);
         }
     }
+
+    if let Err(e) = super::window_size::apply(session_name, tmux_window_size()) {
+        tracing::debug!("Failed to apply tmux window size policy: {}", e);
+    }
 }
 
 /// Session info retrieved from tmux user options.
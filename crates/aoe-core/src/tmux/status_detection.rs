@@ -543,6 +543,60 @@ pub fn detect_gemini_status(raw_content: &str) -> Status {
     Status::Idle
 }
 
+/// Messages agents print when their credentials are missing, expired, or
+/// revoked. Matched case-insensitively against the last lines of the pane.
+const AUTH_REQUIRED_PATTERNS: &[&str] = &[
+    "invalid api key",
+    "please run /login",
+    "oauth token has expired",
+    "token has expired",
+    "token expired",
+    "authentication_error",
+    "authentication failed",
+    "not logged in",
+    "please log in",
+    "please login",
+    "re-authenticate",
+    "reauthenticate",
+];
+
+/// Messages agents print when their plan's usage limit or API quota is
+/// used up. Transient rate limits, which the agents retry themselves, are
+/// deliberately not included.
+const QUOTA_EXCEEDED_PATTERNS: &[&str] = &[
+    "usage limit reached",
+    "hit your usage limit",
+    "quota exceeded",
+    "exceeded your current quota",
+    "insufficient_quota",
+    "credit balance is too low",
+];
+
+/// Whether the pane shows an agent asking for its credentials to be renewed.
+/// Only the last few non-empty lines are checked so an old failure that has
+/// scrolled away, or the agent discussing auth code, does not match.
+pub fn detect_auth_required(raw_content: &str) -> bool {
+    tail_matches(raw_content, AUTH_REQUIRED_PATTERNS)
+}
+
+/// Whether the pane shows the agent stopped by a usage limit or quota.
+/// Checked the same way as `detect_auth_required`.
+pub fn detect_quota_exceeded(raw_content: &str) -> bool {
+    tail_matches(raw_content, QUOTA_EXCEEDED_PATTERNS)
+}
+
+fn tail_matches(raw_content: &str, patterns: &[&str]) -> bool {
+    let tail: Vec<String> = raw_content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .rev()
+        .take(8)
+        .map(|l| strip_ansi(l).to_lowercase())
+        .collect();
+    tail.iter()
+        .any(|line| patterns.iter().any(|p| line.contains(p)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -799,4 +853,36 @@ mod tests {
         assert_eq!(detect_pi_status("file saved"), Status::Idle);
         assert_eq!(detect_pi_status("random output text"), Status::Idle);
     }
+
+    #[test]
+    fn test_detect_auth_required() {
+        assert!(detect_auth_required(
+            "working...\nAPI Error: 401 OAuth token has expired\n> "
+        ));
+        assert!(detect_auth_required(
+            "\x1b[31mInvalid API key · Please run /login\x1b[0m\n"
+        ));
+        assert!(!detect_auth_required("all tests passed\n> "));
+
+        // An old failure that has scrolled out of the tail is ignored
+        let mut content = String::from("Invalid API key\n");
+        for i in 0..10 {
+            content.push_str(&format!("line {}\n", i));
+        }
+        assert!(!detect_auth_required(&content));
+    }
+
+    #[test]
+    fn test_detect_quota_exceeded() {
+        assert!(detect_quota_exceeded(
+            "Claude usage limit reached. Your limit will reset at 3pm.\n> "
+        ));
+        assert!(detect_quota_exceeded(
+            "ERROR: You've hit your usage limit. Try again later.\n"
+        ));
+        assert!(!detect_quota_exceeded(
+            "API Error: 429 rate_limit_error, retrying\n"
+        ));
+        assert!(!detect_auth_required("Claude usage limit reached.\n"));
+    }
 }
@@ -7,10 +7,11 @@ use super::utils::{
     append_pane_base_index_args, append_remain_on_exit_args, is_pane_dead, sanitize_session_name,
 };
 use super::{
-    refresh_session_cache, session_exists_from_cache, CONTAINER_TERMINAL_PREFIX, TERMINAL_PREFIX,
+    refresh_session_cache, session_exists_from_cache, COMMAND_PREFIX, CONTAINER_TERMINAL_PREFIX,
+    LOGIN_PREFIX, TERMINAL_PREFIX, WATCH_PREFIX,
 };
-use crate::cli::truncate_id;
 use crate::process;
+use crate::session::truncate_id;
 
 pub struct TerminalSession {
     name: String,
@@ -61,6 +62,7 @@ impl TerminalSession {
         let mut args = build_terminal_create_args(&self.name, working_dir, command, size);
         append_remain_on_exit_args(&mut args, &self.name);
         append_pane_base_index_args(&mut args, &self.name);
+        super::options::append_session_option_args(&mut args, &self.name);
 
         let output = Command::new("tmux").args(&args).output()?;
 
@@ -107,28 +109,9 @@ impl TerminalSession {
             bail!("Terminal session does not exist: {}", self.name);
         }
 
-        if std::env::var("TMUX").is_ok() {
-            let status = Command::new("tmux")
-                .args(["switch-client", "-t", &self.name])
-                .status()?;
-
-            if !status.success() {
-                let status = Command::new("tmux")
-                    .args(["attach-session", "-t", &self.name])
-                    .status()?;
-
-                if !status.success() {
-                    bail!("Failed to attach to terminal session");
-                }
-            }
-        } else {
-            let status = Command::new("tmux")
-                .args(["attach-session", "-t", &self.name])
-                .status()?;
-
-            if !status.success() {
-                bail!("Failed to attach to terminal session");
-            }
+        let status = super::nested::attach_command(&self.name).status()?;
+        if !status.success() {
+            bail!("Failed to attach to terminal session");
         }
 
         Ok(())
@@ -214,6 +197,7 @@ impl ContainerTerminalSession {
         let mut args = build_terminal_create_args(&self.name, working_dir, command, size);
         append_remain_on_exit_args(&mut args, &self.name);
         append_pane_base_index_args(&mut args, &self.name);
+        super::options::append_session_option_args(&mut args, &self.name);
 
         let output = Command::new("tmux").args(&args).output()?;
 
@@ -260,28 +244,9 @@ impl ContainerTerminalSession {
             bail!("Container terminal session does not exist: {}", self.name);
         }
 
-        if std::env::var("TMUX").is_ok() {
-            let status = Command::new("tmux")
-                .args(["switch-client", "-t", &self.name])
-                .status()?;
-
-            if !status.success() {
-                let status = Command::new("tmux")
-                    .args(["attach-session", "-t", &self.name])
-                    .status()?;
-
-                if !status.success() {
-                    bail!("Failed to attach to container terminal session");
-                }
-            }
-        } else {
-            let status = Command::new("tmux")
-                .args(["attach-session", "-t", &self.name])
-                .status()?;
-
-            if !status.success() {
-                bail!("Failed to attach to container terminal session");
-            }
+        let status = super::nested::attach_command(&self.name).status()?;
+        if !status.success() {
+            bail!("Failed to attach to container terminal session");
         }
 
         Ok(())
@@ -315,6 +280,260 @@ impl ContainerTerminalSession {
     }
 }
 
+/// Temporary session that runs an agent's login flow. It is created without
+/// remain-on-exit, so it disappears as soon as the login command finishes.
+pub struct LoginSession {
+    name: String,
+}
+
+impl LoginSession {
+    pub fn new(id: &str, title: &str) -> Result<Self> {
+        Ok(Self {
+            name: Self::generate_name(id, title),
+        })
+    }
+
+    pub fn generate_name(id: &str, title: &str) -> String {
+        let safe_title = sanitize_session_name(title);
+        format!("{}{}_{}", LOGIN_PREFIX, safe_title, truncate_id(id, 8))
+    }
+
+    pub fn exists(&self) -> bool {
+        if let Some(exists) = session_exists_from_cache(&self.name) {
+            return exists;
+        }
+
+        Command::new("tmux")
+            .args(["has-session", "-t", &self.name])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    pub fn create_with_size(
+        &self,
+        working_dir: &str,
+        command: &str,
+        size: Option<(u16, u16)>,
+    ) -> Result<()> {
+        let mut args = build_terminal_create_args(&self.name, working_dir, Some(command), size);
+        // When the session ends, move a client that switched here (from inside
+        // tmux) back to its previous session instead of detaching it.
+        args.extend([
+            ";".to_string(),
+            "set-option".to_string(),
+            "-t".to_string(),
+            self.name.clone(),
+            "detach-on-destroy".to_string(),
+            "off".to_string(),
+        ]);
+        super::options::append_session_option_args(&mut args, &self.name);
+
+        let output = Command::new("tmux").args(&args).output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("Failed to create login session: {}", stderr);
+        }
+
+        refresh_session_cache();
+
+        Ok(())
+    }
+
+    pub fn kill(&self) -> Result<()> {
+        if !self.exists() {
+            return Ok(());
+        }
+
+        if let Some(pane_pid) = process::get_pane_pid(&self.name) {
+            process::kill_process_tree(pane_pid);
+        }
+
+        let output = Command::new("tmux")
+            .args(["kill-session", "-t", &self.name])
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("Failed to kill login session: {}", stderr);
+        }
+
+        refresh_session_cache();
+
+        Ok(())
+    }
+
+    pub fn attach(&self) -> Result<()> {
+        if !self.exists() {
+            bail!("Login session does not exist: {}", self.name);
+        }
+
+        let status = super::nested::attach_command(&self.name).status()?;
+
+        if !status.success() {
+            bail!("Failed to attach to login session");
+        }
+
+        Ok(())
+    }
+}
+
+/// Companion session running one of a session's named commands, or its
+/// test watcher. The pane stays open after the command exits so its output
+/// can be reviewed and its exit status read.
+pub struct CommandSession {
+    name: String,
+}
+
+impl CommandSession {
+    pub fn new(id: &str, title: &str) -> Result<Self> {
+        Ok(Self {
+            name: Self::generate_name(id, title),
+        })
+    }
+
+    /// The companion session for the session's `hooks.test_watch` command
+    pub fn new_watch(id: &str, title: &str) -> Result<Self> {
+        Ok(Self {
+            name: Self::watch_name(id, title),
+        })
+    }
+
+    pub fn watch_name(id: &str, title: &str) -> String {
+        let safe_title = sanitize_session_name(title);
+        format!("{}{}_{}", WATCH_PREFIX, safe_title, truncate_id(id, 8))
+    }
+
+    pub fn generate_name(id: &str, title: &str) -> String {
+        let safe_title = sanitize_session_name(title);
+        format!("{}{}_{}", COMMAND_PREFIX, safe_title, truncate_id(id, 8))
+    }
+
+    /// The text the pane currently shows, without colors or scrollback.
+    /// Empty when the session is gone.
+    pub fn capture_screen(&self) -> String {
+        let target = format!("{}:^.0", self.name);
+        Command::new("tmux")
+            .args(["capture-pane", "-t", &target, "-p", "-J"])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn exists(&self) -> bool {
+        if let Some(exists) = session_exists_from_cache(&self.name) {
+            return exists;
+        }
+
+        Command::new("tmux")
+            .args(["has-session", "-t", &self.name])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    pub fn create_with_size(
+        &self,
+        working_dir: &str,
+        command: &str,
+        size: Option<(u16, u16)>,
+    ) -> Result<()> {
+        let mut args = build_terminal_create_args(&self.name, working_dir, Some(command), size);
+        append_remain_on_exit_args(&mut args, &self.name);
+        append_pane_base_index_args(&mut args, &self.name);
+        args.extend([
+            ";".to_string(),
+            "set-option".to_string(),
+            "-t".to_string(),
+            self.name.clone(),
+            "detach-on-destroy".to_string(),
+            "off".to_string(),
+        ]);
+        super::options::append_session_option_args(&mut args, &self.name);
+
+        let output = Command::new("tmux").args(&args).output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("Failed to create command session: {}", stderr);
+        }
+
+        refresh_session_cache();
+
+        Ok(())
+    }
+
+    /// Exit status of the command once it has finished, `None` while it is
+    /// still running or when the session is gone.
+    pub fn exit_status(&self) -> Option<i32> {
+        let target = format!("{}:^.0", self.name);
+        let output = Command::new("tmux")
+            .args([
+                "display-message",
+                "-t",
+                &target,
+                "-p",
+                "#{pane_dead} #{pane_dead_status}",
+            ])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        parse_dead_status(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    pub fn kill(&self) -> Result<()> {
+        if !self.exists() {
+            return Ok(());
+        }
+
+        if let Some(pane_pid) = process::get_pane_pid(&self.name) {
+            process::kill_process_tree(pane_pid);
+        }
+
+        let output = Command::new("tmux")
+            .args(["kill-session", "-t", &self.name])
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("Failed to kill command session: {}", stderr);
+        }
+
+        refresh_session_cache();
+
+        Ok(())
+    }
+
+    pub fn attach(&self) -> Result<()> {
+        if !self.exists() {
+            bail!("Command session does not exist: {}", self.name);
+        }
+
+        let status = super::nested::attach_command(&self.name).status()?;
+
+        if !status.success() {
+            bail!("Failed to attach to command session");
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse `#{pane_dead} #{pane_dead_status}`: the exit status once the pane
+/// has died.
+fn parse_dead_status(output: &str) -> Option<i32> {
+    let mut parts = output.split_whitespace();
+    if parts.next()? != "1" {
+        return None;
+    }
+    parts.next()?.parse().ok()
+}
+
 /// Build the argument list for tmux new-session command (terminal sessions).
 /// Extracted for testability.
 fn build_terminal_create_args(
@@ -385,6 +604,26 @@ mod tests {
         assert!(container_name.starts_with(CONTAINER_TERMINAL_PREFIX));
     }
 
+    #[test]
+    fn test_login_session_generate_name() {
+        let name = LoginSession::generate_name("abc123def456", "My Project");
+        assert!(name.starts_with(LOGIN_PREFIX));
+        assert_ne!(
+            name,
+            TerminalSession::generate_name("abc123def456", "My Project")
+        );
+    }
+
+    #[test]
+    fn test_command_session_name_and_dead_status() {
+        let name = CommandSession::generate_name("abc123def456", "My Project");
+        assert!(name.starts_with(COMMAND_PREFIX));
+        assert_eq!(parse_dead_status("1 0\n"), Some(0));
+        assert_eq!(parse_dead_status("1 101"), Some(101));
+        assert_eq!(parse_dead_status("0 "), None);
+        assert_eq!(parse_dead_status(""), None);
+    }
+
     #[test]
     fn test_build_terminal_create_args_without_size() {
         let args = build_terminal_create_args("test_terminal", "/tmp/work", None, None);
@@ -1,5 +1,6 @@
 //! tmux utility functions
 
+use anyhow::{bail, Result};
 use std::process::Command;
 
 pub fn strip_ansi(content: &str) -> String {
@@ -43,17 +44,24 @@ pub fn sanitize_session_name(name: &str) -> String {
 /// creation. Using pane-level (`-p`) avoids bleeding into user-created panes
 /// in the same session.
 ///
-/// Note: the `-p` (pane-level) flag requires tmux >= 3.0.
+/// The `-p` (pane-level) flag requires tmux >= 3.0; older versions get the
+/// window option instead.
 pub fn append_remain_on_exit_args(args: &mut Vec<String>, target: &str) {
-    args.extend([
-        ";".to_string(),
-        "set-option".to_string(),
-        "-p".to_string(),
-        "-t".to_string(),
-        target.to_string(),
-        "remain-on-exit".to_string(),
-        "on".to_string(),
-    ]);
+    let pane_options = super::version::capabilities().pane_options();
+    args.extend(remain_on_exit_args(target, pane_options));
+}
+
+fn remain_on_exit_args(target: &str, pane_options: bool) -> Vec<String> {
+    let set = if pane_options {
+        ["set-option", "-p"].as_slice()
+    } else {
+        ["set-window-option"].as_slice()
+    };
+    std::iter::once(";")
+        .chain(set.iter().copied())
+        .chain(["-t", target, "remain-on-exit", "on"])
+        .map(String::from)
+        .collect()
 }
 
 /// Append `; set-option -t <target> pane-base-index 0` to an in-flight tmux
@@ -71,6 +79,30 @@ pub fn append_pane_base_index_args(args: &mut Vec<String>, target: &str) {
     ]);
 }
 
+/// Set the name of `session_name`'s window, or with `None` let tmux name
+/// it after the command running in it again.
+pub fn set_window_name(session_name: &str, name: Option<&str>) -> Result<()> {
+    let args = match name {
+        Some(name) => vec!["rename-window", "-t", session_name, name],
+        None => vec![
+            "set-window-option",
+            "-t",
+            session_name,
+            "automatic-rename",
+            "on",
+        ],
+    };
+    let output = Command::new("tmux").args(&args).output()?;
+    if !output.status.success() {
+        bail!(
+            "tmux {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
 pub fn is_pane_dead(session_name: &str) -> bool {
     // Use `^.0` to target the first window's first pane regardless of
     // base-index or which pane is active, so the check always hits the
@@ -126,6 +158,18 @@ pub fn is_pane_running_shell(session_name: &str) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_remain_on_exit_args_by_version() {
+        assert_eq!(
+            remain_on_exit_args("s", true),
+            [";", "set-option", "-p", "-t", "s", "remain-on-exit", "on"]
+        );
+        assert_eq!(
+            remain_on_exit_args("s", false),
+            [";", "set-window-option", "-t", "s", "remain-on-exit", "on"]
+        );
+    }
+
     #[test]
     fn test_sanitize_session_name() {
         assert_eq!(sanitize_session_name("my-project"), "my-project");
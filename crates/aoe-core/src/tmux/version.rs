@@ -0,0 +1,153 @@
+//! tmux version detection
+//!
+//! The version is read once from `tmux -V`. Commands whose flags changed
+//! between releases ask `capabilities()` which form to use, and releases
+//! older than `MINIMUM` are refused up front with a clear error instead of
+//! failing partway through creating a session.
+
+use anyhow::{bail, Result};
+use std::fmt;
+use std::process::Command;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TmuxVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+/// Oldest tmux aoe supports
+pub const MINIMUM: TmuxVersion = TmuxVersion::new(2, 6);
+
+/// Pane options (`set-option -p`). Before this, `remain-on-exit` is a
+/// window option.
+const PANE_OPTIONS: TmuxVersion = TmuxVersion::new(3, 0);
+
+/// `window-size` as a window option, with the `latest` policy
+const WINDOW_SIZE_OPTION: TmuxVersion = TmuxVersion::new(3, 1);
+
+impl TmuxVersion {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    /// Parse `tmux -V` output such as `tmux 3.3a`, `tmux 3.4-rc` or
+    /// `tmux next-3.5`. Builds without a version number (`tmux master`,
+    /// `tmux openbsd-7.4`) give `None`.
+    pub fn parse(output: &str) -> Option<Self> {
+        let version = output.trim().strip_prefix("tmux ")?;
+        let version = version.strip_prefix("next-").unwrap_or(version);
+        if version.starts_with("openbsd") {
+            return None;
+        }
+        let (major, rest) = version.split_once('.')?;
+        let minor: String = rest.chars().take_while(char::is_ascii_digit).collect();
+        Some(Self::new(major.parse().ok()?, minor.parse().ok()?))
+    }
+}
+
+impl fmt::Display for TmuxVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// What the installed tmux supports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// `None` for tmux missing or built without a version number
+    pub version: Option<TmuxVersion>,
+}
+
+impl Capabilities {
+    /// Unversioned builds are development or OS builds, assumed current
+    fn at_least(&self, required: TmuxVersion) -> bool {
+        self.version.map_or(true, |v| v >= required)
+    }
+
+    pub fn pane_options(&self) -> bool {
+        self.at_least(PANE_OPTIONS)
+    }
+
+    pub fn window_size_option(&self) -> bool {
+        self.at_least(WINDOW_SIZE_OPTION)
+    }
+
+    /// An error naming the installed and required versions if tmux is too old
+    pub fn check_supported(&self) -> Result<()> {
+        match self.version {
+            Some(version) if version < MINIMUM => bail!(
+                "tmux {} is too old; aoe needs tmux {} or newer\n\
+                 Tip: Upgrade tmux with your package manager (e.g. brew upgrade tmux)",
+                version,
+                MINIMUM
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The installed tmux version, detected on first use
+pub fn detected() -> Option<TmuxVersion> {
+    static VERSION: OnceLock<Option<TmuxVersion>> = OnceLock::new();
+    *VERSION.get_or_init(|| {
+        let output = Command::new("tmux").arg("-V").output().ok()?;
+        TmuxVersion::parse(&String::from_utf8_lossy(&output.stdout))
+    })
+}
+
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        version: detected(),
+    }
+}
+
+/// Fail early if the installed tmux is older than aoe supports
+pub fn check_supported() -> Result<()> {
+    capabilities().check_supported()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version_strings() {
+        assert_eq!(
+            TmuxVersion::parse("tmux 3.3a\n"),
+            Some(TmuxVersion::new(3, 3))
+        );
+        assert_eq!(
+            TmuxVersion::parse("tmux 3.4-rc"),
+            Some(TmuxVersion::new(3, 4))
+        );
+        assert_eq!(
+            TmuxVersion::parse("tmux next-3.5"),
+            Some(TmuxVersion::new(3, 5))
+        );
+        assert_eq!(
+            TmuxVersion::parse("tmux 2.10"),
+            Some(TmuxVersion::new(2, 10))
+        );
+        assert_eq!(TmuxVersion::parse("tmux master"), None);
+        assert_eq!(TmuxVersion::parse("tmux openbsd-7.4"), None);
+        assert_eq!(TmuxVersion::parse(""), None);
+    }
+
+    #[test]
+    fn test_capabilities_by_version() {
+        let caps = |version| Capabilities { version };
+
+        let old = caps(Some(TmuxVersion::new(2, 1)));
+        let err = old.check_supported().unwrap_err().to_string();
+        assert!(err.contains("tmux 2.1 is too old"), "{}", err);
+
+        let pre_pane = caps(Some(TmuxVersion::new(2, 9)));
+        assert!(pre_pane.check_supported().is_ok());
+        assert!(!pre_pane.pane_options());
+
+        assert!(caps(Some(TmuxVersion::new(3, 0))).pane_options());
+        assert!(caps(None).pane_options());
+        assert!(caps(None).check_supported().is_ok());
+    }
+}
@@ -0,0 +1,113 @@
+//! Window sizing for aoe sessions
+//!
+//! A window shrinks to fit a smaller terminal attached to it, and tmux keeps
+//! that size after the terminal detaches, so the agent (and the preview aoe
+//! captures from it) would stay cramped. Each aoe session remembers the size
+//! it was created with as its `default-size`. While clients are attached the
+//! window follows them according to `tmux.window_size`; when the last one
+//! detaches a session hook resizes the window back to the default.
+
+use anyhow::{bail, Result};
+use std::process::Command;
+
+use crate::session::TmuxWindowSize;
+
+/// Runs when a client detaches from the session. `resize-window -A` with no
+/// clients left falls back to `default-size`.
+const RESTORE_ON_DETACH: &str = "if-shell -F '#{session_attached}' '' 'resize-window -A'";
+
+/// Apply `policy` to a newly created session, keeping its current size as
+/// the one to return to. Older tmux, without per-window sizing, is left as
+/// it is.
+pub fn apply(session_name: &str, policy: TmuxWindowSize) -> Result<()> {
+    if !super::version::capabilities().window_size_option() {
+        return Ok(());
+    }
+    let output = Command::new("tmux")
+        .args([
+            "display-message",
+            "-t",
+            session_name,
+            "-p",
+            "#{window_width}x#{window_height}",
+        ])
+        .output()?;
+    if !output.status.success() {
+        bail!(
+            "Failed to read window size: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let size = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let args = build_args(session_name, &size, policy);
+    let output = Command::new("tmux").args(&args).output()?;
+    if !output.status.success() {
+        bail!(
+            "Failed to set window size policy: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+fn build_args(session_name: &str, size: &str, policy: TmuxWindowSize) -> Vec<String> {
+    let mut commands = vec![
+        vec!["set-option", "-t", session_name, "default-size", size],
+        vec![
+            "set-window-option",
+            "-t",
+            session_name,
+            "window-size",
+            policy.as_str(),
+        ],
+    ];
+    // Resizing on detach switches the window to manual sizing, so the
+    // policy is put back whenever a client attaches
+    let reapply = format!("set-window-option window-size {}", policy.as_str());
+    if policy != TmuxWindowSize::Manual {
+        commands.push(vec![
+            "set-hook",
+            "-t",
+            session_name,
+            "client-attached",
+            &reapply,
+        ]);
+        commands.push(vec![
+            "set-hook",
+            "-t",
+            session_name,
+            "client-detached",
+            RESTORE_ON_DETACH,
+        ]);
+    }
+
+    let mut args = Vec::new();
+    for command in commands {
+        if !args.is_empty() {
+            args.push(";".to_string());
+        }
+        args.extend(command.into_iter().map(str::to_string));
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_hooks_restore_size_on_detach() {
+        let args = build_args("aoe_s", "200x50", TmuxWindowSize::Largest);
+        let joined = args.join(" ");
+        assert!(joined.starts_with("set-option -t aoe_s default-size 200x50 ; "));
+        assert!(joined.contains("set-window-option -t aoe_s window-size largest"));
+        assert!(args.contains(&"set-window-option window-size largest".to_string()));
+        assert!(args.contains(&RESTORE_ON_DETACH.to_string()));
+
+        // Manual never follows clients, so there is nothing to restore
+        let args = build_args("aoe_s", "200x50", TmuxWindowSize::Manual);
+        assert!(!args.contains(&"set-hook".to_string()));
+        assert_eq!(args.last().map(String::as_str), Some("manual"));
+    }
+}
@@ -0,0 +1,242 @@
+//! System tray indicator
+//!
+//! With `notifications.tray` on, the TUI starts `aoe tray run` as a helper
+//! process. On Linux the helper drives a `yad --notification` icon whose
+//! tooltip counts the sessions with pending inbox items and which focuses
+//! the terminal running aoe when clicked. macOS has no tray API a command
+//! line tool can use, so there the menu bar item comes from a SwiftBar or
+//! xbar plugin running `aoe tray status --format xbar`.
+//!
+//! Clicking focuses the terminal through `notifications.tray_focus_command`,
+//! or else a command worked out from the environment the TUI started in and
+//! saved to `tray_focus` in the app directory.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+
+use crate::notifications::NotificationConfig;
+use crate::session::get_app_dir;
+use crate::session::inbox::Inbox;
+
+/// How often the helper re-reads the inbox
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Sessions with pending inbox items, as (title, reasons) in title order.
+pub fn attention() -> Vec<(String, Vec<&'static str>)> {
+    let inbox = Inbox::load();
+    let mut sessions: BTreeMap<&str, (String, Vec<&'static str>)> = BTreeMap::new();
+    for item in inbox.items.iter().filter(|i| i.is_pending()) {
+        let entry = sessions
+            .entry(item.session_id.as_str())
+            .or_insert_with(|| (item.title.clone(), Vec::new()));
+        entry.0 = item.title.clone();
+        if !entry.1.contains(&item.reason.label()) {
+            entry.1.push(item.reason.label());
+        }
+    }
+    let mut list: Vec<_> = sessions.into_values().collect();
+    list.sort_by(|a, b| a.0.cmp(&b.0));
+    list
+}
+
+pub fn summary(count: usize) -> String {
+    match count {
+        0 => "No sessions need attention".to_string(),
+        1 => "1 session needs attention".to_string(),
+        n => format!("{} sessions need attention", n),
+    }
+}
+
+/// SwiftBar/xbar plugin output: the count in the menu bar, then a menu of
+/// the sessions, each focusing the terminal when chosen.
+pub fn xbar_output(attention: &[(String, Vec<&str>)], aoe: &str) -> String {
+    let focus = format!("bash=\"{}\" param1=tray param2=focus terminal=false", aoe);
+    let mut out = if attention.is_empty() {
+        "aoe\n".to_string()
+    } else {
+        format!("aoe {}\n", attention.len())
+    };
+    out.push_str("---\n");
+    out.push_str(&format!("{} | {}\n", summary(attention.len()), focus));
+    for (title, reasons) in attention {
+        out.push_str(&format!(
+            "{}: {} | {}\n",
+            title.replace('|', "/"),
+            reasons.join(", "),
+            focus
+        ));
+    }
+    out
+}
+
+fn focus_file() -> Result<PathBuf> {
+    Ok(get_app_dir()?.join("tray_focus"))
+}
+
+/// A command that brings the current terminal to the front, if one can be
+/// worked out from the environment.
+fn detect_focus_command() -> Option<String> {
+    if cfg!(target_os = "macos") {
+        if let Ok(bundle) = std::env::var("__CFBundleIdentifier") {
+            return Some(format!("open -b '{}'", bundle));
+        }
+        let app = match std::env::var("TERM_PROGRAM").ok()?.as_str() {
+            "Apple_Terminal" => "Terminal",
+            "iTerm.app" => "iTerm",
+            "WezTerm" => "WezTerm",
+            "ghostty" => "Ghostty",
+            _ => return None,
+        };
+        return Some(format!("open -a '{}'", app));
+    }
+    let window: u64 = std::env::var("WINDOWID").ok()?.parse().ok()?;
+    if crate::sound::which_command("xdotool").is_ok() {
+        Some(format!("xdotool windowactivate {}", window))
+    } else if crate::sound::which_command("wmctrl").is_ok() {
+        Some(format!("wmctrl -ia {:#x}", window))
+    } else {
+        None
+    }
+}
+
+/// Remember how to focus the terminal the TUI runs in, for `aoe tray focus`.
+pub fn record_terminal() {
+    let Some(command) = detect_focus_command() else {
+        return;
+    };
+    if let Err(e) = focus_file().and_then(|path| Ok(fs::write(path, command)?)) {
+        tracing::warn!("Failed to save tray focus command: {}", e);
+    }
+}
+
+/// Bring the terminal running aoe to the front.
+pub fn focus(config: &NotificationConfig) -> Result<()> {
+    let command = config
+        .tray_focus_command
+        .clone()
+        .filter(|c| !c.trim().is_empty())
+        .or_else(|| focus_file().ok().and_then(|p| fs::read_to_string(p).ok()))
+        .filter(|c| !c.trim().is_empty())
+        .context("Don't know how to focus the terminal; set notifications.tray_focus_command")?;
+    let status = Command::new("sh").args(["-c", &command]).status()?;
+    if !status.success() {
+        bail!("'{}' failed", command);
+    }
+    Ok(())
+}
+
+/// Run the tray icon until it is closed, or until the process that started
+/// the helper exits when `exit_with_parent` is set. Linux only.
+pub fn run(exit_with_parent: bool) -> Result<()> {
+    if cfg!(target_os = "macos") {
+        bail!(
+            "The tray icon on macOS comes from a SwiftBar or xbar plugin\n\
+             Tip: Add a plugin script that runs `aoe tray status --format xbar`"
+        );
+    }
+    if crate::sound::which_command("yad").is_err() {
+        bail!("The tray icon needs yad\nTip: Install it with your package manager, e.g. apt install yad");
+    }
+
+    let aoe = std::env::current_exe()?;
+    let mut child = Command::new("yad")
+        .args([
+            "--notification",
+            "--listen",
+            "--image=utilities-terminal",
+            "--text=Agent of Empires",
+        ])
+        .arg(format!("--command={} tray focus", aoe.display()))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to start yad")?;
+    let mut stdin = child.stdin.take().context("yad has no stdin")?;
+
+    let parent = std::os::unix::process::parent_id();
+    let mut last = None;
+    loop {
+        if child.try_wait()?.is_some() {
+            return Ok(());
+        }
+        if exit_with_parent && std::os::unix::process::parent_id() != parent {
+            let _ = writeln!(stdin, "quit");
+            let _ = child.kill();
+            return Ok(());
+        }
+
+        let count = attention().len();
+        if last != Some(count) {
+            let icon = if count == 0 {
+                "utilities-terminal"
+            } else {
+                "dialog-warning"
+            };
+            let update = writeln!(stdin, "icon:{}", icon)
+                .and_then(|_| writeln!(stdin, "tooltip:aoe: {}", summary(count)))
+                .and_then(|_| stdin.flush());
+            if update.is_err() {
+                return Ok(());
+            }
+            last = Some(count);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Start the helper if the tray is enabled and this platform has one. The
+/// helper exits, closing the icon, once the TUI does.
+pub fn start_helper(config: &NotificationConfig) {
+    if !config.tray {
+        return;
+    }
+    record_terminal();
+    if cfg!(target_os = "macos") || crate::sound::which_command("yad").is_err() {
+        return;
+    }
+    let Ok(aoe) = std::env::current_exe() else {
+        return;
+    };
+    if let Err(e) = Command::new(aoe)
+        .args(["tray", "run", "--exit-with-parent"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        tracing::warn!("Failed to start tray helper: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xbar_output() {
+        let out = xbar_output(&[], "/usr/bin/aoe");
+        assert!(out.starts_with("aoe\n---\nNo sessions need attention | bash=\"/usr/bin/aoe\""));
+
+        let attention = vec![
+            ("api | fix".to_string(), vec!["waiting"]),
+            ("docs".to_string(), vec!["error", "finished"]),
+        ];
+        let out = xbar_output(&attention, "aoe");
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "aoe 2");
+        assert_eq!(
+            lines[2].split(" | ").next(),
+            Some("2 sessions need attention")
+        );
+        assert!(lines[3].starts_with("api / fix: waiting | "));
+        assert!(lines[4].starts_with("docs: error, finished | "));
+        assert!(lines[4].ends_with("param1=tray param2=focus terminal=false"));
+    }
+}
@@ -0,0 +1,159 @@
+//! Reference client for the `aoe ipc` protocol
+//!
+//! Start the server with `aoe ipc`, then:
+//!
+//! ```text
+//! cargo run --example ipc_client -- list
+//! cargo run --example ipc_client -- new /path/to/project "fix login bug"
+//! cargo run --example ipc_client -- send <session> "run the tests"
+//! cargo run --example ipc_client -- watch
+//! ```
+//!
+//! Set `AOE_IPC_SOCKET` to talk to a server started with `--socket` or for
+//! another profile (`aoe -p work ipc --print-socket` prints the path).
+//!
+//! Editor plugins can follow the same steps: connect, read the `hello` line,
+//! write one JSON request per line, and read lines until the response with
+//! the matching `id` arrives. Lines with an `event` field are notifications.
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+use serde_json::{json, Value};
+
+struct Client {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+    next_id: u64,
+}
+
+impl Client {
+    fn connect(socket: &Path) -> std::io::Result<(Self, Value)> {
+        let writer = UnixStream::connect(socket)?;
+        let mut client = Self {
+            reader: BufReader::new(writer.try_clone()?),
+            writer,
+            next_id: 1,
+        };
+        let hello = client.read_message()?;
+        Ok((client, hello))
+    }
+
+    fn read_message(&mut self) -> std::io::Result<Value> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        serde_json::from_str(&line).map_err(std::io::Error::other)
+    }
+
+    /// Send a request and wait for its response. Returns the `result` on
+    /// success and the `error` message on failure.
+    fn call(&mut self, method: &str, params: Value) -> std::io::Result<Result<Value, String>> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let request = json!({ "id": id, "method": method, "params": params });
+        writeln!(self.writer, "{}", request)?;
+
+        loop {
+            let message = self.read_message()?;
+            if message["id"] != id {
+                // A notification for an earlier subscription
+                continue;
+            }
+            return Ok(if message["ok"] == true {
+                Ok(message["result"].clone())
+            } else {
+                Err(message["error"]
+                    .as_str()
+                    .unwrap_or("unknown error")
+                    .to_string())
+            });
+        }
+    }
+}
+
+fn socket_path() -> PathBuf {
+    if let Some(path) = std::env::var_os("AOE_IPC_SOCKET") {
+        return PathBuf::from(path);
+    }
+    let output = Command::new("aoe")
+        .args(["ipc", "--print-socket"])
+        .output()
+        .expect("failed to run `aoe ipc --print-socket`; set AOE_IPC_SOCKET instead");
+    PathBuf::from(String::from_utf8_lossy(&output.stdout).trim())
+}
+
+fn print_session(session: &Value) {
+    println!(
+        "{:<10} {:<30} {}",
+        session["status"].as_str().unwrap_or("?"),
+        session["title"].as_str().unwrap_or("?"),
+        session["path"].as_str().unwrap_or("?"),
+    );
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let socket = socket_path();
+    let (mut client, hello) = match Client::connect(&socket) {
+        Ok(connected) => connected,
+        Err(e) => {
+            eprintln!("Could not connect to {}: {}", socket.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if hello["protocol"] != 1 {
+        eprintln!("Unsupported protocol version: {}", hello["protocol"]);
+        return ExitCode::FAILURE;
+    }
+
+    let (method, params) = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] | ["list"] => ("list", Value::Null),
+        ["watch"] => ("status-subscribe", Value::Null),
+        ["new", path] => ("new", json!({ "path": path })),
+        ["new", path, prompt] => ("new", json!({ "path": path, "prompt": prompt })),
+        ["send", session, message] => ("send", json!({ "session": session, "message": message })),
+        _ => {
+            eprintln!(
+                "Usage: ipc_client [list | watch | new <path> [prompt] | send <session> <message>]"
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match client.call(method, params) {
+        Ok(Ok(result)) => result,
+        Ok(Err(message)) => {
+            eprintln!("Error: {}", message);
+            return ExitCode::FAILURE;
+        }
+        Err(e) => {
+            eprintln!("Connection failed: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match method {
+        "list" | "status-subscribe" => {
+            for session in result.as_array().into_iter().flatten() {
+                print_session(session);
+            }
+        }
+        "new" => print_session(&result),
+        _ => println!("Sent"),
+    }
+
+    if method == "status-subscribe" {
+        while let Ok(event) = client.read_message() {
+            match event["event"].as_str() {
+                Some("status") => print_session(&event["session"]),
+                Some("removed") => println!("removed    {}", event["id"]),
+                _ => {}
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -4,17 +4,38 @@ use anyhow::{bail, Result};
 use clap::Args;
 use std::path::{Path, PathBuf};
 
+use crate::agents::Provider;
 use crate::containers::{self, ContainerRuntimeInterface};
+use crate::git::clone;
+use crate::git::issue::{self, IssueRef};
+use crate::session::budget;
 use crate::session::builder;
+use crate::session::host_sandbox::HostSandbox;
+use crate::session::journal;
+use crate::session::locks;
+use crate::session::orchestrator;
+use crate::session::providers;
+use crate::session::proxy::{self, Proxy};
 use crate::session::repo_config;
-use crate::session::{civilizations, resolve_config, GroupTree, Instance, SandboxInfo, Storage};
+use crate::session::resource_limits::ResourceLimits;
+use crate::session::stats;
+use crate::session::timeline;
+use crate::session::{
+    civilizations, resolve_config, BudgetAction, Config, GroupTree, HostSandboxProfile, Instance,
+    IssueInfo, SandboxInfo, Storage,
+};
 
 #[derive(Args)]
 pub struct AddArgs {
-    /// Project directory (defaults to current directory)
+    /// Project directory (defaults to current directory), or a git URL to
+    /// clone into `worktree.clone_dir` first
     #[arg(default_value = ".")]
     path: PathBuf,
 
+    /// Project directory, as an alternative to the positional argument
+    #[arg(long = "path", value_name = "DIR", conflicts_with = "path")]
+    path_flag: Option<PathBuf>,
+
     /// Session title (defaults to folder name)
     #[arg(short = 't', long)]
     title: Option<String>,
@@ -23,10 +44,21 @@ pub struct AddArgs {
     #[arg(short = 'g', long)]
     group: Option<String>,
 
+    /// Emoji or nerd-font glyph shown before the title in the list and tmux
+    /// window name. Defaults to `session.icon` from config
+    #[arg(long, value_name = "ICON")]
+    icon: Option<String>,
+
     /// Command to run (e.g., 'claude' or any other supported agent)
     #[arg(short = 'c', long = "cmd")]
     command: Option<String>,
 
+    /// Provider the agent talks to (anthropic, openai, google, mistral,
+    /// github, cursor, local, or other), when it is not the agent's usual
+    /// one. Counts toward `session.provider_limits`
+    #[arg(long, value_name = "PROVIDER", value_parser = parse_provider)]
+    provider: Option<Provider>,
+
     /// Parent session (creates sub-session, inherits group)
     #[arg(short = 'P', long)]
     parent: Option<String>,
@@ -43,6 +75,10 @@ pub struct AddArgs {
     #[arg(short = 'b', long = "new-branch")]
     create_branch: bool,
 
+    /// Branch, tag or commit the new branch starts from (use with --new-branch)
+    #[arg(long, value_name = "REF", requires = "create_branch")]
+    base: Option<String>,
+
     /// Additional repositories for multi-repo workspace (use with --worktree)
     #[arg(long = "repo", short = 'r')]
     extra_repos: Vec<PathBuf>,
@@ -70,17 +106,111 @@ pub struct AddArgs {
     /// Override the agent binary command
     #[arg(long)]
     cmd_override: Option<String>,
+
+    /// GitHub or GitLab issue URL: names the session after the issue, passes
+    /// its title and body as the agent's first prompt, and links the session to it
+    #[arg(long, value_name = "URL")]
+    issue: Option<String>,
+
+    /// The agent's first prompt, or `-` to read it from stdin
+    #[arg(long, value_name = "TEXT", conflicts_with_all = ["issue", "prompt_file"])]
+    prompt: Option<String>,
+
+    /// Read the agent's first prompt from a file
+    #[arg(long, value_name = "FILE", conflicts_with = "issue")]
+    prompt_file: Option<PathBuf>,
+
+    /// Named lock this session holds (repeatable). Sessions sharing a lock
+    /// run one at a time. Defaults to `session.locks` from config
+    #[arg(long = "lock", value_name = "NAME")]
+    locks: Vec<String>,
+
+    /// Run the agent under a restricted host sandbox: `workdir` limits writes
+    /// to the project, `isolated` also hides the home directory. Defaults to
+    /// `sandbox.host_profile` from config
+    #[arg(
+        long = "host-sandbox",
+        value_name = "PROFILE",
+        value_parser = ["off", "workdir", "isolated"]
+    )]
+    host_sandbox: Option<String>,
+
+    /// Block network access inside the host sandbox
+    #[arg(long = "no-network")]
+    no_network: bool,
+
+    /// Route the agent's traffic through this proxy (http://, https://, or
+    /// socks5:// URL). Defaults to `session.proxy` from config
+    #[arg(long, value_name = "URL", conflicts_with = "no_proxy")]
+    proxy: Option<String>,
+
+    /// Don't use the proxy from config for this session
+    #[arg(long = "no-proxy")]
+    no_proxy: bool,
+
+    /// Clone only the latest commit when the path is a git URL. Defaults to
+    /// `worktree.shallow_clone` from config
+    #[arg(long)]
+    shallow: bool,
+
+    /// Make this an orchestrator: its agent is told how to create and drive
+    /// worker sessions with aoe, and its aoe commands target this profile
+    #[arg(long, conflicts_with_all = ["parent", "sandbox", "sandbox_image"])]
+    orchestrator: bool,
+}
+
+fn parse_provider(name: &str) -> Result<Provider, String> {
+    Provider::from_name(name).ok_or_else(|| {
+        let known: Vec<&str> = Provider::ALL.iter().map(|p| p.name()).collect();
+        format!("one of {}", known.join(", "))
+    })
+}
+
+/// The prompt from `--prompt` (`-` reads stdin) or `--prompt-file`
+fn read_prompt(args: &AddArgs) -> Result<Option<String>> {
+    let prompt = match (&args.prompt, &args.prompt_file) {
+        (Some(text), _) if text == "-" => {
+            use std::io::{IsTerminal, Read};
+            if std::io::stdin().is_terminal() {
+                bail!("--prompt - reads the prompt from stdin, but nothing is piped in\nTip: cat spec.md | aoe add . --prompt -");
+            }
+            let mut buf = String::new();
+            std::io::stdin().read_to_string(&mut buf)?;
+            buf
+        }
+        (Some(text), _) => text.clone(),
+        (None, Some(file)) => std::fs::read_to_string(file)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file.display(), e))?,
+        (None, None) => return Ok(None),
+    };
+    let prompt = prompt.trim();
+    if prompt.is_empty() {
+        bail!("The prompt is empty");
+    }
+    Ok(Some(prompt.to_string()))
 }
 
 pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
-    let mut path = if args.path.as_os_str() == "." {
+    let arg_path = args.path_flag.as_ref().unwrap_or(&args.path);
+    let clone_url = arg_path
+        .to_str()
+        .filter(|p| clone::is_clone_url(p))
+        .map(|p| p.trim().to_string());
+    let mut path = if clone_url.is_some() {
+        PathBuf::new()
+    } else if arg_path.as_os_str() == "." {
         std::env::current_dir()?
     } else {
-        args.path.canonicalize()?
+        arg_path.canonicalize()?
     };
 
-    if !path.is_dir() {
-        bail!("Path is not a directory: {}", path.display());
+    if clone_url.is_none() {
+        if args.shallow {
+            bail!("--shallow only applies when the path is a git URL");
+        }
+        if !path.is_dir() {
+            bail!("Path is not a directory: {}", path.display());
+        }
     }
 
     if !args.extra_repos.is_empty() && args.worktree_branch.is_none() {
@@ -89,6 +219,61 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
 
     let config = resolve_config(profile).unwrap_or_default();
 
+    if config.session.budget_action == BudgetAction::Block
+        && (config.session.daily_budget > 0 || config.session.weekly_budget > 0)
+    {
+        let live_cost: f64 = Storage::new(profile)?
+            .load()?
+            .iter()
+            .filter_map(stats::pane_cost)
+            .sum();
+        if let Some(reason) = budget::check_profile(profile, live_cost)?.block_reason() {
+            bail!(
+                "Over budget: {}\nTip: Raise daily_budget or weekly_budget in the settings, or set budget_action to \"warn\"",
+                reason
+            );
+        }
+    }
+
+    if let Some(url) = &clone_url {
+        path = clone_repository(url, &config, args.shallow)?;
+    }
+
+    // Checked before any worktree exists that would need cleaning up
+    if let Some(icon) = &args.icon {
+        crate::session::validate_icon(icon).map_err(anyhow::Error::msg)?;
+    }
+    let proxy = {
+        let mut session_config = repo_config::resolve_config_with_repo(profile, &path)
+            .map(|c| c.session)
+            .unwrap_or_else(|_| config.session.clone());
+        if let Some(url) = &args.proxy {
+            proxy::validate_url(url).map_err(anyhow::Error::msg)?;
+            session_config.proxy = Some(url.clone());
+        }
+        Proxy::from_config(&session_config).filter(|_| !args.no_proxy)
+    };
+    if let Some(proxy) = &proxy {
+        proxy.check()?;
+    }
+
+    let prompt = read_prompt(&args)?;
+    let prompt_from_stdin = args.prompt.as_deref() == Some("-");
+
+    // Fetch the issue before creating any worktree so a bad URL leaves nothing behind
+    let issue = if let Some(url) = &args.issue {
+        let reference = IssueRef::parse(url).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Not a GitHub or GitLab issue URL: {}\n\
+                 Tip: Use e.g. https://github.com/org/repo/issues/123",
+                url
+            )
+        })?;
+        Some(issue::fetch_issue(&reference)?)
+    } else {
+        None
+    };
+
     let mut worktree_info_opt = None;
     let mut workspace_info_opt = None;
 
@@ -99,10 +284,22 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
 
         let branch = branch_raw.trim();
 
-        if !args.extra_repos.is_empty() {
+        let extra_repos = if args.extra_repos.is_empty() {
+            let repos = repo_config::resolve_config_with_repo(profile, &path)
+                .map(|c| c.worktree.repos)
+                .unwrap_or_else(|_| config.worktree.repos.clone());
+            builder::configured_workspace_repos(&path, &repos)?
+        } else {
+            args.extra_repos.clone()
+        };
+
+        if !extra_repos.is_empty() {
+            if args.base.is_some() {
+                bail!("--base cannot be used with a multi-repo workspace\nTip: Each repository branches from its own HEAD");
+            }
             let ws_result = builder::create_workspace(
                 &path,
-                &args.extra_repos,
+                &extra_repos,
                 branch,
                 args.create_branch,
                 &config.worktree.workspace_path_template,
@@ -126,7 +323,11 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
             }
 
             let main_repo_path = GitWorktree::find_main_repo(&path)?;
-            let git_wt = GitWorktree::new(main_repo_path.clone())?;
+            let repo_worktree = repo_config::resolve_config_with_repo(profile, &path)
+                .map(|c| c.worktree)
+                .unwrap_or_else(|_| config.worktree.clone());
+            let git_wt = GitWorktree::new(main_repo_path.clone())?
+                .with_sparse_paths(repo_worktree.sparse_paths);
 
             let session_id = uuid::Uuid::new_v4().to_string();
             let session_id_short = &session_id[..8];
@@ -149,7 +350,15 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
             }
 
             println!("Creating worktree at: {}", worktree_path.display());
-            git_wt.create_worktree(branch, &worktree_path, args.create_branch)?;
+            builder::create_session_worktree(
+                &git_wt,
+                &worktree_path,
+                branch,
+                args.create_branch,
+                args.base.as_deref(),
+                repo_worktree.pool_size,
+                profile,
+            )?;
 
             path = worktree_path;
 
@@ -158,6 +367,7 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
                 main_repo_path: main_repo_path.to_string_lossy().to_string(),
                 managed_by_aoe: true,
                 created_at: Utc::now(),
+                base_commit: GitWorktree::head_commit(&path).ok(),
             });
 
             println!("✓ Worktree created successfully");
@@ -191,6 +401,8 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
             return Ok(());
         }
         trimmed_title.to_string()
+    } else if let Some(issue) = &issue {
+        issue.session_title()
     } else {
         let existing_titles: Vec<&str> = instances.iter().map(|i| i.title.as_str()).collect();
         civilizations::generate_random_title(&existing_titles)
@@ -216,16 +428,7 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
             instance.command = cmd.clone();
         }
     } else {
-        // Use default_tool from resolved config, then first available tool, then "claude"
-        let available_tools = crate::tmux::AvailableTools::detect();
-        instance.tool = config
-            .session
-            .default_tool
-            .as_deref()
-            .and_then(crate::agents::resolve_tool_name)
-            .or_else(|| available_tools.available_list().first().copied())
-            .unwrap_or("claude")
-            .to_string();
+        instance.tool = default_tool(&config);
     }
 
     // Apply set_default_command for agents that need it (e.g., opencode, codex)
@@ -246,6 +449,36 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
 
     instance.yolo_mode = args.yolo || config.session.yolo_mode_default;
 
+    if let Some(issue) = &issue {
+        instance.issue_info = Some(IssueInfo {
+            provider: issue.reference.provider,
+            url: issue.url.clone(),
+            number: issue.reference.number,
+            title: issue.title.clone(),
+        });
+        if crate::agents::get_agent(&instance.tool).is_some_and(|a| a.prompt_flag.is_some()) {
+            instance.initial_prompt = Some(issue.to_prompt());
+            instance.original_prompt = instance.initial_prompt.clone();
+        } else {
+            eprintln!(
+                "Warning: '{}' does not accept an initial prompt; the issue is linked but not sent",
+                instance.tool
+            );
+        }
+    }
+
+    if let Some(prompt) = prompt {
+        if crate::agents::get_agent(&instance.tool).is_some_and(|a| a.prompt_flag.is_some()) {
+            instance.initial_prompt = Some(prompt);
+            instance.original_prompt = instance.initial_prompt.clone();
+        } else {
+            eprintln!(
+                "Warning: '{}' does not accept an initial prompt; the prompt is not sent",
+                instance.tool
+            );
+        }
+    }
+
     // Apply extra_args and command override: CLI flags take priority, then config defaults
     if let Some(ref extra) = args.extra_args {
         instance.extra_args = extra.clone();
@@ -263,6 +496,37 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
         }
     }
 
+    let repo_resolved =
+        repo_config::resolve_config_with_repo(profile, &path).unwrap_or(config.clone());
+    instance.locks = if args.locks.is_empty() {
+        locks::normalize(repo_resolved.session.locks.clone())
+    } else {
+        locks::normalize(args.locks.clone())
+    };
+    instance.fallback_tool = repo_resolved.session.fallback_tool.clone();
+    instance.provider = args.provider;
+    instance.icon = args
+        .icon
+        .clone()
+        .or_else(|| repo_resolved.session.icon.clone())
+        .filter(|icon| !icon.is_empty());
+
+    let host_profile = match args.host_sandbox.as_deref() {
+        Some("workdir") => HostSandboxProfile::Workdir,
+        Some("isolated") => HostSandboxProfile::Isolated,
+        Some(_) => HostSandboxProfile::Off,
+        None => repo_resolved.sandbox.host_profile,
+    };
+    if args.no_network && host_profile == HostSandboxProfile::Off {
+        bail!("--no-network requires a host sandbox (--host-sandbox workdir or isolated)");
+    }
+    instance.host_sandbox = HostSandbox::new(
+        host_profile,
+        args.no_network || repo_resolved.sandbox.host_block_network,
+    );
+    instance.resource_limits = ResourceLimits::from_config(&repo_resolved.session);
+    instance.proxy = proxy;
+
     // Handle sandbox setup
     let use_sandbox = args.sandbox || args.sandbox_image.is_some();
 
@@ -274,6 +538,7 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
                     "Container runtime is not installed or not accessible.\n\
                      Install Docker: https://docs.docker.com/get-docker/\n\
                      Or on macOS: Apple Container\n\
+                     Or Podman: https://podman.io/docs/installation (set sandbox.container_runtime = \"podman\")\n\
                      Tip: Use 'aoe add' without --sandbox to run directly on host"
                 );
             }
@@ -296,12 +561,28 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
         }
     }
 
+    // Fails only after any worktree exists, so it shares the hooks' cleanup
+    let orchestrator_result = if args.orchestrator {
+        orchestrator::apply(&mut instance, storage.profile())
+    } else {
+        Ok(())
+    };
+
+    if orchestrator_result.is_ok() {
+        timeline::record_created(&instance);
+        journal::record_created(profile, &instance);
+    }
+
     // Check for repository hooks
-    let hook_result: Result<()> = (|| {
+    let hook_result: Result<()> = orchestrator_result.and_then(|()| {
         match repo_config::check_hook_trust(&path) {
             Ok(repo_config::HookTrustStatus::NeedsTrust { hooks, hooks_hash }) => {
                 let should_trust = if args.trust_hooks {
                     true
+                } else if prompt_from_stdin {
+                    println!("\nRepository hooks detected in .aoe/config.toml, but stdin held the prompt");
+                    println!("Tip: Pass --trust-hooks to run them");
+                    false
                 } else {
                     println!("\nRepository hooks detected in .aoe/config.toml:");
                     if !hooks.on_create.is_empty() {
@@ -325,7 +606,7 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
                 };
 
                 if should_trust {
-                    trust_and_run_on_create(&path, &hooks_hash, &hooks)?;
+                    trust_and_run_on_create(&instance.id, &path, &hooks_hash, &hooks)?;
                 } else {
                     println!("Hooks skipped (session created without running hooks)");
                 }
@@ -333,7 +614,9 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
             Ok(repo_config::HookTrustStatus::Trusted(hooks)) => {
                 if !hooks.on_create.is_empty() {
                     println!("Running on_create hooks...");
-                    repo_config::execute_hooks(&hooks.on_create, &path)?;
+                    let result = repo_config::execute_hooks(&hooks.on_create, &path);
+                    timeline::record_hooks(&instance.id, "on_create", &hooks.on_create, &result);
+                    result?;
                     println!("✓ on_create hooks completed");
                 }
             }
@@ -343,7 +626,7 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
             }
         }
         Ok(())
-    })();
+    });
 
     if let Err(e) = hook_result {
         // Clean up worktree if we created one
@@ -369,6 +652,7 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
             }
             let _ = std::fs::remove_dir_all(&ws_info.workspace_dir);
         }
+        timeline::remove(&instance.id);
         return Err(e);
     }
 
@@ -396,9 +680,36 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
     if instance.sandbox_info.is_some() {
         println!("  Sandbox: enabled");
     }
+    if let Some(host_sandbox) = &instance.host_sandbox {
+        println!("  Host sandbox: {}", host_sandbox.label());
+    }
+    if let Some(limits) = instance
+        .resource_limits
+        .as_ref()
+        .filter(|_| instance.sandbox_info.is_none())
+    {
+        println!("  Limits:  {}", limits.label());
+    }
+    if let Some(proxy) = &instance.proxy {
+        println!("  Proxy:   {}", proxy.label());
+    }
+    if instance.orchestrator.is_some() {
+        println!(
+            "  Orchestrator: workers are created with -P {}",
+            instance.id
+        );
+    }
     if instance.yolo_mode {
         println!("  YOLO:    enabled");
     }
+    if let Some(issue) = &instance.issue_info {
+        println!("  Issue:   #{} {}", issue.number, issue.url);
+    } else if let Some(prompt) = &instance.initial_prompt {
+        println!("  Prompt:  {} lines", prompt.lines().count());
+    }
+    if !instance.locks.is_empty() {
+        println!("  Locks:   {}", instance.locks.join(", "));
+    }
     if let Some(ws) = &instance.workspace_info {
         println!("  Workspace: {} repos", ws.repos.len());
         for repo in &ws.repos {
@@ -411,6 +722,8 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
             .iter()
             .position(|i| i.id == instance.id)
             .expect("just added instance");
+        locks::ensure_can_start(&instances[idx])?;
+        providers::ensure_can_start(&instances[idx], &config.session)?;
         instances[idx].start_with_size(crate::terminal::get_size())?;
         storage.save_with_groups(&instances, &group_tree)?;
 
@@ -429,6 +742,41 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
     Ok(())
 }
 
+/// Clone `url` for the new session, showing git's progress on one line.
+fn clone_repository(url: &str, config: &Config, shallow: bool) -> Result<PathBuf> {
+    use std::io::Write;
+
+    let worktree = &config.worktree;
+    println!("Cloning {} into {}", url, worktree.clone_dir);
+    let path = clone::ensure_clone(
+        url,
+        &worktree.clone_dir,
+        shallow || worktree.shallow_clone,
+        |line| {
+            eprint!("\r\x1b[K  {}", line);
+            let _ = std::io::stderr().flush();
+        },
+    );
+    eprint!("\r\x1b[K");
+    let path = path?;
+    println!("✓ Repository ready at {}", path.display());
+    Ok(path)
+}
+
+/// The agent for a session created without one: `default_tool` from config,
+/// then the first installed agent, then "claude".
+pub(crate) fn default_tool(config: &Config) -> String {
+    let available_tools = crate::tmux::AvailableTools::detect();
+    config
+        .session
+        .default_tool
+        .as_deref()
+        .and_then(crate::agents::resolve_tool_name)
+        .or_else(|| available_tools.available_list().first().copied())
+        .unwrap_or("claude")
+        .to_string()
+}
+
 pub fn is_duplicate_session(instances: &[Instance], title: &str, path: &str) -> bool {
     let normalized_path = path.trim_end_matches('/');
     instances.iter().any(|inst| {
@@ -438,6 +786,7 @@ pub fn is_duplicate_session(instances: &[Instance], title: &str, path: &str) ->
 }
 
 fn trust_and_run_on_create(
+    session_id: &str,
     project_path: &Path,
     hooks_hash: &str,
     hooks: &crate::session::HooksConfig,
@@ -446,13 +795,15 @@ fn trust_and_run_on_create(
     println!("✓ Repository hooks trusted");
     if !hooks.on_create.is_empty() {
         println!("Running on_create hooks...");
-        repo_config::execute_hooks(&hooks.on_create, project_path)?;
+        let result = repo_config::execute_hooks(&hooks.on_create, project_path);
+        timeline::record_hooks(session_id, "on_create", &hooks.on_create, &result);
+        result?;
         println!("✓ on_create hooks completed");
     }
     Ok(())
 }
 
-fn detect_tool(cmd: &str) -> Result<String> {
+pub(crate) fn detect_tool(cmd: &str) -> Result<String> {
     crate::agents::resolve_tool_name(cmd)
         .map(|name| name.to_string())
         .ok_or_else(|| {
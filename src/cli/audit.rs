@@ -0,0 +1,159 @@
+//! `agent-of-empires audit` subcommands implementation
+
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::session::audit::{self, AuditEntry};
+
+#[derive(Subcommand)]
+pub enum AuditCommands {
+    /// Show the most recent entries
+    Tail(AuditTailArgs),
+
+    /// Show entries matching a search term
+    Search(AuditSearchArgs),
+}
+
+#[derive(Args)]
+pub struct AuditTailArgs {
+    /// Number of entries to show
+    #[arg(short = 'n', long, default_value_t = 20)]
+    lines: usize,
+
+    /// Keep printing new entries as they are recorded
+    #[arg(short, long)]
+    follow: bool,
+
+    /// Output as JSON lines
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+pub struct AuditSearchArgs {
+    /// Text to look for in the session, action, source, or details (case-insensitive)
+    query: String,
+
+    /// Only entries with this action
+    #[arg(long, value_parser = [
+        "created", "started", "stopped", "removed", "restored", "purged", "prompt", "hook", "config",
+    ])]
+    action: Option<String>,
+
+    /// Show at most this many of the newest matches
+    #[arg(short = 'n', long, default_value_t = 100)]
+    limit: usize,
+
+    /// Output as JSON lines
+    #[arg(long)]
+    json: bool,
+}
+
+pub async fn run(command: AuditCommands) -> Result<()> {
+    match command {
+        AuditCommands::Tail(args) => tail(args).await,
+        AuditCommands::Search(args) => search(args),
+    }
+}
+
+async fn tail(args: AuditTailArgs) -> Result<()> {
+    let entries = audit::load()?;
+    let start = entries.len().saturating_sub(args.lines);
+    for entry in &entries[start..] {
+        print_entry(entry, args.json)?;
+    }
+    if !args.follow {
+        return Ok(());
+    }
+
+    let mut seen = entries.len();
+    loop {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let entries = audit::load()?;
+        for entry in entries.iter().skip(seen) {
+            print_entry(entry, args.json)?;
+        }
+        seen = entries.len();
+    }
+}
+
+fn search(args: AuditSearchArgs) -> Result<()> {
+    let matches: Vec<AuditEntry> = audit::load()?
+        .into_iter()
+        .filter(|e| {
+            args.action
+                .as_deref()
+                .map_or(true, |a| e.action.label() == a)
+        })
+        .filter(|e| e.matches(&args.query))
+        .collect();
+    if matches.is_empty() {
+        if !args.json {
+            println!("No matching audit entries.");
+        }
+        return Ok(());
+    }
+    let start = matches.len().saturating_sub(args.limit);
+    for entry in &matches[start..] {
+        print_entry(entry, args.json)?;
+    }
+    Ok(())
+}
+
+fn print_entry(entry: &AuditEntry, json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string(entry)?);
+    } else {
+        println!("{}", format_entry(entry));
+    }
+    Ok(())
+}
+
+fn format_entry(entry: &AuditEntry) -> String {
+    let session = entry
+        .title
+        .as_deref()
+        .or(entry.session_id.as_deref())
+        .unwrap_or("-");
+    format!(
+        "{}  {:<3}  {:<8}  {:<20}  {}",
+        entry
+            .at
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S"),
+        entry.source.label(),
+        entry.action.label(),
+        super::truncate(session, 20),
+        entry.detail
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::audit::{Action, Source};
+
+    #[test]
+    fn test_format_entry() {
+        let entry = AuditEntry {
+            at: chrono::Utc::now(),
+            source: Source::Tui,
+            action: Action::Prompt,
+            session_id: Some("abc123".to_string()),
+            title: Some("fix-login".to_string()),
+            detail: "run the tests".to_string(),
+        };
+        let line = format_entry(&entry);
+        assert!(line.ends_with("  tui  prompt    fix-login             run the tests"));
+
+        let entry = AuditEntry {
+            title: None,
+            session_id: None,
+            action: Action::Config,
+            ..entry
+        };
+        assert!(format_entry(&entry).contains("  config    -  "));
+    }
+}
@@ -0,0 +1,471 @@
+//! `agent-of-empires config` subcommands implementation
+
+use std::fs;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Subcommand};
+
+use crate::session::config::Config;
+use crate::session::profile_config::{self, ProfileConfig};
+use crate::session::repo_config::{self, INIT_TEMPLATE, REPO_CONFIG_PATH};
+use crate::session::{audit, get_app_dir, relocate};
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Open a config file in $EDITOR and validate it before saving
+    Edit(ConfigEditArgs),
+
+    /// Print where a config file lives on this platform
+    Path(ConfigPathArgs),
+
+    /// Move config and profile data left in another platform's location
+    /// (e.g. ~/.agent-of-empires on Linux) into this platform's one
+    Migrate(ConfigMigrateArgs),
+}
+
+#[derive(Args)]
+pub struct ConfigEditArgs {
+    /// Edit this profile's overrides instead of the global config
+    #[arg(long, value_name = "NAME", conflicts_with = "local")]
+    profile_name: Option<String>,
+
+    /// Edit the project-local .aoe/config.toml of DIR (default: current
+    /// directory) instead of the global config
+    #[arg(long, value_name = "DIR", num_args = 0..=1, default_missing_value = ".")]
+    local: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct ConfigPathArgs {
+    /// Print this profile's config file instead of the global one
+    #[arg(long, value_name = "NAME", conflicts_with = "local")]
+    profile_name: Option<String>,
+
+    /// Print the project-local .aoe/config.toml of DIR (default: current
+    /// directory) instead of the global config
+    #[arg(long, value_name = "DIR", num_args = 0..=1, default_missing_value = ".")]
+    local: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct ConfigMigrateArgs {
+    /// Show what would be moved without changing anything
+    #[arg(short = 'n', long)]
+    dry_run: bool,
+
+    /// Leave a symlink to the new location where the old directory was
+    #[arg(long)]
+    symlink: bool,
+}
+
+/// Which config file is being edited
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Target {
+    Global,
+    Profile(String),
+    Repo(PathBuf),
+}
+
+impl Target {
+    fn path(&self) -> Result<PathBuf> {
+        Ok(match self {
+            Target::Global => get_app_dir()?.join("config.toml"),
+            Target::Profile(name) => profile_config::get_profile_config_path(name)?,
+            Target::Repo(dir) => dir.join(REPO_CONFIG_PATH),
+        })
+    }
+
+    /// Name used in messages and the audit log
+    fn describe(&self) -> String {
+        match self {
+            Target::Global => "global config".to_string(),
+            Target::Profile(name) => format!("profile '{}' config", name),
+            Target::Repo(dir) => format!("repo config for {}", dir.display()),
+        }
+    }
+
+    /// Starting point for a file that doesn't exist yet
+    fn template(&self) -> Result<String> {
+        Ok(match self {
+            Target::Global => toml::to_string_pretty(&Config::default())?,
+            Target::Profile(name) => format!(
+                "# Overrides for profile '{}'. Any global setting can be set here\n\
+                 # and replaces the global value for this profile, e.g.\n\
+                 #\n\
+                 # [session]\n\
+                 # default_tool = \"codex\"\n",
+                name
+            ),
+            Target::Repo(_) => INIT_TEMPLATE.to_string(),
+        })
+    }
+}
+
+/// Result of checking edited config text
+#[derive(Debug, Default)]
+struct Validation {
+    /// Problems that prevent saving
+    errors: Vec<String>,
+    /// Likely mistakes that are saved anyway
+    warnings: Vec<String>,
+}
+
+pub async fn run(profile: &str, command: ConfigCommands) -> Result<()> {
+    match command {
+        ConfigCommands::Edit(args) => edit(profile, args),
+        ConfigCommands::Path(args) => {
+            println!(
+                "{}",
+                target(args.profile_name, args.local)?.path()?.display()
+            );
+            Ok(())
+        }
+        ConfigCommands::Migrate(args) => migrate(args),
+    }
+}
+
+fn target(profile_name: Option<String>, local: Option<PathBuf>) -> Result<Target> {
+    Ok(match (profile_name, local) {
+        (Some(name), _) => {
+            if !crate::session::list_profiles()?.contains(&name) {
+                bail!("Profile '{}' does not exist", name);
+            }
+            Target::Profile(name)
+        }
+        (None, Some(dir)) => {
+            let dir = dir
+                .canonicalize()
+                .with_context(|| format!("Directory not found: {}", dir.display()))?;
+            Target::Repo(dir)
+        }
+        (None, None) => Target::Global,
+    })
+}
+
+fn edit(profile: &str, args: ConfigEditArgs) -> Result<()> {
+    let target = target(args.profile_name, args.local)?;
+    let path = target.path()?;
+    let original = fs::read_to_string(&path).ok();
+    let mut content = match &original {
+        Some(text) => text.clone(),
+        None => target.template()?,
+    };
+
+    let editor = crate::terminal::preferred_editor();
+    let scratch = std::env::temp_dir().join(format!("aoe-config-{}.toml", std::process::id()));
+    let result = loop {
+        fs::write(&scratch, &content)?;
+        let status = Command::new(&editor)
+            .arg(&scratch)
+            .status()
+            .with_context(|| format!("Failed to run editor '{}'", editor));
+        let edited = fs::read_to_string(&scratch);
+        let status = match status {
+            Ok(status) => status,
+            Err(e) => break Err(e),
+        };
+        if !status.success() {
+            break Err(anyhow::anyhow!(
+                "Editor '{}' exited with {}",
+                editor,
+                status
+            ));
+        }
+        content = edited?;
+
+        if original.as_deref() == Some(content.as_str()) {
+            println!("No changes to {}", path.display());
+            break Ok(());
+        }
+
+        let validation = validate(&target, profile, &content);
+        for warning in &validation.warnings {
+            eprintln!("warning: {}", warning);
+        }
+        if validation.errors.is_empty() {
+            break save(&target, &path, original.as_deref(), &content);
+        }
+        for error in &validation.errors {
+            eprintln!("error: {}", error);
+        }
+        if !ask_edit_again()? {
+            break Err(anyhow::anyhow!(
+                "Invalid config discarded; {} is unchanged",
+                path.display()
+            ));
+        }
+    };
+    let _ = fs::remove_file(&scratch);
+    result
+}
+
+fn migrate(args: ConfigMigrateArgs) -> Result<()> {
+    let app_dir = get_app_dir()?;
+    let mut found = false;
+    for source in relocate::other_app_dirs() {
+        let Some(plan) = relocate::plan(&source, &app_dir)? else {
+            continue;
+        };
+        found = true;
+        let verb = if args.dry_run { "Would move" } else { "Moving" };
+        println!("{} {} → {}", verb, source.display(), app_dir.display());
+        for relocate::Move { from, .. } in &plan.moves {
+            println!("  {}", relative(&source, from));
+        }
+        for conflict in &plan.conflicts {
+            println!(
+                "  {} (skipped: already exists)",
+                relative(&source, conflict)
+            );
+        }
+        if plan.is_empty() {
+            println!("  (empty)");
+        }
+        if args.dry_run {
+            continue;
+        }
+        if relocate::apply(&plan, args.symlink)? {
+            if args.symlink {
+                println!("✓ Done; {} now links to the new location", source.display());
+            } else {
+                println!("✓ Done; removed {}", source.display());
+            }
+        } else {
+            println!(
+                "✓ Moved {} entries; {} still holds the skipped files",
+                plan.moves.len(),
+                source.display()
+            );
+        }
+    }
+    if !found {
+        println!("Nothing to migrate; config is in {}", app_dir.display());
+    }
+    Ok(())
+}
+
+fn relative(base: &Path, path: &Path) -> String {
+    path.strip_prefix(base)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn save(target: &Target, path: &Path, original: Option<&str>, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    audit::record_config_change(&target.describe(), original, content);
+    println!("✓ Saved {}", path.display());
+    Ok(())
+}
+
+/// Ask whether to reopen the editor on the rejected text. Without a
+/// terminal to ask on, the edit is abandoned.
+fn ask_edit_again() -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    print!("Edit again? [Y/n] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(!answer.trim().eq_ignore_ascii_case("n"))
+}
+
+/// Check edited text for `target`: TOML syntax, value types, and a few
+/// settings whose values are known, plus warnings for unrecognised keys.
+fn validate(target: &Target, profile: &str, content: &str) -> Validation {
+    let mut validation = Validation::default();
+    let table = match content.parse::<toml::Table>() {
+        Ok(table) => table,
+        Err(e) => {
+            validation.errors.push(format!("Invalid TOML: {}", e));
+            return validation;
+        }
+    };
+
+    let typed = match target {
+        Target::Global => toml::Value::Table(table.clone())
+            .try_into::<Config>()
+            .map_err(anyhow::Error::from)
+            .and_then(|c| Ok((toml::Value::try_from(&c)?, c))),
+        Target::Profile(_) => toml::Value::Table(table.clone())
+            .try_into::<ProfileConfig>()
+            .map_err(anyhow::Error::from)
+            .and_then(|p| {
+                let merged = profile_config::merge_configs(Config::load()?, &p);
+                Ok((toml::Value::try_from(&p)?, merged))
+            }),
+        Target::Repo(_) => {
+            // Templates are layered in, so check the file on its own for
+            // unknown keys and the result for values
+            let own = toml::Value::Table(table.clone())
+                .try_into::<repo_config::RepoConfig>()
+                .map_err(anyhow::Error::from);
+            own.and_then(|own| {
+                let repo = repo_config::parse_repo_config(content)?;
+                let base = profile_config::resolve_config(profile)?;
+                Ok((
+                    toml::Value::try_from(&own)?,
+                    repo_config::merge_repo_config(base, &repo),
+                ))
+            })
+        }
+    };
+    let (known, config) = match typed {
+        Ok(typed) => typed,
+        Err(e) => {
+            validation.errors.push(format!("{:#}", e));
+            return validation;
+        }
+    };
+
+    if let toml::Value::Table(known) = known {
+        unknown_keys(&table, &known, "", &mut validation.warnings);
+    }
+    check_values(&config, &mut validation);
+    validation
+}
+
+/// Keys in `edited` that didn't survive a round trip through the config
+/// type, i.e. serde ignored them. Empty values are skipped since they are
+/// omitted when serialising whether known or not.
+fn unknown_keys(edited: &toml::Table, known: &toml::Table, prefix: &str, out: &mut Vec<String>) {
+    for (key, value) in edited {
+        let name = format!("{}{}", prefix, key);
+        match (value, known.get(key)) {
+            (toml::Value::Table(edited), Some(toml::Value::Table(known))) => {
+                unknown_keys(edited, known, &format!("{}.", name), out)
+            }
+            (_, Some(_)) => {}
+            (toml::Value::Array(a), None) if a.is_empty() => {}
+            (toml::Value::Table(t), None) if t.is_empty() => {}
+            (toml::Value::String(s), None) if s.is_empty() => {}
+            (_, None) => out.push(format!("Unknown setting '{}' is ignored", name)),
+        }
+    }
+}
+
+fn check_values(config: &Config, validation: &mut Validation) {
+    for (key, tool) in [
+        ("session.default_tool", &config.session.default_tool),
+        ("session.fallback_tool", &config.session.fallback_tool),
+    ] {
+        if let Some(tool) = tool {
+            if crate::agents::get_agent(tool).is_none() {
+                validation.errors.push(format!(
+                    "{} = \"{}\" is not a known agent (use one of: {})",
+                    key,
+                    tool,
+                    crate::agents::agent_names().join(", ")
+                ));
+            }
+        }
+    }
+    if let Err(e) =
+        profile_config::validate_health_check_interval(config.hooks.health_check_interval_secs)
+    {
+        validation
+            .errors
+            .push(format!("hooks.health_check_interval_secs: {}", e));
+    }
+    if let Err(e) = profile_config::validate_pane_capture_interval(config.polling.pane_capture_ms) {
+        validation
+            .errors
+            .push(format!("polling.pane_capture_ms: {}", e));
+    }
+    for (key, secs) in [
+        ("polling.git_check_secs", config.polling.git_check_secs),
+        (
+            "polling.resource_usage_secs",
+            config.polling.resource_usage_secs,
+        ),
+    ] {
+        if let Err(e) = profile_config::validate_poll_interval(secs) {
+            validation.errors.push(format!("{}: {}", key, e));
+        }
+    }
+    if let Err(e) = crate::notifications::parse_rules(&config.notifications.rules) {
+        validation
+            .errors
+            .push(format!("notifications.rules: {}", e));
+    }
+    for (key, pattern) in [
+        (
+            "worktree.branch_tag_pattern",
+            &config.worktree.branch_tag_pattern,
+        ),
+        (
+            "worktree.branch_ticket_pattern",
+            &config.worktree.branch_ticket_pattern,
+        ),
+    ] {
+        if let Err(e) = crate::session::branch_labels::validate_pattern(pattern) {
+            validation.errors.push(format!("{}: {}", key, e));
+        }
+    }
+    if let Some(proxy) = &config.session.proxy {
+        if let Err(e) = crate::session::proxy::validate_url(proxy) {
+            validation.errors.push(format!("session.proxy: {}", e));
+        }
+    }
+    for pattern in &config.session.prompt_guardrails {
+        if regex::Regex::new(pattern.trim()).is_err() {
+            validation.warnings.push(format!(
+                "session.prompt_guardrails: '{}' is not a valid regular expression and is matched as plain text",
+                pattern
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_reports_syntax_type_and_value_errors() {
+        let invalid = validate(&Target::Global, "default", "[session\n");
+        assert!(invalid.errors[0].starts_with("Invalid TOML"));
+
+        let invalid = validate(&Target::Global, "default", "[session]\nnice = \"high\"\n");
+        assert_eq!(invalid.errors.len(), 1);
+
+        let invalid = validate(
+            &Target::Global,
+            "default",
+            "[session]\ndefault_tool = \"clade\"\n",
+        );
+        assert!(invalid.errors[0].contains("not a known agent"));
+
+        let valid = validate(
+            &Target::Global,
+            "default",
+            "[session]\ndefault_tool = \"codex\"\nlocks = []\n",
+        );
+        assert!(valid.errors.is_empty());
+        assert!(valid.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_keys_are_warnings() {
+        let validation = validate(
+            &Target::Global,
+            "default",
+            "colour = \"red\"\n[session]\ndefualt_tool = \"codex\"\nyolo_mode_default = true\n",
+        );
+        assert!(validation.errors.is_empty());
+        assert_eq!(
+            validation.warnings,
+            vec![
+                "Unknown setting 'colour' is ignored",
+                "Unknown setting 'session.defualt_tool' is ignored",
+            ]
+        );
+    }
+}
@@ -5,19 +5,34 @@
 
 use clap::{Parser, Subcommand};
 use clap_complete::Shell;
+use std::path::PathBuf;
 
 use super::add::AddArgs;
+use super::audit::AuditCommands;
+use super::config::ConfigCommands;
 use super::group::GroupCommands;
 use super::init::InitArgs;
+use super::interactive::InteractiveArgs;
+use super::ipc::IpcArgs;
 use super::list::ListArgs;
 use super::profile::ProfileCommands;
 use super::remove::RemoveArgs;
+use super::rename::RenameArgs;
 use super::send::SendArgs;
+use super::serve::ServeArgs;
 use super::session::SessionCommands;
+use super::share::ShareArgs;
+use super::shim::ShimArgs;
 use super::sounds::SoundsCommands;
+use super::stats::StatsArgs;
 use super::status::StatusArgs;
+use super::template::TemplateCommands;
 use super::tmux::TmuxCommands;
+use super::top::TopArgs;
+use super::trash::TrashCommands;
+use super::tray::TrayCommands;
 use super::uninstall::UninstallArgs;
+use super::wait::WaitArgs;
 use super::worktree::WorktreeCommands;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -36,6 +51,16 @@ pub struct Cli {
     #[arg(short = 'p', long, global = true, env = "AGENT_OF_EMPIRES_PROFILE")]
     pub profile: Option<String>,
 
+    /// Launch the TUI with sample sessions and simulated activity. Needs no
+    /// tmux or agents and never touches your real sessions
+    #[arg(long)]
+    pub demo: bool,
+
+    /// Play a script of keys into the TUI and write screen captures at its
+    /// markers, to reproduce a bug or drive the TUI from tests
+    #[arg(long, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -43,7 +68,8 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Commands {
     /// Add a new session
-    Add(AddArgs),
+    #[command(alias = "new")]
+    Add(Box<AddArgs>),
 
     /// Initialize .aoe/config.toml in a repository
     Init(InitArgs),
@@ -56,12 +82,38 @@ pub enum Commands {
     #[command(alias = "rm")]
     Remove(RemoveArgs),
 
+    /// Rename every session whose title matches a pattern
+    Rename(RenameArgs),
+
     /// Send a message to a running agent session
     Send(SendArgs),
 
+    /// Wait until a session is done, needs input, or fails
+    Wait(WaitArgs),
+
     /// Show session status summary
     Status(StatusArgs),
 
+    /// Live table of sessions with status, activity, CPU, and cost
+    Top(TopArgs),
+
+    /// Open the TUI, or with --plain numbered menus on plain lines of text
+    /// for dumb terminals and CI shells
+    Interactive(InteractiveArgs),
+
+    /// Weekly charts of finished sessions, success rate, and spend by project
+    Stats(StatsArgs),
+
+    /// Serve a read-only web dashboard of session status and output
+    Serve(ServeArgs),
+
+    /// Write a session's metadata, diff, and transcript to a static HTML page,
+    /// optionally uploading it to a gist or S3
+    Share(ShareArgs),
+
+    /// Serve the JSON protocol used by editor plugins on a unix socket
+    Ipc(IpcArgs),
+
     /// Manage session lifecycle (start, stop, attach, etc.)
     Session {
         #[command(subcommand)]
@@ -74,12 +126,36 @@ pub enum Commands {
         command: GroupCommands,
     },
 
+    /// Browse, restore, or purge removed sessions
+    Trash {
+        #[command(subcommand)]
+        command: TrashCommands,
+    },
+
+    /// Browse the audit log of changes made from the TUI, CLI, and API
+    Audit {
+        #[command(subcommand)]
+        command: AuditCommands,
+    },
+
+    /// Edit and validate config files
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+
     /// Manage profiles (separate workspaces)
     Profile {
         #[command(subcommand)]
         command: Option<ProfileCommands>,
     },
 
+    /// Install shared repo config templates from git repos or URLs
+    Template {
+        #[command(subcommand)]
+        command: TemplateCommands,
+    },
+
     /// Manage git worktrees for parallel development
     Worktree {
         #[command(subcommand)]
@@ -92,12 +168,23 @@ pub enum Commands {
         command: TmuxCommands,
     },
 
+    /// System tray indicator for sessions that need attention
+    Tray {
+        #[command(subcommand)]
+        command: TrayCommands,
+    },
+
     /// Manage sound effects for agent state transitions
     Sounds {
         #[command(subcommand)]
         command: SoundsCommands,
     },
 
+    /// Run an agent and answer health pings for it (used by sessions
+    /// when session.agent_shim is on)
+    #[command(hide = true)]
+    Shim(ShimArgs),
+
     /// Uninstall Agent of Empires
     Uninstall(UninstallArgs),
 
@@ -51,7 +51,7 @@ pub struct GroupDeleteArgs {
 
 #[derive(Args)]
 pub struct GroupMoveArgs {
-    /// Session ID or title
+    /// Session ID (or a unique prefix of it) or title
     identifier: String,
 
     /// Target group
@@ -198,11 +198,8 @@ async fn move_session(profile: &str, args: GroupMoveArgs) -> Result<()> {
     let storage = Storage::new(profile)?;
     let (mut instances, groups) = storage.load_with_groups()?;
 
-    let identifier = args.identifier.trim();
-    let inst = instances
-        .iter_mut()
-        .find(|i| i.id == identifier || i.id.starts_with(identifier) || i.title == identifier)
-        .ok_or_else(|| anyhow::anyhow!("Session not found: {}", identifier))?;
+    let index = super::resolve_session_index(&args.identifier, &instances)?;
+    let inst = &mut instances[index];
 
     let group = args.group.trim();
     let old_group = inst.group_path.clone();
@@ -3,15 +3,47 @@
 use anyhow::{bail, Result};
 use clap::Args;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::session::repo_config::INIT_TEMPLATE;
+use crate::session::repo_config::{INIT_TEMPLATE, REPO_CONFIG_PATH};
+use crate::session::Config;
+use crate::tmux::AvailableTools;
 
 #[derive(Args)]
 pub struct InitArgs {
     /// Directory to initialize (defaults to current directory)
     #[arg(default_value = ".")]
     path: PathBuf,
+
+    /// Write the commented template without detecting defaults
+    #[arg(long)]
+    bare: bool,
+
+    /// Stage the new file with `git add`
+    #[arg(long)]
+    git: bool,
+}
+
+/// Build output and dependency folders worth skipping in path completion
+const IGNORE_CANDIDATES: &[&str] = &[
+    "node_modules",
+    "target",
+    "dist",
+    "build",
+    ".venv",
+    "venv",
+    "__pycache__",
+    "vendor",
+    ".next",
+    "coverage",
+];
+
+/// Defaults `aoe init` fills in from what it finds in the repo
+#[derive(Debug, Default, PartialEq)]
+struct Detected {
+    test_command: Option<String>,
+    tool: Option<String>,
+    completion_ignores: Vec<String>,
 }
 
 pub async fn run(args: InitArgs) -> Result<()> {
@@ -31,11 +63,231 @@ pub async fn run(args: InitArgs) -> Result<()> {
         );
     }
 
+    let detected = if args.bare {
+        Detected::default()
+    } else {
+        detect(&path, default_tool())
+    };
+
     fs::create_dir_all(&aoe_dir)?;
-    fs::write(&config_path, INIT_TEMPLATE)?;
+    fs::write(&config_path, render(&detected))?;
 
     println!("Created .aoe/config.toml at {}", path.display());
+    if let Some(cmd) = &detected.test_command {
+        println!("  test command:       {}", cmd);
+    }
+    if let Some(tool) = &detected.tool {
+        println!("  default agent:      {}", tool);
+    }
+    if !detected.completion_ignores.is_empty() {
+        println!(
+            "  completion ignores: {}",
+            detected.completion_ignores.join(", ")
+        );
+    }
+
+    if args.git {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&path)
+            .args(["add", REPO_CONFIG_PATH])
+            .output()?;
+        if !output.status.success() {
+            bail!(
+                "git add failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        println!("Staged {} with git", REPO_CONFIG_PATH);
+    }
+
     println!("Edit the file to configure hooks and session defaults for this repo.");
 
     Ok(())
 }
+
+/// The agent new sessions in this repo should start with: the global
+/// default if one is set, otherwise the first one installed
+fn default_tool() -> Option<String> {
+    Config::load()
+        .ok()
+        .and_then(|c| c.session.default_tool)
+        .or_else(|| {
+            AvailableTools::detect()
+                .available_list()
+                .first()
+                .map(|t| t.to_string())
+        })
+}
+
+fn detect(dir: &Path, tool: Option<String>) -> Detected {
+    let mut completion_ignores: Vec<String> = IGNORE_CANDIDATES
+        .iter()
+        .filter(|name| dir.join(name).is_dir())
+        .map(|name| name.to_string())
+        .collect();
+    // Not there until the first build or install, but will be
+    for (marker, name) in [("Cargo.toml", "target"), ("package.json", "node_modules")] {
+        if dir.join(marker).is_file() && !completion_ignores.iter().any(|i| i == name) {
+            completion_ignores.push(name.to_string());
+        }
+    }
+
+    Detected {
+        test_command: detect_test_command(dir),
+        tool,
+        completion_ignores,
+    }
+}
+
+fn detect_test_command(dir: &Path) -> Option<String> {
+    let has = |name: &str| dir.join(name).exists();
+
+    if has("Cargo.toml") {
+        return Some("cargo test".to_string());
+    }
+    if has("package.json") && has_npm_test_script(&dir.join("package.json")) {
+        let runner = if has("pnpm-lock.yaml") {
+            "pnpm test"
+        } else if has("yarn.lock") {
+            "yarn test"
+        } else if has("bun.lockb") || has("bun.lock") {
+            "bun run test"
+        } else {
+            "npm test"
+        };
+        return Some(runner.to_string());
+    }
+    if has("go.mod") {
+        return Some("go test ./...".to_string());
+    }
+    if has("pyproject.toml") || has("pytest.ini") || has("setup.py") {
+        let runner = if has("uv.lock") {
+            "uv run pytest"
+        } else {
+            "pytest"
+        };
+        return Some(runner.to_string());
+    }
+    if has("mix.exs") {
+        return Some("mix test".to_string());
+    }
+    let makefile = fs::read_to_string(dir.join("Makefile")).unwrap_or_default();
+    if makefile.lines().any(|l| l.starts_with("test:")) {
+        return Some("make test".to_string());
+    }
+    None
+}
+
+/// Whether package.json defines a test script other than npm's placeholder
+fn has_npm_test_script(path: &Path) -> bool {
+    let Ok(content) = fs::read_to_string(path) else {
+        return false;
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return false;
+    };
+    json.pointer("/scripts/test")
+        .and_then(|t| t.as_str())
+        .is_some_and(|t| !t.contains("no test specified"))
+}
+
+/// The init template with the detected settings uncommented
+fn render(detected: &Detected) -> String {
+    let mut content = INIT_TEMPLATE.to_string();
+    let quoted = |s: &str| toml::Value::String(s.to_string()).to_string();
+
+    if !detected.completion_ignores.is_empty() {
+        let list = detected
+            .completion_ignores
+            .iter()
+            .map(|i| quoted(i))
+            .collect::<Vec<_>>()
+            .join(", ");
+        content = content.replacen(
+            "# completion_ignores = [\"target\", \"node_modules\"]",
+            &format!("completion_ignores = [{}]", list),
+            1,
+        );
+    }
+    if let Some(tool) = &detected.tool {
+        content = content.replacen(
+            "# [session]\n# default_tool = \"claude\"",
+            &format!("[session]\ndefault_tool = {}", quoted(tool)),
+            1,
+        );
+    }
+    if let Some(cmd) = &detected.test_command {
+        content = content.replacen(
+            "# [worktree]\n# enabled = true\n# merge_test_command = \"cargo test\"",
+            &format!(
+                "[worktree]\n# enabled = true\nmerge_test_command = {}",
+                quoted(cmd)
+            ),
+            1,
+        );
+        content = content.replacen(
+            "# [commands]\n# test = \"cargo test\"",
+            &format!("[commands]\ntest = {}", quoted(cmd)),
+            1,
+        );
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::repo_config::parse_repo_config;
+
+    #[test]
+    fn test_detect_node_project() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"test": "vitest run"}}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+        fs::create_dir(dir.path().join("dist")).unwrap();
+
+        let detected = detect(dir.path(), Some("codex".to_string()));
+        assert_eq!(
+            detected,
+            Detected {
+                test_command: Some("pnpm test".to_string()),
+                tool: Some("codex".to_string()),
+                completion_ignores: vec!["dist".to_string(), "node_modules".to_string()],
+            }
+        );
+
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"test": "echo \"Error: no test specified\" && exit 1"}}"#,
+        )
+        .unwrap();
+        assert_eq!(detect_test_command(dir.path()), None);
+    }
+
+    #[test]
+    fn test_render_uncomments_detected_settings() {
+        let detected = Detected {
+            test_command: Some("cargo test".to_string()),
+            tool: Some("opencode".to_string()),
+            completion_ignores: vec!["target".to_string()],
+        };
+        let config = parse_repo_config(&render(&detected)).unwrap();
+        assert_eq!(config.completion_ignores, vec!["target"]);
+        assert_eq!(
+            config.session.unwrap().default_tool.as_deref(),
+            Some("opencode")
+        );
+        assert_eq!(
+            config.worktree.unwrap().merge_test_command.as_deref(),
+            Some("cargo test")
+        );
+        assert_eq!(config.commands["test"], "cargo test");
+
+        assert_eq!(render(&Detected::default()), INIT_TEMPLATE);
+    }
+}
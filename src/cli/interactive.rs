@@ -0,0 +1,290 @@
+//! `agent-of-empires interactive` command implementation
+//!
+//! Opens the TUI, or with `--plain` (and on terminals that cannot show it)
+//! a line-based REPL with numbered menus for listing, creating, messaging,
+//! and checking on sessions. The REPL only reads lines and prints text, so
+//! it works in dumb terminals, CI shells, and over pipes.
+
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use anyhow::{bail, Result};
+use clap::{Args, FromArgMatches};
+
+use super::add::AddArgs;
+use crate::agents;
+use crate::session::{guardrails, Instance, Storage};
+use crate::tui::styles::status_icon;
+
+/// Pane lines shown for a session's status
+const STATUS_OUTPUT_LINES: usize = 10;
+
+#[derive(Args)]
+pub struct InteractiveArgs {
+    /// Use numbered menus on plain lines of text instead of the TUI. Chosen
+    /// automatically when TERM is "dumb" or stdin/stdout is not a terminal
+    #[arg(long)]
+    plain: bool,
+}
+
+const MENU: [&str; 5] = [
+    "List sessions",
+    "New session",
+    "Send a message",
+    "Session status",
+    "Quit",
+];
+
+pub async fn run(profile: &str, args: InteractiveArgs) -> Result<()> {
+    if !args.plain && !needs_plain() {
+        return crate::tui::run(profile, None, None).await;
+    }
+
+    let storage = Storage::new(profile)?;
+    let mut input = io::stdin().lock();
+    println!("Agent of Empires - profile '{}'", storage.profile());
+    loop {
+        println!();
+        let Some(choice) = choose(&mut input, "What next?", &MENU)? else {
+            return Ok(());
+        };
+        let result = match choice {
+            0 => list(&storage),
+            1 => new_session(&mut input, profile).await,
+            2 => send(&mut input, &storage, profile),
+            3 => status(&mut input, &storage),
+            _ => return Ok(()),
+        };
+        if let Err(e) = result {
+            println!("Error: {}", e);
+        }
+    }
+}
+
+/// Whether the TUI cannot run here
+fn needs_plain() -> bool {
+    std::env::var("TERM").is_ok_and(|term| term == "dumb")
+        || !io::stdin().is_terminal()
+        || !io::stdout().is_terminal()
+}
+
+/// Print `question` and read one line, trimmed. None at end of input.
+fn ask(input: &mut impl BufRead, question: &str) -> Result<Option<String>> {
+    print!("{} ", question);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        println!();
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
+
+/// Which of `count` options `answer` picks: a number from 1, or None.
+fn parse_choice(answer: &str, count: usize) -> Option<usize> {
+    answer
+        .parse::<usize>()
+        .ok()
+        .filter(|n| (1..=count).contains(n))
+        .map(|n| n - 1)
+}
+
+/// Print `options` numbered from 1 and read a choice, asking again until it
+/// is valid. An empty answer or the end of input chooses nothing.
+fn choose(
+    input: &mut impl BufRead,
+    title: &str,
+    options: &[impl AsRef<str>],
+) -> Result<Option<usize>> {
+    println!("{}", title);
+    for (i, option) in options.iter().enumerate() {
+        println!("  {:>2}) {}", i + 1, option.as_ref());
+    }
+    loop {
+        let Some(answer) = ask(input, ">")? else {
+            return Ok(None);
+        };
+        if answer.is_empty() {
+            return Ok(None);
+        }
+        match parse_choice(&answer, options.len()) {
+            Some(index) => return Ok(Some(index)),
+            None => println!("Enter a number from 1 to {}", options.len()),
+        }
+    }
+}
+
+/// Sessions with their status refreshed from tmux
+fn load_sessions(storage: &Storage) -> Result<Vec<Instance>> {
+    let (mut instances, _) = storage.load_with_groups()?;
+    crate::tmux::refresh_session_cache();
+    for inst in &mut instances {
+        inst.update_status();
+    }
+    Ok(instances)
+}
+
+fn describe(inst: &Instance) -> String {
+    format!(
+        "{} {:<24} {:<9} {:<10} {}",
+        status_icon(inst.status),
+        super::truncate(&inst.title, 24),
+        format!("{:?}", inst.status),
+        super::truncate(&inst.tool, 10),
+        inst.project_path
+    )
+}
+
+/// Pick a session from a numbered list. None if there are none or the user
+/// backs out.
+fn choose_session(input: &mut impl BufRead, storage: &Storage) -> Result<Option<Instance>> {
+    let mut instances = load_sessions(storage)?;
+    if instances.is_empty() {
+        println!("No sessions in profile '{}'.", storage.profile());
+        return Ok(None);
+    }
+    let options: Vec<String> = instances.iter().map(describe).collect();
+    let choice = choose(input, "Which session? (empty to go back)", &options)?;
+    Ok(choice.map(|index| instances.swap_remove(index)))
+}
+
+fn list(storage: &Storage) -> Result<()> {
+    let instances = load_sessions(storage)?;
+    if instances.is_empty() {
+        println!("No sessions in profile '{}'.", storage.profile());
+        return Ok(());
+    }
+    for (i, inst) in instances.iter().enumerate() {
+        println!("  {:>2}) {}", i + 1, describe(inst));
+    }
+    Ok(())
+}
+
+async fn new_session(input: &mut impl BufRead, profile: &str) -> Result<()> {
+    let Some(path) = ask(input, "Project directory [.]:")? else {
+        return Ok(());
+    };
+    let Some(title) = ask(input, "Title [generated]:")? else {
+        return Ok(());
+    };
+    let Some(agent) = choose(input, "Which agent?", &agents::agent_names())? else {
+        return Ok(());
+    };
+    let Some(launch) = ask(input, "Start it now? [Y/n]")? else {
+        return Ok(());
+    };
+
+    let mut argv = vec![
+        "add".to_string(),
+        if path.is_empty() {
+            ".".to_string()
+        } else {
+            path
+        },
+        "--cmd".to_string(),
+        agents::AGENTS[agent].name.to_string(),
+    ];
+    if !title.is_empty() {
+        argv.extend(["--title".to_string(), title]);
+    }
+    let matches = AddArgs::augment_args(clap::Command::new("add")).try_get_matches_from(argv)?;
+
+    let storage = Storage::new(profile)?;
+    let before: Vec<String> = storage.load()?.into_iter().map(|i| i.id).collect();
+    super::add::run(profile, AddArgs::from_arg_matches(&matches)?).await?;
+    if launch.eq_ignore_ascii_case("n") {
+        return Ok(());
+    }
+    // `aoe add --launch` would attach, which needs a real terminal
+    let added = storage
+        .load()?
+        .into_iter()
+        .find(|i| !before.contains(&i.id));
+    match added {
+        Some(inst) => super::session::start(profile, &inst.id, false),
+        None => Ok(()),
+    }
+}
+
+fn send(input: &mut impl BufRead, storage: &Storage, profile: &str) -> Result<()> {
+    let Some(inst) = choose_session(input, storage)? else {
+        return Ok(());
+    };
+    let Some(message) = ask(input, "Message:")? else {
+        return Ok(());
+    };
+    if message.is_empty() {
+        bail!("Message cannot be empty");
+    }
+
+    let matched = super::send::guardrail_matches(profile, &inst, &message);
+    if !matched.is_empty() {
+        let question = format!(
+            "Message matches prompt guardrail {}. Send anyway? [y/N]",
+            guardrails::describe(&matched)
+        );
+        if !ask(input, &question)?.is_some_and(|a| a.eq_ignore_ascii_case("y")) {
+            return Ok(());
+        }
+    }
+
+    crate::session::ownership::claim_stored(storage, &inst.id, false)?;
+    super::send::deliver(profile, &inst, &message, true, false)?;
+    println!("Sent message to '{}'", inst.title);
+    Ok(())
+}
+
+fn status(input: &mut impl BufRead, storage: &Storage) -> Result<()> {
+    let Some(inst) = choose_session(input, storage)? else {
+        return Ok(());
+    };
+    println!();
+    println!("{} {}", status_icon(inst.status), inst.title);
+    println!("  Status:  {:?}", inst.status);
+    println!("  Agent:   {}", inst.tool);
+    println!("  Path:    {}", inst.project_path);
+    if let Some(worktree) = &inst.worktree_info {
+        println!("  Branch:  {}", worktree.branch);
+    }
+    if !inst.group_path.is_empty() {
+        println!("  Group:   {}", inst.group_path);
+    }
+
+    let output = inst
+        .tmux_session()
+        .ok()
+        .filter(|session| session.exists())
+        .and_then(|session| session.capture_pane(STATUS_OUTPUT_LINES).ok());
+    if let Some(output) = output {
+        println!("  Recent output:");
+        for line in output.trim_end().lines() {
+            println!("    {}", line);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_choice() {
+        assert_eq!(parse_choice("1", 3), Some(0));
+        assert_eq!(parse_choice("3", 3), Some(2));
+        assert_eq!(parse_choice("0", 3), None);
+        assert_eq!(parse_choice("4", 3), None);
+        assert_eq!(parse_choice("two", 3), None);
+    }
+
+    #[test]
+    fn test_choose_asks_again_until_valid() {
+        let mut input = io::Cursor::new("9\nnope\n2\n");
+        assert_eq!(choose(&mut input, "Pick", &["a", "b"]).unwrap(), Some(1));
+
+        let mut input = io::Cursor::new("\n");
+        assert_eq!(choose(&mut input, "Pick", &["a", "b"]).unwrap(), None);
+
+        let mut input = io::Cursor::new("");
+        assert_eq!(choose(&mut input, "Pick", &["a", "b"]).unwrap(), None);
+    }
+}
@@ -0,0 +1,480 @@
+//! `agent-of-empires ipc` command implementation
+//!
+//! Serves a newline-delimited JSON protocol on a unix socket so editor
+//! plugins can list, create, and message sessions and follow their status.
+//! The protocol is documented in docs/guides/editor-integration.md and is a
+//! stable surface: within a `PROTOCOL_VERSION`, fields and methods are only
+//! ever added, never removed or changed.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::unix::OwnedWriteHalf;
+use tokio::net::{UnixListener, UnixStream};
+
+use crate::session::builder::{self, InstanceParams};
+use crate::session::{
+    locks, providers, repo_config, resolve_config, GroupTree, Instance, Status, Storage,
+};
+
+/// Bumped only for incompatible changes to the protocol.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// How often subscribed connections are checked for status changes.
+const SUBSCRIBE_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Args)]
+pub struct IpcArgs {
+    /// Socket path. Defaults to `ipc-<profile>.sock` in the aoe config directory
+    #[arg(long, value_name = "PATH")]
+    socket: Option<PathBuf>,
+
+    /// Print the socket path and exit, for plugins locating the server
+    #[arg(long)]
+    print_socket: bool,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    /// Echoed back on the response so clients can match them up
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct SessionView {
+    id: String,
+    title: String,
+    path: String,
+    group: String,
+    tool: String,
+    status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+}
+
+impl SessionView {
+    fn new(inst: &Instance) -> Self {
+        Self {
+            id: inst.id.clone(),
+            title: inst.title.clone(),
+            path: inst.project_path.clone(),
+            group: inst.group_path.clone(),
+            tool: inst.tool.clone(),
+            status: inst.status,
+            branch: inst.worktree_info.as_ref().map(|w| w.branch.clone()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct NewParams {
+    path: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    tool: Option<String>,
+    #[serde(default)]
+    group: Option<String>,
+    /// Branch to create the session's worktree on
+    #[serde(default)]
+    worktree: Option<String>,
+    #[serde(default)]
+    new_branch: bool,
+    /// Ref a new branch starts from
+    #[serde(default)]
+    base: Option<String>,
+    #[serde(default)]
+    prompt: Option<String>,
+    #[serde(default)]
+    yolo: bool,
+    #[serde(default)]
+    launch: bool,
+}
+
+#[derive(Deserialize)]
+struct SendParams {
+    session: String,
+    message: String,
+    #[serde(default)]
+    force: bool,
+    #[serde(default)]
+    steal: bool,
+}
+
+pub async fn run(profile: &str, args: IpcArgs) -> Result<()> {
+    let profile = Storage::new(profile)?.profile().to_string();
+    let socket = match args.socket {
+        Some(path) => path,
+        None => crate::session::ipc_socket_path(&profile)?,
+    };
+    if args.print_socket {
+        println!("{}", socket.display());
+        return Ok(());
+    }
+
+    let listener = bind(&socket).await?;
+    println!("Listening for editor clients on {}", socket.display());
+    println!("  Press Ctrl+C to stop");
+
+    let serve = async {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let profile = profile.clone();
+            tokio::spawn(async move {
+                if let Err(e) = handle_connection(stream, &profile).await {
+                    tracing::debug!("IPC connection closed: {}", e);
+                }
+            });
+        }
+    };
+    let result: Result<()> = tokio::select! {
+        result = serve => result,
+        _ = tokio::signal::ctrl_c() => Ok(()),
+    };
+    let _ = std::fs::remove_file(&socket);
+    result
+}
+
+/// Listen on `socket`, replacing a stale socket file left by a server that
+/// didn't shut down cleanly. Only the current user may connect.
+async fn bind(socket: &Path) -> Result<UnixListener> {
+    if socket.exists() {
+        if UnixStream::connect(socket).await.is_ok() {
+            bail!(
+                "Another aoe ipc server is already listening on {}",
+                socket.display()
+            );
+        }
+        std::fs::remove_file(socket)
+            .with_context(|| format!("Failed to remove stale socket {}", socket.display()))?;
+    }
+    if let Some(parent) = socket.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let listener = UnixListener::bind(socket)
+        .with_context(|| format!("Failed to listen on {}", socket.display()))?;
+
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(socket, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+async fn handle_connection(stream: UnixStream, profile: &str) -> Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut lines = BufReader::new(read).lines();
+    write_line(
+        &mut write,
+        &json!({
+            "event": "hello",
+            "protocol": PROTOCOL_VERSION,
+            "version": env!("CARGO_PKG_VERSION"),
+            "profile": profile,
+        }),
+    )
+    .await?;
+
+    let mut subscription: Option<Subscription> = None;
+    let mut ticker = tokio::time::interval(SUBSCRIBE_INTERVAL);
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                let Some(line) = line? else {
+                    return Ok(());
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let response = match serde_json::from_str::<Request>(&line) {
+                    Ok(request) => {
+                        let result = handle_request(profile, &request, &mut subscription).await;
+                        response(&request.id, result)
+                    }
+                    Err(e) => response(&Value::Null, Err(anyhow::anyhow!("Invalid request: {}", e))),
+                };
+                write_line(&mut write, &response).await?;
+            }
+            _ = ticker.tick(), if subscription.is_some() => {
+                let sessions = load_sessions_blocking(profile).await?;
+                if let Some(subscription) = subscription.as_mut() {
+                    for event in subscription.changes(&sessions) {
+                        write_line(&mut write, &event).await?;
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn write_line(write: &mut OwnedWriteHalf, value: &Value) -> Result<()> {
+    let mut line = serde_json::to_vec(value)?;
+    line.push(b'\n');
+    write.write_all(&line).await?;
+    Ok(())
+}
+
+fn response(id: &Value, result: Result<Value>) -> Value {
+    match result {
+        Ok(result) => json!({ "id": id, "ok": true, "result": result }),
+        Err(e) => json!({ "id": id, "ok": false, "error": e.to_string() }),
+    }
+}
+
+async fn handle_request(
+    profile: &str,
+    request: &Request,
+    subscription: &mut Option<Subscription>,
+) -> Result<Value> {
+    let profile = profile.to_string();
+    match request.method.as_str() {
+        "list" => Ok(serde_json::to_value(
+            load_sessions_blocking(&profile).await?,
+        )?),
+        "status-subscribe" => {
+            let sessions = load_sessions_blocking(&profile).await?;
+            *subscription = Some(Subscription::new(&sessions));
+            Ok(serde_json::to_value(sessions)?)
+        }
+        "new" => {
+            let params: NewParams = parse_params(&request.params)?;
+            let view =
+                tokio::task::spawn_blocking(move || create_session(&profile, params)).await??;
+            Ok(serde_json::to_value(view)?)
+        }
+        "send" => {
+            let params: SendParams = parse_params(&request.params)?;
+            tokio::task::spawn_blocking(move || send_message(&profile, params)).await??;
+            Ok(Value::Null)
+        }
+        other => bail!("Unknown method: {}", other),
+    }
+}
+
+fn parse_params<T: serde::de::DeserializeOwned>(params: &Value) -> Result<T> {
+    serde_json::from_value(params.clone()).map_err(|e| anyhow::anyhow!("Invalid params: {}", e))
+}
+
+async fn load_sessions_blocking(profile: &str) -> Result<Vec<SessionView>> {
+    let profile = profile.to_string();
+    tokio::task::spawn_blocking(move || load_sessions(&profile)).await?
+}
+
+/// Every session in the profile with a freshly checked status.
+fn load_sessions(profile: &str) -> Result<Vec<SessionView>> {
+    let mut instances = Storage::new(profile)?.load()?;
+    crate::tmux::refresh_session_cache();
+    Ok(instances
+        .iter_mut()
+        .map(|inst| {
+            inst.update_status();
+            SessionView::new(inst)
+        })
+        .collect())
+}
+
+fn create_session(profile: &str, params: NewParams) -> Result<SessionView> {
+    let config = resolve_config(profile).unwrap_or_default();
+    let tool = match &params.tool {
+        Some(tool) => super::add::detect_tool(tool)?,
+        None => super::add::default_tool(&config),
+    };
+
+    let storage = Storage::new(profile)?;
+    let (mut instances, groups) = storage.load_with_groups()?;
+    let existing_titles: Vec<&str> = instances.iter().map(|i| i.title.as_str()).collect();
+    let build = builder::build_instance(
+        InstanceParams {
+            title: params.title.unwrap_or_default().trim().to_string(),
+            path: params.path,
+            group: params.group.unwrap_or_default().trim().to_string(),
+            tool,
+            worktree_branch: params.worktree,
+            create_new_branch: params.new_branch,
+            base_ref: params.base,
+            sandbox: false,
+            sandbox_image: String::new(),
+            yolo_mode: params.yolo || config.session.yolo_mode_default,
+            extra_env: Vec::new(),
+            extra_args: String::new(),
+            command_override: String::new(),
+            extra_repo_paths: Vec::new(),
+            issue_info: None,
+            initial_prompt: params.prompt.filter(|p| !p.trim().is_empty()),
+        },
+        &existing_titles,
+        profile,
+    )?;
+    let mut instance = build.instance;
+    let cleanup = |instance: &Instance| {
+        builder::cleanup_instance(
+            instance,
+            build.created_worktree.as_ref(),
+            &build.created_workspace_worktrees,
+        )
+    };
+
+    // Trusted repo hooks run as they would for `aoe add`; untrusted ones are
+    // skipped, since there is no one to ask
+    let project = PathBuf::from(&instance.project_path);
+    match repo_config::check_hook_trust(&project) {
+        Ok(repo_config::HookTrustStatus::Trusted(hooks)) if !hooks.on_create.is_empty() => {
+            let result = repo_config::execute_hooks(&hooks.on_create, &project);
+            crate::session::timeline::record_hooks(
+                &instance.id,
+                "on_create",
+                &hooks.on_create,
+                &result,
+            );
+            if let Err(e) = result {
+                cleanup(&instance);
+                return Err(e);
+            }
+        }
+        Ok(repo_config::HookTrustStatus::NeedsTrust { .. }) => {
+            tracing::warn!(
+                "Skipping untrusted repository hooks for IPC session '{}'",
+                instance.title
+            );
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to check repo hooks: {}", e),
+    }
+
+    if params.launch {
+        if let Err(e) = locks::ensure_can_start(&instance)
+            .and_then(|_| providers::ensure_can_start(&instance, &config.session))
+            .and_then(|_| instance.start())
+        {
+            cleanup(&instance);
+            return Err(e);
+        }
+        instance.update_status();
+    }
+
+    instances.push(instance.clone());
+    let mut group_tree = GroupTree::new_with_groups(&instances, &groups);
+    if !instance.group_path.is_empty() {
+        group_tree.create_group(&instance.group_path);
+    }
+    storage.save_with_groups(&instances, &group_tree)?;
+    Ok(SessionView::new(&instance))
+}
+
+fn send_message(profile: &str, params: SendParams) -> Result<()> {
+    if params.message.trim().is_empty() {
+        bail!("Message cannot be empty");
+    }
+    let storage = Storage::new(profile)?;
+    let instances = storage.load()?;
+    let inst = super::resolve_session(&params.session, &instances)?;
+    crate::session::ownership::claim_stored(&storage, &inst.id, params.steal)?;
+    let multiline = params.message.trim_end().contains('\n');
+    super::send::deliver(profile, inst, &params.message, params.force, multiline)
+}
+
+/// Last status seen by a subscribed connection, per session.
+struct Subscription {
+    seen: HashMap<String, Status>,
+}
+
+impl Subscription {
+    fn new(sessions: &[SessionView]) -> Self {
+        Self {
+            seen: sessions.iter().map(|s| (s.id.clone(), s.status)).collect(),
+        }
+    }
+
+    /// Events for sessions that appeared or changed status since the last
+    /// check, then for sessions that were removed.
+    fn changes(&mut self, sessions: &[SessionView]) -> Vec<Value> {
+        let mut events: Vec<Value> = sessions
+            .iter()
+            .filter(|s| self.seen.get(&s.id) != Some(&s.status))
+            .map(|s| json!({ "event": "status", "session": s }))
+            .collect();
+        let removed: Vec<String> = self
+            .seen
+            .keys()
+            .filter(|id| !sessions.iter().any(|s| &s.id == *id))
+            .cloned()
+            .collect();
+        events.extend(
+            removed
+                .iter()
+                .map(|id| json!({ "event": "removed", "id": id })),
+        );
+        self.seen = sessions.iter().map(|s| (s.id.clone(), s.status)).collect();
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(id: &str, status: Status) -> SessionView {
+        SessionView {
+            id: id.to_string(),
+            title: id.to_string(),
+            path: "/tmp".to_string(),
+            group: String::new(),
+            tool: "claude".to_string(),
+            status,
+            branch: None,
+        }
+    }
+
+    #[test]
+    fn test_subscription_reports_changes() {
+        let mut sub = Subscription::new(&[view("a", Status::Running), view("b", Status::Idle)]);
+        assert!(sub
+            .changes(&[view("a", Status::Running), view("b", Status::Idle)])
+            .is_empty());
+
+        let events = sub.changes(&[view("a", Status::Waiting), view("c", Status::Starting)]);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["event"], "status");
+        assert_eq!(events[0]["session"]["id"], "a");
+        assert_eq!(events[0]["session"]["status"], "waiting");
+        assert_eq!(events[1]["session"]["id"], "c");
+        assert_eq!(events[2], json!({ "event": "removed", "id": "b" }));
+    }
+
+    #[test]
+    fn test_response_shape() {
+        assert_eq!(
+            response(&json!(7), Ok(json!([]))),
+            json!({ "id": 7, "ok": true, "result": [] })
+        );
+        assert_eq!(
+            response(&Value::Null, Err(anyhow::anyhow!("Unknown method: x"))),
+            json!({ "id": null, "ok": false, "error": "Unknown method: x" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_method_and_bad_params() {
+        let mut subscription = None;
+        let request: Request = serde_json::from_str(r#"{"method":"nope"}"#).unwrap();
+        let err = handle_request("default", &request, &mut subscription)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Unknown method: nope");
+
+        let request: Request =
+            serde_json::from_str(r#"{"id":1,"method":"send","params":{"session":"x"}}"#).unwrap();
+        let err = handle_request("default", &request, &mut subscription)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Invalid params"));
+    }
+}
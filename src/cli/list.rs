@@ -4,7 +4,7 @@ use anyhow::Result;
 use clap::Args;
 use serde::Serialize;
 
-use crate::session::{Instance, Storage};
+use crate::session::{Instance, SessionQuery, Storage};
 
 const TABLE_COL_TITLE: usize = 20;
 const TABLE_COL_GROUP: usize = 15;
@@ -20,6 +20,23 @@ pub struct ListArgs {
     /// List sessions from all profiles
     #[arg(long)]
     all: bool,
+
+    /// Only sessions whose title, path, or branch contains this text
+    #[arg(long)]
+    search: Option<String>,
+
+    /// Only sessions running this agent
+    #[arg(long)]
+    tool: Option<String>,
+}
+
+impl ListArgs {
+    fn query(&self) -> SessionQuery {
+        SessionQuery {
+            text: self.search.clone(),
+            tool: self.tool.clone(),
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -55,7 +72,7 @@ fn print_table_row(inst: &Instance) {
     let title = super::truncate(&inst.title, TABLE_COL_TITLE);
     let group = super::truncate(&inst.group_path, TABLE_COL_GROUP);
     let path = super::truncate(&inst.project_path, TABLE_COL_PATH);
-    let id_display = super::truncate_id(&inst.id, TABLE_COL_ID_DISPLAY);
+    let id_display = crate::session::truncate_id(&inst.id, TABLE_COL_ID_DISPLAY);
     println!(
         "{:<width_title$} {:<width_group$} {:<width_path$} {}",
         title,
@@ -69,15 +86,20 @@ fn print_table_row(inst: &Instance) {
 }
 
 pub async fn run(profile: &str, args: ListArgs) -> Result<()> {
+    let query = args.query();
     if args.all {
-        return run_all_profiles(args.json).await;
+        return run_all_profiles(args.json, &query).await;
     }
 
     let storage = Storage::new(profile)?;
-    let (instances, _) = storage.load_with_groups()?;
+    let instances = storage.query(&query)?;
 
     if instances.is_empty() {
-        println!("No sessions found in profile '{}'.", storage.profile());
+        if query == SessionQuery::default() {
+            println!("No sessions found in profile '{}'.", storage.profile());
+        } else {
+            println!("No matching sessions in profile '{}'.", storage.profile());
+        }
         return Ok(());
     }
 
@@ -111,7 +133,7 @@ pub async fn run(profile: &str, args: ListArgs) -> Result<()> {
     Ok(())
 }
 
-async fn run_all_profiles(json: bool) -> Result<()> {
+async fn run_all_profiles(json: bool, query: &SessionQuery) -> Result<()> {
     let profiles = crate::session::list_profiles()?;
 
     if profiles.is_empty() {
@@ -123,7 +145,7 @@ async fn run_all_profiles(json: bool) -> Result<()> {
         let mut all_sessions: Vec<SessionJson> = Vec::new();
         for profile_name in &profiles {
             if let Ok(storage) = Storage::new(profile_name) {
-                if let Ok((instances, _)) = storage.load_with_groups() {
+                if let Ok(instances) = storage.query(query) {
                     for inst in instances {
                         all_sessions.push(SessionJson {
                             id: inst.id,
@@ -146,7 +168,7 @@ async fn run_all_profiles(json: bool) -> Result<()> {
     let mut total_sessions = 0;
     for profile_name in &profiles {
         if let Ok(storage) = Storage::new(profile_name) {
-            if let Ok((instances, _)) = storage.load_with_groups() {
+            if let Ok(instances) = storage.query(query) {
                 if instances.is_empty() {
                     continue;
                 }
@@ -1,18 +1,32 @@
 //! CLI command implementations
 
 pub mod add;
+pub mod audit;
+pub mod config;
 pub mod definition;
 pub mod group;
 pub mod init;
+pub mod interactive;
+pub mod ipc;
 pub mod list;
 pub mod profile;
 pub mod remove;
+pub mod rename;
 pub mod send;
+pub mod serve;
 pub mod session;
+pub mod share;
+pub mod shim;
 pub mod sounds;
+pub mod stats;
 pub mod status;
+pub mod template;
 pub mod tmux;
+pub mod top;
+pub mod trash;
+pub mod tray;
 pub mod uninstall;
+pub mod wait;
 pub mod worktree;
 
 pub use definition::{Cli, Commands};
@@ -20,28 +34,39 @@ pub use definition::{Cli, Commands};
 use crate::session::Instance;
 use anyhow::{bail, Result};
 
+/// Find the session `identifier` names. A full session ID always wins: IDs
+/// never change, so scripts that use them keep working across renames.
+/// Otherwise an exact title is tried, then an ID prefix, then a project path.
+/// An identifier matching more than one session is an error rather than a
+/// guess.
 pub fn resolve_session<'a>(identifier: &str, instances: &'a [Instance]) -> Result<&'a Instance> {
-    // Try exact ID match
-    if let Some(inst) = instances.iter().find(|i| i.id == identifier) {
-        return Ok(inst);
-    }
-
-    // Try ID prefix match
-    if let Some(inst) = instances.iter().find(|i| i.id.starts_with(identifier)) {
-        return Ok(inst);
-    }
+    Ok(&instances[resolve_session_index(identifier, instances)?])
+}
 
-    // Try exact title match
-    if let Some(inst) = instances.iter().find(|i| i.title == identifier) {
-        return Ok(inst);
+/// Like `resolve_session`, returning the session's index in `instances`.
+pub fn resolve_session_index(identifier: &str, instances: &[Instance]) -> Result<usize> {
+    let identifier = identifier.trim();
+    if identifier.is_empty() {
+        bail!("No session given");
     }
 
-    // Try path match
-    if let Some(inst) = instances.iter().find(|i| i.project_path == identifier) {
-        return Ok(inst);
+    let matches = crate::session::match_sessions(identifier, instances);
+    match matches.as_slice() {
+        [] => bail!("Session not found: {}", identifier),
+        [index] => Ok(*index),
+        _ => {
+            let candidates: Vec<String> = matches
+                .iter()
+                .map(|&i| format!("{} ({})", instances[i].short_id(), instances[i].title))
+                .collect();
+            bail!(
+                "'{}' matches more than one session: {}\n\
+                 Tip: Use the session's ID, shown by `aoe list`",
+                identifier,
+                candidates.join(", ")
+            )
+        }
     }
-
-    bail!("Session not found: {}", identifier)
 }
 
 pub fn truncate(s: &str, max: usize) -> String {
@@ -56,10 +81,56 @@ pub fn truncate(s: &str, max: usize) -> String {
     }
 }
 
-pub fn truncate_id(id: &str, max_len: usize) -> &str {
-    if id.len() > max_len {
-        &id[..max_len]
-    } else {
-        id
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str, title: &str, path: &str) -> Instance {
+        let mut inst = Instance::new(title, path);
+        inst.id = id.to_string();
+        inst
+    }
+
+    #[test]
+    fn test_resolve_session_ranks_ids_then_titles_and_refuses_ambiguity() {
+        let instances = vec![
+            session("a1b2c3d4e5f60718", "api", "/src/api"),
+            session("f00dcafe00000000", "a1b2c3d4", "/src/web"),
+            session("beef000000000000", "web", "/src/web"),
+        ];
+        let title = |id: &str| resolve_session(id, &instances).map(|i| i.title.clone());
+        let duplicates = vec![
+            session("dup0000000000000", "dup", "/src/a"),
+            session("dup1000000000000", "dup", "/src/b"),
+        ];
+
+        // A full ID wins even over a title that looks like it
+        assert_eq!(title("a1b2c3d4e5f60718").unwrap(), "api");
+        assert_eq!(title("f00d").unwrap(), "a1b2c3d4");
+        assert_eq!(title("web").unwrap(), "web");
+
+        // An exact title beats another session's ID prefix, even a hex-looking one
+        assert_eq!(title("a1b2c3d4").unwrap(), "a1b2c3d4");
+        assert_eq!(title("a1b2").unwrap(), "api");
+
+        // Titles and ID prefixes shared by several sessions are refused
+        let err = resolve_session("dup", &duplicates).unwrap_err().to_string();
+        assert!(
+            err.contains("dup00000 (dup)") && err.contains("dup10000 (dup)"),
+            "{}",
+            err
+        );
+        assert!(resolve_session("dup", &duplicates[..1]).is_ok());
+        assert!(resolve_session("du", &duplicates).is_err());
+        assert_eq!(
+            resolve_session("dup1", &duplicates).unwrap().id,
+            duplicates[1].id
+        );
+
+        // Paths only count when nothing matched by ID or title, and must be unique
+        assert_eq!(title("/src/api").unwrap(), "api");
+        assert!(title("/src/web").is_err());
+        assert!(title("nope").is_err());
+        assert!(title("").is_err());
     }
 }
@@ -2,9 +2,11 @@
 
 use anyhow::{bail, Result};
 use clap::Subcommand;
-use std::io::{self, Write};
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
 
 use crate::session;
+use crate::session::profile_bundle::{self, ProfileBundle};
 
 #[derive(Subcommand)]
 pub enum ProfileCommands {
@@ -40,6 +42,26 @@ pub enum ProfileCommands {
         /// Profile name (optional, shows current if not provided)
         name: Option<String>,
     },
+
+    /// Write a profile's settings as a shareable bundle to stdout
+    Export {
+        /// Profile name
+        name: String,
+    },
+
+    /// Create or update a profile from a bundle written by `profile export`
+    Import {
+        /// Bundle file path, or `-` to read from stdin
+        file: PathBuf,
+
+        /// Import under this profile name instead of the one in the bundle
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Overwrite an existing profile's config and templates that differ
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 pub async fn run(command: Option<ProfileCommands>) -> Result<()> {
@@ -57,6 +79,10 @@ pub async fn run(command: Option<ProfileCommands>) -> Result<()> {
                 show_default_profile().await
             }
         }
+        Some(ProfileCommands::Export { name }) => export_profile(&name).await,
+        Some(ProfileCommands::Import { file, name, force }) => {
+            import_profile(&file, name.as_deref(), force).await
+        }
     }
 }
 
@@ -141,3 +167,40 @@ async fn set_default_profile(name: &str) -> Result<()> {
     println!("✓ Default profile set to: {}", name);
     Ok(())
 }
+
+async fn export_profile(name: &str) -> Result<()> {
+    let bundle = profile_bundle::export_profile(name)?;
+    print!("{}", bundle.to_toml()?);
+    io::stdout().flush()?;
+    Ok(())
+}
+
+async fn import_profile(file: &std::path::Path, name: Option<&str>, force: bool) -> Result<()> {
+    let content = if file.as_os_str() == "-" {
+        let mut buf = String::new();
+        io::stdin().read_to_string(&mut buf)?;
+        buf
+    } else {
+        std::fs::read_to_string(file)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file.display(), e))?
+    };
+    let bundle = ProfileBundle::from_toml(&content)?;
+    let target = name.unwrap_or(&bundle.profile);
+
+    profile_bundle::import_profile(&bundle, target, force)?;
+    println!("✓ Imported profile: {}", target);
+    if !bundle.templates.is_empty() {
+        let names: Vec<&str> = bundle.templates.keys().map(String::as_str).collect();
+        println!("  Templates: {}", names.join(", "));
+    }
+
+    let hooks = bundle.hook_commands();
+    if !hooks.is_empty() {
+        println!("  This profile runs hooks on session create/launch:");
+        for cmd in hooks {
+            println!("    {}", cmd);
+        }
+    }
+    println!("  Use with: agent-of-empires -p {}", target);
+    Ok(())
+}
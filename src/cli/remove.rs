@@ -1,17 +1,17 @@
 //! `agent-of-empires remove` command implementation
 
-use anyhow::{bail, Result};
+use anyhow::Result;
 use clap::Args;
 
 use crate::containers;
 use crate::git::cleanup::remove_managed_worktree;
 use crate::git::GitWorktree;
-use crate::session::{GroupTree, Instance, Storage};
+use crate::session::{ownership, stats, timeline, trash, GroupTree, Instance, Storage};
 use std::path::PathBuf;
 
 #[derive(Args)]
 pub struct RemoveArgs {
-    /// Session ID or title to remove
+    /// Session ID (or a unique prefix of it) or title to remove
     identifier: String,
 
     /// Delete worktree directory (default: keep worktree)
@@ -29,6 +29,10 @@ pub struct RemoveArgs {
     /// Keep container instead of deleting it (default: delete per config)
     #[arg(long = "keep-container")]
     keep_container: bool,
+
+    /// Remove a session owned by another user or host
+    #[arg(long)]
+    steal: bool,
 }
 
 fn needs_worktree_cleanup(inst: &Instance, args: &RemoveArgs) -> bool {
@@ -42,17 +46,18 @@ pub async fn run(profile: &str, args: RemoveArgs) -> Result<()> {
     let (instances, groups) = storage.load_with_groups()?;
     let config = crate::session::resolve_config(profile).unwrap_or_default();
 
-    let mut found = false;
     let mut removed_title = String::new();
+    let mut trashed = false;
     let mut new_instances = Vec::with_capacity(instances.len());
 
-    for inst in instances {
-        if inst.id == args.identifier
-            || inst.id.starts_with(&args.identifier)
-            || inst.title == args.identifier
-        {
-            found = true;
+    let id = super::resolve_session(&args.identifier, &instances)?
+        .id
+        .clone();
+    for mut inst in instances {
+        if inst.id == id {
+            ownership::claim(&mut inst, args.steal)?;
             removed_title = inst.title.clone();
+            stats::record_removed(&inst, storage.profile());
 
             let will_cleanup_worktree = needs_worktree_cleanup(&inst, &args);
             // Delete branch if explicitly requested, or if worktree is being
@@ -165,19 +170,19 @@ pub async fn run(profile: &str, args: RemoveArgs) -> Result<()> {
                     println!("Container preserved: {}", sandbox.container_name);
                 }
             }
+
+            match trash::move_to_trash(storage.profile(), &inst) {
+                Ok(kept) => trashed = kept,
+                Err(e) => {
+                    eprintln!("Warning: failed to move session to trash: {}", e);
+                    timeline::remove(&inst.id);
+                }
+            }
         } else {
             new_instances.push(inst);
         }
     }
 
-    if !found {
-        bail!(
-            "Session not found in profile '{}': {}",
-            storage.profile(),
-            args.identifier
-        );
-    }
-
     // Rebuild group tree and save
     let group_tree = GroupTree::new_with_groups(&new_instances, &groups);
     storage.save_with_groups(&new_instances, &group_tree)?;
@@ -187,6 +192,12 @@ pub async fn run(profile: &str, args: RemoveArgs) -> Result<()> {
         removed_title,
         storage.profile()
     );
+    if trashed {
+        println!(
+            "  Moved to trash (restore with: aoe trash restore \"{}\")",
+            removed_title
+        );
+    }
 
     Ok(())
 }
@@ -0,0 +1,82 @@
+//! `agent-of-empires rename` command implementation
+
+use anyhow::{bail, Result};
+use clap::Args;
+
+use crate::session::bulk_rename::{self, Pattern};
+use crate::session::{ownership, GroupTree, Storage};
+
+#[derive(Args)]
+#[command(
+    after_help = "Patterns use `*` for any run of characters and `?` for one character. \
+    Each wildcard in the replacement takes what the wildcard in the same position matched:\n  \
+    aoe rename --match 'old-*' --replace 'new-*'"
+)]
+pub struct RenameArgs {
+    /// Pattern matched against whole session titles
+    #[arg(long = "match", value_name = "PATTERN")]
+    pattern: String,
+
+    /// New title for each matching session
+    #[arg(long, value_name = "PATTERN")]
+    replace: String,
+
+    /// Show what would be renamed without changing anything
+    #[arg(short = 'n', long)]
+    dry_run: bool,
+
+    /// Rename sessions owned by another user or host, taking them over
+    #[arg(long)]
+    steal: bool,
+}
+
+pub async fn run(profile: &str, args: RenameArgs) -> Result<()> {
+    let pattern = Pattern::new(&args.pattern, &args.replace).map_err(anyhow::Error::msg)?;
+
+    let storage = Storage::new(profile)?;
+    let (mut instances, groups) = storage.load_with_groups()?;
+
+    let renames = bulk_rename::plan(&pattern, &instances).map_err(anyhow::Error::msg)?;
+    if renames.is_empty() {
+        println!("No sessions match '{}'", args.pattern);
+        return Ok(());
+    }
+
+    if args.dry_run {
+        for rename in &renames {
+            println!("  {} → {}", rename.from, rename.to);
+        }
+        println!("{} session(s) would be renamed", renames.len());
+        return Ok(());
+    }
+
+    // Check every session before renaming any of them
+    for rename in &renames {
+        let inst = instances.iter().find(|i| i.id == rename.id);
+        if let Some(owner) = inst.and_then(ownership::foreign_owner) {
+            if !args.steal {
+                bail!(
+                    "Session '{}' is owned by {}. Pass --steal to take it over.",
+                    rename.from,
+                    owner
+                );
+            }
+        }
+    }
+    for rename in &renames {
+        if let Some(inst) = instances.iter_mut().find(|i| i.id == rename.id) {
+            ownership::claim(inst, args.steal)?;
+        }
+    }
+
+    bulk_rename::apply(&mut instances, &renames)?;
+
+    let group_tree = GroupTree::new_with_groups(&instances, &groups);
+    storage.save_with_groups(&instances, &group_tree)?;
+
+    for rename in &renames {
+        println!("✓ Renamed session: {} → {}", rename.from, rename.to);
+    }
+
+    Ok(())
+}
@@ -1,38 +1,102 @@
 //! `agent-of-empires send` subcommand implementation
 
-use anyhow::{bail, Result};
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
 use clap::Args;
 
-use crate::session::Storage;
+use crate::session::guardrails::{self, Guardrails};
+use crate::session::{Instance, Storage};
 
 #[derive(Args)]
 pub struct SendArgs {
-    /// Session ID or title
+    /// Session ID (or a unique prefix of it) or title
     identifier: String,
 
     /// Message to send to the agent
-    message: String,
+    #[arg(required_unless_present = "file", conflicts_with = "file")]
+    message: Option<String>,
+
+    /// Send the contents of a file as one message, pasted with bracketed
+    /// paste so multi-line prompts arrive intact
+    #[arg(long, value_name = "PATH")]
+    file: Option<PathBuf>,
+
+    /// Send even if the message matches a prompt guardrail (the override is
+    /// recorded on the session timeline)
+    #[arg(short, long)]
+    force: bool,
+
+    /// Send to a session owned by another user or host, taking it over
+    #[arg(long)]
+    steal: bool,
 }
 
 pub async fn run(profile: &str, args: SendArgs) -> Result<()> {
     let storage = Storage::new(profile)?;
     let (instances, _) = storage.load_with_groups()?;
 
-    if args.message.trim().is_empty() {
+    let message = match (&args.message, &args.file) {
+        (_, Some(path)) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?,
+        (Some(message), None) => message.clone(),
+        (None, None) => bail!("Provide a message or --file"),
+    };
+    if message.trim().is_empty() {
         bail!("Message cannot be empty");
     }
 
     let inst = super::resolve_session(&args.identifier, &instances)?;
-    let tmux_session = crate::tmux::Session::new(&inst.id, &inst.title)?;
+    crate::session::ownership::claim_stored(&storage, &inst.id, args.steal)?;
+    deliver(profile, inst, &message, args.force, args.file.is_some())?;
+    println!("Sent message to '{}'", inst.title);
+    Ok(())
+}
 
+/// Send `message` to a running session, checking prompt guardrails and
+/// recording it on the timeline. `paste` sends it with bracketed paste, for
+/// multi-line text.
+pub(crate) fn deliver(
+    profile: &str,
+    inst: &Instance,
+    message: &str,
+    force: bool,
+    paste: bool,
+) -> Result<()> {
+    let tmux_session = crate::tmux::Session::new(&inst.id, &inst.title)?;
     if !tmux_session.exists() {
         bail!(
             "Session is not running. Start it first with: aoe session start {}",
-            args.identifier
+            inst.title
         );
     }
 
-    tmux_session.send_keys(&args.message)?;
-    println!("Sent message to '{}'", inst.title);
+    let matched = guardrail_matches(profile, inst, message);
+    if !matched.is_empty() && !force {
+        bail!(
+            "Message matches prompt guardrail {}. Re-run with --force to send it anyway",
+            guardrails::describe(&matched)
+        );
+    }
+
+    if paste {
+        tmux_session.paste_text(message.trim_end())?;
+    } else {
+        tmux_session.send_keys(message)?;
+    }
+    crate::session::timeline::record_prompt(inst, message);
+    if !matched.is_empty() {
+        guardrails::record_override(&inst.id, &matched);
+    }
     Ok(())
 }
+
+/// Prompt guardrails of the session's project that `message` matches
+pub(crate) fn guardrail_matches(profile: &str, inst: &Instance, message: &str) -> Vec<String> {
+    let source_profile = if inst.source_profile.is_empty() {
+        profile
+    } else {
+        &inst.source_profile
+    };
+    Guardrails::for_project(source_profile, &inst.project_path).check(message)
+}
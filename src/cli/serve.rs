@@ -0,0 +1,324 @@
+//! `agent-of-empires serve` command implementation
+//!
+//! A small read-only HTTP server: `GET /api/sessions` returns session status
+//! and recent output as JSON, and `GET /` serves a single-page dashboard that
+//! polls it. There is deliberately nothing that can start, stop, or send
+//! input to a session.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use clap::Args;
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::session::{Instance, Storage};
+
+const DASHBOARD_HTML: &str = include_str!("dashboard.html");
+
+/// Requests larger than this are rejected; a dashboard GET is tiny.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+#[derive(Args)]
+pub struct ServeArgs {
+    /// Address to listen on. Use 0.0.0.0 to reach the dashboard from other devices
+    #[arg(long, default_value = "127.0.0.1")]
+    host: IpAddr,
+
+    /// Port to listen on
+    #[arg(long, default_value_t = 8080)]
+    port: u16,
+
+    /// Access token required on every request (generated at startup if not given)
+    #[arg(long)]
+    token: Option<String>,
+
+    /// Number of output lines included per session
+    #[arg(long, default_value_t = 20)]
+    lines: usize,
+}
+
+#[derive(Serialize)]
+struct SessionView {
+    id: String,
+    title: String,
+    path: String,
+    group: String,
+    tool: String,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+    output: String,
+}
+
+struct ServerState {
+    profile: String,
+    token: String,
+    /// Set when listening on a loopback address: the port requests must name
+    /// in their `Host` header
+    loopback_port: Option<u16>,
+    lines: usize,
+}
+
+pub async fn run(profile: &str, args: ServeArgs) -> Result<()> {
+    // Even on loopback: any web page the user opens can reach a local port,
+    // and DNS rebinding gets around the browser's same-origin checks
+    let token = match args.token {
+        Some(t) if t.is_empty() => bail!("--token cannot be empty"),
+        Some(t) => t,
+        None => uuid::Uuid::new_v4().simple().to_string(),
+    };
+
+    let addr = SocketAddr::new(args.host, args.port);
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to listen on {}: {}", addr, e))?;
+
+    let shown_host = if args.host.is_unspecified() {
+        "<this-machine-ip>".to_string()
+    } else {
+        args.host.to_string()
+    };
+    println!("Serving read-only dashboard on http://{}", addr);
+    println!(
+        "  Open: http://{}:{}/?token={}",
+        shown_host, args.port, token
+    );
+    println!("  Press Ctrl+C to stop");
+
+    let state = Arc::new(ServerState {
+        profile: profile.to_string(),
+        token,
+        loopback_port: args.host.is_loopback().then_some(args.port),
+        lines: args.lines,
+    });
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &state).await {
+                tracing::debug!("Dashboard request failed: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, state: &ServerState) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if buf.len() > MAX_REQUEST_BYTES {
+            return respond(&mut stream, 413, "text/plain", "Request too large").await;
+        }
+    }
+
+    let request = String::from_utf8_lossy(&buf);
+    let Some((method, target)) = parse_request_line(&request) else {
+        return respond(&mut stream, 400, "text/plain", "Bad request").await;
+    };
+    if method != "GET" {
+        return respond(&mut stream, 405, "text/plain", "Method not allowed").await;
+    }
+
+    if let Some(port) = state.loopback_port {
+        if !is_local_host(&request, port) {
+            return respond(&mut stream, 403, "text/plain", "Unexpected Host header").await;
+        }
+    }
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    if !is_authorized(&state.token, query, &request) {
+        return respond(&mut stream, 401, "text/plain", "Missing or invalid token").await;
+    }
+
+    match path {
+        "/" => respond(&mut stream, 200, "text/html; charset=utf-8", DASHBOARD_HTML).await,
+        "/api/sessions" => {
+            let profile = state.profile.clone();
+            let lines = state.lines;
+            let sessions = tokio::task::spawn_blocking(move || load_sessions(&profile, lines))
+                .await?
+                .and_then(|s| Ok(serde_json::to_string(&s)?));
+            match sessions {
+                Ok(json) => respond(&mut stream, 200, "application/json", &json).await,
+                Err(e) => {
+                    let body = serde_json::json!({ "error": format!("{:#}", e) }).to_string();
+                    respond(&mut stream, 500, "application/json", &body).await
+                }
+            }
+        }
+        _ => respond(&mut stream, 404, "text/plain", "Not found").await,
+    }
+}
+
+fn parse_request_line(request: &str) -> Option<(&str, &str)> {
+    let mut parts = request.lines().next()?.split_whitespace();
+    let method = parts.next()?;
+    let target = parts.next()?;
+    Some((method, target))
+}
+
+/// The value of header `name`, if the request has it
+fn header<'a>(request: &'a str, name: &str) -> Option<&'a str> {
+    request.lines().skip(1).find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then_some(value.trim())
+    })
+}
+
+/// Accept the token either as `?token=` (so the dashboard URL can be
+/// bookmarked on a phone) or as an `Authorization: Bearer` header.
+fn is_authorized(token: &str, query: &str, request: &str) -> bool {
+    let in_query = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .any(|(k, v)| k == "token" && constant_time_eq(v, token));
+    let in_header = header(request, "authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|value| constant_time_eq(value.trim(), token));
+    in_query || in_header
+}
+
+/// Compare without stopping at the first differing byte, so response times
+/// don't reveal how much of a guess was right.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+/// Whether a request to a loopback listener names it by a loopback host.
+/// A page that rebinds its own domain to 127.0.0.1 still sends that domain.
+fn is_local_host(request: &str, port: u16) -> bool {
+    let Some(host) = header(request, "host") else {
+        return false;
+    };
+    let expected_port = port.to_string();
+    ["localhost", "127.0.0.1", "[::1]"].iter().any(|name| {
+        host.strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .is_some_and(|p| p == expected_port)
+            || (port == 80 && host == *name)
+    })
+}
+
+async fn respond(stream: &mut TcpStream, code: u16, content_type: &str, body: &str) -> Result<()> {
+    let reason = match code {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        code,
+        reason,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+fn load_sessions(profile: &str, lines: usize) -> Result<Vec<SessionView>> {
+    let storage = Storage::new(profile)?;
+    let (mut instances, _) = storage.load_with_groups()?;
+
+    crate::tmux::refresh_session_cache();
+    for inst in &mut instances {
+        inst.update_status();
+    }
+
+    Ok(instances
+        .iter()
+        .map(|inst| session_view(inst, lines))
+        .collect())
+}
+
+fn session_view(inst: &Instance, lines: usize) -> SessionView {
+    let output = inst
+        .tmux_session()
+        .and_then(|s| s.capture_pane(lines))
+        .map(|raw| crate::tmux::utils::strip_ansi(&raw))
+        .unwrap_or_default();
+    SessionView {
+        id: inst.id.clone(),
+        title: inst.title.clone(),
+        path: inst.project_path.clone(),
+        group: inst.group_path.clone(),
+        tool: inst.tool.clone(),
+        status: format!("{:?}", inst.status).to_lowercase(),
+        branch: inst.worktree_info.as_ref().map(|w| w.branch.clone()),
+        output: output.trim_end().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_line() {
+        let req = "GET /api/sessions?token=abc HTTP/1.1\r\nHost: x\r\n\r\n";
+        assert_eq!(
+            parse_request_line(req),
+            Some(("GET", "/api/sessions?token=abc"))
+        );
+        assert_eq!(parse_request_line(""), None);
+        assert_eq!(parse_request_line("GET\r\n"), None);
+    }
+
+    #[test]
+    fn test_is_local_host() {
+        let req = |host: &str| format!("GET / HTTP/1.1\r\nHost: {}\r\n\r\n", host);
+        assert!(is_local_host(&req("127.0.0.1:8080"), 8080));
+        assert!(is_local_host(&req("localhost:8080"), 8080));
+        assert!(is_local_host(&req("[::1]:8080"), 8080));
+        assert!(!is_local_host(&req("localhost:9090"), 8080));
+        assert!(!is_local_host(&req("evil.example:8080"), 8080));
+        assert!(!is_local_host(&req("localhost.evil.example:8080"), 8080));
+        assert!(!is_local_host("GET / HTTP/1.1\r\n\r\n", 8080));
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq("secret", "secret"));
+        assert!(!constant_time_eq("secret", "secreT"));
+        assert!(!constant_time_eq("secret", "secret2"));
+        assert!(!constant_time_eq("", "secret"));
+    }
+
+    #[test]
+    fn test_is_authorized_with_query_token() {
+        let req = "GET /?token=secret HTTP/1.1\r\n\r\n";
+        assert!(is_authorized("secret", "token=secret", req));
+        assert!(is_authorized("secret", "a=1&token=secret", req));
+        assert!(!is_authorized("secret", "token=wrong", req));
+        assert!(!is_authorized("secret", "", req));
+    }
+
+    #[test]
+    fn test_is_authorized_with_bearer_header() {
+        let req = "GET /api/sessions HTTP/1.1\r\nauthorization: Bearer secret\r\n\r\n";
+        assert!(is_authorized("secret", "", req));
+        let req = "GET /api/sessions HTTP/1.1\r\nAuthorization: Bearer nope\r\n\r\n";
+        assert!(!is_authorized("secret", "", req));
+    }
+}
@@ -1,10 +1,21 @@
 //! `agent-of-empires session` subcommands implementation
 
+use std::path::PathBuf;
+
 use anyhow::{bail, Result};
 use clap::{Args, Subcommand};
 use serde::Serialize;
 
-use crate::session::{GroupTree, Storage};
+use crate::session::checkpoints;
+use crate::session::journal;
+use crate::session::locks::{self, LockOwner};
+use crate::session::notes;
+use crate::session::ownership;
+use crate::session::project_env::ProjectEnv;
+use crate::session::providers;
+use crate::session::timeline;
+use crate::session::transcript;
+use crate::session::{resolve_config, GroupTree, Storage};
 
 #[derive(Subcommand)]
 pub enum SessionCommands {
@@ -18,7 +29,7 @@ pub enum SessionCommands {
     Restart(SessionIdArgs),
 
     /// Attach to session interactively
-    Attach(SessionIdArgs),
+    Attach(AttachArgs),
 
     /// Show session details
     Show(ShowArgs),
@@ -29,19 +40,39 @@ pub enum SessionCommands {
     /// Capture tmux pane output
     Capture(CaptureArgs),
 
+    /// Show or edit a session's notes
+    Notes(NotesArgs),
+
+    /// List, create, or roll back to a session's named checkpoints
+    Checkpoint(CheckpointArgs),
+
     /// Auto-detect current session
     Current(CurrentArgs),
 }
 
 #[derive(Args)]
 pub struct SessionIdArgs {
-    /// Session ID or title
+    /// Session ID (or a unique prefix of it) or title
     identifier: String,
+
+    /// Act on a session owned by another user or host, taking it over
+    #[arg(long)]
+    steal: bool,
+}
+
+#[derive(Args)]
+pub struct AttachArgs {
+    /// Session ID (or a unique prefix of it) or title
+    identifier: String,
+
+    /// Watch without being able to type into the session (tmux attach -r)
+    #[arg(short = 'r', long, visible_alias = "read-only")]
+    observe: bool,
 }
 
 #[derive(Args)]
 pub struct RenameArgs {
-    /// Session ID or title (optional, auto-detects in tmux)
+    /// Session ID (or a unique prefix of it) or title (optional, auto-detects in tmux)
     identifier: Option<String>,
 
     /// New title for the session
@@ -51,11 +82,20 @@ pub struct RenameArgs {
     /// New group for the session (empty string to ungroup)
     #[arg(short, long)]
     group: Option<String>,
+
+    /// New icon for the session: an emoji or nerd-font glyph (empty string
+    /// to remove it)
+    #[arg(long)]
+    icon: Option<String>,
+
+    /// Rename a session owned by another user or host, taking it over
+    #[arg(long)]
+    steal: bool,
 }
 
 #[derive(Args)]
 pub struct ShowArgs {
-    /// Session ID or title (optional, auto-detects in tmux)
+    /// Session ID (or a unique prefix of it) or title (optional, auto-detects in tmux)
     identifier: Option<String>,
 
     /// Output as JSON
@@ -65,7 +105,7 @@ pub struct ShowArgs {
 
 #[derive(Args)]
 pub struct CaptureArgs {
-    /// Session ID or title (auto-detects in tmux if omitted)
+    /// Session ID (or a unique prefix of it) or title (auto-detects in tmux if omitted)
     identifier: Option<String>,
 
     /// Number of lines to capture
@@ -79,6 +119,68 @@ pub struct CaptureArgs {
     /// Output as JSON
     #[arg(long)]
     json: bool,
+
+    /// Write a transcript file instead: plain text, preceded by TOML
+    /// front-matter describing the session
+    #[arg(short, long, value_name = "FILE", conflicts_with = "json")]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct NotesArgs {
+    /// Session ID (or a unique prefix of it) or title (optional, auto-detects in tmux)
+    identifier: Option<String>,
+
+    /// Replace the notes with this text
+    #[arg(short, long, conflicts_with_all = ["append", "edit", "clear"])]
+    set: Option<String>,
+
+    /// Append a line to the notes
+    #[arg(short, long, conflicts_with_all = ["edit", "clear"])]
+    append: Option<String>,
+
+    /// Edit the notes in $EDITOR
+    #[arg(short, long, conflicts_with = "clear")]
+    edit: bool,
+
+    /// Remove the notes
+    #[arg(long)]
+    clear: bool,
+}
+
+#[derive(Args)]
+pub struct CheckpointArgs {
+    /// Session ID (or a unique prefix of it) or title (optional, auto-detects in tmux)
+    identifier: Option<String>,
+
+    /// Record a checkpoint with this name
+    #[arg(short, long, conflicts_with_all = ["rollback", "remove", "show"])]
+    add: Option<String>,
+
+    /// Note to keep with the new checkpoint
+    #[arg(long, requires = "add")]
+    note: Option<String>,
+
+    /// Restore the session's directory to this checkpoint
+    #[arg(long, conflicts_with_all = ["remove", "show"])]
+    rollback: Option<String>,
+
+    /// Delete this checkpoint
+    #[arg(long, conflicts_with = "show")]
+    remove: Option<String>,
+
+    /// Show this checkpoint and the agent's output since it
+    #[arg(long)]
+    show: Option<String>,
+
+    /// Output the list as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Change the checkpoints of a session owned by another user or host,
+    /// taking it over
+    #[arg(long)]
+    steal: bool,
 }
 
 #[derive(Args)]
@@ -106,14 +208,32 @@ struct CaptureOutput {
 struct SessionDetails {
     id: String,
     title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<String>,
     path: String,
     group: String,
     tool: String,
+    provider: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failed_over_from: Option<String>,
     command: String,
     status: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     parent_session_id: Option<String>,
     profile: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issue_url: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locks: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    final_summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner: Option<String>,
+    orchestrator: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project_env: Option<ProjectEnv>,
 }
 
 pub async fn run(profile: &str, command: SessionCommands) -> Result<()> {
@@ -125,28 +245,56 @@ pub async fn run(profile: &str, command: SessionCommands) -> Result<()> {
         SessionCommands::Show(args) => show_session(profile, args).await,
         SessionCommands::Capture(args) => capture_session(profile, args).await,
         SessionCommands::Rename(args) => rename_session(profile, args).await,
+        SessionCommands::Notes(args) => session_notes(profile, args).await,
+        SessionCommands::Checkpoint(args) => session_checkpoint(profile, args).await,
         SessionCommands::Current(args) => current_session(args).await,
     }
 }
 
 async fn start_session(profile: &str, args: SessionIdArgs) -> Result<()> {
+    start(profile, &args.identifier, args.steal)
+}
+
+/// Start the session `identifier` names without attaching, failing over to
+/// its fallback agent if it does not start.
+pub(crate) fn start(profile: &str, identifier: &str, steal: bool) -> Result<()> {
     let storage = Storage::new(profile)?;
     let (mut instances, groups) = storage.load_with_groups()?;
 
-    let idx = instances
-        .iter()
-        .position(|i| {
-            i.id == args.identifier
-                || i.id.starts_with(&args.identifier)
-                || i.title == args.identifier
-        })
-        .ok_or_else(|| anyhow::anyhow!("Session not found: {}", args.identifier))?;
-
-    instances[idx].start_with_size(crate::terminal::get_size())?;
-    let title = instances[idx].title.clone();
+    let idx = super::resolve_session_index(identifier, &instances)?;
+
+    ownership::claim(&mut instances[idx], steal)?;
+    locks::ensure_can_start(&instances[idx])?;
+    let config = resolve_config(profile).unwrap_or_default();
+    providers::ensure_can_start(&instances[idx], &config.session)?;
+    let inst = &mut instances[idx];
+    let old_status = inst.status;
+    let mut result = inst.start_with_size(crate::terminal::get_size());
+    if let Err(e) = &result {
+        if let Some(from) = inst.fail_over() {
+            eprintln!(
+                "{} failed to start ({:#}), switching to fallback {}",
+                from, e, inst.tool
+            );
+            timeline::record(
+                &inst.id,
+                timeline::EventKind::Status,
+                format!(
+                    "failover: {} failed to start, switched to {}",
+                    from, inst.tool
+                ),
+            );
+            result = inst.start_with_size(crate::terminal::get_size());
+        }
+    }
+    let title = inst.title.clone();
+    if result.is_ok() {
+        journal::record_transition(storage.profile(), inst, old_status, inst.status);
+    }
 
     let group_tree = GroupTree::new_with_groups(&instances, &groups);
     storage.save_with_groups(&instances, &group_tree)?;
+    result?;
 
     println!("✓ Started session: {}", title);
     Ok(())
@@ -171,12 +319,16 @@ async fn stop_session(profile: &str, args: SessionIdArgs) -> Result<()> {
         return Ok(());
     }
 
-    inst.stop()?;
+    let stored = instances
+        .iter_mut()
+        .find(|i| i.id == session_id)
+        .ok_or_else(|| anyhow::anyhow!("Session not found"))?;
+    ownership::claim(stored, args.steal)?;
+    stored.stop()?;
 
     // Persist Stopped status to disk so it survives TUI restarts
-    if let Some(stored) = instances.iter_mut().find(|i| i.id == session_id) {
-        stored.status = crate::session::Status::Stopped;
-    }
+    let old_status = std::mem::replace(&mut stored.status, crate::session::Status::Stopped);
+    journal::record_transition(storage.profile(), stored, old_status, stored.status);
     let group_tree = crate::session::GroupTree::new_with_groups(&instances, &groups);
     storage.save_with_groups(&instances, &group_tree)?;
 
@@ -193,15 +345,9 @@ async fn restart_session(profile: &str, args: SessionIdArgs) -> Result<()> {
     let storage = Storage::new(profile)?;
     let (mut instances, groups) = storage.load_with_groups()?;
 
-    let idx = instances
-        .iter()
-        .position(|i| {
-            i.id == args.identifier
-                || i.id.starts_with(&args.identifier)
-                || i.title == args.identifier
-        })
-        .ok_or_else(|| anyhow::anyhow!("Session not found: {}", args.identifier))?;
+    let idx = super::resolve_session_index(&args.identifier, &instances)?;
 
+    ownership::claim(&mut instances[idx], args.steal)?;
     instances[idx].restart_with_size(crate::terminal::get_size())?;
     let title = instances[idx].title.clone();
 
@@ -212,7 +358,7 @@ async fn restart_session(profile: &str, args: SessionIdArgs) -> Result<()> {
     Ok(())
 }
 
-async fn attach_session(profile: &str, args: SessionIdArgs) -> Result<()> {
+async fn attach_session(profile: &str, args: AttachArgs) -> Result<()> {
     let storage = Storage::new(profile)?;
     let (instances, _) = storage.load_with_groups()?;
 
@@ -226,7 +372,11 @@ async fn attach_session(profile: &str, args: SessionIdArgs) -> Result<()> {
         );
     }
 
-    tmux_session.attach()?;
+    if args.observe {
+        tmux_session.attach_read_only()?;
+    } else {
+        tmux_session.attach()?;
+    }
     Ok(())
 }
 
@@ -245,10 +395,7 @@ async fn show_session(profile: &str, args: ShowArgs) -> Result<()> {
         if let Some(session_name) = current_session {
             instances
                 .iter()
-                .find(|i| {
-                    let tmux_name = crate::tmux::Session::generate_name(&i.id, &i.title);
-                    tmux_name == session_name
-                })
+                .find(|i| crate::tmux::Session::name_matches_id(&session_name, &i.id))
                 .ok_or_else(|| {
                     anyhow::anyhow!("Current tmux session is not an Agent of Empires session")
                 })?
@@ -261,27 +408,89 @@ async fn show_session(profile: &str, args: ShowArgs) -> Result<()> {
         let details = SessionDetails {
             id: inst.id.clone(),
             title: inst.title.clone(),
+            icon: inst.icon.clone(),
             path: inst.project_path.clone(),
             group: inst.group_path.clone(),
             tool: inst.tool.clone(),
+            provider: inst.provider().name(),
+            failed_over_from: inst.failed_over_from.clone(),
             command: inst.command.clone(),
             status: format!("{:?}", inst.status).to_lowercase(),
             parent_session_id: inst.parent_session_id.clone(),
             profile: storage.profile().to_string(),
+            issue_url: inst.issue_info.as_ref().map(|i| i.url.clone()),
+            locks: inst.locks.clone(),
+            notes: inst.notes.clone(),
+            final_summary: inst.final_summary.clone(),
+            owner: inst.owner.clone(),
+            orchestrator: inst.orchestrator.is_some(),
+            project_env: inst.project_env.clone(),
         };
         println!("{}", serde_json::to_string_pretty(&details)?);
     } else {
         println!("Session: {}", inst.title);
         println!("  ID:      {}", inst.id);
+        if let Some(icon) = &inst.icon {
+            println!("  Icon:    {}", icon);
+        }
         println!("  Path:    {}", inst.project_path);
         println!("  Group:   {}", inst.group_path);
-        println!("  Tool:    {}", inst.tool);
+        match &inst.failed_over_from {
+            Some(from) => println!("  Tool:    {} (fallback for {})", inst.tool, from),
+            None => println!("  Tool:    {}", inst.tool),
+        }
+        println!("  Provider: {}", inst.provider().name());
         println!("  Command: {}", inst.command);
-        println!("  Status:  {:?}", inst.status);
+        let mut live = inst.clone();
+        live.update_status();
+        if live.auth_required {
+            println!(
+                "  Status:  {:?} (auth required, run the agent's login or press A in the TUI)",
+                live.status
+            );
+        } else {
+            println!("  Status:  {:?}", inst.status);
+        }
         println!("  Profile: {}", storage.profile());
+        if let Some(owner) = &inst.owner {
+            println!("  Owner:   {}", owner);
+        }
         if let Some(parent_id) = &inst.parent_session_id {
             println!("  Parent:  {}", parent_id);
         }
+        if inst.orchestrator.is_some() {
+            println!("  Orchestrator: yes");
+        }
+        if let Some(issue) = &inst.issue_info {
+            println!("  Issue:   #{} {}", issue.number, issue.url);
+        }
+        if let Some(env) = &inst.project_env {
+            println!("  Env:     {}", env.label());
+        }
+        if !inst.locks.is_empty() {
+            let all = locks::load_all_with_status().unwrap_or_default();
+            let owners: Vec<String> = locks::owners(&live, &all)
+                .into_iter()
+                .map(|(lock, owner)| match owner {
+                    LockOwner::This => format!("{} (held)", lock),
+                    LockOwner::Other(title) => format!("{} (held by {})", lock, title),
+                    LockOwner::Free => format!("{} (free)", lock),
+                })
+                .collect();
+            println!("  Locks:   {}", owners.join(", "));
+        }
+        if let Some(notes) = &inst.notes {
+            println!("  Notes:");
+            for line in notes.lines() {
+                println!("    {}", line);
+            }
+        }
+        if let Some(summary) = &inst.final_summary {
+            println!("  Summary:");
+            for line in summary.lines() {
+                println!("    {}", line);
+            }
+        }
     }
 
     Ok(())
@@ -301,10 +510,7 @@ async fn capture_session(profile: &str, args: CaptureArgs) -> Result<()> {
         if let Some(session_name) = current_session {
             instances
                 .iter()
-                .find(|i| {
-                    let tmux_name = crate::tmux::Session::generate_name(&i.id, &i.title);
-                    tmux_name == session_name
-                })
+                .find(|i| crate::tmux::Session::name_matches_id(&session_name, &i.id))
                 .ok_or_else(|| {
                     anyhow::anyhow!("Current tmux session is not an Agent of Empires session")
                 })?
@@ -315,6 +521,16 @@ async fn capture_session(profile: &str, args: CaptureArgs) -> Result<()> {
 
     let tmux_session = crate::tmux::Session::new(&inst.id, &inst.title)?;
 
+    if let Some(path) = &args.output {
+        if !tmux_session.exists() {
+            bail!("Session is not running: {}", inst.title);
+        }
+        let content = crate::tmux::utils::strip_ansi(&tmux_session.capture_pane(args.lines)?);
+        transcript::write(inst, &content, path)?;
+        println!("{}", path.display());
+        return Ok(());
+    }
+
     let (content, status) = if !tmux_session.exists() {
         (String::new(), "stopped".to_string())
     } else {
@@ -347,8 +563,11 @@ async fn capture_session(profile: &str, args: CaptureArgs) -> Result<()> {
 }
 
 async fn rename_session(profile: &str, args: RenameArgs) -> Result<()> {
-    if args.title.is_none() && args.group.is_none() {
-        bail!("At least one of --title or --group must be specified");
+    if args.title.is_none() && args.group.is_none() && args.icon.is_none() {
+        bail!("At least one of --title, --group or --icon must be specified");
+    }
+    if let Some(icon) = &args.icon {
+        crate::session::validate_icon(icon.trim()).map_err(anyhow::Error::msg)?;
     }
 
     let storage = Storage::new(profile)?;
@@ -365,10 +584,7 @@ async fn rename_session(profile: &str, args: RenameArgs) -> Result<()> {
         if let Some(session_name) = current_session {
             instances
                 .iter()
-                .find(|i| {
-                    let tmux_name = crate::tmux::Session::generate_name(&i.id, &i.title);
-                    tmux_name == session_name
-                })
+                .find(|i| crate::tmux::Session::name_matches_id(&session_name, &i.id))
                 .ok_or_else(|| {
                     anyhow::anyhow!("Current tmux session is not an Agent of Empires session")
                 })?
@@ -387,6 +603,7 @@ async fn rename_session(profile: &str, args: RenameArgs) -> Result<()> {
         .iter()
         .position(|i| i.id == id)
         .ok_or_else(|| anyhow::anyhow!("Session not found"))?;
+    ownership::claim(&mut instances[idx], args.steal)?;
 
     // Rename tmux session if title changed
     if instances[idx].title != effective_title {
@@ -406,6 +623,14 @@ async fn rename_session(profile: &str, args: RenameArgs) -> Result<()> {
     if let Some(group) = args.group {
         instances[idx].group_path = group.trim().to_string();
     }
+    if let Some(icon) = args.icon {
+        let icon = icon.trim();
+        instances[idx].icon = (!icon.is_empty()).then(|| icon.to_string());
+    }
+    // The window is named after the title too, so a new title renames it
+    if instances[idx].tmux_session()?.exists() {
+        instances[idx].apply_window_name();
+    }
 
     let mut group_tree = GroupTree::new_with_groups(&instances, &groups);
     if !instances[idx].group_path.is_empty() {
@@ -422,6 +647,162 @@ async fn rename_session(profile: &str, args: RenameArgs) -> Result<()> {
     Ok(())
 }
 
+async fn session_notes(profile: &str, args: NotesArgs) -> Result<()> {
+    let storage = Storage::new(profile)?;
+    let (mut instances, groups) = storage.load_with_groups()?;
+
+    let inst = if let Some(id) = &args.identifier {
+        super::resolve_session(id, &instances)?
+    } else {
+        // Auto-detect from tmux
+        let current_session = std::env::var("TMUX_PANE")
+            .ok()
+            .and_then(|_| crate::tmux::get_current_session_name());
+
+        if let Some(session_name) = current_session {
+            instances
+                .iter()
+                .find(|i| crate::tmux::Session::name_matches_id(&session_name, &i.id))
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Current tmux session is not an Agent of Empires session")
+                })?
+        } else {
+            bail!("Not in a tmux session. Specify a session ID or run inside tmux.");
+        }
+    };
+    let id = inst.id.clone();
+    let current = inst.notes.clone().unwrap_or_default();
+
+    let updated = if let Some(text) = args.set {
+        text
+    } else if let Some(line) = args.append {
+        if current.trim().is_empty() {
+            line
+        } else {
+            format!("{}\n{}", current.trim_end(), line)
+        }
+    } else if args.edit {
+        notes::edit_notes(&crate::terminal::preferred_editor(), &id, &current)?
+    } else if args.clear {
+        String::new()
+    } else {
+        if current.trim().is_empty() {
+            println!("No notes for '{}'", inst.title);
+        } else {
+            println!("{}", current.trim_end());
+        }
+        return Ok(());
+    };
+
+    let idx = instances
+        .iter()
+        .position(|i| i.id == id)
+        .ok_or_else(|| anyhow::anyhow!("Session not found"))?;
+    instances[idx].notes = notes::normalize(&updated);
+    let group_tree = GroupTree::new_with_groups(&instances, &groups);
+    storage.save_with_groups(&instances, &group_tree)?;
+
+    let inst = &instances[idx];
+    println!("✓ Updated notes for '{}'", inst.title);
+    if let Some(path) = notes::sync_if_enabled(inst, profile) {
+        println!("  Synced to {}", path.display());
+    }
+    Ok(())
+}
+
+async fn session_checkpoint(profile: &str, args: CheckpointArgs) -> Result<()> {
+    let storage = Storage::new(profile)?;
+    let (mut instances, groups) = storage.load_with_groups()?;
+
+    let inst = if let Some(id) = &args.identifier {
+        super::resolve_session(id, &instances)?
+    } else {
+        // Auto-detect from tmux
+        let current_session = std::env::var("TMUX_PANE")
+            .ok()
+            .and_then(|_| crate::tmux::get_current_session_name());
+
+        if let Some(session_name) = current_session {
+            instances
+                .iter()
+                .find(|i| crate::tmux::Session::name_matches_id(&session_name, &i.id))
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Current tmux session is not an Agent of Empires session")
+                })?
+        } else {
+            bail!("Not in a tmux session. Specify a session ID or run inside tmux.");
+        }
+    };
+
+    if let Some(name) = &args.show {
+        let cp = checkpoints::find(inst, name)
+            .ok_or_else(|| anyhow::anyhow!("No checkpoint named '{}'", name))?;
+        println!("Checkpoint: {}", cp.name);
+        println!("  Created:  {}", cp.at.format("%Y-%m-%d %H:%M:%S UTC"));
+        println!("  Git:      {}", cp.git_label());
+        if let Some(note) = &cp.note {
+            println!("  Note:     {}", note);
+        }
+        match checkpoints::transcript_since(inst, cp) {
+            Some(output) => {
+                println!("  Output since:");
+                print!("{}", output);
+            }
+            None => println!("  Output since: not available (session not running)"),
+        }
+        return Ok(());
+    }
+
+    if args.add.is_none() && args.rollback.is_none() && args.remove.is_none() {
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&inst.checkpoints)?);
+        } else if inst.checkpoints.is_empty() {
+            println!("No checkpoints for '{}'", inst.title);
+        } else {
+            for cp in &inst.checkpoints {
+                println!(
+                    "  {}  {}  {}{}",
+                    cp.at.format("%Y-%m-%d %H:%M"),
+                    cp.name,
+                    cp.git_label(),
+                    cp.note
+                        .as_ref()
+                        .map(|n| format!(" - {}", n))
+                        .unwrap_or_default()
+                );
+            }
+        }
+        return Ok(());
+    }
+
+    let idx = instances
+        .iter()
+        .position(|i| i.id == inst.id)
+        .ok_or_else(|| anyhow::anyhow!("Session not found"))?;
+    let inst = &mut instances[idx];
+    ownership::claim(inst, args.steal)?;
+    let message = if let Some(name) = &args.add {
+        let cp = checkpoints::create(inst, name, args.note.as_deref())?;
+        format!("Created checkpoint '{}' ({})", cp.name, cp.git_label())
+    } else if let Some(name) = &args.rollback {
+        let saved = checkpoints::rollback(inst, name)?;
+        format!(
+            "Rolled back '{}' to checkpoint '{}' (previous state saved as '{}')",
+            inst.title, name, saved.name
+        )
+    } else if let Some(name) = &args.remove {
+        checkpoints::remove(inst, name)?;
+        format!("Removed checkpoint '{}'", name)
+    } else {
+        unreachable!()
+    };
+
+    let group_tree = GroupTree::new_with_groups(&instances, &groups);
+    storage.save_with_groups(&instances, &group_tree)?;
+    println!("✓ {}", message);
+    Ok(())
+}
+
 async fn current_session(args: CurrentArgs) -> Result<()> {
     // Auto-detect profile and session from tmux
     let current_session = std::env::var("TMUX_PANE")
@@ -436,10 +817,10 @@ async fn current_session(args: CurrentArgs) -> Result<()> {
     for profile_name in &profiles {
         if let Ok(storage) = Storage::new(profile_name) {
             if let Ok((instances, _)) = storage.load_with_groups() {
-                if let Some(inst) = instances.iter().find(|i| {
-                    let tmux_name = crate::tmux::Session::generate_name(&i.id, &i.title);
-                    tmux_name == session_name
-                }) {
+                if let Some(inst) = instances
+                    .iter()
+                    .find(|i| crate::tmux::Session::name_matches_id(&session_name, &i.id))
+                {
                     if args.json {
                         #[derive(Serialize)]
                         struct CurrentInfo {
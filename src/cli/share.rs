@@ -0,0 +1,46 @@
+//! `agent-of-empires share` command implementation
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::session::{resolve_config, share, Storage};
+
+#[derive(Args)]
+pub struct ShareArgs {
+    /// Session ID (or a unique prefix of it) or title
+    identifier: String,
+
+    /// Where to write the HTML bundle (defaults to shares/ in the app directory)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Upload the bundle to the configured destination (share.destination)
+    /// and print its URL
+    #[arg(short, long)]
+    upload: bool,
+
+    /// Number of pane lines to include in the transcript
+    #[arg(short = 'n', long, default_value = "2000")]
+    lines: usize,
+}
+
+pub async fn run(profile: &str, args: ShareArgs) -> Result<()> {
+    let storage = Storage::new(profile)?;
+    let instances = storage.load()?;
+    let inst = super::resolve_session(&args.identifier, &instances)?;
+
+    let bundle = share::collect(inst, args.lines);
+    let path = share::write(&bundle, args.output.as_deref())?;
+
+    if !args.upload {
+        println!("{}", path.display());
+        return Ok(());
+    }
+    let config = resolve_config(profile).unwrap_or_default();
+    let url = share::upload(&path, &inst.title, &config.share)?;
+    eprintln!("Wrote {}", path.display());
+    println!("{}", url);
+    Ok(())
+}
@@ -0,0 +1,24 @@
+//! `aoe shim` command implementation
+//!
+//! Sessions launch their agent through this when `session.agent_shim` is
+//! on; see `crate::hooks::shim` for the ping protocol.
+
+use anyhow::Result;
+use clap::Args;
+
+#[derive(Args)]
+pub struct ShimArgs {
+    /// Session the agent belongs to
+    #[arg(long)]
+    id: String,
+
+    /// Shell command that starts the agent
+    #[arg(last = true, required = true)]
+    command: String,
+}
+
+/// Run the agent and exit with its exit code.
+pub async fn run(args: ShimArgs) -> Result<()> {
+    let code = crate::hooks::shim::run(&args.id, &args.command).await?;
+    std::process::exit(code);
+}
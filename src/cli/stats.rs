@@ -0,0 +1,278 @@
+//! `agent-of-empires stats` command implementation
+
+use anyhow::Result;
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use clap::Args;
+use serde::Serialize;
+
+use crate::session::digest;
+use crate::session::resolve_config;
+use crate::session::stats::{self, HistoryQuery, Summary};
+
+/// Width of the longest bar in a chart
+const BAR_WIDTH: usize = 24;
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Args)]
+pub struct StatsArgs {
+    /// Number of weeks to cover, including the current one
+    #[arg(short = 'w', long, default_value_t = 8)]
+    weeks: usize,
+
+    /// Only sessions whose project path contains this text
+    #[arg(long)]
+    project: Option<String>,
+
+    /// Show last week's digest instead of the weekly charts
+    #[arg(long)]
+    digest: bool,
+
+    /// Deliver the digest as `notifications.digest` is set up instead of
+    /// printing it
+    #[arg(long, requires = "digest")]
+    send: bool,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Serialize)]
+struct Week {
+    week_of: NaiveDate,
+    #[serde(flatten)]
+    summary: Summary,
+}
+
+#[derive(Serialize)]
+struct Project {
+    project: String,
+    #[serde(flatten)]
+    summary: Summary,
+}
+
+pub fn run(profile: &str, args: StatsArgs) -> Result<()> {
+    if args.digest {
+        return run_digest(profile, &args);
+    }
+    let weeks = args.weeks.max(1);
+    let now = Utc::now();
+    let since = stats::week_start(now) - Duration::weeks(weeks as i64 - 1);
+    let records = stats::query(&HistoryQuery {
+        since: Some(since.and_time(NaiveTime::MIN).and_utc()),
+        project: args.project.clone(),
+        ..Default::default()
+    })?;
+
+    let weekly: Vec<Week> = stats::weekly(&records, weeks, now)
+        .into_iter()
+        .map(|(week_of, summary)| Week { week_of, summary })
+        .collect();
+    let mut projects: Vec<Project> = stats::by_project(&records)
+        .into_iter()
+        .map(|(project, summary)| Project { project, summary })
+        .collect();
+    projects.sort_by(|a, b| {
+        b.summary
+            .cost
+            .total_cmp(&a.summary.cost)
+            .then(b.summary.sessions.cmp(&a.summary.sessions))
+    });
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "weeks": weekly,
+                "projects": projects,
+            }))?
+        );
+        return Ok(());
+    }
+
+    for line in render(&weekly, &projects) {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+fn run_digest(profile: &str, args: &StatsArgs) -> Result<()> {
+    let week_of = digest::last_week(Utc::now());
+    let records = stats::query(&HistoryQuery {
+        project: args.project.clone(),
+        ..digest::history_query(week_of)
+    })?;
+    let digest = digest::build(&records, week_of);
+
+    if args.send {
+        let config = resolve_config(profile)?;
+        println!("{}", digest::deliver(&config.notifications, &digest)?);
+    } else if args.json {
+        println!("{}", serde_json::to_string_pretty(&digest)?);
+    } else {
+        print!("{}", digest.text);
+    }
+    Ok(())
+}
+
+fn render(weekly: &[Week], projects: &[Project]) -> Vec<String> {
+    let mut total = Summary::default();
+    for week in weekly {
+        total.sessions += week.summary.sessions;
+        total.succeeded += week.summary.succeeded;
+        total.merged += week.summary.merged;
+        total.cost += week.summary.cost;
+    }
+    if total.sessions == 0 {
+        return vec![format!(
+            "No finished sessions in the last {} weeks. Sessions are recorded when removed.",
+            weekly.len()
+        )];
+    }
+
+    let mut lines = vec![
+        format!(
+            "{} sessions in {} weeks: {} succeeded, {} merged, ${:.2} spent",
+            total.sessions,
+            weekly.len(),
+            percent(total.success_rate()),
+            total.merged,
+            total.cost
+        ),
+        String::new(),
+        format!(
+            "  Sessions {}   Success {}   Spend {}",
+            sparkline(weekly.iter().map(|w| w.summary.sessions as f64)),
+            sparkline(
+                weekly
+                    .iter()
+                    .map(|w| w.summary.success_rate().unwrap_or(0.0))
+            ),
+            sparkline(weekly.iter().map(|w| w.summary.cost)),
+        ),
+        String::new(),
+        format!(
+            "  {:<10}  {:<width$}  {:>8}  {:>9}",
+            "WEEK OF",
+            "SESSIONS",
+            "SUCCESS",
+            "SPEND",
+            width = BAR_WIDTH + 5
+        ),
+    ];
+    let max_sessions = weekly.iter().map(|w| w.summary.sessions).max().unwrap_or(0);
+    for week in weekly {
+        lines.push(format!(
+            "  {:<10}  {:<bar$} {:>4}  {:>8}  {:>9}",
+            week.week_of.format("%Y-%m-%d"),
+            bar(week.summary.sessions as f64, max_sessions as f64),
+            week.summary.sessions,
+            percent(week.summary.success_rate()),
+            format!("${:.2}", week.summary.cost),
+            bar = BAR_WIDTH,
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "  {:<30}  {:<width$}  {:>8}  {:>8}  {:>6}",
+        "PROJECT",
+        "SPEND",
+        "SESSIONS",
+        "SUCCESS",
+        "MERGED",
+        width = BAR_WIDTH + 10
+    ));
+    let max_cost = projects.iter().map(|p| p.summary.cost).fold(0.0, f64::max);
+    for project in projects {
+        lines.push(format!(
+            "  {:<30}  {:<bar$} {:>9}  {:>8}  {:>8}  {:>6}",
+            super::truncate(&shorten_project(&project.project), 30),
+            bar(project.summary.cost, max_cost),
+            format!("${:.2}", project.summary.cost),
+            project.summary.sessions,
+            percent(project.summary.success_rate()),
+            project.summary.merged,
+            bar = BAR_WIDTH,
+        ));
+    }
+    lines
+}
+
+fn percent(rate: Option<f64>) -> String {
+    rate.map(|r| format!("{:.0}%", r * 100.0))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// A horizontal bar `value / max` of [`BAR_WIDTH`], at least one cell wide
+/// for any nonzero value.
+fn bar(value: f64, max: f64) -> String {
+    if value <= 0.0 || max <= 0.0 {
+        return String::new();
+    }
+    let cells = ((value / max) * BAR_WIDTH as f64).round() as usize;
+    "█".repeat(cells.clamp(1, BAR_WIDTH))
+}
+
+/// One block character per value, scaled to the largest.
+fn sparkline(values: impl Iterator<Item = f64>) -> String {
+    let values: Vec<f64> = values.collect();
+    let max = values.iter().copied().fold(0.0, f64::max);
+    values
+        .iter()
+        .map(|&v| {
+            if max <= 0.0 || v <= 0.0 {
+                SPARK_LEVELS[0]
+            } else {
+                let level = ((v / max) * (SPARK_LEVELS.len() - 1) as f64).round() as usize;
+                SPARK_LEVELS[level.min(SPARK_LEVELS.len() - 1)]
+            }
+        })
+        .collect()
+}
+
+fn shorten_project(path: &str) -> String {
+    dirs::home_dir()
+        .and_then(|home| {
+            path.strip_prefix(home.to_str()?)
+                .map(|rest| format!("~{}", rest))
+        })
+        .unwrap_or_else(|| path.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparkline_and_bar() {
+        assert_eq!(sparkline([0.0, 1.0, 2.0, 4.0].into_iter()), "▁▃▅█");
+        assert_eq!(sparkline([0.0, 0.0].into_iter()), "▁▁");
+        assert_eq!(bar(1.0, 100.0), "█");
+        assert_eq!(bar(10.0, 10.0).chars().count(), BAR_WIDTH);
+        assert_eq!(bar(0.0, 10.0), "");
+    }
+
+    #[test]
+    fn test_render_empty_and_summary() {
+        let week = |sessions, succeeded, cost| Week {
+            week_of: NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+            summary: Summary {
+                sessions,
+                succeeded,
+                merged: 0,
+                duration_secs: 0,
+                cost,
+            },
+        };
+        let lines = render(&[week(0, 0, 0.0)], &[]);
+        assert!(lines[0].starts_with("No finished sessions"));
+
+        let lines = render(&[week(4, 3, 2.5)], &[]);
+        assert_eq!(
+            lines[0],
+            "4 sessions in 1 weeks: 75% succeeded, 0 merged, $2.50 spent"
+        );
+    }
+}
@@ -143,7 +143,15 @@ fn print_status_group(
     println!("{} ({}):", label, matching.len());
     for inst in matching {
         let path = shorten_path(&inst.project_path);
-        println!("  {} {:<16} {:<10} {}", symbol, inst.title, inst.tool, path);
+        let auth = if inst.auth_required {
+            "  [auth required]"
+        } else {
+            ""
+        };
+        println!(
+            "  {} {:<16} {:<10} {}{}",
+            symbol, inst.title, inst.tool, path, auth
+        );
     }
     println!();
 }
@@ -0,0 +1,223 @@
+//! `agent-of-empires template` subcommands implementation
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+use crate::session::repo_config::templates_dir;
+use crate::session::template_sources::{self, short_commit, TemplateSource};
+
+#[derive(Subcommand)]
+pub enum TemplateCommands {
+    /// Install templates from a git repo or URL and record where they came from
+    Add(TemplateAddArgs),
+
+    /// List templates and the sources they were installed from
+    #[command(alias = "ls")]
+    List(TemplateListArgs),
+
+    /// Fetch sources again and re-install their templates
+    Update(TemplateUpdateArgs),
+
+    /// Remove a source and the templates it installed
+    Remove(TemplateRemoveArgs),
+}
+
+#[derive(Args)]
+pub struct TemplateAddArgs {
+    /// Git URL, github:owner/repo, gitlab:group/repo, or URL of a .toml file
+    source: String,
+
+    /// Branch, tag, or commit to pin the templates to
+    #[arg(long)]
+    rev: Option<String>,
+
+    /// Name for the source (defaults to the repository or file name)
+    #[arg(long)]
+    name: Option<String>,
+}
+
+#[derive(Args)]
+pub struct TemplateListArgs {
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+pub struct TemplateUpdateArgs {
+    /// Source to update (all sources if omitted)
+    name: Option<String>,
+
+    /// Pin the source to this branch, tag, or commit
+    #[arg(long, requires = "name")]
+    rev: Option<String>,
+}
+
+#[derive(Args)]
+pub struct TemplateRemoveArgs {
+    /// Source name
+    name: String,
+}
+
+#[derive(Serialize)]
+struct TemplateInfo {
+    name: String,
+    source: Option<String>,
+}
+
+#[derive(Serialize)]
+struct TemplateList {
+    templates: Vec<TemplateInfo>,
+    sources: Vec<TemplateSource>,
+}
+
+pub async fn run(command: TemplateCommands) -> Result<()> {
+    match command {
+        TemplateCommands::Add(args) => add_source(args).await,
+        TemplateCommands::List(args) => list_templates(args),
+        TemplateCommands::Update(args) => update_sources(args).await,
+        TemplateCommands::Remove(args) => remove_source(args),
+    }
+}
+
+/// `url` at `rev` or its commit, for messages
+fn describe(source: &TemplateSource) -> String {
+    match (&source.rev, &source.commit) {
+        (Some(rev), Some(commit)) => {
+            format!("{} ({} at {})", source.url, rev, short_commit(commit))
+        }
+        (None, Some(commit)) => format!("{} ({})", source.url, short_commit(commit)),
+        (Some(rev), None) => format!("{} ({})", source.url, rev),
+        (None, None) => source.url.clone(),
+    }
+}
+
+async fn add_source(args: TemplateAddArgs) -> Result<()> {
+    let added = template_sources::add(&args.source, args.rev, args.name).await?;
+
+    println!("✓ Added template source: {}", added.name);
+    println!("  From: {}", describe(&added));
+    println!("  Templates: {}", added.templates.join(", "));
+    println!("  Use one with `extends = \"<template>\"` in .aoe/config.toml");
+
+    Ok(())
+}
+
+fn list_templates(args: TemplateListArgs) -> Result<()> {
+    let sources = template_sources::load()?;
+    let dir = templates_dir()?;
+    let mut names: Vec<String> = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+            .filter_map(|p| Some(p.file_stem()?.to_str()?.to_string()))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    names.sort();
+    let templates: Vec<TemplateInfo> = names
+        .into_iter()
+        .map(|name| TemplateInfo {
+            source: sources
+                .iter()
+                .find(|s| s.templates.contains(&name))
+                .map(|s| s.name.clone()),
+            name,
+        })
+        .collect();
+
+    if args.json {
+        let list = TemplateList { templates, sources };
+        println!("{}", serde_json::to_string_pretty(&list)?);
+        return Ok(());
+    }
+
+    if templates.is_empty() {
+        println!("No templates in {}", dir.display());
+        println!("Add some with: aoe template add github:<org>/<repo>");
+        return Ok(());
+    }
+
+    println!("{:<24} SOURCE", "TEMPLATE");
+    println!("{}", "-".repeat(60));
+    for template in &templates {
+        println!(
+            "{:<24} {}",
+            super::truncate(&template.name, 24),
+            template.source.as_deref().unwrap_or("(local)")
+        );
+    }
+
+    if !sources.is_empty() {
+        println!();
+        println!("{:<20} {:<17} FROM", "SOURCE", "UPDATED");
+        println!("{}", "-".repeat(80));
+        for source in &sources {
+            println!(
+                "{:<20} {:<17} {}",
+                super::truncate(&source.name, 20),
+                source
+                    .updated_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M"),
+                describe(source)
+            );
+        }
+    }
+    println!("\nTotal: {} templates", templates.len());
+
+    Ok(())
+}
+
+async fn update_sources(args: TemplateUpdateArgs) -> Result<()> {
+    let updated = template_sources::update(args.name.as_deref(), args.rev).await?;
+    if updated.is_empty() {
+        println!("No template sources to update.");
+        println!("Add one with: aoe template add github:<org>/<repo>");
+        return Ok(());
+    }
+
+    for (before, after) in &updated {
+        match (&before.commit, &after.commit) {
+            (Some(from), Some(to)) if from == to => {
+                println!("✓ {} is up to date ({})", after.name, short_commit(to))
+            }
+            (Some(from), Some(to)) => println!(
+                "✓ Updated {}: {} -> {}",
+                after.name,
+                short_commit(from),
+                short_commit(to)
+            ),
+            _ => println!("✓ Updated {}", after.name),
+        }
+        for template in after
+            .templates
+            .iter()
+            .filter(|t| !before.templates.contains(t))
+        {
+            println!("  + {}", template);
+        }
+        for template in before
+            .templates
+            .iter()
+            .filter(|t| !after.templates.contains(t))
+        {
+            println!("  - {}", template);
+        }
+    }
+
+    Ok(())
+}
+
+fn remove_source(args: TemplateRemoveArgs) -> Result<()> {
+    let removed = template_sources::remove(&args.name)?;
+
+    println!("✓ Removed template source: {}", removed.name);
+    if !removed.templates.is_empty() {
+        println!("  Deleted templates: {}", removed.templates.join(", "));
+    }
+
+    Ok(())
+}
@@ -10,6 +10,9 @@ pub enum TmuxCommands {
     /// Add this to your ~/.tmux.conf:
     ///   set -g status-right "#(aoe tmux status)"
     Status(TmuxStatusArgs),
+
+    /// Check the tmux version and config options that conflict with aoe
+    Check,
 }
 
 #[derive(Args)]
@@ -55,3 +58,40 @@ pub fn run_status(args: TmuxStatusArgs) -> Result<()> {
 
     Ok(())
 }
+
+pub fn run_check() -> Result<()> {
+    use crate::tmux::options::check_user_options;
+    use crate::tmux::version;
+
+    match version::detected() {
+        Some(v) => println!("tmux {} (aoe needs {} or newer)", v, version::MINIMUM),
+        None => println!(
+            "tmux version unknown (aoe needs {} or newer)",
+            version::MINIMUM
+        ),
+    }
+    version::check_supported()?;
+
+    let conflicts = check_user_options();
+    if conflicts.is_empty() {
+        println!("✓ No conflicting tmux options found");
+        return Ok(());
+    }
+
+    for conflict in &conflicts {
+        println!(
+            "{} {} (recommended: {})",
+            conflict.option, conflict.value, conflict.recommended
+        );
+        println!("  {}", conflict.impact);
+        if conflict.overridden {
+            println!("  aoe sets this per session, so only your other tmux sessions are affected");
+        } else {
+            println!(
+                "  Server-wide; add `set -s {} {}` to ~/.tmux.conf to fix",
+                conflict.option, conflict.recommended
+            );
+        }
+    }
+    Ok(())
+}
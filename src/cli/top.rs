@@ -0,0 +1,283 @@
+//! `agent-of-empires top` command implementation
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use clap::Args;
+use crossterm::{cursor, execute, terminal};
+
+use crate::process::{self, ProcessTable};
+use crate::session::{Instance, Status, Storage};
+use crate::tmux::output_parser::reported_cost;
+use crate::tui::format_age;
+use crate::tui::styles::status_icon;
+
+/// Pane lines searched for the agent's latest cost report
+const COST_SCAN_LINES: usize = 200;
+
+/// Width of everything except the title column
+const FIXED_COLUMNS_WIDTH: usize = 52;
+
+#[derive(Args)]
+pub struct TopArgs {
+    /// Seconds between refreshes (defaults to polling.resource_usage_secs, 2)
+    #[arg(short = 'n', long, value_name = "SECS")]
+    interval: Option<u64>,
+
+    /// Print a single snapshot and exit
+    #[arg(long)]
+    once: bool,
+}
+
+/// One table row
+struct TopRow {
+    title: String,
+    status: Status,
+    tool: String,
+    /// Seconds since the tmux session last saw output or input
+    idle_secs: Option<i64>,
+    /// CPU use of the agent's process tree since the previous refresh, in percent
+    cpu: Option<f64>,
+    /// Latest cost the agent reported, in dollars
+    cost: Option<f64>,
+}
+
+/// Sessions needing attention first, then busy ones, then the rest.
+fn status_rank(status: Status) -> u8 {
+    match status {
+        Status::Waiting => 0,
+        Status::Error => 1,
+        Status::Running => 2,
+        Status::Starting => 3,
+        Status::Idle | Status::Unknown => 4,
+        Status::Stopped | Status::Deleting => 5,
+    }
+}
+
+/// Tracks cumulative CPU time per session between refreshes.
+struct CpuSampler {
+    previous: HashMap<String, f64>,
+    sampled_at: Option<Instant>,
+}
+
+impl CpuSampler {
+    fn new() -> Self {
+        Self {
+            previous: HashMap::new(),
+            sampled_at: None,
+        }
+    }
+
+    /// CPU percent for each session with a live pane. Empty on the first
+    /// call, since usage is measured between two samples.
+    fn sample(&mut self, sessions: &[(String, u32)]) -> HashMap<String, f64> {
+        let Some(table) = ProcessTable::snapshot() else {
+            return HashMap::new();
+        };
+        let now = Instant::now();
+        let elapsed = self.sampled_at.map(|t| now.duration_since(t).as_secs_f64());
+
+        let mut current = HashMap::new();
+        let mut usage = HashMap::new();
+        for (id, pid) in sessions {
+            let cpu_time = table.tree_cpu_time(*pid);
+            if let (Some(elapsed), Some(previous)) = (elapsed, self.previous.get(id)) {
+                if elapsed > 0.0 {
+                    usage.insert(
+                        id.clone(),
+                        ((cpu_time - previous) / elapsed * 100.0).max(0.0),
+                    );
+                }
+            }
+            current.insert(id.clone(), cpu_time);
+        }
+        self.previous = current;
+        self.sampled_at = Some(now);
+        usage
+    }
+}
+
+pub async fn run(profile: &str, args: TopArgs) -> Result<()> {
+    let storage = Storage::new(profile)?;
+    let interval = args.interval.unwrap_or_else(|| {
+        crate::session::resolve_config(profile)
+            .map(|c| c.polling.resource_usage_secs)
+            .unwrap_or(2)
+    });
+    let interval = Duration::from_secs(interval.max(1));
+    let mut sampler = CpuSampler::new();
+
+    if args.once {
+        // CPU use needs two samples; take the first a second early
+        collect_rows(&storage, &mut sampler)?;
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let rows = collect_rows(&storage, &mut sampler)?;
+        for line in render(storage.profile(), &rows, terminal_width()) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    let mut stdout = io::stdout();
+    loop {
+        let rows = collect_rows(&storage, &mut sampler)?;
+        let frame = render(storage.profile(), &rows, terminal_width()).join("\n");
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(terminal::ClearType::All)
+        )?;
+        writeln!(stdout, "{}", frame)?;
+        stdout.flush()?;
+        tokio::time::sleep(interval).await;
+    }
+}
+
+fn terminal_width() -> usize {
+    terminal::size().map(|(w, _)| w as usize).unwrap_or(100)
+}
+
+fn collect_rows(storage: &Storage, sampler: &mut CpuSampler) -> Result<Vec<TopRow>> {
+    let mut instances = storage.load()?;
+    crate::tmux::refresh_session_cache();
+    for inst in &mut instances {
+        inst.update_status();
+    }
+
+    let names: Vec<String> = instances
+        .iter()
+        .map(|i| crate::tmux::Session::generate_name(&i.id, &i.title))
+        .collect();
+    let panes: Vec<(String, u32)> = instances
+        .iter()
+        .zip(&names)
+        .filter(|(inst, _)| is_live(inst))
+        .filter_map(|(inst, name)| Some((inst.id.clone(), process::get_pane_pid(name)?)))
+        .collect();
+    let cpu = sampler.sample(&panes);
+
+    let now = chrono::Utc::now().timestamp();
+    let mut rows: Vec<TopRow> = instances
+        .iter()
+        .zip(&names)
+        .map(|(inst, name)| {
+            let cost = is_live(inst)
+                .then(|| inst.tmux_session().ok())
+                .flatten()
+                .and_then(|s| s.capture_pane(COST_SCAN_LINES).ok())
+                .and_then(|content| reported_cost(&content));
+            TopRow {
+                title: inst.title.clone(),
+                status: inst.status,
+                tool: inst.tool.clone(),
+                idle_secs: crate::tmux::session_activity_from_cache(name)
+                    .filter(|&t| t > 0)
+                    .map(|t| now - t),
+                cpu: cpu.get(&inst.id).copied(),
+                cost,
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        status_rank(a.status)
+            .cmp(&status_rank(b.status))
+            .then_with(|| a.title.cmp(&b.title))
+    });
+    Ok(rows)
+}
+
+fn is_live(inst: &Instance) -> bool {
+    !matches!(inst.status, Status::Stopped | Status::Deleting)
+}
+
+/// The full screen: a summary line, a blank line, then the table.
+fn render(profile: &str, rows: &[TopRow], width: usize) -> Vec<String> {
+    let count = |status: Status| rows.iter().filter(|r| r.status == status).count();
+    let total_cost: f64 = rows.iter().filter_map(|r| r.cost).sum();
+    let mut summary = format!(
+        "aoe top - {} - {} sessions: {} waiting, {} running, {} error",
+        profile,
+        rows.len(),
+        count(Status::Waiting),
+        count(Status::Running),
+        count(Status::Error),
+    );
+    if total_cost > 0.0 {
+        summary.push_str(&format!(" - ${:.2}", total_cost));
+    }
+
+    let title_width = width.saturating_sub(FIXED_COLUMNS_WIDTH).max(10);
+    let mut lines = vec![
+        summary,
+        String::new(),
+        format!(
+            "  {:<title_width$} {:<9} {:>7} {:>7} {:>9}  {}",
+            "TITLE", "STATUS", "ACTIVE", "CPU", "COST", "TOOL"
+        ),
+    ];
+    for row in rows {
+        lines.push(format!(
+            "{} {:<title_width$} {:<9} {:>7} {:>7} {:>9}  {}",
+            status_icon(row.status),
+            super::truncate(&row.title, title_width),
+            format!("{:?}", row.status),
+            row.idle_secs.map(format_age).unwrap_or_else(|| "-".into()),
+            row.cpu
+                .map(|c| format!("{:.1}%", c))
+                .unwrap_or_else(|| "-".into()),
+            row.cost
+                .map(|c| format!("${:.2}", c))
+                .unwrap_or_else(|| "-".into()),
+            super::truncate(&row.tool, 10),
+        ));
+    }
+    if rows.is_empty() {
+        lines.push("  No sessions.".to_string());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_table() {
+        let rows = vec![
+            TopRow {
+                title: "fix-login".to_string(),
+                status: Status::Waiting,
+                tool: "claude".to_string(),
+                idle_secs: Some(90),
+                cpu: Some(0.0),
+                cost: Some(1.5),
+            },
+            TopRow {
+                title: "a-very-long-session-title".to_string(),
+                status: Status::Stopped,
+                tool: "opencode".to_string(),
+                idle_secs: None,
+                cpu: None,
+                cost: None,
+            },
+        ];
+        let lines = render("default", &rows, 64);
+        assert_eq!(
+            lines[0],
+            "aoe top - default - 2 sessions: 1 waiting, 0 running, 0 error - $1.50"
+        );
+        assert!(lines[2].starts_with("  TITLE        STATUS"));
+        assert!(lines[3].contains("fix-login    Waiting        1m    0.0%     $1.50  claude"));
+        assert!(lines[4].contains("a-very-lo... Stopped         -       -         -  opencode"));
+        assert!(lines[3..].iter().all(|l| l.chars().count() <= 64));
+    }
+
+    #[test]
+    fn test_status_rank_puts_attention_first() {
+        assert!(status_rank(Status::Waiting) < status_rank(Status::Running));
+        assert!(status_rank(Status::Error) < status_rank(Status::Idle));
+        assert!(status_rank(Status::Idle) < status_rank(Status::Stopped));
+    }
+}
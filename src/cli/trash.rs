@@ -0,0 +1,140 @@
+//! `agent-of-empires trash` subcommands implementation
+
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+use crate::session::trash;
+
+#[derive(Subcommand)]
+pub enum TrashCommands {
+    /// List removed sessions that can still be restored
+    #[command(alias = "ls")]
+    List(TrashListArgs),
+
+    /// Restore a removed session (it comes back stopped)
+    Restore(TrashRestoreArgs),
+
+    /// Permanently delete sessions from the trash
+    Purge(TrashPurgeArgs),
+}
+
+#[derive(Args)]
+pub struct TrashListArgs {
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+pub struct TrashRestoreArgs {
+    /// Session ID or title
+    identifier: String,
+}
+
+#[derive(Args)]
+pub struct TrashPurgeArgs {
+    /// Session ID or title (omit with --all)
+    identifier: Option<String>,
+
+    /// Empty the whole trash
+    #[arg(long, conflicts_with = "identifier")]
+    all: bool,
+}
+
+#[derive(Serialize)]
+struct TrashInfo {
+    id: String,
+    title: String,
+    path: String,
+    group: String,
+    tool: String,
+    deleted_at: String,
+}
+
+pub async fn run(profile: &str, command: TrashCommands) -> Result<()> {
+    match command {
+        TrashCommands::List(args) => list_trash(profile, args),
+        TrashCommands::Restore(args) => restore_session(profile, args),
+        TrashCommands::Purge(args) => purge_trash(profile, args),
+    }
+}
+
+fn list_trash(profile: &str, args: TrashListArgs) -> Result<()> {
+    let entries = trash::list(profile)?;
+
+    if args.json {
+        let infos: Vec<TrashInfo> = entries
+            .iter()
+            .map(|e| TrashInfo {
+                id: e.instance.id.clone(),
+                title: e.instance.title.clone(),
+                path: e.instance.project_path.clone(),
+                group: e.instance.group_path.clone(),
+                tool: e.instance.tool.clone(),
+                deleted_at: e.deleted_at.to_rfc3339(),
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&infos)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("Trash is empty.");
+        return Ok(());
+    }
+
+    println!("{:<20} {:<10} {:<17} PATH", "TITLE", "ID", "DELETED");
+    println!("{}", "-".repeat(80));
+    for entry in &entries {
+        let inst = &entry.instance;
+        println!(
+            "{:<20} {:<10} {:<17} {}",
+            super::truncate(&inst.title, 20),
+            crate::session::truncate_id(&inst.id, 8),
+            entry
+                .deleted_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M"),
+            inst.project_path
+        );
+    }
+    println!("\nTotal: {} sessions", entries.len());
+
+    Ok(())
+}
+
+fn restore_session(profile: &str, args: TrashRestoreArgs) -> Result<()> {
+    let restored = trash::restore(profile, &args.identifier)?;
+    let inst = restored.instance;
+
+    println!("✓ Restored session: {}", inst.title);
+    for note in &restored.notes {
+        println!("  {}", note);
+    }
+    if !std::path::Path::new(&inst.project_path).exists() {
+        println!(
+            "  Warning: project path no longer exists: {}",
+            inst.project_path
+        );
+    }
+    println!("  Start it with: aoe session start \"{}\"", inst.title);
+
+    Ok(())
+}
+
+fn purge_trash(profile: &str, args: TrashPurgeArgs) -> Result<()> {
+    if args.all {
+        let count = trash::purge_all(profile)?;
+        println!("✓ Purged {} sessions from trash", count);
+        return Ok(());
+    }
+
+    let Some(identifier) = args.identifier else {
+        bail!("Specify a session to purge, or use --all to empty the trash");
+    };
+    let inst = trash::purge(profile, &identifier)?;
+    println!("✓ Purged from trash: {}", inst.title);
+
+    Ok(())
+}
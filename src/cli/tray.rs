@@ -0,0 +1,80 @@
+//! `aoe tray` command implementation
+
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueEnum};
+
+use crate::session::resolve_config;
+use crate::tray;
+
+#[derive(Subcommand)]
+pub enum TrayCommands {
+    /// Show the tray icon (Linux, needs yad); the TUI starts this itself
+    /// when notifications.tray is on
+    Run(TrayRunArgs),
+
+    /// Print how many sessions need attention
+    ///
+    /// For a macOS menu bar item, save a SwiftBar or xbar plugin such as
+    /// `aoe.5s.sh` containing:
+    ///   #!/bin/sh
+    ///   exec aoe tray status --format xbar
+    Status(TrayStatusArgs),
+
+    /// Bring the terminal running aoe to the front
+    Focus,
+}
+
+#[derive(Args)]
+pub struct TrayRunArgs {
+    /// Exit when the process that started the helper exits
+    #[arg(long, hide = true)]
+    exit_with_parent: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum TrayFormat {
+    Text,
+    Json,
+    Xbar,
+}
+
+#[derive(Args)]
+pub struct TrayStatusArgs {
+    /// Output format
+    #[arg(short, long, value_enum, default_value = "text")]
+    format: TrayFormat,
+}
+
+pub fn run(profile: &str, command: TrayCommands) -> Result<()> {
+    match command {
+        TrayCommands::Run(args) => tray::run(args.exit_with_parent),
+        TrayCommands::Status(args) => {
+            let attention = tray::attention();
+            match args.format {
+                TrayFormat::Text => println!("{}", tray::summary(attention.len())),
+                TrayFormat::Json => {
+                    let sessions: Vec<_> = attention
+                        .iter()
+                        .map(|(title, reasons)| {
+                            serde_json::json!({ "title": title, "reasons": reasons })
+                        })
+                        .collect();
+                    let json = serde_json::json!({
+                        "count": attention.len(),
+                        "sessions": sessions,
+                    });
+                    println!("{}", serde_json::to_string(&json)?);
+                }
+                TrayFormat::Xbar => {
+                    let aoe = std::env::current_exe()?;
+                    print!(
+                        "{}",
+                        tray::xbar_output(&attention, &aoe.display().to_string())
+                    );
+                }
+            }
+            Ok(())
+        }
+        TrayCommands::Focus => tray::focus(&resolve_config(profile)?.notifications),
+    }
+}
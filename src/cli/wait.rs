@@ -0,0 +1,195 @@
+//! `agent-of-empires wait` command implementation
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+
+use crate::session::{Instance, Status, Storage};
+use crate::tmux::output_parser::final_message;
+
+/// Pane lines searched for the agent's final message
+const FINAL_SUMMARY_LINES: usize = 200;
+
+/// Exit code when the timeout expires first (same as `timeout(1)`).
+const EXIT_TIMEOUT: i32 = 124;
+
+#[derive(Args)]
+#[command(after_help = "Exit codes: 0 done, 2 waiting, 3 error, 4 stopped or removed, 124 timeout")]
+pub struct WaitArgs {
+    /// Session ID (or a unique prefix of it) or title
+    identifier: String,
+
+    /// Status to wait for. `any` returns as soon as the agent stops working
+    #[arg(long = "for", value_enum, default_value_t = WaitFor::Any)]
+    target: WaitFor,
+
+    /// Give up after this many seconds
+    #[arg(long, value_name = "SECS")]
+    timeout: Option<u64>,
+
+    /// Seconds between status checks
+    #[arg(long, default_value_t = 2, value_name = "SECS")]
+    interval: u64,
+
+    /// Don't print the outcome; rely on the exit code
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum WaitFor {
+    /// Done, waiting for input, or errored
+    Any,
+    /// Finished and idle
+    Done,
+    /// Waiting for input or a permission prompt
+    Waiting,
+    /// Errored (e.g. the agent exited)
+    Error,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Outcome {
+    Done,
+    Waiting,
+    Error,
+    Stopped,
+    Timeout,
+}
+
+impl Outcome {
+    /// The outcome a status settles on, or None while the agent is busy or
+    /// its state can't be told.
+    fn from_status(status: Status) -> Option<Self> {
+        match status {
+            Status::Idle => Some(Self::Done),
+            Status::Waiting => Some(Self::Waiting),
+            Status::Error => Some(Self::Error),
+            Status::Stopped | Status::Deleting => Some(Self::Stopped),
+            Status::Running | Status::Starting | Status::Unknown => None,
+        }
+    }
+
+    fn exit_code(self) -> i32 {
+        match self {
+            Self::Done => 0,
+            Self::Waiting => 2,
+            Self::Error => 3,
+            Self::Stopped => 4,
+            Self::Timeout => EXIT_TIMEOUT,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Done => "done",
+            Self::Waiting => "waiting",
+            Self::Error => "error",
+            Self::Stopped => "stopped",
+            Self::Timeout => "timed out",
+        }
+    }
+
+    /// Whether waiting for `target` ends at this outcome. Error and stopped
+    /// always end the wait, since the session can't progress on its own.
+    fn ends_wait(self, target: WaitFor) -> bool {
+        matches!(
+            (target, self),
+            (_, Self::Error | Self::Stopped | Self::Timeout)
+                | (WaitFor::Any, _)
+                | (WaitFor::Done, Self::Done)
+                | (WaitFor::Waiting, Self::Waiting)
+        )
+    }
+}
+
+pub async fn run(profile: &str, args: WaitArgs) -> Result<()> {
+    let storage = Storage::new(profile)?;
+    let (instances, _) = storage.load_with_groups()?;
+    let inst = super::resolve_session(&args.identifier, &instances)?;
+    let (id, title) = (inst.id.clone(), inst.title.clone());
+
+    let deadline = args
+        .timeout
+        .map(|secs| Instant::now() + Duration::from_secs(secs));
+    let interval = Duration::from_secs(args.interval.max(1));
+
+    // Require two matching checks in a row so a momentary status flicker
+    // (e.g. between tool calls) doesn't end the wait early.
+    let mut previous: Option<Outcome> = None;
+    let mut last_seen: Option<Instance> = None;
+    let outcome = loop {
+        crate::tmux::refresh_session_cache();
+        let current = match storage.load()?.into_iter().find(|i| i.id == id) {
+            Some(mut inst) => {
+                inst.update_status();
+                let outcome = Outcome::from_status(inst.status);
+                last_seen = Some(inst);
+                outcome
+            }
+            None => Some(Outcome::Stopped),
+        };
+
+        if let Some(outcome) = current {
+            if previous == Some(outcome) && outcome.ends_wait(args.target) {
+                break outcome;
+            }
+        }
+        previous = current;
+
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            break Outcome::Timeout;
+        }
+        tokio::time::sleep(interval).await;
+    };
+
+    if !args.quiet {
+        println!("{}: {}", title, outcome.label());
+        if outcome == Outcome::Done {
+            if let Some(summary) = last_seen.as_ref().and_then(final_summary) {
+                println!("{}", summary);
+            }
+        }
+    }
+    let code = outcome.exit_code();
+    if code != 0 {
+        std::process::exit(code);
+    }
+    Ok(())
+}
+
+/// The agent's last message: read from its pane, or the one the TUI
+/// captured when the session last finished.
+fn final_summary(inst: &Instance) -> Option<String> {
+    inst.tmux_session()
+        .and_then(|session| session.capture_pane(FINAL_SUMMARY_LINES))
+        .ok()
+        .and_then(|content| final_message(&content))
+        .or_else(|| inst.final_summary.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outcome_from_status() {
+        assert_eq!(Outcome::from_status(Status::Running), None);
+        assert_eq!(Outcome::from_status(Status::Unknown), None);
+        assert_eq!(Outcome::from_status(Status::Idle), Some(Outcome::Done));
+        assert_eq!(
+            Outcome::from_status(Status::Deleting),
+            Some(Outcome::Stopped)
+        );
+    }
+
+    #[test]
+    fn test_ends_wait() {
+        assert!(Outcome::Waiting.ends_wait(WaitFor::Any));
+        assert!(!Outcome::Waiting.ends_wait(WaitFor::Done));
+        assert!(!Outcome::Done.ends_wait(WaitFor::Error));
+        assert!(Outcome::Error.ends_wait(WaitFor::Done));
+        assert!(Outcome::Stopped.ends_wait(WaitFor::Waiting));
+    }
+}
@@ -4,7 +4,8 @@ use anyhow::{bail, Result};
 use clap::Subcommand;
 use std::path::{Path, PathBuf};
 
-use crate::git::GitWorktree;
+use crate::git::{pool, GitWorktree};
+use crate::session::repo_config::resolve_config_with_repo;
 use crate::session::Storage;
 
 #[derive(Subcommand)]
@@ -15,7 +16,7 @@ pub enum WorktreeCommands {
 
     /// Show worktree information for a session
     Info {
-        /// Session ID or title
+        /// Session ID (or a unique prefix of it) or title
         identifier: String,
     },
 
@@ -25,6 +26,34 @@ pub enum WorktreeCommands {
         #[arg(short = 'f', long = "force")]
         force: bool,
     },
+
+    /// Manage the pool of spare worktrees new sessions claim
+    Pool {
+        #[command(subcommand)]
+        command: PoolCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PoolCommands {
+    /// Show the spare worktrees of a repository
+    Status {
+        /// Repository path (defaults to current directory)
+        path: Option<PathBuf>,
+    },
+
+    /// Create spare worktrees up to worktree.pool_size, and move existing
+    /// ones to the repository's HEAD
+    Fill {
+        /// Repository path (defaults to current directory)
+        path: Option<PathBuf>,
+    },
+
+    /// Remove all spare worktrees of a repository
+    Clear {
+        /// Repository path (defaults to current directory)
+        path: Option<PathBuf>,
+    },
 }
 
 pub async fn run(profile: &str, command: WorktreeCommands) -> Result<()> {
@@ -32,7 +61,57 @@ pub async fn run(profile: &str, command: WorktreeCommands) -> Result<()> {
         WorktreeCommands::List => list_worktrees().await,
         WorktreeCommands::Info { identifier } => show_info(profile, &identifier).await,
         WorktreeCommands::Cleanup { force } => cleanup_orphaned(profile, force).await,
+        WorktreeCommands::Pool { command } => run_pool(profile, command),
+    }
+}
+
+fn pool_repo(path: Option<PathBuf>) -> Result<GitWorktree> {
+    let path = match path {
+        Some(path) => path,
+        None => std::env::current_dir()?,
+    };
+    if !GitWorktree::is_git_repo(&path) {
+        bail!("Not in a git repository\nTip: Navigate to a git repository first");
     }
+    let main_repo = GitWorktree::find_main_repo(&path)?;
+    Ok(GitWorktree::new(
+        main_repo.canonicalize().unwrap_or(main_repo),
+    )?)
+}
+
+fn run_pool(profile: &str, command: PoolCommands) -> Result<()> {
+    match command {
+        PoolCommands::Status { path } => {
+            let git_wt = pool_repo(path)?;
+            let config = resolve_config_with_repo(profile, &git_wt.repo_path)?;
+            let pooled = pool::list(&git_wt)?;
+            println!(
+                "Worktree pool: {} of {} spare",
+                pooled.len(),
+                config.worktree.pool_size
+            );
+            for path in &pooled {
+                println!("  {}", shorten_path(path));
+            }
+        }
+        PoolCommands::Fill { path } => {
+            let git_wt = pool_repo(path)?;
+            let config = resolve_config_with_repo(profile, &git_wt.repo_path)?;
+            let git_wt = git_wt.with_sparse_paths(config.worktree.sparse_paths.clone());
+            let size = config.worktree.pool_size;
+            if size == 0 {
+                bail!("The worktree pool is off\nTip: Set worktree.pool_size to the number of spare worktrees to keep");
+            }
+            let added = pool::fill(&git_wt, pool::template(&git_wt, &config.worktree), size)?;
+            println!("✓ Worktree pool has {} spare ({} added)", size, added);
+        }
+        PoolCommands::Clear { path } => {
+            let git_wt = pool_repo(path)?;
+            let removed = pool::clear(&git_wt)?;
+            println!("✓ Removed {} spare worktrees", removed);
+        }
+    }
+    Ok(())
 }
 
 async fn list_worktrees() -> Result<()> {
@@ -46,6 +125,7 @@ async fn list_worktrees() -> Result<()> {
     let git_wt = GitWorktree::new(main_repo)?;
 
     let worktrees = git_wt.list_worktrees()?;
+    let pooled = pool::list(&git_wt).unwrap_or_default();
 
     println!("Git Worktrees:\n");
     println!("{:<40} {:<30} {:<10}", "PATH", "BRANCH", "TYPE");
@@ -62,6 +142,8 @@ async fn list_worktrees() -> Result<()> {
 
         let wt_type = if wt.path == git_wt.repo_path {
             "main"
+        } else if pooled.contains(&wt.path) {
+            "pool"
         } else {
             "worktree"
         };
@@ -180,10 +262,11 @@ async fn cleanup_orphaned(profile: &str, force: bool) -> Result<()> {
         let main_repo = GitWorktree::find_main_repo(&current_dir)?;
         let git_wt = GitWorktree::new(main_repo)?;
         let worktrees = git_wt.list_worktrees()?;
+        let pooled = pool::list(&git_wt).unwrap_or_default();
 
         for wt in worktrees {
             let is_main = wt.path == git_wt.repo_path;
-            if is_main {
+            if is_main || pooled.contains(&wt.path) {
                 continue;
             }
 
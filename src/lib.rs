@@ -1,15 +1,14 @@
 //! Agent of Empires library - Core functionality for the terminal session manager
+//!
+//! Session management lives in the `aoe-core` crate; its modules are
+//! re-exported here so the CLI and TUI reach them as `crate::session` etc.
+
+pub use aoe_core::{
+    agents, containers, git, hooks, migrations, multiplexer, notifications, process, session,
+    sound, tmux, tray,
+};
 
-pub mod agents;
 pub mod cli;
-pub mod containers;
-pub mod git;
-pub mod hooks;
-pub mod migrations;
-pub mod process;
-pub mod session;
-pub mod sound;
 pub mod terminal;
-pub mod tmux;
 pub mod tui;
 pub mod update;
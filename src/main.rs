@@ -2,6 +2,7 @@
 
 use agent_of_empires::cli::{self, Cli, Commands};
 use agent_of_empires::migrations;
+use agent_of_empires::session::audit;
 use agent_of_empires::tui;
 use anyhow::Result;
 use clap::{CommandFactory, Parser};
@@ -19,7 +20,7 @@ async fn main() -> Result<()> {
             .and_then(|p| std::fs::File::create(p).ok());
         if let Some(file) = log_file {
             tracing_subscriber::fmt()
-                .with_env_filter("agent_of_empires=debug")
+                .with_env_filter("agent_of_empires=debug,aoe_core=debug")
                 .with_writer(std::sync::Mutex::new(file))
                 .with_ansi(false)
                 .init();
@@ -33,6 +34,27 @@ async fn main() -> Result<()> {
 
     let cli = Cli::parse();
 
+    let replay = match &cli.replay {
+        Some(_) if cli.command.is_some() => {
+            anyhow::bail!("--replay only applies to the TUI and cannot be used with a subcommand")
+        }
+        Some(path) => Some(tui::Replay::load(path)?),
+        None => None,
+    };
+
+    if cli.demo {
+        if cli.command.is_some() {
+            anyhow::bail!("--demo only applies to the TUI and cannot be used with a subcommand");
+        }
+        return tui::run_demo(replay).await;
+    }
+
+    audit::set_source(match cli.command {
+        None => audit::Source::Tui,
+        Some(Commands::Ipc(_)) => audit::Source::Api,
+        Some(_) => audit::Source::Cli,
+    });
+
     // Handle commands that don't need app data or migrations.
     // These work in read-only/sandboxed environments (e.g. Nix builds).
     match cli.command {
@@ -45,10 +67,16 @@ async fn main() -> Result<()> {
             use cli::tmux::TmuxCommands;
             return match command {
                 TmuxCommands::Status(args) => cli::tmux::run_status(args),
+                TmuxCommands::Check => cli::tmux::run_check(),
             };
         }
         Some(Commands::Sounds { command }) => return cli::sounds::run(command).await,
+        Some(Commands::Audit { command }) => return cli::audit::run(command).await,
+        Some(Commands::Stats(args)) => {
+            return cli::stats::run(cli.profile.as_deref().unwrap_or_default(), args)
+        }
         Some(Commands::Uninstall(args)) => return cli::uninstall::run(args).await,
+        Some(Commands::Shim(args)) => return cli::shim::run(args).await,
         _ => {}
     }
 
@@ -60,16 +88,27 @@ async fn main() -> Result<()> {
     }
 
     match cli.command {
-        Some(Commands::Add(args)) => cli::add::run(&profile, args).await,
+        Some(Commands::Add(args)) => cli::add::run(&profile, *args).await,
         Some(Commands::List(args)) => cli::list::run(&profile, args).await,
         Some(Commands::Remove(args)) => cli::remove::run(&profile, args).await,
+        Some(Commands::Rename(args)) => cli::rename::run(&profile, args).await,
         Some(Commands::Send(args)) => cli::send::run(&profile, args).await,
+        Some(Commands::Wait(args)) => cli::wait::run(&profile, args).await,
         Some(Commands::Status(args)) => cli::status::run(&profile, args).await,
+        Some(Commands::Top(args)) => cli::top::run(&profile, args).await,
+        Some(Commands::Interactive(args)) => cli::interactive::run(&profile, args).await,
+        Some(Commands::Serve(args)) => cli::serve::run(&profile, args).await,
+        Some(Commands::Share(args)) => cli::share::run(&profile, args).await,
+        Some(Commands::Ipc(args)) => cli::ipc::run(&profile, args).await,
         Some(Commands::Session { command }) => cli::session::run(&profile, command).await,
         Some(Commands::Group { command }) => cli::group::run(&profile, command).await,
+        Some(Commands::Trash { command }) => cli::trash::run(&profile, command).await,
+        Some(Commands::Config { command }) => cli::config::run(&profile, command).await,
         Some(Commands::Profile { command }) => cli::profile::run(command).await,
+        Some(Commands::Template { command }) => cli::template::run(command).await,
         Some(Commands::Worktree { command }) => cli::worktree::run(&profile, command).await,
-        None => tui::run(&profile, debug_log_warning).await,
+        Some(Commands::Tray { command }) => cli::tray::run(&profile, command),
+        None => tui::run(&profile, debug_log_warning, replay).await,
         _ => unreachable!(),
     }
 }
@@ -1,68 +0,0 @@
-//! Process utilities for tmux session management
-
-use std::process::Command;
-
-#[cfg(target_os = "linux")]
-mod linux;
-
-#[cfg(target_os = "macos")]
-mod macos;
-
-/// Get the PID of the shell process running in a tmux pane
-pub fn get_pane_pid(session_name: &str) -> Option<u32> {
-    // Use `^.0` to target the first window's first pane regardless of
-    // base-index or which pane is active, so we always query the agent's
-    // pane even when the user has created additional tmux windows or split
-    // panes.  See #435, #488.
-    let target = format!("{session_name}:^.0");
-    let output = Command::new("tmux")
-        .args(["display-message", "-t", &target, "-p", "#{pane_pid}"])
-        .output()
-        .ok()?;
-
-    if !output.status.success() {
-        return None;
-    }
-
-    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
-}
-
-/// Get the foreground process group leader PID for a given shell PID
-/// This finds the actual process that has the terminal foreground
-pub fn get_foreground_pid(shell_pid: u32) -> Option<u32> {
-    #[cfg(target_os = "linux")]
-    {
-        linux::get_foreground_pid(shell_pid)
-    }
-
-    #[cfg(target_os = "macos")]
-    {
-        macos::get_foreground_pid(shell_pid)
-    }
-
-    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
-    {
-        let _ = shell_pid;
-        None
-    }
-}
-
-/// Kill a process and all its descendants
-/// Sends SIGTERM first, then SIGKILL to any survivors
-pub fn kill_process_tree(pid: u32) {
-    #[cfg(target_os = "linux")]
-    {
-        linux::kill_process_tree(pid);
-    }
-
-    #[cfg(target_os = "macos")]
-    {
-        macos::kill_process_tree(pid);
-    }
-
-    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
-    {
-        let _ = pid;
-        // No-op on unsupported platforms, fall back to tmux kill-session only
-    }
-}
@@ -4,3 +4,26 @@
 pub fn get_size() -> Option<(u16, u16)> {
     crossterm::terminal::size().ok()
 }
+
+/// Editor for opening files: `$EDITOR`, else vim or nano if installed,
+/// else vim.
+pub fn preferred_editor() -> String {
+    let installed = |cmd: &str| {
+        std::process::Command::new(cmd)
+            .arg("--version")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok()
+    };
+    std::env::var("EDITOR")
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .or_else(|| {
+            ["vim", "nano"]
+                .into_iter()
+                .find(|cmd| installed(cmd))
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| "vim".to_string())
+}
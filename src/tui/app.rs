@@ -6,13 +6,21 @@ use ratatui::prelude::*;
 use std::path::PathBuf;
 use std::time::Duration;
 
+use super::components::Toast;
+use super::config_watcher::{self, ConfigWatcher};
 use super::home::{HomeView, TerminalMode};
+use super::input_queue::InputQueue;
+use super::replay::{self, Cue, Replay};
 use super::styles::load_theme;
 use super::styles::Theme;
-use crate::session::{get_update_settings, load_config, save_config};
+use crate::session::{get_update_settings, load_config, resolve_config, save_config, ColorVision};
 use crate::tmux::AvailableTools;
+use crate::update::agents::{self, AgentVersion};
 use crate::update::{check_for_update, UpdateInfo};
 
+/// How often installed agent versions are re-checked
+const AGENT_UPDATE_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 /// Temporarily leave TUI mode, run a closure, and restore TUI mode.
 /// Drains stale events and clears the terminal on return.
 fn with_raw_mode_disabled<F, R>(
@@ -27,17 +35,22 @@ where
         terminal.backend_mut(),
         crossterm::terminal::LeaveAlternateScreen,
         crossterm::event::DisableMouseCapture,
+        crossterm::event::DisableFocusChange,
         crossterm::cursor::Show
     )?;
     std::io::Write::flush(terminal.backend_mut())?;
+    super::terminal_title::suspend();
 
     let result = f();
 
+    super::terminal_title::resume();
+
     crossterm::terminal::enable_raw_mode()?;
     crossterm::execute!(
         terminal.backend_mut(),
         crossterm::terminal::EnterAlternateScreen,
         crossterm::event::EnableMouseCapture,
+        crossterm::event::EnableFocusChange,
         crossterm::cursor::Hide
     )?;
     std::io::Write::flush(terminal.backend_mut())?;
@@ -56,8 +69,19 @@ pub struct App {
     should_quit: bool,
     theme: Theme,
     needs_redraw: bool,
+    input: InputQueue,
+    /// Scripted keys played in as if typed (`aoe --replay`)
+    replay: Option<Replay>,
     update_info: Option<UpdateInfo>,
     update_rx: Option<tokio::sync::oneshot::Receiver<anyhow::Result<UpdateInfo>>>,
+    agent_updates_rx: Option<tokio::sync::oneshot::Receiver<anyhow::Result<Vec<AgentVersion>>>>,
+    /// When installed agent versions were last checked
+    agent_updates_checked_at: Option<std::time::Instant>,
+    config_watcher: ConfigWatcher,
+    /// Directory the TUI was launched from, for its `.aoe/config.toml`
+    launch_dir: Option<PathBuf>,
+    /// Fingerprint of the last applied config, to skip no-op reloads
+    config_fingerprint: Option<String>,
 }
 
 /// Check if the app version changed and return the previous version if changelog should be shown.
@@ -93,7 +117,7 @@ impl App {
         } else {
             &config.theme.name
         };
-        let theme = load_theme(theme_name);
+        let theme = load_theme(theme_name).with_color_vision(color_vision(home.config_profile()));
         let current_version = env!("CARGO_PKG_VERSION").to_string();
 
         if !config.app_state.has_seen_welcome {
@@ -106,27 +130,102 @@ impl App {
             home.show_changelog(config.app_state.last_seen_version.clone());
             config.app_state.last_seen_version = Some(current_version);
             save_config(&config)?;
+        } else if !config.app_state.has_seen_nested_tmux_notice && crate::tmux::nested::is_nested()
+        {
+            let prefix = crate::tmux::nested::prefix_label();
+            home.info_dialog = Some(crate::tui::dialogs::InfoDialog::new(
+                "Running Inside tmux",
+                &format!(
+                    "aoe is running inside tmux, so attaching to a session switches this tmux client to it instead of opening a new one. Press {p} L to come back to aoe. {p} d would detach your whole tmux client, aoe included.",
+                    p = prefix
+                ),
+            ));
+            config.app_state.has_seen_nested_tmux_notice = true;
+            save_config(&config)?;
         }
 
+        let launch_dir = config_watcher::launch_dir();
+        let config_fingerprint =
+            config_watcher::load_for_reload(home.config_profile(), launch_dir.as_deref())
+                .ok()
+                .map(|r| r.fingerprint);
+
         Ok(Self {
             home,
             should_quit: false,
             theme,
             needs_redraw: true,
+            input: InputQueue::new(),
+            replay: None,
             update_info: None,
             update_rx: None,
+            agent_updates_rx: None,
+            agent_updates_checked_at: None,
+            config_watcher: ConfigWatcher::new(launch_dir.as_deref()),
+            launch_dir,
+            config_fingerprint,
         })
     }
 
+    /// Play the sample sessions `demo::prepare` created (`aoe --demo`).
+    pub fn start_demo(&mut self) {
+        self.home.start_demo();
+    }
+
+    /// Play `replay` into the TUI before taking input from the terminal.
+    pub fn set_replay(&mut self, replay: Replay) {
+        self.replay = Some(replay);
+    }
+
     pub fn show_startup_warning(&mut self, message: &str) {
         self.home.info_dialog = Some(crate::tui::dialogs::InfoDialog::new("Warning", message));
     }
 
     pub fn set_theme(&mut self, name: &str) {
-        self.theme = load_theme(name);
+        self.theme = load_theme(name).with_color_vision(color_vision(self.home.config_profile()));
         self.needs_redraw = true;
     }
 
+    /// Re-read config files after an external edit and apply what can change
+    /// live (theme, sounds, terminal mode). Invalid files leave the running
+    /// config untouched and surface the parse error in a toast.
+    fn reload_config(&mut self) {
+        match config_watcher::load_for_reload(
+            self.home.config_profile(),
+            self.launch_dir.as_deref(),
+        ) {
+            Ok(reloaded) => {
+                if self.config_fingerprint.as_deref() == Some(reloaded.fingerprint.as_str()) {
+                    return;
+                }
+                self.config_fingerprint = Some(reloaded.fingerprint);
+                let theme_name = if reloaded.config.theme.name.is_empty() {
+                    "phosphor"
+                } else {
+                    &reloaded.config.theme.name
+                };
+                self.set_theme(theme_name);
+                self.home.refresh_from_config();
+                self.home.show_toast(Toast::info("Config reloaded"));
+            }
+            Err(e) => {
+                self.home
+                    .show_toast(Toast::error(format!("Config not reloaded: {:#}", e)));
+            }
+        }
+    }
+
+    /// Remember the config as it is after a change made inside the TUI, so
+    /// the file watcher doesn't report the TUI's own write as an outside
+    /// edit.
+    fn refresh_config_fingerprint(&mut self) {
+        if let Ok(reloaded) =
+            config_watcher::load_for_reload(self.home.config_profile(), self.launch_dir.as_deref())
+        {
+            self.config_fingerprint = Some(reloaded.fingerprint);
+        }
+    }
+
     pub async fn run(
         &mut self,
         terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
@@ -134,10 +233,15 @@ impl App {
         // Initial render
         terminal.clear()?;
         terminal.draw(|f| self.render(f))?;
+        self.update_terminal_title();
 
         // Refresh tmux session cache
         crate::tmux::refresh_session_cache();
 
+        // The list was drawn from the saved statuses; replace them with live
+        // ones right away rather than after the first interval
+        self.home.request_status_refresh();
+
         // Spawn async update check
         let settings = get_update_settings();
         if settings.check_enabled {
@@ -151,8 +255,10 @@ impl App {
 
         let mut last_status_refresh = std::time::Instant::now();
         let mut last_disk_refresh = std::time::Instant::now();
-        const STATUS_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+        let mut last_draw = std::time::Instant::now();
         const DISK_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+        // Ages and the other tabs change with time even when no session does
+        const CLOCK_REDRAW_INTERVAL: Duration = Duration::from_secs(1);
 
         loop {
             // Force full redraw if needed (e.g., after returning from tmux)
@@ -161,29 +267,42 @@ impl App {
                 self.needs_redraw = false;
             }
 
-            // Poll with short timeout for responsive input
-            if event::poll(Duration::from_millis(50))? {
-                match event::read()? {
-                    Event::Key(key) => {
-                        self.handle_key(key, terminal).await?;
-
-                        // Draw immediately after input for responsiveness
-                        terminal.draw(|f| self.render(f))?;
-
-                        if self.should_quit {
-                            break;
+            self.advance_replay(terminal)?;
+
+            // Poll with short timeout for responsive input, then handle
+            // everything that arrived in order before drawing once
+            if self.input.fill(Duration::from_millis(50))? {
+                let mut interacted = false;
+                while let Some(event) = self.input.pop() {
+                    match event {
+                        Event::Key(key) => {
+                            // Focus events are lost while attached to a session
+                            self.home.set_focused(true);
+                            self.handle_key(key, terminal).await?;
+                            interacted = true;
                         }
-                        continue; // Skip status refresh this iteration for responsiveness
+                        Event::Mouse(mouse) => {
+                            self.handle_mouse(mouse, terminal).await?;
+                            interacted = true;
+                        }
+                        Event::FocusGained => self.home.set_focused(true),
+                        Event::FocusLost => self.home.set_focused(false),
+                        // Re-flow right away; the preview re-captures at the new size
+                        Event::Resize(_, _) => interacted = true,
+                        _ => {}
                     }
-                    Event::Mouse(mouse) => {
-                        self.handle_mouse(mouse, terminal).await?;
-
-                        // Draw immediately after input for responsiveness
-                        terminal.draw(|f| self.render(f))?;
-
-                        continue;
+                    if self.should_quit {
+                        break;
                     }
-                    _ => {}
+                }
+                if self.should_quit {
+                    break;
+                }
+
+                if interacted {
+                    // Draw immediately after input for responsiveness
+                    terminal.draw(|f| self.render(f))?;
+                    continue; // Skip status refresh this iteration for responsiveness
                 }
             }
 
@@ -195,9 +314,23 @@ impl App {
             // Periodic refreshes (only when no input pending)
             let mut refresh_needed = false;
 
+            self.request_agent_update_check(false);
+            if self.poll_agent_update_check() {
+                refresh_needed = true;
+            }
+
             // Request status refresh every interval (non-blocking)
-            if last_status_refresh.elapsed() >= STATUS_REFRESH_INTERVAL {
+            // Slowed down in eco mode while the terminal is unfocused
+            let slowdown = self.home.poll_slowdown();
+            if last_status_refresh.elapsed() >= self.home.status_refresh_interval() {
                 self.home.request_status_refresh();
+                self.home.request_health_checks();
+                self.home.request_test_watches();
+                // Git checks hold off until the first status pass is in
+                if self.home.is_hydrated() {
+                    self.home.request_merge_checks();
+                    self.home.request_conflict_checks();
+                }
                 last_status_refresh = std::time::Instant::now();
             }
 
@@ -206,6 +339,57 @@ impl App {
                 refresh_needed = true;
             }
 
+            // Captures the selected pane at an adaptive rate; only a changed
+            // pane is redrawn
+            if self.home.refresh_preview() {
+                refresh_needed = true;
+            }
+
+            if self.home.apply_health_results() {
+                refresh_needed = true;
+            }
+
+            if self.home.apply_watch_results() {
+                refresh_needed = true;
+            }
+
+            if self.home.apply_command_results() {
+                refresh_needed = true;
+            }
+
+            if self.home.apply_merge_results() {
+                refresh_needed = true;
+            }
+
+            if self.home.apply_conflict_results() {
+                refresh_needed = true;
+            }
+
+            let (cloned, action) = self.home.apply_clone_results();
+            if cloned {
+                refresh_needed = true;
+            }
+            if let Some(action) = action {
+                self.execute_action(action, terminal)?;
+            }
+
+            // Reads the whole stats log, so it waits like the git checks
+            if self.home.is_hydrated() && self.home.check_budgets() {
+                refresh_needed = true;
+            }
+
+            if self.home.check_credentials() {
+                refresh_needed = true;
+            }
+
+            if self.home.start_unblocked_sessions() {
+                refresh_needed = true;
+            }
+
+            if self.home.resume_after_login() {
+                refresh_needed = true;
+            }
+
             // Check for and apply deletion results (non-blocking)
             if self.home.apply_deletion_results() {
                 refresh_needed = true;
@@ -223,16 +407,32 @@ impl App {
                 refresh_needed = true;
             }
 
+            if self.home.tick_toast() {
+                refresh_needed = true;
+            }
+
+            // Apply config edits made outside the TUI
+            if self.config_watcher.poll_changed() {
+                self.reload_config();
+                refresh_needed = true;
+            }
+
             // Periodic disk refresh to sync with other instances
-            if last_disk_refresh.elapsed() >= DISK_REFRESH_INTERVAL {
+            if last_disk_refresh.elapsed() >= DISK_REFRESH_INTERVAL * slowdown {
                 self.home.reload()?;
                 last_disk_refresh = std::time::Instant::now();
                 refresh_needed = true;
             }
 
+            if last_draw.elapsed() >= CLOCK_REDRAW_INTERVAL * slowdown {
+                refresh_needed = true;
+            }
+
             // Single draw after all refreshes to avoid flicker
             if refresh_needed {
                 terminal.draw(|f| self.render(f))?;
+                self.update_terminal_title();
+                last_draw = std::time::Instant::now();
             }
 
             if self.should_quit {
@@ -243,15 +443,150 @@ impl App {
         if let Err(e) = self.home.save() {
             tracing::error!("Failed to save on quit: {}", e);
         }
+        self.home.save_ui_state();
+
+        Ok(())
+    }
 
+    /// Hand the replay's due keys to the input queue and run its screen
+    /// steps against a fresh frame.
+    fn advance_replay(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    ) -> Result<()> {
+        let Some(mut script) = self.replay.take() else {
+            return Ok(());
+        };
+        while let Some(cue) = script.next(self.input.is_empty()) {
+            match cue {
+                Cue::Key(key) => self.input.push(Event::Key(key)),
+                Cue::Screen => {
+                    let frame = terminal.draw(|f| self.render(f))?;
+                    if !script.screen(&replay::screen_text(frame.buffer))? {
+                        break;
+                    }
+                }
+            }
+        }
+        if !script.is_finished() {
+            self.replay = Some(script);
+        }
         Ok(())
     }
 
+    fn update_terminal_title(&self) {
+        match self.home.terminal_title() {
+            Some(title) => super::terminal_title::set(&title),
+            None => super::terminal_title::restore(),
+        }
+    }
+
     fn render(&mut self, frame: &mut Frame) {
         self.home
             .render(frame, frame.area(), &self.theme, self.update_info.as_ref());
     }
 
+    /// Check installed agent versions in the background, once an hour (the
+    /// check itself is cached for `updates.check_interval_hours`), or right
+    /// away with `force`.
+    fn request_agent_update_check(&mut self, force: bool) {
+        if self.agent_updates_rx.is_some() || self.home.is_demo() {
+            return;
+        }
+        if !force
+            && self
+                .agent_updates_checked_at
+                .is_some_and(|t| t.elapsed() < AGENT_UPDATE_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.agent_updates_checked_at = Some(std::time::Instant::now());
+        let settings = get_update_settings();
+        if !settings.check_enabled || !settings.check_agents {
+            self.home.set_agent_updates(Vec::new());
+            return;
+        }
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.agent_updates_rx = Some(rx);
+        tokio::spawn(async move {
+            let _ = tx.send(agents::check_agent_updates(force).await);
+        });
+    }
+
+    /// Returns true if the agent update list changed.
+    fn poll_agent_update_check(&mut self) -> bool {
+        let Some(rx) = &mut self.agent_updates_rx else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                self.agent_updates_rx = None;
+                match result {
+                    Ok(updates) => self.home.set_agent_updates(updates),
+                    Err(e) => {
+                        tracing::warn!("Agent version check failed: {}", e);
+                        false
+                    }
+                }
+            }
+            Err(tokio::sync::oneshot::error::TryRecvError::Empty) => false,
+            Err(tokio::sync::oneshot::error::TryRecvError::Closed) => {
+                self.agent_updates_rx = None;
+                false
+            }
+        }
+    }
+
+    /// Run the upgrade command of every agent with a newer release in the
+    /// foreground, then re-check their versions.
+    fn upgrade_agents(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    ) -> Result<()> {
+        let settings = get_update_settings();
+        let commands: Vec<(String, String)> = self
+            .home
+            .agent_updates()
+            .iter()
+            .filter_map(|u| {
+                agents::upgrade_command(&settings, &u.agent).map(|c| (u.agent.clone(), c))
+            })
+            .collect();
+        if commands.is_empty() {
+            self.home.show_toast(Toast::info(
+                "No upgrade command configured (updates.agent_upgrade_commands)",
+            ));
+            return Ok(());
+        }
+
+        let failed = with_raw_mode_disabled(terminal, || {
+            let mut failed = Vec::new();
+            for (agent, command) in &commands {
+                println!("\n$ {}", command);
+                let ok = std::process::Command::new("sh")
+                    .args(["-c", command])
+                    .status()
+                    .is_ok_and(|s| s.success());
+                if !ok {
+                    failed.push(agent.clone());
+                }
+            }
+            println!("\nPress Enter to return to aoe");
+            let _ = std::io::stdin().read_line(&mut String::new());
+            failed
+        })?;
+
+        self.resumed();
+        if !failed.is_empty() {
+            self.home.show_toast(Toast::error(format!(
+                "Upgrade failed for {}",
+                failed.join(", ")
+            )));
+        }
+        self.request_agent_update_check(true);
+        Ok(())
+    }
+
     /// Poll for update check result (non-blocking).
     /// Returns true if an update is available and was just received.
     fn poll_update_check(&mut self) -> bool {
@@ -293,7 +628,22 @@ fn poll_update_receiver(
     }
 }
 
+/// The configured status palette, including profile overrides.
+fn color_vision(profile: &str) -> ColorVision {
+    resolve_config(profile)
+        .map(|c| c.theme.color_vision)
+        .unwrap_or_default()
+}
+
 impl App {
+    /// Back from a session or editor: redraw from scratch and drop keys
+    /// buffered before leaving, as `with_raw_mode_disabled` does for the
+    /// terminal's own queue
+    fn resumed(&mut self) {
+        self.needs_redraw = true;
+        self.input.clear();
+    }
+
     async fn handle_key(
         &mut self,
         key: KeyEvent,
@@ -334,17 +684,46 @@ impl App {
         action: Action,
         terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
     ) -> Result<()> {
+        if self.home.is_demo()
+            && matches!(
+                action,
+                Action::AttachSession(_)
+                    | Action::ObserveSession(_)
+                    | Action::AttachTerminal(..)
+                    | Action::Reauthenticate(_)
+                    | Action::AttachCommand(_)
+                    | Action::StopSession(_)
+                    | Action::UpgradeAgents
+            )
+        {
+            self.home
+                .show_toast(Toast::info(super::demo::DEMO_UNAVAILABLE));
+            return Ok(());
+        }
+
         match action {
             Action::Quit => self.should_quit = true,
             Action::AttachSession(id) => {
                 self.attach_session(&id, terminal)?;
             }
+            Action::ObserveSession(id) => {
+                self.observe_session(&id, terminal)?;
+            }
             Action::AttachTerminal(id, mode) => {
                 self.attach_terminal(&id, mode, terminal)?;
             }
             Action::EditFile(path) => {
                 self.edit_file(&path, terminal)?;
             }
+            Action::EditNotes(id) => {
+                self.edit_notes(&id, terminal)?;
+            }
+            Action::Reauthenticate(id) => {
+                self.reauthenticate(&id, terminal)?;
+            }
+            Action::AttachCommand(id) => {
+                self.attach_command(&id, terminal)?;
+            }
             Action::StopSession(id) => {
                 if let Some(inst) = self.home.get_instance(&id) {
                     let inst_clone = inst.clone();
@@ -374,6 +753,12 @@ impl App {
             Action::SetTheme(name) => {
                 self.set_theme(&name);
             }
+            Action::ConfigChanged => {
+                self.refresh_config_fingerprint();
+            }
+            Action::UpgradeAgents => {
+                self.upgrade_agents(terminal)?;
+            }
         }
         Ok(())
     }
@@ -397,6 +782,14 @@ impl App {
             if tmux_session.exists() {
                 let _ = tmux_session.kill();
             }
+            if let Some((lock, holder)) = self.home.lock_conflict(session_id) {
+                self.home.queue_for_lock(session_id, &lock, &holder);
+                return Ok(());
+            }
+            if let Some(reached) = self.home.provider_limit(session_id) {
+                self.home.queue_for_provider(session_id, reached);
+                return Ok(());
+            }
             // Show warning (once) if custom instruction is configured for an unsupported agent
             if instance.is_sandboxed() {
                 let has_instruction = instance
@@ -443,18 +836,18 @@ impl App {
                 .set_instance_status(session_id, crate::session::Status::Starting);
             let mut inst = instance.clone();
             if let Err(e) = inst.start_with_size_opts(size, skip_on_launch) {
-                self.home
-                    .set_instance_error(session_id, Some(e.to_string()));
-                self.home
-                    .set_instance_status(session_id, crate::session::Status::Error);
+                self.home.report_start_failure(session_id, &e);
                 return Ok(());
             }
             self.home.set_instance_error(session_id, None);
+            if instance.initial_prompt.is_some() && inst.initial_prompt.is_none() {
+                self.home.clear_initial_prompt(session_id)?;
+            }
         }
 
         let attach_result = with_raw_mode_disabled(terminal, || tmux_session.attach())?;
 
-        self.needs_redraw = true;
+        self.resumed();
         crate::tmux::refresh_session_cache();
         self.home.reload()?;
         self.home.select_session_by_id(session_id);
@@ -466,6 +859,102 @@ impl App {
         Ok(())
     }
 
+    /// Watch a running session through a read-only tmux client. Unlike
+    /// attaching, a stopped session is not started.
+    fn observe_session(
+        &mut self,
+        session_id: &str,
+        terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    ) -> Result<()> {
+        let tmux_session = match self.home.get_instance(session_id) {
+            Some(inst) => inst.tmux_session()?,
+            None => return Ok(()),
+        };
+        if !tmux_session.exists() {
+            self.home
+                .show_toast(Toast::info("Session is not running; nothing to observe"));
+            return Ok(());
+        }
+
+        let attach_result = with_raw_mode_disabled(terminal, || tmux_session.attach_read_only())?;
+
+        self.resumed();
+        crate::tmux::refresh_session_cache();
+        self.home.reload()?;
+        self.home.select_session_by_id(session_id);
+
+        if let Err(e) = attach_result {
+            tracing::warn!("tmux observe returned error: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Run the agent's login flow in a temporary tmux session. The agent is
+    /// restarted by `HomeView::resume_after_login` once that session closes.
+    fn reauthenticate(
+        &mut self,
+        session_id: &str,
+        terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    ) -> Result<()> {
+        let mut instance = match self.home.get_instance(session_id) {
+            Some(inst) => inst.clone(),
+            None => return Ok(()),
+        };
+
+        let login = match instance.start_login_with_size(crate::terminal::get_size()) {
+            Ok(login) => login,
+            Err(e) => {
+                self.home
+                    .set_instance_error(session_id, Some(format!("Login failed: {}", e)));
+                return Ok(());
+            }
+        };
+        self.home.begin_reauth(session_id);
+
+        let attach_result = with_raw_mode_disabled(terminal, || login.attach())?;
+
+        self.resumed();
+        crate::tmux::refresh_session_cache();
+        self.home.resume_after_login();
+        self.home.select_session_by_id(session_id);
+
+        if let Err(e) = attach_result {
+            tracing::warn!("tmux login attach returned error: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Attach to the companion session where the session's last named
+    /// command ran.
+    fn attach_command(
+        &mut self,
+        session_id: &str,
+        terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    ) -> Result<()> {
+        let session = match self
+            .home
+            .get_instance(session_id)
+            .map(|inst| inst.command_tmux_session())
+        {
+            Some(Ok(session)) => session,
+            _ => return Ok(()),
+        };
+
+        let attach_result = with_raw_mode_disabled(terminal, || session.attach())?;
+
+        self.resumed();
+        crate::tmux::refresh_session_cache();
+        self.home.select_session_by_id(session_id);
+
+        if let Err(e) = attach_result {
+            tracing::warn!("tmux command attach returned error: {}", e);
+        }
+
+        Ok(())
+    }
+
     fn attach_terminal(
         &mut self,
         session_id: &str,
@@ -520,7 +1009,7 @@ impl App {
 
         let attach_result = with_raw_mode_disabled(terminal, attach_fn)?;
 
-        self.needs_redraw = true;
+        self.resumed();
         crate::tmux::refresh_session_cache();
         self.home.reload()?;
         self.home.select_session_by_id(session_id);
@@ -532,37 +1021,38 @@ impl App {
         Ok(())
     }
 
+    fn edit_notes(
+        &mut self,
+        session_id: &str,
+        terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    ) -> Result<()> {
+        let current = match self.home.get_instance(session_id) {
+            Some(inst) => inst.notes.clone().unwrap_or_default(),
+            None => return Ok(()),
+        };
+
+        let editor = crate::terminal::preferred_editor();
+        let id = session_id.to_string();
+        let result = with_raw_mode_disabled(terminal, move || {
+            crate::session::notes::edit_notes(&editor, &id, &current)
+        })?;
+        self.resumed();
+
+        match result {
+            Ok(text) => self.home.set_notes(session_id, &text)?,
+            Err(e) => self
+                .home
+                .show_toast(crate::tui::components::Toast::error(e.to_string())),
+        }
+        Ok(())
+    }
+
     fn edit_file(
         &mut self,
         path: &std::path::Path,
         terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
     ) -> Result<()> {
-        // Determine which editor to use (prefer vim, fall back to nano)
-        let editor = std::env::var("EDITOR")
-            .ok()
-            .or_else(|| {
-                // Check if vim is available
-                if std::process::Command::new("vim")
-                    .arg("--version")
-                    .stdout(std::process::Stdio::null())
-                    .stderr(std::process::Stdio::null())
-                    .status()
-                    .is_ok()
-                {
-                    Some("vim".to_string())
-                } else if std::process::Command::new("nano")
-                    .arg("--version")
-                    .stdout(std::process::Stdio::null())
-                    .stderr(std::process::Stdio::null())
-                    .status()
-                    .is_ok()
-                {
-                    Some("nano".to_string())
-                } else {
-                    None
-                }
-            })
-            .unwrap_or_else(|| "vim".to_string());
+        let editor = crate::terminal::preferred_editor();
 
         let path = path.to_owned();
         let editor_clone = editor.clone();
@@ -572,7 +1062,7 @@ impl App {
                 .status()
         })?;
 
-        self.needs_redraw = true;
+        self.resumed();
 
         // Refresh diff view if it's open (file may have changed)
         if let Some(ref mut diff_view) = self.home.diff_view {
@@ -594,10 +1084,19 @@ impl App {
 pub enum Action {
     Quit,
     AttachSession(String),
+    /// Attach to a session read-only
+    ObserveSession(String),
     AttachTerminal(String, TerminalMode),
     EditFile(PathBuf),
+    EditNotes(String),
+    Reauthenticate(String),
+    AttachCommand(String),
     StopSession(String),
     SetTheme(String),
+    /// Settings were saved or the profile switched from inside the TUI
+    ConfigChanged,
+    /// Run the upgrade command of every agent with a newer release
+    UpgradeAgents,
 }
 
 #[cfg(test)]
@@ -0,0 +1,61 @@
+//! Background clone of a git URL entered as a new session's path
+//!
+//! The clone runs on its own thread so a large repository never blocks the
+//! TUI. Progress lines and the outcome come back over a channel and are
+//! applied by `HomeView::apply_clone_results`, which then carries on with
+//! creating the session in the cloned repository.
+
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+
+use crate::git::clone;
+use crate::tui::dialogs::NewSessionData;
+
+pub enum CloneEvent {
+    /// A progress line from git
+    Progress(String),
+    /// The clone finished; `data` is the session to create from it
+    Done {
+        data: Box<NewSessionData>,
+        result: Result<PathBuf, String>,
+    },
+}
+
+pub struct ClonePoller {
+    event_tx: mpsc::Sender<CloneEvent>,
+    event_rx: mpsc::Receiver<CloneEvent>,
+}
+
+impl ClonePoller {
+    pub fn new() -> Self {
+        let (event_tx, event_rx) = mpsc::channel();
+        Self { event_tx, event_rx }
+    }
+
+    /// Clone `data.path` into `clone_dir` (non-blocking).
+    pub fn clone(&self, data: NewSessionData, clone_dir: String, shallow: bool) {
+        let tx = self.event_tx.clone();
+        thread::spawn(move || {
+            let result = clone::ensure_clone(&data.path, &clone_dir, shallow, |line| {
+                let _ = tx.send(CloneEvent::Progress(line.to_string()));
+            })
+            .map_err(|e| e.to_string());
+            let _ = tx.send(CloneEvent::Done {
+                data: Box::new(data),
+                result,
+            });
+        });
+    }
+
+    /// Collect all events without blocking.
+    pub fn try_recv_events(&self) -> Vec<CloneEvent> {
+        self.event_rx.try_iter().collect()
+    }
+}
+
+impl Default for ClonePoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,69 @@
+//! Background watcher for named session commands
+//!
+//! Each running command gets a thread that polls its companion tmux session
+//! until the command exits or the session is closed. Results come back over
+//! a channel and are applied by `HomeView::apply_command_results`.
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crate::tmux::CommandSession;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A named command that stopped running
+#[derive(Debug)]
+pub struct CommandResult {
+    pub id: String,
+    pub name: String,
+    /// `None` when the companion session was closed before the command exited
+    pub exit_code: Option<i32>,
+}
+
+pub struct CommandPoller {
+    result_tx: mpsc::Sender<CommandResult>,
+    result_rx: mpsc::Receiver<CommandResult>,
+}
+
+impl CommandPoller {
+    pub fn new() -> Self {
+        let (result_tx, result_rx) = mpsc::channel();
+        Self {
+            result_tx,
+            result_rx,
+        }
+    }
+
+    /// Watch command `name` of session `id` running in `session` (non-blocking).
+    pub fn watch(&self, id: String, name: String, session: CommandSession) {
+        let tx = self.result_tx.clone();
+        thread::spawn(move || {
+            let exit_code = loop {
+                thread::sleep(POLL_INTERVAL);
+                if let Some(code) = session.exit_status() {
+                    break Some(code);
+                }
+                if !session.exists() {
+                    break None;
+                }
+            };
+            let _ = tx.send(CommandResult {
+                id,
+                name,
+                exit_code,
+            });
+        });
+    }
+
+    /// Collect all finished commands without blocking.
+    pub fn try_recv_results(&self) -> Vec<CommandResult> {
+        self.result_rx.try_iter().collect()
+    }
+}
+
+impl Default for CommandPoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
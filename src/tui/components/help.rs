@@ -7,7 +7,7 @@ use crate::session::config::SortOrder;
 use crate::tui::styles::Theme;
 
 const DIALOG_WIDTH: u16 = 50;
-const DIALOG_HEIGHT: u16 = 36;
+const DIALOG_HEIGHT: u16 = 47;
 #[cfg(test)]
 const BORDER_HEIGHT: u16 = 2;
 #[cfg(test)]
@@ -25,28 +25,38 @@ fn shortcuts() -> Vec<(&'static str, Vec<(&'static str, &'static str)>)> {
                 ("h/←", "Collapse group"),
                 ("l/→", "Expand group"),
                 ("g/G", "Go to top / bottom"),
-                ("PgUp/Dn", "Move 10 items up / down"),
+                ("PgUp/Dn", "Move 10 items; Shift scrolls preview"),
             ],
         ),
         (
             "Actions",
             vec![
-                ("Enter", "Attach to session"),
+                ("Enter/O", "Attach / observe read-only"),
                 ("n", "New session"),
                 ("N", "New from selection"),
-                ("x", "Stop session"),
+                ("x/R", "Stop / retry from scratch"),
                 ("d", "Delete session/group"),
-                ("r", "Rename session"),
+                ("r/B", "Rename session / bulk rename"),
                 ("m", "Send message to agent"),
+                ("e", "Edit session notes"),
+                ("A/y", "Log in again / answer permission"),
+                ("!/C/T", "Commands / checkpoints / processes"),
+                ("F", "Ask agent to resolve merge conflicts"),
             ],
         ),
         (
             "Views",
             vec![
+                ("1-6/Tab", "Switch tab (dashboard, inbox, ...)"),
                 ("t", "Toggle Agent/Terminal view"),
                 ("c", "Toggle container/host (sandbox)"),
                 ("D", "Diff view (git changes)"),
+                ("M", "Merge queue (land worktree branches)"),
+                ("b", "Board view (H/L move, A archive)"),
+                ("a", "Toggle activity timeline"),
+                ("v", "Toggle tool-call events view"),
                 ("H/L", "Resize list panel"),
+                ("z", "Cycle layout (auto/full/compact)"),
                 ("o", "Cycle sort forward"),
                 ("Ctrl+o", "Cycle sort backward"),
             ],
@@ -54,7 +64,8 @@ fn shortcuts() -> Vec<(&'static str, Vec<(&'static str, &'static str)>)> {
         (
             "Other",
             vec![
-                ("/", "Search"),
+                ("Ctrl+p", "Command palette"),
+                ("/ f", "Search / filter"),
                 ("n/N", "Next/prev match"),
                 ("s", "Settings"),
                 ("P", "Profiles"),
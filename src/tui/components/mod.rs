@@ -4,12 +4,16 @@ mod dir_picker;
 mod help;
 mod list_picker;
 mod preview;
+mod prompt_text;
 mod text_input;
+mod toast;
 
 pub use dir_picker::{DirPicker, DirPickerResult};
 pub use help::HelpOverlay;
 pub use list_picker::{ListPicker, ListPickerResult};
-pub use preview::Preview;
+pub use preview::{event_kind_color, Preview};
+pub use prompt_text::{dictionary, render_prompt_text, Dictionary};
 pub use text_input::{
     longest_common_prefix, render_text_field, render_text_field_with_ghost, GroupGhostCompletion,
 };
+pub use toast::Toast;
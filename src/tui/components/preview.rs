@@ -1,11 +1,14 @@
 //! Preview panel component
 
 use ansi_to_tui::IntoText;
+use chrono::Local;
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
-use crate::session::Instance;
-use crate::tui::styles::Theme;
+use crate::session::timeline::{EventKind, TimelineEvent};
+use crate::session::{ownership, Instance};
+use crate::tmux::output_parser::OutputEvent;
+use crate::tui::styles::{status_color, status_icon, Theme};
 
 pub struct Preview;
 
@@ -110,35 +113,156 @@ impl Preview {
         }
     }
 
+    /// Render the session's info and its output scrolled `scroll` lines up
+    /// from the bottom. Returns the scroll actually shown, which is less when
+    /// the output has fewer lines.
+    #[allow(clippy::too_many_arguments)]
     pub fn render_with_cache(
         frame: &mut Frame,
         area: Rect,
         instance: &Instance,
+        lock_summary: Option<&str>,
+        commands_summary: Option<&str>,
         cached_output: &str,
+        scroll: u16,
+        theme: &Theme,
+    ) -> u16 {
+        let chunks = Self::info_layout(area, instance, lock_summary, commands_summary);
+        Self::render_info(
+            frame,
+            chunks[0],
+            instance,
+            lock_summary,
+            commands_summary,
+            theme,
+        );
+        Self::render_output_cached(frame, chunks[1], instance, cached_output, scroll, theme)
+    }
+
+    /// Like `render_with_cache`, but shows the session's activity timeline in
+    /// place of its output.
+    pub fn render_with_timeline(
+        frame: &mut Frame,
+        area: Rect,
+        instance: &Instance,
+        lock_summary: Option<&str>,
+        commands_summary: Option<&str>,
+        events: &[TimelineEvent],
         theme: &Theme,
     ) {
-        // 3 base lines (path/tool/status) + optional profile + optional worktree block
+        let chunks = Self::info_layout(area, instance, lock_summary, commands_summary);
+        Self::render_info(
+            frame,
+            chunks[0],
+            instance,
+            lock_summary,
+            commands_summary,
+            theme,
+        );
+        Self::render_timeline(frame, chunks[1], events, theme);
+    }
+
+    /// Like `render_with_cache`, but lists structured events parsed from
+    /// the agent's output.
+    pub fn render_with_events(
+        frame: &mut Frame,
+        area: Rect,
+        instance: &Instance,
+        lock_summary: Option<&str>,
+        commands_summary: Option<&str>,
+        events: &[OutputEvent],
+        theme: &Theme,
+    ) {
+        let chunks = Self::info_layout(area, instance, lock_summary, commands_summary);
+        Self::render_info(
+            frame,
+            chunks[0],
+            instance,
+            lock_summary,
+            commands_summary,
+            theme,
+        );
+        Self::render_events(frame, chunks[1], events, theme);
+    }
+
+    fn info_layout(
+        area: Rect,
+        instance: &Instance,
+        lock_summary: Option<&str>,
+        commands_summary: Option<&str>,
+    ) -> std::rc::Rc<[Rect]> {
+        // 3 base lines (path/tool/status) + optional profile + optional owner + optional locks
+        // + optional commands + optional host sandbox + optional limits + optional proxy + optional env
+        // + optional notes + optional summary
+        // + optional worktree block
         let has_profile = !instance.source_profile.is_empty();
-        let base = if has_profile { 4 } else { 3 };
+        let base = 3
+            + u16::from(has_profile)
+            + u16::from(ownership::foreign_owner(instance).is_some())
+            + u16::from(lock_summary.is_some())
+            + u16::from(commands_summary.is_some())
+            + u16::from(Self::host_sandbox_label(instance).is_some())
+            + u16::from(Self::limits_label(instance).is_some())
+            + u16::from(instance.proxy.is_some())
+            + u16::from(instance.project_env.is_some())
+            + u16::from(Self::notes_summary(instance).is_some())
+            + u16::from(Self::final_summary_line(instance).is_some());
         let info_height = if instance.worktree_info.is_some() {
             base + 4 // blank + header + branch + main
         } else {
             base
         };
 
-        let chunks = Layout::default()
+        Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(info_height), // Info section
                 Constraint::Min(1),              // Output section
             ])
-            .split(area);
+            .split(area)
+    }
+
+    /// Host sandbox description, for sessions not running in a container.
+    fn host_sandbox_label(instance: &Instance) -> Option<String> {
+        instance
+            .host_sandbox
+            .filter(|_| !instance.is_sandboxed())
+            .map(|sb| format!("host ({})", sb.label()))
+    }
+
+    /// Resource limits, for sessions not running in a container.
+    fn limits_label(instance: &Instance) -> Option<String> {
+        instance
+            .resource_limits
+            .as_ref()
+            .filter(|_| !instance.is_sandboxed())
+            .map(|limits| limits.label())
+    }
+
+    /// First line of the session's notes.
+    fn notes_summary(instance: &Instance) -> Option<&str> {
+        instance
+            .notes
+            .as_deref()
+            .and_then(|n| n.lines().map(str::trim).find(|l| !l.is_empty()))
+    }
 
-        Self::render_info(frame, chunks[0], instance, theme);
-        Self::render_output_cached(frame, chunks[1], instance, cached_output, theme);
+    /// First line of the agent's final message from its last run.
+    fn final_summary_line(instance: &Instance) -> Option<&str> {
+        instance
+            .final_summary
+            .as_deref()
+            .and_then(|s| s.lines().next())
     }
 
-    fn render_info(frame: &mut Frame, area: Rect, instance: &Instance, theme: &Theme) {
+    fn render_info(
+        frame: &mut Frame,
+        area: Rect,
+        instance: &Instance,
+        lock_summary: Option<&str>,
+        commands_summary: Option<&str>,
+        theme: &Theme,
+    ) {
         let mut info_lines = Vec::new();
 
         if !instance.source_profile.is_empty() {
@@ -159,25 +283,100 @@ impl Preview {
             Line::from(vec![
                 Span::styled("Tool:    ", Style::default().fg(theme.dimmed)),
                 Span::styled(&instance.tool, Style::default().fg(theme.accent)),
+                match &instance.failed_over_from {
+                    Some(from) => Span::styled(
+                        format!("  fallback for {}", from),
+                        Style::default().fg(theme.waiting),
+                    ),
+                    None => Span::raw(""),
+                },
             ]),
             Line::from(vec![
                 Span::styled("Status:  ", Style::default().fg(theme.dimmed)),
                 Span::styled(
-                    format!("{:?}", instance.status),
-                    Style::default().fg(match instance.status {
-                        crate::session::Status::Running => theme.running,
-                        crate::session::Status::Waiting => theme.waiting,
-                        crate::session::Status::Idle => theme.idle,
-                        crate::session::Status::Unknown => theme.waiting,
-                        crate::session::Status::Stopped => theme.dimmed,
-                        crate::session::Status::Error => theme.error,
-                        crate::session::Status::Starting => theme.dimmed,
-                        crate::session::Status::Deleting => theme.waiting,
-                    }),
+                    format!("{} {:?}", status_icon(instance.status), instance.status),
+                    Style::default().fg(status_color(instance.status, theme)),
                 ),
+                if instance.auth_required {
+                    Span::styled(
+                        "  auth required (press A to log in)",
+                        Style::default().fg(theme.error),
+                    )
+                } else if instance.permission_prompt.is_some() {
+                    Span::styled(
+                        "  permission prompt (press y to answer)",
+                        Style::default().fg(theme.waiting),
+                    )
+                } else {
+                    Span::raw("")
+                },
             ]),
         ]);
 
+        if let Some(owner) = ownership::foreign_owner(instance) {
+            info_lines.push(Line::from(vec![
+                Span::styled("Owner:   ", Style::default().fg(theme.dimmed)),
+                Span::styled(owner, Style::default().fg(theme.waiting)),
+            ]));
+        }
+
+        if let Some(summary) = lock_summary {
+            info_lines.push(Line::from(vec![
+                Span::styled("Locks:   ", Style::default().fg(theme.dimmed)),
+                Span::styled(summary, Style::default().fg(theme.text)),
+            ]));
+        }
+
+        if let Some(summary) = commands_summary {
+            info_lines.push(Line::from(vec![
+                Span::styled("Cmds:    ", Style::default().fg(theme.dimmed)),
+                Span::styled("! ", Style::default().fg(theme.accent)),
+                Span::styled(summary, Style::default().fg(theme.text)),
+            ]));
+        }
+
+        if let Some(label) = Self::host_sandbox_label(instance) {
+            info_lines.push(Line::from(vec![
+                Span::styled("Sandbox: ", Style::default().fg(theme.dimmed)),
+                Span::styled(label, Style::default().fg(theme.sandbox)),
+            ]));
+        }
+
+        if let Some(label) = Self::limits_label(instance) {
+            info_lines.push(Line::from(vec![
+                Span::styled("Limits:  ", Style::default().fg(theme.dimmed)),
+                Span::styled(label, Style::default().fg(theme.text)),
+            ]));
+        }
+
+        if let Some(proxy) = &instance.proxy {
+            info_lines.push(Line::from(vec![
+                Span::styled("Proxy:   ", Style::default().fg(theme.dimmed)),
+                Span::styled(proxy.label(), Style::default().fg(theme.text)),
+            ]));
+        }
+
+        if let Some(env) = &instance.project_env {
+            info_lines.push(Line::from(vec![
+                Span::styled("Env:     ", Style::default().fg(theme.dimmed)),
+                Span::styled(env.label(), Style::default().fg(theme.text)),
+            ]));
+        }
+
+        if let Some(notes) = Self::notes_summary(instance) {
+            info_lines.push(Line::from(vec![
+                Span::styled("Notes:   ", Style::default().fg(theme.dimmed)),
+                Span::styled(notes.to_string(), Style::default().fg(theme.text)),
+            ]));
+        }
+
+        if let Some(summary) = Self::final_summary_line(instance) {
+            info_lines.push(Line::from(vec![
+                Span::styled("Summary: ", Style::default().fg(theme.dimmed)),
+                Span::styled(summary.to_string(), Style::default().fg(theme.text)),
+            ]));
+        }
+
         // Add worktree information if present
         if let Some(wt_info) = &instance.worktree_info {
             info_lines.push(Line::from(""));
@@ -190,6 +389,20 @@ impl Preview {
                 Span::styled("Branch:  ", Style::default().fg(theme.dimmed)),
                 Span::styled(&wt_info.branch, Style::default().fg(theme.branch)),
             ]));
+            if let Some(ticket) = &instance.ticket {
+                info_lines.push(Line::from(vec![
+                    Span::styled("Ticket:  ", Style::default().fg(theme.dimmed)),
+                    Span::styled(&ticket.id, Style::default().fg(theme.accent)),
+                    Span::styled(
+                        ticket
+                            .url
+                            .as_ref()
+                            .map(|url| format!("  {}", url))
+                            .unwrap_or_default(),
+                        Style::default().fg(theme.text),
+                    ),
+                ]));
+            }
             info_lines.push(Line::from(vec![
                 Span::styled("Main:    ", Style::default().fg(theme.dimmed)),
                 Span::styled(
@@ -208,12 +421,24 @@ impl Preview {
         area: Rect,
         instance: &Instance,
         cached_output: &str,
+        scroll: u16,
         theme: &Theme,
-    ) {
+    ) -> u16 {
+        let output_text = parse_output_text(cached_output);
+        let line_count = output_text.lines.len();
+        let inner_height = area.height.saturating_sub(1) as usize;
+        let bottom = line_count.saturating_sub(inner_height);
+        let scroll = scroll.min(bottom.min(u16::MAX as usize) as u16);
+
+        let title = if scroll > 0 {
+            format!(" Output ↑{} ", scroll)
+        } else {
+            " Output ".to_string()
+        };
         let block = Block::default()
             .borders(Borders::TOP)
             .border_style(Style::default().fg(theme.border))
-            .title(" Output ")
+            .title(title)
             .title_style(Style::default().fg(theme.dimmed));
 
         let inner = block.inner(area);
@@ -233,7 +458,7 @@ impl Preview {
             ];
             let paragraph = Paragraph::new(error_lines).wrap(Wrap { trim: false });
             frame.render_widget(paragraph, inner);
-            return;
+            return scroll;
         }
 
         if cached_output.is_empty() {
@@ -242,16 +467,8 @@ impl Preview {
                 .alignment(Alignment::Center);
             frame.render_widget(hint, inner);
         } else {
-            let output_text = parse_output_text(cached_output);
-            let line_count = output_text.lines.len();
-            let visible_height = inner.height as usize;
-
-            // Scroll to show the bottom of the content
-            let scroll_offset = if line_count > visible_height {
-                (line_count - visible_height) as u16
-            } else {
-                0
-            };
+            // Show the bottom of the content, less any scroll back
+            let scroll_offset = (bottom as u16).saturating_sub(scroll);
 
             let paragraph = Paragraph::new(output_text)
                 .style(Style::default().fg(theme.text))
@@ -259,9 +476,120 @@ impl Preview {
 
             frame.render_widget(paragraph, inner);
         }
+        scroll
+    }
+
+    fn render_timeline(frame: &mut Frame, area: Rect, events: &[TimelineEvent], theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(theme.border))
+            .title(" Activity ")
+            .title_style(Style::default().fg(theme.dimmed));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if events.is_empty() {
+            let hint = Paragraph::new("No activity recorded yet")
+                .style(Style::default().fg(theme.dimmed))
+                .alignment(Alignment::Center);
+            frame.render_widget(hint, inner);
+            return;
+        }
+
+        // Newest events at the bottom, like the output view
+        let lines = timeline_lines(events, theme);
+        let visible_height = inner.height as usize;
+        let scroll_offset = lines.len().saturating_sub(visible_height) as u16;
+        let paragraph = Paragraph::new(lines).scroll((scroll_offset, 0));
+        frame.render_widget(paragraph, inner);
+    }
+
+    fn render_events(frame: &mut Frame, area: Rect, events: &[OutputEvent], theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(theme.border))
+            .title(" Events ")
+            .title_style(Style::default().fg(theme.dimmed));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        // Newest events at the bottom, like the output view
+        let lines = event_lines(events, theme);
+        let scroll_offset = lines.len().saturating_sub(inner.height as usize) as u16;
+        let paragraph = Paragraph::new(lines).scroll((scroll_offset, 0));
+        frame.render_widget(paragraph, inner);
+    }
+}
+
+fn event_lines(events: &[OutputEvent], theme: &Theme) -> Vec<Line<'static>> {
+    events
+        .iter()
+        .map(|event| {
+            let (icon, label, detail, color) = match event {
+                OutputEvent::ToolCall { tool, detail } => {
+                    ("▸", tool.clone(), detail.clone(), theme.accent)
+                }
+                OutputEvent::FileEdit { tool, path } => {
+                    ("✎", tool.clone(), path.clone(), theme.branch)
+                }
+                OutputEvent::TestRun { command, passed } => {
+                    let (icon, color) = match passed {
+                        Some(true) => ("✓", theme.running),
+                        Some(false) => ("✗", theme.error),
+                        None => ("…", theme.waiting),
+                    };
+                    (icon, "Test".to_string(), command.clone(), color)
+                }
+            };
+            Line::from(vec![
+                Span::styled(format!("{} ", icon), Style::default().fg(color)),
+                Span::styled(format!("{:<10} ", label), Style::default().fg(color)),
+                Span::styled(detail, Style::default().fg(theme.text)),
+            ])
+        })
+        .collect()
+}
+
+/// Colour for a timeline event kind, shared by the preview and the logs tab.
+pub fn event_kind_color(kind: EventKind, theme: &Theme) -> Color {
+    match kind {
+        EventKind::Created => theme.accent,
+        EventKind::Status => theme.idle,
+        EventKind::Prompt => theme.running,
+        EventKind::Hook => theme.waiting,
+        EventKind::Git => theme.branch,
+        EventKind::Guardrail => theme.error,
+        EventKind::Command => theme.hint,
     }
 }
 
+fn timeline_lines(events: &[TimelineEvent], theme: &Theme) -> Vec<Line<'static>> {
+    let today = Local::now().date_naive();
+    events
+        .iter()
+        .map(|event| {
+            let at = event.at.with_timezone(&Local);
+            let time = if at.date_naive() == today {
+                at.format("%H:%M:%S").to_string()
+            } else {
+                at.format("%m-%d %H:%M").to_string()
+            };
+            let color = event_kind_color(event.kind, theme);
+            Line::from(vec![
+                Span::styled(format!("{:>11} ", time), Style::default().fg(theme.dimmed)),
+                Span::styled("● ", Style::default().fg(color)),
+                Span::styled(
+                    format!("{:<8}", event.kind.label()),
+                    Style::default().fg(color),
+                ),
+                Span::styled(event.message.clone(), Style::default().fg(theme.text)),
+            ])
+        })
+        .collect()
+}
+
 fn parse_output_text(content: &str) -> Text<'static> {
     content
         .into_text()
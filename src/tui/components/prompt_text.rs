@@ -0,0 +1,418 @@
+//! Prompt text with code highlighting and spellcheck
+//!
+//! Prompt editors draw their text here instead of through tui-textarea,
+//! which can only style one search pattern. Fenced code blocks and `inline
+//! code` get code colors, with keywords, strings, numbers, and comments
+//! picked out inside fences. Outside code, words missing from the
+//! dictionary are underlined.
+//!
+//! The dictionary is the system word list (`/usr/share/dict/words`) plus
+//! `words.txt` in the app directory, one word per line, for project jargon.
+//! Without a system word list there is no spellcheck.
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use tui_textarea::TextArea;
+use unicode_width::UnicodeWidthChar;
+
+use crate::tui::styles::Theme;
+
+const SYSTEM_WORD_LISTS: &[&str] = &["/usr/share/dict/words", "/usr/dict/words"];
+
+/// Words shorter than this are never flagged
+const MIN_CHECKED_LEN: usize = 3;
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "class", "const", "continue", "def", "else", "enum", "export",
+    "false", "fn", "for", "from", "func", "function", "if", "impl", "import", "in", "let", "match",
+    "mut", "nil", "None", "null", "pub", "return", "self", "struct", "trait", "true", "type",
+    "use", "var", "while",
+];
+
+/// Suffixes stripped, with what replaces them, when a word is not in the
+/// list as written, so "fixes", "parsed", and "tried" pass on "fix",
+/// "parse", and "try".
+const SUFFIXES: &[(&str, &[&str])] = &[
+    ("'s", &[""]),
+    ("ies", &["y"]),
+    ("ied", &["y"]),
+    ("es", &["", "e"]),
+    ("s", &[""]),
+    ("ed", &["", "e"]),
+    ("ing", &["", "e"]),
+    ("ly", &[""]),
+    ("er", &["", "e"]),
+    ("est", &["", "e"]),
+];
+
+pub struct Dictionary {
+    words: HashSet<String>,
+}
+
+impl Dictionary {
+    /// A dictionary of the words in `text`, one per line.
+    pub fn from_words(text: &str) -> Self {
+        Self {
+            words: text
+                .lines()
+                .map(|w| w.trim().to_lowercase())
+                .filter(|w| !w.is_empty())
+                .collect(),
+        }
+    }
+
+    fn system() -> Option<Self> {
+        let text = SYSTEM_WORD_LISTS
+            .iter()
+            .find_map(|path| std::fs::read_to_string(path).ok())?;
+        let mut dictionary = Self::from_words(&text);
+        if let Ok(personal) = crate::session::get_app_dir()
+            .and_then(|dir| Ok(std::fs::read_to_string(dir.join("words.txt"))?))
+        {
+            dictionary.words.extend(Self::from_words(&personal).words);
+        }
+        Some(dictionary)
+    }
+
+    pub fn knows(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+        if self.words.contains(&word) {
+            return true;
+        }
+        SUFFIXES.iter().any(|(suffix, replacements)| {
+            word.strip_suffix(suffix).is_some_and(|stem| {
+                stem.len() >= MIN_CHECKED_LEN - 1
+                    && replacements
+                        .iter()
+                        .any(|r| self.words.contains(&format!("{}{}", stem, r)))
+            })
+        })
+    }
+}
+
+/// The system dictionary, loaded on first use; None without a word list.
+pub fn dictionary() -> Option<&'static Dictionary> {
+    static DICTIONARY: OnceLock<Option<Dictionary>> = OnceLock::new();
+    DICTIONARY.get_or_init(Dictionary::system).as_ref()
+}
+
+/// Whether a whitespace-separated chunk of prose is worth checking, and the
+/// words in it. Paths, URLs, identifiers, and acronyms are left alone.
+fn checkable_words(chunk: &str) -> Vec<(usize, &str)> {
+    let start = chunk.len()
+        - chunk
+            .trim_start_matches(|c: char| !c.is_alphanumeric())
+            .len();
+    let core = chunk[start..].trim_end_matches(|c: char| !c.is_alphanumeric());
+    if core.is_empty()
+        || !core
+            .chars()
+            .all(|c| c.is_alphabetic() || c == '\'' || c == '-')
+    {
+        return Vec::new();
+    }
+    let mut words = Vec::new();
+    let mut offset = start;
+    for part in core.split('-') {
+        let camel_or_acronym = part.chars().skip(1).any(char::is_uppercase);
+        if part.chars().count() >= MIN_CHECKED_LEN && !camel_or_acronym {
+            words.push((offset, part));
+        }
+        offset += part.len() + 1;
+    }
+    words
+}
+
+/// Styled pieces of a prose line: inline code in code colors, misspelled
+/// words underlined.
+fn prose_spans(line: &str, theme: &Theme, dictionary: Option<&Dictionary>) -> Vec<(String, Style)> {
+    let text = Style::default().fg(theme.text);
+    let code = Style::default().fg(theme.hint);
+    let misspelled = text
+        .add_modifier(Modifier::UNDERLINED)
+        .underline_color(theme.error);
+
+    let mut spans = Vec::new();
+    for (i, piece) in line.split('`').enumerate() {
+        if i > 0 {
+            spans.push(("`".to_string(), code));
+        }
+        if i % 2 == 1 {
+            spans.push((piece.to_string(), code));
+            continue;
+        }
+        let Some(dictionary) = dictionary else {
+            spans.push((piece.to_string(), text));
+            continue;
+        };
+        let mut pos = 0;
+        let mut chunk_start = None;
+        let mut check_chunk = |spans: &mut Vec<(String, Style)>, from: usize, to: usize| {
+            for (offset, word) in checkable_words(&piece[from..to]) {
+                if dictionary.knows(word) {
+                    continue;
+                }
+                let word_start = from + offset;
+                spans.push((piece[pos..word_start].to_string(), text));
+                spans.push((word.to_string(), misspelled));
+                pos = word_start + word.len();
+            }
+        };
+        for (idx, c) in piece.char_indices() {
+            match (c.is_whitespace(), chunk_start) {
+                (false, None) => chunk_start = Some(idx),
+                (true, Some(from)) => {
+                    check_chunk(&mut spans, from, idx);
+                    chunk_start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(from) = chunk_start {
+            check_chunk(&mut spans, from, piece.len());
+        }
+        spans.push((piece[pos..].to_string(), text));
+    }
+    spans
+}
+
+/// Styled pieces of a line inside a code fence.
+fn code_spans(line: &str, theme: &Theme) -> Vec<(String, Style)> {
+    let code = Style::default().fg(theme.hint);
+    let keyword = Style::default().fg(theme.accent);
+    let string = Style::default().fg(theme.diff_add);
+    let number = Style::default().fg(theme.waiting);
+    let comment = Style::default().fg(theme.dimmed);
+
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let (start, c) = chars[i];
+        let rest = &line[start..];
+        let at_word_start = i == 0 || !chars[i - 1].1.is_alphanumeric() && chars[i - 1].1 != '_';
+        if rest.starts_with("//") || c == '#' && (i == 0 || chars[i - 1].1.is_whitespace()) {
+            spans.push((rest.to_string(), comment));
+            break;
+        }
+        let end = if c == '"' || c == '\'' || c == '`' {
+            let mut j = i + 1;
+            while j < chars.len() && chars[j].1 != c {
+                j += if chars[j].1 == '\\' { 2 } else { 1 };
+            }
+            (j + 1).min(chars.len())
+        } else if c.is_alphanumeric() || c == '_' {
+            let mut j = i + 1;
+            while j < chars.len() && (chars[j].1.is_alphanumeric() || chars[j].1 == '_') {
+                j += 1;
+            }
+            j
+        } else {
+            i + 1
+        };
+        let byte_end = chars.get(end).map(|(b, _)| *b).unwrap_or(line.len());
+        let token = &line[start..byte_end];
+        let style = if c == '"' || c == '\'' || c == '`' {
+            string
+        } else if c.is_ascii_digit() && at_word_start {
+            number
+        } else if KEYWORDS.contains(&token) {
+            keyword
+        } else {
+            code
+        };
+        match spans.last_mut() {
+            Some((text, last)) if *last == style => text.push_str(token),
+            _ => spans.push((token.to_string(), style)),
+        }
+        i = end;
+    }
+    spans
+}
+
+/// Highlight prompt lines: fences and their contents as code, the rest as
+/// prose, spellchecked when there is a dictionary.
+pub fn highlight_lines(
+    lines: &[String],
+    theme: &Theme,
+    dictionary: Option<&Dictionary>,
+) -> Vec<Line<'static>> {
+    let fence = Style::default().fg(theme.dimmed);
+    let mut in_code = false;
+    lines
+        .iter()
+        .map(|line| {
+            let spans = if line.trim_start().starts_with("```") {
+                in_code = !in_code;
+                vec![(line.clone(), fence)]
+            } else if in_code {
+                code_spans(line, theme)
+            } else {
+                prose_spans(line, theme, dictionary)
+            };
+            Line::from(
+                spans
+                    .into_iter()
+                    .filter(|(text, _)| !text.is_empty())
+                    .map(|(text, style)| Span::styled(text, style))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect()
+}
+
+fn display_width(line: &str, chars: usize) -> u16 {
+    line.chars()
+        .take(chars)
+        .map(|c| c.width().unwrap_or(0) as u16)
+        .sum()
+}
+
+/// Draw the contents of a prompt editor, scrolled to keep the cursor in view.
+pub fn render_prompt_text(
+    frame: &mut Frame,
+    area: Rect,
+    text_area: &TextArea,
+    theme: &Theme,
+    dictionary: Option<&Dictionary>,
+) {
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+    let lines = text_area.lines();
+    let (row, col) = text_area.cursor();
+    let cursor_line = lines.get(row).map(String::as_str).unwrap_or_default();
+    let cursor_x = display_width(cursor_line, col);
+    let top = (row as u16).saturating_sub(area.height - 1);
+    let left = cursor_x.saturating_sub(area.width - 1);
+
+    frame.render_widget(
+        Paragraph::new(highlight_lines(lines, theme, dictionary)).scroll((top, left)),
+        area,
+    );
+
+    let buffer = frame.buffer_mut();
+    let mut style_cell = |line_row: usize, x: u16, style: Style| {
+        let y = line_row as u16;
+        if y >= top && y - top < area.height && x >= left && x - left < area.width {
+            buffer[(area.x + x - left, area.y + y - top)].set_style(style);
+        }
+    };
+
+    if let Some(((start_row, start_col), (end_row, end_col))) = text_area.selection_range() {
+        let selected = Style::default().bg(theme.selection);
+        for (r, line) in lines.iter().enumerate().take(end_row + 1).skip(start_row) {
+            let from = if r == start_row { start_col } else { 0 };
+            let to = if r == end_row {
+                end_col
+            } else {
+                line.chars().count()
+            };
+            let mut x = display_width(line, from);
+            for c in line.chars().skip(from).take(to.saturating_sub(from)) {
+                for _ in 0..c.width().unwrap_or(0) {
+                    style_cell(r, x, selected);
+                    x += 1;
+                }
+            }
+        }
+    }
+
+    style_cell(
+        row,
+        cursor_x,
+        Style::default().fg(theme.background).bg(theme.accent),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styled(line: &Line) -> Vec<(String, Style)> {
+        line.spans
+            .iter()
+            .map(|s| (s.content.to_string(), s.style))
+            .collect()
+    }
+
+    #[test]
+    fn test_dictionary_knows_inflections() {
+        let dict = Dictionary::from_words("fix\nparse\ntry\nbug\nquick\n");
+        for word in [
+            "Fix", "fixes", "parsed", "parsing", "tried", "bugs", "quickly",
+        ] {
+            assert!(dict.knows(word), "{}", word);
+        }
+        assert!(!dict.knows("pasre"));
+    }
+
+    #[test]
+    fn test_misspelled_words_are_underlined() {
+        let theme = Theme::default();
+        let dict = Dictionary::from_words("please\nthe\nfix\nbug\nin\nrun\n");
+        let lines = highlight_lines(
+            &["Please fix teh bug in `parse_args` at src/main.rs, then run CI.".to_string()],
+            &theme,
+            Some(&dict),
+        );
+        let flagged: Vec<String> = styled(&lines[0])
+            .into_iter()
+            .filter(|(_, style)| style.add_modifier.contains(Modifier::UNDERLINED))
+            .map(|(text, _)| text)
+            .collect();
+        // Code, paths, and acronyms are not checked
+        assert_eq!(flagged, ["teh", "then"]);
+        let text: String = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(
+            text,
+            "Please fix teh bug in `parse_args` at src/main.rs, then run CI."
+        );
+
+        let unchecked = highlight_lines(&["teh".to_string()], &theme, None);
+        assert!(!styled(&unchecked[0])[0]
+            .1
+            .add_modifier
+            .contains(Modifier::UNDERLINED));
+    }
+
+    #[test]
+    fn test_code_fences_are_highlighted() {
+        let theme = Theme::default();
+        let dict = Dictionary::from_words("run\nthis\n");
+        let lines: Vec<String> = [
+            "Run this:",
+            "```rust",
+            "let n = 42; // teh answer",
+            "```",
+            "tehn",
+        ]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+        let out = highlight_lines(&lines, &theme, Some(&dict));
+
+        assert_eq!(out[1].spans[0].style.fg, Some(theme.dimmed));
+        let code = styled(&out[2]);
+        assert_eq!(
+            code[0],
+            ("let".to_string(), Style::default().fg(theme.accent))
+        );
+        assert!(code.contains(&("42".to_string(), Style::default().fg(theme.waiting))));
+        assert_eq!(
+            code.last().unwrap(),
+            &(
+                "// teh answer".to_string(),
+                Style::default().fg(theme.dimmed)
+            )
+        );
+        // Prose resumes, and is checked again, after the closing fence
+        assert!(out[4].spans[0]
+            .style
+            .add_modifier
+            .contains(Modifier::UNDERLINED));
+    }
+}
@@ -0,0 +1,98 @@
+//! Transient one-line notifications shown in place of the status bar
+
+use std::time::{Duration, Instant};
+
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+
+use crate::tui::styles::Theme;
+
+const INFO_DURATION: Duration = Duration::from_secs(3);
+const ERROR_DURATION: Duration = Duration::from_secs(8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub kind: ToastKind,
+    shown_at: Instant,
+}
+
+impl Toast {
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            kind: ToastKind::Info,
+            shown_at: Instant::now(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            kind: ToastKind::Error,
+            shown_at: Instant::now(),
+        }
+    }
+
+    /// Errors stay up longer so there is time to read them.
+    fn duration(&self) -> Duration {
+        match self.kind {
+            ToastKind::Info => INFO_DURATION,
+            ToastKind::Error => ERROR_DURATION,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.shown_at.elapsed() >= self.duration()
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let (icon, color) = match self.kind {
+            ToastKind::Info => ("✓", theme.running),
+            ToastKind::Error => ("✗", theme.error),
+        };
+        // Keep to a single line; multi-line errors (e.g. TOML parse output)
+        // are flattened so the first useful part stays visible.
+        let message = self
+            .message
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let line = Line::from(vec![
+            Span::styled(format!(" {} ", icon), Style::default().fg(color).bold()),
+            Span::styled(message, Style::default().fg(color)),
+        ]);
+        frame.render_widget(
+            Paragraph::new(line).style(Style::default().bg(theme.selection)),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_toast_is_not_expired() {
+        assert!(!Toast::info("Config reloaded").is_expired());
+        assert!(!Toast::error("bad").is_expired());
+    }
+
+    #[test]
+    fn test_toast_expires() {
+        let mut toast = Toast::info("Config reloaded");
+        toast.shown_at = Instant::now() - INFO_DURATION;
+        assert!(toast.is_expired());
+
+        let mut toast = Toast::error("bad");
+        toast.shown_at = Instant::now() - INFO_DURATION;
+        assert!(!toast.is_expired());
+    }
+}
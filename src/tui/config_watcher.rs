@@ -0,0 +1,301 @@
+//! Config file watching for live reload
+//!
+//! Watches the global `config.toml`, profile configs, and the launch
+//! directory's `.aoe/config.toml` so edits made outside the TUI apply without
+//! a restart, including in a `profiles/` or `.aoe/` directory created after
+//! startup. Events are debounced because editors often write a file in
+//! several steps (truncate, write, rename).
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::session::config::AppStateConfig;
+use crate::session::repo_config::{load_repo_config, REPO_CONFIG_PATH};
+use crate::session::{get_app_dir, resolve_config, Config};
+
+/// Quiet period after the last file event before a reload is triggered.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+pub struct ConfigWatcher {
+    event_rx: mpsc::Receiver<Vec<PathBuf>>,
+    /// Held so the OS watch stays registered for the watcher's lifetime.
+    watcher: Option<RecommendedWatcher>,
+    /// `profiles/` and the project's `.aoe/`, which may not exist yet. Their
+    /// parents are watched too, so one created later gets its own watch.
+    dirs: Vec<WatchedDir>,
+    pending_since: Option<Instant>,
+}
+
+/// A directory whose config files are watched while it exists
+struct WatchedDir {
+    path: PathBuf,
+    mode: RecursiveMode,
+    watching: bool,
+}
+
+impl ConfigWatcher {
+    /// Start watching. Failure to set up the OS watch is logged and leaves
+    /// the watcher inert rather than failing TUI startup.
+    pub fn new(project_dir: Option<&Path>) -> Self {
+        let (event_tx, event_rx) = mpsc::channel();
+        let (watcher, dirs) = match Self::start(event_tx, project_dir) {
+            Ok((w, dirs)) => (Some(w), dirs),
+            Err(e) => {
+                tracing::warn!("Config hot-reload disabled: {}", e);
+                (None, Vec::new())
+            }
+        };
+        let mut config_watcher = Self {
+            event_rx,
+            watcher,
+            dirs,
+            pending_since: None,
+        };
+        config_watcher.refresh_dirs();
+        config_watcher
+    }
+
+    fn start(
+        event_tx: mpsc::Sender<Vec<PathBuf>>,
+        project_dir: Option<&Path>,
+    ) -> Result<(RecommendedWatcher, Vec<WatchedDir>)> {
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                if let Ok(event) = res {
+                    if !event.kind.is_access() {
+                        let _ = event_tx.send(event.paths);
+                    }
+                }
+            })?;
+
+        // Watch directories rather than files so atomic saves (write to temp,
+        // rename over) keep being observed after the first edit.
+        let app_dir = get_app_dir()?;
+        watcher.watch(&app_dir, RecursiveMode::NonRecursive)?;
+        let mut dirs = vec![WatchedDir {
+            path: app_dir.join("profiles"),
+            mode: RecursiveMode::Recursive,
+            watching: false,
+        }];
+        if let Some(project_dir) = project_dir.filter(|d| d.is_dir()) {
+            watcher.watch(project_dir, RecursiveMode::NonRecursive)?;
+            if let Some(repo_config_dir) = project_dir.join(REPO_CONFIG_PATH).parent() {
+                dirs.push(WatchedDir {
+                    path: repo_config_dir.to_path_buf(),
+                    mode: RecursiveMode::NonRecursive,
+                    watching: false,
+                });
+            }
+        }
+
+        Ok((watcher, dirs))
+    }
+
+    /// Watch the directories that now exist and forget the ones that are
+    /// gone. Returns true if one was newly watched.
+    fn refresh_dirs(&mut self) -> bool {
+        let Some(watcher) = self.watcher.as_mut() else {
+            return false;
+        };
+        let mut added = false;
+        for dir in &mut self.dirs {
+            let exists = dir.path.is_dir();
+            if exists && !dir.watching {
+                match watcher.watch(&dir.path, dir.mode) {
+                    Ok(()) => {
+                        dir.watching = true;
+                        added = true;
+                    }
+                    Err(e) => tracing::warn!("Failed to watch {}: {}", dir.path.display(), e),
+                }
+            } else if !exists && dir.watching {
+                // The OS drops the watch along with the directory
+                let _ = watcher.unwatch(&dir.path);
+                dir.watching = false;
+            }
+        }
+        added
+    }
+
+    /// Returns true once a burst of config changes has settled.
+    pub fn poll_changed(&mut self) -> bool {
+        while let Ok(paths) = self.event_rx.try_recv() {
+            let mut changed = paths.iter().any(|p| is_config_path(p));
+            // Matched by name: some platforms report paths resolved through
+            // symlinks, so they needn't equal the ones watched
+            let touches_dir = paths.iter().any(|p| {
+                self.dirs
+                    .iter()
+                    .any(|d| p.file_name().is_some() && p.file_name() == d.path.file_name())
+            });
+            if touches_dir && self.refresh_dirs() {
+                // Its config may have been written before the watch was added
+                changed = true;
+            }
+            if changed {
+                self.pending_since = Some(Instant::now());
+            }
+        }
+        match self.pending_since {
+            Some(since) if since.elapsed() >= DEBOUNCE => {
+                self.pending_since = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+fn is_config_path(path: &Path) -> bool {
+    path.file_name().and_then(|n| n.to_str()) == Some(CONFIG_FILE_NAME)
+}
+
+/// Effective config after a reload, plus a fingerprint of everything a user
+/// can edit. `app_state` is excluded because the TUI writes it itself (list
+/// width, sort order), and those writes should not announce a reload.
+pub struct ReloadedConfig {
+    pub config: Config,
+    pub fingerprint: String,
+}
+
+/// Load and validate the full config chain for `profile`.
+pub fn load_for_reload(profile: &str, project_dir: Option<&Path>) -> Result<ReloadedConfig> {
+    let config = resolve_config(profile)?;
+    let repo = match project_dir {
+        Some(dir) => load_repo_config(dir)?,
+        None => None,
+    };
+
+    let mut editable = config.clone();
+    editable.app_state = AppStateConfig::default();
+    let mut fingerprint = toml::to_string(&editable)?;
+    if let Some(repo) = repo {
+        fingerprint.push_str(&toml::to_string(&repo)?);
+    }
+
+    Ok(ReloadedConfig {
+        config,
+        fingerprint,
+    })
+}
+
+/// The launch directory, used to locate a project's `.aoe/config.toml`.
+pub fn launch_dir() -> Option<PathBuf> {
+    std::env::current_dir().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    fn setup_test_home(temp: &Path) {
+        std::env::set_var("HOME", temp);
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.join(".config"));
+    }
+
+    #[test]
+    fn test_is_config_path() {
+        assert!(is_config_path(Path::new("/x/config.toml")));
+        assert!(is_config_path(Path::new("/x/profiles/work/config.toml")));
+        assert!(!is_config_path(Path::new("/x/profiles/work/sessions.json")));
+        assert!(!is_config_path(Path::new("/x/config.toml.bak")));
+    }
+
+    #[test]
+    #[serial]
+    fn test_fingerprint_ignores_app_state() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let before = load_for_reload("default", None)?;
+
+        let mut config = Config::default();
+        config.app_state.home_list_width = Some(77);
+        crate::session::save_config(&config)?;
+        let after = load_for_reload("default", None)?;
+        assert_eq!(before.fingerprint, after.fingerprint);
+
+        config.theme.name = "tide".to_string();
+        crate::session::save_config(&config)?;
+        let changed = load_for_reload("default", None)?;
+        assert_ne!(before.fingerprint, changed.fingerprint);
+        assert_eq!(changed.config.theme.name, "tide");
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_invalid_config_reports_error() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let app_dir = get_app_dir()?;
+        std::fs::write(app_dir.join(CONFIG_FILE_NAME), "[theme\nname = ")?;
+        assert!(load_for_reload("default", None).is_err());
+        Ok(())
+    }
+
+    /// Poll until a settled change is reported, or give up after a few seconds
+    fn wait_for_change(watcher: &mut ConfigWatcher) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if watcher.poll_changed() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        false
+    }
+
+    #[test]
+    #[serial]
+    fn test_watches_config_dirs_created_later() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+        let project = temp.path().join("project");
+        std::fs::create_dir_all(&project)?;
+
+        let mut watcher = ConfigWatcher::new(Some(&project));
+        if watcher.watcher.is_none() {
+            return Ok(());
+        }
+        assert!(watcher.dirs.iter().all(|d| !d.watching));
+
+        std::fs::create_dir_all(project.join(".aoe"))?;
+        std::fs::write(project.join(REPO_CONFIG_PATH), "")?;
+        assert!(wait_for_change(&mut watcher));
+
+        // Edits after the directory appeared are seen through its own watch
+        std::fs::write(project.join(REPO_CONFIG_PATH), "[session]\n")?;
+        assert!(wait_for_change(&mut watcher));
+
+        let profile_dir = get_app_dir()?.join("profiles").join("work");
+        std::fs::create_dir_all(&profile_dir)?;
+        std::fs::write(profile_dir.join(CONFIG_FILE_NAME), "")?;
+        assert!(wait_for_change(&mut watcher));
+        assert!(watcher.dirs.iter().all(|d| d.watching));
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_invalid_repo_config_reports_error() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let project = temp.path().join("project");
+        std::fs::create_dir_all(project.join(".aoe"))?;
+        std::fs::write(project.join(REPO_CONFIG_PATH), "hooks = 3")?;
+        assert!(load_for_reload("default", Some(&project)).is_err());
+        Ok(())
+    }
+}
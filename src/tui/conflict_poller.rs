@@ -0,0 +1,53 @@
+//! Background check for merge conflicts in worktree sessions
+//!
+//! Reading a worktree's index is quick but touches the disk, so like merge
+//! checks each one runs on its own short-lived thread. Results come back
+//! over a channel and are applied by `HomeView::apply_conflict_results`.
+
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+
+use crate::git::conflicts::{self, Conflict};
+
+/// Outcome of one conflict check
+#[derive(Debug)]
+pub struct ConflictCheckResult {
+    pub id: String,
+    pub conflict: Option<Conflict>,
+}
+
+pub struct ConflictPoller {
+    result_tx: mpsc::Sender<ConflictCheckResult>,
+    result_rx: mpsc::Receiver<ConflictCheckResult>,
+}
+
+impl ConflictPoller {
+    pub fn new() -> Self {
+        let (result_tx, result_rx) = mpsc::channel();
+        Self {
+            result_tx,
+            result_rx,
+        }
+    }
+
+    /// Check the worktree of session `id` for conflicts (non-blocking).
+    pub fn request_check(&self, id: String, worktree_path: PathBuf) {
+        let tx = self.result_tx.clone();
+        thread::spawn(move || {
+            let conflict = conflicts::detect(&worktree_path);
+            let _ = tx.send(ConflictCheckResult { id, conflict });
+        });
+    }
+
+    /// Collect all finished checks without blocking.
+    pub fn try_recv_results(&self) -> Vec<ConflictCheckResult> {
+        self.result_rx.try_iter().collect()
+    }
+}
+
+impl Default for ConflictPoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -8,7 +8,7 @@ use std::thread;
 
 use crate::session::builder::{self, CreatedWorktree, InstanceParams};
 use crate::session::repo_config::{self, HookProgress, HooksConfig};
-use crate::session::Instance;
+use crate::session::{timeline, Instance};
 use crate::tui::dialogs::NewSessionData;
 
 pub struct CreationRequest {
@@ -107,6 +107,7 @@ impl CreationPoller {
             tool: data.tool,
             worktree_branch: data.worktree_branch,
             create_new_branch: data.create_new_branch,
+            base_ref: data.base_ref,
             sandbox: data.sandbox,
             sandbox_image: data.sandbox_image,
             yolo_mode: data.yolo_mode,
@@ -114,6 +115,8 @@ impl CreationPoller {
             extra_args: data.extra_args,
             command_override: data.command_override,
             extra_repo_paths: data.extra_repo_paths,
+            issue_info: data.issue_info,
+            initial_prompt: data.initial_prompt,
         };
 
         let build_result = match builder::build_instance(params, &existing_titles, &profile) {
@@ -147,27 +150,33 @@ impl CreationPoller {
                 container_started = true;
                 if let Some(ref sandbox) = instance.sandbox_info {
                     let workdir = instance.container_workdir();
-                    if let Err(e) = repo_config::execute_hooks_in_container_streamed(
+                    let result = repo_config::execute_hooks_in_container_streamed(
                         &hooks.on_create,
                         &sandbox.container_name,
                         &workdir,
                         progress_tx,
-                    ) {
+                    );
+                    timeline::record_hooks(&instance.id, "on_create", &hooks.on_create, &result);
+                    if let Err(e) = result {
                         tracing::warn!("on_create hook failed in container: {:#}", e);
                         return CreationResult::Error(format!("on_create hook failed: {:#}", e));
                     }
                 }
-            } else if let Err(e) = repo_config::execute_hooks_streamed(
-                &hooks.on_create,
-                std::path::Path::new(&instance.project_path),
-                progress_tx,
-            ) {
-                builder::cleanup_instance(
-                    &instance,
-                    created_worktree.as_ref(),
-                    &created_workspace_worktrees,
+            } else {
+                let result = repo_config::execute_hooks_streamed(
+                    &hooks.on_create,
+                    std::path::Path::new(&instance.project_path),
+                    progress_tx,
                 );
-                return CreationResult::Error(format!("on_create hook failed: {:#}", e));
+                timeline::record_hooks(&instance.id, "on_create", &hooks.on_create, &result);
+                if let Err(e) = result {
+                    builder::cleanup_instance(
+                        &instance,
+                        created_worktree.as_ref(),
+                        &created_workspace_worktrees,
+                    );
+                    return CreationResult::Error(format!("on_create hook failed: {:#}", e));
+                }
             }
         }
 
@@ -188,22 +197,33 @@ impl CreationPoller {
                 if container_started {
                     if let Some(ref sandbox) = instance.sandbox_info {
                         let workdir = instance.container_workdir();
-                        if let Err(e) = repo_config::execute_hooks_in_container_streamed(
+                        let result = repo_config::execute_hooks_in_container_streamed(
                             &hooks.on_launch,
                             &sandbox.container_name,
                             &workdir,
                             progress_tx,
-                        ) {
+                        );
+                        timeline::record_hooks(
+                            &instance.id,
+                            "on_launch",
+                            &hooks.on_launch,
+                            &result,
+                        );
+                        if let Err(e) = result {
                             tracing::warn!("on_launch hook failed in container: {}", e);
                         }
                     }
                 }
-            } else if let Err(e) = repo_config::execute_hooks_streamed(
-                &hooks.on_launch,
-                std::path::Path::new(&instance.project_path),
-                progress_tx,
-            ) {
-                tracing::warn!("on_launch hook failed: {}", e);
+            } else {
+                let result = repo_config::execute_hooks_streamed(
+                    &hooks.on_launch,
+                    std::path::Path::new(&instance.project_path),
+                    progress_tx,
+                );
+                timeline::record_hooks(&instance.id, "on_launch", &hooks.on_launch, &result);
+                if let Err(e) = result {
+                    tracing::warn!("on_launch hook failed: {}", e);
+                }
             }
         }
 
@@ -62,6 +62,9 @@ impl DeletionPoller {
     fn perform_deletion(request: &DeletionRequest) -> DeletionResult {
         let mut errors = Vec::new();
 
+        // Before the worktree and tmux session are gone
+        crate::session::stats::record_removed(&request.instance, &request.instance.source_profile);
+
         // Track branch info for potential deletion after worktree removal
         let branch_to_delete = if request.delete_branch {
             request
@@ -204,6 +207,13 @@ impl DeletionPoller {
 
         // Clean up hook status files
         crate::hooks::cleanup_hook_status_dir(&request.instance.id);
+        if let Err(e) = crate::session::trash::move_to_trash(
+            &request.instance.source_profile,
+            &request.instance,
+        ) {
+            tracing::warn!("Failed to move session to trash: {}", e);
+            crate::session::timeline::remove(&request.instance.id);
+        }
 
         DeletionResult {
             session_id: request.session_id.clone(),
@@ -234,14 +244,27 @@ impl Default for DeletionPoller {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
     use std::time::Duration;
 
+    /// Deletion moves sessions to the trash, so keep it out of the real
+    /// app directory.
+    fn setup_temp_home() -> tempfile::TempDir {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::env::set_var("HOME", temp.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.path().join(".config"));
+        temp
+    }
+
     fn create_test_instance() -> Instance {
         Instance::new("Test Session", "/tmp/test-project")
     }
 
     #[test]
+    #[serial]
     fn test_deletion_result_success_when_no_worktree_or_sandbox() {
+        let _temp = setup_temp_home();
         let instance = create_test_instance();
         let request = DeletionRequest {
             session_id: instance.id.clone(),
@@ -260,7 +283,9 @@ mod tests {
     }
 
     #[test]
+    #[serial]
     fn test_deletion_result_success_even_with_delete_worktree_flag_when_no_worktree() {
+        let _temp = setup_temp_home();
         let instance = create_test_instance();
         let request = DeletionRequest {
             session_id: instance.id.clone(),
@@ -278,7 +303,9 @@ mod tests {
     }
 
     #[test]
+    #[serial]
     fn test_deletion_poller_channel_communication() {
+        let _temp = setup_temp_home();
         let poller = DeletionPoller::new();
         let instance = create_test_instance();
         let session_id = instance.id.clone();
@@ -314,7 +341,9 @@ mod tests {
     }
 
     #[test]
+    #[serial]
     fn test_deletion_request_preserves_session_id() {
+        let _temp = setup_temp_home();
         let instance = create_test_instance();
         let custom_id = "custom-session-id-123".to_string();
 
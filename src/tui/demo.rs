@@ -0,0 +1,390 @@
+//! Demo mode (`aoe --demo`)
+//!
+//! Fills the TUI with sample sessions whose status and output follow a
+//! script, so the interface can be tried (or screenshotted) without tmux or
+//! any agent installed. Everything lives in a throwaway app directory that
+//! is removed on exit; the user's own sessions and config are never read.
+//! Each script loops: a session's output starts over once its last step
+//! has played.
+
+use std::path::PathBuf;
+use std::time::Instant;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+
+use crate::session::timeline::{self, EventKind};
+use crate::session::{
+    override_app_dir, save_config, Config, GroupTree, Instance, Status, Storage, WorktreeInfo,
+    DEFAULT_PROFILE,
+};
+
+/// Shown for actions that need tmux or an agent
+pub const DEMO_UNAVAILABLE: &str =
+    "Not available in the demo. Install tmux and an agent, then run aoe without --demo.";
+
+/// A stretch of a script: the status shown while it plays and the output
+/// lines that appear, evenly spaced, over `secs` seconds.
+struct Step {
+    status: Status,
+    secs: u64,
+    lines: &'static [&'static str],
+}
+
+struct Script {
+    title: &'static str,
+    tool: &'static str,
+    group: &'static str,
+    project: &'static str,
+    branch: Option<&'static str>,
+    prompt: &'static str,
+    /// How long ago the session was created, in minutes
+    age_mins: i64,
+    /// Seconds into the script the demo starts at, so sessions are out of step
+    offset: u64,
+    notes: Option<&'static str>,
+    final_summary: Option<&'static str>,
+    steps: &'static [Step],
+}
+
+const SCRIPTS: &[Script] = &[
+    Script {
+        title: "fix-login-redirect",
+        tool: "claude",
+        group: "web",
+        project: "~/src/storefront",
+        branch: Some("fix/login-redirect"),
+        prompt: "Users land on a 404 after logging in from a deep link. Fix the redirect.",
+        age_mins: 42,
+        offset: 0,
+        notes: Some("Repro: open /orders/123 logged out, sign in, get 404."),
+        final_summary: None,
+        steps: &[
+            Step {
+                status: Status::Running,
+                secs: 12,
+                lines: &[
+                    "> Users land on a 404 after logging in from a deep link. Fix the redirect.",
+                    "",
+                    "● I'll start by finding where the post-login redirect is built.",
+                    "",
+                    "● Search(pattern: \"returnTo\", path: \"src\")",
+                    "  ⎿  Found 4 files",
+                    "",
+                    "● Read(src/auth/callback.ts)",
+                    "  ⎿  Read 88 lines",
+                    "",
+                    "● The callback strips the query string before redirecting, so",
+                    "  returnTo=/orders/123 becomes /orders and the route 404s.",
+                ],
+            },
+            Step {
+                status: Status::Waiting,
+                secs: 8,
+                lines: &[
+                    "",
+                    "● Update(src/auth/callback.ts)",
+                    "",
+                    "  Do you want to make this edit to callback.ts?",
+                    "  ❯ 1. Yes",
+                    "    2. Yes, and don't ask again this session",
+                    "    3. No, and tell Claude what to do differently",
+                ],
+            },
+            Step {
+                status: Status::Running,
+                secs: 10,
+                lines: &[
+                    "  ⎿  Updated src/auth/callback.ts with 3 additions and 1 removal",
+                    "",
+                    "● Bash(npm test -- auth)",
+                    "  ⎿  PASS src/auth/callback.test.ts",
+                    "     Tests: 14 passed, 14 total",
+                ],
+            },
+            Step {
+                status: Status::Idle,
+                secs: 10,
+                lines: &[
+                    "",
+                    "● Fixed: the callback now keeps the full returnTo path, including",
+                    "  its query string, and only allows same-origin targets.",
+                    "",
+                    "> ",
+                ],
+            },
+        ],
+    },
+    Script {
+        title: "add-rate-limiter",
+        tool: "claude",
+        group: "api",
+        project: "~/src/gateway",
+        branch: Some("feat/rate-limiter"),
+        prompt: "Add per-key rate limiting to the public API with a token bucket.",
+        age_mins: 95,
+        offset: 6,
+        notes: None,
+        final_summary: None,
+        steps: &[Step {
+            status: Status::Running,
+            secs: 36,
+            lines: &[
+                "> Add per-key rate limiting to the public API with a token bucket.",
+                "",
+                "● Read(src/middleware/mod.rs)",
+                "  ⎿  Read 61 lines",
+                "",
+                "● Write(src/middleware/rate_limit.rs)",
+                "  ⎿  Wrote 142 lines to src/middleware/rate_limit.rs",
+                "",
+                "● Update(src/middleware/mod.rs)",
+                "  ⎿  Updated src/middleware/mod.rs with 2 additions",
+                "",
+                "● Bash(cargo test rate_limit)",
+                "  ⎿  running 6 tests",
+                "     test rate_limit::refills_over_time ... ok",
+                "     test rate_limit::rejects_when_empty ... ok",
+                "     test rate_limit::keys_are_independent ... ok",
+                "",
+                "✻ Wiring the limiter into the router… (esc to interrupt)",
+            ],
+        }],
+    },
+    Script {
+        title: "upgrade-deps",
+        tool: "codex",
+        group: "api",
+        project: "~/src/gateway",
+        branch: Some("chore/upgrade-deps"),
+        prompt: "Upgrade all dependencies to their latest minor versions.",
+        age_mins: 240,
+        offset: 0,
+        notes: None,
+        final_summary: Some(
+            "Upgraded 23 crates. tokio 1.38 -> 1.41 needed one change to a \
+             timeout call; all tests pass.",
+        ),
+        steps: &[Step {
+            status: Status::Idle,
+            secs: 3600,
+            lines: &[
+                "user",
+                "Upgrade all dependencies to their latest minor versions.",
+                "",
+                "codex",
+                "Upgraded 23 crates. tokio 1.38 -> 1.41 needed one change to a",
+                "timeout call; all tests pass.",
+                "",
+                "▌ Ask Codex to do anything",
+            ],
+        }],
+    },
+    Script {
+        title: "flaky-e2e-tests",
+        tool: "opencode",
+        group: "web",
+        project: "~/src/storefront",
+        branch: None,
+        prompt: "The checkout e2e test fails about one run in five. Find out why.",
+        age_mins: 18,
+        offset: 3,
+        notes: None,
+        final_summary: None,
+        steps: &[
+            Step {
+                status: Status::Running,
+                secs: 14,
+                lines: &[
+                    "The checkout e2e test fails about one run in five. Find out why.",
+                    "",
+                    "Running the test 20 times to reproduce...",
+                    "  run 1-20: 4 failures",
+                    "",
+                    "All failures time out waiting for the payment iframe.",
+                ],
+            },
+            Step {
+                status: Status::Waiting,
+                secs: 16,
+                lines: &[
+                    "",
+                    "Should I raise the iframe timeout, or stub the payment",
+                    "provider in e2e runs? Stubbing is faster but skips the",
+                    "real integration.",
+                ],
+            },
+        ],
+    },
+    Script {
+        title: "docs-refresh",
+        tool: "gemini",
+        group: "",
+        project: "~/src/handbook",
+        branch: None,
+        prompt: "Bring the setup guide up to date with the new install script.",
+        age_mins: 1440,
+        offset: 0,
+        notes: None,
+        final_summary: None,
+        steps: &[Step {
+            status: Status::Stopped,
+            secs: 3600,
+            lines: &[],
+        }],
+    },
+];
+
+/// Where the demo's app directory goes
+fn demo_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("aoe-demo-{}", std::process::id()))
+}
+
+/// Switch the process to a fresh app directory holding the sample sessions.
+/// Returns the directory, to be removed when the demo ends.
+pub fn prepare() -> Result<PathBuf> {
+    let dir = demo_dir();
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)?;
+    }
+    std::fs::create_dir_all(&dir)?;
+    override_app_dir(dir.clone());
+
+    let mut config = Config::default();
+    config.updates.check_enabled = false;
+    config.app_state.has_seen_welcome = true;
+    config.app_state.last_seen_version = Some(env!("CARGO_PKG_VERSION").to_string());
+    save_config(&config)?;
+
+    let instances = sample_instances();
+    for (inst, script) in instances.iter().zip(SCRIPTS) {
+        timeline::record(
+            &inst.id,
+            EventKind::Created,
+            format!("created with {}", inst.tool),
+        );
+        timeline::record(&inst.id, EventKind::Prompt, script.prompt);
+    }
+    let storage = Storage::new(DEFAULT_PROFILE)?;
+    storage.save_with_groups(&instances, &GroupTree::new_with_groups(&instances, &[]))?;
+    Ok(dir)
+}
+
+fn sample_instances() -> Vec<Instance> {
+    let now = Utc::now();
+    SCRIPTS
+        .iter()
+        .map(|script| {
+            let mut inst = Instance::new(script.title, script.project);
+            inst.tool = script.tool.to_string();
+            inst.group_path = script.group.to_string();
+            inst.created_at = now - Duration::minutes(script.age_mins);
+            inst.last_accessed_at = Some(now - Duration::minutes(script.age_mins / 3));
+            inst.status = script.steps[0].status;
+            inst.original_prompt = Some(script.prompt.to_string());
+            inst.notes = script.notes.map(str::to_string);
+            inst.final_summary = script.final_summary.map(str::to_string);
+            inst.worktree_info = script.branch.map(|branch| WorktreeInfo {
+                branch: branch.to_string(),
+                main_repo_path: script.project.to_string(),
+                managed_by_aoe: false,
+                created_at: inst.created_at,
+                base_commit: None,
+            });
+            inst
+        })
+        .collect()
+}
+
+/// Plays the scripts for the sessions `prepare` created
+pub struct Demo {
+    started: Instant,
+    /// Session id and the script it follows
+    sessions: Vec<(String, &'static Script)>,
+}
+
+impl Demo {
+    /// Match the stored sample sessions to their scripts by title.
+    pub fn new(instances: &[Instance]) -> Self {
+        let sessions = instances
+            .iter()
+            .filter_map(|inst| {
+                let script = SCRIPTS.iter().find(|s| s.title == inst.title)?;
+                Some((inst.id.clone(), script))
+            })
+            .collect();
+        Self {
+            started: Instant::now(),
+            sessions,
+        }
+    }
+
+    fn script(&self, id: &str) -> Option<&'static Script> {
+        self.sessions
+            .iter()
+            .find(|(session_id, _)| session_id == id)
+            .map(|(_, script)| *script)
+    }
+
+    fn elapsed(&self) -> u64 {
+        self.started.elapsed().as_secs()
+    }
+
+    /// Current status of every scripted session
+    pub fn statuses(&self) -> Vec<(String, Status)> {
+        let elapsed = self.elapsed();
+        self.sessions
+            .iter()
+            .map(|(id, script)| (id.clone(), play(script, elapsed).0))
+            .collect()
+    }
+
+    /// What the session's pane shows now, or `None` for sessions the demo
+    /// did not create.
+    pub fn pane(&self, id: &str) -> Option<String> {
+        let script = self.script(id)?;
+        Some(play(script, self.elapsed()).1.join("\n"))
+    }
+}
+
+/// Status and output of `script` `elapsed` seconds into the demo
+fn play(script: &Script, elapsed: u64) -> (Status, Vec<&'static str>) {
+    let cycle: u64 = script.steps.iter().map(|s| s.secs).sum();
+    let mut t = (elapsed + script.offset) % cycle.max(1);
+    let mut lines = Vec::new();
+    for step in script.steps {
+        if t < step.secs {
+            // Lines appear one at a time, the first straight away
+            let shown = (step.lines.len() as u64 * (t + 1)).div_ceil(step.secs);
+            lines.extend(step.lines.iter().take(shown as usize));
+            return (step.status, lines);
+        }
+        t -= step.secs;
+        lines.extend(step.lines);
+    }
+    (script.steps[0].status, lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_play_follows_steps_and_loops() {
+        let script = &SCRIPTS[0];
+        let (status, lines) = play(script, 0);
+        assert_eq!(status, Status::Running);
+        assert_eq!(lines.len(), 1);
+
+        let first = script.steps[0].secs;
+        let (status, lines) = play(script, first);
+        assert_eq!(status, Status::Waiting);
+        assert!(lines.len() > script.steps[0].lines.len());
+
+        let cycle: u64 = script.steps.iter().map(|s| s.secs).sum();
+        let (status, lines) = play(script, cycle - 1);
+        assert_eq!(status, Status::Idle);
+        assert_eq!(lines.last(), Some(&"> "));
+        assert_eq!(play(script, cycle), play(script, 0));
+    }
+}
@@ -0,0 +1,211 @@
+//! Bulk rename dialog: rename every session matching a pattern
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+use super::DialogResult;
+use crate::session::bulk_rename::{self, Pattern, Rename};
+use crate::session::Instance;
+use crate::tui::components::render_text_field;
+use crate::tui::styles::Theme;
+
+const DIALOG_WIDTH: u16 = 64;
+const MAX_PREVIEW: usize = 10;
+
+pub struct BulkRenameDialog {
+    instances: Vec<Instance>,
+    pattern: Input,
+    replace: Input,
+    focused_field: usize, // 0 = match, 1 = replace
+    /// The renames the current pattern makes, or why it can't be applied
+    preview: Result<Vec<Rename>, String>,
+}
+
+impl BulkRenameDialog {
+    /// `instances` are the sessions the pattern may rename; `initial` seeds
+    /// both fields, usually with the selected session's title.
+    pub fn new(instances: Vec<Instance>, initial: &str) -> Self {
+        let mut dialog = Self {
+            instances,
+            pattern: Input::new(initial.to_string()),
+            replace: Input::new(initial.to_string()),
+            focused_field: 0,
+            preview: Ok(Vec::new()),
+        };
+        dialog.update_preview();
+        dialog
+    }
+
+    fn update_preview(&mut self) {
+        self.preview = Pattern::new(self.pattern.value(), self.replace.value())
+            .and_then(|pattern| bulk_rename::plan(&pattern, &self.instances));
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<Vec<Rename>> {
+        match key.code {
+            KeyCode::Esc => DialogResult::Cancel,
+            KeyCode::Enter => match &self.preview {
+                Ok(renames) if renames.is_empty() => DialogResult::Cancel,
+                Ok(renames) => DialogResult::Submit(renames.clone()),
+                Err(_) => DialogResult::Continue,
+            },
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                self.focused_field = 1 - self.focused_field;
+                DialogResult::Continue
+            }
+            _ => {
+                let input = if self.focused_field == 0 {
+                    &mut self.pattern
+                } else {
+                    &mut self.replace
+                };
+                if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('u') {
+                    *input = Input::default();
+                } else {
+                    input.handle_event(&crossterm::event::Event::Key(key));
+                }
+                self.update_preview();
+                DialogResult::Continue
+            }
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let preview_lines = match &self.preview {
+            Ok(renames) => renames.len().clamp(1, MAX_PREVIEW + 1),
+            Err(_) => 1,
+        } as u16;
+        let dialog_area = super::centered_rect(area, DIALOG_WIDTH, preview_lines + 9);
+
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(" Rename Sessions ")
+            .title_style(Style::default().fg(theme.title).bold());
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(1),             // Match field
+                Constraint::Length(1),             // Replace field
+                Constraint::Length(1),             // Spacer
+                Constraint::Length(preview_lines), // Preview
+                Constraint::Length(1),             // Spacer
+                Constraint::Min(1),                // Hint
+            ])
+            .split(inner);
+
+        render_text_field(
+            frame,
+            chunks[0],
+            "Match:  ",
+            &self.pattern,
+            self.focused_field == 0,
+            Some("old-*"),
+            theme,
+        );
+        render_text_field(
+            frame,
+            chunks[1],
+            "Replace:",
+            &self.replace,
+            self.focused_field == 1,
+            Some("new-*"),
+            theme,
+        );
+
+        let preview: Vec<Line> = match &self.preview {
+            Err(e) => vec![Line::from(Span::styled(
+                e.as_str(),
+                Style::default().fg(theme.error),
+            ))],
+            Ok(renames) if renames.is_empty() => vec![Line::from(Span::styled(
+                "No sessions would be renamed",
+                Style::default().fg(theme.dimmed),
+            ))],
+            Ok(renames) => {
+                let mut lines: Vec<Line> = renames
+                    .iter()
+                    .take(MAX_PREVIEW)
+                    .map(|r| {
+                        Line::from(vec![
+                            Span::styled(r.from.as_str(), Style::default().fg(theme.dimmed)),
+                            Span::styled(" → ", Style::default().fg(theme.dimmed)),
+                            Span::styled(r.to.as_str(), Style::default().fg(theme.text)),
+                        ])
+                    })
+                    .collect();
+                if renames.len() > MAX_PREVIEW {
+                    lines.push(Line::from(Span::styled(
+                        format!("…and {} more", renames.len() - MAX_PREVIEW),
+                        Style::default().fg(theme.dimmed),
+                    )));
+                }
+                lines
+            }
+        };
+        frame.render_widget(Paragraph::new(preview), chunks[3]);
+
+        let hint = Line::from(vec![
+            Span::styled("*", Style::default().fg(theme.hint)),
+            Span::raw(" any  "),
+            Span::styled("?", Style::default().fg(theme.hint)),
+            Span::raw(" one char  "),
+            Span::styled("Tab", Style::default().fg(theme.hint)),
+            Span::raw(" switch  "),
+            Span::styled("Enter", Style::default().fg(theme.hint)),
+            Span::raw(" rename  "),
+            Span::styled("Esc", Style::default().fg(theme.hint)),
+            Span::raw(" cancel"),
+        ]);
+        frame.render_widget(Paragraph::new(hint), chunks[5]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_str(dialog: &mut BulkRenameDialog, s: &str) {
+        for c in s.chars() {
+            dialog.handle_key(key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_submits_previewed_renames() {
+        let instances = vec![
+            Instance::new("old-api", "/tmp/api"),
+            Instance::new("web", "/tmp/web"),
+        ];
+        let mut dialog = BulkRenameDialog::new(instances, "");
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Continue
+        ));
+
+        type_str(&mut dialog, "old-*");
+        dialog.handle_key(key(KeyCode::Tab));
+        type_str(&mut dialog, "new-*");
+        match dialog.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(renames) => {
+                assert_eq!(renames.len(), 1);
+                assert_eq!(renames[0].to, "new-api");
+            }
+            _ => panic!("expected submit"),
+        }
+    }
+}
@@ -0,0 +1,335 @@
+//! Checkpoints dialog: list a session's checkpoints, make new ones, read
+//! what the agent did since one, and roll back to one
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+use super::DialogResult;
+use crate::session::checkpoints::Checkpoint;
+use crate::tui::styles::Theme;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckpointAction {
+    Create(String),
+    /// Show the agent's output since the checkpoint
+    View(String),
+    Rollback(String),
+    Remove(String),
+}
+
+enum Mode {
+    List,
+    Naming(Input),
+    ConfirmRollback,
+    Transcript { lines: Vec<String>, scroll: usize },
+}
+
+pub struct CheckpointsDialog {
+    session_id: String,
+    session_title: String,
+    checkpoints: Vec<Checkpoint>,
+    selected: usize,
+    mode: Mode,
+}
+
+impl CheckpointsDialog {
+    pub fn new(session_id: &str, session_title: &str, checkpoints: Vec<Checkpoint>) -> Self {
+        let selected = checkpoints.len().saturating_sub(1);
+        Self {
+            session_id: session_id.to_string(),
+            session_title: session_title.to_string(),
+            checkpoints,
+            selected,
+            mode: Mode::List,
+        }
+    }
+
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// Replace the list after a change, selecting the newest checkpoint.
+    pub fn set_checkpoints(&mut self, checkpoints: Vec<Checkpoint>) {
+        self.selected = checkpoints.len().saturating_sub(1);
+        self.checkpoints = checkpoints;
+        self.mode = Mode::List;
+    }
+
+    pub fn show_transcript(&mut self, text: &str) {
+        let lines: Vec<String> = text.trim_end().lines().map(String::from).collect();
+        self.mode = Mode::Transcript { lines, scroll: 0 };
+    }
+
+    fn selected_name(&self) -> Option<String> {
+        self.checkpoints.get(self.selected).map(|c| c.name.clone())
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<CheckpointAction> {
+        match &mut self.mode {
+            Mode::Naming(input) => match key.code {
+                KeyCode::Esc => self.mode = Mode::List,
+                KeyCode::Enter => {
+                    let name = input.value().trim().to_string();
+                    if !name.is_empty() {
+                        return DialogResult::Submit(CheckpointAction::Create(name));
+                    }
+                }
+                _ => {
+                    input.handle_event(&crossterm::event::Event::Key(key));
+                }
+            },
+            Mode::ConfirmRollback => match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    self.mode = Mode::List;
+                    if let Some(name) = self.selected_name() {
+                        return DialogResult::Submit(CheckpointAction::Rollback(name));
+                    }
+                }
+                _ => self.mode = Mode::List,
+            },
+            Mode::Transcript { lines, scroll } => match key.code {
+                KeyCode::Up | KeyCode::Char('k') => *scroll = scroll.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    *scroll = (*scroll + 1).min(lines.len().saturating_sub(1))
+                }
+                KeyCode::PageUp => *scroll = scroll.saturating_sub(10),
+                KeyCode::PageDown => *scroll = (*scroll + 10).min(lines.len().saturating_sub(1)),
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => self.mode = Mode::List,
+                _ => {}
+            },
+            Mode::List => match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => return DialogResult::Cancel,
+                KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j')
+                    if self.selected + 1 < self.checkpoints.len() =>
+                {
+                    self.selected += 1;
+                }
+                KeyCode::Char('n') => self.mode = Mode::Naming(Input::default()),
+                KeyCode::Enter => {
+                    if let Some(name) = self.selected_name() {
+                        return DialogResult::Submit(CheckpointAction::View(name));
+                    }
+                }
+                KeyCode::Char('r')
+                    if self
+                        .checkpoints
+                        .get(self.selected)
+                        .is_some_and(|c| c.commit.is_some()) =>
+                {
+                    self.mode = Mode::ConfirmRollback;
+                }
+                KeyCode::Char('x') => {
+                    if let Some(name) = self.selected_name() {
+                        return DialogResult::Submit(CheckpointAction::Remove(name));
+                    }
+                }
+                _ => {}
+            },
+        }
+        DialogResult::Continue
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let height = (area.height * 3 / 4).max(12);
+        let dialog_area = super::centered_rect(area, 80, height);
+        frame.render_widget(Clear, dialog_area);
+
+        let hint = |key: &'static str, label: &'static str| {
+            [
+                Span::styled(key, Style::default().fg(theme.accent)),
+                Span::styled(label, Style::default().fg(theme.dimmed)),
+            ]
+        };
+        let hints: Vec<Span> = match &self.mode {
+            Mode::List => [
+                hint(" n", " new "),
+                hint("Enter", " output since "),
+                hint("r", " roll back "),
+                hint("x", " delete "),
+                hint("Esc", " close "),
+            ]
+            .concat(),
+            Mode::Naming(_) => [hint(" Enter", " create "), hint("Esc", " cancel ")].concat(),
+            Mode::ConfirmRollback => [hint(" y", " roll back "), hint("n", " cancel ")].concat(),
+            Mode::Transcript { .. } => [hint(" j/k", " scroll "), hint("Esc", " back ")].concat(),
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(format!(" Checkpoints: {} ", self.session_title))
+            .title_style(Style::default().fg(theme.accent).bold())
+            .title_bottom(Line::from(hints).right_aligned());
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        if let Mode::Transcript { lines, scroll } = &self.mode {
+            let text: Vec<Line> = if lines.is_empty() {
+                vec![Line::styled(
+                    "No output since this checkpoint",
+                    Style::default().fg(theme.dimmed),
+                )]
+            } else {
+                lines
+                    .iter()
+                    .skip(*scroll)
+                    .map(|l| Line::styled(l.clone(), Style::default().fg(theme.text)))
+                    .collect()
+            };
+            frame.render_widget(Paragraph::new(text), inner);
+            return;
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(5)])
+            .split(inner);
+
+        let mut lines: Vec<Line> = Vec::new();
+        if self.checkpoints.is_empty() {
+            lines.push(Line::styled(
+                "No checkpoints yet. Press n to record one.",
+                Style::default().fg(theme.dimmed),
+            ));
+        }
+        let name_width = self
+            .checkpoints
+            .iter()
+            .map(|c| c.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        for (i, cp) in self.checkpoints.iter().enumerate() {
+            let selected = i == self.selected;
+            let marker = if selected { "> " } else { "  " };
+            let name_style = if selected {
+                Style::default().fg(theme.accent).bold()
+            } else {
+                Style::default().fg(theme.text)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(marker, Style::default().fg(theme.accent)),
+                Span::styled(
+                    cp.at
+                        .with_timezone(&chrono::Local)
+                        .format("%m-%d %H:%M  ")
+                        .to_string(),
+                    Style::default().fg(theme.dimmed),
+                ),
+                Span::styled(
+                    format!("{:<width$}  ", cp.name, width = name_width),
+                    name_style,
+                ),
+                Span::styled(cp.git_label(), Style::default().fg(theme.dimmed)),
+            ]));
+        }
+        let visible = chunks[0].height as usize;
+        let offset = (self.selected + 1).saturating_sub(visible);
+        frame.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), chunks[0]);
+
+        let footer: Vec<Line> = match &self.mode {
+            Mode::Naming(input) => vec![
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("Name: ", Style::default().fg(theme.accent)),
+                    Span::styled(input.value().to_string(), Style::default().fg(theme.text)),
+                    Span::styled("_", Style::default().fg(theme.accent)),
+                ]),
+            ],
+            Mode::ConfirmRollback => vec![
+                Line::from(""),
+                Line::styled(
+                    format!(
+                        "Restore the session's files to '{}'? The current state is saved as a checkpoint first.",
+                        self.selected_name().unwrap_or_default()
+                    ),
+                    Style::default().fg(theme.waiting),
+                ),
+            ],
+            _ => match self.checkpoints.get(self.selected) {
+                Some(cp) => {
+                    let mut footer = vec![Line::from("")];
+                    if let Some(note) = &cp.note {
+                        footer.push(Line::styled(note.clone(), Style::default().fg(theme.text)));
+                    }
+                    if cp.commit.is_none() {
+                        footer.push(Line::styled(
+                            "Not in a git repository; cannot be rolled back to",
+                            Style::default().fg(theme.dimmed),
+                        ));
+                    }
+                    footer
+                }
+                None => Vec::new(),
+            },
+        };
+        frame.render_widget(Paragraph::new(footer).wrap(Wrap { trim: true }), chunks[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn checkpoint(name: &str, commit: Option<&str>) -> Checkpoint {
+        Checkpoint {
+            name: name.to_string(),
+            at: chrono::Utc::now(),
+            commit: commit.map(str::to_string),
+            snapshot: None,
+            transcript_line: None,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_create_view_and_confirm_rollback() {
+        let mut dialog = CheckpointsDialog::new(
+            "id",
+            "Test",
+            vec![checkpoint("plan", Some("abc")), checkpoint("scratch", None)],
+        );
+        // The newest is selected, and without git state it cannot be rolled back to
+        dialog.handle_key(key(KeyCode::Char('r')));
+        assert!(matches!(dialog.mode, Mode::List));
+
+        dialog.handle_key(key(KeyCode::Char('k')));
+        dialog.handle_key(key(KeyCode::Char('r')));
+        assert!(matches!(dialog.mode, Mode::ConfirmRollback));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('y'))),
+            DialogResult::Submit(CheckpointAction::Rollback(ref n)) if n == "plan"
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Submit(CheckpointAction::View(ref n)) if n == "plan"
+        ));
+
+        dialog.handle_key(key(KeyCode::Char('n')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Continue
+        ));
+        for c in "tests".chars() {
+            dialog.handle_key(key(KeyCode::Char(c)));
+        }
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Submit(CheckpointAction::Create(ref n)) if n == "tests"
+        ));
+
+        dialog.set_checkpoints(vec![checkpoint("plan", Some("abc"))]);
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+}
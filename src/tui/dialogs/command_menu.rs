@@ -0,0 +1,172 @@
+//! Command menu: run one of a session's named commands with a single key
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::DialogResult;
+use crate::session::commands::NamedCommand;
+use crate::tui::styles::Theme;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandMenuAction {
+    /// Run the named command
+    Run(String),
+    /// Attach to the companion session to read the last command's output
+    ViewOutput,
+}
+
+pub struct CommandMenuDialog {
+    session_id: String,
+    session_title: String,
+    commands: Vec<NamedCommand>,
+    /// Command still running in the companion session, if any
+    running: Option<String>,
+    /// Whether a companion session is open to attach to
+    has_output: bool,
+}
+
+impl CommandMenuDialog {
+    pub fn new(
+        session_id: &str,
+        session_title: &str,
+        commands: Vec<NamedCommand>,
+        running: Option<String>,
+        has_output: bool,
+    ) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            session_title: session_title.to_string(),
+            commands,
+            running,
+            has_output,
+        }
+    }
+
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<CommandMenuAction> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => DialogResult::Cancel,
+            KeyCode::Enter if self.has_output => {
+                DialogResult::Submit(CommandMenuAction::ViewOutput)
+            }
+            KeyCode::Char(c) => {
+                let c = c.to_ascii_lowercase();
+                match self.commands.iter().find(|cmd| cmd.key == c) {
+                    Some(cmd) => DialogResult::Submit(CommandMenuAction::Run(cmd.name.clone())),
+                    None => DialogResult::Continue,
+                }
+            }
+            _ => DialogResult::Continue,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        // 2 for borders + 1 per command + 2 for the status line
+        let height = (self.commands.len() as u16 + 4).min(area.height);
+        let dialog_area = super::centered_rect(area, 70, height);
+
+        frame.render_widget(Clear, dialog_area);
+
+        let mut hints = vec![
+            Span::styled(" key", Style::default().fg(theme.accent)),
+            Span::styled(" run ", Style::default().fg(theme.dimmed)),
+        ];
+        if self.has_output {
+            hints.push(Span::styled("Enter", Style::default().fg(theme.accent)));
+            hints.push(Span::styled(" output ", Style::default().fg(theme.dimmed)));
+        }
+        hints.push(Span::styled("Esc", Style::default().fg(theme.accent)));
+        hints.push(Span::styled(" close ", Style::default().fg(theme.dimmed)));
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(format!(" Commands: {} ", self.session_title))
+            .title_style(Style::default().fg(theme.accent).bold())
+            .title_bottom(Line::from(hints).right_aligned());
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let name_width = self
+            .commands
+            .iter()
+            .map(|c| c.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut lines: Vec<Line> = self
+            .commands
+            .iter()
+            .map(|cmd| {
+                Line::from(vec![
+                    Span::styled(format!("[{}] ", cmd.key), Style::default().fg(theme.accent)),
+                    Span::styled(
+                        format!("{:<width$}  ", cmd.name, width = name_width),
+                        Style::default().fg(theme.text),
+                    ),
+                    Span::styled(cmd.run.clone(), Style::default().fg(theme.dimmed)),
+                ])
+            })
+            .collect();
+        lines.push(Line::from(""));
+        let status = match &self.running {
+            Some(name) => Span::styled(
+                format!("{} is running; starting another replaces it", name),
+                Style::default().fg(theme.waiting),
+            ),
+            None => Span::styled(
+                "Runs in a companion tmux session in the session's directory",
+                Style::default().fg(theme.dimmed),
+            ),
+        };
+        lines.push(Line::from(status));
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_command_key_runs_and_enter_needs_output() {
+        let commands = vec![NamedCommand {
+            key: 't',
+            name: "test".to_string(),
+            run: "cargo test".to_string(),
+        }];
+        let mut dialog = CommandMenuDialog::new("id", "Test", commands.clone(), None, false);
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('t'))),
+            DialogResult::Submit(CommandMenuAction::Run(ref name)) if name == "test"
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('x'))),
+            DialogResult::Continue
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Continue
+        ));
+
+        let mut dialog = CommandMenuDialog::new("id", "Test", commands, None, true);
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Submit(CommandMenuAction::ViewOutput)
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+}
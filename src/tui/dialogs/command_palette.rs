@@ -0,0 +1,306 @@
+//! Command palette - fuzzy-searchable list of home screen actions
+//!
+//! Each command is tied to the key that already performs it, so picking one
+//! replays that key and behaves exactly like pressing the shortcut.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+use super::DialogResult;
+use crate::tui::styles::Theme;
+
+const DIALOG_WIDTH: u16 = 56;
+const MAX_VISIBLE: usize = 12;
+
+/// A palette entry: what it does, the key shown beside it, and the key
+/// event it replays.
+pub struct PaletteCommand {
+    pub label: &'static str,
+    pub key_hint: &'static str,
+    code: KeyCode,
+}
+
+impl PaletteCommand {
+    const fn new(label: &'static str, key_hint: &'static str, code: KeyCode) -> Self {
+        Self {
+            label,
+            key_hint,
+            code,
+        }
+    }
+
+    pub fn key_event(&self) -> KeyEvent {
+        KeyEvent::new(self.code, KeyModifiers::NONE)
+    }
+}
+
+/// Every action offered by the palette, in the order shown when the filter
+/// is empty.
+fn commands() -> Vec<PaletteCommand> {
+    vec![
+        PaletteCommand::new("New session", "n", KeyCode::Char('n')),
+        PaletteCommand::new("New session from selection", "N", KeyCode::Char('N')),
+        PaletteCommand::new("Attach to session", "Enter", KeyCode::Enter),
+        PaletteCommand::new("Observe session (read-only)", "O", KeyCode::Char('O')),
+        PaletteCommand::new("Stop (kill) session", "x", KeyCode::Char('x')),
+        PaletteCommand::new("Retry session from scratch", "R", KeyCode::Char('R')),
+        PaletteCommand::new("Delete session (move to archives)", "d", KeyCode::Char('d')),
+        PaletteCommand::new("Rename session", "r", KeyCode::Char('r')),
+        PaletteCommand::new("Bulk rename sessions by pattern", "B", KeyCode::Char('B')),
+        PaletteCommand::new("Send message to agent", "m", KeyCode::Char('m')),
+        PaletteCommand::new("Edit session notes", "e", KeyCode::Char('e')),
+        PaletteCommand::new("Re-authenticate agent", "A", KeyCode::Char('A')),
+        PaletteCommand::new("Answer permission prompt", "y", KeyCode::Char('y')),
+        PaletteCommand::new("Run a named command", "!", KeyCode::Char('!')),
+        PaletteCommand::new("Session checkpoints", "C", KeyCode::Char('C')),
+        PaletteCommand::new("Session process tree", "T", KeyCode::Char('T')),
+        PaletteCommand::new("Diff view", "D", KeyCode::Char('D')),
+        PaletteCommand::new("Merge queue", "M", KeyCode::Char('M')),
+        PaletteCommand::new("Resolve merge conflicts", "F", KeyCode::Char('F')),
+        PaletteCommand::new("Board view", "b", KeyCode::Char('b')),
+        PaletteCommand::new("Toggle agent/terminal view", "t", KeyCode::Char('t')),
+        PaletteCommand::new("Toggle activity timeline", "a", KeyCode::Char('a')),
+        PaletteCommand::new("Toggle tool-call events", "v", KeyCode::Char('v')),
+        PaletteCommand::new("Cycle layout (auto/full/compact)", "z", KeyCode::Char('z')),
+        PaletteCommand::new("Cycle sort order", "o", KeyCode::Char('o')),
+        PaletteCommand::new("Search sessions", "/", KeyCode::Char('/')),
+        PaletteCommand::new("Filter sessions", "f", KeyCode::Char('f')),
+        PaletteCommand::new("Dashboard", "2", KeyCode::Char('2')),
+        PaletteCommand::new("Archives", "3", KeyCode::Char('3')),
+        PaletteCommand::new("Logs", "4", KeyCode::Char('4')),
+        PaletteCommand::new("Inbox", "6", KeyCode::Char('6')),
+        PaletteCommand::new("Open settings", "s", KeyCode::Char('s')),
+        PaletteCommand::new("Switch profile", "P", KeyCode::Char('P')),
+        PaletteCommand::new("Help", "?", KeyCode::Char('?')),
+        PaletteCommand::new("Quit", "q", KeyCode::Char('q')),
+    ]
+}
+
+/// Score `text` against `query` as a case-insensitive subsequence, or None
+/// if it doesn't match. Consecutive characters and word starts score higher.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut previous: Option<usize> = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = pos + text[pos..].iter().position(|&c| c == q)?;
+        score += 1;
+        if previous == Some(found.wrapping_sub(1)) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (found - pos) as i32 / 4;
+        previous = Some(found);
+        pos = found + 1;
+    }
+    Some(score)
+}
+
+pub struct CommandPaletteDialog {
+    commands: Vec<PaletteCommand>,
+    filter: Input,
+    selected: usize,
+}
+
+impl Default for CommandPaletteDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandPaletteDialog {
+    pub fn new() -> Self {
+        Self {
+            commands: commands(),
+            filter: Input::default(),
+            selected: 0,
+        }
+    }
+
+    /// Commands matching the filter, best match first.
+    fn matches(&self) -> Vec<&PaletteCommand> {
+        let query = self.filter.value().trim();
+        if query.is_empty() {
+            return self.commands.iter().collect();
+        }
+        let mut scored: Vec<(i32, &PaletteCommand)> = self
+            .commands
+            .iter()
+            .filter_map(|cmd| fuzzy_score(query, cmd.label).map(|s| (s, cmd)))
+            .collect();
+        // Stable sort keeps the listed order among equal scores
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, cmd)| cmd).collect()
+    }
+
+    /// Submits the key event of the chosen command.
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<KeyEvent> {
+        let count = self.matches().len();
+        match key.code {
+            KeyCode::Esc => DialogResult::Cancel,
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                DialogResult::Cancel
+            }
+            KeyCode::Enter => match self.matches().get(self.selected) {
+                Some(cmd) => DialogResult::Submit(cmd.key_event()),
+                None => DialogResult::Cancel,
+            },
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                DialogResult::Continue
+            }
+            KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.selected = self.selected.saturating_sub(1);
+                DialogResult::Continue
+            }
+            KeyCode::Down => {
+                if self.selected + 1 < count {
+                    self.selected += 1;
+                }
+                DialogResult::Continue
+            }
+            KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if self.selected + 1 < count {
+                    self.selected += 1;
+                }
+                DialogResult::Continue
+            }
+            _ => {
+                self.filter.handle_event(&crossterm::event::Event::Key(key));
+                self.selected = 0;
+                DialogResult::Continue
+            }
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let matches = self.matches();
+        let list_height = matches.len().clamp(1, MAX_VISIBLE) as u16;
+        // borders (2) + filter (1) + spacer (1) + list
+        let height = list_height + 4;
+        let dialog_area = super::centered_rect(area, DIALOG_WIDTH, height);
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(" Commands ")
+            .title_style(Style::default().fg(theme.title).bold())
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Enter", Style::default().fg(theme.hint)),
+                    Span::styled(" run ", Style::default().fg(theme.dimmed)),
+                    Span::styled("Esc", Style::default().fg(theme.hint)),
+                    Span::styled(" close ", Style::default().fg(theme.dimmed)),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(1),
+            ])
+            .split(inner);
+
+        let filter_line = Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme.accent)),
+            Span::styled(self.filter.value(), Style::default().fg(theme.text).bold()),
+            Span::styled("_", Style::default().fg(theme.accent)),
+        ]);
+        frame.render_widget(Paragraph::new(filter_line), chunks[0]);
+
+        if matches.is_empty() {
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    "  (no matching commands)",
+                    Style::default().fg(theme.dimmed),
+                )),
+                chunks[2],
+            );
+            return;
+        }
+
+        let visible = chunks[2].height as usize;
+        let offset = (self.selected + 1).saturating_sub(visible);
+        let label_width = (inner.width as usize).saturating_sub(10);
+        let lines: Vec<Line> = matches
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible)
+            .map(|(i, cmd)| {
+                let selected = i == self.selected;
+                let style = if selected {
+                    Style::default().fg(theme.accent).bold()
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                Line::from(vec![
+                    Span::styled(if selected { "> " } else { "  " }, style),
+                    Span::styled(format!("{:<label_width$}", cmd.label), style),
+                    Span::styled(
+                        format!("{:>6}", cmd.key_hint),
+                        Style::default().fg(theme.hint),
+                    ),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), chunks[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(dialog: &mut CommandPaletteDialog, text: &str) {
+        for c in text.chars() {
+            dialog.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("nse", "New session").is_some());
+        assert!(fuzzy_score("xyz", "New session").is_none());
+        // Word starts beat scattered letters
+        assert!(
+            fuzzy_score("sp", "Switch profile").unwrap()
+                > fuzzy_score("sp", "Send message to agent").unwrap_or(i32::MIN)
+        );
+    }
+
+    #[test]
+    fn test_filter_and_submit() {
+        let mut dialog = CommandPaletteDialog::new();
+        type_text(&mut dialog, "prof");
+        assert_eq!(dialog.matches()[0].label, "Switch profile");
+
+        match dialog.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)) {
+            DialogResult::Submit(key) => assert_eq!(key.code, KeyCode::Char('P')),
+            _ => panic!("expected submit"),
+        }
+    }
+
+    #[test]
+    fn test_no_match_cancels() {
+        let mut dialog = CommandPaletteDialog::new();
+        type_text(&mut dialog, "zzzz");
+        assert!(dialog.matches().is_empty());
+        assert!(matches!(
+            dialog.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            DialogResult::Cancel
+        ));
+    }
+}
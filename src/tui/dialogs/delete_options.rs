@@ -21,6 +21,9 @@ pub struct DeleteOptions {
 pub struct DeleteDialogConfig {
     pub worktree_branch: Option<String>,
     pub has_sandbox: bool,
+    /// Session worktrees with uncommitted changes (every repo of a
+    /// multi-repo workspace is checked).
+    pub dirty_worktrees: Vec<String>,
 }
 
 /// Focus states for navigation
@@ -47,8 +50,11 @@ impl UnifiedDeleteDialog {
     pub fn new(session_title: String, config: DeleteDialogConfig, profile: &str) -> Self {
         let user_config = crate::session::resolve_config(profile).unwrap_or_default();
 
+        // Uncommitted work is never removed by default
         let options = DeleteOptions {
-            delete_worktree: config.worktree_branch.is_some() && user_config.worktree.auto_cleanup,
+            delete_worktree: config.worktree_branch.is_some()
+                && user_config.worktree.auto_cleanup
+                && config.dirty_worktrees.is_empty(),
             force_delete: false,
             delete_branch: config.worktree_branch.is_some()
                 && user_config.worktree.delete_branch_on_cleanup,
@@ -226,12 +232,14 @@ impl UnifiedDeleteDialog {
         let checkbox_count =
             if has_worktree { 2 } else { 0 } + (show_force as u16) + (has_sandbox as u16);
 
+        let show_dirty = has_worktree && !self.config.dirty_worktrees.is_empty();
+
         let dialog_width = 55;
         let dialog_height = if checkbox_count > 0 {
             8 + checkbox_count
         } else {
             7
-        };
+        } + show_dirty as u16;
 
         let dialog_area = super::centered_rect(area, dialog_width, dialog_height);
 
@@ -246,10 +254,11 @@ impl UnifiedDeleteDialog {
         let inner = block.inner(dialog_area);
         frame.render_widget(block, dialog_area);
 
-        let mut constraints = vec![
-            Constraint::Length(1), // message
-            Constraint::Length(1), // spacer after message
-        ];
+        let mut constraints = vec![Constraint::Length(1)]; // message
+        if show_dirty {
+            constraints.push(Constraint::Length(1)); // uncommitted changes
+        }
+        constraints.push(Constraint::Length(1)); // spacer after message
 
         if checkbox_count > 0 {
             for _ in 0..checkbox_count {
@@ -277,6 +286,20 @@ impl UnifiedDeleteDialog {
             chunks[chunk_idx],
         );
         chunk_idx += 1;
+
+        if show_dirty {
+            let warning = format!(
+                "Uncommitted changes in: {}",
+                self.config.dirty_worktrees.join(", ")
+            );
+            frame.render_widget(
+                Paragraph::new(warning)
+                    .style(Style::default().fg(theme.waiting))
+                    .alignment(Alignment::Center),
+                chunks[chunk_idx],
+            );
+            chunk_idx += 1;
+        }
         chunk_idx += 1; // skip spacer
 
         if checkbox_count > 0 {
@@ -495,6 +518,7 @@ mod tests {
             DeleteDialogConfig {
                 worktree_branch: Some("feature-branch".to_string()),
                 has_sandbox: true,
+                dirty_worktrees: Vec::new(),
             },
             "default",
         )
@@ -538,6 +562,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_dirty_worktrees_are_kept_by_default() {
+        let dialog = UnifiedDeleteDialog::new(
+            "Test Session".to_string(),
+            DeleteDialogConfig {
+                worktree_branch: Some("feature-branch".to_string()),
+                has_sandbox: false,
+                dirty_worktrees: vec!["api".to_string()],
+            },
+            "default",
+        );
+        assert!(!dialog.options.delete_worktree);
+        assert!(!dialog
+            .focusable_elements
+            .contains(&FocusElement::ForceCheckbox));
+    }
+
     #[test]
     fn test_tab_cycles_through_elements() {
         let mut dialog = full_dialog();
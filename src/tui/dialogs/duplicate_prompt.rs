@@ -0,0 +1,121 @@
+//! Warning shown when a new session repeats a recent session's prompt
+
+use chrono::Utc;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::DialogResult;
+use crate::session::duplicates::DuplicatePrompt;
+use crate::tui::home::format_age;
+use crate::tui::styles::Theme;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DuplicatePromptAction {
+    /// Drop the new session and select the existing one
+    SwitchTo(String),
+    CreateAnyway,
+}
+
+pub struct DuplicatePromptDialog {
+    duplicate: DuplicatePrompt,
+}
+
+impl DuplicatePromptDialog {
+    pub fn new(duplicate: DuplicatePrompt) -> Self {
+        Self { duplicate }
+    }
+
+    /// Esc returns to the new session dialog.
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<DuplicatePromptAction> {
+        match key.code {
+            KeyCode::Esc => DialogResult::Cancel,
+            KeyCode::Enter | KeyCode::Char('s') => DialogResult::Submit(
+                DuplicatePromptAction::SwitchTo(self.duplicate.session_id.clone()),
+            ),
+            KeyCode::Char('c') => DialogResult::Submit(DuplicatePromptAction::CreateAnyway),
+            _ => DialogResult::Continue,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let dialog_area = super::centered_rect(area, 60, 9);
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.waiting))
+            .title(" Duplicate Prompt ")
+            .title_style(Style::default().fg(theme.waiting).bold());
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let age = format_age((Utc::now() - self.duplicate.created_at).num_seconds());
+        let message = format!(
+            "\"{}\" was started {} ago in this project with a {:.0}% similar prompt. \
+             Switch to it instead of paying for the same work twice?",
+            self.duplicate.title,
+            age,
+            self.duplicate.similarity * 100.0
+        );
+        frame.render_widget(
+            Paragraph::new(message)
+                .style(Style::default().fg(theme.text))
+                .wrap(Wrap { trim: true }),
+            chunks[0],
+        );
+
+        let key = |k: &'static str| Span::styled(k, Style::default().fg(theme.hint));
+        let desc = |d: &'static str| Span::styled(d, Style::default().fg(theme.dimmed));
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                key("Enter"),
+                desc(" switch to it  "),
+                key("c"),
+                desc(" create anyway  "),
+                key("Esc"),
+                desc(" back"),
+            ]))
+            .alignment(Alignment::Center),
+            chunks[1],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_actions() {
+        let mut dialog = DuplicatePromptDialog::new(DuplicatePrompt {
+            session_id: "abc".to_string(),
+            title: "Fix login".to_string(),
+            created_at: Utc::now(),
+            similarity: 0.95,
+        });
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Submit(DuplicatePromptAction::SwitchTo(ref id)) if id == "abc"
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('c'))),
+            DialogResult::Submit(DuplicatePromptAction::CreateAnyway)
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+}
@@ -110,6 +110,26 @@ impl HookTrustDialog {
             for cmd in &self.hooks.on_launch {
                 lines.push(Line::from(format!("  {}", cmd)));
             }
+            lines.push(Line::from(""));
+        }
+
+        if let Some(cmd) = &self.hooks.health_check {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "health_check (every {}s):",
+                    self.hooks.health_check_interval_secs
+                ),
+                Style::default().bold(),
+            )));
+            lines.push(Line::from(format!("  {}", cmd)));
+        }
+
+        if let Some(cmd) = &self.hooks.test_watch {
+            lines.push(Line::from(Span::styled(
+                "test_watch (kept running):",
+                Style::default().bold(),
+            )));
+            lines.push(Line::from(format!("  {}", cmd)));
         }
 
         lines
@@ -207,6 +227,7 @@ mod tests {
             HooksConfig {
                 on_create: vec!["npm install".to_string()],
                 on_launch: vec!["echo start".to_string()],
+                ..Default::default()
             },
             "abc123".to_string(),
             "/home/user/project".to_string(),
@@ -0,0 +1,419 @@
+//! Merge queue dialog - land worktree session branches on their base in turn
+
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::DialogResult;
+use crate::git::merge_queue::{self, MergeEntry, MergeOutcome, MergeProgress};
+use crate::session::timeline;
+use crate::tui::styles::Theme;
+
+/// A worktree session that can be queued.
+pub struct MergeQueueRow {
+    pub session_id: String,
+    pub title: String,
+    pub branch: String,
+    pub worktree_path: PathBuf,
+    /// Whether the session is part of the next run.
+    pub included: bool,
+    state: RowState,
+}
+
+impl MergeQueueRow {
+    pub fn new(
+        session_id: String,
+        title: String,
+        branch: String,
+        worktree_path: PathBuf,
+        included: bool,
+    ) -> Self {
+        Self {
+            session_id,
+            title,
+            branch,
+            worktree_path,
+            included,
+            state: RowState::Pending,
+        }
+    }
+}
+
+enum RowState {
+    Pending,
+    Busy(&'static str),
+    Done(MergeOutcome),
+}
+
+#[derive(PartialEq, Eq)]
+enum Phase {
+    Ordering,
+    Running,
+    Finished,
+}
+
+pub struct MergeQueueDialog {
+    repo_path: PathBuf,
+    base_branch: String,
+    test_command: Option<String>,
+    run_tests: bool,
+    rows: Vec<MergeQueueRow>,
+    selected: usize,
+    phase: Phase,
+    /// Row index for each entry of the running queue, in order.
+    queued: Vec<usize>,
+    rx: Option<Receiver<MergeProgress>>,
+}
+
+impl MergeQueueDialog {
+    pub fn new(
+        repo_path: PathBuf,
+        base_branch: String,
+        test_command: Option<String>,
+        rows: Vec<MergeQueueRow>,
+    ) -> Self {
+        let run_tests = test_command.is_some();
+        Self {
+            repo_path,
+            base_branch,
+            test_command,
+            run_tests,
+            rows,
+            selected: 0,
+            phase: Phase::Ordering,
+            queued: Vec::new(),
+            rx: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.phase == Phase::Running
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<()> {
+        match self.phase {
+            // The queue can't be interrupted mid-rebase
+            Phase::Running => return DialogResult::Continue,
+            Phase::Finished => {
+                return match key.code {
+                    KeyCode::Esc | KeyCode::Enter => DialogResult::Cancel,
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        self.selected = self.selected.saturating_sub(1);
+                        DialogResult::Continue
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        self.move_cursor_down();
+                        DialogResult::Continue
+                    }
+                    _ => DialogResult::Continue,
+                };
+            }
+            Phase::Ordering => {}
+        }
+
+        match key.code {
+            KeyCode::Esc => return DialogResult::Cancel,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor_down(),
+            KeyCode::Char('K') if self.selected > 0 => {
+                self.rows.swap(self.selected, self.selected - 1);
+                self.selected -= 1;
+            }
+            KeyCode::Char('J') if self.selected + 1 < self.rows.len() => {
+                self.rows.swap(self.selected, self.selected + 1);
+                self.selected += 1;
+            }
+            KeyCode::Char(' ') => {
+                if let Some(row) = self.rows.get_mut(self.selected) {
+                    row.included = !row.included;
+                }
+            }
+            KeyCode::Char('t') if self.test_command.is_some() => {
+                self.run_tests = !self.run_tests;
+            }
+            KeyCode::Enter => self.start(),
+            _ => {}
+        }
+        DialogResult::Continue
+    }
+
+    fn move_cursor_down(&mut self) {
+        if self.selected + 1 < self.rows.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Start landing the included sessions on a worker thread.
+    fn start(&mut self) {
+        self.queued = (0..self.rows.len())
+            .filter(|&i| self.rows[i].included)
+            .collect();
+        if self.queued.is_empty() {
+            return;
+        }
+        for row in &mut self.rows {
+            row.state = RowState::Pending;
+        }
+
+        let entries: Vec<MergeEntry> = self
+            .queued
+            .iter()
+            .map(|&i| MergeEntry {
+                session_id: self.rows[i].session_id.clone(),
+                branch: self.rows[i].branch.clone(),
+                worktree_path: self.rows[i].worktree_path.clone(),
+            })
+            .collect();
+        let repo_path = self.repo_path.clone();
+        let base_branch = self.base_branch.clone();
+        let test_command = self.test_command.clone().filter(|_| self.run_tests);
+
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            merge_queue::run(
+                &repo_path,
+                &base_branch,
+                &entries,
+                test_command.as_deref(),
+                &tx,
+            );
+        });
+        self.rx = Some(rx);
+        self.phase = Phase::Running;
+    }
+
+    /// Apply progress from the worker. Returns true when a redraw is needed.
+    pub fn tick(&mut self) -> bool {
+        let Some(rx) = &self.rx else {
+            return false;
+        };
+        let mut changed = false;
+        while let Ok(progress) = rx.try_recv() {
+            changed = true;
+            match progress {
+                MergeProgress::Stage(idx, stage) => {
+                    if let Some(&row) = self.queued.get(idx) {
+                        self.rows[row].state = RowState::Busy(stage);
+                    }
+                }
+                MergeProgress::Finished(idx, outcome) => {
+                    if let Some(&row) = self.queued.get(idx) {
+                        let row = &mut self.rows[row];
+                        timeline::record(
+                            &row.session_id,
+                            timeline::EventKind::Git,
+                            format!(
+                                "merge queue into {}: {}",
+                                self.base_branch,
+                                outcome.summary()
+                            ),
+                        );
+                        row.state = RowState::Done(outcome);
+                    }
+                }
+                MergeProgress::Done => {
+                    self.phase = Phase::Finished;
+                }
+            }
+        }
+        if self.phase == Phase::Finished {
+            self.rx = None;
+        }
+        changed
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let detail = self.detail_lines(theme);
+        // header(2) + rows + detail + hint(1) + borders(2) + margin(2)
+        let height = (self.rows.len() + detail.len() + 7) as u16;
+        let dialog_area = super::centered_rect(area, 76, height.min(area.height));
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(format!(" Merge Queue into {} ", self.base_branch))
+            .title_style(Style::default().fg(theme.title).bold());
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Length(self.rows.len() as u16),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let tests = match (&self.test_command, self.run_tests) {
+            (Some(cmd), true) => Span::styled(cmd.clone(), Style::default().fg(theme.text)),
+            (Some(_), false) => Span::styled("off", Style::default().fg(theme.dimmed)),
+            (None, _) => Span::styled(
+                "none (set worktree.merge_test_command)",
+                Style::default().fg(theme.dimmed),
+            ),
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled("Tests: ", Style::default().fg(theme.dimmed)),
+                tests,
+            ])),
+            chunks[0],
+        );
+
+        let lines: Vec<Line> = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| self.row_line(i, row, theme))
+            .collect();
+        frame.render_widget(Paragraph::new(lines), chunks[1]);
+        frame.render_widget(Paragraph::new(detail).wrap(Wrap { trim: false }), chunks[2]);
+
+        let hint = |key: &'static str, label: &'static str| {
+            [
+                Span::styled(key, Style::default().fg(theme.hint)),
+                Span::raw(label),
+            ]
+        };
+        let hints: Vec<Span> = match self.phase {
+            Phase::Ordering => [
+                hint("Space", " include  "),
+                hint("J/K", " reorder  "),
+                hint("t", " tests  "),
+                hint("Enter", " run  "),
+                hint("Esc", " close"),
+            ]
+            .concat(),
+            Phase::Running => vec![Span::styled(
+                "Running...",
+                Style::default().fg(theme.dimmed),
+            )],
+            Phase::Finished => [hint("j/k", " details  "), hint("Esc", " close")].concat(),
+        };
+        frame.render_widget(Paragraph::new(Line::from(hints)), chunks[3]);
+    }
+
+    fn row_line(&self, idx: usize, row: &MergeQueueRow, theme: &Theme) -> Line<'static> {
+        let selected = idx == self.selected;
+        let marker = if row.included { "[x] " } else { "[ ] " };
+        let name_style = if selected {
+            Style::default().fg(theme.accent).bold()
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let (status, status_style) = match &row.state {
+            RowState::Pending if row.included && self.phase != Phase::Ordering => {
+                ("queued".to_string(), Style::default().fg(theme.dimmed))
+            }
+            RowState::Pending => (String::new(), Style::default()),
+            RowState::Busy(stage) => (format!("{}...", stage), Style::default().fg(theme.waiting)),
+            RowState::Done(outcome) => (
+                outcome.summary(),
+                Style::default().fg(if outcome.is_success() {
+                    theme.running
+                } else {
+                    theme.error
+                }),
+            ),
+        };
+        Line::from(vec![
+            Span::styled(if selected { "> " } else { "  " }, name_style),
+            Span::styled(marker, Style::default().fg(theme.dimmed)),
+            Span::styled(row.title.clone(), name_style),
+            Span::styled(
+                format!(" ({})", row.branch),
+                Style::default().fg(theme.branch),
+            ),
+            Span::raw("  "),
+            Span::styled(status, status_style),
+        ])
+    }
+
+    /// Conflicting files or test output for the selected session.
+    fn detail_lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let Some(RowState::Done(outcome)) = self.rows.get(self.selected).map(|r| &r.state) else {
+            return Vec::new();
+        };
+        let dimmed = Style::default().fg(theme.dimmed);
+        let body: Vec<String> = match outcome {
+            MergeOutcome::Conflict(files) => files.iter().map(|f| format!("  {}", f)).collect(),
+            MergeOutcome::TestsFailed(output) | MergeOutcome::Failed(output) => {
+                output.lines().map(|l| format!("  {}", l)).collect()
+            }
+            _ => return Vec::new(),
+        };
+        let title = match outcome {
+            MergeOutcome::Conflict(_) => "Conflicting files (rebase aborted):",
+            MergeOutcome::TestsFailed(_) => "Test output:",
+            _ => "Error:",
+        };
+        std::iter::once(Line::from(""))
+            .chain(std::iter::once(Line::styled(title, dimmed)))
+            .chain(
+                body.into_iter()
+                    .map(|l| Line::styled(l, Style::default().fg(theme.text))),
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn dialog() -> MergeQueueDialog {
+        let rows = ["a", "b", "c"]
+            .iter()
+            .map(|name| {
+                MergeQueueRow::new(
+                    name.to_string(),
+                    name.to_string(),
+                    name.to_string(),
+                    PathBuf::from("/nonexistent"),
+                    true,
+                )
+            })
+            .collect();
+        MergeQueueDialog::new(PathBuf::from("/nonexistent"), "main".into(), None, rows)
+    }
+
+    #[test]
+    fn test_reorder_and_toggle() {
+        let mut d = dialog();
+        d.handle_key(key(KeyCode::Char('J')));
+        assert_eq!(d.rows[1].title, "a");
+        assert_eq!(d.selected, 1);
+        d.handle_key(key(KeyCode::Char(' ')));
+        assert!(!d.rows[1].included);
+        d.handle_key(key(KeyCode::Char('K')));
+        assert_eq!(d.rows[0].title, "a");
+    }
+
+    #[test]
+    fn test_enter_without_included_rows_does_nothing() {
+        let mut d = dialog();
+        for row in &mut d.rows {
+            row.included = false;
+        }
+        d.handle_key(key(KeyCode::Enter));
+        assert!(!d.is_running());
+        assert!(matches!(
+            d.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+}
@@ -1,31 +1,51 @@
 //! TUI dialog components
 
+mod bulk_rename;
 mod changelog;
+mod checkpoints;
+mod command_menu;
+mod command_palette;
 mod confirm;
 mod custom_instruction;
 mod delete_options;
+mod duplicate_prompt;
 mod group_delete_options;
 mod hook_trust;
 mod hooks_install;
 mod info;
+mod merge_queue;
 mod new_session;
+mod permission;
+mod processes;
 mod profile_picker;
 mod rename;
+mod retry;
 mod send_message;
+mod start_failure;
 mod welcome;
 
+pub use bulk_rename::BulkRenameDialog;
 pub use changelog::ChangelogDialog;
+pub use checkpoints::{CheckpointAction, CheckpointsDialog};
+pub use command_menu::{CommandMenuAction, CommandMenuDialog};
+pub use command_palette::CommandPaletteDialog;
 pub use confirm::ConfirmDialog;
 pub use custom_instruction::CustomInstructionDialog;
 pub use delete_options::{DeleteDialogConfig, DeleteOptions, UnifiedDeleteDialog};
+pub use duplicate_prompt::{DuplicatePromptAction, DuplicatePromptDialog};
 pub use group_delete_options::{GroupDeleteOptions, GroupDeleteOptionsDialog};
 pub use hook_trust::{HookTrustAction, HookTrustDialog};
 pub use hooks_install::HooksInstallDialog;
 pub use info::InfoDialog;
-pub use new_session::{NewSessionData, NewSessionDialog};
+pub use merge_queue::{MergeQueueDialog, MergeQueueRow};
+pub use new_session::{ExistingSessionPath, NewSessionData, NewSessionDialog};
+pub use permission::PermissionDialog;
+pub use processes::{ProcessAction, ProcessesDialog};
 pub use profile_picker::{ProfileEntry, ProfilePickerAction, ProfilePickerDialog};
 pub use rename::{RenameData, RenameDialog};
+pub use retry::RetryDialog;
 pub use send_message::SendMessageDialog;
+pub use start_failure::{StartFailureAction, StartFailureDialog};
 pub use welcome::WelcomeDialog;
 
 pub enum DialogResult<T> {
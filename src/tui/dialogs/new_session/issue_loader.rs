@@ -0,0 +1,134 @@
+//! Background `gh` calls for the issue picker.
+//!
+//! Listing a repository's open issues and fetching the picked one both go
+//! over the network, so they run on a worker thread and the dialog applies
+//! the result on a later tick. A result is dropped if another request was
+//! made after it.
+
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use tui_input::Input;
+
+use super::NewSessionDialog;
+use crate::git::issue::{self, Issue, IssueRef, IssueSummary};
+
+pub(super) enum IssueLoad {
+    List(Result<Vec<IssueSummary>, String>),
+    Fetch(Result<Issue, String>),
+}
+
+struct IssueLoadResult {
+    request: u64,
+    load: IssueLoad,
+}
+
+pub(super) struct IssueLoader {
+    tx: Sender<IssueLoadResult>,
+    rx: Receiver<IssueLoadResult>,
+    /// Number of the most recent request
+    latest: u64,
+    /// What the most recent request is doing, while it runs
+    running: Option<&'static str>,
+}
+
+impl IssueLoader {
+    pub(super) fn new() -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            tx,
+            rx,
+            latest: 0,
+            running: None,
+        }
+    }
+
+    pub(super) fn start(
+        &mut self,
+        label: &'static str,
+        work: impl FnOnce() -> IssueLoad + Send + 'static,
+    ) {
+        self.latest += 1;
+        self.running = Some(label);
+        let request = self.latest;
+        let tx = self.tx.clone();
+        thread::spawn(move || {
+            let _ = tx.send(IssueLoadResult {
+                request,
+                load: work(),
+            });
+        });
+    }
+}
+
+impl NewSessionDialog {
+    /// What the issue picker is waiting on, e.g. "Loading issues".
+    pub(super) fn issue_loading(&self) -> Option<&'static str> {
+        self.issue_loader.running
+    }
+
+    /// Start loading open issues for the repository at the path field; the
+    /// picker opens once they arrive.
+    pub(super) fn open_issue_picker(&mut self) {
+        let path = PathBuf::from(super::expand_tilde(self.path.value().trim()));
+        self.error_message = None;
+        self.issue_loader.start("Loading issues", move || {
+            IssueLoad::List(issue::list_open_issues(&path).map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Start fetching the picked issue; the title is pre-filled from it once
+    /// it arrives.
+    pub(super) fn select_issue(&mut self, label: &str) {
+        let Some(reference) = self
+            .issue_summaries
+            .iter()
+            .find(|s| s.label() == label)
+            .and_then(|s| IssueRef::parse(&s.url))
+        else {
+            return;
+        };
+        self.issue_loader.start("Fetching issue", move || {
+            IssueLoad::Fetch(issue::fetch_issue(&reference).map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Apply a finished issue request. Returns true when the dialog changed.
+    pub(super) fn tick_issue_loading(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok(result) = self.issue_loader.rx.try_recv() {
+            if result.request != self.issue_loader.latest {
+                continue;
+            }
+            self.issue_loader.running = None;
+            self.apply_issue_load(result.load);
+            changed = true;
+        }
+        changed
+    }
+
+    fn apply_issue_load(&mut self, load: IssueLoad) {
+        match load {
+            IssueLoad::List(Ok(summaries)) if summaries.is_empty() => {
+                self.error_message = Some("No open issues found for this repository".to_string());
+            }
+            IssueLoad::List(Ok(summaries)) => {
+                self.issue_picker
+                    .activate(summaries.iter().map(|s| s.label()).collect());
+                self.issue_summaries = summaries;
+            }
+            IssueLoad::List(Err(e)) => {
+                self.error_message = Some(format!("Failed to list issues: {}", e));
+            }
+            IssueLoad::Fetch(Ok(fetched)) => {
+                self.title = Input::new(fetched.session_title());
+                self.issue = Some(fetched);
+                self.error_message = None;
+            }
+            IssueLoad::Fetch(Err(e)) => {
+                self.error_message = Some(format!("Failed to fetch issue: {}", e));
+            }
+        }
+    }
+}
@@ -1,7 +1,10 @@
 //! New session dialog
 
 mod group_input;
+mod issue_loader;
+mod path_cache;
 mod path_input;
+mod path_status;
 mod render;
 
 #[cfg(test)]
@@ -14,16 +17,24 @@ use tui_input::Input;
 
 use super::DialogResult;
 use crate::containers::{self, ContainerRuntimeInterface};
+use crate::git::diff::CommitSummary;
+use crate::git::issue::{Issue, IssueSummary};
+use crate::session::attachments::{self, Attachment};
+use crate::session::claude_history::{self, ClaudeConversation};
 use crate::session::config::{DefaultTerminalMode, SandboxConfig};
 use crate::session::repo_config::HookProgress;
 #[cfg(test)]
 use crate::session::Config;
-use crate::session::{civilizations, resolve_config};
+use crate::session::{civilizations, resolve_config, IssueInfo};
 use crate::tmux::AvailableTools;
 use crate::tui::components::{
     DirPicker, DirPickerResult, GroupGhostCompletion, ListPicker, ListPickerResult,
 };
+use issue_loader::IssueLoader;
+pub(super) use path_input::expand_tilde;
 use path_input::PathGhostCompletion;
+pub use path_status::ExistingSessionPath;
+use path_status::{PathStatus, PathValidator};
 
 pub(super) struct FieldHelp {
     pub(super) name: &'static str,
@@ -32,6 +43,25 @@ pub(super) struct FieldHelp {
 
 pub(super) const HELP_DIALOG_WIDTH: u16 = 85;
 
+/// How many commits the Ctrl+G picker offers
+const RECENT_COMMIT_LIMIT: usize = 20;
+
+/// Marks picker entries already added as context
+const PICKED_MARK: &str = "✓ ";
+
+/// Picker row for a Claude conversation, e.g. "2h ago  Fix login (3 prompts)"
+fn resume_label(conversation: &ClaudeConversation, now: chrono::DateTime<chrono::Utc>) -> String {
+    let age = crate::tui::format_age((now - conversation.modified).num_seconds().max(0));
+    let title: String = conversation.title.chars().take(50).collect();
+    format!(
+        "{:>4} ago  {} ({} prompt{})",
+        age,
+        title,
+        conversation.prompts,
+        if conversation.prompts == 1 { "" } else { "s" }
+    )
+}
+
 pub(super) const FIELD_HELP: &[FieldHelp] = &[
     FieldHelp {
         name: "Profile",
@@ -39,15 +69,15 @@ pub(super) const FIELD_HELP: &[FieldHelp] = &[
     },
     FieldHelp {
         name: "Title",
-        description: "Session name (auto-generates if empty)",
+        description: "Session name (auto-generates if empty, Ctrl+P to pick a GitHub issue)",
     },
     FieldHelp {
         name: "Path",
-        description: "Working directory for the session",
+        description: "Working directory (Ctrl+G: add commits, Ctrl+T: attach files/diff to prompt)",
     },
     FieldHelp {
         name: "Tool",
-        description: "Which AI tool to use (Ctrl+P to configure command and extra args)",
+        description: "Which AI tool to use (Ctrl+P: command and args, Ctrl+R: resume Claude)",
     },
     FieldHelp {
         name: "YOLO Mode",
@@ -56,8 +86,7 @@ pub(super) const FIELD_HELP: &[FieldHelp] = &[
     },
     FieldHelp {
         name: "Worktree",
-        description:
-            "Branch name for git worktree (Ctrl+P to configure branch mode and extra repos)",
+        description: "Branch name for git worktree (Ctrl+P: branch mode, base ref and extra repos)",
     },
     FieldHelp {
         name: "Sandbox",
@@ -86,6 +115,8 @@ pub struct NewSessionData {
     pub tool: String,
     pub worktree_branch: Option<String>,
     pub create_new_branch: bool,
+    /// Ref the new branch starts from (picked in the worktree options)
+    pub base_ref: Option<String>,
     pub extra_repo_paths: Vec<String>,
     pub sandbox: bool,
     /// The sandbox image to use (always populated from the input field).
@@ -98,6 +129,10 @@ pub struct NewSessionData {
     pub extra_args: String,
     /// Command override for the agent binary (replaces the default binary)
     pub command_override: String,
+    /// Issue picked from the tracker (Ctrl+P on the title field)
+    pub issue_info: Option<IssueInfo>,
+    /// Prompt passed to the agent on first launch
+    pub initial_prompt: Option<String>,
 }
 
 /// Spinner frames for loading animation
@@ -116,6 +151,8 @@ pub struct NewSessionDialog {
     pub(super) existing_titles: Vec<String>,
     pub(super) worktree_branch: Input,
     pub(super) create_new_branch: bool,
+    /// Ref a new worktree branch starts from; the repo's default branch when unset
+    pub(super) base_ref: Option<String>,
     pub(super) sandbox_enabled: bool,
     pub(super) sandbox_image: Input,
     pub(super) docker_available: bool,
@@ -164,6 +201,29 @@ pub struct NewSessionDialog {
     pub(super) existing_groups: Vec<String>,
     pub(super) group_picker: ListPicker,
     pub(super) branch_picker: ListPicker,
+    pub(super) base_ref_picker: ListPicker,
+    pub(super) issue_picker: ListPicker,
+    /// Open issues offered by the issue picker
+    pub(super) issue_summaries: Vec<IssueSummary>,
+    /// Issue linked to the new session, fetched after picking from the list
+    pub(super) issue: Option<Issue>,
+    issue_loader: IssueLoader,
+    pub(super) commit_picker: ListPicker,
+    /// Recent commits offered by the commit picker (Ctrl+G)
+    pub(super) recent_commits: Vec<CommitSummary>,
+    /// Commits added to the first prompt as context, in the order picked
+    pub(super) context_commits: Vec<CommitSummary>,
+    pub(super) attachment_picker: ListPicker,
+    /// Files and diffs added to the first prompt (Ctrl+T), in the order picked
+    pub(super) attachments: Vec<Attachment>,
+    pub(super) resume_picker: ListPicker,
+    /// Claude Code conversations offered by the resume picker (Ctrl+R)
+    pub(super) claude_conversations: Vec<ClaudeConversation>,
+    /// Conversation the new Claude session resumes
+    pub(super) resume_conversation: Option<ClaudeConversation>,
+    /// Number of earlier Claude conversations in the directory at the path
+    /// field, and the path it was counted for
+    pub(super) resume_candidates: Option<(String, usize)>,
     pub(super) dir_picker: DirPicker,
     pub(super) error_message: Option<String>,
     pub(super) show_help: bool,
@@ -179,6 +239,11 @@ pub struct NewSessionDialog {
     pub(super) hook_output: Vec<String>,
     /// Temporary highlight state for invalid path input.
     pub(super) path_invalid_flash_until: Option<Instant>,
+    /// Sessions that already exist, for the duplicate path warning.
+    existing_sessions: Vec<ExistingSessionPath>,
+    /// Latest result of the background path check and the input it was for.
+    path_status: Option<(String, PathStatus)>,
+    path_validator: PathValidator,
     /// Ghost text completion for the path field (fish-shell style).
     path_ghost: Option<PathGhostCompletion>,
     /// Ghost text completion for the group field (fish-shell style).
@@ -378,9 +443,24 @@ impl NewSessionDialog {
             existing_groups,
             group_picker: ListPicker::new("Select Group"),
             branch_picker: ListPicker::new("Select Branch"),
+            base_ref_picker: ListPicker::new("Select Base"),
+            issue_picker: ListPicker::new("Select Issue"),
+            issue_summaries: Vec::new(),
+            issue: None,
+            issue_loader: IssueLoader::new(),
+            commit_picker: ListPicker::new("Continue From Commit"),
+            recent_commits: Vec::new(),
+            context_commits: Vec::new(),
+            attachment_picker: ListPicker::new("Attach To Prompt"),
+            attachments: Vec::new(),
+            resume_picker: ListPicker::new("Resume Claude Conversation"),
+            claude_conversations: Vec::new(),
+            resume_conversation: None,
+            resume_candidates: None,
             dir_picker: DirPicker::new(),
             worktree_branch: Input::default(),
             create_new_branch: true,
+            base_ref: None,
             workspace_repos: Vec::new(),
             workspace_repos_expanded: false,
             workspace_repo_selected_index: 0,
@@ -417,6 +497,9 @@ impl NewSessionDialog {
             current_hook: None,
             hook_output: Vec::new(),
             path_invalid_flash_until: None,
+            existing_sessions: Vec::new(),
+            path_status: None,
+            path_validator: PathValidator::new(),
             path_ghost: None,
             group_ghost: None,
             confirm_create_dir: None,
@@ -495,6 +578,18 @@ impl NewSessionDialog {
             }
         }
 
+        if self.tick_issue_loading() {
+            changed = true;
+        }
+
+        if !self.loading && self.tick_path_validation() {
+            changed = true;
+        }
+
+        if !self.loading && self.refresh_resume_candidates() {
+            changed = true;
+        }
+
         changed
     }
 
@@ -514,6 +609,15 @@ impl NewSessionDialog {
             .is_some_and(|y| matches!(y, crate::agents::YoloMode::AlwaysYolo))
     }
 
+    /// The field index of the title field (shifts based on whether profile picker is visible)
+    fn title_field(&self) -> usize {
+        if self.has_profile_selection() {
+            1
+        } else {
+            0
+        }
+    }
+
     /// The field index of the path field (shifts based on whether profile picker is visible)
     fn path_field(&self) -> usize {
         if self.has_profile_selection() {
@@ -616,9 +720,24 @@ impl NewSessionDialog {
             existing_groups: Vec::new(),
             group_picker: ListPicker::new("Select Group"),
             branch_picker: ListPicker::new("Select Branch"),
+            base_ref_picker: ListPicker::new("Select Base"),
+            issue_picker: ListPicker::new("Select Issue"),
+            issue_summaries: Vec::new(),
+            issue: None,
+            issue_loader: IssueLoader::new(),
+            commit_picker: ListPicker::new("Continue From Commit"),
+            recent_commits: Vec::new(),
+            context_commits: Vec::new(),
+            attachment_picker: ListPicker::new("Attach To Prompt"),
+            attachments: Vec::new(),
+            resume_picker: ListPicker::new("Resume Claude Conversation"),
+            claude_conversations: Vec::new(),
+            resume_conversation: None,
+            resume_candidates: None,
             dir_picker: DirPicker::new(),
             worktree_branch: Input::default(),
             create_new_branch: true,
+            base_ref: None,
             workspace_repos: Vec::new(),
             workspace_repos_expanded: false,
             workspace_repo_selected_index: 0,
@@ -655,6 +774,9 @@ impl NewSessionDialog {
             current_hook: None,
             hook_output: Vec::new(),
             path_invalid_flash_until: None,
+            existing_sessions: Vec::new(),
+            path_status: None,
+            path_validator: PathValidator::new(),
             path_ghost: None,
             group_ghost: None,
             confirm_create_dir: None,
@@ -677,9 +799,24 @@ impl NewSessionDialog {
             existing_groups: Vec::new(),
             group_picker: ListPicker::new("Select Group"),
             branch_picker: ListPicker::new("Select Branch"),
+            base_ref_picker: ListPicker::new("Select Base"),
+            issue_picker: ListPicker::new("Select Issue"),
+            issue_summaries: Vec::new(),
+            issue: None,
+            issue_loader: IssueLoader::new(),
+            commit_picker: ListPicker::new("Continue From Commit"),
+            recent_commits: Vec::new(),
+            context_commits: Vec::new(),
+            attachment_picker: ListPicker::new("Attach To Prompt"),
+            attachments: Vec::new(),
+            resume_picker: ListPicker::new("Resume Claude Conversation"),
+            claude_conversations: Vec::new(),
+            resume_conversation: None,
+            resume_candidates: None,
             dir_picker: DirPicker::new(),
             worktree_branch: Input::default(),
             create_new_branch: true,
+            base_ref: None,
             workspace_repos: Vec::new(),
             workspace_repos_expanded: false,
             workspace_repo_selected_index: 0,
@@ -716,6 +853,9 @@ impl NewSessionDialog {
             current_hook: None,
             hook_output: Vec::new(),
             path_invalid_flash_until: None,
+            existing_sessions: Vec::new(),
+            path_status: None,
+            path_validator: PathValidator::new(),
             path_ghost: None,
             group_ghost: None,
             confirm_create_dir: None,
@@ -753,6 +893,21 @@ impl NewSessionDialog {
             return self.handle_tool_config_key(key);
         }
 
+        // Pickers opened from the worktree config overlay
+        if self.branch_picker.is_active() {
+            if let ListPickerResult::Selected(value) = self.branch_picker.handle_key(key) {
+                self.worktree_branch = Input::new(value);
+            }
+            return DialogResult::Continue;
+        }
+
+        if self.base_ref_picker.is_active() {
+            if let ListPickerResult::Selected(value) = self.base_ref_picker.handle_key(key) {
+                self.base_ref = Some(value);
+            }
+            return DialogResult::Continue;
+        }
+
         // Delegate to worktree config mode handler when active
         if self.worktree_config_mode {
             return self.handle_worktree_config_key(key);
@@ -770,9 +925,30 @@ impl NewSessionDialog {
             return DialogResult::Continue;
         }
 
-        if self.branch_picker.is_active() {
-            if let ListPickerResult::Selected(value) = self.branch_picker.handle_key(key) {
-                self.worktree_branch = Input::new(value);
+        if self.issue_picker.is_active() {
+            if let ListPickerResult::Selected(value) = self.issue_picker.handle_key(key) {
+                self.select_issue(&value);
+            }
+            return DialogResult::Continue;
+        }
+
+        if self.commit_picker.is_active() {
+            if let ListPickerResult::Selected(value) = self.commit_picker.handle_key(key) {
+                self.toggle_context_commit(&value);
+            }
+            return DialogResult::Continue;
+        }
+
+        if self.attachment_picker.is_active() {
+            if let ListPickerResult::Selected(value) = self.attachment_picker.handle_key(key) {
+                self.toggle_attachment(&value);
+            }
+            return DialogResult::Continue;
+        }
+
+        if self.resume_picker.is_active() {
+            if let ListPickerResult::Selected(value) = self.resume_picker.handle_key(key) {
+                self.toggle_resume_conversation(&value);
             }
             return DialogResult::Continue;
         }
@@ -838,8 +1014,30 @@ impl NewSessionDialog {
         fi += 1;
         let max_field = fi;
 
+        // Ctrl+G picks recent commits to hand the agent as context
+        if key.code == KeyCode::Char('g') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.open_commit_picker();
+            return DialogResult::Continue;
+        }
+
+        // Ctrl+T attaches files or the uncommitted diff to the prompt
+        if key.code == KeyCode::Char('t') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.open_attachment_picker();
+            return DialogResult::Continue;
+        }
+
+        // Ctrl+R resumes a Claude Code conversation started in this directory
+        if key.code == KeyCode::Char('r') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.open_resume_picker();
+            return DialogResult::Continue;
+        }
+
         // Ctrl+P opens a context-sensitive picker/config overlay
         if key.code == KeyCode::Char('p') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            if self.focused_field == self.title_field() {
+                self.open_issue_picker();
+                return DialogResult::Continue;
+            }
             if self.focused_field == self.path_field() {
                 let path_value = self.path.value().trim().to_string();
                 self.dir_picker.activate(&path_value);
@@ -885,9 +1083,17 @@ impl NewSessionDialog {
             }
             KeyCode::Enter => {
                 self.error_message = None;
+                if let Some((status, false)) = self.path_status() {
+                    if status.level == path_status::PathLevel::Error {
+                        self.error_message = Some(status.message.clone());
+                        return DialogResult::Continue;
+                    }
+                }
                 let path_str = self.path.value().trim().to_string();
                 let resolved = path_input::expand_tilde(&path_str);
-                if !std::path::Path::new(&resolved).exists() {
+                if !crate::git::clone::is_clone_url(&path_str)
+                    && !std::path::Path::new(&resolved).exists()
+                {
                     self.confirm_create_dir = Some(false);
                     return DialogResult::Continue;
                 }
@@ -1116,10 +1322,11 @@ impl NewSessionDialog {
 
     /// Handle key events when in worktree configuration mode.
     fn handle_worktree_config_key(&mut self, key: KeyEvent) -> DialogResult<NewSessionData> {
-        // Worktree config fields: 0=new_branch checkbox, 1=extra_repos list
+        // Worktree config fields: 0=new_branch checkbox, 1=base ref, 2=extra_repos list
         const WT_NEW_BRANCH: usize = 0;
-        const WT_EXTRA_REPOS: usize = 1;
-        const WT_MAX: usize = 2;
+        const WT_BASE: usize = 1;
+        const WT_EXTRA_REPOS: usize = 2;
+        const WT_MAX: usize = 3;
 
         // Handle workspace repos list editing when expanded
         if self.workspace_repos_expanded && self.worktree_config_focused_field == WT_EXTRA_REPOS {
@@ -1148,6 +1355,24 @@ impl NewSessionDialog {
                 }
                 DialogResult::Continue
             }
+            // Enter or Ctrl+P on the base field opens the ref picker
+            KeyCode::Char('p')
+                if key.modifiers.contains(KeyModifiers::CONTROL)
+                    && self.worktree_config_focused_field == WT_BASE =>
+            {
+                self.open_base_ref_picker();
+                DialogResult::Continue
+            }
+            KeyCode::Enter if self.worktree_config_focused_field == WT_BASE => {
+                self.open_base_ref_picker();
+                DialogResult::Continue
+            }
+            KeyCode::Backspace | KeyCode::Delete
+                if self.worktree_config_focused_field == WT_BASE =>
+            {
+                self.base_ref = None;
+                DialogResult::Continue
+            }
             KeyCode::Enter if self.worktree_config_focused_field == WT_EXTRA_REPOS => {
                 self.workspace_repos_expanded = true;
                 self.workspace_repo_selected_index = 0;
@@ -1180,6 +1405,38 @@ impl NewSessionDialog {
         }
     }
 
+    /// Whether the base ref applies: a new branch in a single repository.
+    /// Multi-repo workspaces branch each repository from its own HEAD.
+    pub(super) fn base_ref_applies(&self) -> bool {
+        self.create_new_branch && self.workspace_repos.is_empty()
+    }
+
+    /// The repository's default branch, which new branches start from unless
+    /// another base is picked.
+    pub(super) fn default_base_ref(&self) -> Option<String> {
+        crate::git::diff::get_default_branch(std::path::Path::new(self.path.value().trim())).ok()
+    }
+
+    /// Offer the repository's branches, remote branches and tags, the
+    /// default branch first.
+    fn open_base_ref_picker(&mut self) {
+        if !self.base_ref_applies() {
+            return;
+        }
+        let path = std::path::Path::new(self.path.value().trim());
+        match crate::git::diff::list_refs(path) {
+            Ok(mut refs) if !refs.is_empty() => {
+                if let Some(default) = self.default_base_ref() {
+                    refs.retain(|r| *r != default);
+                    refs.insert(0, default);
+                }
+                self.base_ref_picker.activate(refs);
+            }
+            Ok(_) => self.error_message = Some("No branches or tags found".to_string()),
+            Err(e) => self.error_message = Some(format!("Could not list refs: {}", e)),
+        }
+    }
+
     /// Handle key events when the env list is expanded
     fn handle_env_list_key(&mut self, key: KeyEvent) -> DialogResult<NewSessionData> {
         let validate =
@@ -1369,7 +1626,7 @@ impl NewSessionDialog {
         let group_field = next;
 
         let path_field = self.path_field();
-        let title_field = if self.has_profile_selection() { 1 } else { 0 };
+        let title_field = self.title_field();
         match self.focused_field {
             n if n == title_field => &mut self.title,
             n if n == path_field => &mut self.path,
@@ -1379,7 +1636,191 @@ impl NewSessionDialog {
         }
     }
 
-    fn build_submit_result(&self) -> DialogResult<NewSessionData> {
+    /// Load recent commits for the repository at the path field and show the
+    /// picker. Commits already added are marked; picking one again removes it.
+    fn open_commit_picker(&mut self) {
+        let path = path_input::expand_tilde(self.path.value().trim());
+        match crate::git::diff::recent_commits(std::path::Path::new(&path), RECENT_COMMIT_LIMIT) {
+            Ok(commits) if commits.is_empty() => {
+                self.error_message = Some("No commits found for this repository".to_string());
+            }
+            Ok(commits) => {
+                let labels = commits
+                    .iter()
+                    .map(|c| {
+                        if self.context_commits.contains(c) {
+                            format!("{}{}", PICKED_MARK, c.label())
+                        } else {
+                            c.label()
+                        }
+                    })
+                    .collect();
+                self.commit_picker.activate(labels);
+                self.recent_commits = commits;
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to read git log: {}", e));
+            }
+        }
+    }
+
+    fn toggle_context_commit(&mut self, label: &str) {
+        let label = label.strip_prefix(PICKED_MARK).unwrap_or(label);
+        let Some(commit) = self.recent_commits.iter().find(|c| c.label() == label) else {
+            return;
+        };
+        if let Some(pos) = self.context_commits.iter().position(|c| c == commit) {
+            self.context_commits.remove(pos);
+        } else {
+            self.context_commits.push(commit.clone());
+        }
+    }
+
+    pub(super) fn selected_tool_is_claude(&self) -> bool {
+        self.available_tools[self.tool_index] == "claude"
+    }
+
+    /// Recount the Claude conversations for the path field once it changes,
+    /// dropping a conversation picked for the previous path. Returns true if
+    /// anything shown changed.
+    fn refresh_resume_candidates(&mut self) -> bool {
+        let path = path_input::expand_tilde(self.path.value().trim());
+        if self
+            .resume_candidates
+            .as_ref()
+            .is_some_and(|(counted, _)| *counted == path)
+        {
+            return false;
+        }
+        let count = if path.is_empty() {
+            0
+        } else {
+            claude_history::count(std::path::Path::new(&path))
+        };
+        let previous = self.resume_candidates.replace((path, count));
+        let dropped = previous.is_some() && self.resume_conversation.take().is_some();
+        dropped || previous.map(|(_, n)| n) != Some(count)
+    }
+
+    /// Offer the Claude Code conversations recorded for the path field. The
+    /// one already picked is marked; picking it again starts fresh instead.
+    fn open_resume_picker(&mut self) {
+        if !self.selected_tool_is_claude() {
+            self.error_message = Some("Only Claude Code conversations can be resumed".to_string());
+            return;
+        }
+        let path = path_input::expand_tilde(self.path.value().trim());
+        let conversations = claude_history::list(std::path::Path::new(&path));
+        if conversations.is_empty() {
+            self.error_message =
+                Some("No earlier Claude conversations in this directory".to_string());
+            return;
+        }
+        let now = chrono::Utc::now();
+        let labels = conversations
+            .iter()
+            .map(|c| {
+                let label = resume_label(c, now);
+                if self
+                    .resume_conversation
+                    .as_ref()
+                    .is_some_and(|r| r.id == c.id)
+                {
+                    format!("{}{}", PICKED_MARK, label)
+                } else {
+                    label
+                }
+            })
+            .collect();
+        self.resume_picker.activate(labels);
+        self.claude_conversations = conversations;
+    }
+
+    fn toggle_resume_conversation(&mut self, label: &str) {
+        let label = label.strip_prefix(PICKED_MARK).unwrap_or(label);
+        let now = chrono::Utc::now();
+        let Some(conversation) = self
+            .claude_conversations
+            .iter()
+            .find(|c| resume_label(c, now) == label)
+        else {
+            return;
+        };
+        if self
+            .resume_conversation
+            .as_ref()
+            .is_some_and(|r| r.id == conversation.id)
+        {
+            self.resume_conversation = None;
+        } else {
+            self.resume_conversation = Some(conversation.clone());
+        }
+    }
+
+    /// Offer the uncommitted diff and the repository's files at the path
+    /// field. Attachments already added are marked; picking one again
+    /// removes it.
+    fn open_attachment_picker(&mut self) {
+        let path = path_input::expand_tilde(self.path.value().trim());
+        match attachments::list_files(std::path::Path::new(&path)) {
+            Ok(files) => {
+                let labels = std::iter::once(Attachment::GitDiff)
+                    .chain(files.into_iter().map(Attachment::File))
+                    .map(|a| {
+                        if self.attachments.contains(&a) {
+                            format!("{}{}", PICKED_MARK, a.label())
+                        } else {
+                            a.label()
+                        }
+                    })
+                    .collect();
+                self.attachment_picker.activate(labels);
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to list files: {}", e));
+            }
+        }
+    }
+
+    fn toggle_attachment(&mut self, label: &str) {
+        let label = label.strip_prefix(PICKED_MARK).unwrap_or(label);
+        let attachment = if label == Attachment::GitDiff.label() {
+            Attachment::GitDiff
+        } else {
+            Attachment::File(label.to_string())
+        };
+        if let Some(pos) = self.attachments.iter().position(|a| *a == attachment) {
+            self.attachments.remove(pos);
+        } else {
+            self.attachments.push(attachment);
+        }
+    }
+
+    /// The first prompt: the linked issue, then any picked commits, then
+    /// attachments.
+    fn initial_prompt(&self) -> anyhow::Result<Option<String>> {
+        let issue = self.issue.as_ref().map(|i| i.to_prompt());
+        let commits = commit_context_prompt(&self.context_commits);
+        let path = path_input::expand_tilde(self.path.value().trim());
+        let file_reference = crate::agents::get_agent(self.available_tools[self.tool_index])
+            .and_then(|a| a.file_reference);
+        let attached = attachments::render(
+            &self.attachments,
+            std::path::Path::new(&path),
+            file_reference,
+        )?;
+        let parts: Vec<String> = [issue, commits, attached].into_iter().flatten().collect();
+        Ok((!parts.is_empty()).then(|| parts.join("\n\n")))
+    }
+
+    fn build_submit_result(&mut self) -> DialogResult<NewSessionData> {
+        let initial_prompt = match self.initial_prompt() {
+            Ok(prompt) => prompt,
+            Err(e) => {
+                self.error_message = Some(format!("{:#}", e));
+                return DialogResult::Continue;
+            }
+        };
         let title_value = self.title.value().trim();
         let final_title = if title_value.is_empty() {
             let refs: Vec<&str> = self.existing_titles.iter().map(|s| s.as_str()).collect();
@@ -1402,6 +1843,11 @@ impl NewSessionDialog {
             tool: self.available_tools[self.tool_index].to_string(),
             worktree_branch,
             create_new_branch: self.create_new_branch,
+            base_ref: if has_worktree_branch && self.base_ref_applies() {
+                self.base_ref.clone().or_else(|| self.default_base_ref())
+            } else {
+                None
+            },
             extra_repo_paths: if has_worktree_branch {
                 self.workspace_repos.clone()
             } else {
@@ -1415,11 +1861,32 @@ impl NewSessionDialog {
             } else {
                 Vec::new()
             },
-            extra_args: self.extra_args.value().trim().to_string(),
+            extra_args: self.submitted_extra_args(),
             command_override: self.command_override.value().trim().to_string(),
+            issue_info: self.issue.as_ref().map(|i| IssueInfo {
+                provider: i.reference.provider,
+                url: i.url.clone(),
+                number: i.reference.number,
+                title: i.title.clone(),
+            }),
+            initial_prompt,
         })
     }
 
+    /// Extra args from the tool config, plus `--resume` when a Claude
+    /// conversation was picked.
+    fn submitted_extra_args(&self) -> String {
+        let extra_args = self.extra_args.value().trim();
+        match &self.resume_conversation {
+            Some(conversation) if self.selected_tool_is_claude() => {
+                format!("{} --resume {}", extra_args, conversation.id)
+                    .trim()
+                    .to_string()
+            }
+            _ => extra_args.to_string(),
+        }
+    }
+
     fn handle_confirm_create_dir_key(&mut self, key: KeyEvent) -> DialogResult<NewSessionData> {
         let selected = self.confirm_create_dir.as_mut().unwrap();
         match key.code {
@@ -1471,3 +1938,22 @@ impl NewSessionDialog {
         }
     }
 }
+
+/// Prompt text asking the agent to pick up from `commits`, or None if there
+/// are none.
+pub(super) fn commit_context_prompt(commits: &[CommitSummary]) -> Option<String> {
+    match commits {
+        [] => None,
+        [commit] => Some(format!(
+            "Continue work from commit {} (\"{}\").",
+            commit.short_id, commit.subject
+        )),
+        _ => {
+            let mut prompt = "Continue work from these commits:".to_string();
+            for commit in commits {
+                prompt.push_str(&format!("\n- {} {}", commit.short_id, commit.subject));
+            }
+            Some(prompt)
+        }
+    }
+}
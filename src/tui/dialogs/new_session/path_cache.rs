@@ -0,0 +1,215 @@
+//! Cached directory listings for path completion.
+//!
+//! The ghost completion is recomputed on every keystroke, and listing a
+//! directory on NFS or SSHFS can take long enough to stall typing. Listings
+//! are kept for a short while per directory: a recent one is used as is, an
+//! older one is used while a worker thread refreshes it, and only a
+//! directory never seen (or not seen for a long time) is read in place.
+//! After each completion the directories the user is likely to descend into
+//! next are read ahead on a worker thread.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a listing is used without checking the directory again.
+const LISTING_FRESH: Duration = Duration::from_secs(2);
+
+/// How long a listing may be shown while it is refreshed in the background.
+/// Older ones are read again before use.
+const LISTING_MAX_AGE: Duration = Duration::from_secs(30);
+
+/// How many completion candidates are read ahead.
+const PREFETCH_LIMIT: usize = 8;
+
+/// The subdirectories of a directory, and the completion ignores of the
+/// repo it is in.
+#[derive(Debug, Default)]
+pub(super) struct Listing {
+    /// Names of subdirectories, hidden ones included, sorted
+    pub(super) dirs: Vec<String>,
+    pub(super) ignores: Vec<String>,
+}
+
+struct Entry {
+    listing: Arc<Listing>,
+    read_at: Instant,
+}
+
+#[derive(Default)]
+struct Cache {
+    entries: HashMap<PathBuf, Entry>,
+    /// Directories a worker thread is reading right now
+    in_flight: HashSet<PathBuf>,
+}
+
+fn cache() -> &'static Mutex<Cache> {
+    static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+    CACHE.get_or_init(Mutex::default)
+}
+
+/// The listing of `dir`, or None if it cannot be read.
+pub(super) fn listing(dir: &Path) -> Option<Arc<Listing>> {
+    let cached = {
+        let cache = cache().lock().unwrap();
+        cache
+            .entries
+            .get(dir)
+            .map(|entry| (entry.listing.clone(), entry.read_at.elapsed()))
+    };
+    match cached {
+        Some((listing, age)) if age < LISTING_FRESH => Some(listing),
+        Some((listing, age)) if age < LISTING_MAX_AGE => {
+            refresh_in_background(vec![dir.to_path_buf()]);
+            Some(listing)
+        }
+        _ => read_and_store(dir),
+    }
+}
+
+/// Read the listings of `dirs` ahead of time on a worker thread, skipping
+/// ones that are still fresh.
+pub(super) fn prefetch(dirs: impl IntoIterator<Item = PathBuf>) {
+    let dirs: Vec<PathBuf> = {
+        let cache = cache().lock().unwrap();
+        dirs.into_iter()
+            .filter(|dir| {
+                cache
+                    .entries
+                    .get(dir)
+                    .map_or(true, |entry| entry.read_at.elapsed() >= LISTING_FRESH)
+            })
+            .take(PREFETCH_LIMIT)
+            .collect()
+    };
+    refresh_in_background(dirs);
+}
+
+fn refresh_in_background(dirs: Vec<PathBuf>) {
+    let dirs: Vec<PathBuf> = {
+        let mut cache = cache().lock().unwrap();
+        dirs.into_iter()
+            .filter(|dir| cache.in_flight.insert(dir.clone()))
+            .collect()
+    };
+    if dirs.is_empty() {
+        return;
+    }
+    thread::spawn(move || {
+        for dir in dirs {
+            if read_and_store(&dir).is_none() {
+                cache().lock().unwrap().entries.remove(&dir);
+            }
+            cache().lock().unwrap().in_flight.remove(&dir);
+        }
+    });
+}
+
+fn read_and_store(dir: &Path) -> Option<Arc<Listing>> {
+    let listing = Arc::new(read_listing(dir)?);
+    let mut cache = cache().lock().unwrap();
+    cache
+        .entries
+        .retain(|_, entry| entry.read_at.elapsed() < LISTING_MAX_AGE);
+    cache.entries.insert(
+        dir.to_path_buf(),
+        Entry {
+            listing: listing.clone(),
+            read_at: Instant::now(),
+        },
+    );
+    Some(listing)
+}
+
+fn read_listing(dir: &Path) -> Option<Listing> {
+    let mut dirs: Vec<String> = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect();
+    dirs.sort();
+    Some(Listing {
+        dirs,
+        ignores: completion_ignores(dir),
+    })
+}
+
+/// `completion_ignores` of the repo `dir` is in, found by walking up to the
+/// nearest `.aoe/config.toml`.
+fn completion_ignores(dir: &Path) -> Vec<String> {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    dir.ancestors()
+        .find(|d| {
+            d.join(crate::session::repo_config::REPO_CONFIG_PATH)
+                .is_file()
+        })
+        .and_then(|root| {
+            crate::session::repo_config::load_repo_config(root)
+                .ok()
+                .flatten()
+        })
+        .map(|config| config.completion_ignores)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn age(dir: &Path, by: Duration) {
+        let mut cache = cache().lock().unwrap();
+        let entry = cache.entries.get_mut(dir).unwrap();
+        entry.read_at -= by;
+    }
+
+    fn wait_for_refresh(dir: &Path) {
+        for _ in 0..100 {
+            if !cache().lock().unwrap().in_flight.contains(dir) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("background refresh of {} never finished", dir.display());
+    }
+
+    #[test]
+    fn test_listing_is_reused_then_refreshed_in_background() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        std::fs::create_dir(dir.join("alpha")).unwrap();
+        assert_eq!(listing(dir).unwrap().dirs, vec!["alpha"]);
+
+        // A fresh listing does not look at the directory again
+        std::fs::create_dir(dir.join("beta")).unwrap();
+        assert_eq!(listing(dir).unwrap().dirs, vec!["alpha"]);
+
+        // An older one is still shown, but refreshed for the next keystroke
+        age(dir, LISTING_FRESH);
+        assert_eq!(listing(dir).unwrap().dirs, vec!["alpha"]);
+        wait_for_refresh(dir);
+        assert_eq!(listing(dir).unwrap().dirs, vec!["alpha", "beta"]);
+
+        // A very old one is read again before use
+        std::fs::create_dir(dir.join("gamma")).unwrap();
+        age(dir, LISTING_MAX_AGE);
+        assert_eq!(listing(dir).unwrap().dirs, vec!["alpha", "beta", "gamma"]);
+    }
+
+    #[test]
+    fn test_prefetch_fills_cache_for_candidates() {
+        let tmp = tempfile::tempdir().unwrap();
+        let inner = tmp.path().join("inner");
+        std::fs::create_dir_all(inner.join("deep")).unwrap();
+
+        prefetch(vec![inner.clone(), tmp.path().join("missing")]);
+        wait_for_refresh(&inner);
+        wait_for_refresh(&tmp.path().join("missing"));
+
+        let cache = cache().lock().unwrap();
+        assert_eq!(cache.entries[&inner].listing.dirs, vec!["deep"]);
+        assert!(!cache.entries.contains_key(&tmp.path().join("missing")));
+    }
+}
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
+use super::path_cache;
 use super::NewSessionDialog;
 use crate::tui::components::longest_common_prefix;
 
@@ -39,7 +40,7 @@ pub(super) fn collapse_tilde(path: &str) -> String {
 }
 
 /// Expand a leading `~` to the user's home directory.
-pub(super) fn expand_tilde(path: &str) -> String {
+pub(crate) fn expand_tilde(path: &str) -> String {
     if path == "~" {
         if let Some(home) = dirs::home_dir() {
             return home.to_string_lossy().to_string();
@@ -91,29 +92,22 @@ pub(super) fn compute_path_ghost(input: &Input) -> Option<PathGhostCompletion> {
 
     let base_dir = path_completion_base(parent_prefix)?;
     let include_hidden = current_segment.starts_with('.');
-    let mut matches = Vec::new();
-    let entries = std::fs::read_dir(&base_dir).ok()?;
-
-    for entry in entries.flatten() {
-        if !entry.path().is_dir() {
-            continue;
-        }
-        let file_name = entry.file_name();
-        let Some(name) = file_name.to_str() else {
-            continue;
-        };
-        if !include_hidden && name.starts_with('.') {
-            continue;
-        }
-        if name.starts_with(current_segment) {
-            matches.push(name.to_string());
-        }
-    }
+    let listing = path_cache::listing(&base_dir)?;
+    let matches: Vec<String> = listing
+        .dirs
+        .iter()
+        .filter(|name| include_hidden || !name.starts_with('.'))
+        .filter(|name| !listing.ignores.contains(name))
+        .filter(|name| name.starts_with(current_segment))
+        .cloned()
+        .collect();
+
+    // The user is likely to descend into one of these next
+    path_cache::prefetch(matches.iter().map(|name| base_dir.join(name)));
 
     if matches.is_empty() {
         return None;
     }
-    matches.sort();
 
     let ghost_text = if matches.len() == 1 {
         let remainder = &matches[0][current_segment.len()..];
@@ -140,6 +134,29 @@ pub(super) fn compute_path_ghost(input: &Input) -> Option<PathGhostCompletion> {
     })
 }
 
+/// Start of the path segment before `cursor`, skipping slashes directly
+/// before it.
+fn previous_segment_start(chars: &[char], mut cursor: usize) -> usize {
+    while cursor > 0 && chars[cursor - 1] == '/' {
+        cursor -= 1;
+    }
+    while cursor > 0 && chars[cursor - 1] != '/' {
+        cursor -= 1;
+    }
+    cursor
+}
+
+/// End of the path segment after `cursor`, skipping slashes directly after it.
+fn next_segment_end(chars: &[char], mut cursor: usize) -> usize {
+    while cursor < chars.len() && chars[cursor] == '/' {
+        cursor += 1;
+    }
+    while cursor < chars.len() && chars[cursor] != '/' {
+        cursor += 1;
+    }
+    cursor
+}
+
 impl NewSessionDialog {
     pub(super) fn handle_path_shortcuts(&mut self, key: KeyEvent) -> bool {
         if self.focused_field != self.path_field() {
@@ -188,6 +205,33 @@ impl NewSessionDialog {
             return true;
         }
 
+        // Readline kills, path-aware: words are path segments
+        let chars: Vec<char> = self.path.value().chars().collect();
+        let cursor = self.path.visual_cursor().min(chars.len());
+        let kill = match key.code {
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some((previous_segment_start(&chars, cursor), cursor))
+            }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Some((cursor, next_segment_end(&chars, cursor)))
+            }
+            KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some((cursor, chars.len()))
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some((0, cursor))
+            }
+            _ => None,
+        };
+        if let Some((start, end)) = kill {
+            let value: String = chars[..start].iter().chain(&chars[end..]).collect();
+            self.set_path_value_with_cursor(value, start);
+            self.error_message = None;
+            self.path_invalid_flash_until = None;
+            self.recompute_path_ghost();
+            return true;
+        }
+
         false
     }
 
@@ -217,19 +261,8 @@ impl NewSessionDialog {
 
     fn move_path_cursor_to_previous_segment(&mut self) {
         let chars: Vec<char> = self.path.value().chars().collect();
-        let mut cursor = self.path.visual_cursor().min(chars.len());
-        if cursor == 0 {
-            return;
-        }
-
-        while cursor > 0 && chars[cursor - 1] == '/' {
-            cursor -= 1;
-        }
-        while cursor > 0 && chars[cursor - 1] != '/' {
-            cursor -= 1;
-        }
-
-        self.move_path_cursor_to(cursor);
+        let cursor = self.path.visual_cursor().min(chars.len());
+        self.move_path_cursor_to(previous_segment_start(&chars, cursor));
     }
 
     fn set_path_value_with_cursor(&mut self, value: String, cursor_char_idx: usize) {
@@ -0,0 +1,221 @@
+//! Background validation of the path field.
+//!
+//! Each edit to the path schedules a check (after a short debounce) that runs
+//! on a worker thread, so slow filesystems or large repositories never block
+//! typing. Results are keyed by the input they were computed for and dropped
+//! if the path changed in the meantime.
+
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::path_input::expand_tilde;
+use super::NewSessionDialog;
+use crate::git::GitWorktree;
+
+/// How long the path must stay unchanged before it is validated.
+const PATH_CHECK_DEBOUNCE: Duration = Duration::from_millis(150);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PathLevel {
+    Ok,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct PathStatus {
+    pub(super) level: PathLevel,
+    pub(super) message: String,
+}
+
+impl PathStatus {
+    fn new(level: PathLevel, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            message: message.into(),
+        }
+    }
+}
+
+/// A session that already uses a project path, for duplicate detection.
+#[derive(Debug, Clone)]
+pub struct ExistingSessionPath {
+    pub title: String,
+    pub path: String,
+}
+
+struct PathCheckResult {
+    input: String,
+    status: Option<PathStatus>,
+}
+
+pub(super) struct PathValidator {
+    tx: Sender<PathCheckResult>,
+    rx: Receiver<PathCheckResult>,
+    /// Input value and git requirement of the most recently requested check.
+    requested: Option<(String, bool)>,
+    /// Unchecked input and when it was last edited, for debouncing.
+    pending: Option<(String, Instant)>,
+}
+
+impl PathValidator {
+    pub(super) fn new() -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            tx,
+            rx,
+            requested: None,
+            pending: None,
+        }
+    }
+}
+
+/// Validate a typed path. Returns None for an empty input.
+pub(super) fn check_path(
+    input: &str,
+    needs_git: bool,
+    existing: &[ExistingSessionPath],
+) -> Option<PathStatus> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    if crate::git::clone::is_clone_url(input) {
+        return Some(PathStatus::new(
+            PathLevel::Ok,
+            "Git URL (cloned into the clone directory first)",
+        ));
+    }
+    let expanded = expand_tilde(input);
+    let path = Path::new(&expanded);
+
+    if !path.exists() {
+        let parent_exists = path
+            .parent()
+            .is_some_and(|p| p.as_os_str().is_empty() || p.is_dir());
+        return Some(PathStatus::new(
+            PathLevel::Warning,
+            if parent_exists {
+                "Directory does not exist (you will be asked to create it)"
+            } else {
+                "Directory and its parents do not exist (you will be asked to create them)"
+            },
+        ));
+    }
+    if !path.is_dir() {
+        return Some(PathStatus::new(PathLevel::Error, "Not a directory"));
+    }
+
+    let is_git = GitWorktree::is_git_repo(path);
+    if needs_git && !is_git {
+        return Some(PathStatus::new(
+            PathLevel::Error,
+            "Not a git repository (required for a worktree branch)",
+        ));
+    }
+    if !is_writable(path) {
+        return Some(PathStatus::new(
+            PathLevel::Warning,
+            "Directory is not writable",
+        ));
+    }
+    if let Some(session) = existing.iter().find(|s| same_path(&s.path, &expanded)) {
+        return Some(PathStatus::new(
+            PathLevel::Warning,
+            format!("Already used by session \"{}\"", session.title),
+        ));
+    }
+
+    Some(if is_git {
+        match GitWorktree::get_current_branch(path) {
+            Ok(branch) => PathStatus::new(PathLevel::Ok, format!("Git repository on {}", branch)),
+            Err(_) => PathStatus::new(PathLevel::Ok, "Git repository"),
+        }
+    } else {
+        PathStatus::new(PathLevel::Ok, "Directory (not a git repository)")
+    })
+}
+
+fn same_path(a: &str, b: &str) -> bool {
+    let a = Path::new(a);
+    let b = Path::new(b);
+    if a == b {
+        return true;
+    }
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(unix)]
+fn is_writable(path: &Path) -> bool {
+    nix::unistd::access(path, nix::unistd::AccessFlags::W_OK).is_ok()
+}
+
+#[cfg(not(unix))]
+fn is_writable(path: &Path) -> bool {
+    std::fs::metadata(path).is_ok_and(|m| !m.permissions().readonly())
+}
+
+impl NewSessionDialog {
+    /// Record which paths already have sessions so the path field can warn
+    /// about duplicates.
+    pub fn set_existing_sessions(&mut self, sessions: Vec<ExistingSessionPath>) {
+        self.existing_sessions = sessions;
+    }
+
+    /// The latest path check result, and whether it was computed for an
+    /// older value of the path field.
+    pub(super) fn path_status(&self) -> Option<(&PathStatus, bool)> {
+        self.path_status
+            .as_ref()
+            .map(|(input, status)| (status, input != self.path.value()))
+    }
+
+    /// Schedule path checks after edits and collect finished results.
+    /// Returns true when the displayed status changed.
+    pub(super) fn tick_path_validation(&mut self) -> bool {
+        let mut changed = false;
+        let current = self.path.value().to_string();
+
+        while let Ok(result) = self.path_validator.rx.try_recv() {
+            if result.input == current {
+                self.path_status = result.status.map(|status| (result.input, status));
+                changed = true;
+            }
+        }
+
+        let needs_git = !self.worktree_branch.value().trim().is_empty();
+        let key = (current.clone(), needs_git);
+        if self.path_validator.requested.as_ref() == Some(&key) {
+            return changed;
+        }
+        match &self.path_validator.pending {
+            Some((input, edited_at)) if *input == current => {
+                if edited_at.elapsed() < PATH_CHECK_DEBOUNCE {
+                    return changed;
+                }
+            }
+            _ => {
+                self.path_validator.pending = Some((current, Instant::now()));
+                return changed;
+            }
+        }
+
+        self.path_validator.pending = None;
+        self.path_validator.requested = Some(key);
+        let existing = self.existing_sessions.clone();
+        let tx = self.path_validator.tx.clone();
+        thread::spawn(move || {
+            let status = check_path(&current, needs_git, &existing);
+            let _ = tx.send(PathCheckResult {
+                input: current,
+                status,
+            });
+        });
+        changed
+    }
+}
@@ -3,6 +3,7 @@
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
+use super::path_status::PathLevel;
 use super::{NewSessionDialog, FIELD_HELP, HELP_DIALOG_WIDTH, SPINNER_FRAMES};
 use crate::tui::components::{render_text_field, render_text_field_with_ghost};
 use crate::tui::styles::Theme;
@@ -58,6 +59,18 @@ impl NewSessionDialog {
             constraints.push(Constraint::Length(2)); // Sandbox checkbox (summary only)
         }
         constraints.push(Constraint::Length(2)); // Group (always, at the bottom)
+        let has_context = !self.context_commits.is_empty();
+        if has_context {
+            constraints.push(Constraint::Length(2)); // Commits picked as prompt context
+        }
+        let has_attachments = !self.attachments.is_empty();
+        if has_attachments {
+            constraints.push(Constraint::Length(2)); // Files and diffs attached to the prompt
+        }
+        let has_resume = self.shows_resume_line();
+        if has_resume {
+            constraints.push(Constraint::Length(2)); // Claude conversation to resume
+        }
 
         // For errors, calculate how many lines we need based on the text length.
         // Inner width = dialog_width - 2 (border) - 2 (margin) = 76
@@ -133,13 +146,18 @@ impl NewSessionDialog {
         }
 
         // Title
+        let title_placeholder = if self.focused_field == title_field {
+            "(random civ, Ctrl+P: pick an issue, Ctrl+G: recent commits)"
+        } else {
+            "(random civ)"
+        };
         render_text_field(
             frame,
             chunks[ci],
             "Title:",
             &self.title,
             self.focused_field == title_field,
-            Some("(random civ)"),
+            Some(title_placeholder),
             theme,
         );
         ci += 1;
@@ -307,10 +325,10 @@ impl NewSessionDialog {
                 }
 
                 // Summary of config
-                let branch_mode = if self.create_new_branch {
-                    "new"
-                } else {
-                    "existing"
+                let branch_mode = match &self.base_ref {
+                    Some(base) if self.base_ref_applies() => format!("new from {}", base),
+                    _ if self.create_new_branch => "new".to_string(),
+                    _ => "existing".to_string(),
                 };
                 let repos_count = self.workspace_repos.len();
                 let summary = if repos_count > 0 {
@@ -392,6 +410,21 @@ impl NewSessionDialog {
         );
         ci += 1;
 
+        if has_context {
+            self.render_context_commits(frame, chunks[ci], theme);
+            ci += 1;
+        }
+
+        if has_attachments {
+            self.render_attachments(frame, chunks[ci], theme);
+            ci += 1;
+        }
+
+        if has_resume {
+            self.render_resume(frame, chunks[ci], theme);
+            ci += 1;
+        }
+
         // Hints/errors (last chunk)
         let hint_chunk = ci;
         if self.confirm_create_dir.is_some() {
@@ -416,6 +449,12 @@ impl NewSessionDialog {
                 Span::styled("[N]o", no_style),
             ]);
             frame.render_widget(Paragraph::new(line), chunks[hint_chunk]);
+        } else if let Some(status) = self.issue_loading() {
+            let line = Line::from(Span::styled(
+                format!("{}…", status),
+                Style::default().fg(theme.dimmed),
+            ));
+            frame.render_widget(Paragraph::new(line), chunks[hint_chunk]);
         } else if let Some(error) = &self.error_message {
             let error_text = format!("✗ Error: {}", error);
             let error_paragraph = Paragraph::new(error_text)
@@ -479,11 +518,115 @@ impl NewSessionDialog {
             self.branch_picker.render(frame, area, theme);
         }
 
+        if self.issue_picker.is_active() {
+            self.issue_picker.render(frame, area, theme);
+        }
+
+        if self.commit_picker.is_active() {
+            self.commit_picker.render(frame, area, theme);
+        }
+
+        if self.attachment_picker.is_active() {
+            self.attachment_picker.render(frame, area, theme);
+        }
+
+        if self.resume_picker.is_active() {
+            self.resume_picker.render(frame, area, theme);
+        }
+
         if self.dir_picker.is_active() {
             self.dir_picker.render(frame, area, theme);
         }
     }
 
+    /// Summary of the commits handed to the agent, e.g. "a1b2c3d Add parser (+2 more)".
+    fn render_context_commits(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Some(first) = self.context_commits.first() else {
+            return;
+        };
+        let mut spans = vec![
+            Span::styled("Context:", Style::default().fg(theme.text)),
+            Span::raw(" "),
+            Span::styled(first.label(), Style::default().fg(theme.text)),
+        ];
+        let more = self.context_commits.len() - 1;
+        if more > 0 {
+            spans.push(Span::styled(
+                format!(" (+{} more)", more),
+                Style::default().fg(theme.dimmed),
+            ));
+        }
+        spans.push(Span::styled(
+            "  (Ctrl+G to change)",
+            Style::default().fg(theme.dimmed),
+        ));
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    /// Summary of the prompt attachments, e.g. "src/lib.rs (+1 more)".
+    fn render_attachments(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Some(first) = self.attachments.first() else {
+            return;
+        };
+        let mut spans = vec![
+            Span::styled("Attached:", Style::default().fg(theme.text)),
+            Span::raw(" "),
+            Span::styled(first.label(), Style::default().fg(theme.text)),
+        ];
+        let more = self.attachments.len() - 1;
+        if more > 0 {
+            spans.push(Span::styled(
+                format!(" (+{} more)", more),
+                Style::default().fg(theme.dimmed),
+            ));
+        }
+        spans.push(Span::styled(
+            "  (Ctrl+T to change)",
+            Style::default().fg(theme.dimmed),
+        ));
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    /// Whether to show the Claude conversation line: one is picked, or the
+    /// directory has earlier conversations to offer.
+    fn shows_resume_line(&self) -> bool {
+        self.selected_tool_is_claude()
+            && (self.resume_conversation.is_some()
+                || self.resume_candidates.as_ref().is_some_and(|(_, n)| *n > 0))
+    }
+
+    /// The Claude conversation being resumed, or how many could be.
+    fn render_resume(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let mut spans = vec![
+            Span::styled("Resume:", Style::default().fg(theme.text)),
+            Span::raw(" "),
+        ];
+        match &self.resume_conversation {
+            Some(conversation) => {
+                spans.push(Span::styled(
+                    conversation.title.clone(),
+                    Style::default().fg(theme.text),
+                ));
+                spans.push(Span::styled(
+                    "  (Ctrl+R to change)",
+                    Style::default().fg(theme.dimmed),
+                ));
+            }
+            None => {
+                let count = self.resume_candidates.as_ref().map_or(0, |(_, n)| *n);
+                spans.push(Span::styled(
+                    format!(
+                        "{} earlier Claude conversation{} here (Ctrl+R to pick one)",
+                        count,
+                        if count == 1 { "" } else { "s" }
+                    ),
+                    Style::default().fg(theme.dimmed),
+                ));
+            }
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
     fn render_profile_field(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let is_focused = self.focused_field == 0;
         let label_style = if is_focused {
@@ -581,7 +724,25 @@ impl NewSessionDialog {
             spans.push(Span::styled(value, value_style));
         }
 
-        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+        let mut lines = vec![Line::from(spans)];
+        if let Some((status, stale)) = self.path_status() {
+            let (icon, color) = match status.level {
+                PathLevel::Ok => ("✓", theme.running),
+                PathLevel::Warning => ("⚠", theme.waiting),
+                PathLevel::Error => ("✗", theme.error),
+            };
+            let style = if stale {
+                Style::default().fg(theme.dimmed)
+            } else {
+                Style::default().fg(color)
+            };
+            lines.push(Line::from(Span::styled(
+                format!("      {} {}", icon, status.message),
+                style,
+            )));
+        }
+
+        frame.render_widget(Paragraph::new(lines), area);
     }
 
     fn render_sandbox_config(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
@@ -778,6 +939,7 @@ impl NewSessionDialog {
 
         let constraints = vec![
             Constraint::Length(2),            // New Branch checkbox
+            Constraint::Length(2),            // Base ref
             Constraint::Length(repos_height), // Extra Repos
             Constraint::Min(1),               // Hints
         ];
@@ -847,11 +1009,40 @@ impl NewSessionDialog {
             frame.render_widget(Paragraph::new(line), chunks[0]);
         }
 
+        // Base ref
+        {
+            let is_focused = self.worktree_config_focused_field == 1;
+            let label_style = if is_focused {
+                Style::default().fg(theme.accent).underlined()
+            } else {
+                Style::default().fg(theme.text)
+            };
+            let value = if !self.create_new_branch {
+                Span::styled("(existing branch)", Style::default().fg(theme.dimmed))
+            } else if !self.workspace_repos.is_empty() {
+                Span::styled("(each repo's HEAD)", Style::default().fg(theme.dimmed))
+            } else if let Some(base) = &self.base_ref {
+                Span::styled(base.clone(), Style::default().fg(theme.accent))
+            } else {
+                let default = self
+                    .default_base_ref()
+                    .map(|b| format!("{} (default)", b))
+                    .unwrap_or_else(|| "HEAD".to_string());
+                Span::styled(default, Style::default().fg(theme.accent))
+            };
+            let line = Line::from(vec![
+                Span::styled("Base:", label_style),
+                Span::raw(" "),
+                value,
+            ]);
+            frame.render_widget(Paragraph::new(line), chunks[1]);
+        }
+
         // Extra Repos
         self.render_extra_repos_field(
             frame,
-            chunks[1],
-            self.worktree_config_focused_field == 1,
+            chunks[2],
+            self.worktree_config_focused_field == 2,
             theme,
         );
 
@@ -868,7 +1059,19 @@ impl NewSessionDialog {
             Span::styled("Esc", Style::default().fg(theme.hint)),
             Span::raw(" back"),
         ];
-        if self.worktree_config_focused_field == 1 && !self.workspace_repos_expanded {
+        if self.worktree_config_focused_field == 1 && self.base_ref_applies() {
+            hint_spans = vec![
+                Span::styled("Tab", Style::default().fg(theme.hint)),
+                Span::raw(" next  "),
+                Span::styled("Enter", Style::default().fg(theme.hint)),
+                Span::raw(" pick ref  "),
+                Span::styled("Bksp", Style::default().fg(theme.hint)),
+                Span::raw(" default  "),
+                Span::styled("Esc", Style::default().fg(theme.hint)),
+                Span::raw(" back"),
+            ];
+        }
+        if self.worktree_config_focused_field == 2 && !self.workspace_repos_expanded {
             hint_spans = vec![
                 Span::styled("Tab", Style::default().fg(theme.hint)),
                 Span::raw(" next  "),
@@ -878,7 +1081,7 @@ impl NewSessionDialog {
                 Span::raw(" back"),
             ];
         }
-        frame.render_widget(Paragraph::new(Line::from(hint_spans)), chunks[2]);
+        frame.render_widget(Paragraph::new(Line::from(hint_spans)), chunks[3]);
 
         if self.show_help {
             self.render_help_overlay(frame, area, theme);
@@ -888,6 +1091,10 @@ impl NewSessionDialog {
             self.branch_picker.render(frame, area, theme);
         }
 
+        if self.base_ref_picker.is_active() {
+            self.base_ref_picker.render(frame, area, theme);
+        }
+
         if self.dir_picker.is_active() {
             self.dir_picker.render(frame, area, theme);
         }
@@ -1323,7 +1530,9 @@ impl NewSessionDialog {
 
             frame.render_widget(Paragraph::new(lines), inner);
         } else {
-            let loading_text = if self.sandbox_enabled {
+            let loading_text = if crate::git::clone::is_clone_url(self.path.value()) {
+                "Cloning repository..."
+            } else if self.sandbox_enabled {
                 "Setting up sandbox..."
             } else {
                 "Creating session..."
@@ -1,4 +1,5 @@
 use super::*;
+use crate::git::issue::IssueRef;
 use crate::session::{merge_configs, Config, ProfileConfig, SessionConfigOverride};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::fs;
@@ -83,6 +84,120 @@ fn test_enter_preserves_custom_title() {
     }
 }
 
+#[test]
+fn test_enter_submits_without_issue_by_default() {
+    let mut dialog = single_tool_dialog();
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => {
+            assert!(data.issue_info.is_none());
+            assert!(data.initial_prompt.is_none());
+        }
+        _ => panic!("Expected Submit"),
+    }
+}
+
+#[test]
+fn test_enter_submits_linked_issue() {
+    let mut dialog = single_tool_dialog();
+    let reference = IssueRef::parse("https://github.com/org/repo/issues/42").unwrap();
+    let picked = Issue {
+        url: reference.url(),
+        reference,
+        title: "Crash on startup".to_string(),
+        body: "Stack trace attached".to_string(),
+    };
+    dialog.title = Input::new(picked.session_title());
+    dialog.issue = Some(picked);
+
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => {
+            assert_eq!(data.title, "#42 Crash on startup");
+            let info = data.issue_info.expect("issue should be linked");
+            assert_eq!(info.number, 42);
+            assert_eq!(info.url, "https://github.com/org/repo/issues/42");
+            let prompt = data.initial_prompt.expect("prompt should be prefilled");
+            assert!(prompt.contains("Stack trace attached"));
+        }
+        _ => panic!("Expected Submit"),
+    }
+}
+
+#[test]
+fn test_picked_commits_become_prompt_context() {
+    let mut dialog = single_tool_dialog();
+    dialog.recent_commits = vec![
+        CommitSummary {
+            short_id: "b2c3d4e".to_string(),
+            subject: "Wire parser into preview".to_string(),
+        },
+        CommitSummary {
+            short_id: "a1b2c3d".to_string(),
+            subject: "Add parser".to_string(),
+        },
+    ];
+    dialog.toggle_context_commit("b2c3d4e Wire parser into preview");
+    assert_eq!(
+        dialog.initial_prompt().unwrap().as_deref(),
+        Some("Continue work from commit b2c3d4e (\"Wire parser into preview\").")
+    );
+
+    dialog.toggle_context_commit("a1b2c3d Add parser");
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => {
+            assert_eq!(
+                data.initial_prompt.as_deref(),
+                Some(
+                    "Continue work from these commits:\n\
+                     - b2c3d4e Wire parser into preview\n\
+                     - a1b2c3d Add parser"
+                )
+            );
+        }
+        _ => panic!("Expected Submit"),
+    }
+
+    // Picking a marked commit again removes it
+    dialog.toggle_context_commit("✓ b2c3d4e Wire parser into preview");
+    dialog.toggle_context_commit("✓ a1b2c3d Add parser");
+    assert!(dialog.initial_prompt().unwrap().is_none());
+}
+
+#[test]
+fn test_attachments_are_added_to_prompt() {
+    let dir = tempfile::TempDir::new().unwrap();
+    fs::write(dir.path().join("notes.txt"), "remember the edge case\n").unwrap();
+    let mut dialog = NewSessionDialog::new_with_tools(
+        vec!["claude", "opencode"],
+        dir.path().to_string_lossy().to_string(),
+    );
+
+    // Claude reads files named with @path itself
+    dialog.toggle_attachment("notes.txt");
+    assert_eq!(
+        dialog.initial_prompt().unwrap().as_deref(),
+        Some("Attached files: @notes.txt")
+    );
+
+    // Other agents get the contents inline
+    dialog.tool_index = 1;
+    assert_eq!(
+        dialog.initial_prompt().unwrap().as_deref(),
+        Some("--- file notes.txt ---\nremember the edge case\n--- end file notes.txt ---")
+    );
+
+    // A missing file blocks submitting with an error
+    dialog.toggle_attachment("✓ notes.txt");
+    dialog.toggle_attachment("gone.txt");
+    assert!(matches!(
+        dialog.handle_key(key(KeyCode::Enter)),
+        DialogResult::Continue
+    ));
+    assert!(dialog
+        .error_message
+        .as_deref()
+        .is_some_and(|e| e.contains("gone.txt")));
+}
+
 #[test]
 fn test_tab_cycles_fields_single_tool() {
     let mut dialog = single_tool_dialog();
@@ -231,6 +346,26 @@ fn test_ghost_text_none_when_no_matches() {
     assert_eq!(dialog.ghost_text(), None);
 }
 
+#[test]
+fn test_ghost_skips_repo_completion_ignores() {
+    let tmp = tempfile::tempdir().expect("failed to create temp dir");
+    fs::create_dir_all(tmp.path().join(".aoe")).expect("failed to create directory");
+    fs::write(
+        tmp.path().join(".aoe/config.toml"),
+        "completion_ignores = [\"target\"]\n",
+    )
+    .expect("failed to write config");
+    fs::create_dir_all(tmp.path().join("src/target")).expect("failed to create directory");
+    fs::create_dir(tmp.path().join("src/tests")).expect("failed to create directory");
+
+    let mut dialog = single_tool_dialog();
+    dialog.focused_field = 1; // path
+    dialog.path = Input::new(format!("{}/src/t", tmp.path().display()));
+    dialog.recompute_path_ghost();
+
+    assert_eq!(dialog.ghost_text(), Some("ests/"));
+}
+
 #[test]
 fn test_ghost_shows_slash_for_exact_directory_match() {
     let tmp = tempfile::tempdir().expect("failed to create temp dir");
@@ -414,6 +549,63 @@ fn test_ctrl_a_jumps_to_start_of_path() {
     assert_eq!(dialog.path.value(), "X/tmp/alpha/beta");
 }
 
+#[test]
+fn test_ctrl_w_deletes_previous_path_segment() {
+    let mut dialog = single_tool_dialog();
+    dialog.focused_field = 1; // path
+    dialog.path = Input::new("/tmp/alpha/beta/".to_string());
+
+    dialog.handle_key(ctrl_key(KeyCode::Char('w')));
+    assert_eq!(dialog.path.value(), "/tmp/alpha/");
+    dialog.handle_key(ctrl_key(KeyCode::Char('w')));
+    assert_eq!(dialog.path.value(), "/tmp/");
+}
+
+#[test]
+fn test_alt_d_deletes_next_path_segment() {
+    let mut dialog = single_tool_dialog();
+    dialog.focused_field = 1; // path
+    dialog.path = Input::new("/tmp/alpha/beta".to_string());
+
+    dialog.handle_key(alt_key(KeyCode::Char('b')));
+    dialog.handle_key(alt_key(KeyCode::Char('b')));
+    dialog.handle_key(alt_key(KeyCode::Char('d')));
+    assert_eq!(dialog.path.value(), "/tmp//beta");
+    assert_eq!(dialog.path.visual_cursor(), 5);
+}
+
+#[test]
+fn test_ctrl_k_and_ctrl_u_kill_around_cursor() {
+    let mut dialog = single_tool_dialog();
+    dialog.focused_field = 1; // path
+    dialog.path = Input::new("/tmp/alpha/beta".to_string());
+
+    dialog.handle_key(ctrl_key(KeyCode::Left));
+    dialog.handle_key(ctrl_key(KeyCode::Char('k')));
+    assert_eq!(dialog.path.value(), "/tmp/alpha/");
+
+    dialog.handle_key(ctrl_key(KeyCode::Left));
+    dialog.handle_key(ctrl_key(KeyCode::Char('u')));
+    assert_eq!(dialog.path.value(), "alpha/");
+    assert_eq!(dialog.path.visual_cursor(), 0);
+}
+
+#[test]
+fn test_kill_recomputes_path_ghost() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::create_dir(tmp.path().join("alpha")).unwrap();
+    let mut dialog = single_tool_dialog();
+    dialog.focused_field = 1; // path
+    dialog.path = Input::new(format!("{}/alpzz", tmp.path().display()));
+    dialog.handle_key(key(KeyCode::Left));
+    dialog.handle_key(key(KeyCode::Left));
+    dialog.recompute_path_ghost();
+    assert_eq!(dialog.ghost_text(), None);
+
+    dialog.handle_key(ctrl_key(KeyCode::Char('k')));
+    assert_eq!(dialog.ghost_text(), Some("ha/"));
+}
+
 #[test]
 fn test_char_input_to_group() {
     let mut dialog = single_tool_dialog();
@@ -574,6 +766,56 @@ fn test_submit_respects_create_new_branch() {
     }
 }
 
+#[test]
+fn test_base_ref_picker_defaults_to_default_branch() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo = git2::Repository::init(tmp.path()).unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let tree = repo
+        .find_tree(repo.index().unwrap().write_tree().unwrap())
+        .unwrap();
+    let oid = repo
+        .commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+        .unwrap();
+    let commit = repo.find_commit(oid).unwrap();
+    repo.tag_lightweight("v1.0", commit.as_object(), false)
+        .unwrap();
+    let default = crate::git::diff::get_default_branch(tmp.path()).unwrap();
+
+    let mut dialog = single_tool_dialog();
+    dialog.path = Input::new(tmp.path().to_string_lossy().to_string());
+    dialog.worktree_branch = Input::new("feature-branch".to_string());
+    dialog.focused_field = 3; // worktree field
+    dialog.handle_key(ctrl_key(KeyCode::Char('p')));
+    dialog.handle_key(key(KeyCode::Tab)); // base ref
+    dialog.handle_key(key(KeyCode::Enter));
+    assert!(dialog.base_ref_picker.is_active());
+    assert_eq!(dialog.base_ref_picker.filtered_items()[0], &default);
+
+    for c in "v1".chars() {
+        dialog.handle_key(key(KeyCode::Char(c)));
+    }
+    dialog.handle_key(key(KeyCode::Enter));
+    assert_eq!(dialog.base_ref.as_deref(), Some("v1.0"));
+    dialog.handle_key(key(KeyCode::Esc));
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => assert_eq!(data.base_ref.as_deref(), Some("v1.0")),
+        _ => panic!("Expected Submit"),
+    }
+
+    // Backspace goes back to the default branch
+    dialog.focused_field = 3;
+    dialog.handle_key(ctrl_key(KeyCode::Char('p')));
+    dialog.handle_key(key(KeyCode::Tab));
+    dialog.handle_key(key(KeyCode::Backspace));
+    assert_eq!(dialog.base_ref, None);
+    dialog.handle_key(key(KeyCode::Esc));
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => assert_eq!(data.base_ref, Some(default)),
+        _ => panic!("Expected Submit"),
+    }
+}
+
 #[test]
 fn test_new_branch_field_hidden_without_worktree() {
     let mut dialog = single_tool_dialog();
@@ -913,6 +1155,17 @@ fn test_enter_with_existing_path_submits_directly() {
     assert!(dialog.confirm_create_dir.is_none());
 }
 
+#[test]
+fn test_enter_with_git_url_submits_for_cloning() {
+    let mut dialog =
+        NewSessionDialog::new_with_tools(vec!["claude"], "git@github.com:org/repo.git".to_string());
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => assert_eq!(data.path, "git@github.com:org/repo.git"),
+        _ => panic!("expected the URL to be submitted"),
+    }
+    assert!(dialog.confirm_create_dir.is_none());
+}
+
 #[test]
 fn test_confirm_esc_cancels() {
     let mut dialog = nonexistent_dialog();
@@ -1141,3 +1394,178 @@ fn test_sandbox_config_mode_enter_on_image_returns_to_main() {
     assert!(matches!(result, DialogResult::Continue));
     assert!(!dialog.sandbox_config_mode);
 }
+
+// --- path status tests ---
+
+fn wait_for_path_status(dialog: &mut NewSessionDialog) {
+    let deadline = Instant::now() + std::time::Duration::from_secs(5);
+    while Instant::now() < deadline {
+        dialog.tick();
+        if matches!(dialog.path_status(), Some((_, false))) {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    panic!("path status was never computed");
+}
+
+#[test]
+fn test_check_path_reports_missing_and_file() {
+    use path_status::{check_path, PathLevel};
+
+    let tmp = tempfile::tempdir().expect("temp dir");
+    assert!(check_path("  ", false, &[]).is_none());
+
+    let missing = tmp.path().join("new-project");
+    let status = check_path(&missing.to_string_lossy(), false, &[]).unwrap();
+    assert_eq!(status.level, PathLevel::Warning);
+    assert!(status.message.contains("does not exist"));
+
+    let file = tmp.path().join("file.txt");
+    fs::write(&file, "x").unwrap();
+    let status = check_path(&file.to_string_lossy(), false, &[]).unwrap();
+    assert_eq!(status.level, PathLevel::Error);
+    assert_eq!(status.message, "Not a directory");
+}
+
+#[test]
+fn test_check_path_git_and_duplicate_session() {
+    use path_status::{check_path, PathLevel};
+
+    let tmp = tempfile::tempdir().expect("temp dir");
+    let path = tmp.path().to_string_lossy().to_string();
+
+    let status = check_path(&path, false, &[]).unwrap();
+    assert_eq!(status.level, PathLevel::Ok);
+    assert!(status.message.contains("not a git repository"));
+
+    let status = check_path(&path, true, &[]).unwrap();
+    assert_eq!(status.level, PathLevel::Error);
+    assert!(status.message.contains("Not a git repository"));
+
+    git2::Repository::init(tmp.path()).unwrap();
+    let status = check_path(&path, true, &[]).unwrap();
+    assert_eq!(status.level, PathLevel::Ok);
+    assert!(status.message.starts_with("Git repository"));
+
+    let existing = vec![ExistingSessionPath {
+        title: "fix-bug".to_string(),
+        path: format!("{}/", path),
+    }];
+    let status = check_path(&path, false, &existing).unwrap();
+    assert_eq!(status.level, PathLevel::Warning);
+    assert_eq!(status.message, "Already used by session \"fix-bug\"");
+}
+
+#[test]
+fn test_path_status_updates_in_background_and_blocks_submit() {
+    let tmp = tempfile::tempdir().expect("temp dir");
+    let file = tmp.path().join("file.txt");
+    fs::write(&file, "x").unwrap();
+
+    let mut dialog =
+        NewSessionDialog::new_with_tools(vec!["claude"], file.to_string_lossy().to_string());
+    wait_for_path_status(&mut dialog);
+    let (status, _) = dialog.path_status().unwrap();
+    assert_eq!(status.message, "Not a directory");
+
+    let result = dialog.handle_key(key(KeyCode::Enter));
+    assert!(matches!(result, DialogResult::Continue));
+    assert_eq!(dialog.error_message.as_deref(), Some("Not a directory"));
+
+    // Editing the path marks the old result stale until it is rechecked
+    dialog.focused_field = dialog.path_field();
+    dialog.handle_key(key(KeyCode::Backspace));
+    assert!(matches!(dialog.path_status(), Some((_, true))));
+}
+
+#[test]
+fn test_resumed_claude_conversation_adds_resume_flag() {
+    use crate::session::claude_history::ClaudeConversation;
+
+    let mut dialog = multi_tool_dialog();
+    let conversation = ClaudeConversation {
+        id: "abc-123".to_string(),
+        title: "Fix the login bug".to_string(),
+        modified: chrono::Utc::now() - chrono::Duration::hours(3),
+        prompts: 2,
+    };
+    dialog.claude_conversations = vec![conversation.clone()];
+    dialog.toggle_resume_conversation(&resume_label(&conversation, chrono::Utc::now()));
+    assert_eq!(dialog.resume_conversation, Some(conversation));
+
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => assert_eq!(data.extra_args, "--resume abc-123"),
+        _ => panic!("Expected Submit"),
+    }
+
+    // Other agents can't resume a Claude conversation
+    dialog.tool_index = 1;
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => assert_eq!(data.extra_args, ""),
+        _ => panic!("Expected Submit"),
+    }
+}
+
+fn issue_summary(number: u64, title: &str) -> IssueSummary {
+    IssueSummary {
+        number,
+        title: title.to_string(),
+        url: format!("https://github.com/org/repo/issues/{}", number),
+    }
+}
+
+fn wait_for_issue_load(dialog: &mut NewSessionDialog) {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while !dialog.tick_issue_loading() && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn test_listed_issues_open_the_picker_when_they_arrive() {
+    use issue_loader::IssueLoad;
+    use std::sync::mpsc;
+
+    let mut dialog = single_tool_dialog();
+    let (tx, rx) = mpsc::channel::<()>();
+    dialog.issue_loader.start("Loading issues", move || {
+        let _ = rx.recv();
+        IssueLoad::List(Ok(vec![issue_summary(7, "Flaky test")]))
+    });
+    assert_eq!(dialog.issue_loading(), Some("Loading issues"));
+    assert!(!dialog.tick_issue_loading());
+    assert!(!dialog.issue_picker.is_active());
+
+    tx.send(()).unwrap();
+    wait_for_issue_load(&mut dialog);
+    assert_eq!(dialog.issue_loading(), None);
+    assert!(dialog.issue_picker.is_active());
+    assert_eq!(dialog.issue_summaries.len(), 1);
+}
+
+#[test]
+fn test_only_the_latest_issue_request_is_applied() {
+    use issue_loader::IssueLoad;
+    use std::sync::mpsc;
+
+    let mut dialog = single_tool_dialog();
+    let (tx, rx) = mpsc::channel::<()>();
+    dialog.issue_loader.start("Loading issues", move || {
+        let _ = rx.recv();
+        IssueLoad::List(Ok(vec![issue_summary(1, "Stale")]))
+    });
+    dialog.issue_loader.start("Loading issues", || {
+        IssueLoad::List(Err("gh is not installed".to_string()))
+    });
+    wait_for_issue_load(&mut dialog);
+    tx.send(()).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    assert!(!dialog.tick_issue_loading());
+
+    assert!(!dialog.issue_picker.is_active());
+    assert!(dialog
+        .error_message
+        .as_deref()
+        .is_some_and(|e| e.contains("gh is not installed")));
+}
@@ -0,0 +1,137 @@
+//! Dialog answering an agent's tool-permission prompt without attaching
+//!
+//! Shows the question the agent paused on with the pane lines above it,
+//! which name the tool call, and one key per answer the prompt offers.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::DialogResult;
+use crate::session::Instance;
+use crate::tmux::permission_prompt::{PermissionChoice, PermissionPrompt};
+use crate::tui::styles::Theme;
+
+const DIALOG_WIDTH: u16 = 72;
+
+pub struct PermissionDialog {
+    pub session_id: String,
+    /// The prompt as detected, context lines included: answering checks
+    /// the agent still shows exactly this, so only what was shown here
+    /// gets approved
+    pub prompt: PermissionPrompt,
+    title: String,
+}
+
+impl PermissionDialog {
+    pub fn new(inst: &Instance, prompt: PermissionPrompt) -> Self {
+        Self {
+            session_id: inst.id.clone(),
+            title: inst.title.clone(),
+            prompt,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<PermissionChoice> {
+        let choice = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return DialogResult::Cancel,
+            // No Enter: allowing runs a command, so it takes a deliberate key
+            KeyCode::Char('y') => PermissionChoice::Allow,
+            KeyCode::Char('a') => PermissionChoice::AlwaysAllow,
+            KeyCode::Char('n') => PermissionChoice::Deny,
+            _ => return DialogResult::Continue,
+        };
+        if self.prompt.key(choice).is_none() {
+            return DialogResult::Continue;
+        }
+        DialogResult::Submit(choice)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let width = DIALOG_WIDTH.min(area.width.saturating_sub(4));
+        // borders (2) + context + spacer (1) + question (1) + spacer (1) + actions (1)
+        let height = (self.prompt.context.len() as u16 + 6).min(area.height.saturating_sub(2));
+        let dialog_area = super::centered_rect(area, width, height);
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.waiting))
+            .title(format!(" Permission: {} ", self.title))
+            .title_style(Style::default().fg(theme.waiting).bold());
+        let inner = block.inner(dialog_area).inner(Margin::new(1, 0));
+        frame.render_widget(block, dialog_area);
+
+        let mut lines: Vec<Line> = self
+            .prompt
+            .context
+            .iter()
+            .map(|l| Line::from(Span::styled(l.as_str(), Style::default().fg(theme.dimmed))))
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            self.prompt.question.as_str(),
+            Style::default().fg(theme.text).bold(),
+        )));
+        lines.push(Line::from(""));
+
+        let key = |k: &'static str| Span::styled(k, Style::default().fg(theme.hint));
+        let desc = |d: &'static str| Span::styled(d, Style::default().fg(theme.dimmed));
+        let mut actions = vec![key("y"), desc(" allow  ")];
+        if self.prompt.key(PermissionChoice::AlwaysAllow).is_some() {
+            actions.extend([key("a"), desc(" always allow  ")]);
+        }
+        actions.extend([key("n"), desc(" deny  "), key("Esc"), desc(" close")]);
+        lines.push(Line::from(actions).alignment(Alignment::Center));
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_keys_pick_offered_choices() {
+        let inst = Instance::new("t", "/tmp/project");
+        let prompt = PermissionPrompt {
+            question: "Continue? (y/n)".to_string(),
+            options: vec![
+                (PermissionChoice::Allow, "y".to_string()),
+                (PermissionChoice::Deny, "n".to_string()),
+            ],
+            enter: true,
+            context: vec!["rm -rf build".to_string()],
+        };
+        let mut dialog = PermissionDialog::new(&inst, prompt);
+
+        // Allowing takes an explicit `y`
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Continue
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('y'))),
+            DialogResult::Submit(PermissionChoice::Allow)
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('n'))),
+            DialogResult::Submit(PermissionChoice::Deny)
+        ));
+        // Not offered by a (y/n) question
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('a'))),
+            DialogResult::Continue
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+}
@@ -0,0 +1,352 @@
+//! Processes dialog: a session's process tree with state and CPU use, for
+//! finding and killing what the agent left running
+
+use crossterm::event::{KeyCode, KeyEvent};
+use nix::sys::signal::Signal;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::DialogResult;
+use crate::process::ProcessNode;
+use crate::tui::styles::Theme;
+
+/// Signals offered by `s`, most useful first
+const SIGNALS: [Signal; 7] = [
+    Signal::SIGTERM,
+    Signal::SIGKILL,
+    Signal::SIGINT,
+    Signal::SIGHUP,
+    Signal::SIGSTOP,
+    Signal::SIGCONT,
+    Signal::SIGUSR1,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProcessAction {
+    /// Read the process tree again
+    Refresh,
+    Signal {
+        pid: u32,
+        signal: Signal,
+    },
+}
+
+enum Mode {
+    List,
+    ConfirmKill,
+    /// Picking a signal, the index into `SIGNALS`
+    ChooseSignal(usize),
+}
+
+pub struct ProcessesDialog {
+    session_title: String,
+    root_pid: u32,
+    processes: Vec<ProcessNode>,
+    selected: usize,
+    mode: Mode,
+}
+
+impl ProcessesDialog {
+    pub fn new(session_title: &str, root_pid: u32, processes: Vec<ProcessNode>) -> Self {
+        Self {
+            session_title: session_title.to_string(),
+            root_pid,
+            processes,
+            selected: 0,
+            mode: Mode::List,
+        }
+    }
+
+    pub fn root_pid(&self) -> u32 {
+        self.root_pid
+    }
+
+    /// Replace the tree after a refresh, keeping the selected process if it
+    /// is still running.
+    pub fn set_processes(&mut self, processes: Vec<ProcessNode>) {
+        let pid = self.selected_process().map(|p| p.pid);
+        self.selected = pid
+            .and_then(|pid| processes.iter().position(|p| p.pid == pid))
+            .unwrap_or(self.selected)
+            .min(processes.len().saturating_sub(1));
+        self.processes = processes;
+        self.mode = Mode::List;
+    }
+
+    fn selected_process(&self) -> Option<&ProcessNode> {
+        self.processes.get(self.selected)
+    }
+
+    fn signal_selected(&mut self, signal: Signal) -> DialogResult<ProcessAction> {
+        self.mode = Mode::List;
+        match self.selected_process() {
+            Some(p) => DialogResult::Submit(ProcessAction::Signal { pid: p.pid, signal }),
+            None => DialogResult::Continue,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<ProcessAction> {
+        match &mut self.mode {
+            Mode::ConfirmKill => match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    return self.signal_selected(Signal::SIGTERM);
+                }
+                _ => self.mode = Mode::List,
+            },
+            Mode::ChooseSignal(index) => match key.code {
+                KeyCode::Up | KeyCode::Char('k') => *index = index.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => *index = (*index + 1).min(SIGNALS.len() - 1),
+                KeyCode::Enter => {
+                    let signal = SIGNALS[*index];
+                    return self.signal_selected(signal);
+                }
+                KeyCode::Esc | KeyCode::Char('q') => self.mode = Mode::List,
+                _ => {}
+            },
+            Mode::List => match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => return DialogResult::Cancel,
+                KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') if self.selected + 1 < self.processes.len() => {
+                    self.selected += 1;
+                }
+                KeyCode::Char('g') => self.selected = 0,
+                KeyCode::Char('G') => self.selected = self.processes.len().saturating_sub(1),
+                KeyCode::Char('r') => return DialogResult::Submit(ProcessAction::Refresh),
+                KeyCode::Char('x') if self.selected_process().is_some() => {
+                    self.mode = Mode::ConfirmKill;
+                }
+                KeyCode::Char('s') if self.selected_process().is_some() => {
+                    self.mode = Mode::ChooseSignal(0);
+                }
+                _ => {}
+            },
+        }
+        DialogResult::Continue
+    }
+
+    fn state_color(process: &ProcessNode, theme: &Theme) -> Color {
+        if process.is_zombie() {
+            theme.error
+        } else if process.is_stopped() {
+            theme.waiting
+        } else if process.state.starts_with('R') {
+            theme.running
+        } else {
+            theme.dimmed
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let height = (area.height * 3 / 4).max(12);
+        let dialog_area = super::centered_rect(area, 100, height);
+        frame.render_widget(Clear, dialog_area);
+
+        let hint = |key: &'static str, label: &'static str| {
+            [
+                Span::styled(key, Style::default().fg(theme.accent)),
+                Span::styled(label, Style::default().fg(theme.dimmed)),
+            ]
+        };
+        let hints: Vec<Span> = match &self.mode {
+            Mode::List => [
+                hint(" x", " terminate "),
+                hint("s", " send signal "),
+                hint("r", " refresh "),
+                hint("Esc", " close "),
+            ]
+            .concat(),
+            Mode::ConfirmKill => [hint(" y", " terminate "), hint("n", " cancel ")].concat(),
+            Mode::ChooseSignal(_) => [hint(" Enter", " send "), hint("Esc", " cancel ")].concat(),
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(format!(" Processes: {} ", self.session_title))
+            .title_style(Style::default().fg(theme.accent).bold())
+            .title_bottom(Line::from(hints).right_aligned());
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let footer_height = match self.mode {
+            Mode::ChooseSignal(_) => SIGNALS.len() as u16 + 1,
+            _ => 3,
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Min(3),
+                Constraint::Length(footer_height),
+            ])
+            .split(inner);
+
+        frame.render_widget(
+            Paragraph::new(Line::styled(
+                format!("  {:>7}  {:<5} {:>6}  COMMAND", "PID", "STATE", "CPU%"),
+                Style::default().fg(theme.dimmed).bold(),
+            )),
+            chunks[0],
+        );
+
+        let mut lines: Vec<Line> = Vec::new();
+        if self.processes.is_empty() {
+            lines.push(Line::styled(
+                "  No processes. The session may have exited; press r to look again.",
+                Style::default().fg(theme.dimmed),
+            ));
+        }
+        for (i, p) in self.processes.iter().enumerate() {
+            let selected = i == self.selected;
+            let marker = if selected { "> " } else { "  " };
+            let command_style = if selected {
+                Style::default().fg(theme.accent).bold()
+            } else {
+                Style::default().fg(theme.text)
+            };
+            let branch = if p.depth == 0 {
+                String::new()
+            } else {
+                format!("{}└ ", "  ".repeat(p.depth - 1))
+            };
+            lines.push(Line::from(vec![
+                Span::styled(marker, Style::default().fg(theme.accent)),
+                Span::styled(format!("{:>7}  ", p.pid), Style::default().fg(theme.dimmed)),
+                Span::styled(
+                    format!("{:<5} ", p.state),
+                    Style::default().fg(Self::state_color(p, theme)),
+                ),
+                Span::styled(format!("{:>6.1}  ", p.cpu), Style::default().fg(theme.text)),
+                Span::styled(branch, Style::default().fg(theme.dimmed)),
+                Span::styled(p.command.clone(), command_style),
+            ]));
+        }
+        let visible = chunks[1].height as usize;
+        let offset = (self.selected + 1).saturating_sub(visible);
+        frame.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), chunks[1]);
+
+        let footer: Vec<Line> = match (&self.mode, self.selected_process()) {
+            (Mode::ChooseSignal(index), Some(p)) => {
+                let mut footer = vec![Line::styled(
+                    format!("Send a signal to {}:", p.pid),
+                    Style::default().fg(theme.text),
+                )];
+                for (i, signal) in SIGNALS.iter().enumerate() {
+                    let (marker, style) = if i == *index {
+                        ("> ", Style::default().fg(theme.accent).bold())
+                    } else {
+                        ("  ", Style::default().fg(theme.text))
+                    };
+                    footer.push(Line::styled(format!("{}{}", marker, signal), style));
+                }
+                footer
+            }
+            (Mode::ConfirmKill, Some(p)) => {
+                let what = if p.depth == 0 {
+                    "the session's shell"
+                } else {
+                    "it"
+                };
+                vec![
+                    Line::from(""),
+                    Line::styled(
+                        format!(
+                            "Send SIGTERM to {} ({})? Use s and SIGKILL if {} ignores it.",
+                            p.pid, p.command, what
+                        ),
+                        Style::default().fg(theme.waiting),
+                    ),
+                ]
+            }
+            (_, Some(p)) if p.is_zombie() => vec![
+                Line::from(""),
+                Line::styled(
+                    format!(
+                        "{} has exited but its parent ({}) has not reaped it; signal the parent.",
+                        p.pid, p.ppid
+                    ),
+                    Style::default().fg(theme.dimmed),
+                ),
+            ],
+            _ => Vec::new(),
+        };
+        frame.render_widget(Paragraph::new(footer).wrap(Wrap { trim: true }), chunks[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn process(pid: u32, depth: usize) -> ProcessNode {
+        ProcessNode {
+            pid,
+            ppid: 0,
+            depth,
+            state: "S".to_string(),
+            cpu: 0.0,
+            command: format!("proc-{}", pid),
+        }
+    }
+
+    #[test]
+    fn test_terminate_and_send_signal() {
+        let mut dialog = ProcessesDialog::new("Test", 10, vec![process(10, 0), process(11, 1)]);
+        dialog.handle_key(key(KeyCode::Char('j')));
+
+        // Terminating asks first
+        dialog.handle_key(key(KeyCode::Char('x')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('n'))),
+            DialogResult::Continue
+        ));
+        dialog.handle_key(key(KeyCode::Char('x')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('y'))),
+            DialogResult::Submit(ProcessAction::Signal {
+                pid: 11,
+                signal: Signal::SIGTERM
+            })
+        ));
+
+        dialog.handle_key(key(KeyCode::Char('s')));
+        dialog.handle_key(key(KeyCode::Down));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Submit(ProcessAction::Signal {
+                pid: 11,
+                signal: Signal::SIGKILL
+            })
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+
+    #[test]
+    fn test_refresh_keeps_selected_process() {
+        let mut dialog = ProcessesDialog::new(
+            "Test",
+            10,
+            vec![process(10, 0), process(11, 1), process(12, 1)],
+        );
+        dialog.handle_key(key(KeyCode::Char('G')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('r'))),
+            DialogResult::Submit(ProcessAction::Refresh)
+        ));
+
+        dialog.set_processes(vec![process(10, 0), process(12, 1)]);
+        assert_eq!(dialog.selected_process().map(|p| p.pid), Some(12));
+
+        // Gone processes leave the selection in range
+        dialog.set_processes(vec![process(10, 0)]);
+        assert_eq!(dialog.selected_process().map(|p| p.pid), Some(10));
+    }
+}
@@ -13,6 +13,11 @@ use crate::tui::components::{
 };
 use crate::tui::styles::Theme;
 
+/// Icons offered on the icon field; any other glyph can be typed in
+const ICON_PALETTE: &[&str] = &[
+    "🚀", "🐛", "✨", "🔧", "🧪", "📝", "🔥", "🎨", "📦", "🔒", "⚡", "🌐",
+];
+
 /// Data returned when the rename dialog is submitted
 #[derive(Debug, Clone)]
 pub struct RenameData {
@@ -22,6 +27,8 @@ pub struct RenameData {
     pub group: Option<String>,
     /// New profile (None means keep current, Some(name) means move to that profile)
     pub profile: Option<String>,
+    /// New icon (None means keep current, Some("") means remove it)
+    pub icon: Option<String>,
 }
 
 pub struct RenameDialog {
@@ -32,7 +39,9 @@ pub struct RenameDialog {
     new_title: Input,
     new_group: Input,
     profile_index: usize,
-    focused_field: usize, // 0 = title, 1 = group, 2 = profile
+    current_icon: Option<String>,
+    icon: Option<String>,
+    focused_field: usize, // 0 = title, 1 = group, 2 = profile, 3 = icon
     existing_groups: Vec<String>,
     group_picker: ListPicker,
     group_ghost: Option<GroupGhostCompletion>,
@@ -59,6 +68,8 @@ impl RenameDialog {
             new_title: Input::default(),
             new_group: Input::new(current_group.to_string()),
             profile_index,
+            current_icon: None,
+            icon: None,
             focused_field: 0,
             existing_groups,
             group_picker: ListPicker::new("Select Group"),
@@ -66,6 +77,29 @@ impl RenameDialog {
         }
     }
 
+    /// Start the icon field at the session's current icon
+    pub fn with_icon(mut self, icon: Option<&str>) -> Self {
+        self.current_icon = icon.map(str::to_string);
+        self.icon = self.current_icon.clone();
+        self
+    }
+
+    /// Step through no icon and the palette. A typed-in icon steps to the
+    /// palette's start or end.
+    fn cycle_icon(&mut self, forward: bool) {
+        let choices: Vec<Option<&str>> = std::iter::once(None)
+            .chain(ICON_PALETTE.iter().map(|icon| Some(*icon)))
+            .collect();
+        let next = match choices.iter().position(|c| *c == self.icon.as_deref()) {
+            Some(i) if forward => (i + 1) % choices.len(),
+            Some(0) => choices.len() - 1,
+            Some(i) => i - 1,
+            None if forward => 1,
+            None => choices.len() - 1,
+        };
+        self.icon = choices[next].map(str::to_string);
+    }
+
     fn focused_input(&mut self) -> Option<&mut Input> {
         match self.focused_field {
             0 => Some(&mut self.new_title),
@@ -75,12 +109,12 @@ impl RenameDialog {
     }
 
     fn next_field(&mut self) {
-        self.focused_field = (self.focused_field + 1) % 3;
+        self.focused_field = (self.focused_field + 1) % 4;
     }
 
     fn prev_field(&mut self) {
         self.focused_field = if self.focused_field == 0 {
-            2
+            3
         } else {
             self.focused_field - 1
         };
@@ -148,9 +182,14 @@ impl RenameDialog {
                 let group_value = self.new_group.value().trim();
                 let selected_profile = self.selected_profile();
                 let profile_changed = selected_profile != self.current_profile;
+                let icon_changed = self.icon != self.current_icon;
 
                 // If nothing has changed, cancel
-                if title_value.is_empty() && group_value == self.current_group && !profile_changed {
+                if title_value.is_empty()
+                    && group_value == self.current_group
+                    && !profile_changed
+                    && !icon_changed
+                {
                     return DialogResult::Cancel;
                 }
 
@@ -173,10 +212,13 @@ impl RenameDialog {
                     None
                 };
 
+                let icon = icon_changed.then(|| self.icon.clone().unwrap_or_default());
+
                 DialogResult::Submit(RenameData {
                     title: title_value,
                     group,
                     profile,
+                    icon,
                 })
             }
             KeyCode::Tab => {
@@ -224,6 +266,23 @@ impl RenameDialog {
                 self.profile_index = (self.profile_index + 1) % self.available_profiles.len();
                 DialogResult::Continue
             }
+            KeyCode::Left if self.focused_field == 3 => {
+                self.cycle_icon(false);
+                DialogResult::Continue
+            }
+            KeyCode::Right | KeyCode::Char(' ') if self.focused_field == 3 => {
+                self.cycle_icon(true);
+                DialogResult::Continue
+            }
+            KeyCode::Backspace | KeyCode::Delete if self.focused_field == 3 => {
+                self.icon = None;
+                DialogResult::Continue
+            }
+            KeyCode::Char(c) if self.focused_field == 3 => {
+                // A glyph typed or pasted in, e.g. from a nerd font
+                self.icon = Some(c.to_string());
+                DialogResult::Continue
+            }
             _ => {
                 if let Some(input) = self.focused_input() {
                     input.handle_event(&crossterm::event::Event::Key(key));
@@ -237,8 +296,8 @@ impl RenameDialog {
     }
 
     pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        let dialog_width = 50;
-        let dialog_area = super::centered_rect(area, dialog_width, 15);
+        let dialog_width = 56;
+        let dialog_area = super::centered_rect(area, dialog_width, 16);
 
         frame.render_widget(Clear, dialog_area);
 
@@ -262,6 +321,7 @@ impl RenameDialog {
                 Constraint::Length(1), // New title field
                 Constraint::Length(1), // New group field
                 Constraint::Length(1), // Profile selector
+                Constraint::Length(1), // Icon selector
                 Constraint::Length(1), // Spacer
                 Constraint::Min(1),    // Hint
             ])
@@ -345,6 +405,25 @@ impl RenameDialog {
         ]);
         frame.render_widget(Paragraph::new(profile_line), chunks[6]);
 
+        let icon_focused = self.focused_field == 3;
+        let icon_line = Line::from(vec![
+            Span::styled(
+                "Icon:       ",
+                Style::default().fg(if icon_focused {
+                    theme.accent
+                } else {
+                    theme.dimmed
+                }),
+            ),
+            Span::styled("< ", Style::default().fg(theme.dimmed)),
+            match &self.icon {
+                Some(icon) => Span::styled(icon.as_str(), Style::default().fg(theme.text)),
+                None => Span::styled("(none)", Style::default().fg(theme.dimmed)),
+            },
+            Span::styled(" >", Style::default().fg(theme.dimmed)),
+        ]);
+        frame.render_widget(Paragraph::new(icon_line), chunks[7]);
+
         // Hint
         let mut hint_spans = vec![
             Span::styled("Tab", Style::default().fg(theme.hint)),
@@ -358,12 +437,18 @@ impl RenameDialog {
             hint_spans.push(Span::styled("C-p", Style::default().fg(theme.hint)));
             hint_spans.push(Span::raw(" groups  "));
         }
+        if self.focused_field == 3 {
+            hint_spans.push(Span::styled("←→", Style::default().fg(theme.hint)));
+            hint_spans.push(Span::raw(" pick  "));
+            hint_spans.push(Span::styled("Del", Style::default().fg(theme.hint)));
+            hint_spans.push(Span::raw(" clear  "));
+        }
         hint_spans.push(Span::styled("Enter", Style::default().fg(theme.hint)));
         hint_spans.push(Span::raw(" save  "));
         hint_spans.push(Span::styled("Esc", Style::default().fg(theme.hint)));
         hint_spans.push(Span::raw(" cancel"));
         let hint = Line::from(hint_spans);
-        frame.render_widget(Paragraph::new(hint), chunks[8]);
+        frame.render_widget(Paragraph::new(hint), chunks[9]);
 
         // Render group picker overlay
         if self.group_picker.is_active() {
@@ -572,6 +657,9 @@ mod tests {
         dialog.handle_key(key(KeyCode::Tab));
         assert_eq!(dialog.focused_field, 2);
 
+        dialog.handle_key(key(KeyCode::Tab));
+        assert_eq!(dialog.focused_field, 3);
+
         dialog.handle_key(key(KeyCode::Tab));
         assert_eq!(dialog.focused_field, 0);
     }
@@ -582,6 +670,9 @@ mod tests {
             RenameDialog::new("Test", "group", "default", default_profiles(), Vec::new());
         assert_eq!(dialog.focused_field, 0);
 
+        dialog.handle_key(shift_key(KeyCode::Tab));
+        assert_eq!(dialog.focused_field, 3);
+
         dialog.handle_key(shift_key(KeyCode::Tab));
         assert_eq!(dialog.focused_field, 2);
 
@@ -929,6 +1020,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_icon_palette_cycles_and_submits() {
+        let mut dialog =
+            RenameDialog::new("Test", "group", "default", default_profiles(), Vec::new());
+        dialog.focused_field = 3;
+
+        dialog.handle_key(key(KeyCode::Right));
+        assert_eq!(dialog.icon.as_deref(), Some(ICON_PALETTE[0]));
+        dialog.handle_key(key(KeyCode::Left));
+        assert_eq!(dialog.icon, None);
+        dialog.handle_key(key(KeyCode::Left));
+        assert_eq!(dialog.icon.as_deref(), ICON_PALETTE.last().copied());
+
+        match dialog.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(data) => {
+                assert_eq!(data.title, "");
+                assert_eq!(data.icon.as_deref(), ICON_PALETTE.last().copied());
+            }
+            _ => panic!("Expected Submit"),
+        }
+    }
+
+    #[test]
+    fn test_icon_typed_and_cleared() {
+        let mut dialog =
+            RenameDialog::new("Test", "group", "default", default_profiles(), Vec::new())
+                .with_icon(Some("🚀"));
+        dialog.focused_field = 3;
+
+        // Unchanged icon is not a change
+        dialog.handle_key(key(KeyCode::Char('x')));
+        dialog.handle_key(key(KeyCode::Char('🚀')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Cancel
+        ));
+
+        dialog.handle_key(key(KeyCode::Delete));
+        match dialog.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(data) => assert_eq!(data.icon.as_deref(), Some("")),
+            _ => panic!("Expected Submit"),
+        }
+    }
+
     fn ctrl_p() -> KeyEvent {
         KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)
     }
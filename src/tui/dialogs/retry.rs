@@ -0,0 +1,152 @@
+//! Retry dialog: edit the prompt before a failed session starts over
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use tui_textarea::TextArea;
+
+use super::DialogResult;
+use crate::tui::components::{dictionary, render_prompt_text, Dictionary};
+use crate::tui::styles::Theme;
+
+pub struct RetryDialog {
+    session_id: String,
+    session_title: String,
+    /// What resetting discards, e.g. "worktree feat/x"; `None` if the
+    /// session has no worktree aoe manages
+    resets: Option<String>,
+    text_area: TextArea<'static>,
+    dictionary: Option<&'static Dictionary>,
+}
+
+impl RetryDialog {
+    pub fn new(
+        session_id: &str,
+        session_title: &str,
+        resets: Option<String>,
+        prompt: Option<&str>,
+    ) -> Self {
+        let lines = prompt
+            .map(|p| p.lines().map(String::from).collect::<Vec<_>>())
+            .filter(|lines| !lines.is_empty())
+            .unwrap_or_else(|| vec![String::new()]);
+        let mut text_area = TextArea::new(lines);
+        text_area.set_cursor_line_style(Style::default());
+        text_area.move_cursor(tui_textarea::CursorMove::Bottom);
+        text_area.move_cursor(tui_textarea::CursorMove::End);
+
+        Self {
+            session_id: session_id.to_string(),
+            session_title: session_title.to_string(),
+            resets,
+            text_area,
+            dictionary: None,
+        }
+    }
+
+    /// Underline misspelled words, if a system dictionary is installed.
+    pub fn with_spellcheck(mut self, enabled: bool) -> Self {
+        self.dictionary = enabled.then(dictionary).flatten();
+        self
+    }
+
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    fn get_text(&self) -> String {
+        self.text_area.lines().join("\n")
+    }
+
+    /// Submits the edited prompt, which may be empty to retry without one.
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<String> {
+        match key.code {
+            KeyCode::Esc => DialogResult::Cancel,
+            KeyCode::Enter if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.text_area.insert_newline();
+                DialogResult::Continue
+            }
+            KeyCode::Enter => DialogResult::Submit(self.get_text().trim().to_string()),
+            _ => {
+                self.text_area.input(key);
+                DialogResult::Continue
+            }
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        // 2 for borders + 2 for the warning + 1 per prompt line, max 14
+        let content_lines = self.text_area.lines().len() as u16;
+        let height = (content_lines + 4).clamp(5, 14);
+        let dialog_area = super::centered_rect(area, 70, height);
+
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(format!(" Retry {} ", self.session_title))
+            .title_style(Style::default().fg(theme.accent).bold())
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Enter", Style::default().fg(theme.accent)),
+                    Span::styled(" retry ", Style::default().fg(theme.dimmed)),
+                    Span::styled("Shift+Enter", Style::default().fg(theme.accent)),
+                    Span::styled(" newline ", Style::default().fg(theme.dimmed)),
+                    Span::styled("Esc", Style::default().fg(theme.accent)),
+                    Span::styled(" cancel ", Style::default().fg(theme.dimmed)),
+                ])
+                .right_aligned(),
+            );
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(1)])
+            .split(inner);
+
+        let warning = match &self.resets {
+            Some(what) => format!(
+                "Kills the agent and discards all commits and changes in {}.",
+                what
+            ),
+            None => "Kills the agent and starts it again.".to_string(),
+        };
+        frame.render_widget(
+            Paragraph::new(warning)
+                .style(Style::default().fg(theme.waiting))
+                .wrap(Wrap { trim: true }),
+            chunks[0],
+        );
+
+        render_prompt_text(frame, chunks[1], &self.text_area, theme, self.dictionary);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_prompt_is_prefilled_and_editable() {
+        let mut dialog = RetryDialog::new("id", "Test", None, Some("fix the\nbug"));
+        dialog.handle_key(key(KeyCode::Char('s')));
+        let result = dialog.handle_key(key(KeyCode::Enter));
+        assert!(matches!(result, DialogResult::Submit(ref s) if s == "fix the\nbugs"));
+    }
+
+    #[test]
+    fn test_empty_prompt_submits() {
+        let mut dialog = RetryDialog::new("id", "Test", None, None);
+        let result = dialog.handle_key(key(KeyCode::Enter));
+        assert!(matches!(result, DialogResult::Submit(ref s) if s.is_empty()));
+        let result = dialog.handle_key(key(KeyCode::Esc));
+        assert!(matches!(result, DialogResult::Cancel));
+    }
+}
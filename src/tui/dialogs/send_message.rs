@@ -6,11 +6,13 @@ use ratatui::widgets::*;
 use tui_textarea::TextArea;
 
 use super::DialogResult;
+use crate::tui::components::{dictionary, render_prompt_text, Dictionary};
 use crate::tui::styles::Theme;
 
 pub struct SendMessageDialog {
     session_title: String,
     text_area: TextArea<'static>,
+    dictionary: Option<&'static Dictionary>,
 }
 
 impl SendMessageDialog {
@@ -21,9 +23,16 @@ impl SendMessageDialog {
         Self {
             session_title: session_title.to_string(),
             text_area,
+            dictionary: None,
         }
     }
 
+    /// Underline misspelled words, if a system dictionary is installed.
+    pub fn with_spellcheck(mut self, enabled: bool) -> Self {
+        self.dictionary = enabled.then(dictionary).flatten();
+        self
+    }
+
     fn get_text(&self) -> String {
         self.text_area.lines().join("\n")
     }
@@ -78,11 +87,7 @@ impl SendMessageDialog {
         let inner = block.inner(dialog_area);
         frame.render_widget(block, dialog_area);
 
-        let mut text_area_clone = self.text_area.clone();
-        text_area_clone.set_style(Style::default().fg(theme.text));
-        text_area_clone.set_cursor_style(Style::default().fg(theme.background).bg(theme.accent));
-
-        frame.render_widget(&text_area_clone, inner);
+        render_prompt_text(frame, inner, &self.text_area, theme, self.dictionary);
     }
 }
 
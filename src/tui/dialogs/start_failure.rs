@@ -0,0 +1,342 @@
+//! Triage dialog shown when a session fails to start
+//!
+//! Shows the captured error, a best guess at the cause, and one-key
+//! remediation actions instead of a bare error message.
+
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+use super::DialogResult;
+use crate::session::Instance;
+use crate::tui::components::render_text_field;
+use crate::tui::styles::Theme;
+
+const DIALOG_WIDTH: u16 = 72;
+
+/// Probable reason a start failed, used to pick the advice shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailureCause {
+    /// The project directory was moved or deleted.
+    PathMissing,
+    /// The agent's binary is not on PATH.
+    AgentMissing(String),
+    /// The container runtime could not start or reach the container.
+    Container,
+    /// tmux could not create the session.
+    Tmux,
+    Unknown,
+}
+
+impl FailureCause {
+    pub fn diagnose(inst: &Instance, error: &str) -> Self {
+        let lower = error.to_lowercase();
+        if !Path::new(&inst.project_path).is_dir() {
+            FailureCause::PathMissing
+        } else if lower.contains("not found in path") || lower.contains("is not installed") {
+            FailureCause::AgentMissing(inst.tool.clone())
+        } else if inst.is_sandboxed()
+            && ["container", "docker", "image", "podman"]
+                .iter()
+                .any(|w| lower.contains(w))
+        {
+            FailureCause::Container
+        } else if lower.contains("tmux") {
+            FailureCause::Tmux
+        } else {
+            FailureCause::Unknown
+        }
+    }
+
+    fn advice(&self) -> String {
+        match self {
+            FailureCause::PathMissing => {
+                "The project directory no longer exists. It may have been \
+                moved, renamed, or removed with its worktree. Press p to point the session at its \
+                new location."
+                    .to_string()
+            }
+            FailureCause::AgentMissing(tool) => format!(
+                "{} is not installed or not on PATH. Install it, or check that the shell aoe runs \
+                 from has the same PATH as your terminal.",
+                tool
+            ),
+            FailureCause::Container => "The container runtime failed. Check that it is running \
+                (`docker info`) and that the sandbox image can be pulled."
+                .to_string(),
+            FailureCause::Tmux => {
+                "tmux could not create the session. Check that tmux is installed \
+                and that `tmux ls` works from this shell."
+                    .to_string()
+            }
+            FailureCause::Unknown => "No known cause matched. The Logs tab has the session's \
+                recent events, including hook output."
+                .to_string(),
+        }
+    }
+}
+
+/// Remediation chosen in the dialog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartFailureAction {
+    Retry,
+    /// Move the session to this project directory, then retry.
+    EditPath(String),
+    /// Open the Logs tab.
+    Diagnostics,
+}
+
+pub struct StartFailureDialog {
+    pub session_id: String,
+    title: String,
+    error: String,
+    cause: FailureCause,
+    /// Set while the project path is being edited.
+    path_input: Option<Input>,
+    path_error: Option<String>,
+    project_path: String,
+}
+
+impl StartFailureDialog {
+    pub fn new(inst: &Instance, error: &str) -> Self {
+        Self {
+            session_id: inst.id.clone(),
+            title: inst.title.clone(),
+            error: error.to_string(),
+            cause: FailureCause::diagnose(inst, error),
+            path_input: None,
+            path_error: None,
+            project_path: inst.project_path.clone(),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<StartFailureAction> {
+        if let Some(input) = &mut self.path_input {
+            match key.code {
+                KeyCode::Esc => {
+                    self.path_input = None;
+                    self.path_error = None;
+                }
+                KeyCode::Enter => {
+                    let path = super::new_session::expand_tilde(input.value().trim());
+                    if Path::new(&path).is_dir() {
+                        return DialogResult::Submit(StartFailureAction::EditPath(path));
+                    }
+                    self.path_error = Some(format!("Not a directory: {}", path));
+                }
+                _ => {
+                    input.handle_event(&crossterm::event::Event::Key(key));
+                    self.path_error = None;
+                }
+            }
+            return DialogResult::Continue;
+        }
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => DialogResult::Cancel,
+            KeyCode::Char('r') | KeyCode::Enter => DialogResult::Submit(StartFailureAction::Retry),
+            KeyCode::Char('p') => {
+                self.path_input = Some(Input::new(self.project_path.clone()));
+                DialogResult::Continue
+            }
+            KeyCode::Char('l') => DialogResult::Submit(StartFailureAction::Diagnostics),
+            _ => DialogResult::Continue,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let width = DIALOG_WIDTH.min(area.width.saturating_sub(4));
+        let text_width = width.saturating_sub(4).max(1) as usize;
+        let wrapped_height = |text: &str| -> u16 {
+            text.lines()
+                .map(|l| (l.chars().count() / text_width + 1) as u16)
+                .sum::<u16>()
+                .max(1)
+        };
+
+        let advice = self.cause.advice();
+        let advice_height = wrapped_height(&advice);
+        let error_height = wrapped_height(&self.error).min(10);
+        let path_height = if self.path_input.is_some() { 3 } else { 0 };
+        // borders (2) + labels (2) + spacers (2) + actions (1)
+        let height =
+            (advice_height + error_height + path_height + 7).min(area.height.saturating_sub(2));
+        let dialog_area = super::centered_rect(area, width, height);
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.error))
+            .title(format!(" Failed to start: {} ", self.title))
+            .title_style(Style::default().fg(theme.error).bold());
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let inner = inner.inner(Margin::new(1, 0));
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(advice_height),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(path_height),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let label = |text: &'static str| {
+            Paragraph::new(Span::styled(text, Style::default().fg(theme.title).bold()))
+        };
+        frame.render_widget(label("Probable cause"), chunks[0]);
+        frame.render_widget(
+            Paragraph::new(advice)
+                .style(Style::default().fg(theme.text))
+                .wrap(Wrap { trim: true }),
+            chunks[1],
+        );
+        frame.render_widget(label("Error output"), chunks[3]);
+        frame.render_widget(
+            Paragraph::new(self.error.as_str())
+                .style(Style::default().fg(theme.dimmed))
+                .wrap(Wrap { trim: false }),
+            chunks[4],
+        );
+
+        if let Some(input) = &self.path_input {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                ])
+                .split(chunks[5]);
+            render_text_field(frame, rows[1], "Project path:", input, true, None, theme);
+            if let Some(err) = &self.path_error {
+                frame.render_widget(
+                    Paragraph::new(Span::styled(err.as_str(), Style::default().fg(theme.error))),
+                    rows[2],
+                );
+            }
+        }
+
+        let key = |k: &'static str| Span::styled(k, Style::default().fg(theme.hint));
+        let desc = |d: &'static str| Span::styled(d, Style::default().fg(theme.dimmed));
+        let actions = if self.path_input.is_some() {
+            Line::from(vec![
+                key("Enter"),
+                desc(" move session and retry  "),
+                key("Esc"),
+                desc(" back"),
+            ])
+        } else {
+            Line::from(vec![
+                key("r"),
+                desc(" retry  "),
+                key("p"),
+                desc(" edit path  "),
+                key("l"),
+                desc(" logs  "),
+                key("Esc"),
+                desc(" close"),
+            ])
+        };
+        frame.render_widget(
+            Paragraph::new(actions).alignment(Alignment::Center),
+            chunks[6],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut inst = Instance::new("t", dir.path().to_str().unwrap());
+        inst.tool = "claude".to_string();
+
+        assert_eq!(
+            FailureCause::diagnose(
+                &inst,
+                "claude is not installed: `claude` was not found in PATH"
+            ),
+            FailureCause::AgentMissing("claude".to_string())
+        );
+        assert_eq!(
+            FailureCause::diagnose(&inst, "Failed to create tmux session: no server"),
+            FailureCause::Tmux
+        );
+        assert_eq!(
+            FailureCause::diagnose(&inst, "something else"),
+            FailureCause::Unknown
+        );
+
+        inst.project_path = dir.path().join("gone").to_string_lossy().to_string();
+        assert_eq!(
+            FailureCause::diagnose(&inst, "Failed to create tmux session"),
+            FailureCause::PathMissing
+        );
+    }
+
+    #[test]
+    fn test_actions() {
+        let inst = Instance::new("t", "/nonexistent/project");
+        let mut dialog = StartFailureDialog::new(&inst, "Project path no longer exists");
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('r'))),
+            DialogResult::Submit(StartFailureAction::Retry)
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('l'))),
+            DialogResult::Submit(StartFailureAction::Diagnostics)
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+
+    #[test]
+    fn test_edit_path_rejects_missing_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let inst = Instance::new("t", "/nonexistent/project");
+        let mut dialog = StartFailureDialog::new(&inst, "Project path no longer exists");
+
+        dialog.handle_key(key(KeyCode::Char('p')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Continue
+        ));
+        assert!(dialog.path_error.is_some());
+
+        dialog.path_input = Some(Input::new(dir.path().to_string_lossy().to_string()));
+        match dialog.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(StartFailureAction::EditPath(path)) => {
+                assert_eq!(Path::new(&path), dir.path())
+            }
+            _ => panic!("expected EditPath"),
+        }
+
+        // Esc leaves path editing without closing the dialog
+        dialog.handle_key(key(KeyCode::Char('p')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Continue
+        ));
+        assert!(dialog.path_input.is_none());
+    }
+}
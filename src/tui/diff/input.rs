@@ -98,8 +98,7 @@ impl DiffView {
             // Open external editor
             (KeyCode::Char('e'), _) | (KeyCode::Enter, _) => {
                 if let Some(file) = self.selected_file() {
-                    let full_path = self.repo_path.join(&file.path);
-                    return DiffAction::EditFile(full_path);
+                    return DiffAction::EditFile(self.full_path(file));
                 }
                 DiffAction::Continue
             }
@@ -204,6 +203,7 @@ mod tests {
     fn make_diff_view_with_warning() -> DiffView {
         DiffView {
             repo_path: PathBuf::from("/tmp/fake"),
+            repos: Vec::new(),
             base_branch: "main".to_string(),
             files: Vec::new(),
             selected_file: 0,
@@ -224,6 +224,7 @@ mod tests {
     fn make_diff_view_no_warning() -> DiffView {
         DiffView {
             repo_path: PathBuf::from("/tmp/fake"),
+            repos: Vec::new(),
             base_branch: "main".to_string(),
             files: Vec::new(),
             selected_file: 0,
@@ -282,4 +283,44 @@ mod tests {
         let action = view.handle_key(key(KeyCode::Char('q')));
         assert!(matches!(action, DiffAction::Close));
     }
+
+    #[test]
+    fn test_workspace_lists_files_from_each_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut repos = Vec::new();
+        for name in ["api", "web"] {
+            let path = dir.path().join(name);
+            let repo = git2::Repository::init(&path).unwrap();
+            std::fs::write(path.join("README"), "one\n").unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(std::path::Path::new("README")).unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+                .unwrap();
+            std::fs::write(path.join("README"), "two\n").unwrap();
+            repos.push((name.to_string(), path));
+        }
+        let base = crate::git::GitWorktree::get_current_branch(&repos[0].1).unwrap();
+
+        let mut view = make_diff_view_no_warning();
+        view.repo_path = dir.path().to_path_buf();
+        view.repos = repos;
+        view.base_branch = base;
+        view.refresh_files().unwrap();
+
+        let paths: Vec<PathBuf> = view.files.iter().map(|f| f.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("api/README"), PathBuf::from("web/README")]
+        );
+        assert_eq!(
+            view.full_path(&view.files[1]),
+            dir.path().join("web").join("README")
+        );
+        view.selected_file = 1;
+        let diff = view.get_current_diff().unwrap();
+        assert_eq!(diff.file.path, PathBuf::from("web/README"));
+        assert!(!diff.hunks.is_empty());
+    }
 }
@@ -4,7 +4,7 @@ mod input;
 mod render;
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::git::diff::{
     check_merge_base_status, compute_changed_files, compute_file_diff, get_default_branch,
@@ -25,9 +25,14 @@ pub struct BranchSelectState {
 
 /// The diff view state
 pub struct DiffView {
-    /// Path to the repository root
+    /// Path to the repository root (the workspace directory for a
+    /// multi-repo workspace)
     pub(crate) repo_path: PathBuf,
 
+    /// Repositories of a multi-repo workspace, by name. Empty for a single
+    /// repository. File paths are then prefixed with the repo name.
+    pub(crate) repos: Vec<(String, PathBuf)>,
+
     /// Base branch to compare against
     pub(crate) base_branch: String,
 
@@ -74,23 +79,45 @@ pub struct DiffView {
 impl DiffView {
     /// Create a new diff view for a repository
     pub fn new(repo_path: PathBuf) -> anyhow::Result<Self> {
+        Self::with_repos(repo_path, Vec::new())
+    }
+
+    /// Create a combined diff view over the repos of a multi-repo
+    /// workspace. The first repo is the primary one: its branches are
+    /// offered as the base.
+    pub fn new_workspace(
+        workspace_dir: PathBuf,
+        repos: Vec<(String, PathBuf)>,
+    ) -> anyhow::Result<Self> {
+        if repos.is_empty() {
+            anyhow::bail!("Workspace has no repositories");
+        }
+        Self::with_repos(workspace_dir, repos)
+    }
+
+    fn with_repos(repo_path: PathBuf, repos: Vec<(String, PathBuf)>) -> anyhow::Result<Self> {
         let config = Config::load().unwrap_or_default();
+        let primary = repos
+            .first()
+            .map(|(_, path)| path.clone())
+            .unwrap_or_else(|| repo_path.clone());
 
         // Determine base branch
         let base_branch = config
             .diff
             .default_branch
             .clone()
-            .or_else(|| get_default_branch(&repo_path).ok())
+            .or_else(|| get_default_branch(&primary).ok())
             .unwrap_or_else(|| "main".to_string());
 
         let context_lines = config.diff.context_lines;
 
-        let warning_dialog = check_merge_base_status(&repo_path, &base_branch)
+        let warning_dialog = check_merge_base_status(&primary, &base_branch)
             .map(|msg| InfoDialog::new("Warning", &msg));
 
         let mut view = Self {
             repo_path,
+            repos,
             base_branch,
             files: Vec::new(),
             selected_file: 0,
@@ -111,9 +138,63 @@ impl DiffView {
         Ok(view)
     }
 
+    /// The repository whose branches are offered as the base.
+    fn primary_repo(&self) -> &Path {
+        self.repos
+            .first()
+            .map(|(_, path)| path.as_path())
+            .unwrap_or(&self.repo_path)
+    }
+
+    /// Base branch for one repo of a workspace: the selected one if the repo
+    /// has it, else the repo's own default branch.
+    fn base_for(&self, repo: &Path) -> String {
+        if repo == self.primary_repo()
+            || list_branches(repo).is_ok_and(|b| b.contains(&self.base_branch))
+        {
+            return self.base_branch.clone();
+        }
+        get_default_branch(repo).unwrap_or_else(|_| self.base_branch.clone())
+    }
+
+    /// Repo root, path within it, and base branch for a listed file.
+    fn locate(&self, path: &Path) -> (PathBuf, PathBuf, String) {
+        for (name, repo) in &self.repos {
+            if let Ok(rest) = path.strip_prefix(name) {
+                return (repo.clone(), rest.to_path_buf(), self.base_for(repo));
+            }
+        }
+        (
+            self.repo_path.clone(),
+            path.to_path_buf(),
+            self.base_branch.clone(),
+        )
+    }
+
+    /// Absolute path of a listed file.
+    pub fn full_path(&self, file: &DiffFile) -> PathBuf {
+        let (repo, path, _) = self.locate(&file.path);
+        repo.join(path)
+    }
+
     /// Refresh the list of changed files
     pub fn refresh_files(&mut self) -> anyhow::Result<()> {
-        self.files = compute_changed_files(&self.repo_path, &self.base_branch)?;
+        self.files = if self.repos.is_empty() {
+            compute_changed_files(&self.repo_path, &self.base_branch)?
+        } else {
+            let mut files = Vec::new();
+            for (name, repo) in &self.repos {
+                let base = self.base_for(repo);
+                let changed = compute_changed_files(repo, &base)
+                    .map_err(|e| anyhow::anyhow!("{}: {}", name, e))?;
+                files.extend(changed.into_iter().map(|mut file| {
+                    file.path = Path::new(name).join(&file.path);
+                    file.old_path = file.old_path.map(|p| Path::new(name).join(p));
+                    file
+                }));
+            }
+            files
+        };
         self.diff_cache.clear();
         if self.selected_file >= self.files.len() {
             self.selected_file = self.files.len().saturating_sub(1);
@@ -133,13 +214,11 @@ impl DiffView {
         let path = file.path.clone();
 
         if !self.diff_cache.contains_key(&path) {
-            match compute_file_diff(
-                &self.repo_path,
-                &path,
-                &self.base_branch,
-                self.context_lines,
-            ) {
-                Ok(diff) => {
+            let (repo, repo_file, base) = self.locate(&path);
+            match compute_file_diff(&repo, &repo_file, &base, self.context_lines) {
+                Ok(mut diff) => {
+                    diff.file.path = path.clone();
+                    diff.file.old_path = file.old_path.clone();
                     self.diff_cache.insert(path.clone(), diff);
                 }
                 Err(e) => {
@@ -154,7 +233,7 @@ impl DiffView {
 
     /// Open the branch selection dialog
     pub fn open_branch_select(&mut self) {
-        match list_branches(&self.repo_path) {
+        match list_branches(self.primary_repo()) {
             Ok(branches) => {
                 let selected = branches
                     .iter()
@@ -172,7 +251,7 @@ impl DiffView {
     pub fn select_branch(&mut self, branch: String) {
         self.base_branch = branch;
         self.branch_select = None;
-        self.warning_dialog = check_merge_base_status(&self.repo_path, &self.base_branch)
+        self.warning_dialog = check_merge_base_status(self.primary_repo(), &self.base_branch)
             .map(|msg| InfoDialog::new("Warning", &msg));
         if let Err(e) = self.refresh_files() {
             self.error_message = Some(format!("Failed to refresh: {}", e));
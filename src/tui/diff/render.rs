@@ -79,11 +79,19 @@ impl DiffView {
         let deletions: usize = self.files.iter().map(|f| f.deletions).sum();
 
         // Get repo name from path
-        let repo_name = self
-            .repo_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("repo");
+        let repo_name = if self.repos.is_empty() {
+            self.repo_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("repo")
+                .to_string()
+        } else {
+            self.repos
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+                .join(" + ")
+        };
 
         let header = Line::from(vec![
             Span::styled(
@@ -0,0 +1,61 @@
+//! Background health check runner
+//!
+//! Each probe runs on its own short-lived thread so a slow check (like
+//! `cargo check`) never delays the others or the UI. Results come back over
+//! a channel and are applied by `HomeView::apply_health_results`.
+
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crate::session::repo_config;
+
+/// Probes that run longer than this are killed and reported as failing.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Outcome of one health check run
+#[derive(Debug)]
+pub struct HealthResult {
+    pub id: String,
+    /// `None` when the check passed, otherwise a short failure detail
+    pub failure: Option<String>,
+}
+
+pub struct HealthPoller {
+    result_tx: mpsc::Sender<HealthResult>,
+    result_rx: mpsc::Receiver<HealthResult>,
+}
+
+impl HealthPoller {
+    pub fn new() -> Self {
+        let (result_tx, result_rx) = mpsc::channel();
+        Self {
+            result_tx,
+            result_rx,
+        }
+    }
+
+    /// Run `command` in `project_path` for session `id` (non-blocking).
+    pub fn request_check(&self, id: String, command: String, project_path: PathBuf) {
+        let tx = self.result_tx.clone();
+        thread::spawn(move || {
+            let failure =
+                repo_config::run_health_check(&command, &project_path, HEALTH_CHECK_TIMEOUT)
+                    .err()
+                    .map(|e| e.to_string());
+            let _ = tx.send(HealthResult { id, failure });
+        });
+    }
+
+    /// Collect all finished checks without blocking.
+    pub fn try_recv_results(&self) -> Vec<HealthResult> {
+        self.result_rx.try_iter().collect()
+    }
+}
+
+impl Default for HealthPoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
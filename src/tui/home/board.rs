@@ -0,0 +1,280 @@
+//! Board view - sessions as cards in status lanes
+//!
+//! Each session sits in the lane its status implies (queued, running,
+//! waiting, done) until the user moves it, e.g. from done to archived.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::HomeView;
+use crate::session::{BoardLane, Instance, Item, Status};
+use crate::tui::app::Action;
+use crate::tui::styles::{status_color, status_icon, Theme};
+
+/// Cursor position on the board.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(super) struct BoardState {
+    pub(super) lane: usize,
+    pub(super) row: usize,
+}
+
+/// Outcome of a key press while the board is open.
+pub(super) enum BoardKey {
+    Handled(Option<Action>),
+    /// Not a board key; fall through to the normal bindings.
+    Unhandled,
+}
+
+impl HomeView {
+    /// Sessions grouped by lane, in `BoardLane::ALL` order.
+    pub(super) fn board_lanes(&self) -> Vec<Vec<&Instance>> {
+        let mut lanes: Vec<Vec<&Instance>> = vec![Vec::new(); BoardLane::ALL.len()];
+        for inst in &self.instances {
+            if inst.status == Status::Deleting {
+                continue;
+            }
+            if let Some(profile) = &self.active_profile {
+                if inst.source_profile != *profile {
+                    continue;
+                }
+            }
+            let idx = lane_index(inst.lane());
+            lanes[idx].push(inst);
+        }
+        for lane in &mut lanes {
+            lane.sort_by_key(|i| std::cmp::Reverse(i.last_accessed_at.unwrap_or(i.created_at)));
+        }
+        lanes
+    }
+
+    /// Open the board with the selected session focused.
+    pub(super) fn open_board(&mut self) {
+        let mut state = BoardState::default();
+        if let Some(id) = &self.selected_session {
+            for (lane, cards) in self.board_lanes().iter().enumerate() {
+                if let Some(row) = cards.iter().position(|i| i.id == *id) {
+                    state = BoardState { lane, row };
+                }
+            }
+        }
+        self.board = Some(state);
+        self.sync_board_selection();
+    }
+
+    /// Clamp the board cursor to the current cards and mirror the focused
+    /// card into the list selection, so session actions (delete, rename,
+    /// send) work on it.
+    pub(super) fn sync_board_selection(&mut self) {
+        let Some(mut state) = self.board else {
+            return;
+        };
+        let id = {
+            let lanes = self.board_lanes();
+            state.lane = state.lane.min(lanes.len() - 1);
+            let cards = &lanes[state.lane];
+            state.row = state.row.min(cards.len().saturating_sub(1));
+            cards.get(state.row).map(|i| i.id.clone())
+        };
+        self.board = Some(state);
+
+        let Some(id) = id else {
+            return;
+        };
+        if let Some(pos) = self
+            .flat_items
+            .iter()
+            .position(|item| matches!(item, Item::Session { id: item_id, .. } if *item_id == id))
+        {
+            self.cursor = pos;
+        }
+        self.selected_session = Some(id);
+        self.selected_group = None;
+        self.selected_group_profile = None;
+    }
+
+    pub(super) fn handle_board_key(&mut self, key: KeyEvent) -> BoardKey {
+        let Some(mut state) = self.board else {
+            return BoardKey::Unhandled;
+        };
+        let lane_count = BoardLane::ALL.len();
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('b') => {
+                self.board = None;
+                return BoardKey::Handled(None);
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                state.lane = (state.lane + lane_count - 1) % lane_count;
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                state.lane = (state.lane + 1) % lane_count;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                state.row = state.row.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                state.row += 1;
+            }
+            KeyCode::Char('H') | KeyCode::Char('<') => {
+                self.move_board_card(-1);
+                return BoardKey::Handled(None);
+            }
+            KeyCode::Char('L') | KeyCode::Char('>') => {
+                self.move_board_card(1);
+                return BoardKey::Handled(None);
+            }
+            KeyCode::Char('A') => {
+                self.toggle_board_archive();
+                return BoardKey::Handled(None);
+            }
+            // List-only bindings that would act on the hidden list
+            KeyCode::Char('/') | KeyCode::Char('t') => return BoardKey::Handled(None),
+            KeyCode::Enter => {
+                return BoardKey::Handled(
+                    self.board_focused_id()
+                        .filter(|id| {
+                            self.get_instance(id)
+                                .is_some_and(|i| i.status != Status::Deleting)
+                        })
+                        .map(Action::AttachSession),
+                );
+            }
+            _ => return BoardKey::Unhandled,
+        }
+
+        self.board = Some(state);
+        self.sync_board_selection();
+        BoardKey::Handled(None)
+    }
+
+    /// ID of the card under the board cursor.
+    fn board_focused_id(&self) -> Option<String> {
+        let state = self.board?;
+        self.board_lanes()
+            .get(state.lane)?
+            .get(state.row)
+            .map(|i| i.id.clone())
+    }
+
+    /// Move the focused card one lane left or right, keeping it focused.
+    fn move_board_card(&mut self, delta: isize) {
+        let Some(state) = self.board else {
+            return;
+        };
+        let lane_count = BoardLane::ALL.len() as isize;
+        let target = state.lane as isize + delta;
+        if !(0..lane_count).contains(&target) {
+            return;
+        }
+        self.set_focused_lane(BoardLane::ALL[target as usize]);
+    }
+
+    /// Archive the focused card, or send an archived card back to the lane
+    /// its status implies.
+    fn toggle_board_archive(&mut self) {
+        let Some(lane) = self
+            .board_focused_id()
+            .and_then(|id| self.get_instance(&id))
+            .map(|i| {
+                if i.lane() == BoardLane::Archived {
+                    BoardLane::for_status(i.status)
+                } else {
+                    BoardLane::Archived
+                }
+            })
+        else {
+            return;
+        };
+        self.set_focused_lane(lane);
+    }
+
+    fn set_focused_lane(&mut self, lane: BoardLane) {
+        let Some(id) = self.board_focused_id() else {
+            return;
+        };
+        self.mutate_instance(&id, |inst| inst.set_lane(lane));
+        if let Err(e) = self.save() {
+            tracing::error!("Failed to save board lane: {}", e);
+        }
+
+        let row = self.board_lanes()[lane_index(lane)]
+            .iter()
+            .position(|i| i.id == id)
+            .unwrap_or(0);
+        self.board = Some(BoardState {
+            lane: lane_index(lane),
+            row,
+        });
+        self.sync_board_selection();
+    }
+
+    pub(super) fn render_board(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Some(state) = self.board else {
+            return;
+        };
+        let lanes = self.board_lanes();
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, lanes.len() as u32); lanes.len()])
+            .split(area);
+
+        for (idx, (lane, cards)) in BoardLane::ALL.iter().zip(&lanes).enumerate() {
+            let focused = idx == state.lane;
+            let border = if focused { theme.accent } else { theme.border };
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border))
+                .title(format!(" {} ({}) ", lane.label(), cards.len()))
+                .title_style(Style::default().fg(theme.title).bold());
+            let inner = block.inner(columns[idx]);
+            frame.render_widget(block, columns[idx]);
+
+            // Two lines per card plus a spacer; scroll to keep the focused
+            // card visible.
+            let per_page = (inner.height as usize / 3).max(1);
+            let skip = if focused && state.row >= per_page {
+                state.row + 1 - per_page
+            } else {
+                0
+            };
+
+            let mut lines = Vec::new();
+            for (row, inst) in cards.iter().enumerate().skip(skip).take(per_page) {
+                let selected = focused && row == state.row;
+                let title_style = if selected {
+                    Style::default()
+                        .fg(theme.text)
+                        .bg(theme.session_selection)
+                        .bold()
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("{} ", status_icon(inst.status)),
+                        Style::default().fg(status_color(inst.status, theme)),
+                    ),
+                    Span::styled(inst.title.clone(), title_style),
+                ]));
+                let detail = match &inst.worktree_info {
+                    Some(wt) => format!("  {} @ {}", inst.tool, wt.branch),
+                    None => format!("  {}", inst.tool),
+                };
+                lines.push(Line::styled(detail, Style::default().fg(theme.dimmed)));
+                lines.push(Line::from(""));
+            }
+            if cards.is_empty() {
+                lines.push(Line::styled("  (empty)", Style::default().fg(theme.dimmed)));
+            }
+            frame.render_widget(Paragraph::new(lines), inner);
+        }
+    }
+}
+
+fn lane_index(lane: BoardLane) -> usize {
+    BoardLane::ALL
+        .iter()
+        .position(|l| *l == lane)
+        .unwrap_or_default()
+}
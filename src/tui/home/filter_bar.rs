@@ -0,0 +1,404 @@
+//! Filter bar for the session list
+//!
+//! `f` opens a one-line bar at the bottom of the list. The expression (see
+//! `session::filter`) is applied as it is typed; saved filters are listed in
+//! a dropdown above the bar and stored per profile with the UI state.
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+use super::HomeView;
+use crate::session::filter::{SavedFilter, SessionFilter};
+use crate::tui::styles::Theme;
+
+/// Most saved filters shown in the dropdown at once.
+const DROPDOWN_ROWS: usize = 8;
+
+pub(super) struct FilterBar {
+    input: Input,
+    /// Filter that was active when the bar opened, restored on Esc
+    previous: String,
+    error: Option<String>,
+    /// Highlighted saved filter while the dropdown is open
+    dropdown: Option<usize>,
+    /// Name being typed for Ctrl+S
+    naming: Option<Input>,
+}
+
+/// What a key press in the bar asks the home view to do.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum FilterBarEvent {
+    Continue,
+    /// The query changed; apply it live.
+    Changed(String),
+    /// Keep the current query and close the bar.
+    Apply,
+    /// Restore this query and close the bar.
+    Cancel(String),
+    Save(SavedFilter),
+    Delete(String),
+}
+
+impl FilterBar {
+    pub(super) fn new(query: &str) -> Self {
+        Self {
+            input: Input::new(query.to_string()),
+            previous: query.to_string(),
+            error: None,
+            dropdown: None,
+            naming: None,
+        }
+    }
+
+    pub(super) fn handle_key(&mut self, key: KeyEvent, saved: &[SavedFilter]) -> FilterBarEvent {
+        if let Some(name) = &mut self.naming {
+            match key.code {
+                KeyCode::Esc => self.naming = None,
+                KeyCode::Enter => {
+                    let name = name.value().trim().to_string();
+                    if name.is_empty() {
+                        return FilterBarEvent::Continue;
+                    }
+                    self.naming = None;
+                    return FilterBarEvent::Save(SavedFilter {
+                        name,
+                        query: self.input.value().trim().to_string(),
+                    });
+                }
+                _ => {
+                    name.handle_event(&Event::Key(key));
+                }
+            }
+            return FilterBarEvent::Continue;
+        }
+
+        if let Some(idx) = self.dropdown {
+            match key.code {
+                KeyCode::Esc => self.dropdown = None,
+                KeyCode::Up | KeyCode::BackTab => {
+                    self.dropdown =
+                        Some(idx.checked_sub(1).unwrap_or(saved.len().saturating_sub(1)))
+                }
+                KeyCode::Down | KeyCode::Tab => {
+                    self.dropdown = Some(if idx + 1 >= saved.len() { 0 } else { idx + 1 })
+                }
+                KeyCode::Enter => {
+                    self.dropdown = None;
+                    if let Some(filter) = saved.get(idx) {
+                        self.input = Input::new(filter.query.clone());
+                        return self.changed();
+                    }
+                }
+                KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    if let Some(filter) = saved.get(idx) {
+                        // Keep the highlight on the row that takes its place
+                        self.dropdown = (saved.len() > 1).then(|| idx.min(saved.len() - 2));
+                        return FilterBarEvent::Delete(filter.name.clone());
+                    }
+                }
+                _ => {}
+            }
+            return FilterBarEvent::Continue;
+        }
+
+        match key.code {
+            KeyCode::Esc => FilterBarEvent::Cancel(self.previous.clone()),
+            KeyCode::Enter => {
+                if self.error.is_some() {
+                    FilterBarEvent::Continue
+                } else {
+                    FilterBarEvent::Apply
+                }
+            }
+            KeyCode::Down | KeyCode::Tab if !saved.is_empty() => {
+                let current = saved
+                    .iter()
+                    .position(|f| f.query == self.input.value().trim())
+                    .unwrap_or(0);
+                self.dropdown = Some(current);
+                FilterBarEvent::Continue
+            }
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if self.error.is_none() && !self.input.value().trim().is_empty() {
+                    let name = saved
+                        .iter()
+                        .find(|f| f.query == self.input.value().trim())
+                        .map(|f| f.name.clone())
+                        .unwrap_or_default();
+                    self.naming = Some(Input::new(name));
+                }
+                FilterBarEvent::Continue
+            }
+            _ => {
+                let before = self.input.value().to_string();
+                self.input.handle_event(&Event::Key(key));
+                if self.input.value() == before {
+                    FilterBarEvent::Continue
+                } else {
+                    self.changed()
+                }
+            }
+        }
+    }
+
+    fn changed(&mut self) -> FilterBarEvent {
+        let query = self.input.value().trim().to_string();
+        self.error = SessionFilter::parse(&query).err();
+        if self.error.is_some() {
+            FilterBarEvent::Continue
+        } else {
+            FilterBarEvent::Changed(query)
+        }
+    }
+
+    /// Render the bar on the bottom row of `area`, with the dropdown above it.
+    pub(super) fn render(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        saved: &[SavedFilter],
+        match_count: usize,
+        theme: &Theme,
+    ) {
+        let bar = Rect {
+            y: area.y + area.height.saturating_sub(1),
+            height: 1.min(area.height),
+            ..area
+        };
+        frame.render_widget(Clear, bar);
+
+        let text_style = Style::default().fg(theme.search);
+        let mut spans = match &self.naming {
+            Some(name) => {
+                let mut spans = vec![Span::styled("Save filter as: ", text_style)];
+                spans.extend(input_spans(name, theme));
+                spans
+            }
+            None => {
+                let mut spans = vec![Span::styled("filter: ", text_style)];
+                spans.extend(input_spans(&self.input, theme));
+                spans
+            }
+        };
+        match &self.error {
+            Some(error) if self.naming.is_none() => {
+                spans.push(Span::styled(
+                    format!("  {}", error),
+                    Style::default().fg(theme.error),
+                ));
+            }
+            _ => spans.push(Span::styled(
+                format!("  [{}]", match_count),
+                Style::default().fg(theme.dimmed),
+            )),
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), bar);
+
+        let Some(selected) = self.dropdown else {
+            return;
+        };
+        let rows = saved.len().min(DROPDOWN_ROWS);
+        let height = (rows as u16 + 2).min(area.height.saturating_sub(1));
+        let dropdown = Rect {
+            y: bar.y.saturating_sub(height),
+            height,
+            ..area
+        };
+        let first = selected.saturating_sub(rows.saturating_sub(1));
+        let items: Vec<ListItem> = saved
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(rows)
+            .map(|(idx, filter)| {
+                let style = if idx == selected {
+                    Style::default().bg(theme.session_selection)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(filter.name.clone(), Style::default().fg(theme.accent)),
+                    Span::styled(
+                        format!("  {}", filter.query),
+                        Style::default().fg(theme.dimmed),
+                    ),
+                ]))
+                .style(style)
+            })
+            .collect();
+        frame.render_widget(Clear, dropdown);
+        frame.render_widget(
+            List::new(items).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.border))
+                    .title(" Saved filters (Enter use, Ctrl+D delete) ")
+                    .title_style(Style::default().fg(theme.dimmed)),
+            ),
+            dropdown,
+        );
+    }
+}
+
+fn input_spans<'a>(input: &Input, theme: &Theme) -> Vec<Span<'a>> {
+    let value = input.value();
+    let cursor = input.visual_cursor();
+    let text_style = Style::default().fg(theme.search);
+    let before: String = value.chars().take(cursor).collect();
+    let at: String = value
+        .chars()
+        .nth(cursor)
+        .map(|c| c.to_string())
+        .unwrap_or_else(|| " ".to_string());
+    let after: String = value.chars().skip(cursor + 1).collect();
+    vec![
+        Span::styled(before, text_style),
+        Span::styled(at, Style::default().fg(theme.background).bg(theme.search)),
+        Span::styled(after, text_style),
+    ]
+}
+
+impl HomeView {
+    pub(super) fn open_filter_bar(&mut self) {
+        self.filter_bar = Some(FilterBar::new(&self.filter_query));
+    }
+
+    /// Show only sessions matching `query` (empty shows everything).
+    pub(super) fn set_filter(&mut self, query: &str) {
+        self.filter_query = query.trim().to_string();
+        self.filter = SessionFilter::parse(&self.filter_query)
+            .ok()
+            .filter(|f| !f.is_empty());
+        let selected = self.selected_session.clone();
+        self.flat_items = self.build_flat_items();
+        match selected {
+            Some(id)
+                if self.flat_items.iter().any(
+                    |item| matches!(item, crate::session::Item::Session { id: i, .. } if *i == id),
+                ) =>
+            {
+                self.select_session_by_id(&id);
+            }
+            _ => {
+                self.cursor = self.cursor.min(self.flat_items.len().saturating_sub(1));
+                self.update_selected();
+            }
+        }
+    }
+
+    pub(super) fn handle_filter_bar_key(&mut self, key: KeyEvent) {
+        let Some(bar) = &mut self.filter_bar else {
+            return;
+        };
+        match bar.handle_key(key, &self.saved_filters) {
+            FilterBarEvent::Continue => {}
+            FilterBarEvent::Changed(query) => self.set_filter(&query),
+            FilterBarEvent::Apply => self.filter_bar = None,
+            FilterBarEvent::Cancel(previous) => {
+                self.filter_bar = None;
+                self.set_filter(&previous);
+            }
+            FilterBarEvent::Save(filter) => {
+                let name = filter.name.clone();
+                match self.saved_filters.iter_mut().find(|f| f.name == name) {
+                    Some(existing) => *existing = filter,
+                    None => self.saved_filters.push(filter),
+                }
+                self.save_ui_state();
+                self.show_toast(crate::tui::components::Toast::info(format!(
+                    "Saved filter '{}'",
+                    name
+                )));
+            }
+            FilterBarEvent::Delete(name) => {
+                self.saved_filters.retain(|f| f.name != name);
+                self.save_ui_state();
+            }
+        }
+    }
+
+    /// Sessions shown under the active filter.
+    pub(super) fn filtered_session_count(&self) -> usize {
+        match &self.filter {
+            Some(filter) => self.instances.iter().filter(|i| filter.matches(i)).count(),
+            None => self.instances.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_str(bar: &mut FilterBar, text: &str) -> FilterBarEvent {
+        let mut last = FilterBarEvent::Continue;
+        for c in text.chars() {
+            last = bar.handle_key(key(KeyCode::Char(c)), &[]);
+        }
+        last
+    }
+
+    #[test]
+    fn test_invalid_query_is_not_applied() {
+        let mut bar = FilterBar::new("");
+        assert_eq!(type_str(&mut bar, "status:wai"), FilterBarEvent::Continue);
+        assert!(bar.error.is_some());
+        assert_eq!(
+            bar.handle_key(key(KeyCode::Enter), &[]),
+            FilterBarEvent::Continue
+        );
+        assert_eq!(
+            type_str(&mut bar, "ting"),
+            FilterBarEvent::Changed("status:waiting".to_string())
+        );
+        assert_eq!(
+            bar.handle_key(key(KeyCode::Enter), &[]),
+            FilterBarEvent::Apply
+        );
+    }
+
+    #[test]
+    fn test_save_and_pick_saved_filter() {
+        let saved = vec![
+            SavedFilter {
+                name: "blocked".to_string(),
+                query: "status:waiting,error".to_string(),
+            },
+            SavedFilter {
+                name: "api".to_string(),
+                query: "project:api".to_string(),
+            },
+        ];
+        let mut bar = FilterBar::new("tag:ui");
+        bar.handle_key(
+            KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL),
+            &saved,
+        );
+        type_str(&mut bar, "ui");
+        assert_eq!(
+            bar.handle_key(key(KeyCode::Enter), &saved),
+            FilterBarEvent::Save(SavedFilter {
+                name: "ui".to_string(),
+                query: "tag:ui".to_string()
+            })
+        );
+
+        bar.handle_key(key(KeyCode::Down), &saved);
+        bar.handle_key(key(KeyCode::Down), &saved);
+        assert_eq!(
+            bar.handle_key(key(KeyCode::Enter), &saved),
+            FilterBarEvent::Changed("project:api".to_string())
+        );
+        assert_eq!(
+            bar.handle_key(key(KeyCode::Esc), &saved),
+            FilterBarEvent::Cancel("tag:ui".to_string())
+        );
+    }
+}
@@ -4,17 +4,24 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
 use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
+use super::board::BoardKey;
+use super::tabs::Tab;
 use super::{HomeView, TerminalMode, ViewMode};
-use crate::session::config::{load_config, save_config, SortOrder};
-use crate::session::{list_profiles, repo_config, resolve_config, Item, Status};
+use crate::session::config::SortOrder;
+use crate::session::{list_profiles, repo_config, resolve_config, Instance, Item, Status};
 use crate::tui::app::Action;
+use crate::tui::demo::DEMO_UNAVAILABLE;
 use crate::tui::dialogs::{
-    ConfirmDialog, DeleteDialogConfig, DialogResult, GroupDeleteOptionsDialog, HookTrustAction,
-    HooksInstallDialog, InfoDialog, NewSessionData, NewSessionDialog, ProfilePickerAction,
-    RenameDialog, SendMessageDialog, UnifiedDeleteDialog,
+    BulkRenameDialog, CommandMenuAction, CommandPaletteDialog, ConfirmDialog, DeleteDialogConfig,
+    DialogResult, DuplicatePromptAction, DuplicatePromptDialog, GroupDeleteOptionsDialog,
+    HookTrustAction, HooksInstallDialog, InfoDialog, NewSessionData, NewSessionDialog,
+    ProfilePickerAction, RenameDialog, SendMessageDialog, StartFailureAction, UnifiedDeleteDialog,
 };
 use crate::tui::diff::{DiffAction, DiffView};
-use crate::tui::settings::{SettingsAction, SettingsView};
+use crate::tui::settings::SettingsAction;
+
+/// Lines Shift+Up/Down scroll the output preview
+const PREVIEW_SCROLL_STEP: i32 = 3;
 
 impl HomeView {
     pub fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
@@ -35,6 +42,7 @@ impl HomeView {
                             settings.force_close();
                         }
                         self.settings_view = None;
+                        self.tab = Tab::Sessions;
                         self.confirm_dialog = None;
                         self.settings_close_confirm = false;
                         // Revert theme to saved config (undo any preview)
@@ -54,14 +62,27 @@ impl HomeView {
             }
         }
 
-        // Handle settings view (full-screen takeover)
-        if let Some(ref mut settings) = self.settings_view {
+        // Handle the settings tab. Number keys switch tabs unless settings is
+        // capturing input; the editor stays open in the background.
+        if let (Tab::Settings, Some(settings)) = (self.tab, self.settings_view.as_mut()) {
+            let switch_to = match key.code {
+                KeyCode::Char(c) if !settings.is_capturing_input() => Tab::from_digit(c),
+                _ => None,
+            };
+            if let Some(tab) = switch_to {
+                self.switch_tab(tab);
+                return None;
+            }
             match settings.handle_key(key) {
                 SettingsAction::Continue => {
                     return None;
                 }
+                SettingsAction::Saved => {
+                    return Some(Action::ConfigChanged);
+                }
                 SettingsAction::Close => {
                     self.settings_view = None;
+                    self.tab = Tab::Sessions;
                     // Refresh config-dependent state in case settings changed
                     self.refresh_from_config();
                     // Reload theme from saved config
@@ -142,6 +163,45 @@ impl HomeView {
             return None;
         }
 
+        if let Some(dialog) = &mut self.start_failure_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => self.start_failure_dialog = None,
+                DialogResult::Submit(action) => {
+                    let session_id = dialog.session_id.clone();
+                    self.start_failure_dialog = None;
+                    match action {
+                        StartFailureAction::Retry => {
+                            return Some(Action::AttachSession(session_id))
+                        }
+                        StartFailureAction::EditPath(path) => {
+                            self.mutate_instance(&session_id, |inst| inst.project_path = path);
+                            if let Err(e) = self.save() {
+                                tracing::error!("Failed to save project path: {}", e);
+                            }
+                            return Some(Action::AttachSession(session_id));
+                        }
+                        StartFailureAction::Diagnostics => self.switch_tab(Tab::Logs),
+                    }
+                }
+            }
+            return None;
+        }
+
+        if let Some(dialog) = &mut self.permission_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => self.permission_dialog = None,
+                DialogResult::Submit(choice) => {
+                    let session_id = dialog.session_id.clone();
+                    let prompt = dialog.prompt.clone();
+                    self.permission_dialog = None;
+                    self.answer_permission(&session_id, &prompt, choice);
+                }
+            }
+            return None;
+        }
+
         // Handle other dialog input
         if self.show_help {
             if matches!(
@@ -153,6 +213,32 @@ impl HomeView {
             return None;
         }
 
+        if let Some(dialog) = &mut self.duplicate_prompt_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => {
+                    self.duplicate_prompt_dialog = None;
+                    self.pending_duplicate_data = None;
+                }
+                DialogResult::Submit(action) => {
+                    self.duplicate_prompt_dialog = None;
+                    let data = self.pending_duplicate_data.take();
+                    match action {
+                        DuplicatePromptAction::SwitchTo(session_id) => {
+                            self.new_dialog = None;
+                            self.select_session_by_id(&session_id);
+                        }
+                        DuplicatePromptAction::CreateAnyway => {
+                            if let Some(data) = data {
+                                return self.submit_new_session(data);
+                            }
+                        }
+                    }
+                }
+            }
+            return None;
+        }
+
         if let Some(dialog) = &mut self.hooks_install_dialog {
             match dialog.handle_key(key) {
                 DialogResult::Continue => {}
@@ -232,31 +318,20 @@ impl HomeView {
                     }
                 }
                 DialogResult::Submit(data) => {
-                    // Check if the tool uses hooks and user hasn't acknowledged yet
-                    let tool_name = if data.tool.is_empty() {
-                        "claude".to_string()
-                    } else {
-                        data.tool.clone()
-                    };
-                    let has_hooks = crate::agents::get_agent(&tool_name)
-                        .and_then(|a| a.hook_config.as_ref())
-                        .is_some();
-
-                    if has_hooks {
-                        let acknowledged = crate::session::config::load_config()
-                            .ok()
-                            .flatten()
-                            .map(|c| c.app_state.has_acknowledged_agent_hooks)
-                            .unwrap_or(false);
-
-                        if !acknowledged {
-                            self.hooks_install_dialog = Some(HooksInstallDialog::new(&tool_name));
-                            self.pending_hooks_install_data = Some(data);
-                            return None;
-                        }
+                    let duplicate = data.initial_prompt.as_deref().and_then(|prompt| {
+                        crate::session::duplicates::find_duplicate(
+                            &self.instances,
+                            &data.path,
+                            prompt,
+                            chrono::Utc::now(),
+                        )
+                    });
+                    if let Some(duplicate) = duplicate {
+                        self.duplicate_prompt_dialog = Some(DuplicatePromptDialog::new(duplicate));
+                        self.pending_duplicate_data = Some(data);
+                        return None;
                     }
-
-                    return self.continue_session_creation(data);
+                    return self.submit_new_session(data);
                 }
             }
             return None;
@@ -268,6 +343,10 @@ impl HomeView {
                 DialogResult::Cancel => {
                     self.confirm_dialog = None;
                     self.pending_stop_session = None;
+                    self.pending_steal_session = None;
+                    self.pending_guarded_prompt = None;
+                    self.pending_merged_cleanup = None;
+                    self.pending_conflict_prompt = None;
                 }
                 DialogResult::Submit(_) => {
                     let action = dialog.action().to_string();
@@ -280,6 +359,22 @@ impl HomeView {
                         if let Some(session_id) = self.pending_stop_session.take() {
                             return Some(Action::StopSession(session_id));
                         }
+                    } else if action == "steal_session" {
+                        if let Some(session_id) = self.pending_steal_session.take() {
+                            self.steal_session(&session_id);
+                        }
+                    } else if action == "send_guarded_prompt" {
+                        self.send_guarded_prompt();
+                    } else if action == "purge_archive" {
+                        self.purge_selected_archive();
+                    } else if action == "archive_merged" {
+                        if let Some(session_id) = self.pending_merged_cleanup.take() {
+                            self.archive_merged_session(&session_id);
+                        }
+                    } else if action == "send_conflict_prompt" {
+                        if let Some((session_id, prompt)) = self.pending_conflict_prompt.take() {
+                            self.submit_prompt(session_id, prompt);
+                        }
                     }
                 }
             }
@@ -334,6 +429,7 @@ impl HomeView {
                         &data.title,
                         data.group.as_deref(),
                         data.profile.as_deref(),
+                        data.icon.as_deref(),
                     ) {
                         tracing::error!("Failed to rename session: {}", e);
                     }
@@ -342,6 +438,20 @@ impl HomeView {
             return None;
         }
 
+        if let Some(dialog) = &mut self.bulk_rename_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => {
+                    self.bulk_rename_dialog = None;
+                }
+                DialogResult::Submit(renames) => {
+                    self.bulk_rename_dialog = None;
+                    self.bulk_rename(&renames);
+                }
+            }
+            return None;
+        }
+
         if let Some(dialog) = &mut self.profile_picker_dialog {
             match dialog.handle_key(key) {
                 DialogResult::Continue => {}
@@ -358,18 +468,20 @@ impl HomeView {
                         } else {
                             Some(name)
                         };
-                        if let Err(e) = self.switch_profile(profile) {
-                            tracing::error!("Failed to switch profile: {}", e);
+                        match self.switch_profile(profile) {
+                            Ok(()) => return Some(Action::ConfigChanged),
+                            Err(e) => tracing::error!("Failed to switch profile: {}", e),
                         }
                     }
                     ProfilePickerAction::Created(name) => {
                         self.profile_picker_dialog = None;
                         match crate::session::create_profile(&name) {
-                            Ok(()) => {
-                                if let Err(e) = self.switch_profile(Some(name)) {
-                                    tracing::error!("Failed to switch to new profile: {}", e);
+                            Ok(()) => match self.switch_profile(Some(name)) {
+                                Ok(()) => return Some(Action::ConfigChanged),
+                                Err(e) => {
+                                    tracing::error!("Failed to switch to new profile: {}", e)
                                 }
-                            }
+                            },
                             Err(e) => {
                                 self.info_dialog = Some(InfoDialog::new(
                                     "Error",
@@ -397,6 +509,13 @@ impl HomeView {
             return None;
         }
 
+        if let Some(dialog) = &mut self.merge_queue_dialog {
+            if let DialogResult::Cancel | DialogResult::Submit(_) = dialog.handle_key(key) {
+                self.merge_queue_dialog = None;
+            }
+            return None;
+        }
+
         // Send message dialog
         if let Some(dialog) = &mut self.send_message_dialog {
             match dialog.handle_key(key) {
@@ -408,30 +527,95 @@ impl HomeView {
                 DialogResult::Submit(message) => {
                     self.send_message_dialog = None;
                     if let Some(session_id) = self.pending_send_session.take() {
-                        if let Some(inst) = self.get_instance(&session_id) {
-                            match crate::tmux::Session::new(&inst.id, &inst.title) {
-                                Ok(tmux_session) => {
-                                    if let Err(e) = tmux_session.send_keys(&message) {
-                                        self.info_dialog = Some(InfoDialog::new(
-                                            "Send Failed",
-                                            &format!("Failed to send message: {}", e),
-                                        ));
-                                    }
-                                }
-                                Err(e) => {
-                                    self.info_dialog = Some(InfoDialog::new(
-                                        "Send Failed",
-                                        &format!("Failed to resolve session: {}", e),
-                                    ));
-                                }
-                            }
-                        }
+                        self.submit_prompt(session_id, message);
                     }
                 }
             }
             return None;
         }
 
+        // Retry dialog
+        if let Some(dialog) = &mut self.retry_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => self.retry_dialog = None,
+                DialogResult::Submit(prompt) => {
+                    let id = dialog.session_id().to_string();
+                    self.retry_dialog = None;
+                    self.retry_session(&id, prompt);
+                }
+            }
+            return None;
+        }
+
+        // Checkpoints: record, view, or roll back to a milestone
+        if let Some(dialog) = &mut self.checkpoints_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => self.checkpoints_dialog = None,
+                DialogResult::Submit(action) => {
+                    let id = dialog.session_id().to_string();
+                    self.apply_checkpoint_action(&id, action);
+                }
+            }
+            return None;
+        }
+
+        // Processes: inspect the session's process tree and signal one
+        if let Some(dialog) = &mut self.processes_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => self.processes_dialog = None,
+                DialogResult::Submit(action) => self.apply_process_action(action),
+            }
+            return None;
+        }
+
+        // Command menu: run a named command or view the last one's output
+        if let Some(dialog) = &mut self.command_menu {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => self.command_menu = None,
+                DialogResult::Submit(action) => {
+                    let id = dialog.session_id().to_string();
+                    self.command_menu = None;
+                    match action {
+                        CommandMenuAction::Run(name) => self.run_named_command(&id, &name),
+                        CommandMenuAction::ViewOutput => return Some(Action::AttachCommand(id)),
+                    }
+                }
+            }
+            return None;
+        }
+
+        // Command palette: run the picked command as if its key was pressed
+        if let Some(dialog) = &mut self.command_palette {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => self.command_palette = None,
+                DialogResult::Submit(command_key) => {
+                    self.command_palette = None;
+                    return self.handle_key(command_key);
+                }
+            }
+            return None;
+        }
+
+        if self.tab != Tab::Sessions {
+            return self.handle_tab_key(key);
+        }
+
+        if self.board.is_some() {
+            if let BoardKey::Handled(action) = self.handle_board_key(key) {
+                return action;
+            }
+        }
+
+        if self.filter_bar.is_some() {
+            self.handle_filter_bar_key(key);
+            return None;
+        }
+
         // Search mode
         if self.search_active {
             match key.code {
@@ -463,21 +647,45 @@ impl HomeView {
                     self.search_matches.clear();
                     self.search_match_index = 0;
                     self.search_query = Input::default();
+                } else if self.filter.is_some() {
+                    self.set_filter("");
                 }
             }
             KeyCode::Char('q') => return Some(Action::Quit),
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.command_palette = Some(CommandPaletteDialog::new());
+            }
             KeyCode::Char('?') => {
                 self.show_help = true;
             }
+            KeyCode::Tab => self.switch_tab(self.tab.next()),
+            KeyCode::BackTab => self.switch_tab(self.tab.prev()),
+            KeyCode::Char(c) if Tab::from_digit(c).is_some() => {
+                if let Some(tab) = Tab::from_digit(c) {
+                    self.switch_tab(tab);
+                }
+            }
             KeyCode::Char('P') => {
                 self.show_profile_picker();
             }
+            KeyCode::Char('b') => {
+                self.open_board();
+            }
             KeyCode::Char('t') => {
                 self.view_mode = match self.view_mode {
                     ViewMode::Agent => ViewMode::Terminal,
                     ViewMode::Terminal => ViewMode::Agent,
                 };
             }
+            KeyCode::Char('a') if self.view_mode == ViewMode::Agent => {
+                self.show_timeline = !self.show_timeline;
+                self.show_events = false;
+            }
+            KeyCode::Char('z') => self.cycle_layout(),
+            KeyCode::Char('v') if self.view_mode == ViewMode::Agent => {
+                self.show_events = !self.show_events;
+                self.show_timeline = false;
+            }
             KeyCode::Char('c') => {
                 // Toggle container/host terminal mode (only in Terminal view for sandboxed sessions)
                 if self.view_mode == ViewMode::Terminal {
@@ -500,6 +708,7 @@ impl HomeView {
                 self.search_active = true;
                 self.search_query = Input::default();
             }
+            KeyCode::Char('f') => self.open_filter_bar(),
             KeyCode::Char('n') => {
                 if !self.search_matches.is_empty() {
                     self.search_match_index =
@@ -517,13 +726,15 @@ impl HomeView {
                         .unwrap_or_else(|| "default".to_string());
                     let profiles =
                         list_profiles().unwrap_or_else(|_| vec![current_profile.clone()]);
-                    self.new_dialog = Some(NewSessionDialog::new(
+                    let mut dialog = NewSessionDialog::new(
                         self.available_tools.clone(),
                         existing_titles,
                         existing_groups,
                         &current_profile,
                         profiles,
-                    ));
+                    );
+                    dialog.set_existing_sessions(self.existing_session_paths());
+                    self.new_dialog = Some(dialog);
                 }
             }
             KeyCode::Char('N') => {
@@ -578,6 +789,7 @@ impl HomeView {
                             &current_profile,
                             profiles,
                         );
+                        dialog.set_existing_sessions(self.existing_session_paths());
                         if let Some(path) = prefill_path {
                             dialog.set_path(path);
                         }
@@ -589,25 +801,11 @@ impl HomeView {
                 }
             }
             KeyCode::Char('s') => {
-                // Open settings view with selected session's project path (if any)
-                let project_path = self
-                    .selected_session
-                    .as_ref()
-                    .and_then(|id| self.get_instance(id))
-                    .map(|inst| inst.project_path.clone());
-                match SettingsView::new(
-                    self.active_profile.as_deref().unwrap_or("default"),
-                    project_path,
-                ) {
-                    Ok(view) => self.settings_view = Some(view),
-                    Err(e) => {
-                        tracing::error!("Failed to open settings: {}", e);
-                        self.info_dialog = Some(InfoDialog::new(
-                            "Error",
-                            &format!("Failed to open settings: {}", e),
-                        ));
-                    }
-                }
+                // Open settings with the selected session's project path (if any)
+                self.switch_tab(Tab::Settings);
+            }
+            KeyCode::Char('M') => {
+                self.open_merge_queue();
             }
             KeyCode::Char('D') => {
                 // Open diff view - requires a selected session
@@ -626,7 +824,17 @@ impl HomeView {
                 };
 
                 let repo_path = std::path::PathBuf::from(&inst.project_path);
-                match DiffView::new(repo_path) {
+                let view = match &inst.workspace_info {
+                    Some(ws) => DiffView::new_workspace(
+                        repo_path,
+                        ws.repos
+                            .iter()
+                            .map(|r| (r.name.clone(), r.worktree_path.clone().into()))
+                            .collect(),
+                    ),
+                    None => DiffView::new(repo_path),
+                };
+                match view {
                     Ok(view) => self.diff_view = Some(view),
                     Err(e) => {
                         tracing::error!("Failed to open diff view: {}", e);
@@ -637,14 +845,40 @@ impl HomeView {
                     }
                 }
             }
+            KeyCode::Char('e') => {
+                if let Some(id) = &self.selected_session {
+                    return Some(Action::EditNotes(id.clone()));
+                }
+            }
+            KeyCode::Char('A') if self.view_mode == ViewMode::Agent => {
+                if let Some(id) = &self.selected_session {
+                    if let Some(inst) = self.get_instance(id) {
+                        if matches!(inst.status, Status::Stopped | Status::Deleting) {
+                            return None;
+                        }
+                        return Some(Action::Reauthenticate(id.clone()));
+                    }
+                }
+            }
+            KeyCode::Char('y') if self.view_mode == ViewMode::Agent => {
+                if let Some(id) = self.selected_session.clone() {
+                    if self.confirm_steal(&id) {
+                        return None;
+                    }
+                    self.open_permission_dialog(&id);
+                }
+            }
             KeyCode::Char('x') => {
-                if let Some(session_id) = &self.selected_session {
-                    if let Some(inst) = self.get_instance(session_id) {
+                if let Some(session_id) = self.selected_session.clone() {
+                    if self.confirm_steal(&session_id) {
+                        return None;
+                    }
+                    if let Some(inst) = self.get_instance(&session_id) {
                         if inst.status == Status::Stopped || inst.status == Status::Deleting {
                             return None;
                         }
                         let message = format!("Are you sure you want to stop '{}'?", inst.title);
-                        self.pending_stop_session = Some(session_id.clone());
+                        self.pending_stop_session = Some(session_id);
                         self.confirm_dialog =
                             Some(ConfirmDialog::new("Stop Session", &message, "stop_session"));
                     }
@@ -659,8 +893,11 @@ impl HomeView {
                     ));
                     return None;
                 }
-                if let Some(session_id) = &self.selected_session {
-                    if let Some(inst) = self.get_instance(session_id) {
+                if let Some(session_id) = self.selected_session.clone() {
+                    if self.confirm_steal(&session_id) {
+                        return None;
+                    }
+                    if let Some(inst) = self.get_instance(&session_id) {
                         if inst.status == Status::Deleting {
                             return None;
                         }
@@ -673,6 +910,7 @@ impl HomeView {
                                 .map(|wt| wt.branch.clone())
                                 .or_else(|| inst.workspace_info.as_ref().map(|w| w.branch.clone())),
                             has_sandbox: inst.sandbox_info.as_ref().is_some_and(|s| s.enabled),
+                            dirty_worktrees: inst.dirty_worktrees(),
                         };
 
                         let profile = self.active_profile.as_deref().unwrap_or("default");
@@ -718,8 +956,11 @@ impl HomeView {
                 }
             }
             KeyCode::Char('r') if !key.modifiers.contains(KeyModifiers::SHIFT) => {
-                if let Some(id) = &self.selected_session {
-                    if let Some(inst) = self.get_instance(id) {
+                if let Some(id) = self.selected_session.clone() {
+                    if self.confirm_steal(&id) {
+                        return None;
+                    }
+                    if let Some(inst) = self.get_instance(&id) {
                         if inst.status == Status::Deleting {
                             return None;
                         }
@@ -731,18 +972,79 @@ impl HomeView {
                             list_profiles().unwrap_or_else(|_| vec![current_profile.clone()]);
                         let existing_groups: Vec<String> =
                             self.all_groups().iter().map(|g| g.path.clone()).collect();
-                        self.rename_dialog = Some(RenameDialog::new(
-                            &inst.title,
-                            &inst.group_path,
-                            &current_profile,
-                            profiles,
-                            existing_groups,
-                        ));
+                        self.rename_dialog = Some(
+                            RenameDialog::new(
+                                &inst.title,
+                                &inst.group_path,
+                                &current_profile,
+                                profiles,
+                                existing_groups,
+                            )
+                            .with_icon(inst.icon.as_deref()),
+                        );
+                    }
+                }
+            }
+            KeyCode::Char('B') => {
+                let instances: Vec<Instance> = self
+                    .instances()
+                    .iter()
+                    .filter(|i| {
+                        self.active_profile
+                            .as_ref()
+                            .map_or(true, |p| i.source_profile == *p)
+                    })
+                    .cloned()
+                    .collect();
+                self.bulk_rename_dialog = Some(BulkRenameDialog::new(instances, ""));
+            }
+            KeyCode::Char('R') => {
+                if let Some(id) = self.selected_session.clone() {
+                    if self.confirm_steal(&id) {
+                        return None;
+                    }
+                    self.open_retry_dialog(&id);
+                }
+            }
+            KeyCode::Char('!') => {
+                if let Some(id) = self.selected_session.clone() {
+                    self.open_command_menu(&id);
+                }
+            }
+            KeyCode::Char('F') => {
+                if let Some(id) = self.selected_session.clone() {
+                    if self.confirm_steal(&id) {
+                        return None;
                     }
+                    self.offer_conflict_prompt(&id);
+                }
+            }
+            KeyCode::Char('O') => {
+                if let Some(id) = &self.selected_session {
+                    return Some(Action::ObserveSession(id.clone()));
+                }
+            }
+            KeyCode::Char('C') => {
+                if let Some(id) = self.selected_session.clone() {
+                    if self.confirm_steal(&id) {
+                        return None;
+                    }
+                    self.open_checkpoints_dialog(&id);
+                }
+            }
+            KeyCode::Char('T') => {
+                if let Some(id) = self.selected_session.clone() {
+                    if self.confirm_steal(&id) {
+                        return None;
+                    }
+                    self.open_processes_dialog(&id);
                 }
             }
             KeyCode::Char('m') => {
                 if let Some(id) = self.selected_session.clone() {
+                    if self.confirm_steal(&id) {
+                        return None;
+                    }
                     if let Some(inst) = self.get_instance(&id) {
                         let title = inst.title.clone();
                         let inst_id = inst.id.clone();
@@ -750,7 +1052,9 @@ impl HomeView {
                         let is_running = tmux_session.as_ref().is_some_and(|s| s.exists());
                         if is_running {
                             self.pending_send_session = Some(id);
-                            self.send_message_dialog = Some(SendMessageDialog::new(&title));
+                            self.send_message_dialog = Some(
+                                SendMessageDialog::new(&title).with_spellcheck(self.spellcheck),
+                            );
                         }
                     }
                 }
@@ -761,6 +1065,18 @@ impl HomeView {
             KeyCode::Char('o') => {
                 self.apply_sort_order(self.sort_order.cycle());
             }
+            KeyCode::Up if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.scroll_preview(PREVIEW_SCROLL_STEP);
+            }
+            KeyCode::Down if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.scroll_preview(-PREVIEW_SCROLL_STEP);
+            }
+            KeyCode::PageUp if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.scroll_preview(self.preview_half_page());
+            }
+            KeyCode::PageDown if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.scroll_preview(-self.preview_half_page());
+            }
             KeyCode::Up | KeyCode::Char('k') => {
                 self.move_cursor(-1);
             }
@@ -885,12 +1201,7 @@ impl HomeView {
             self.cursor = self.cursor.min(self.flat_items.len().saturating_sub(1));
             self.update_selected();
         }
-        if let Ok(mut config) = load_config().map(|c| c.unwrap_or_default()) {
-            config.app_state.sort_order = Some(self.sort_order);
-            if let Err(e) = save_config(&config) {
-                tracing::warn!("Failed to save sort order: {}", e);
-            }
-        }
+        self.save_ui_state();
     }
 
     fn toggle_group_collapsed(&mut self, path: &str) {
@@ -1014,6 +1325,45 @@ impl HomeView {
         }
     }
 
+    /// Start creating a session from the new session dialog, first asking
+    /// the user to acknowledge agent hooks if the tool installs them.
+    pub(super) fn submit_new_session(&mut self, data: NewSessionData) -> Option<Action> {
+        if crate::git::clone::is_clone_url(&data.path) {
+            if self.is_demo() {
+                if let Some(dialog) = &mut self.new_dialog {
+                    dialog.set_error(DEMO_UNAVAILABLE.to_string());
+                }
+            } else {
+                self.request_clone(data);
+            }
+            return None;
+        }
+        let tool_name = if data.tool.is_empty() {
+            "claude".to_string()
+        } else {
+            data.tool.clone()
+        };
+        let has_hooks = crate::agents::get_agent(&tool_name)
+            .and_then(|a| a.hook_config.as_ref())
+            .is_some();
+
+        if has_hooks {
+            let acknowledged = crate::session::config::load_config()
+                .ok()
+                .flatten()
+                .map(|c| c.app_state.has_acknowledged_agent_hooks)
+                .unwrap_or(false);
+
+            if !acknowledged {
+                self.hooks_install_dialog = Some(HooksInstallDialog::new(&tool_name));
+                self.pending_hooks_install_data = Some(data);
+                return None;
+            }
+        }
+
+        self.continue_session_creation(data)
+    }
+
     /// Continue session creation after agent hooks acknowledgment.
     /// Runs the repo hook trust check and then creates the session.
     fn continue_session_creation(&mut self, data: NewSessionData) -> Option<Action> {
@@ -1049,6 +1399,19 @@ impl HomeView {
         data: NewSessionData,
         hooks: Option<crate::session::HooksConfig>,
     ) -> Option<Action> {
+        if self.is_demo() {
+            if let Some(dialog) = &mut self.new_dialog {
+                dialog.set_error(DEMO_UNAVAILABLE.to_string());
+            }
+            return None;
+        }
+        if let Some(reason) = self.budget_block_reason(&data.profile) {
+            if let Some(dialog) = &mut self.new_dialog {
+                dialog.set_error(reason);
+            }
+            return None;
+        }
+
         let has_hooks = hooks
             .as_ref()
             .is_some_and(|h| !h.on_create.is_empty() || !h.on_launch.is_empty());
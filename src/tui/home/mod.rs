@@ -1,43 +1,109 @@
 //! Home view - main session list and navigation
 
+mod board;
+mod filter_bar;
 mod input;
 mod operations;
 mod render;
+mod row_format;
+mod tabs;
+mod ui_state;
 
 #[cfg(test)]
 mod tests;
 
 use std::collections::{HashMap, HashSet};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use tui_input::Input;
 
+use crate::agents::Provider;
+use crate::notifications::{Event, Notification};
+use crate::process;
+use crate::session::budget::{self, BudgetCheck, BudgetState};
+use crate::session::checkpoints;
+use crate::session::commands::{self, NamedCommand};
+use crate::session::credentials::{self, Expiring};
+use crate::session::filter::{SavedFilter, SessionFilter};
+use crate::session::inbox::{Inbox, InboxItem, Reason};
+use crate::session::journal;
+use crate::session::locks;
+use crate::session::ownership;
+use crate::session::providers::{self, LimitReached};
+use crate::session::test_watch::TestCounts;
+use crate::session::timeline::{self, TimelineEvent};
 use crate::session::{
     config::{load_config, save_config, SortOrder},
     flatten_tree, flatten_tree_all_profiles, resolve_config, DefaultTerminalMode, Group, GroupTree,
     Instance, Item, Storage,
 };
 use crate::tmux::AvailableTools;
+use crate::update::agents::AgentVersion;
 
+use super::clone_poller::ClonePoller;
+use super::command_poller::CommandPoller;
+use super::components::Toast;
+use super::conflict_poller::ConflictPoller;
 use super::creation_poller::{CreationPoller, CreationRequest};
 use super::deletion_poller::DeletionPoller;
+use super::demo::Demo;
 use super::dialogs::{
-    ChangelogDialog, ConfirmDialog, GroupDeleteOptionsDialog, HookTrustDialog, HooksInstallDialog,
-    InfoDialog, NewSessionData, NewSessionDialog, ProfilePickerDialog, RenameDialog,
-    UnifiedDeleteDialog, WelcomeDialog,
+    BulkRenameDialog, ChangelogDialog, CheckpointAction, CheckpointsDialog, CommandMenuDialog,
+    ConfirmDialog, ExistingSessionPath, GroupDeleteOptionsDialog, HookTrustDialog,
+    HooksInstallDialog, InfoDialog, NewSessionData, NewSessionDialog, ProcessAction,
+    ProcessesDialog, ProfilePickerDialog, RenameDialog, RetryDialog, UnifiedDeleteDialog,
+    WelcomeDialog,
 };
 use super::diff::DiffView;
+use super::health_poller::HealthPoller;
+use super::merge_poller::MergePoller;
 use super::settings::SettingsView;
-use super::status_poller::StatusPoller;
+use super::status_poller::{StatusPoller, StatusUpdate};
+use super::watch_poller::WatchPoller;
+use board::BoardState;
+pub(crate) use row_format::format_age;
+use tabs::{ArchivesState, InboxState, LogsState, Tab};
 
 /// View mode for the home screen
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ViewMode {
     #[default]
     Agent,
     Terminal,
 }
 
+/// How the home screen adapts to the terminal width
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutMode {
+    /// Drop the preview and row details below the configured widths
+    #[default]
+    Auto,
+    /// Always show the preview and row details
+    Full,
+    /// Never show the preview or row details
+    Compact,
+}
+
+impl LayoutMode {
+    pub fn cycle(self) -> Self {
+        match self {
+            LayoutMode::Auto => LayoutMode::Full,
+            LayoutMode::Full => LayoutMode::Compact,
+            LayoutMode::Compact => LayoutMode::Auto,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LayoutMode::Auto => "auto",
+            LayoutMode::Full => "full",
+            LayoutMode::Compact => "compact",
+        }
+    }
+}
+
 /// Terminal mode for sandboxed sessions (container vs host)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum TerminalMode {
@@ -46,12 +112,35 @@ pub enum TerminalMode {
     Container,
 }
 
+/// Default fastest preview capture interval. A pane that changed is
+/// captured again this quickly; each unchanged capture doubles the wait, up
+/// to `PREVIEW_MAX_BACKOFF_FACTOR` times it.
+const PREVIEW_MIN_INTERVAL: Duration = Duration::from_millis(250);
+const PREVIEW_MAX_BACKOFF_FACTOR: u32 = 8;
+/// While the terminal is unfocused in eco mode, everything is polled this
+/// many times less often.
+const ECO_SLOWDOWN: u32 = 4;
+/// Pane lines hashed to decide whether a capture changed
+const PREVIEW_HASH_LINES: usize = 50;
+/// Furthest the output preview scrolls back, in lines
+const PREVIEW_MAX_SCROLL: u16 = 2000;
+/// How often budgets are re-checked when no session has reported a cost
+const BUDGET_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// How often credential expiry dates are re-checked
+const CREDENTIAL_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Cached preview content to avoid subprocess calls on every frame
 pub(super) struct PreviewCache {
     pub(super) session_id: Option<String>,
     pub(super) content: String,
     pub(super) last_refresh: Instant,
     pub(super) dimensions: (u16, u16),
+    /// Hash of the last `PREVIEW_HASH_LINES` lines of `content`
+    content_hash: u64,
+    /// Wait before the next capture, adapted to how busy the pane is
+    interval: Duration,
+    /// Wait after a capture that changed
+    min_interval: Duration,
 }
 
 impl Default for PreviewCache {
@@ -61,10 +150,97 @@ impl Default for PreviewCache {
             content: String::new(),
             last_refresh: Instant::now(),
             dimensions: (0, 0),
+            content_hash: 0,
+            interval: PREVIEW_MIN_INTERVAL,
+            min_interval: PREVIEW_MIN_INTERVAL,
+        }
+    }
+}
+
+impl PreviewCache {
+    /// Change the fastest capture interval, e.g. when polling slows down.
+    pub(super) fn set_min_interval(&mut self, min_interval: Duration) {
+        self.min_interval = min_interval;
+        self.interval = self
+            .interval
+            .clamp(min_interval, min_interval * PREVIEW_MAX_BACKOFF_FACTOR);
+    }
+
+    /// Whether the cache must be re-captured for session `id` at `dimensions`.
+    pub(super) fn is_stale(&self, id: &str, dimensions: (u16, u16)) -> bool {
+        self.session_id.as_deref() != Some(id)
+            || self.dimensions != dimensions
+            || self.last_refresh.elapsed() >= self.interval
+    }
+
+    /// Force a re-capture on the next refresh, e.g. to fetch more scrollback.
+    pub(super) fn invalidate(&mut self) {
+        self.session_id = None;
+    }
+
+    /// Store a fresh capture. Returns true if it differs from the cached
+    /// content; otherwise the old content is kept and polling backs off.
+    pub(super) fn store(&mut self, id: &str, dimensions: (u16, u16), content: String) -> bool {
+        let hash = tail_hash(&content);
+        let changed = self.session_id.as_deref() != Some(id)
+            || self.dimensions != dimensions
+            || self.content_hash != hash;
+        if changed {
+            self.session_id = Some(id.to_string());
+            self.dimensions = dimensions;
+            self.content = content;
+            self.content_hash = hash;
+            self.interval = self.min_interval;
+        } else {
+            self.interval = (self.interval * 2).min(self.min_interval * PREVIEW_MAX_BACKOFF_FACTOR);
+        }
+        self.last_refresh = Instant::now();
+        changed
+    }
+}
+
+fn tail_hash(content: &str) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    for line in content.lines().rev().take(PREVIEW_HASH_LINES) {
+        line.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Cached activity timeline for the selected session
+pub(super) struct TimelineCache {
+    pub(super) session_id: Option<String>,
+    pub(super) events: Vec<TimelineEvent>,
+    pub(super) last_refresh: Instant,
+}
+
+impl Default for TimelineCache {
+    fn default() -> Self {
+        Self {
+            session_id: None,
+            events: Vec::new(),
+            last_refresh: Instant::now(),
         }
     }
 }
 
+/// Latest health check result for a session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Health {
+    Passing,
+    Failing(String),
+}
+
+/// A prompt that matched guardrails and awaits confirmation
+pub(super) struct PendingPrompt {
+    pub(super) session_id: String,
+    pub(super) message: String,
+    pub(super) matched: Vec<String>,
+}
+
 pub(super) const INDENTS: [&str; 10] = [
     "",
     " ",
@@ -78,20 +254,22 @@ pub(super) const INDENTS: [&str; 10] = [
     "         ",
 ];
 
+fn parse_row_format(theme: &crate::session::ThemeConfig) -> Option<Vec<row_format::Segment>> {
+    theme
+        .row_format
+        .as_deref()
+        .filter(|t| !t.trim().is_empty())
+        .map(row_format::parse)
+}
+
 pub(super) fn get_indent(depth: usize) -> &'static str {
     INDENTS.get(depth).copied().unwrap_or(INDENTS[9])
 }
 
-pub(super) const ICON_RUNNING: &str = "●";
-pub(super) const ICON_WAITING: &str = "◐";
-pub(super) const ICON_IDLE: &str = "○";
-pub(super) const ICON_ERROR: &str = "✕";
-pub(super) const ICON_STARTING: &str = "◌";
-pub(super) const ICON_UNKNOWN: &str = "?";
-pub(super) const ICON_STOPPED: &str = "■";
-pub(super) const ICON_DELETING: &str = "✗";
 pub(super) const ICON_COLLAPSED: &str = "▶";
 pub(super) const ICON_EXPANDED: &str = "▼";
+pub(super) const ICON_HEALTHY: &str = "✓";
+pub(super) const ICON_UNHEALTHY: &str = "✗";
 
 pub struct HomeView {
     pub(super) storages: HashMap<String, Storage>,
@@ -117,28 +295,52 @@ pub struct HomeView {
     pub(super) unified_delete_dialog: Option<UnifiedDeleteDialog>,
     pub(super) group_delete_options_dialog: Option<GroupDeleteOptionsDialog>,
     pub(super) rename_dialog: Option<RenameDialog>,
+    pub(super) bulk_rename_dialog: Option<BulkRenameDialog>,
     pub(super) hook_trust_dialog: Option<HookTrustDialog>,
     /// Session data pending hook trust approval
     pub(super) pending_hook_trust_data: Option<NewSessionData>,
     pub(super) hooks_install_dialog: Option<HooksInstallDialog>,
     /// Session data pending agent hooks acknowledgment
     pub(super) pending_hooks_install_data: Option<NewSessionData>,
+    pub(super) duplicate_prompt_dialog: Option<super::dialogs::DuplicatePromptDialog>,
+    /// Session data pending the duplicate prompt warning
+    pub(super) pending_duplicate_data: Option<NewSessionData>,
     pub(super) welcome_dialog: Option<WelcomeDialog>,
     pub(super) changelog_dialog: Option<ChangelogDialog>,
     pub(super) info_dialog: Option<InfoDialog>,
     pub(super) profile_picker_dialog: Option<ProfilePickerDialog>,
     pub(super) send_message_dialog: Option<super::dialogs::SendMessageDialog>,
+    pub(super) retry_dialog: Option<RetryDialog>,
+    pub(super) merge_queue_dialog: Option<super::dialogs::MergeQueueDialog>,
+    pub(super) command_palette: Option<super::dialogs::CommandPaletteDialog>,
+    pub(super) command_menu: Option<super::dialogs::CommandMenuDialog>,
+    pub(super) checkpoints_dialog: Option<super::dialogs::CheckpointsDialog>,
+    pub(super) processes_dialog: Option<ProcessesDialog>,
+    pub(super) start_failure_dialog: Option<super::dialogs::StartFailureDialog>,
+    pub(super) permission_dialog: Option<super::dialogs::PermissionDialog>,
     /// Session to receive the message from the send dialog
     pub(super) pending_send_session: Option<String>,
     /// Session to attach after the custom instruction warning dialog is dismissed
     pub(super) pending_attach_after_warning: Option<String>,
     /// Session to stop after the confirmation dialog is accepted
     pub(super) pending_stop_session: Option<String>,
+    /// Session owned elsewhere to take over after the confirmation dialog
+    pub(super) pending_steal_session: Option<String>,
+    /// Prompt held back by a guardrail until the user confirms it
+    pub(super) pending_guarded_prompt: Option<PendingPrompt>,
+    /// Transient notification shown in place of the status bar
+    pub(super) toast: Option<Toast>,
     // Search
     pub(super) search_active: bool,
     pub(super) search_query: Input,
     pub(super) search_matches: Vec<usize>,
     pub(super) search_match_index: usize,
+    // Filter
+    /// Active filter expression, empty when none
+    pub(super) filter_query: String,
+    pub(super) filter: Option<SessionFilter>,
+    filter_bar: Option<filter_bar::FilterBar>,
+    pub(super) saved_filters: Vec<SavedFilter>,
 
     // Tool availability
     pub(super) available_tools: AvailableTools,
@@ -146,6 +348,9 @@ pub struct HomeView {
     // Performance: background status polling
     pub(super) status_poller: StatusPoller,
     pub(super) pending_status_refresh: bool,
+    /// Whether the first status pass has landed. Until then rows show the
+    /// status saved with each session.
+    pub(super) hydrated: bool,
 
     // Performance: background deletion
     pub(super) deletion_poller: DeletionPoller,
@@ -156,11 +361,66 @@ pub struct HomeView {
     pub(super) creation_cancelled: bool,
     /// Sessions whose on_launch hooks already ran in the creation poller
     pub(super) on_launch_hooks_ran: HashSet<String>,
+    /// Clones of git URLs entered as a new session's path
+    pub(super) clone_poller: ClonePoller,
+
+    // Background health checks
+    pub(super) health_poller: HealthPoller,
+    pub(super) health: HashMap<String, Health>,
+    pub(super) health_next_due: HashMap<String, Instant>,
+    pub(super) health_pending: HashSet<String>,
+
+    // Test watchers running in companion tmux sessions
+    pub(super) watch_poller: WatchPoller,
+    /// Latest counts each session's watcher showed
+    pub(super) test_counts: HashMap<String, TestCounts>,
+    watch_pending: HashSet<String>,
+    /// Resolved `hooks.test_watch` per session, re-resolved once a minute
+    watch_commands: HashMap<String, Option<String>>,
+    watch_resolved_at: Option<Instant>,
+    /// Sessions whose watcher failed to start; tried again once they stop
+    watch_failed: HashSet<String>,
+
+    // Named session commands running in companion tmux sessions
+    pub(super) command_poller: CommandPoller,
+    /// Command currently running for each session, by session id
+    pub(super) running_commands: HashMap<String, String>,
+    /// Named commands by project path, loaded on first use
+    pub(super) commands_cache: HashMap<String, Vec<NamedCommand>>,
+
+    // Background checks for merged worktree branches
+    pub(super) merge_poller: MergePoller,
+    pub(super) merge_next_due: HashMap<String, Instant>,
+    pub(super) merge_pending: HashSet<String>,
+    /// Sessions whose branch was found merged, and how
+    pub(super) merged_branches: HashMap<String, String>,
+    /// Merged sessions waiting to be offered for cleanup
+    pub(super) merged_queue: Vec<String>,
+    /// Merged session offered for cleanup in the confirmation dialog
+    pub(super) pending_merged_cleanup: Option<String>,
+
+    // Background checks for merge conflicts in worktree sessions
+    pub(super) conflict_poller: ConflictPoller,
+    conflict_next_due: HashMap<String, Instant>,
+    conflict_pending: HashSet<String>,
+    /// Worktree sessions stopped on merge or rebase conflicts
+    pub(super) conflicts: HashMap<String, crate::git::conflicts::Conflict>,
+    /// Session whose conflict prompt awaits confirmation
+    pub(super) pending_conflict_prompt: Option<(String, String)>,
 
     // Performance: preview caching
     pub(super) preview_cache: PreviewCache,
     pub(super) terminal_preview_cache: PreviewCache,
     pub(super) container_terminal_preview_cache: PreviewCache,
+    /// Lines each session's output preview is scrolled up from the bottom;
+    /// sessions without an entry follow new output
+    pub(super) preview_scroll: HashMap<String, u16>,
+
+    /// Show the activity timeline instead of agent output in the preview
+    pub(super) show_timeline: bool,
+    /// Show structured events parsed from agent output instead of the raw pane
+    pub(super) show_events: bool,
+    pub(super) timeline_cache: TimelineCache,
 
     // Terminal mode for sandboxed sessions (per-session, ephemeral)
     pub(super) terminal_modes: HashMap<String, TerminalMode>,
@@ -169,6 +429,11 @@ pub struct HomeView {
 
     // Sound config for state transition sounds
     pub(super) sound_config: crate::sound::SoundConfig,
+    pub(super) notification_config: crate::notifications::NotificationConfig,
+    pub(super) polling: crate::session::PollingConfig,
+    /// Whether the terminal has focus, from focus events; assumed until told
+    /// otherwise
+    pub(super) focused: bool,
 
     // Settings view
     pub(super) settings_view: Option<SettingsView>,
@@ -178,6 +443,62 @@ pub struct HomeView {
     // Diff view
     pub(super) diff_view: Option<DiffView>,
 
+    // Board view (sessions as cards in status lanes)
+    board: Option<BoardState>,
+
+    // Top-level tabs; each keeps its state while another is shown
+    tab: Tab,
+    dashboard_selected: usize,
+    archives: ArchivesState,
+    logs: LogsState,
+    /// Attention items from status changes, handled or not
+    inbox: Inbox,
+    inbox_state: InboxState,
+
+    /// Sessions waiting for a held lock or a free slot under their
+    /// provider's cap, in the order they were queued
+    pub(super) lock_queue: Vec<String>,
+    /// Caps from `session.provider_limits`
+    pub(super) provider_limits: HashMap<Provider, usize>,
+
+    /// Cost each session's agent last reported, counted toward its
+    /// profile's budget while the session is alive
+    pub(super) live_costs: HashMap<String, f64>,
+    /// Budget standing per profile, refreshed by `check_budgets`
+    pub(super) budgets: HashMap<String, BudgetCheck>,
+    pub(super) budget_checked_at: Option<Instant>,
+
+    /// Credentials near or past expiry per shown profile, soonest first,
+    /// refreshed by `check_credentials`
+    pub(super) credential_warnings: Vec<(String, Expiring)>,
+    pub(super) credentials_checked_at: Option<Instant>,
+    /// Warnings the refresh command already ran for
+    credentials_refreshed: HashSet<(String, Expiring)>,
+
+    /// Installed agents with a newer release, shown on the dashboard
+    agent_updates: Vec<AgentVersion>,
+
+    /// Parsed `theme.row_format`; None uses the built-in row layout
+    row_format: Option<Vec<row_format::Segment>>,
+    /// Emit paths and URLs in the preview as OSC 8 hyperlinks
+    pub(super) hyperlinks: bool,
+    /// Underline misspelled words in prompt editors
+    pub(super) spellcheck: bool,
+    /// Layout forced with `z`, or Auto to follow the width breakpoints
+    pub(super) layout: LayoutMode,
+    /// `theme.preview_min_width`
+    pub(super) preview_min_width: u16,
+    /// `theme.row_details_min_width`
+    pub(super) row_details_min_width: u16,
+    /// `theme.terminal_title`
+    terminal_title: String,
+
+    /// Sessions whose login session is open; restarted once it closes
+    pub(super) reauth_pending: Vec<String>,
+
+    /// Scripted activity shown instead of tmux in `aoe --demo`
+    pub(super) demo: Option<Demo>,
+
     // Resizable list column width (percentage-like units)
     pub(super) list_width: u16,
 }
@@ -229,6 +550,36 @@ impl HomeView {
             .as_ref()
             .map(|config| config.sound.clone())
             .unwrap_or_default();
+        let notification_config = resolved
+            .as_ref()
+            .map(|config| config.notifications.clone())
+            .unwrap_or_default();
+        let provider_limits = resolved
+            .as_ref()
+            .map(|config| providers::limits(&config.session))
+            .unwrap_or_default();
+        let polling = resolved
+            .as_ref()
+            .map(|config| config.polling.clone())
+            .unwrap_or_default();
+        let hyperlinks = resolved
+            .as_ref()
+            .is_ok_and(|config| super::hyperlink::enabled(config.theme.hyperlinks));
+        let spellcheck = resolved
+            .as_ref()
+            .map_or(true, |config| config.theme.spellcheck);
+        let terminal_title = resolved.as_ref().map_or_else(
+            |_| crate::session::ThemeConfig::default().terminal_title,
+            |config| config.theme.terminal_title.clone(),
+        );
+        let (preview_min_width, row_details_min_width) = resolved.as_ref().map_or_else(
+            |_| crate::session::ThemeConfig::default().breakpoints(),
+            |config| config.theme.breakpoints(),
+        );
+        let row_format = resolved
+            .as_ref()
+            .ok()
+            .and_then(|config| parse_row_format(&config.theme));
         let user_config = load_config().ok().flatten();
         let sort_order = user_config
             .as_ref()
@@ -254,160 +605,1678 @@ impl HomeView {
             unified_delete_dialog: None,
             group_delete_options_dialog: None,
             rename_dialog: None,
+            bulk_rename_dialog: None,
             hook_trust_dialog: None,
             pending_hook_trust_data: None,
             hooks_install_dialog: None,
             pending_hooks_install_data: None,
+            duplicate_prompt_dialog: None,
+            pending_duplicate_data: None,
             welcome_dialog: None,
             changelog_dialog: None,
             info_dialog: None,
             profile_picker_dialog: None,
             send_message_dialog: None,
+            retry_dialog: None,
+            merge_queue_dialog: None,
+            command_palette: None,
+            command_menu: None,
+            checkpoints_dialog: None,
+            processes_dialog: None,
+            start_failure_dialog: None,
+            permission_dialog: None,
             pending_send_session: None,
             pending_attach_after_warning: None,
             pending_stop_session: None,
+            pending_steal_session: None,
+            pending_guarded_prompt: None,
+            toast: None,
             search_active: false,
             search_query: Input::default(),
+            filter_query: String::new(),
+            filter: None,
+            filter_bar: None,
+            saved_filters: Vec::new(),
             search_matches: Vec::new(),
             search_match_index: 0,
             available_tools,
             status_poller: StatusPoller::new(),
             pending_status_refresh: false,
+            hydrated: false,
             deletion_poller: DeletionPoller::new(),
             creation_poller: CreationPoller::new(),
             creation_cancelled: false,
+            clone_poller: ClonePoller::new(),
             on_launch_hooks_ran: HashSet::new(),
+            health_poller: HealthPoller::new(),
+            health: HashMap::new(),
+            health_next_due: HashMap::new(),
+            health_pending: HashSet::new(),
+            watch_poller: WatchPoller::new(),
+            test_counts: HashMap::new(),
+            watch_pending: HashSet::new(),
+            watch_commands: HashMap::new(),
+            watch_resolved_at: None,
+            watch_failed: HashSet::new(),
+            command_poller: CommandPoller::new(),
+            running_commands: HashMap::new(),
+            commands_cache: HashMap::new(),
+            merge_poller: MergePoller::new(),
+            merge_next_due: HashMap::new(),
+            merge_pending: HashSet::new(),
+            merged_branches: HashMap::new(),
+            merged_queue: Vec::new(),
+            pending_merged_cleanup: None,
+            conflict_poller: ConflictPoller::new(),
+            conflict_next_due: HashMap::new(),
+            conflict_pending: HashSet::new(),
+            conflicts: HashMap::new(),
+            pending_conflict_prompt: None,
             preview_cache: PreviewCache::default(),
             terminal_preview_cache: PreviewCache::default(),
             container_terminal_preview_cache: PreviewCache::default(),
+            preview_scroll: HashMap::new(),
+            show_timeline: false,
+            show_events: false,
+            timeline_cache: TimelineCache::default(),
             terminal_modes: HashMap::new(),
             default_terminal_mode,
             sound_config,
+            notification_config,
+            polling,
+            focused: true,
             settings_view: None,
             settings_close_confirm: false,
             diff_view: None,
+            board: None,
+            tab: Tab::Sessions,
+            dashboard_selected: 0,
+            archives: ArchivesState::default(),
+            logs: LogsState::default(),
+            inbox: Inbox::load(),
+            inbox_state: InboxState::default(),
+            lock_queue: Vec::new(),
+            provider_limits,
+            live_costs: HashMap::new(),
+            budgets: HashMap::new(),
+            budget_checked_at: None,
+            credential_warnings: Vec::new(),
+            credentials_checked_at: None,
+            credentials_refreshed: HashSet::new(),
+            agent_updates: Vec::new(),
+            row_format,
+            hyperlinks,
+            spellcheck,
+            layout: LayoutMode::default(),
+            preview_min_width,
+            row_details_min_width,
+            terminal_title,
+            reauth_pending: Vec::new(),
+            demo: None,
             list_width: user_config
                 .and_then(|c| c.app_state.home_list_width)
                 .unwrap_or(35),
         };
 
-        view.flat_items = view.build_flat_items();
-        view.update_selected();
-        Ok(view)
+        view.flat_items = view.build_flat_items();
+        view.update_selected();
+        view.apply_preview_interval();
+        if let Some(state) = ui_state::load(view.active_profile.as_deref()) {
+            view.restore_ui_state(state);
+        }
+        Ok(view)
+    }
+
+    pub fn reload(&mut self) -> anyhow::Result<()> {
+        use crate::session::list_profiles;
+
+        let mut all_instances = Vec::new();
+
+        // Re-discover profiles in "all" mode
+        if self.active_profile.is_none() {
+            let current_profiles = list_profiles()?;
+            for name in &current_profiles {
+                if !self.storages.contains_key(name) {
+                    self.storages.insert(name.clone(), Storage::new(name)?);
+                }
+            }
+            self.storages.retain(|k, _| current_profiles.contains(k));
+        }
+
+        for (profile_name, storage) in &self.storages {
+            let (mut instances, groups) = storage.load_with_groups()?;
+            for inst in &mut instances {
+                inst.source_profile = profile_name.clone();
+                if let Some(prev) = self.instance_map.get(&inst.id) {
+                    inst.status = prev.status;
+                    inst.last_error = prev.last_error.clone();
+                    inst.last_error_check = prev.last_error_check;
+                    inst.last_start_time = prev.last_start_time;
+                    inst.auth_required = prev.auth_required;
+                    inst.quota_exceeded = prev.quota_exceeded;
+                    inst.permission_prompt = prev.permission_prompt.clone();
+                }
+            }
+            // Rebuild this profile's tree from disk, preserving any collapsed
+            // state that was toggled in-memory but not yet on disk
+            let mut new_tree = GroupTree::new_with_groups(&instances, &groups);
+            if let Some(old_tree) = self.group_trees.get(profile_name) {
+                for g in old_tree.get_all_groups() {
+                    if g.collapsed {
+                        new_tree.set_collapsed(&g.path, true);
+                    }
+                }
+            }
+            self.group_trees.insert(profile_name.clone(), new_tree);
+            all_instances.extend(instances);
+        }
+
+        // Remove trees for profiles that no longer exist
+        let storage_keys: Vec<String> = self.storages.keys().cloned().collect();
+        self.group_trees.retain(|k, _| storage_keys.contains(k));
+
+        self.instances = all_instances;
+        self.instance_map = self
+            .instances
+            .iter()
+            .map(|i| (i.id.clone(), i.clone()))
+            .collect();
+        self.flat_items = self.build_flat_items();
+
+        if self.cursor >= self.flat_items.len() && !self.flat_items.is_empty() {
+            self.cursor = self.flat_items.len() - 1;
+        }
+
+        if self.search_active && !self.search_query.value().is_empty() {
+            self.update_search();
+        } else if !self.search_matches.is_empty() {
+            // Recalculate match indices without moving the cursor
+            self.refresh_search_matches();
+        }
+
+        self.update_selected();
+        Ok(())
+    }
+
+    /// Request a status refresh in the background (non-blocking).
+    /// Call `apply_status_updates` to check for and apply results.
+    /// How many times slower than configured to poll: `ECO_SLOWDOWN` while
+    /// the terminal is unfocused in eco mode, otherwise 1.
+    pub fn poll_slowdown(&self) -> u32 {
+        if self.polling.eco_mode && !self.focused {
+            ECO_SLOWDOWN
+        } else {
+            1
+        }
+    }
+
+    /// How often session status is polled
+    pub fn status_refresh_interval(&self) -> Duration {
+        Duration::from_millis(self.polling.pane_capture_ms) * self.poll_slowdown()
+    }
+
+    /// Record a terminal focus change, which slows or restores polling in
+    /// eco mode.
+    pub fn set_focused(&mut self, focused: bool) {
+        if self.focused != focused {
+            self.focused = focused;
+            self.apply_preview_interval();
+        }
+    }
+
+    /// The selected pane's preview is captured twice as often as status.
+    fn apply_preview_interval(&mut self) {
+        let min_interval = self.status_refresh_interval() / 2;
+        self.preview_cache.set_min_interval(min_interval);
+        self.terminal_preview_cache.set_min_interval(min_interval);
+        self.container_terminal_preview_cache
+            .set_min_interval(min_interval);
+    }
+
+    pub fn request_status_refresh(&mut self) {
+        if self.demo.is_some() {
+            return;
+        }
+        if !self.pending_status_refresh {
+            let instances: Vec<Instance> = self.instances.clone();
+            self.status_poller
+                .request_refresh(instances, self.status_refresh_interval());
+            self.pending_status_refresh = true;
+        }
+    }
+
+    /// Whether live statuses have replaced the saved ones since startup.
+    /// Git and budget checks wait for this, so they don't hold up the first
+    /// status pass.
+    pub fn is_hydrated(&self) -> bool {
+        self.hydrated
+    }
+
+    /// Apply any pending status updates from the background poller.
+    /// Returns true if any session changed, so an unchanged poll costs no
+    /// redraw.
+    pub fn apply_status_updates(&mut self) -> bool {
+        if self.demo.is_some() {
+            return self.apply_demo_statuses();
+        }
+
+        if let Some(updates) = self.status_poller.try_recv_updates() {
+            let changed = self.apply_updates(updates);
+            self.pending_status_refresh = false;
+            self.hydrated = true;
+            return changed;
+        }
+        false
+    }
+
+    /// Apply one batch of poller results, notifying on the transitions they
+    /// bring. Returns true if any session changed.
+    fn apply_updates(&mut self, updates: Vec<StatusUpdate>) -> bool {
+        use crate::session::Status;
+
+        let mut changed = false;
+        for update in updates {
+            let old_status = self.get_instance(&update.id).map(|i| i.status);
+
+            let should_update = old_status.is_some_and(|s| {
+                s != Status::Deleting && s != Status::Stopped && update.status != Status::Stopped
+            });
+
+            if should_update {
+                let new_status = update.status;
+                let new_error = update.last_error;
+                let auth_required = update.auth_required;
+                let quota_exceeded = update.quota_exceeded;
+                let permission_prompt = update.permission_prompt;
+                let (was_auth_required, was_quota_exceeded) = self
+                    .get_instance(&update.id)
+                    .map_or((false, false), |i| (i.auth_required, i.quota_exceeded));
+                changed |= old_status != Some(new_status)
+                    || auth_required != was_auth_required
+                    || quota_exceeded != was_quota_exceeded
+                    || update.final_summary.is_some()
+                    || self
+                        .get_instance(&update.id)
+                        .is_some_and(|i| i.permission_prompt != permission_prompt)
+                    || self
+                        .get_instance(&update.id)
+                        .is_some_and(|i| i.last_error != new_error);
+                self.mutate_instance(&update.id, |inst| {
+                    inst.status = new_status;
+                    inst.last_error = new_error;
+                    inst.auth_required = auth_required;
+                    inst.quota_exceeded = quota_exceeded;
+                    inst.permission_prompt = permission_prompt;
+                });
+
+                if auth_required && !was_auth_required {
+                    self.notify_auth_required(&update.id);
+                }
+                if quota_exceeded && !was_quota_exceeded {
+                    self.notify_quota_exceeded(&update.id);
+                }
+                // The pane died right after launch, e.g. the agent
+                // rejected its flags or exited on a missing login
+                if old_status == Some(Status::Starting) && new_status == Status::Error {
+                    self.fail_over(&update.id, "exited on launch");
+                }
+
+                if let Some(old) = old_status {
+                    if old != new_status {
+                        crate::sound::play_for_transition(old, new_status, &self.sound_config);
+                        timeline::record_status(&update.id, old, new_status);
+                        if let Some(inst) = self.get_instance(&update.id) {
+                            let profile = if inst.source_profile.is_empty() {
+                                self.config_profile()
+                            } else {
+                                &inst.source_profile
+                            };
+                            journal::record_transition(profile, inst, old, new_status);
+                        }
+                        if let Some(reason) = Reason::for_transition(old, new_status) {
+                            let message = match reason {
+                                Reason::Waiting => "waiting for input".to_string(),
+                                Reason::Error => self
+                                    .get_instance(&update.id)
+                                    .and_then(|i| i.last_error.clone())
+                                    .unwrap_or_else(|| "session is in the error state".into()),
+                                Reason::Finished => "finished".to_string(),
+                            };
+                            self.add_to_inbox(&update.id, reason, &message);
+                        }
+                        if let Some(event) = Event::for_status(new_status) {
+                            let message = match new_status {
+                                Status::Error => self
+                                    .get_instance(&update.id)
+                                    .and_then(|i| i.last_error.clone())
+                                    .unwrap_or_else(|| "session is in the error state".into()),
+                                _ => format!("session is {}", event.name()),
+                            };
+                            self.notify(&update.id, event, message);
+                        }
+                    }
+                }
+
+                if let Some(summary) = update.final_summary {
+                    self.record_final_summary(&update.id, summary);
+                }
+                if let Some(cost) = update.cost {
+                    self.live_costs.insert(update.id.clone(), cost);
+                    self.budget_checked_at = None;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Keep the agent's last message with the session once it finishes.
+    fn record_final_summary(&mut self, id: &str, summary: String) {
+        if self
+            .get_instance(id)
+            .is_some_and(|i| i.final_summary.as_deref() == Some(summary.as_str()))
+        {
+            return;
+        }
+        let first_line = summary.lines().next().unwrap_or_default();
+        timeline::record(
+            id,
+            timeline::EventKind::Status,
+            format!("finished: {}", first_line),
+        );
+        if self.inbox.describe(id, Reason::Finished, first_line) {
+            self.inbox.save();
+        }
+        self.mutate_instance(id, |inst| inst.final_summary = Some(summary));
+        if let Err(e) = self.save() {
+            tracing::warn!("Failed to save final summary: {}", e);
+        }
+    }
+
+    /// Queue an item for session `id` in the attention inbox.
+    fn add_to_inbox(&mut self, id: &str, reason: Reason, message: &str) {
+        let Some(title) = self.get_instance(id).map(|i| i.title.clone()) else {
+            return;
+        };
+        self.inbox.push(InboxItem::new(id, &title, reason, message));
+        self.inbox.save();
+    }
+
+    /// Send `event` for session `id` to the channels the notification
+    /// rules route it to.
+    fn notify(&self, id: &str, event: Event, message: String) {
+        let Some(inst) = self.get_instance(id) else {
+            return;
+        };
+        crate::notifications::notify(
+            &self.notification_config,
+            Notification {
+                event,
+                session_id: id.to_string(),
+                title: inst.title.clone(),
+                tags: crate::session::filter::tags(inst),
+                message,
+            },
+        );
+    }
+
+    fn notify_auth_required(&mut self, id: &str) {
+        let Some(title) = self.get_instance(id).map(|i| i.title.clone()) else {
+            return;
+        };
+        timeline::record(
+            id,
+            timeline::EventKind::Status,
+            "auth required: agent credentials expired",
+        );
+        crate::sound::play_for_failure(&self.sound_config);
+        self.add_to_inbox(id, Reason::Error, "agent needs to log in again");
+        self.notify(id, Event::Error, "agent needs to log in again".to_string());
+        self.show_toast(Toast::error(format!(
+            "{}: agent needs to log in again (press A to re-authenticate)",
+            title
+        )));
+    }
+
+    fn notify_quota_exceeded(&mut self, id: &str) {
+        if self.fail_over(id, "hit a usage limit") {
+            return;
+        }
+        let Some(title) = self.get_instance(id).map(|i| i.title.clone()) else {
+            return;
+        };
+        timeline::record(id, timeline::EventKind::Status, "usage limit reached");
+        crate::sound::play_for_failure(&self.sound_config);
+        self.add_to_inbox(id, Reason::Error, "agent hit a usage limit");
+        self.notify(id, Event::Error, "agent hit a usage limit".to_string());
+        self.show_toast(Toast::error(format!(
+            "{}: agent hit a usage limit (set session.fallback_tool to switch agents)",
+            title
+        )));
+    }
+
+    /// Switch `id` to its fallback agent and restart it, because its agent
+    /// `reason` (e.g. "hit a usage limit"). Returns false, leaving the
+    /// session alone, if it has no fallback or already failed over.
+    pub fn fail_over(&mut self, id: &str, reason: &str) -> bool {
+        use crate::session::Status;
+
+        let Some(mut inst) = self.get_instance(id).cloned() else {
+            return false;
+        };
+        let Some(from) = inst.fail_over() else {
+            return false;
+        };
+        let message = format!("{} {}, switched to {}", from, reason, inst.tool);
+        timeline::record(
+            id,
+            timeline::EventKind::Status,
+            format!("failover: {}", message),
+        );
+        let updated = inst.clone();
+        self.mutate_instance(id, |i| *i = updated);
+        if let Err(e) = self.save() {
+            tracing::warn!("Failed to save failover: {}", e);
+        }
+
+        self.set_instance_status(id, Status::Starting);
+        match inst.restart_with_size(crate::terminal::get_size()) {
+            Ok(()) => {
+                self.set_instance_error(id, None);
+                self.show_toast(Toast::info(format!("{}: {}", inst.title, message)));
+            }
+            Err(e) => self.report_start_failure(id, &e),
+        }
+        true
+    }
+
+    /// Switch between the automatic, full, and compact layouts.
+    pub(super) fn cycle_layout(&mut self) {
+        self.layout = self.layout.cycle();
+        self.show_toast(Toast::info(format!("Layout: {}", self.layout.label())));
+    }
+
+    /// Whether the preview pane and the branch and tags of session rows fit
+    /// in `width` columns.
+    pub(super) fn layout_for_width(&self, width: u16) -> (bool, bool) {
+        match self.layout {
+            LayoutMode::Auto => (
+                width >= self.preview_min_width,
+                width >= self.row_details_min_width,
+            ),
+            LayoutMode::Full => (true, true),
+            LayoutMode::Compact => (false, false),
+        }
+    }
+
+    /// Ask to take over `id` if another user or host owns it. Returns true
+    /// when it does, in which case the caller drops the action; the user
+    /// repeats it once the session is theirs.
+    pub(super) fn confirm_steal(&mut self, id: &str) -> bool {
+        let Some(inst) = self.get_instance(id) else {
+            return false;
+        };
+        let Some(owner) = ownership::foreign_owner(inst) else {
+            return false;
+        };
+        let message = format!(
+            "'{}' is owned by {}. Take it over as {}?",
+            inst.title,
+            owner,
+            ownership::current()
+        );
+        self.pending_steal_session = Some(id.to_string());
+        self.confirm_dialog = Some(ConfirmDialog::new(
+            "Session Owned Elsewhere",
+            &message,
+            "steal_session",
+        ));
+        true
+    }
+
+    pub(super) fn steal_session(&mut self, id: &str) {
+        self.mutate_instance(id, ownership::steal);
+        if let Err(e) = self.save() {
+            tracing::error!("Failed to save session owner: {}", e);
+        }
+        if let Some(inst) = self.get_instance(id) {
+            self.show_toast(Toast::info(format!("Took over '{}'", inst.title)));
+        }
+    }
+
+    /// Open the retry dialog for `id`, pre-filled with the prompt its last
+    /// attempt was launched with.
+    pub(super) fn open_retry_dialog(&mut self, id: &str) {
+        let Some(inst) = self.get_instance(id) else {
+            return;
+        };
+        let resets = if let Some(ws) = &inst.workspace_info {
+            let managed: Vec<&str> = ws
+                .repos
+                .iter()
+                .filter(|r| r.managed_by_aoe)
+                .map(|r| r.name.as_str())
+                .collect();
+            (!managed.is_empty()).then(|| format!("worktrees {}", managed.join(", ")))
+        } else {
+            inst.worktree_info
+                .as_ref()
+                .filter(|wt| wt.managed_by_aoe)
+                .map(|wt| format!("worktree {}", wt.branch))
+        };
+        self.retry_dialog = Some(
+            RetryDialog::new(id, &inst.title, resets, inst.original_prompt.as_deref())
+                .with_spellcheck(self.spellcheck),
+        );
+    }
+
+    /// Start `id` over: kill it, reset its worktree to the commit it
+    /// started from, and launch it again with `prompt`.
+    pub fn retry_session(&mut self, id: &str, prompt: String) {
+        use crate::session::Status;
+
+        let Some(mut inst) = self.get_instance(id).cloned() else {
+            return;
+        };
+        if let Err(e) = inst.reset_for_retry(Some(prompt)) {
+            self.show_toast(Toast::error(format!("Retry failed: {}", e)));
+            return;
+        }
+        timeline::record(
+            id,
+            timeline::EventKind::Status,
+            "retried from the base commit",
+        );
+        let updated = inst.clone();
+        self.mutate_instance(id, |i| *i = updated);
+
+        self.set_instance_status(id, Status::Starting);
+        match inst.start_with_size(crate::terminal::get_size()) {
+            Ok(()) => {
+                self.mutate_instance(id, |i| i.initial_prompt = inst.initial_prompt.clone());
+                self.set_instance_error(id, None);
+                self.show_toast(Toast::info(format!("Retrying {}", inst.title)));
+            }
+            Err(e) => self.report_start_failure(id, &e),
+        }
+        if let Err(e) = self.save() {
+            tracing::warn!("Failed to save retry: {}", e);
+        }
+    }
+
+    /// Start health checks for live sessions whose interval has elapsed.
+    /// Sessions without a configured probe are re-resolved at the default
+    /// interval so config edits are picked up.
+    pub fn request_health_checks(&mut self) {
+        use crate::session::repo_config::{
+            resolve_health_check, DEFAULT_HEALTH_CHECK_INTERVAL_SECS,
+        };
+        use crate::session::Status;
+        use std::time::Duration;
+
+        let now = Instant::now();
+        for inst in &self.instances {
+            if matches!(inst.status, Status::Stopped | Status::Deleting) {
+                self.health.remove(&inst.id);
+                continue;
+            }
+            if self.health_pending.contains(&inst.id)
+                || self.health_next_due.get(&inst.id).is_some_and(|t| *t > now)
+            {
+                continue;
+            }
+
+            let profile = if inst.source_profile.is_empty() {
+                self.config_profile()
+            } else {
+                &inst.source_profile
+            };
+            let project_path = std::path::PathBuf::from(&inst.project_path);
+            match resolve_health_check(profile, &project_path) {
+                Some((command, interval)) => {
+                    self.health_next_due
+                        .insert(inst.id.clone(), now + Duration::from_secs(interval));
+                    self.health_pending.insert(inst.id.clone());
+                    self.health_poller
+                        .request_check(inst.id.clone(), command, project_path);
+                }
+                None => {
+                    self.health.remove(&inst.id);
+                    self.health_next_due.insert(
+                        inst.id.clone(),
+                        now + Duration::from_secs(DEFAULT_HEALTH_CHECK_INTERVAL_SECS),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Keep test watchers running beside live sessions whose config defines
+    /// one, and read each watcher's counts in the background.
+    pub fn request_test_watches(&mut self) {
+        use crate::session::repo_config::{resolve_test_watch, DEFAULT_HEALTH_CHECK_INTERVAL_SECS};
+        use crate::session::Status;
+
+        if self.is_demo() {
+            return;
+        }
+        if self.watch_resolved_at.map_or(true, |t| {
+            t.elapsed().as_secs() >= DEFAULT_HEALTH_CHECK_INTERVAL_SECS
+        }) {
+            self.watch_commands.clear();
+            self.watch_resolved_at = Some(Instant::now());
+        }
+
+        let sessions: Vec<(String, Status, String, String)> = self
+            .instances
+            .iter()
+            .map(|i| {
+                (
+                    i.id.clone(),
+                    i.status,
+                    i.source_profile.clone(),
+                    i.project_path.clone(),
+                )
+            })
+            .collect();
+        for (id, status, profile, project_path) in sessions {
+            if matches!(status, Status::Stopped | Status::Deleting) {
+                self.test_counts.remove(&id);
+                self.watch_failed.remove(&id);
+                continue;
+            }
+            if self.watch_pending.contains(&id) || self.watch_failed.contains(&id) {
+                continue;
+            }
+            let profile = if profile.is_empty() {
+                self.config_profile().to_string()
+            } else {
+                profile
+            };
+            let command = self
+                .watch_commands
+                .entry(id.clone())
+                .or_insert_with(|| {
+                    resolve_test_watch(&profile, std::path::Path::new(&project_path))
+                })
+                .clone();
+            let Some(command) = command else {
+                self.test_counts.remove(&id);
+                continue;
+            };
+            let Some(mut inst) = self.get_instance(&id).cloned() else {
+                continue;
+            };
+
+            // A watcher that exited keeps its pane, so only a killed one is
+            // started again
+            match inst.start_test_watch_with_size(&command, crate::terminal::get_size()) {
+                Ok(session) => {
+                    self.watch_pending.insert(id.clone());
+                    self.watch_poller.request_read(id, session);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to start test watcher for {}: {}", inst.title, e);
+                    self.test_counts.remove(&id);
+                    self.watch_failed.insert(id);
+                }
+            }
+        }
+    }
+
+    /// Apply what the test watchers showed. A session whose tests start
+    /// failing shows a toast. Returns true if any counts changed.
+    pub fn apply_watch_results(&mut self) -> bool {
+        let mut changed = false;
+        for result in self.watch_poller.try_recv_results() {
+            self.watch_pending.remove(&result.id);
+            // Keep the last counts while a run is in progress
+            let Some(counts) = result.counts else {
+                continue;
+            };
+            let previous = self.test_counts.insert(result.id.clone(), counts);
+            if previous == Some(counts) {
+                continue;
+            }
+            changed = true;
+            if counts.failed > 0 && previous.map_or(true, |p| p.failed == 0) {
+                if let Some(title) = self.get_instance(&result.id).map(|i| i.title.clone()) {
+                    self.show_toast(Toast::error(format!(
+                        "{}: {} tests failing",
+                        title, counts.failed
+                    )));
+                }
+            }
+        }
+        changed
+    }
+
+    /// Apply finished health checks. A session that starts failing plays the
+    /// error sound and shows a toast; recovery shows an info toast. Both are
+    /// recorded on the session's timeline. Returns true if anything changed.
+    pub fn apply_health_results(&mut self) -> bool {
+        let results = self.health_poller.try_recv_results();
+        let changed = !results.is_empty();
+
+        for result in results {
+            self.health_pending.remove(&result.id);
+            let Some(title) = self.get_instance(&result.id).map(|i| i.title.clone()) else {
+                continue;
+            };
+            let was_failing = matches!(self.health.get(&result.id), Some(Health::Failing(_)));
+
+            match result.failure {
+                None => {
+                    if was_failing {
+                        timeline::record(
+                            &result.id,
+                            timeline::EventKind::Hook,
+                            "health_check passing",
+                        );
+                        self.show_toast(Toast::info(format!("{}: health check passing", title)));
+                    }
+                    self.health.insert(result.id, Health::Passing);
+                }
+                Some(detail) => {
+                    if !was_failing {
+                        timeline::record(
+                            &result.id,
+                            timeline::EventKind::Hook,
+                            format!("health_check failed: {}", detail),
+                        );
+                        crate::sound::play_for_failure(&self.sound_config);
+                        self.notify(
+                            &result.id,
+                            Event::Error,
+                            format!("health check failed: {}", detail),
+                        );
+                        self.show_toast(Toast::error(format!(
+                            "{}: health check failed ({})",
+                            title, detail
+                        )));
+                    }
+                    self.health.insert(result.id, Health::Failing(detail));
+                }
+            }
+        }
+        changed
+    }
+
+    /// Named commands for the session's project, loaded once and cached.
+    fn commands_for(&mut self, id: &str) -> Vec<NamedCommand> {
+        let Some(project_path) = self.get_instance(id).map(|i| i.project_path.clone()) else {
+            return Vec::new();
+        };
+        self.commands_cache
+            .entry(project_path.clone())
+            .or_insert_with(|| commands::for_project(std::path::Path::new(&project_path)))
+            .clone()
+    }
+
+    /// Command keys for the preview, if the session's project defines any.
+    pub(super) fn commands_summary(&mut self, id: &str) -> Option<String> {
+        commands::summary(&self.commands_for(id))
+    }
+
+    /// Open the command menu for a session, re-reading its project's
+    /// commands so edits to the repo config show up.
+    pub(super) fn open_command_menu(&mut self, id: &str) {
+        let Some(inst) = self.get_instance(id) else {
+            return;
+        };
+        let title = inst.title.clone();
+        let project_path = inst.project_path.clone();
+        let has_output = inst
+            .command_tmux_session()
+            .is_ok_and(|session| session.exists());
+        self.commands_cache.remove(&project_path);
+        let named = self.commands_for(id);
+        if named.is_empty() {
+            self.show_toast(Toast::info(
+                "No commands defined; add a [commands] table to .aoe/config.toml",
+            ));
+            return;
+        }
+        self.command_menu = Some(CommandMenuDialog::new(
+            id,
+            &title,
+            named,
+            self.running_commands.get(id).cloned(),
+            has_output,
+        ));
+    }
+
+    pub(super) fn open_checkpoints_dialog(&mut self, id: &str) {
+        let Some(inst) = self.get_instance(id) else {
+            return;
+        };
+        self.checkpoints_dialog = Some(CheckpointsDialog::new(
+            id,
+            &inst.title,
+            inst.checkpoints.clone(),
+        ));
+    }
+
+    /// Carry out an action chosen in the checkpoints dialog, which stays
+    /// open with the updated list.
+    pub(super) fn apply_checkpoint_action(&mut self, id: &str, action: CheckpointAction) {
+        let result = match &action {
+            CheckpointAction::View(name) => {
+                let Some(inst) = self.get_instance(id) else {
+                    return;
+                };
+                let output = checkpoints::find(inst, name)
+                    .and_then(|cp| checkpoints::transcript_since(inst, cp));
+                match (output, &mut self.checkpoints_dialog) {
+                    (Some(output), Some(dialog)) => dialog.show_transcript(&output),
+                    (None, _) => self.show_toast(Toast::info(
+                        "Output since this checkpoint is only available while the session runs",
+                    )),
+                    _ => {}
+                }
+                return;
+            }
+            CheckpointAction::Create(name) => self
+                .try_mutate_instance(id, |inst| checkpoints::create(inst, name, None).map(|_| ()))
+                .map(|()| format!("Created checkpoint '{}'", name)),
+            CheckpointAction::Rollback(name) => {
+                let mut saved = None;
+                self.try_mutate_instance(id, |inst| {
+                    saved = Some(checkpoints::rollback(inst, name)?.name);
+                    Ok(())
+                })
+                .map(|()| {
+                    format!(
+                        "Rolled back to '{}'; previous state saved as '{}'",
+                        name,
+                        saved.unwrap_or_default()
+                    )
+                })
+            }
+            CheckpointAction::Remove(name) => self
+                .try_mutate_instance(id, |inst| checkpoints::remove(inst, name))
+                .map(|()| format!("Removed checkpoint '{}'", name)),
+        };
+
+        match result {
+            Ok(message) => {
+                if let Err(e) = self.save() {
+                    tracing::warn!("Failed to save checkpoints: {}", e);
+                }
+                self.show_toast(Toast::info(message));
+            }
+            Err(e) => self.show_toast(Toast::error(format!("Checkpoint failed: {}", e))),
+        }
+        let list = self
+            .get_instance(id)
+            .map(|i| i.checkpoints.clone())
+            .unwrap_or_default();
+        if let Some(dialog) = &mut self.checkpoints_dialog {
+            dialog.set_checkpoints(list);
+        }
+    }
+
+    /// Show the process tree of the session's agent pane. Sandboxed agents
+    /// run inside their container, out of reach of the host's `ps`.
+    pub(super) fn open_processes_dialog(&mut self, id: &str) {
+        let Some(inst) = self.get_instance(id) else {
+            return;
+        };
+        if inst.is_sandboxed() {
+            self.show_toast(Toast::info(
+                "Processes of sandboxed sessions run inside their container",
+            ));
+            return;
+        }
+        let root = inst.tmux_session().ok().and_then(|s| s.get_pane_pid());
+        let Some(root) = root else {
+            self.show_toast(Toast::info("Session is not running"));
+            return;
+        };
+        let title = inst.title.clone();
+        self.processes_dialog = Some(ProcessesDialog::new(
+            &title,
+            root,
+            process::process_tree(root),
+        ));
+    }
+
+    /// Carry out an action chosen in the processes dialog, which stays open
+    /// with a fresh tree. A signal is only sent while the pid is still in
+    /// the session's tree: the one shown may have exited and its pid been
+    /// reused by an unrelated process.
+    pub(super) fn apply_process_action(&mut self, action: ProcessAction) {
+        let Some(dialog) = &mut self.processes_dialog else {
+            return;
+        };
+        let root = dialog.root_pid();
+        if let ProcessAction::Signal { pid, signal } = action {
+            if !process::process_tree(root).iter().any(|p| p.pid == pid) {
+                self.show_toast(Toast::error(format!(
+                    "Process {} is no longer part of this session",
+                    pid
+                )));
+            } else {
+                match process::send_signal(pid, signal) {
+                    Ok(()) => self.show_toast(Toast::info(format!("Sent {} to {}", signal, pid))),
+                    Err(e) => self.show_toast(Toast::error(e.to_string())),
+                }
+            }
+        }
+        if let Some(dialog) = &mut self.processes_dialog {
+            dialog.set_processes(process::process_tree(root));
+        }
+    }
+
+    /// Run a named command in the session's companion tmux session and
+    /// watch it until it exits.
+    pub(super) fn run_named_command(&mut self, id: &str, name: &str) {
+        let Some(command) = self.commands_for(id).into_iter().find(|c| c.name == name) else {
+            return;
+        };
+        let Some(mut inst) = self.get_instance(id).cloned() else {
+            return;
+        };
+        match inst.start_command_with_size(&command.run, crate::terminal::get_size()) {
+            Ok(session) => {
+                self.running_commands
+                    .insert(id.to_string(), command.name.clone());
+                self.command_poller
+                    .watch(id.to_string(), command.name.clone(), session);
+                self.show_toast(Toast::info(format!(
+                    "{}: running {} (! then Enter to view)",
+                    inst.title, command.name
+                )));
+            }
+            Err(e) => {
+                self.show_toast(Toast::error(format!(
+                    "{}: could not run {}: {}",
+                    inst.title, command.name, e
+                )));
+            }
+        }
+    }
+
+    /// Apply finished named commands: record each result on the session's
+    /// timeline and show a toast. Returns true if anything finished.
+    pub fn apply_command_results(&mut self) -> bool {
+        let results = self.command_poller.try_recv_results();
+        let changed = !results.is_empty();
+
+        for result in results {
+            // A newer run of another command replaced this one
+            if self.running_commands.get(&result.id) != Some(&result.name) {
+                continue;
+            }
+            self.running_commands.remove(&result.id);
+            commands::record_result(&result.id, &result.name, result.exit_code);
+            let Some(title) = self.get_instance(&result.id).map(|i| i.title.clone()) else {
+                continue;
+            };
+            let message = format!(
+                "{}: {}",
+                title,
+                commands::result_message(&result.name, result.exit_code)
+            );
+            if result.exit_code.is_some_and(|code| code != 0) {
+                self.show_toast(Toast::error(message));
+            } else {
+                self.show_toast(Toast::info(message));
+            }
+        }
+        changed
+    }
+
+    /// Start merge checks for worktree sessions whose interval has elapsed,
+    /// unless `worktree.on_merged` is off for their project.
+    pub fn request_merge_checks(&mut self) {
+        use crate::session::{repo_config, MergedSessionAction, Status};
+
+        // Demo sessions have no repositories to check
+        if self.demo.is_some() {
+            return;
+        }
+
+        let now = Instant::now();
+        let check_interval =
+            Duration::from_secs(self.polling.git_check_secs) * self.poll_slowdown();
+        for inst in &self.instances {
+            let Some(wt) = &inst.worktree_info else {
+                continue;
+            };
+            if inst.status == Status::Deleting
+                || self.merged_branches.contains_key(&inst.id)
+                || self.merge_pending.contains(&inst.id)
+                || self.merge_next_due.get(&inst.id).is_some_and(|t| *t > now)
+            {
+                continue;
+            }
+            self.merge_next_due
+                .insert(inst.id.clone(), now + check_interval);
+
+            let profile = if inst.source_profile.is_empty() {
+                self.config_profile()
+            } else {
+                &inst.source_profile
+            };
+            let action = repo_config::resolve_config_with_repo(
+                profile,
+                std::path::Path::new(&inst.project_path),
+            )
+            .map(|c| c.worktree.on_merged)
+            .unwrap_or_default();
+            if action == MergedSessionAction::Off {
+                continue;
+            }
+
+            self.merge_pending.insert(inst.id.clone());
+            self.merge_poller.request_check(
+                inst.id.clone(),
+                std::path::PathBuf::from(&wt.main_repo_path),
+                wt.branch.clone(),
+                wt.created_at,
+            );
+        }
+    }
+
+    /// Apply finished merge checks. A merged branch is recorded on the
+    /// session's timeline and, depending on `worktree.on_merged`, the session
+    /// is archived right away or queued to be offered for cleanup. Returns
+    /// true if anything changed.
+    pub fn apply_merge_results(&mut self) -> bool {
+        use crate::session::{repo_config, MergedSessionAction};
+
+        let results = self.merge_poller.try_recv_results();
+        let mut changed = !results.is_empty();
+
+        for result in results {
+            self.merge_pending.remove(&result.id);
+            let Some(how) = result.merged else {
+                continue;
+            };
+            let Some(inst) = self.get_instance(&result.id) else {
+                continue;
+            };
+            let branch = inst
+                .worktree_info
+                .as_ref()
+                .map(|wt| wt.branch.clone())
+                .unwrap_or_default();
+            let profile = if inst.source_profile.is_empty() {
+                self.config_profile().to_string()
+            } else {
+                inst.source_profile.clone()
+            };
+            let action = repo_config::resolve_config_with_repo(
+                &profile,
+                std::path::Path::new(&inst.project_path),
+            )
+            .map(|c| c.worktree.on_merged)
+            .unwrap_or_default();
+
+            timeline::record(
+                &result.id,
+                timeline::EventKind::Git,
+                format!("branch {} {}", branch, how),
+            );
+            self.merged_branches.insert(result.id.clone(), how);
+            match action {
+                MergedSessionAction::Archive => self.archive_merged_session(&result.id),
+                MergedSessionAction::Prompt => self.merged_queue.push(result.id),
+                MergedSessionAction::Off => {}
+            }
+        }
+
+        if !self.merged_queue.is_empty() && !self.has_dialog() {
+            self.offer_merged_cleanup();
+            changed = true;
+        }
+        changed
     }
 
-    pub fn reload(&mut self) -> anyhow::Result<()> {
-        use crate::session::list_profiles;
+    /// Ask whether to archive the next queued merged session.
+    fn offer_merged_cleanup(&mut self) {
+        while !self.merged_queue.is_empty() {
+            let id = self.merged_queue.remove(0);
+            let Some(inst) = self.get_instance(&id) else {
+                continue;
+            };
+            let how = self.merged_branches.get(&id).cloned().unwrap_or_default();
+            let branch = inst
+                .worktree_info
+                .as_ref()
+                .map(|wt| wt.branch.as_str())
+                .unwrap_or_default();
+            self.confirm_dialog = Some(ConfirmDialog::new(
+                "Branch Merged",
+                &format!(
+                    "'{}': branch '{}' {}. Archive the session and remove its worktree?",
+                    inst.title, branch, how
+                ),
+                "archive_merged",
+            ));
+            self.pending_merged_cleanup = Some(id);
+            return;
+        }
+    }
 
-        let mut all_instances = Vec::new();
+    /// Start conflict checks for worktree sessions whose interval has elapsed.
+    pub fn request_conflict_checks(&mut self) {
+        use crate::session::Status;
 
-        // Re-discover profiles in "all" mode
-        if self.active_profile.is_none() {
-            let current_profiles = list_profiles()?;
-            for name in &current_profiles {
-                if !self.storages.contains_key(name) {
-                    self.storages.insert(name.clone(), Storage::new(name)?);
-                }
+        // Demo sessions have no worktrees to check
+        if self.demo.is_some() {
+            return;
+        }
+
+        let now = Instant::now();
+        let check_interval =
+            Duration::from_secs(self.polling.git_check_secs) * self.poll_slowdown();
+        for inst in &self.instances {
+            if inst.worktree_info.is_none()
+                || inst.status == Status::Deleting
+                || self.conflict_pending.contains(&inst.id)
+                || self
+                    .conflict_next_due
+                    .get(&inst.id)
+                    .is_some_and(|t| *t > now)
+            {
+                continue;
             }
-            self.storages.retain(|k, _| current_profiles.contains(k));
+            self.conflict_next_due
+                .insert(inst.id.clone(), now + check_interval);
+            self.conflict_pending.insert(inst.id.clone());
+            self.conflict_poller.request_check(
+                inst.id.clone(),
+                std::path::PathBuf::from(&inst.project_path),
+            );
         }
+    }
 
-        for (profile_name, storage) in &self.storages {
-            let (mut instances, groups) = storage.load_with_groups()?;
-            for inst in &mut instances {
-                inst.source_profile = profile_name.clone();
-                if let Some(prev) = self.instance_map.get(&inst.id) {
-                    inst.status = prev.status;
-                    inst.last_error = prev.last_error.clone();
-                    inst.last_error_check = prev.last_error_check;
-                    inst.last_start_time = prev.last_start_time;
+    /// Apply finished conflict checks. A session newly stopped on conflicts
+    /// is flagged, recorded on its timeline and announced with a toast
+    /// pointing at the resolve action. Returns true if any flag changed.
+    pub fn apply_conflict_results(&mut self) -> bool {
+        let mut changed = false;
+        for result in self.conflict_poller.try_recv_results() {
+            self.conflict_pending.remove(&result.id);
+            let Some(title) = self.get_instance(&result.id).map(|i| i.title.clone()) else {
+                continue;
+            };
+            match result.conflict {
+                Some(conflict) => {
+                    if self.conflicts.get(&result.id) == Some(&conflict) {
+                        continue;
+                    }
+                    if !self.conflicts.contains_key(&result.id) {
+                        timeline::record(
+                            &result.id,
+                            timeline::EventKind::Git,
+                            format!("stopped on {}", conflict.summary()),
+                        );
+                        self.show_toast(Toast::error(format!(
+                            "'{}': {} (F to ask the agent to resolve them)",
+                            title,
+                            conflict.summary()
+                        )));
+                    }
+                    self.conflicts.insert(result.id, conflict);
+                    changed = true;
+                }
+                None => {
+                    if self.conflicts.remove(&result.id).is_some() {
+                        timeline::record(
+                            &result.id,
+                            timeline::EventKind::Git,
+                            "conflicts resolved",
+                        );
+                        changed = true;
+                    }
                 }
             }
-            // Rebuild this profile's tree from disk, preserving any collapsed
-            // state that was toggled in-memory but not yet on disk
-            let mut new_tree = GroupTree::new_with_groups(&instances, &groups);
-            if let Some(old_tree) = self.group_trees.get(profile_name) {
-                for g in old_tree.get_all_groups() {
-                    if g.collapsed {
-                        new_tree.set_collapsed(&g.path, true);
+        }
+        changed
+    }
+
+    /// The held lock keeping session `id` from starting, and the title of the
+    /// session holding it.
+    pub fn lock_conflict(&self, id: &str) -> Option<(String, String)> {
+        let inst = self.get_instance(id)?;
+        locks::conflict(inst, &self.instances).map(|c| (c.lock.to_string(), c.holder.title.clone()))
+    }
+
+    /// Queue a session blocked by a held lock. It is started (but not
+    /// attached) by `start_unblocked_sessions` once the lock is released.
+    pub fn queue_for_lock(&mut self, id: &str, lock: &str, holder: &str) {
+        let Some(title) = self.get_instance(id).map(|i| i.title.clone()) else {
+            return;
+        };
+        if !self.lock_queue.iter().any(|q| q == id) {
+            self.lock_queue.push(id.to_string());
+            timeline::record(
+                id,
+                timeline::EventKind::Status,
+                format!("queued for lock {} (held by {})", lock, holder),
+            );
+        }
+        self.show_toast(Toast::info(format!(
+            "{}: queued, lock '{}' is held by {}",
+            title, lock, holder
+        )));
+    }
+
+    /// The provider cap keeping session `id` from starting, if its provider
+    /// already has as many active sessions as it may.
+    pub fn provider_limit(&self, id: &str) -> Option<LimitReached> {
+        let inst = self.get_instance(id)?;
+        providers::limit_reached(inst, &self.instances, &self.provider_limits)
+    }
+
+    /// Queue a session over its provider's cap. It is started (but not
+    /// attached) by `start_unblocked_sessions` once a slot frees up.
+    pub fn queue_for_provider(&mut self, id: &str, reached: LimitReached) {
+        let Some(title) = self.get_instance(id).map(|i| i.title.clone()) else {
+            return;
+        };
+        if !self.lock_queue.iter().any(|q| q == id) {
+            self.lock_queue.push(id.to_string());
+            timeline::record(
+                id,
+                timeline::EventKind::Status,
+                format!(
+                    "queued, {} is at its limit of {} sessions",
+                    reached.provider.name(),
+                    reached.limit
+                ),
+            );
+        }
+        self.show_toast(Toast::info(format!(
+            "{}: queued, {} sessions are at their limit of {}",
+            title,
+            reached.provider.name(),
+            reached.limit
+        )));
+    }
+
+    /// Active sessions per provider for the list title, e.g.
+    /// "anthropic 2/3 · openai 1". None when no session is active.
+    pub(super) fn provider_title(&self) -> Option<String> {
+        let counts = providers::active_counts(&self.instances);
+        if counts.is_empty() {
+            return None;
+        }
+        let parts: Vec<String> = counts
+            .into_iter()
+            .map(
+                |(provider, count)| match self.provider_limits.get(&provider) {
+                    Some(limit) => format!("{} {}/{}", provider.name(), count, limit),
+                    None => format!("{} {}", provider.name(), count),
+                },
+            )
+            .collect();
+        Some(parts.join(" · "))
+    }
+
+    /// One-line lock ownership for the preview, e.g.
+    /// "db (held by fix-schema), queued".
+    pub(super) fn lock_summary(&self, id: &str) -> Option<String> {
+        let inst = self.get_instance(id)?;
+        if inst.locks.is_empty() {
+            return None;
+        }
+        let mut parts: Vec<String> = locks::owners(inst, &self.instances)
+            .into_iter()
+            .map(|(lock, owner)| match owner {
+                locks::LockOwner::This => format!("{} (held)", lock),
+                locks::LockOwner::Other(title) => format!("{} (held by {})", lock, title),
+                locks::LockOwner::Free => format!("{} (free)", lock),
+            })
+            .collect();
+        if self.lock_queue.iter().any(|q| q == id) {
+            parts.push("queued".to_string());
+        }
+        Some(parts.join(", "))
+    }
+
+    /// Start queued sessions whose locks have been released and whose
+    /// providers are under their caps, oldest first.
+    /// Returns true if anything changed.
+    pub fn start_unblocked_sessions(&mut self) -> bool {
+        use crate::session::Status;
+
+        if self.lock_queue.is_empty() {
+            return false;
+        }
+        let mut changed = false;
+        for id in std::mem::take(&mut self.lock_queue) {
+            let Some(inst) = self.get_instance(&id) else {
+                changed = true;
+                continue;
+            };
+            if locks::holds_locks(inst.status) {
+                // Started some other way (e.g. attached) since it was queued
+                changed = true;
+                continue;
+            }
+            if locks::conflict(inst, &self.instances).is_some()
+                || providers::limit_reached(inst, &self.instances, &self.provider_limits).is_some()
+                || self.budget_blocks(&inst.source_profile)
+            {
+                self.lock_queue.push(id);
+                continue;
+            }
+
+            let mut inst = inst.clone();
+            let had_prompt = inst.initial_prompt.is_some();
+            let skip_on_launch = self.take_on_launch_hooks_ran(&id);
+            self.set_instance_status(&id, Status::Starting);
+            match inst.start_with_size_opts(crate::terminal::get_size(), skip_on_launch) {
+                Ok(()) => {
+                    self.set_instance_error(&id, None);
+                    if had_prompt && inst.initial_prompt.is_none() {
+                        if let Err(e) = self.clear_initial_prompt(&id) {
+                            tracing::warn!("Failed to clear initial prompt: {}", e);
+                        }
                     }
+                    self.show_toast(Toast::info(format!(
+                        "{}: no longer blocked, session started",
+                        inst.title
+                    )));
                 }
+                Err(e) => self.report_start_failure(&id, &e),
             }
-            self.group_trees.insert(profile_name.clone(), new_tree);
-            all_instances.extend(instances);
+            changed = true;
         }
+        changed
+    }
 
-        // Remove trees for profiles that no longer exist
-        let storage_keys: Vec<String> = self.storages.keys().cloned().collect();
-        self.group_trees.retain(|k, _| storage_keys.contains(k));
+    /// Re-check every shown profile's budgets once a minute, or sooner after
+    /// a session reported a cost. Announces a budget crossing its warning
+    /// threshold or running out. Returns true if anything changed.
+    pub fn check_budgets(&mut self) -> bool {
+        if self.is_demo()
+            || self
+                .budget_checked_at
+                .is_some_and(|t| t.elapsed() < BUDGET_CHECK_INTERVAL)
+        {
+            return false;
+        }
+        self.budget_checked_at = Some(Instant::now());
 
-        self.instances = all_instances;
-        self.instance_map = self
+        let profiles: Vec<String> = self.storages.keys().cloned().collect();
+        let mut changed = false;
+        for profile in profiles {
+            let Some(check) = self.refresh_budget(&profile) else {
+                continue;
+            };
+            let previous = self.budgets.insert(profile.clone(), check.clone());
+            if previous.as_ref() == Some(&check) {
+                continue;
+            }
+            changed = true;
+            let before = previous.map_or(0, |p| p.state.severity());
+            if check.state.severity() > before {
+                self.announce_budget(&profile, &check);
+            }
+        }
+        changed
+    }
+
+    /// `profile`'s budget standing from the stats log and its live sessions
+    fn refresh_budget(&self, profile: &str) -> Option<BudgetCheck> {
+        let live: f64 = self
             .instances
             .iter()
-            .map(|i| (i.id.clone(), i.clone()))
-            .collect();
-        self.flat_items = self.build_flat_items();
+            .filter(|i| i.source_profile == profile)
+            .filter_map(|i| self.live_costs.get(&i.id))
+            .sum();
+        match budget::check_profile(profile, live) {
+            Ok(check) => Some(check),
+            Err(e) => {
+                tracing::warn!("Failed to check budget for {}: {}", profile, e);
+                None
+            }
+        }
+    }
 
-        if self.cursor >= self.flat_items.len() && !self.flat_items.is_empty() {
-            self.cursor = self.flat_items.len() - 1;
+    fn announce_budget(&mut self, profile: &str, check: &BudgetCheck) {
+        let message = match (&check.state, check.block_reason()) {
+            (_, Some(reason)) => reason,
+            (BudgetState::Exceeded(usage), None) => format!("{} used up", usage.describe()),
+            (BudgetState::Warning(usage), None) => {
+                format!("{} ({:.0}% spent)", usage.describe(), usage.percent())
+            }
+            (BudgetState::Ok, None) => return,
+        };
+        self.show_toast(Toast::error(format!("[{}] {}", profile, message)));
+    }
+
+    /// Whether `profile`'s budget is used up and set to hold new and queued
+    /// sessions, as of the last check
+    fn budget_blocks(&self, profile: &str) -> bool {
+        let profile = if profile.is_empty() {
+            self.config_profile()
+        } else {
+            profile
+        };
+        self.budgets
+            .get(profile)
+            .is_some_and(|check| check.block_reason().is_some())
+    }
+
+    /// Why a session can't be created in `profile` right now, checked
+    /// against the latest spend
+    pub(super) fn budget_block_reason(&mut self, profile: &str) -> Option<String> {
+        let check = self.refresh_budget(profile)?;
+        let reason = check.block_reason();
+        self.budgets.insert(profile.to_string(), check);
+        reason
+    }
+
+    /// Re-check credential expiry dates for every shown profile once a
+    /// minute. A credential entering its warning window is announced and its
+    /// refresh command run, once. Returns true if the warnings changed.
+    pub fn check_credentials(&mut self) -> bool {
+        if self.is_demo()
+            || self
+                .credentials_checked_at
+                .is_some_and(|t| t.elapsed() < CREDENTIAL_CHECK_INTERVAL)
+        {
+            return false;
+        }
+        self.credentials_checked_at = Some(Instant::now());
+
+        let now = chrono::Utc::now();
+        let mut profiles: Vec<String> = self.storages.keys().cloned().collect();
+        profiles.sort();
+        let mut warnings = Vec::new();
+        for profile in profiles {
+            let Ok(config) = resolve_config(&profile) else {
+                continue;
+            };
+            let expiring = credentials::expiring(
+                &config.session.credential_expiry,
+                config.session.credential_warn_days,
+                now,
+            );
+            for item in expiring {
+                let key = (profile.clone(), item.clone());
+                if self.credentials_refreshed.insert(key) {
+                    credentials::run_refresh(&config.session, &profile, &item);
+                    self.show_toast(Toast::error(format!(
+                        "[{}] {}",
+                        profile,
+                        item.describe(now)
+                    )));
+                }
+                warnings.push((profile.clone(), item));
+            }
         }
+        warnings.sort_by_key(|(_, item)| item.expires_at);
+        if warnings == self.credential_warnings {
+            return false;
+        }
+        self.credential_warnings = warnings;
+        true
+    }
 
-        if self.search_active && !self.search_query.value().is_empty() {
-            self.update_search();
-        } else if !self.search_matches.is_empty() {
-            // Recalculate match indices without moving the cursor
-            self.refresh_search_matches();
+    /// Replace the list of agents with a newer release. Returns true if it
+    /// changed.
+    pub fn set_agent_updates(&mut self, updates: Vec<AgentVersion>) -> bool {
+        if updates == self.agent_updates {
+            return false;
         }
+        self.agent_updates = updates;
+        true
+    }
 
-        self.update_selected();
-        Ok(())
+    pub fn agent_updates(&self) -> &[AgentVersion] {
+        &self.agent_updates
     }
 
-    /// Request a status refresh in the background (non-blocking).
-    /// Call `apply_status_updates` to check for and apply results.
-    pub fn request_status_refresh(&mut self) {
-        if !self.pending_status_refresh {
-            let instances: Vec<Instance> = self.instances.clone();
-            self.status_poller.request_refresh(instances);
-            self.pending_status_refresh = true;
+    /// Half the preview's height, for Shift+PgUp/PgDn
+    pub(super) fn preview_half_page(&self) -> i32 {
+        i32::from(self.preview_cache.dimensions.1 / 2).max(1)
+    }
+
+    /// Scroll the selected session's output preview up by `lines` (down if
+    /// negative). Each session keeps its own position; scrolling back to the
+    /// bottom follows new output again.
+    pub(super) fn scroll_preview(&mut self, lines: i32) {
+        let Some(id) = self.selected_session.clone() else {
+            return;
+        };
+        let current = self.preview_scroll.get(&id).copied().unwrap_or(0);
+        let next = (i32::from(current) + lines).clamp(0, i32::from(PREVIEW_MAX_SCROLL)) as u16;
+        if next == current {
+            return;
+        }
+        if next == 0 {
+            self.preview_scroll.remove(&id);
+        } else {
+            self.preview_scroll.insert(id, next);
         }
+        self.preview_cache.invalidate();
     }
 
-    /// Apply any pending status updates from the background poller.
-    /// Returns true if updates were applied.
-    pub fn apply_status_updates(&mut self) -> bool {
+    /// The soonest credential warning for the banner, e.g.
+    /// "claude credentials expire in 2d 4h (+1 more)", and whether it
+    /// already expired
+    pub(super) fn credential_banner(&self) -> Option<(String, bool)> {
+        let (profile, first) = self.credential_warnings.first()?;
+        let now = chrono::Utc::now();
+        let mut text = first.describe(now);
+        if self.storages.len() > 1 {
+            text = format!("[{}] {}", profile, text);
+        }
+        let more = self.credential_warnings.len() - 1;
+        if more > 0 {
+            text.push_str(&format!(" (+{} more)", more));
+        }
+        Some((text, first.is_expired(now)))
+    }
+
+    /// The terminal title from `theme.terminal_title`, or None if it is
+    /// empty and the user's title is left alone
+    pub fn terminal_title(&self) -> Option<String> {
         use crate::session::Status;
 
-        if let Some(updates) = self.status_poller.try_recv_updates() {
-            for update in updates {
-                let old_status = self.get_instance(&update.id).map(|i| i.status);
+        if self.terminal_title.trim().is_empty() {
+            return None;
+        }
+        let mut counts = super::terminal_title::Counts::default();
+        for inst in self.instances() {
+            counts.total += 1;
+            match inst.status {
+                Status::Running => counts.running += 1,
+                Status::Waiting => counts.waiting += 1,
+                Status::Error => counts.error += 1,
+                _ => {}
+            }
+        }
+        Some(super::terminal_title::render(
+            &self.terminal_title,
+            self.active_profile_display(),
+            &counts,
+        ))
+    }
 
-                let should_update = old_status.is_some_and(|s| {
-                    s != Status::Deleting
-                        && s != Status::Stopped
-                        && update.status != Status::Stopped
-                });
+    /// The shown profile's budget warning for the list title, e.g.
+    /// "daily budget: $8.20 of $10"
+    pub(super) fn budget_title(&self) -> Option<String> {
+        let check = self.budgets.get(self.active_profile.as_deref()?)?;
+        check.state.usage().map(|usage| usage.describe())
+    }
 
-                if should_update {
-                    let new_status = update.status;
-                    let new_error = update.last_error;
-                    self.mutate_instance(&update.id, |inst| {
-                        inst.status = new_status;
-                        inst.last_error = new_error;
-                    });
-
-                    if let Some(old) = old_status {
-                        if old != new_status {
-                            crate::sound::play_for_transition(old, new_status, &self.sound_config);
-                        }
-                    }
+    /// Mark `id` as failed to start, record why on its timeline, and open
+    /// the triage dialog. A session with a fallback agent switches to it
+    /// instead.
+    pub fn report_start_failure(&mut self, id: &str, error: &anyhow::Error) {
+        let message = format!("{:#}", error);
+        if self.fail_over(id, &format!("failed to start ({})", message)) {
+            return;
+        }
+        self.set_instance_error(id, Some(error.to_string()));
+        self.set_instance_status(id, crate::session::Status::Error);
+        timeline::record(
+            id,
+            timeline::EventKind::Status,
+            format!("failed to start: {}", message),
+        );
+        if let Some(inst) = self.get_instance(id) {
+            self.start_failure_dialog =
+                Some(super::dialogs::StartFailureDialog::new(inst, &message));
+        }
+    }
+
+    /// Remember that a login session was opened for `id`, so the agent is
+    /// restarted with its new credentials once the login finishes.
+    pub fn begin_reauth(&mut self, id: &str) {
+        if !self.reauth_pending.iter().any(|p| p == id) {
+            self.reauth_pending.push(id.to_string());
+        }
+        timeline::record(id, timeline::EventKind::Status, "re-authentication started");
+    }
+
+    /// Restart sessions whose login session has closed. Returns true if any
+    /// session was resumed.
+    pub fn resume_after_login(&mut self) -> bool {
+        use crate::session::Status;
+
+        if self.reauth_pending.is_empty() {
+            return false;
+        }
+        let mut changed = false;
+        for id in std::mem::take(&mut self.reauth_pending) {
+            let Some(inst) = self.get_instance(&id) else {
+                changed = true;
+                continue;
+            };
+            if inst.login_tmux_session().is_ok_and(|s| s.exists()) {
+                self.reauth_pending.push(id);
+                continue;
+            }
+
+            let mut inst = inst.clone();
+            self.set_instance_status(&id, Status::Starting);
+            match inst.restart_with_size(crate::terminal::get_size()) {
+                Ok(()) => {
+                    self.set_instance_error(&id, None);
+                    self.mutate_instance(&id, |i| i.auth_required = false);
+                    timeline::record(
+                        &id,
+                        timeline::EventKind::Status,
+                        "restarted after re-authentication",
+                    );
+                    self.show_toast(Toast::info(format!(
+                        "{}: restarted after login",
+                        inst.title
+                    )));
                 }
+                Err(e) => self.report_start_failure(&id, &e),
             }
-            self.pending_status_refresh = false;
-            return true;
+            changed = true;
         }
-        false
+        changed
     }
 
     pub fn apply_deletion_results(&mut self) -> bool {
@@ -417,6 +2286,9 @@ impl HomeView {
             if result.success {
                 self.remove_instance(&result.session_id);
                 self.rebuild_group_trees();
+                // Its cost is in the stats log now
+                self.live_costs.remove(&result.session_id);
+                self.budget_checked_at = None;
 
                 if let Err(e) = self.save() {
                     tracing::error!("Failed to save after deletion: {}", e);
@@ -457,6 +2329,63 @@ impl HomeView {
         self.creation_poller.request_creation(request);
     }
 
+    /// Clone the git URL in `data.path` in the background; the session is
+    /// created in the clone once it finishes (see `apply_clone_results`).
+    pub(super) fn request_clone(&mut self, data: NewSessionData) {
+        let worktree = resolve_config(&data.profile)
+            .map(|c| c.worktree)
+            .unwrap_or_default();
+        if let Some(dialog) = &mut self.new_dialog {
+            dialog.set_loading(true);
+        }
+        self.show_toast(Toast::info(format!("Cloning {}", data.path)));
+        self.clone_poller
+            .clone(data, worktree.clone_dir, worktree.shallow_clone);
+    }
+
+    /// Show the progress of background clones, and carry on creating the
+    /// session from a finished one unless its dialog was cancelled. Returns
+    /// whether anything changed, and the action for the created session.
+    pub fn apply_clone_results(&mut self) -> (bool, Option<super::app::Action>) {
+        use super::clone_poller::CloneEvent;
+
+        let events = self.clone_poller.try_recv_events();
+        let changed = !events.is_empty();
+        let mut action = None;
+        for event in events {
+            let (data, result) = match event {
+                CloneEvent::Progress(line) => {
+                    self.show_toast(Toast::info(format!("Cloning: {}", line)));
+                    continue;
+                }
+                CloneEvent::Done { data, result } => (data, result),
+            };
+            // A dialog no longer loading was cancelled, or replaced by a new one
+            let waiting = self.new_dialog.as_ref().is_some_and(|d| d.is_loading());
+            match result {
+                Ok(path) => {
+                    let path = path.to_string_lossy().to_string();
+                    self.show_toast(Toast::info(format!("Cloned into {}", path)));
+                    if let Some(dialog) = self.new_dialog.as_mut().filter(|_| waiting) {
+                        dialog.set_loading(false);
+                        dialog.set_path(path.clone());
+                        let mut data = *data;
+                        data.path = path;
+                        action = self.submit_new_session(data);
+                    }
+                }
+                Err(e) => {
+                    if let Some(dialog) = self.new_dialog.as_mut().filter(|_| waiting) {
+                        dialog.set_loading(false);
+                        dialog.set_error(e.clone());
+                    }
+                    self.show_toast(Toast::error(e));
+                }
+            }
+        }
+        (changed, action)
+    }
+
     /// Mark the current creation operation as cancelled (user pressed Esc)
     pub fn cancel_creation(&mut self) {
         if self.creation_poller.is_pending() {
@@ -574,6 +2503,12 @@ impl HomeView {
             }
         }
 
+        if let Some(dialog) = &mut self.merge_queue_dialog {
+            if dialog.tick() {
+                changed = true;
+            }
+        }
+
         changed
     }
 
@@ -584,14 +2519,25 @@ impl HomeView {
             || self.unified_delete_dialog.is_some()
             || self.group_delete_options_dialog.is_some()
             || self.rename_dialog.is_some()
+            || self.bulk_rename_dialog.is_some()
             || self.hook_trust_dialog.is_some()
             || self.hooks_install_dialog.is_some()
+            || self.duplicate_prompt_dialog.is_some()
             || self.welcome_dialog.is_some()
             || self.changelog_dialog.is_some()
             || self.info_dialog.is_some()
             || self.profile_picker_dialog.is_some()
             || self.send_message_dialog.is_some()
-            || self.settings_view.is_some()
+            || self.retry_dialog.is_some()
+            || self.merge_queue_dialog.is_some()
+            || self.command_palette.is_some()
+            || self.command_menu.is_some()
+            || self.checkpoints_dialog.is_some()
+            || self.processes_dialog.is_some()
+            || self.start_failure_dialog.is_some()
+            || self.permission_dialog.is_some()
+            || self.filter_bar.is_some()
+            || (self.tab == Tab::Settings && self.settings_view.is_some())
             || self.diff_view.is_some()
     }
 
@@ -629,10 +2575,23 @@ impl HomeView {
     }
 
     pub(super) fn build_flat_items(&self) -> Vec<Item> {
-        if let Some(profile) = &self.active_profile {
+        let matching: Vec<Instance>;
+        let instances = match &self.filter {
+            Some(filter) => {
+                matching = self
+                    .instances
+                    .iter()
+                    .filter(|i| filter.matches(i))
+                    .cloned()
+                    .collect();
+                &matching
+            }
+            None => &self.instances,
+        };
+
+        let mut items = if let Some(profile) = &self.active_profile {
             // Filtered to a single profile -- only include that profile's instances
-            let filtered: Vec<Instance> = self
-                .instances
+            let filtered: Vec<Instance> = instances
                 .iter()
                 .filter(|i| i.source_profile == *profile)
                 .cloned()
@@ -644,12 +2603,26 @@ impl HomeView {
         } else if self.storages.len() <= 1 {
             // All-profiles mode with only one profile -- skip the profile header
             match self.group_trees.values().next() {
-                Some(tree) => flatten_tree(tree, &self.instances, self.sort_order),
+                Some(tree) => flatten_tree(tree, instances, self.sort_order),
                 None => Vec::new(),
             }
         } else {
-            flatten_tree_all_profiles(&self.instances, &self.group_trees, self.sort_order)
+            flatten_tree_all_profiles(instances, &self.group_trees, self.sort_order)
+        };
+
+        // Groups with no matching sessions are hidden while filtering
+        if self.filter.is_some() {
+            items.retain(|item| {
+                !matches!(
+                    item,
+                    Item::Group {
+                        session_count: 0,
+                        ..
+                    }
+                )
+            });
         }
+        items
     }
 
     pub fn active_profile_display(&self) -> &str {
@@ -659,6 +2632,7 @@ impl HomeView {
     /// Switch the active profile filter in-place without destroying the view.
     /// Pass `None` for all-profiles mode, or `Some(name)` to filter to one profile.
     pub fn switch_profile(&mut self, new_profile: Option<String>) -> anyhow::Result<()> {
+        self.save_ui_state();
         self.active_profile = new_profile;
         // Clear selection before reload so stale session/group refs don't linger
         self.selected_session = None;
@@ -670,6 +2644,7 @@ impl HomeView {
         self.preview_cache = PreviewCache::default();
         self.terminal_preview_cache = PreviewCache::default();
         self.container_terminal_preview_cache = PreviewCache::default();
+        self.apply_preview_interval();
         // Clear search since match indices are invalid with new flat_items
         if self.search_active {
             self.search_active = false;
@@ -797,6 +2772,18 @@ impl HomeView {
             .collect()
     }
 
+    /// Project paths of existing sessions, for the new session dialog's
+    /// duplicate path warning.
+    pub(super) fn existing_session_paths(&self) -> Vec<ExistingSessionPath> {
+        self.instances
+            .iter()
+            .map(|i| ExistingSessionPath {
+                title: i.title.clone(),
+                path: i.project_path.clone(),
+            })
+            .collect()
+    }
+
     /// Check if any profile has groups, without collecting them all.
     pub(super) fn has_any_groups(&self) -> bool {
         self.group_trees
@@ -850,6 +2837,12 @@ impl HomeView {
         self.mutate_instance(id, |inst| inst.last_error = error);
     }
 
+    /// Drop a consumed initial prompt so it is not replayed on restart.
+    pub fn clear_initial_prompt(&mut self, id: &str) -> anyhow::Result<()> {
+        self.mutate_instance(id, |inst| inst.initial_prompt = None);
+        self.save()
+    }
+
     pub fn start_terminal_for_instance_with_size(
         &mut self,
         id: &str,
@@ -880,20 +2873,84 @@ impl HomeView {
             .unwrap_or(self.default_terminal_mode)
     }
 
+    /// Play scripted sessions instead of polling tmux (`aoe --demo`).
+    pub fn start_demo(&mut self) {
+        self.demo = Some(Demo::new(&self.instances));
+        // Leave the real inbox alone
+        self.inbox = Inbox::default();
+    }
+
+    pub fn is_demo(&self) -> bool {
+        self.demo.is_some()
+    }
+
+    /// Move scripted sessions to the status their script is at. Sessions
+    /// being deleted or created during the demo are left alone.
+    fn apply_demo_statuses(&mut self) -> bool {
+        use crate::session::Status;
+
+        let Some(demo) = &self.demo else {
+            return false;
+        };
+        let mut changed = false;
+        for (id, status) in demo.statuses() {
+            let current = self.get_instance(&id).map(|i| i.status);
+            if current.is_some_and(|s| s != status && s != Status::Deleting) {
+                self.mutate_instance(&id, |inst| inst.status = status);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Profile whose config drives the TUI. In unified mode this is "default".
+    pub fn config_profile(&self) -> &str {
+        self.active_profile.as_deref().unwrap_or("default")
+    }
+
+    pub fn show_toast(&mut self, toast: Toast) {
+        self.toast = Some(toast);
+    }
+
+    /// Drop the toast once it has expired. Returns true if a redraw is needed.
+    pub fn tick_toast(&mut self) -> bool {
+        if self.toast.as_ref().is_some_and(|t| t.is_expired()) {
+            self.toast = None;
+            return true;
+        }
+        false
+    }
+
     /// Refresh all config-dependent state from the current profile's config.
     /// Call this after settings are saved to pick up any changes.
     pub fn refresh_from_config(&mut self) {
-        let profile = self.active_profile.as_deref().unwrap_or("default");
-        if let Ok(config) = resolve_config(profile) {
+        if let Ok(config) = resolve_config(self.config_profile()) {
             // Refresh default terminal mode for sandboxed sessions
             self.default_terminal_mode = match config.sandbox.default_terminal_mode {
                 DefaultTerminalMode::Host => TerminalMode::Host,
                 DefaultTerminalMode::Container => TerminalMode::Container,
             };
 
-            // Refresh sound config
+            // Refresh sound and notification config
             self.sound_config = config.sound.clone();
+            self.notification_config = config.notifications.clone();
+            self.polling = config.polling.clone();
+            self.apply_preview_interval();
+            self.provider_limits = providers::limits(&config.session);
+
+            self.row_format = parse_row_format(&config.theme);
+            self.hyperlinks = super::hyperlink::enabled(config.theme.hyperlinks);
+            self.spellcheck = config.theme.spellcheck;
+            (self.preview_min_width, self.row_details_min_width) = config.theme.breakpoints();
+            self.terminal_title = config.theme.terminal_title.clone();
         }
+
+        // Re-resolve health checks, test watchers and credential expiry on
+        // the next tick
+        self.credentials_checked_at = None;
+        self.watch_resolved_at = None;
+        self.health_next_due.clear();
+        self.commands_cache.clear();
     }
 
     /// Toggle terminal mode between Container and Host for a session
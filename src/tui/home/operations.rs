@@ -1,11 +1,20 @@
-//! Session operations for HomeView (create, delete, rename)
+//! Session operations for HomeView (create, delete, rename, merge, send)
 
 use crate::session::builder::{self, InstanceParams};
-use crate::session::{list_profiles, GroupTree, Status, Storage};
+use crate::session::bulk_rename::{self, Rename};
+use crate::session::guardrails::{self, Guardrails};
+use crate::session::notes;
+use crate::session::timeline;
+use crate::session::{list_profiles, ownership, GroupTree, Status, Storage};
+use crate::tmux::permission_prompt::{self, PermissionChoice, PermissionPrompt};
+use crate::tui::components::Toast;
 use crate::tui::deletion_poller::DeletionRequest;
-use crate::tui::dialogs::{DeleteOptions, GroupDeleteOptions, NewSessionData};
+use crate::tui::dialogs::{
+    ConfirmDialog, DeleteOptions, GroupDeleteOptions, InfoDialog, MergeQueueDialog, MergeQueueRow,
+    NewSessionData, PermissionDialog,
+};
 
-use super::HomeView;
+use super::{HomeView, PendingPrompt};
 
 impl HomeView {
     pub(super) fn create_session(&mut self, data: NewSessionData) -> anyhow::Result<String> {
@@ -27,6 +36,7 @@ impl HomeView {
             tool: data.tool,
             worktree_branch: data.worktree_branch,
             create_new_branch: data.create_new_branch,
+            base_ref: data.base_ref,
             sandbox: data.sandbox,
             sandbox_image: data.sandbox_image,
             yolo_mode: data.yolo_mode,
@@ -34,6 +44,8 @@ impl HomeView {
             extra_args: data.extra_args,
             command_override: data.command_override,
             extra_repo_paths: data.extra_repo_paths,
+            issue_info: data.issue_info,
+            initial_prompt: data.initial_prompt,
         };
 
         let build_result = builder::build_instance(params, &existing_titles, &target_profile)?;
@@ -81,6 +93,34 @@ impl HomeView {
         Ok(())
     }
 
+    /// Archive a session whose branch was merged: it moves to the trash (so
+    /// it can be restored from the Archives tab) and its aoe-managed worktree
+    /// is removed. The branch itself is kept.
+    pub(super) fn archive_merged_session(&mut self, id: &str) {
+        let Some(inst) = self.get_instance(id).cloned() else {
+            return;
+        };
+        let delete_worktree = inst
+            .worktree_info
+            .as_ref()
+            .is_some_and(|wt| wt.managed_by_aoe);
+        let delete_sandbox = inst.sandbox_info.as_ref().is_some_and(|s| s.enabled);
+        timeline::record(id, timeline::EventKind::Git, "archived after branch merge");
+        self.set_instance_status(id, Status::Deleting);
+        self.deletion_poller.request_deletion(DeletionRequest {
+            session_id: id.to_string(),
+            instance: inst.clone(),
+            delete_worktree,
+            delete_branch: false,
+            delete_sandbox,
+            force_delete: false,
+        });
+        self.show_toast(Toast::info(format!(
+            "Archiving '{}': branch merged",
+            inst.title
+        )));
+    }
+
     pub(super) fn delete_selected_group(&mut self) -> anyhow::Result<()> {
         if let Some(group_path) = self.selected_group.take() {
             let owning_profile = self.selected_group_profile.take();
@@ -214,14 +254,15 @@ impl HomeView {
         new_title: &str,
         new_group: Option<&str>,
         new_profile: Option<&str>,
+        new_icon: Option<&str>,
     ) -> anyhow::Result<()> {
         if let Some(id) = &self.selected_session {
             let id = id.clone();
 
             // Get current values for comparison
-            let (current_title, current_group) = self
+            let (current_title, current_group, current_icon) = self
                 .get_instance(&id)
-                .map(|i| (i.title.clone(), i.group_path.clone()))
+                .map(|i| (i.title.clone(), i.group_path.clone(), i.icon.clone()))
                 .unwrap_or_default();
 
             // Determine effective title (keep current if empty)
@@ -237,6 +278,12 @@ impl HomeView {
                 Some(g) => g.to_string(),      // Set new (empty string means ungroup)
             };
 
+            // Determine effective icon (empty string removes it)
+            let effective_icon = match new_icon {
+                None => current_icon.clone(),
+                Some(i) => (!i.is_empty()).then(|| i.to_string()),
+            };
+
             // Handle profile change (move session to different profile)
             if let Some(target_profile) = new_profile {
                 let current_profile = self
@@ -265,6 +312,7 @@ impl HomeView {
                     // Apply title and group changes to the instance
                     instance.title = effective_title.clone();
                     instance.group_path = effective_group.clone();
+                    instance.icon = effective_icon.clone();
 
                     // Handle tmux rename if title changed
                     if let Some(orig_inst) = self.get_instance(&id) {
@@ -293,7 +341,11 @@ impl HomeView {
                     self.mutate_instance(&id, |inst| {
                         inst.title = instance.title.clone();
                         inst.group_path = instance.group_path.clone();
+                        inst.icon = instance.icon.clone();
                         inst.source_profile = instance.source_profile.clone();
+                        if inst.orchestrator.is_some() {
+                            inst.orchestrator = Some(instance.source_profile.clone());
+                        }
                     });
 
                     self.rebuild_group_trees();
@@ -309,6 +361,9 @@ impl HomeView {
                             tree.create_group(&effective_group);
                         }
                     }
+                    if instance.tmux_session()?.exists() {
+                        instance.apply_window_name();
+                    }
                     self.save()?;
                     self.reload()?;
                     return Ok(());
@@ -332,8 +387,18 @@ impl HomeView {
             self.mutate_instance(&id, |inst| {
                 inst.title = effective_title.clone();
                 inst.group_path = effective_group.clone();
+                inst.icon = effective_icon.clone();
             });
 
+            // The window is named after the title too, so either change renames it
+            if current_title != effective_title || current_icon != effective_icon {
+                if let Some(inst) = self.get_instance(&id) {
+                    if inst.tmux_session()?.exists() {
+                        inst.apply_window_name();
+                    }
+                }
+            }
+
             // Rebuild group trees and create group if needed
             self.rebuild_group_trees();
             if !effective_group.is_empty() {
@@ -355,4 +420,290 @@ impl HomeView {
         }
         Ok(())
     }
+
+    /// Apply renames from the bulk rename dialog. Refuses if any of the
+    /// sessions belongs to someone else; `aoe rename --steal` takes those over.
+    pub(super) fn bulk_rename(&mut self, renames: &[Rename]) {
+        let foreign = renames.iter().find_map(|r| {
+            let owner = ownership::foreign_owner(self.get_instance(&r.id)?)?;
+            Some(format!(
+                "'{}' is owned by {}. Use `aoe rename --steal` to rename it.",
+                r.from, owner
+            ))
+        });
+        if let Some(message) = foreign {
+            self.info_dialog = Some(InfoDialog::new("Rename Failed", &message));
+            return;
+        }
+
+        if let Err(e) = bulk_rename::apply(&mut self.instances, renames) {
+            self.info_dialog = Some(InfoDialog::new("Rename Failed", &e.to_string()));
+            return;
+        }
+        for rename in renames {
+            if let Some(inst) = self.instances.iter().find(|i| i.id == rename.id) {
+                self.instance_map.insert(rename.id.clone(), inst.clone());
+            }
+        }
+        self.rebuild_group_trees();
+        if let Err(e) = self.save().and_then(|_| self.reload()) {
+            tracing::error!("Failed to save renamed sessions: {}", e);
+        }
+        self.show_toast(Toast::info(format!("Renamed {} session(s)", renames.len())));
+    }
+
+    /// Open the merge queue for the selected session's repository, listing
+    /// every worktree session of that repo. Sessions whose agent is busy are
+    /// listed but left out of the run by default.
+    pub(super) fn open_merge_queue(&mut self) {
+        let Some(inst) = self
+            .selected_session
+            .as_ref()
+            .and_then(|id| self.get_instance(id))
+        else {
+            return;
+        };
+        let Some(wt) = &inst.worktree_info else {
+            self.show_toast(Toast::info(
+                "The merge queue works on worktree sessions; select one to open it",
+            ));
+            return;
+        };
+        let repo_path = std::path::PathBuf::from(&wt.main_repo_path);
+        let profile = inst.source_profile.clone();
+
+        let config = crate::session::repo_config::resolve_config_with_repo(&profile, &repo_path)
+            .unwrap_or_default();
+        let base_branch = config
+            .diff
+            .default_branch
+            .clone()
+            .or_else(|| crate::git::diff::get_default_branch(&repo_path).ok())
+            .unwrap_or_else(|| "main".to_string());
+
+        let rows: Vec<MergeQueueRow> =
+            self.instances
+                .iter()
+                .filter_map(|i| {
+                    let wt = i.worktree_info.as_ref()?;
+                    (wt.main_repo_path == repo_path.to_string_lossy() && wt.branch != base_branch)
+                        .then(|| {
+                            MergeQueueRow::new(
+                                i.id.clone(),
+                                i.title.clone(),
+                                wt.branch.clone(),
+                                std::path::PathBuf::from(&i.project_path),
+                                !matches!(
+                                    i.status,
+                                    Status::Running | Status::Starting | Status::Deleting
+                                ),
+                            )
+                        })
+                })
+                .collect();
+        if rows.is_empty() {
+            self.show_toast(Toast::info(format!(
+                "No worktree sessions to merge into {}",
+                base_branch
+            )));
+            return;
+        }
+
+        self.merge_queue_dialog = Some(MergeQueueDialog::new(
+            repo_path,
+            base_branch,
+            config.worktree.merge_test_command.clone(),
+            rows,
+        ));
+    }
+
+    /// Offer to send the selected session's agent `worktree.conflict_prompt`
+    /// for the conflicts its worktree is stopped on. The worktree is checked
+    /// again first so the prompt lists the files as they are now.
+    pub(super) fn offer_conflict_prompt(&mut self, session_id: &str) {
+        let Some(inst) = self.get_instance(session_id) else {
+            return;
+        };
+        let Some(wt) = &inst.worktree_info else {
+            self.show_toast(Toast::info(
+                "Conflict checks work on worktree sessions; select one to resolve its conflicts",
+            ));
+            return;
+        };
+        let title = inst.title.clone();
+        let branch = wt.branch.clone();
+        let project_path = std::path::PathBuf::from(&inst.project_path);
+        let running = crate::tmux::Session::new(&inst.id, &inst.title).is_ok_and(|s| s.exists());
+        let profile = if inst.source_profile.is_empty() {
+            self.config_profile().to_string()
+        } else {
+            inst.source_profile.clone()
+        };
+
+        let Some(conflict) = crate::git::conflicts::detect(&project_path) else {
+            self.conflicts.remove(session_id);
+            self.show_toast(Toast::info(format!("'{}' has no merge conflicts", title)));
+            return;
+        };
+        if !running {
+            self.show_toast(Toast::info(format!(
+                "Start '{}' to have its agent resolve the conflicts",
+                title
+            )));
+            return;
+        }
+
+        let template =
+            crate::session::repo_config::resolve_config_with_repo(&profile, &project_path)
+                .map(|c| c.worktree.conflict_prompt)
+                .unwrap_or_else(|_| crate::git::conflicts::DEFAULT_PROMPT.to_string());
+        self.confirm_dialog = Some(ConfirmDialog::new(
+            "Resolve Conflicts",
+            &format!(
+                "'{}': {} ({}). Ask the agent to resolve them?",
+                title,
+                conflict.summary(),
+                conflict.files.join(", ")
+            ),
+            "send_conflict_prompt",
+        ));
+        self.pending_conflict_prompt =
+            Some((session_id.to_string(), conflict.prompt(&template, &branch)));
+        self.conflicts.insert(session_id.to_string(), conflict);
+    }
+
+    /// Send a prompt from the send dialog, asking for confirmation first when
+    /// it matches one of the configured prompt guardrails.
+    pub(super) fn submit_prompt(&mut self, session_id: String, message: String) {
+        let Some(inst) = self.get_instance(&session_id) else {
+            return;
+        };
+        let profile = if inst.source_profile.is_empty() {
+            self.config_profile()
+        } else {
+            &inst.source_profile
+        };
+        let matched = Guardrails::for_project(profile, &inst.project_path).check(&message);
+        if matched.is_empty() {
+            self.send_prompt(&session_id, &message);
+            return;
+        }
+
+        self.confirm_dialog = Some(ConfirmDialog::new(
+            "Prompt Guardrail",
+            &format!(
+                "This prompt matches guardrail {}. Send it anyway?",
+                guardrails::describe(&matched)
+            ),
+            "send_guarded_prompt",
+        ));
+        self.pending_guarded_prompt = Some(PendingPrompt {
+            session_id,
+            message,
+            matched,
+        });
+    }
+
+    /// Send a prompt that was confirmed despite matching guardrails.
+    pub(super) fn send_guarded_prompt(&mut self) {
+        if let Some(pending) = self.pending_guarded_prompt.take() {
+            if self.send_prompt(&pending.session_id, &pending.message) {
+                guardrails::record_override(&pending.session_id, &pending.matched);
+            }
+        }
+    }
+
+    /// Type a prompt into a session's agent pane. Returns whether it was sent.
+    fn send_prompt(&mut self, session_id: &str, message: &str) -> bool {
+        let Some(inst) = self.get_instance(session_id) else {
+            return false;
+        };
+        let inst = inst.clone();
+        let result = crate::tmux::Session::new(&inst.id, &inst.title)
+            .map_err(|e| format!("Failed to resolve session: {}", e))
+            .and_then(|s| {
+                s.send_keys(message)
+                    .map_err(|e| format!("Failed to send message: {}", e))
+            });
+        match result {
+            Ok(()) => {
+                timeline::record_prompt(&inst, message);
+                true
+            }
+            Err(e) => {
+                self.info_dialog = Some(InfoDialog::new("Send Failed", &e));
+                false
+            }
+        }
+    }
+
+    /// Show the permission prompt `session_id`'s agent is waiting on, to
+    /// answer it without attaching.
+    pub(super) fn open_permission_dialog(&mut self, session_id: &str) {
+        let Some(inst) = self.get_instance(session_id) else {
+            return;
+        };
+        let Some(prompt) = inst.permission_prompt.clone() else {
+            self.show_toast(Toast::error(
+                "No permission prompt detected for this session",
+            ));
+            return;
+        };
+        self.permission_dialog = Some(PermissionDialog::new(inst, prompt));
+    }
+
+    /// Press the keys that answer `prompt` with `choice`.
+    pub(super) fn answer_permission(
+        &mut self,
+        session_id: &str,
+        prompt: &PermissionPrompt,
+        choice: PermissionChoice,
+    ) {
+        let Some(inst) = self.get_instance(session_id) else {
+            return;
+        };
+        let title = inst.title.clone();
+        let result = inst
+            .tmux_session()
+            .and_then(|s| permission_prompt::answer(&s, prompt, choice));
+        match result {
+            Ok(()) => {
+                timeline::record(
+                    session_id,
+                    timeline::EventKind::Prompt,
+                    format!("permission {}: {}", choice.label(), prompt.question),
+                );
+                self.mutate_instance(session_id, |inst| inst.permission_prompt = None);
+                self.show_toast(Toast::info(format!(
+                    "Answered '{}': {}",
+                    title,
+                    choice.label()
+                )));
+            }
+            Err(e) => self.show_toast(Toast::error(format!("{}: {}", title, e))),
+        }
+    }
+
+    /// Store edited notes on a session and mirror them into its worktree
+    /// when `worktree.sync_notes` is enabled.
+    pub fn set_notes(&mut self, session_id: &str, text: &str) -> anyhow::Result<()> {
+        let notes = notes::normalize(text);
+        self.mutate_instance(session_id, |inst| inst.notes = notes);
+        self.save()?;
+
+        let Some(inst) = self.get_instance(session_id) else {
+            return Ok(());
+        };
+        let profile = if inst.source_profile.is_empty() {
+            self.config_profile()
+        } else {
+            &inst.source_profile
+        };
+        let message = match notes::sync_if_enabled(inst, profile) {
+            Some(path) => format!("Notes saved to {}", path.display()),
+            None => "Notes saved".to_string(),
+        };
+        self.show_toast(Toast::info(message));
+        Ok(())
+    }
 }
@@ -4,16 +4,49 @@ use ratatui::prelude::*;
 use ratatui::widgets::*;
 use std::time::Instant;
 
+use super::tabs::Tab;
 use super::{
-    get_indent, HomeView, TerminalMode, ViewMode, ICON_COLLAPSED, ICON_DELETING, ICON_ERROR,
-    ICON_EXPANDED, ICON_IDLE, ICON_RUNNING, ICON_STARTING, ICON_STOPPED, ICON_UNKNOWN,
-    ICON_WAITING,
+    get_indent, Health, HomeView, TerminalMode, ViewMode, ICON_COLLAPSED, ICON_EXPANDED,
+    ICON_HEALTHY, ICON_UNHEALTHY,
 };
-use crate::session::{Item, Status};
+use crate::session::{timeline, Item};
+use crate::tmux::output_parser;
 use crate::tui::components::{HelpOverlay, Preview};
-use crate::tui::styles::Theme;
+use crate::tui::styles::{status_color, status_icon, Theme, ICON_IDLE, ICON_RUNNING};
 use crate::update::UpdateInfo;
 
+/// Smallest terminal the home screen renders in; below this a notice with
+/// the required size is shown instead.
+pub(super) const MIN_WIDTH: u16 = 40;
+pub(super) const MIN_HEIGHT: u16 = 8;
+
+/// Shown in place of everything else when the terminal is below
+/// [`MIN_WIDTH`] x [`MIN_HEIGHT`]. Lines that do not fit are dropped rather
+/// than wrapped so it stays legible down to a single cell.
+fn render_too_small(frame: &mut Frame, area: Rect, theme: &Theme) {
+    frame.render_widget(Clear, area);
+    let lines = [
+        Line::styled(
+            "Terminal too small",
+            Style::default().fg(theme.error).bold(),
+        ),
+        Line::styled(
+            format!("{} x {}", area.width, area.height),
+            Style::default().fg(theme.text),
+        ),
+        Line::styled(
+            format!("Need {} x {}", MIN_WIDTH, MIN_HEIGHT),
+            Style::default().fg(theme.dimmed),
+        ),
+    ];
+    let shown = (area.height as usize).min(lines.len());
+    let top = area.y + (area.height - shown as u16) / 2;
+    for (i, line) in lines.into_iter().take(shown).enumerate() {
+        let row = Rect::new(area.x, top + i as u16, area.width, 1);
+        frame.render_widget(Paragraph::new(line).alignment(Alignment::Center), row);
+    }
+}
+
 impl HomeView {
     pub fn render(
         &mut self,
@@ -22,8 +55,20 @@ impl HomeView {
         theme: &Theme,
         update_info: Option<&UpdateInfo>,
     ) {
-        // Settings view takes over the whole screen
-        if let Some(ref mut settings) = self.settings_view {
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            render_too_small(frame, area, theme);
+            return;
+        }
+
+        // Every tab gets the tab bar on its first line
+        let [tab_bar, area] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .areas(area);
+        self.render_tab_bar(frame, tab_bar, theme);
+
+        // Settings tab fills the rest of the screen
+        if let (Tab::Settings, Some(settings)) = (self.tab, self.settings_view.as_mut()) {
             settings.render(frame, area, theme);
             // Render unsaved changes confirmation dialog over settings
             if self.settings_close_confirm {
@@ -43,6 +88,17 @@ impl HomeView {
             return;
         }
 
+        // Credential expiry banner across the top of the sessions view
+        let area = match self.credential_banner() {
+            Some((text, expired)) if self.tab == Tab::Sessions && area.height > MIN_HEIGHT => {
+                let [banner, rest] =
+                    Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+                self.render_credential_banner(frame, banner, theme, &text, expired);
+                rest
+            }
+            _ => area,
+        };
+
         // Layout: main area + status bar + optional update bar at bottom
         let constraints = if update_info.is_some() {
             vec![
@@ -59,8 +115,10 @@ impl HomeView {
             .split(area);
 
         // Layout: left panel (list) and right panel (preview)
-        // On small screens, cap list width so the preview pane gets adequate space
+        // On small screens, cap list width so the preview pane gets adequate space,
+        // and on narrow ones drop the preview entirely
         let available_width = main_chunks[0].width;
+        let (show_preview, row_details) = self.layout_for_width(available_width);
         let effective_list_width = self
             .list_width
             .min(available_width.saturating_sub(40))
@@ -73,9 +131,26 @@ impl HomeView {
             ])
             .split(main_chunks[0]);
 
-        self.render_list(frame, chunks[0], theme);
-        self.render_preview(frame, chunks[1], theme);
-        self.render_status_bar(frame, main_chunks[1], theme);
+        if !matches!(self.tab, Tab::Sessions | Tab::Settings) {
+            self.render_tab(frame, main_chunks[0], theme);
+        } else if self.board.is_some() {
+            self.sync_board_selection();
+            self.render_board(frame, main_chunks[0], theme);
+        } else {
+            if show_preview {
+                self.render_list(frame, chunks[0], row_details, theme);
+                self.render_preview(frame, chunks[1], theme);
+            } else {
+                self.render_list(frame, main_chunks[0], row_details, theme);
+            }
+        }
+        if let Some(toast) = &self.toast {
+            toast.render(frame, main_chunks[1], theme);
+        } else if !matches!(self.tab, Tab::Sessions | Tab::Settings) {
+            self.render_tab_status_bar(frame, main_chunks[1], theme);
+        } else {
+            self.render_status_bar(frame, main_chunks[1], theme);
+        }
 
         if let Some(info) = update_info {
             self.render_update_bar(frame, main_chunks[2], theme, info);
@@ -106,6 +181,14 @@ impl HomeView {
             dialog.render(frame, area, theme);
         }
 
+        if let Some(dialog) = &self.bulk_rename_dialog {
+            dialog.render(frame, area, theme);
+        }
+
+        if let Some(dialog) = &self.duplicate_prompt_dialog {
+            dialog.render(frame, area, theme);
+        }
+
         if let Some(dialog) = &self.hooks_install_dialog {
             dialog.render(frame, area, theme);
         }
@@ -133,13 +216,56 @@ impl HomeView {
         if let Some(dialog) = &self.send_message_dialog {
             dialog.render(frame, area, theme);
         }
+
+        if let Some(dialog) = &self.retry_dialog {
+            dialog.render(frame, area, theme);
+        }
+
+        if let Some(dialog) = &self.merge_queue_dialog {
+            dialog.render(frame, area, theme);
+        }
+
+        if let Some(dialog) = &self.command_palette {
+            dialog.render(frame, area, theme);
+        }
+
+        if let Some(dialog) = &self.command_menu {
+            dialog.render(frame, area, theme);
+        }
+
+        if let Some(dialog) = &self.checkpoints_dialog {
+            dialog.render(frame, area, theme);
+        }
+
+        if let Some(dialog) = &self.processes_dialog {
+            dialog.render(frame, area, theme);
+        }
+
+        if let Some(dialog) = &self.start_failure_dialog {
+            dialog.render(frame, area, theme);
+        }
+
+        if let Some(dialog) = &self.permission_dialog {
+            dialog.render(frame, area, theme);
+        }
     }
 
-    fn render_list(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        let title = match self.view_mode {
+    /// Draw the session list. `details` adds branch names and tags to the
+    /// built-in row layout.
+    fn render_list(&mut self, frame: &mut Frame, area: Rect, details: bool, theme: &Theme) {
+        let mut title = match self.view_mode {
             ViewMode::Agent => format!(" Agent of Empires [{}] ", self.active_profile_display()),
             ViewMode::Terminal => format!(" Terminals [{}] ", self.active_profile_display()),
         };
+        if self.filter.is_some() {
+            title.push_str(&format!("filter: {} ", self.filter_query));
+        }
+        if let Some(budget) = self.budget_title() {
+            title.push_str(&format!("{} ", budget));
+        }
+        if let Some(providers) = self.provider_title() {
+            title.push_str(&format!("{} ", providers));
+        }
         let (border_color, title_color) = match self.view_mode {
             ViewMode::Agent => (theme.border, theme.title),
             ViewMode::Terminal => (theme.terminal_border, theme.terminal_border),
@@ -174,7 +300,7 @@ impl HomeView {
                 let is_selected = idx == self.cursor;
                 let is_match =
                     !self.search_matches.is_empty() && self.search_matches.contains(&idx);
-                self.render_item(item, is_selected, is_match, theme)
+                self.render_item(item, is_selected, is_match, details, theme)
             })
             .collect();
 
@@ -183,6 +309,16 @@ impl HomeView {
 
         frame.render_widget(list, inner);
 
+        if let Some(bar) = &self.filter_bar {
+            bar.render(
+                frame,
+                inner,
+                &self.saved_filters,
+                self.filtered_session_count(),
+                theme,
+            );
+        }
+
         // Render search bar if active
         if self.search_active {
             let search_area = Rect {
@@ -235,10 +371,26 @@ impl HomeView {
         item: &Item,
         is_selected: bool,
         is_match: bool,
+        details: bool,
         theme: &Theme,
     ) -> ListItem<'_> {
         let indent = get_indent(item.depth());
 
+        if let (Item::Session { id, .. }, Some(segments), ViewMode::Agent) =
+            (item, &self.row_format, self.view_mode)
+        {
+            if let Some(inst) = self.get_instance(id) {
+                let mut spans = vec![Span::raw(indent)];
+                spans.extend(self.row_format_spans(inst, segments, is_selected, is_match, theme));
+                let line = Line::from(spans);
+                return if is_selected {
+                    ListItem::new(line).style(Style::default().bg(theme.session_selection))
+                } else {
+                    ListItem::new(line)
+                };
+            }
+        }
+
         use std::borrow::Cow;
 
         let (icon, text, style): (&str, Cow<str>, Style) = match item {
@@ -261,28 +413,9 @@ impl HomeView {
                 if let Some(inst) = self.get_instance(id) {
                     match self.view_mode {
                         ViewMode::Agent => {
-                            let icon = match inst.status {
-                                Status::Running => ICON_RUNNING,
-                                Status::Waiting => ICON_WAITING,
-                                Status::Idle => ICON_IDLE,
-                                Status::Unknown => ICON_UNKNOWN,
-                                Status::Stopped => ICON_STOPPED,
-                                Status::Error => ICON_ERROR,
-                                Status::Starting => ICON_STARTING,
-                                Status::Deleting => ICON_DELETING,
-                            };
-                            let color = match inst.status {
-                                Status::Running => theme.running,
-                                Status::Waiting => theme.waiting,
-                                Status::Idle => theme.idle,
-                                Status::Unknown => theme.waiting,
-                                Status::Stopped => theme.dimmed,
-                                Status::Error => theme.error,
-                                Status::Starting => theme.dimmed,
-                                Status::Deleting => theme.waiting,
-                            };
-                            let style = Style::default().fg(color);
-                            (icon, Cow::Borrowed(&inst.title), style)
+                            let icon = status_icon(inst.status);
+                            let style = Style::default().fg(status_color(inst.status, theme));
+                            (icon, inst.display_title(), style)
                         }
                         ViewMode::Terminal => {
                             // For sandboxed sessions, check the appropriate terminal based on mode
@@ -307,7 +440,7 @@ impl HomeView {
                                 (ICON_IDLE, theme.dimmed)
                             };
                             let style = Style::default().fg(color);
-                            (icon, Cow::Borrowed(&inst.title), style)
+                            (icon, inst.display_title(), style)
                         }
                     }
                 } else {
@@ -335,18 +468,70 @@ impl HomeView {
 
         if let Item::Session { id, .. } = item {
             if let Some(inst) = self.get_instance(id) {
-                if let Some(ws_info) = &inst.workspace_info {
+                let branch = if let Some(ws_info) = &inst.workspace_info {
+                    Some(format!(
+                        "  {} [{} repos]",
+                        ws_info.branch,
+                        ws_info.repos.len()
+                    ))
+                } else {
+                    inst.worktree_info
+                        .as_ref()
+                        .map(|wt_info| format!("  {}", wt_info.branch))
+                };
+                if let (Some(branch), true) = (branch, details) {
+                    line_spans.push(Span::styled(branch, Style::default().fg(theme.branch)));
+                }
+                match self.health.get(id) {
+                    Some(Health::Passing) => line_spans.push(Span::styled(
+                        format!(" {}", ICON_HEALTHY),
+                        Style::default().fg(theme.running),
+                    )),
+                    Some(Health::Failing(_)) => line_spans.push(Span::styled(
+                        format!(" {}", ICON_UNHEALTHY),
+                        Style::default().fg(theme.error),
+                    )),
+                    None => {}
+                }
+                if let Some(counts) = self.test_counts.get(id) {
+                    let color = if counts.failed > 0 {
+                        theme.error
+                    } else {
+                        theme.running
+                    };
                     line_spans.push(Span::styled(
-                        format!("  {} [{} repos]", ws_info.branch, ws_info.repos.len()),
-                        Style::default().fg(theme.branch),
+                        format!(" {}", counts),
+                        Style::default().fg(color),
                     ));
-                } else if let Some(wt_info) = &inst.worktree_info {
+                }
+                if self.conflicts.contains_key(id) {
                     line_spans.push(Span::styled(
-                        format!("  {}", wt_info.branch),
-                        Style::default().fg(theme.branch),
+                        " [conflicts]",
+                        Style::default().fg(theme.error),
                     ));
                 }
-                if inst.is_sandboxed() {
+                if inst.auth_required {
+                    line_spans.push(Span::styled(" [auth]", Style::default().fg(theme.error)));
+                }
+                if inst.permission_prompt.is_some() {
+                    line_spans.push(Span::styled(
+                        " [permission]",
+                        Style::default().fg(theme.waiting),
+                    ));
+                }
+                if inst.orchestrator.is_some() {
+                    line_spans.push(Span::styled(
+                        " [orchestrator]",
+                        Style::default().fg(theme.accent),
+                    ));
+                }
+                if details && inst.failed_over_from.is_some() {
+                    line_spans.push(Span::styled(
+                        format!(" [{}]", inst.tool),
+                        Style::default().fg(theme.waiting),
+                    ));
+                }
+                if details && inst.is_sandboxed() {
                     match self.view_mode {
                         ViewMode::Agent => {
                             line_spans.push(Span::styled(
@@ -377,96 +562,141 @@ impl HomeView {
         }
     }
 
-    /// Refresh preview cache if needed (session changed, dimensions changed, or timer expired)
-    fn refresh_preview_cache_if_needed(&mut self, width: u16, height: u16) {
-        const PREVIEW_REFRESH_MS: u128 = 250; // Refresh preview 4x/second max
+    /// Refresh preview cache if needed (session changed, dimensions changed, or
+    /// timer expired). Returns true if the captured pane changed.
+    fn refresh_preview_cache_if_needed(&mut self, width: u16, height: u16) -> bool {
+        // The event view parses scrollback, not just the visible pane
+        const EVENT_HISTORY_LINES: usize = 500;
 
-        let session_changed = match &self.selected_session {
-            Some(id) => self.preview_cache.session_id.as_ref() != Some(id),
-            None => false,
+        let Some(id) = self.selected_session.clone() else {
+            return false;
         };
-        let dims_changed = self.preview_cache.dimensions != (width, height);
-        let timer_expired =
-            self.preview_cache.last_refresh.elapsed().as_millis() > PREVIEW_REFRESH_MS;
-
-        let needs_refresh =
-            self.selected_session.is_some() && (session_changed || dims_changed || timer_expired);
-
-        if needs_refresh {
-            if let Some(id) = &self.selected_session {
-                if let Some(inst) = self.get_instance(id) {
-                    self.preview_cache.content = inst
-                        .capture_output_with_size(height as usize, width, height)
-                        .unwrap_or_default();
-                    self.preview_cache.session_id = Some(id.clone());
-                    self.preview_cache.dimensions = (width, height);
-                    self.preview_cache.last_refresh = Instant::now();
-                }
-            }
+        if !self.preview_cache.is_stale(&id, (width, height)) {
+            return false;
         }
+        let Some(inst) = self.get_instance(&id) else {
+            return false;
+        };
+        let lines = if self.show_events {
+            EVENT_HISTORY_LINES.max(height as usize)
+        } else {
+            let scroll = self.preview_scroll.get(&id).copied().unwrap_or(0);
+            height as usize + scroll as usize
+        };
+        let content = match &self.demo {
+            Some(demo) => demo.pane(&id).unwrap_or_default(),
+            None => inst
+                .capture_output_with_size(lines, width, height)
+                .unwrap_or_default(),
+        };
+        self.preview_cache.store(&id, (width, height), content)
     }
 
-    /// Refresh terminal preview cache if needed (for host terminals)
-    fn refresh_terminal_preview_cache_if_needed(&mut self, width: u16, height: u16) {
-        const PREVIEW_REFRESH_MS: u128 = 250;
+    /// Reload the selected session's timeline if the selection changed or the
+    /// cached copy is stale.
+    fn refresh_timeline_cache_if_needed(&mut self) {
+        const TIMELINE_REFRESH_MS: u128 = 1000;
 
         let needs_refresh = match &self.selected_session {
             Some(id) => {
-                self.terminal_preview_cache.session_id.as_ref() != Some(id)
-                    || self.terminal_preview_cache.dimensions != (width, height)
-                    || self
-                        .terminal_preview_cache
-                        .last_refresh
-                        .elapsed()
-                        .as_millis()
-                        > PREVIEW_REFRESH_MS
+                self.timeline_cache.session_id.as_ref() != Some(id)
+                    || self.timeline_cache.last_refresh.elapsed().as_millis() > TIMELINE_REFRESH_MS
             }
             None => false,
         };
 
         if needs_refresh {
             if let Some(id) = &self.selected_session {
-                if let Some(inst) = self.get_instance(id) {
-                    self.terminal_preview_cache.content = inst
-                        .terminal_tmux_session()
-                        .and_then(|s| s.capture_pane(height as usize))
-                        .unwrap_or_default();
-                    self.terminal_preview_cache.session_id = Some(id.clone());
-                    self.terminal_preview_cache.dimensions = (width, height);
-                    self.terminal_preview_cache.last_refresh = Instant::now();
-                }
+                self.timeline_cache.events = timeline::load(id).unwrap_or_default();
+                self.timeline_cache.session_id = Some(id.clone());
+                self.timeline_cache.last_refresh = Instant::now();
             }
         }
     }
 
-    /// Refresh container terminal preview cache if needed
-    fn refresh_container_terminal_preview_cache_if_needed(&mut self, width: u16, height: u16) {
-        const PREVIEW_REFRESH_MS: u128 = 250;
+    /// Refresh terminal preview cache if needed (for host terminals)
+    fn refresh_terminal_preview_cache_if_needed(&mut self, width: u16, height: u16) -> bool {
+        let Some(id) = self.selected_session.clone() else {
+            return false;
+        };
+        if !self.terminal_preview_cache.is_stale(&id, (width, height)) {
+            return false;
+        }
+        let Some(inst) = self.get_instance(&id) else {
+            return false;
+        };
+        let content = inst
+            .terminal_tmux_session()
+            .and_then(|s| s.capture_pane(height as usize))
+            .unwrap_or_default();
+        self.terminal_preview_cache
+            .store(&id, (width, height), content)
+    }
 
-        let needs_refresh = match &self.selected_session {
-            Some(id) => {
-                self.container_terminal_preview_cache.session_id.as_ref() != Some(id)
-                    || self.container_terminal_preview_cache.dimensions != (width, height)
-                    || self
-                        .container_terminal_preview_cache
-                        .last_refresh
-                        .elapsed()
-                        .as_millis()
-                        > PREVIEW_REFRESH_MS
-            }
-            None => false,
+    /// Refresh container terminal preview cache if needed
+    fn refresh_container_terminal_preview_cache_if_needed(
+        &mut self,
+        width: u16,
+        height: u16,
+    ) -> bool {
+        let Some(id) = self.selected_session.clone() else {
+            return false;
+        };
+        if !self
+            .container_terminal_preview_cache
+            .is_stale(&id, (width, height))
+        {
+            return false;
+        }
+        let Some(inst) = self.get_instance(&id) else {
+            return false;
         };
+        let content = inst
+            .container_terminal_tmux_session()
+            .and_then(|s| s.capture_pane(height as usize))
+            .unwrap_or_default();
+        self.container_terminal_preview_cache
+            .store(&id, (width, height), content)
+    }
 
-        if needs_refresh {
-            if let Some(id) = &self.selected_session {
-                if let Some(inst) = self.get_instance(id) {
-                    self.container_terminal_preview_cache.content = inst
-                        .container_terminal_tmux_session()
-                        .and_then(|s| s.capture_pane(height as usize))
-                        .unwrap_or_default();
-                    self.container_terminal_preview_cache.session_id = Some(id.clone());
-                    self.container_terminal_preview_cache.dimensions = (width, height);
-                    self.container_terminal_preview_cache.last_refresh = Instant::now();
+    /// Re-capture the visible preview once its polling interval has elapsed,
+    /// at the size it was last drawn. Returns true if the pane changed and
+    /// the screen needs redrawing; an unchanged pane costs no redraw.
+    pub fn refresh_preview(&mut self) -> bool {
+        if self.tab != Tab::Sessions || self.board.is_some() || self.diff_view.is_some() {
+            return false;
+        }
+        match self.view_mode {
+            ViewMode::Agent if self.show_timeline => false,
+            // Not drawn yet, so there is no size to capture at
+            ViewMode::Agent if self.preview_cache.dimensions == (0, 0) => false,
+            ViewMode::Agent => {
+                let (width, height) = self.preview_cache.dimensions;
+                self.refresh_preview_cache_if_needed(width, height)
+            }
+            ViewMode::Terminal => {
+                let sandboxed = self
+                    .selected_session
+                    .as_ref()
+                    .and_then(|id| self.get_instance(id))
+                    .is_some_and(|inst| inst.is_sandboxed());
+                let container = sandboxed
+                    && self
+                        .selected_session
+                        .as_ref()
+                        .is_some_and(|id| self.get_terminal_mode(id) == TerminalMode::Container);
+                let cache = if container {
+                    &self.container_terminal_preview_cache
+                } else {
+                    &self.terminal_preview_cache
+                };
+                let (width, height) = cache.dimensions;
+                if (width, height) == (0, 0) {
+                    false
+                } else if container {
+                    self.refresh_container_terminal_preview_cache_if_needed(width, height)
+                } else {
+                    self.refresh_terminal_preview_cache_if_needed(width, height)
                 }
             }
         }
@@ -474,6 +704,8 @@ impl HomeView {
 
     fn render_preview(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let title = match self.view_mode {
+            ViewMode::Agent if self.show_timeline => " Activity ",
+            ViewMode::Agent if self.show_events => " Events ",
             ViewMode::Agent => " Preview ",
             ViewMode::Terminal => " Terminal Preview ",
         };
@@ -490,21 +722,81 @@ impl HomeView {
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
+        let commands_summary = self
+            .selected_session
+            .clone()
+            .and_then(|id| self.commands_summary(&id));
+
         match self.view_mode {
-            ViewMode::Agent => {
-                // Refresh cache before borrowing from instance_map to avoid borrow conflicts
-                self.refresh_preview_cache_if_needed(inner.width, inner.height);
+            ViewMode::Agent if self.show_timeline => {
+                self.refresh_timeline_cache_if_needed();
 
                 if let Some(id) = &self.selected_session {
                     if let Some(inst) = self.get_instance(id) {
-                        Preview::render_with_cache(
+                        Preview::render_with_timeline(
                             frame,
                             inner,
                             inst,
-                            &self.preview_cache.content,
+                            self.lock_summary(id).as_deref(),
+                            commands_summary.as_deref(),
+                            &self.timeline_cache.events,
                             theme,
                         );
                     }
+                } else {
+                    let hint = Paragraph::new("Select a session to view its activity")
+                        .style(Style::default().fg(theme.dimmed))
+                        .alignment(Alignment::Center);
+                    frame.render_widget(hint, inner);
+                }
+            }
+            ViewMode::Agent => {
+                // Refresh cache before borrowing from instance_map to avoid borrow conflicts
+                self.refresh_preview_cache_if_needed(inner.width, inner.height);
+
+                if let Some(id) = &self.selected_session {
+                    if let Some(inst) = self.get_instance(id) {
+                        let events = if self.show_events {
+                            output_parser::parser_for(&inst.tool, &inst.command)
+                                .map(|parse| parse(&self.preview_cache.content))
+                                .unwrap_or_default()
+                        } else {
+                            Vec::new()
+                        };
+                        // Raw output is the fallback when nothing parses
+                        if events.is_empty() {
+                            let scroll = self.preview_scroll.get(id).copied().unwrap_or(0);
+                            let shown = Preview::render_with_cache(
+                                frame,
+                                inner,
+                                inst,
+                                self.lock_summary(id).as_deref(),
+                                commands_summary.as_deref(),
+                                &self.preview_cache.content,
+                                scroll,
+                                theme,
+                            );
+                            // Scrolled past the oldest captured line
+                            if shown < scroll {
+                                let id = id.clone();
+                                if shown == 0 {
+                                    self.preview_scroll.remove(&id);
+                                } else {
+                                    self.preview_scroll.insert(id, shown);
+                                }
+                            }
+                        } else {
+                            Preview::render_with_events(
+                                frame,
+                                inner,
+                                inst,
+                                self.lock_summary(id).as_deref(),
+                                commands_summary.as_deref(),
+                                &events,
+                                theme,
+                            );
+                        }
+                    }
                 } else {
                     let hint = Paragraph::new("Select a session to preview")
                         .style(Style::default().fg(theme.dimmed))
@@ -580,6 +872,15 @@ impl HomeView {
                 }
             }
         }
+
+        if self.hyperlinks {
+            let base = self
+                .selected_session
+                .as_ref()
+                .and_then(|id| self.get_instance(id))
+                .map(|inst| std::path::PathBuf::from(&inst.project_path));
+            super::super::hyperlink::apply(frame.buffer_mut(), inner, base.as_deref());
+        }
     }
 
     fn render_status_bar(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
@@ -587,6 +888,32 @@ impl HomeView {
         let desc_style = Style::default().fg(theme.dimmed);
         let sep_style = Style::default().fg(theme.border);
 
+        if self.board.is_some() {
+            let mut spans = vec![Span::styled(
+                " [Board] ",
+                Style::default().fg(theme.accent).bold(),
+            )];
+            for (key, desc) in [
+                ("h/l", " Lane "),
+                ("j/k", " Card "),
+                ("H/L", " Move "),
+                ("A", " Archive "),
+                ("Enter", " Attach "),
+                ("b", " List "),
+                ("q", " Quit"),
+            ] {
+                spans.extend([
+                    Span::styled("│", sep_style),
+                    Span::styled(format!(" {}", key), key_style),
+                    Span::styled(desc, desc_style),
+                ]);
+            }
+            let status =
+                Paragraph::new(Line::from(spans)).style(Style::default().bg(theme.selection));
+            frame.render_widget(status, area);
+            return;
+        }
+
         let (mode_indicator, mode_color) = match self.view_mode {
             ViewMode::Agent => ("[Agent]", theme.waiting),
             ViewMode::Terminal => ("[Term]", theme.terminal_border),
@@ -677,6 +1004,23 @@ impl HomeView {
         frame.render_widget(status, area);
     }
 
+    fn render_credential_banner(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        text: &str,
+        expired: bool,
+    ) {
+        let color = if expired { theme.error } else { theme.waiting };
+        let bar = Paragraph::new(Line::from(Span::styled(
+            format!(" {}", text),
+            Style::default().fg(color).bold(),
+        )))
+        .style(Style::default().bg(theme.selection));
+        frame.render_widget(bar, area);
+    }
+
     fn render_update_bar(&self, frame: &mut Frame, area: Rect, theme: &Theme, info: &UpdateInfo) {
         let update_style = Style::default().fg(theme.waiting).bold();
         let text = format!(
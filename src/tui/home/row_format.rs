@@ -0,0 +1,313 @@
+//! Custom session row layout
+//!
+//! `theme.row_format` is a template such as `"{status} {name:20} {branch} {age}"`.
+//! Each `{field}` is replaced per session and `{field:N}` pads or truncates
+//! the value to N columns. Other text is copied as is, and unknown
+//! placeholders are left untouched so a typo is visible in the list.
+
+use chrono::Utc;
+use ratatui::prelude::*;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use super::{Health, HomeView, ICON_HEALTHY, ICON_UNHEALTHY};
+use crate::session::Instance;
+use crate::tui::styles::{status_color, status_icon, Theme};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum RowField {
+    Status,
+    Name,
+    Branch,
+    Tool,
+    Age,
+    Idle,
+    Group,
+    Path,
+    Id,
+    Profile,
+    Health,
+    Tests,
+    Conflicts,
+    Auth,
+    Permission,
+    Sandbox,
+}
+
+impl RowField {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "status" => Self::Status,
+            "name" | "title" => Self::Name,
+            "branch" => Self::Branch,
+            "tool" | "agent" => Self::Tool,
+            "age" => Self::Age,
+            "idle" => Self::Idle,
+            "group" => Self::Group,
+            "path" => Self::Path,
+            "id" => Self::Id,
+            "profile" => Self::Profile,
+            "health" => Self::Health,
+            "tests" => Self::Tests,
+            "conflicts" => Self::Conflicts,
+            "auth" => Self::Auth,
+            "permission" => Self::Permission,
+            "sandbox" => Self::Sandbox,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Segment {
+    Text(String),
+    Field {
+        field: RowField,
+        width: Option<usize>,
+    },
+}
+
+/// Split a row template into literal text and placeholders.
+pub(super) fn parse(template: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            text.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+
+        let inner = &after[..end];
+        let (name, width) = match inner.split_once(':') {
+            Some((name, width)) => (name.trim(), width.trim().parse::<usize>().ok()),
+            None => (inner.trim(), None),
+        };
+        match RowField::from_name(name) {
+            Some(field) => {
+                if !text.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut text)));
+                }
+                segments.push(Segment::Field { field, width });
+            }
+            None => text.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    segments
+}
+
+/// Pad `value` with spaces, or truncate it with an ellipsis, to `width`
+/// display columns.
+pub(super) fn fit(value: &str, width: Option<usize>) -> String {
+    let Some(width) = width else {
+        return value.to_string();
+    };
+    let current = value.width();
+    if current <= width {
+        return format!("{}{}", value, " ".repeat(width - current));
+    }
+    if width == 0 {
+        return String::new();
+    }
+
+    let mut out = String::new();
+    let mut used = 0;
+    for c in value.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > width - 1 {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    out.push('…');
+    used += 1;
+    out.push_str(&" ".repeat(width.saturating_sub(used)));
+    out
+}
+
+/// Compact elapsed time: "45s", "12m", "3h", "5d", "2w".
+pub(crate) fn format_age(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m", seconds / 60),
+        3600..=86_399 => format!("{}h", seconds / 3600),
+        86_400..=1_209_599 => format!("{}d", seconds / 86_400),
+        _ => format!("{}w", seconds / 604_800),
+    }
+}
+
+fn shorten_home(path: &str) -> String {
+    dirs::home_dir()
+        .and_then(|home| {
+            path.strip_prefix(home.to_str()?)
+                .map(|rest| format!("~{}", rest))
+        })
+        .unwrap_or_else(|| path.to_string())
+}
+
+impl HomeView {
+    /// Spans for a session row laid out by the configured row template.
+    pub(super) fn row_format_spans(
+        &self,
+        inst: &Instance,
+        segments: &[Segment],
+        is_selected: bool,
+        is_match: bool,
+        theme: &Theme,
+    ) -> Vec<Span<'static>> {
+        let now = Utc::now();
+        let dimmed = Style::default().fg(theme.dimmed);
+        let status_style = Style::default().fg(status_color(inst.status, theme));
+
+        segments
+            .iter()
+            .map(|segment| {
+                let (field, width) = match segment {
+                    Segment::Text(text) => {
+                        return Span::styled(text.clone(), Style::default().fg(theme.text));
+                    }
+                    Segment::Field { field, width } => (*field, *width),
+                };
+                let (value, style) = match field {
+                    RowField::Status => (
+                        status_icon(inst.status).to_string(),
+                        if is_match {
+                            Style::default().fg(theme.search)
+                        } else {
+                            status_style
+                        },
+                    ),
+                    RowField::Name => (
+                        inst.display_title().into_owned(),
+                        if is_selected {
+                            status_style.bold()
+                        } else {
+                            status_style
+                        },
+                    ),
+                    RowField::Branch => (
+                        inst.workspace_info
+                            .as_ref()
+                            .map(|ws| ws.branch.clone())
+                            .or_else(|| inst.worktree_info.as_ref().map(|wt| wt.branch.clone()))
+                            .unwrap_or_default(),
+                        Style::default().fg(theme.branch),
+                    ),
+                    RowField::Tool => match &inst.failed_over_from {
+                        Some(from) => (
+                            format!("{} (from {})", inst.tool, from),
+                            Style::default().fg(theme.waiting),
+                        ),
+                        None => (inst.tool.clone(), dimmed),
+                    },
+                    RowField::Age => (format_age((now - inst.created_at).num_seconds()), dimmed),
+                    RowField::Idle => (
+                        format_age(
+                            (now - inst.last_accessed_at.unwrap_or(inst.created_at)).num_seconds(),
+                        ),
+                        dimmed,
+                    ),
+                    RowField::Group => (inst.group_path.clone(), dimmed),
+                    RowField::Path => (shorten_home(&inst.project_path), dimmed),
+                    RowField::Id => (crate::session::truncate_id(&inst.id, 8).to_string(), dimmed),
+                    RowField::Profile => (inst.source_profile.clone(), dimmed),
+                    RowField::Health => match self.health.get(&inst.id) {
+                        Some(Health::Passing) => {
+                            (ICON_HEALTHY.to_string(), Style::default().fg(theme.running))
+                        }
+                        Some(Health::Failing(_)) => {
+                            (ICON_UNHEALTHY.to_string(), Style::default().fg(theme.error))
+                        }
+                        None => (String::new(), dimmed),
+                    },
+                    RowField::Tests => match self.test_counts.get(&inst.id) {
+                        Some(counts) if counts.failed > 0 => {
+                            (counts.to_string(), Style::default().fg(theme.error))
+                        }
+                        Some(counts) => (counts.to_string(), Style::default().fg(theme.running)),
+                        None => (String::new(), dimmed),
+                    },
+                    RowField::Conflicts => (
+                        if self.conflicts.contains_key(&inst.id) {
+                            "[conflicts]"
+                        } else {
+                            ""
+                        }
+                        .to_string(),
+                        Style::default().fg(theme.error),
+                    ),
+                    RowField::Auth => (
+                        if inst.auth_required { "[auth]" } else { "" }.to_string(),
+                        Style::default().fg(theme.error),
+                    ),
+                    RowField::Permission => (
+                        if inst.permission_prompt.is_some() {
+                            "[permission]"
+                        } else {
+                            ""
+                        }
+                        .to_string(),
+                        Style::default().fg(theme.waiting),
+                    ),
+                    RowField::Sandbox => (
+                        if inst.is_sandboxed() { "[sandbox]" } else { "" }.to_string(),
+                        Style::default().fg(theme.sandbox),
+                    ),
+                };
+                Span::styled(fit(&value, width), style)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fields_widths_and_text() {
+        assert_eq!(
+            parse("{status} {name:20} | {bogus} {branch"),
+            vec![
+                Segment::Field {
+                    field: RowField::Status,
+                    width: None
+                },
+                Segment::Text(" ".to_string()),
+                Segment::Field {
+                    field: RowField::Name,
+                    width: Some(20)
+                },
+                Segment::Text(" | {bogus} {branch".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fit_pads_and_truncates() {
+        assert_eq!(fit("abc", None), "abc");
+        assert_eq!(fit("abc", Some(5)), "abc  ");
+        assert_eq!(fit("abcdef", Some(4)), "abc…");
+        assert_eq!(fit("日本語", Some(4)), "日… ");
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(-5), "0s");
+        assert_eq!(format_age(59), "59s");
+        assert_eq!(format_age(600), "10m");
+        assert_eq!(format_age(7200), "2h");
+        assert_eq!(format_age(3 * 86_400), "3d");
+        assert_eq!(format_age(21 * 86_400), "3w");
+    }
+}
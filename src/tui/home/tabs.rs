@@ -0,0 +1,779 @@
+//! Top-level tabs - sessions, dashboard, archives, logs, settings, and inbox
+//!
+//! The home screen is split into tabs switched with the number keys or
+//! Tab/Shift+Tab. Every tab keeps its own state (cursor, scroll position,
+//! an open settings editor) while another tab is shown.
+
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use serde::{Deserialize, Serialize};
+
+use super::HomeView;
+use crate::session::inbox::{Inbox, Reason};
+use crate::session::timeline::{self, EventKind};
+use crate::session::trash::{self, TrashEntry};
+use crate::session::Status;
+use crate::tui::app::Action;
+use crate::tui::components::{event_kind_color, Toast};
+use crate::tui::dialogs::{ConfirmDialog, InfoDialog};
+use crate::tui::settings::SettingsView;
+use crate::tui::styles::{status_color, status_icon, Theme};
+
+/// Most recent events kept in the logs tab.
+const MAX_LOG_EVENTS: usize = 500;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum Tab {
+    #[default]
+    Sessions,
+    Dashboard,
+    Archives,
+    Logs,
+    Settings,
+    Inbox,
+}
+
+impl Tab {
+    pub(super) const ALL: [Tab; 6] = [
+        Tab::Sessions,
+        Tab::Dashboard,
+        Tab::Archives,
+        Tab::Logs,
+        Tab::Settings,
+        Tab::Inbox,
+    ];
+
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            Tab::Sessions => "Sessions",
+            Tab::Dashboard => "Dashboard",
+            Tab::Archives => "Archives",
+            Tab::Logs => "Logs",
+            Tab::Settings => "Settings",
+            Tab::Inbox => "Inbox",
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|t| *t == self).unwrap_or(0)
+    }
+
+    pub(super) fn next(self) -> Tab {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    pub(super) fn prev(self) -> Tab {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// The tab for a number key, `1` being the first.
+    pub(super) fn from_digit(c: char) -> Option<Tab> {
+        let n = c.to_digit(10)? as usize;
+        n.checked_sub(1).and_then(|i| Self::ALL.get(i)).copied()
+    }
+}
+
+/// A trashed session and the profile it was removed from.
+pub(super) struct ArchiveEntry {
+    pub(super) profile: String,
+    pub(super) entry: TrashEntry,
+}
+
+#[derive(Default)]
+pub(super) struct ArchivesState {
+    pub(super) entries: Vec<ArchiveEntry>,
+    pub(super) selected: usize,
+}
+
+/// One row of the logs tab: a timeline event with its session.
+pub(super) struct LogEntry {
+    pub(super) at: DateTime<Utc>,
+    pub(super) session: String,
+    pub(super) kind: EventKind,
+    pub(super) message: String,
+}
+
+#[derive(Default)]
+pub(super) struct LogsState {
+    pub(super) entries: Vec<LogEntry>,
+    /// Lines scrolled up from the newest event; 0 follows new events.
+    pub(super) scroll: usize,
+}
+
+#[derive(Default)]
+pub(super) struct InboxState {
+    /// Index into the visible rows
+    pub(super) selected: usize,
+    pub(super) show_handled: bool,
+}
+
+impl HomeView {
+    /// Show `tab`, loading its contents. Opening the settings tab creates
+    /// the settings editor if it is not already open.
+    pub(super) fn switch_tab(&mut self, tab: Tab) {
+        match tab {
+            Tab::Settings if self.settings_view.is_none() && !self.open_settings() => return,
+            Tab::Dashboard => self.clamp_dashboard_selection(),
+            Tab::Archives => self.load_archives(),
+            Tab::Logs => self.load_logs(),
+            // Another aoe may have added or handled items meanwhile
+            Tab::Inbox => {
+                if !self.is_demo() {
+                    self.inbox = Inbox::load();
+                }
+                self.clamp_inbox_selection();
+            }
+            _ => {}
+        }
+        self.tab = tab;
+    }
+
+    /// Create the settings editor for the selected session's project.
+    fn open_settings(&mut self) -> bool {
+        let project_path = self
+            .selected_session
+            .as_ref()
+            .and_then(|id| self.get_instance(id))
+            .map(|inst| inst.project_path.clone());
+        match SettingsView::new(
+            self.active_profile.as_deref().unwrap_or("default"),
+            project_path,
+        ) {
+            Ok(view) => {
+                self.settings_view = Some(view);
+                true
+            }
+            Err(e) => {
+                tracing::error!("Failed to open settings: {}", e);
+                self.info_dialog = Some(InfoDialog::new(
+                    "Error",
+                    &format!("Failed to open settings: {}", e),
+                ));
+                false
+            }
+        }
+    }
+
+    /// Keys for the dashboard, archives, logs, and inbox tabs.
+    pub(super) fn handle_tab_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Char('q') => return Some(Action::Quit),
+            KeyCode::Char('?') => self.show_help = true,
+            KeyCode::Tab => self.switch_tab(self.tab.next()),
+            KeyCode::BackTab => self.switch_tab(self.tab.prev()),
+            KeyCode::Esc => self.tab = Tab::Sessions,
+            KeyCode::Char(c) if Tab::from_digit(c).is_some() => {
+                if let Some(tab) = Tab::from_digit(c) {
+                    self.switch_tab(tab);
+                }
+            }
+            _ => match self.tab {
+                Tab::Dashboard => return self.handle_dashboard_key(key),
+                Tab::Archives => self.handle_archives_key(key),
+                Tab::Logs => self.handle_logs_key(key),
+                Tab::Inbox => return self.handle_inbox_key(key),
+                Tab::Sessions | Tab::Settings => {}
+            },
+        }
+        None
+    }
+
+    // Dashboard
+
+    /// Sessions waiting on the user or failed, most recently active first.
+    pub(super) fn attention_sessions(&self) -> Vec<&crate::session::Instance> {
+        let mut sessions: Vec<_> = self
+            .instances()
+            .iter()
+            .filter(|i| matches!(i.status, Status::Waiting | Status::Error))
+            .collect();
+        sessions.sort_by_key(|i| std::cmp::Reverse(i.last_accessed_at.unwrap_or(i.created_at)));
+        sessions
+    }
+
+    fn clamp_dashboard_selection(&mut self) {
+        let count = self.attention_sessions().len();
+        self.dashboard_selected = self.dashboard_selected.min(count.saturating_sub(1));
+    }
+
+    fn handle_dashboard_key(&mut self, key: KeyEvent) -> Option<Action> {
+        self.clamp_dashboard_selection();
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.dashboard_selected = self.dashboard_selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.dashboard_selected += 1;
+                self.clamp_dashboard_selection();
+            }
+            KeyCode::Enter => {
+                return self
+                    .attention_sessions()
+                    .get(self.dashboard_selected)
+                    .map(|i| Action::AttachSession(i.id.clone()));
+            }
+            KeyCode::Char('u') if !self.agent_updates().is_empty() => {
+                return Some(Action::UpgradeAgents);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    // Archives
+
+    fn load_archives(&mut self) {
+        let mut profiles: Vec<&String> = self.storages.keys().collect();
+        profiles.sort();
+        let mut entries = Vec::new();
+        for profile in profiles {
+            match trash::list(profile) {
+                Ok(list) => entries.extend(list.into_iter().map(|entry| ArchiveEntry {
+                    profile: profile.clone(),
+                    entry,
+                })),
+                Err(e) => tracing::warn!("Failed to list trash for {}: {}", profile, e),
+            }
+        }
+        entries.sort_by_key(|e| std::cmp::Reverse(e.entry.deleted_at));
+        self.archives.selected = self.archives.selected.min(entries.len().saturating_sub(1));
+        self.archives.entries = entries;
+    }
+
+    fn handle_archives_key(&mut self, key: KeyEvent) {
+        let count = self.archives.entries.len();
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.archives.selected = self.archives.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.archives.selected = (self.archives.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Char('r') | KeyCode::Enter => self.restore_selected_archive(),
+            KeyCode::Char('x') => {
+                if let Some(archive) = self.archives.entries.get(self.archives.selected) {
+                    self.confirm_dialog = Some(ConfirmDialog::new(
+                        "Purge Session",
+                        &format!(
+                            "Permanently delete '{}'? This cannot be undone.",
+                            archive.entry.instance.title
+                        ),
+                        "purge_archive",
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn restore_selected_archive(&mut self) {
+        let Some(archive) = self.archives.entries.get(self.archives.selected) else {
+            return;
+        };
+        let result = trash::restore(&archive.profile, &archive.entry.instance.id);
+        match result.and_then(|restored| self.reload().map(|_| restored)) {
+            Ok(restored) => {
+                let mut message = format!("Restored '{}'", restored.instance.title);
+                if !restored.notes.is_empty() {
+                    message = format!("{}. {}", message, restored.notes.join(". "));
+                }
+                self.show_toast(Toast::info(message));
+            }
+            Err(e) => self.show_toast(Toast::error(format!("Restore failed: {}", e))),
+        }
+        self.load_archives();
+    }
+
+    pub(super) fn purge_selected_archive(&mut self) {
+        let Some(archive) = self.archives.entries.get(self.archives.selected) else {
+            return;
+        };
+        match trash::purge(&archive.profile, &archive.entry.instance.id) {
+            Ok(inst) => self.show_toast(Toast::info(format!("Purged '{}'", inst.title))),
+            Err(e) => self.show_toast(Toast::error(format!("Purge failed: {}", e))),
+        }
+        self.load_archives();
+    }
+
+    // Logs
+
+    /// Merge every session's timeline into one list, oldest first.
+    fn load_logs(&mut self) {
+        let mut entries = Vec::new();
+        for inst in self.instances() {
+            match timeline::load(&inst.id) {
+                Ok(events) => entries.extend(events.into_iter().map(|event| LogEntry {
+                    at: event.at,
+                    session: inst.title.clone(),
+                    kind: event.kind,
+                    message: event.message,
+                })),
+                Err(e) => tracing::warn!("Failed to load timeline for {}: {}", inst.title, e),
+            }
+        }
+        entries.sort_by_key(|e| e.at);
+        let skip = entries.len().saturating_sub(MAX_LOG_EVENTS);
+        entries.drain(..skip);
+        self.logs.scroll = self.logs.scroll.min(entries.len());
+        self.logs.entries = entries;
+    }
+
+    fn handle_logs_key(&mut self, key: KeyEvent) {
+        let count = self.logs.entries.len();
+        let scroll = &mut self.logs.scroll;
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => *scroll = (*scroll + 1).min(count),
+            KeyCode::Down | KeyCode::Char('j') => *scroll = scroll.saturating_sub(1),
+            KeyCode::PageUp => *scroll = (*scroll + 10).min(count),
+            KeyCode::PageDown => *scroll = scroll.saturating_sub(10),
+            KeyCode::Char('g') => *scroll = count,
+            KeyCode::Char('G') => *scroll = 0,
+            KeyCode::Char('r') => self.load_logs(),
+            _ => {}
+        }
+    }
+
+    // Inbox
+
+    /// Indices into the inbox of the rows shown, oldest first.
+    pub(super) fn inbox_rows(&self) -> Vec<usize> {
+        (0..self.inbox.items.len())
+            .filter(|&i| self.inbox_state.show_handled || self.inbox.items[i].is_pending())
+            .collect()
+    }
+
+    fn clamp_inbox_selection(&mut self) {
+        let count = self.inbox_rows().len();
+        self.inbox_state.selected = self.inbox_state.selected.min(count.saturating_sub(1));
+    }
+
+    fn handle_inbox_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let rows = self.inbox_rows();
+        let selected = rows.get(self.inbox_state.selected).copied();
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.inbox_state.selected = self.inbox_state.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.inbox_state.selected += 1;
+            }
+            KeyCode::Enter => {
+                let index = selected?;
+                let id = self.inbox.items[index].session_id.clone();
+                if self.inbox.items[index].is_pending() {
+                    self.inbox.toggle_handled(index);
+                    self.inbox.save();
+                }
+                self.clamp_inbox_selection();
+                if self.get_instance(&id).is_none() {
+                    self.show_toast(Toast::error("That session no longer exists"));
+                    return None;
+                }
+                return Some(Action::AttachSession(id));
+            }
+            KeyCode::Char('x') | KeyCode::Char(' ') => {
+                let index = selected?;
+                self.inbox.toggle_handled(index);
+                self.inbox.save();
+            }
+            KeyCode::Char('X') => {
+                self.inbox.mark_all_handled();
+                self.inbox.save();
+            }
+            KeyCode::Char('h') => {
+                self.inbox_state.show_handled = !self.inbox_state.show_handled;
+            }
+            _ => {}
+        }
+        self.clamp_inbox_selection();
+        None
+    }
+
+    // Rendering
+
+    pub(super) fn render_tab_bar(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let mut spans = Vec::new();
+        for (idx, tab) in Tab::ALL.iter().enumerate() {
+            let style = if *tab == self.tab {
+                Style::default().fg(theme.accent).bg(theme.selection).bold()
+            } else {
+                Style::default().fg(theme.dimmed)
+            };
+            if idx > 0 {
+                spans.push(Span::styled("│", Style::default().fg(theme.border)));
+            }
+            let pending = self.inbox.pending_count();
+            let label = if *tab == Tab::Inbox && pending > 0 {
+                format!(" {} {} ({}) ", idx + 1, tab.label(), pending)
+            } else {
+                format!(" {} {} ", idx + 1, tab.label())
+            };
+            spans.push(Span::styled(label, style));
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    /// Body of the dashboard, archives, logs, or inbox tab.
+    pub(super) fn render_tab(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!(
+                " {} [{}] ",
+                self.tab.label(),
+                self.active_profile_display()
+            ))
+            .title_style(Style::default().fg(theme.title).bold());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        match self.tab {
+            Tab::Dashboard => self.render_dashboard(frame, inner, theme),
+            Tab::Archives => self.render_archives(frame, inner, theme),
+            Tab::Logs => self.render_logs(frame, inner, theme),
+            Tab::Inbox => self.render_inbox(frame, inner, theme),
+            Tab::Sessions | Tab::Settings => {}
+        }
+    }
+
+    fn render_dashboard(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let mut lines = vec![Line::from("")];
+
+        let mut counts = Vec::new();
+        for status in [
+            Status::Running,
+            Status::Waiting,
+            Status::Idle,
+            Status::Starting,
+            Status::Error,
+            Status::Stopped,
+        ] {
+            let n = self
+                .instances()
+                .iter()
+                .filter(|i| i.status == status)
+                .count();
+            if n > 0 {
+                counts.push(Span::styled(
+                    format!(" {} {} {}  ", status_icon(status), n, status_label(status)),
+                    Style::default().fg(status_color(status, theme)),
+                ));
+            }
+        }
+        if counts.is_empty() {
+            counts.push(Span::styled(
+                " No sessions",
+                Style::default().fg(theme.dimmed),
+            ));
+        }
+        lines.push(Line::from(counts));
+        lines.push(Line::from(""));
+
+        let updates = self.agent_updates();
+        if !updates.is_empty() {
+            let text: Vec<String> = updates.iter().map(|u| u.describe()).collect();
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!(" {}", text.join(", ")),
+                    Style::default().fg(theme.dimmed),
+                ),
+                Span::styled("  u", Style::default().fg(theme.hint)),
+                Span::styled(" upgrade", Style::default().fg(theme.dimmed)),
+            ]));
+            lines.push(Line::from(""));
+        }
+
+        let attention = self.attention_sessions();
+        lines.push(Line::from(Span::styled(
+            format!(" Needs attention ({})", attention.len()),
+            Style::default().fg(theme.title).bold(),
+        )));
+        if attention.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "   Nothing is waiting on you",
+                Style::default().fg(theme.dimmed),
+            )));
+        }
+        for (idx, inst) in attention.iter().enumerate() {
+            let title_style = if idx == self.dashboard_selected {
+                Style::default()
+                    .fg(theme.text)
+                    .bg(theme.session_selection)
+                    .bold()
+            } else {
+                Style::default().fg(theme.text)
+            };
+            let detail = match inst.status {
+                Status::Error => inst.last_error.clone().unwrap_or_default(),
+                _ => inst
+                    .last_accessed_at
+                    .map(|at| format!("last attached {}", local_time(at)))
+                    .unwrap_or_default(),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("   {} ", status_icon(inst.status)),
+                    Style::default().fg(status_color(inst.status, theme)),
+                ),
+                Span::styled(inst.title.clone(), title_style),
+                Span::styled(format!("  {}", detail), Style::default().fg(theme.dimmed)),
+            ]));
+        }
+
+        // Keep the selected row visible
+        let header = if updates.is_empty() { 5 } else { 7 };
+        let skip = (header + self.dashboard_selected + 1).saturating_sub(area.height as usize);
+        frame.render_widget(Paragraph::new(lines).scroll((skip as u16, 0)), area);
+    }
+
+    fn render_archives(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if self.archives.entries.is_empty() {
+            let hint = Paragraph::new(vec![
+                Line::from(""),
+                Line::from("No removed sessions").style(Style::default().fg(theme.dimmed)),
+            ])
+            .alignment(Alignment::Center);
+            frame.render_widget(hint, area);
+            return;
+        }
+
+        let per_page = (area.height as usize).max(1);
+        let skip = (self.archives.selected + 1).saturating_sub(per_page);
+        let lines: Vec<Line> = self
+            .archives
+            .entries
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(per_page)
+            .map(|(idx, archive)| {
+                let inst = &archive.entry.instance;
+                let title_style = if idx == self.archives.selected {
+                    Style::default()
+                        .fg(theme.text)
+                        .bg(theme.session_selection)
+                        .bold()
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {:>11} ", local_time(archive.entry.deleted_at)),
+                        Style::default().fg(theme.dimmed),
+                    ),
+                    Span::styled(inst.title.clone(), title_style),
+                    Span::styled(
+                        format!("  {} · {}", inst.tool, inst.project_path),
+                        Style::default().fg(theme.dimmed),
+                    ),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn render_logs(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if self.logs.entries.is_empty() {
+            let hint = Paragraph::new(vec![
+                Line::from(""),
+                Line::from("No activity recorded yet").style(Style::default().fg(theme.dimmed)),
+            ])
+            .alignment(Alignment::Center);
+            frame.render_widget(hint, area);
+            return;
+        }
+
+        let session_width = self
+            .logs
+            .entries
+            .iter()
+            .map(|e| e.session.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(20);
+        let lines: Vec<Line> = self
+            .logs
+            .entries
+            .iter()
+            .map(|event| {
+                let color = event_kind_color(event.kind, theme);
+                let session: String = event.session.chars().take(session_width).collect();
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {:>11} ", local_time(event.at)),
+                        Style::default().fg(theme.dimmed),
+                    ),
+                    Span::styled(
+                        format!("{:<width$} ", session, width = session_width),
+                        Style::default().fg(theme.title),
+                    ),
+                    Span::styled(
+                        format!("{:<10}", event.kind.label()),
+                        Style::default().fg(color),
+                    ),
+                    Span::styled(event.message.clone(), Style::default().fg(theme.text)),
+                ])
+            })
+            .collect();
+
+        // Newest events at the bottom, scrolled up by `scroll` lines
+        let height = area.height as usize;
+        let bottom = lines.len().saturating_sub(height);
+        let offset = bottom.saturating_sub(self.logs.scroll);
+        frame.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), area);
+    }
+
+    fn render_inbox(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let rows = self.inbox_rows();
+        if rows.is_empty() {
+            let message = if self.inbox.items.is_empty() || self.inbox_state.show_handled {
+                "Nothing has needed you yet"
+            } else {
+                "All caught up (h shows handled items)"
+            };
+            let hint = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(message).style(Style::default().fg(theme.dimmed)),
+            ])
+            .alignment(Alignment::Center);
+            frame.render_widget(hint, area);
+            return;
+        }
+
+        let per_page = (area.height as usize).max(1);
+        let skip = (self.inbox_state.selected + 1).saturating_sub(per_page);
+        let lines: Vec<Line> = rows
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(per_page)
+            .map(|(row, &index)| {
+                let item = &self.inbox.items[index];
+                let pending = item.is_pending();
+                let title_style = if row == self.inbox_state.selected {
+                    Style::default()
+                        .fg(theme.text)
+                        .bg(theme.session_selection)
+                        .bold()
+                } else if pending {
+                    Style::default().fg(theme.text)
+                } else {
+                    Style::default().fg(theme.dimmed)
+                };
+                let reason_color = if !pending {
+                    theme.dimmed
+                } else {
+                    match item.reason {
+                        Reason::Waiting => theme.waiting,
+                        Reason::Error => theme.error,
+                        Reason::Finished => theme.idle,
+                    }
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!(
+                            " {} {:>11} ",
+                            if pending { " " } else { "✓" },
+                            local_time(item.at)
+                        ),
+                        Style::default().fg(theme.dimmed),
+                    ),
+                    Span::styled(
+                        format!("{:<9}", item.reason.label()),
+                        Style::default().fg(reason_color),
+                    ),
+                    Span::styled(item.title.clone(), title_style),
+                    Span::styled(
+                        format!("  {}", item.message),
+                        Style::default().fg(theme.dimmed),
+                    ),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    pub(super) fn render_tab_status_bar(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let key_style = Style::default().fg(theme.accent).bold();
+        let desc_style = Style::default().fg(theme.dimmed);
+        let sep_style = Style::default().fg(theme.border);
+
+        let hints: &[(&str, &str)] = match self.tab {
+            Tab::Dashboard => &[("j/k", " Nav "), ("Enter", " Attach ")],
+            Tab::Archives => &[("j/k", " Nav "), ("r", " Restore "), ("x", " Purge ")],
+            Tab::Logs => &[
+                ("j/k", " Scroll "),
+                ("g/G", " Top/End "),
+                ("r", " Refresh "),
+            ],
+            Tab::Inbox => &[
+                ("j/k", " Nav "),
+                ("Enter", " Attach "),
+                ("x", " Handled "),
+                ("X", " All handled "),
+                ("h", " Show handled "),
+            ],
+            Tab::Sessions | Tab::Settings => &[],
+        };
+        let mut spans = vec![Span::styled(
+            format!(" [{}] ", self.tab.label()),
+            Style::default().fg(theme.accent).bold(),
+        )];
+        for (key, desc) in hints
+            .iter()
+            .chain(&[("1-6", " Tabs "), ("?", " Help "), ("q", " Quit")])
+        {
+            spans.extend([
+                Span::styled("│", sep_style),
+                Span::styled(format!(" {}", key), key_style),
+                Span::styled(*desc, desc_style),
+            ]);
+        }
+        let status = Paragraph::new(Line::from(spans)).style(Style::default().bg(theme.selection));
+        frame.render_widget(status, area);
+    }
+}
+
+fn status_label(status: Status) -> &'static str {
+    match status {
+        Status::Running => "running",
+        Status::Waiting => "waiting",
+        Status::Idle => "idle",
+        Status::Unknown => "unknown",
+        Status::Stopped => "stopped",
+        Status::Error => "error",
+        Status::Starting => "starting",
+        Status::Deleting => "deleting",
+    }
+}
+
+/// Short local timestamp: time of day for today, date and time otherwise.
+fn local_time(at: DateTime<Utc>) -> String {
+    let at = at.with_timezone(&Local);
+    if at.date_naive() == Local::now().date_naive() {
+        at.format("%H:%M:%S").to_string()
+    } else {
+        at.format("%m-%d %H:%M").to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tab_cycling_and_digits() {
+        assert_eq!(Tab::Sessions.next(), Tab::Dashboard);
+        assert_eq!(Tab::Settings.next(), Tab::Inbox);
+        assert_eq!(Tab::Inbox.next(), Tab::Sessions);
+        assert_eq!(Tab::Sessions.prev(), Tab::Inbox);
+        assert_eq!(Tab::from_digit('1'), Some(Tab::Sessions));
+        assert_eq!(Tab::from_digit('4'), Some(Tab::Logs));
+        assert_eq!(Tab::from_digit('0'), None);
+        assert_eq!(Tab::from_digit('6'), Some(Tab::Inbox));
+        assert_eq!(Tab::from_digit('7'), None);
+    }
+}
@@ -5,11 +5,14 @@ use serial_test::serial;
 use tempfile::TempDir;
 use tui_input::Input;
 
-use super::{HomeView, ViewMode};
+use super::render::{MIN_HEIGHT, MIN_WIDTH};
+use super::tabs::Tab;
+use super::{HomeView, LayoutMode, PreviewCache, ViewMode};
 use crate::session::{Instance, Item, Storage};
 use crate::tmux::AvailableTools;
 use crate::tui::app::Action;
 use crate::tui::dialogs::{InfoDialog, NewSessionDialog};
+use crate::update::agents::AgentVersion;
 
 fn key(code: KeyCode) -> KeyEvent {
     KeyEvent::new(code, KeyModifiers::NONE)
@@ -679,6 +682,57 @@ fn test_rename_dialog_not_opened_on_group() {
     assert!(env.view.rename_dialog.is_none());
 }
 
+#[test]
+#[serial]
+fn test_bulk_rename_renames_matching_sessions() {
+    let mut env = create_test_env_with_sessions(3);
+    env.view.handle_key(key(KeyCode::Char('B')));
+    assert!(env.view.bulk_rename_dialog.is_some());
+    for c in "session*".chars() {
+        env.view.handle_key(key(KeyCode::Char(c)));
+    }
+    env.view.handle_key(key(KeyCode::Tab));
+    for c in "task-*".chars() {
+        env.view.handle_key(key(KeyCode::Char(c)));
+    }
+    env.view.handle_key(key(KeyCode::Enter));
+    assert!(env.view.bulk_rename_dialog.is_none());
+
+    let mut titles: Vec<String> = Storage::new("test")
+        .unwrap()
+        .load()
+        .unwrap()
+        .into_iter()
+        .map(|i| i.title)
+        .collect();
+    titles.sort();
+    assert_eq!(titles, ["task-0", "task-1", "task-2"]);
+}
+
+#[test]
+#[serial]
+fn test_preview_scroll_is_kept_per_session() {
+    let mut env = create_test_env_with_sessions(2);
+    env.view.update_selected();
+    let first = env.view.selected_session.clone().unwrap();
+    let shift = |code| KeyEvent::new(code, KeyModifiers::SHIFT);
+
+    env.view.handle_key(shift(KeyCode::Up));
+    env.view.handle_key(shift(KeyCode::Up));
+    assert_eq!(env.view.preview_scroll.get(&first), Some(&6));
+
+    env.view.handle_key(key(KeyCode::Down));
+    let second = env.view.selected_session.clone().unwrap();
+    assert!(!env.view.preview_scroll.contains_key(&second));
+
+    env.view.handle_key(key(KeyCode::Up));
+    assert_eq!(env.view.preview_scroll.get(&first), Some(&6));
+    for _ in 0..2 {
+        env.view.handle_key(shift(KeyCode::Down));
+    }
+    assert!(env.view.preview_scroll.is_empty());
+}
+
 #[test]
 #[serial]
 fn test_has_dialog_returns_true_for_rename_dialog() {
@@ -777,8 +831,8 @@ fn test_uppercase_p_picker_switch_profile() {
     view.handle_key(key(KeyCode::Down));
     view.handle_key(key(KeyCode::Down));
     let action = view.handle_key(key(KeyCode::Enter));
-    // Profile switch is handled internally, no Action returned
-    assert_eq!(action, None);
+    // The app re-reads the config of the profile switched to
+    assert_eq!(action, Some(Action::ConfigChanged));
     assert_eq!(view.active_profile, Some("second".to_string()));
     assert!(view.profile_picker_dialog.is_none());
 }
@@ -798,6 +852,24 @@ fn test_t_toggles_view_mode() {
     assert_eq!(view.view_mode, ViewMode::Agent);
 }
 
+#[test]
+#[serial]
+fn test_a_toggles_timeline_in_agent_view() {
+    let env = create_test_env_empty();
+    let mut view = env.view;
+
+    assert!(!view.show_timeline);
+    view.handle_key(key(KeyCode::Char('a')));
+    assert!(view.show_timeline);
+    view.handle_key(key(KeyCode::Char('a')));
+    assert!(!view.show_timeline);
+
+    // Terminal view has no timeline
+    view.handle_key(key(KeyCode::Char('t')));
+    view.handle_key(key(KeyCode::Char('a')));
+    assert!(!view.show_timeline);
+}
+
 #[test]
 #[serial]
 fn test_enter_returns_attach_terminal_in_terminal_view() {
@@ -857,7 +929,12 @@ fn test_has_dialog_includes_settings_view() {
     assert!(!view.has_dialog());
 
     view.settings_view = Some(SettingsView::new("test", None).unwrap());
+    view.tab = Tab::Settings;
     assert!(view.has_dialog());
+
+    // Settings left open on another tab does not capture keys
+    view.tab = Tab::Sessions;
+    assert!(!view.has_dialog());
 }
 
 #[test]
@@ -867,6 +944,126 @@ fn test_s_opens_settings_view() {
     assert!(env.view.settings_view.is_none());
     env.view.handle_key(key(KeyCode::Char('s')));
     assert!(env.view.settings_view.is_some());
+    assert_eq!(env.view.tab, Tab::Settings);
+}
+
+#[test]
+#[serial]
+fn test_settings_save_reports_config_changed() {
+    let mut env = create_test_env_empty();
+    env.view.handle_key(key(KeyCode::Char('s')));
+    assert_eq!(
+        env.view
+            .handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)),
+        Some(Action::ConfigChanged)
+    );
+}
+
+#[test]
+#[serial]
+fn test_tabs_keep_their_state() {
+    let mut env = create_test_env_with_sessions(3);
+    env.view.handle_key(key(KeyCode::Char('j')));
+    env.view.handle_key(key(KeyCode::Char('j')));
+    assert_eq!(env.view.cursor, 2);
+
+    env.view.handle_key(key(KeyCode::Char('2')));
+    assert_eq!(env.view.tab, Tab::Dashboard);
+    // List keys do not reach the hidden sessions list
+    env.view.handle_key(key(KeyCode::Char('k')));
+    env.view.handle_key(key(KeyCode::Tab));
+    assert_eq!(env.view.tab, Tab::Archives);
+    env.view.handle_key(key(KeyCode::BackTab));
+    env.view.handle_key(key(KeyCode::Char('1')));
+    assert_eq!(env.view.tab, Tab::Sessions);
+    assert_eq!(env.view.cursor, 2);
+}
+
+#[test]
+#[serial]
+fn test_dashboard_u_upgrades_outdated_agents() {
+    let mut env = create_test_env_with_sessions(1);
+    env.view.handle_key(key(KeyCode::Char('2')));
+    assert_eq!(env.view.handle_key(key(KeyCode::Char('u'))), None);
+
+    assert!(env.view.set_agent_updates(vec![AgentVersion {
+        agent: "claude".to_string(),
+        installed: "1.2".to_string(),
+        latest: "1.4".to_string(),
+    }]));
+    assert_eq!(
+        env.view.handle_key(key(KeyCode::Char('u'))),
+        Some(Action::UpgradeAgents)
+    );
+}
+
+#[test]
+#[serial]
+fn test_settings_tab_stays_open_in_background() {
+    let mut env = create_test_env_empty();
+    env.view.handle_key(key(KeyCode::Char('5')));
+    assert_eq!(env.view.tab, Tab::Settings);
+    assert!(env.view.settings_view.is_some());
+
+    env.view.handle_key(key(KeyCode::Char('1')));
+    assert_eq!(env.view.tab, Tab::Sessions);
+    assert!(env.view.settings_view.is_some());
+    assert!(!env.view.has_dialog());
+
+    // Closing settings returns to the sessions tab
+    env.view.handle_key(key(KeyCode::Char('5')));
+    env.view.handle_key(key(KeyCode::Esc));
+    assert!(env.view.settings_view.is_none());
+    assert_eq!(env.view.tab, Tab::Sessions);
+}
+
+#[test]
+#[serial]
+fn test_archives_tab_restores_session() {
+    let mut env = create_test_env_with_sessions(2);
+    let removed = env.view.instances()[0].clone();
+    crate::session::trash::move_to_trash("test", &removed).unwrap();
+    let storage = Storage::new("test").unwrap();
+    storage.save(&env.view.instances()[1..]).unwrap();
+    env.view.reload().unwrap();
+    assert_eq!(env.view.instances().len(), 1);
+
+    env.view.handle_key(key(KeyCode::Char('3')));
+    assert_eq!(env.view.archives.entries.len(), 1);
+    env.view.handle_key(key(KeyCode::Char('r')));
+    assert!(env.view.archives.entries.is_empty());
+    assert_eq!(env.view.instances().len(), 2);
+}
+
+#[test]
+#[serial]
+fn test_inbox_tab_attaches_and_marks_handled() {
+    use crate::session::inbox::Reason;
+
+    let mut env = create_test_env_with_sessions(2);
+    let first = env.view.instances()[0].id.clone();
+    let second = env.view.instances()[1].id.clone();
+    env.view.add_to_inbox(&second, Reason::Error, "boom");
+    env.view
+        .add_to_inbox(&first, Reason::Waiting, "waiting for input");
+
+    env.view.handle_key(key(KeyCode::Char('6')));
+    assert_eq!(env.view.tab, Tab::Inbox);
+    assert_eq!(env.view.inbox.pending_count(), 2);
+
+    // Oldest first; handled items drop out of the list
+    let action = env.view.handle_key(key(KeyCode::Enter));
+    assert!(matches!(action, Some(Action::AttachSession(id)) if id == second));
+    assert_eq!(env.view.inbox_rows().len(), 1);
+    env.view.handle_key(key(KeyCode::Char('x')));
+    assert!(env.view.inbox_rows().is_empty());
+
+    env.view.handle_key(key(KeyCode::Char('h')));
+    assert_eq!(env.view.inbox_rows().len(), 2);
+    // Handled state is saved with the inbox
+    env.view.handle_key(key(KeyCode::Char('1')));
+    env.view.handle_key(key(KeyCode::Char('6')));
+    assert_eq!(env.view.inbox.pending_count(), 0);
 }
 
 // Group deletion tests
@@ -932,6 +1129,7 @@ fn test_group_has_managed_worktrees() {
         main_repo_path: "/tmp/main".to_string(),
         managed_by_aoe: true,
         created_at: Utc::now(),
+        base_commit: None,
     });
 
     let mut inst2 = Instance::new("other-session", "/tmp/other");
@@ -1105,6 +1303,7 @@ fn test_delete_group_with_sessions_respects_worktree_option() {
         main_repo_path: "/tmp/main".to_string(),
         managed_by_aoe: true,
         created_at: Utc::now(),
+        base_commit: None,
     });
 
     storage.save(&[inst1]).unwrap();
@@ -1491,6 +1690,73 @@ fn test_ctrl_o_key_cycles_sort_order_backward() {
     assert_eq!(env.view.sort_order, SortOrder::Newest);
 }
 
+#[test]
+#[serial]
+fn test_ui_state_restored_per_profile() {
+    use crate::session::config::SortOrder;
+
+    let mut env = create_test_env_with_mixed_sessions();
+    env.view.handle_key(key(KeyCode::Char('o')));
+    env.view.handle_key(key(KeyCode::Down));
+    env.view.handle_key(key(KeyCode::Down));
+    env.view.handle_key(key(KeyCode::Char('t')));
+    env.view.switch_tab(Tab::Logs);
+    let selected = env.view.selected_session.clone();
+    assert!(selected.is_some());
+    env.view.save_ui_state();
+
+    let tools = AvailableTools::with_tools(&["claude"]);
+    let view = HomeView::new(Some("test".to_string()), tools).unwrap();
+    assert_eq!(view.sort_order, SortOrder::Oldest);
+    assert_eq!(view.selected_session, selected);
+    assert_eq!(view.view_mode, ViewMode::Terminal);
+    assert_eq!(view.tab, Tab::Logs);
+
+    // Other profiles keep their own state
+    let _storage = Storage::new("other").unwrap();
+    let tools = AvailableTools::with_tools(&["claude"]);
+    let view = HomeView::new(Some("other".to_string()), tools).unwrap();
+    assert_eq!(view.sort_order, SortOrder::Newest);
+    assert_eq!(view.tab, Tab::Sessions);
+}
+
+#[test]
+#[serial]
+fn test_filter_bar_hides_non_matching_sessions() {
+    let mut env = create_test_env_with_mixed_sessions();
+    env.view.handle_key(key(KeyCode::Char('f')));
+    for c in "group:work -title:mango".chars() {
+        env.view.handle_key(key(KeyCode::Char(c)));
+    }
+    env.view.handle_key(key(KeyCode::Enter));
+
+    let titles: Vec<String> = env
+        .view
+        .flat_items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Session { id, .. } => env.view.get_instance(id).map(|i| i.title.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(titles.len(), 2);
+    assert!(!titles.contains(&"Mango".to_string()));
+    assert!(!titles.contains(&"Uncategorized".to_string()));
+
+    // The filter survives a restart; Esc clears it
+    env.view.save_ui_state();
+    let tools = AvailableTools::with_tools(&["claude"]);
+    let mut view = HomeView::new(Some("test".to_string()), tools).unwrap();
+    assert_eq!(view.filter_query, "group:work -title:mango");
+    view.handle_key(key(KeyCode::Esc));
+    assert!(view.filter.is_none());
+    assert_eq!(view.flat_items.len(), 5);
+
+    // A filter with no matches hides the empty group too
+    view.set_filter("status:error");
+    assert!(view.flat_items.is_empty());
+}
+
 #[test]
 #[serial]
 fn test_o_key_flat_items_sorted_az() {
@@ -1746,6 +2012,7 @@ fn test_create_session_in_all_mode_is_findable() {
         tool: "claude".to_string(),
         worktree_branch: None,
         create_new_branch: false,
+        base_ref: None,
         extra_repo_paths: Vec::new(),
         sandbox: false,
         sandbox_image: String::new(),
@@ -1753,6 +2020,8 @@ fn test_create_session_in_all_mode_is_findable() {
         extra_env: Vec::new(),
         extra_args: String::new(),
         command_override: String::new(),
+        issue_info: None,
+        initial_prompt: None,
     };
 
     let session_id = view.create_session(data).unwrap();
@@ -2017,6 +2286,7 @@ fn test_shift_n_prefills_main_repo_path_for_worktree_session() {
         main_repo_path: "/tmp/repo".to_string(),
         managed_by_aoe: true,
         created_at: chrono::Utc::now(),
+        base_commit: None,
     });
     storage.save(&[inst]).unwrap();
 
@@ -2087,3 +2357,581 @@ fn effective_list_width_clamps_on_small_screens() {
     // User-resized list to 50 on a 70-col screen: capped to 30, but min 10
     assert_eq!(clamp(50, 70), 30);
 }
+
+#[test]
+#[serial]
+fn test_layout_follows_breakpoints_until_forced() {
+    let mut env = create_test_env_empty();
+    assert_eq!(env.view.layout, LayoutMode::Auto);
+    assert_eq!(env.view.layout_for_width(120), (true, true));
+    assert_eq!(env.view.layout_for_width(70), (true, false));
+    assert_eq!(env.view.layout_for_width(50), (false, false));
+
+    env.view.handle_key(key(KeyCode::Char('z')));
+    assert_eq!(env.view.layout, LayoutMode::Full);
+    assert_eq!(env.view.layout_for_width(50), (true, true));
+
+    env.view.handle_key(key(KeyCode::Char('z')));
+    assert_eq!(env.view.layout, LayoutMode::Compact);
+    assert_eq!(env.view.layout_for_width(120), (false, false));
+
+    env.view.handle_key(key(KeyCode::Char('z')));
+    assert_eq!(env.view.layout, LayoutMode::Auto);
+}
+
+#[test]
+#[serial]
+fn test_board_archive_and_move_cards() {
+    use crate::session::BoardLane;
+
+    let mut env = create_test_env_with_sessions(2);
+    env.view.handle_key(key(KeyCode::Char('b')));
+    let state = env.view.board.expect("board should open");
+    let id = env.view.selected_session.clone().unwrap();
+    assert_eq!(state.lane, 3, "idle sessions start in Done");
+
+    env.view.handle_key(key(KeyCode::Char('A')));
+    assert_eq!(env.view.board.unwrap().lane, 4);
+    assert_eq!(env.view.selected_session.as_deref(), Some(id.as_str()));
+    let saved = Storage::new("test").unwrap().load().unwrap();
+    let inst = saved.iter().find(|i| i.id == id).unwrap();
+    assert_eq!(inst.board_lane, Some(BoardLane::Archived));
+
+    // Unarchiving returns the card to its status lane and clears the pin
+    env.view.handle_key(key(KeyCode::Char('A')));
+    assert_eq!(env.view.get_instance(&id).unwrap().board_lane, None);
+
+    env.view.handle_key(key(KeyCode::Char('H')));
+    assert_eq!(env.view.board.unwrap().lane, 2);
+    assert_eq!(
+        env.view.get_instance(&id).unwrap().lane(),
+        BoardLane::Waiting
+    );
+
+    env.view.handle_key(key(KeyCode::Char('b')));
+    assert!(env.view.board.is_none());
+}
+
+#[test]
+#[serial]
+fn test_board_navigation_and_attach() {
+    let mut env = create_test_env_with_sessions(2);
+    env.view.handle_key(key(KeyCode::Char('b')));
+    let first = env.view.selected_session.clone().unwrap();
+
+    env.view.handle_key(key(KeyCode::Char('j')));
+    let second = env.view.selected_session.clone().unwrap();
+    assert_ne!(first, second);
+    // Moving past the end of a lane stays on the last card
+    env.view.handle_key(key(KeyCode::Char('j')));
+    assert_eq!(env.view.selected_session.as_deref(), Some(second.as_str()));
+
+    let action = env.view.handle_key(key(KeyCode::Enter));
+    assert!(matches!(action, Some(Action::AttachSession(id)) if id == second));
+
+    // An empty lane has nothing to attach to
+    env.view.handle_key(key(KeyCode::Char('l')));
+    assert!(env.view.handle_key(key(KeyCode::Enter)).is_none());
+}
+
+#[test]
+#[serial]
+fn test_lock_queue_waits_for_holder() {
+    use crate::session::Status;
+
+    let mut env = create_test_env_with_sessions(2);
+    let holder = env.view.instances[0].id.clone();
+    let waiter = env.view.instances[1].id.clone();
+    for id in [&holder, &waiter] {
+        env.view
+            .mutate_instance(id, |i| i.locks = vec!["db".to_string()]);
+    }
+    env.view
+        .mutate_instance(&holder, |i| i.status = Status::Running);
+
+    let (lock, holder_title) = env.view.lock_conflict(&waiter).unwrap();
+    assert_eq!(lock, "db");
+    env.view.queue_for_lock(&waiter, &lock, &holder_title);
+    env.view.queue_for_lock(&waiter, &lock, &holder_title);
+    assert_eq!(env.view.lock_queue, vec![waiter.clone()]);
+    assert_eq!(
+        env.view.lock_summary(&waiter).unwrap(),
+        format!("db (held by {}), queued", holder_title)
+    );
+
+    // Still held: the session stays queued
+    env.view.start_unblocked_sessions();
+    assert_eq!(env.view.lock_queue, vec![waiter.clone()]);
+    assert_eq!(env.view.lock_summary(&holder).unwrap(), "db (held)");
+
+    // Removed sessions are dropped from the queue
+    env.view.instances.retain(|i| i.id != waiter);
+    env.view.instance_map.remove(&waiter);
+    env.view.start_unblocked_sessions();
+    assert!(env.view.lock_queue.is_empty());
+}
+
+#[test]
+#[serial]
+fn test_provider_limit_queues_and_counts() {
+    use crate::agents::Provider;
+    use crate::session::Status;
+
+    let mut env = create_test_env_with_sessions(2);
+    let running = env.view.instances[0].id.clone();
+    let waiter = env.view.instances[1].id.clone();
+    env.view
+        .mutate_instance(&running, |i| i.status = Status::Running);
+    env.view.provider_limits = std::collections::HashMap::from([(Provider::Anthropic, 1)]);
+    assert_eq!(env.view.provider_title().as_deref(), Some("anthropic 1/1"));
+
+    let reached = env.view.provider_limit(&waiter).unwrap();
+    assert_eq!(reached.provider, Provider::Anthropic);
+    env.view.queue_for_provider(&waiter, reached);
+    assert_eq!(env.view.lock_queue, vec![waiter.clone()]);
+
+    // Still at the cap: the session stays queued
+    env.view.start_unblocked_sessions();
+    assert_eq!(env.view.lock_queue, vec![waiter.clone()]);
+
+    // Another provider has its own cap
+    env.view
+        .mutate_instance(&waiter, |i| i.tool = "codex".to_string());
+    assert!(env.view.provider_limit(&waiter).is_none());
+}
+
+#[test]
+#[serial]
+fn test_used_up_budget_holds_queue_and_creation() {
+    use crate::session::budget::BudgetState;
+    use crate::session::{load_config, save_config, BudgetAction};
+
+    let mut env = create_test_env_with_sessions(2);
+    let mut config = load_config().unwrap().unwrap_or_default();
+    config.session.daily_budget = 5;
+    config.session.budget_action = BudgetAction::Block;
+    save_config(&config).unwrap();
+
+    let spender = env.view.instances[0].id.clone();
+    let queued = env.view.instances[1].id.clone();
+    env.view.lock_queue.push(queued.clone());
+
+    env.view.live_costs.insert(spender.clone(), 3.0);
+    assert!(env.view.check_budgets());
+    assert!(matches!(env.view.budgets["test"].state, BudgetState::Ok));
+    // Checked at most once a minute
+    assert!(!env.view.check_budgets());
+
+    env.view.live_costs.insert(spender, 6.0);
+    env.view.budget_checked_at = None;
+    assert!(env.view.check_budgets());
+    assert!(matches!(
+        env.view.budgets["test"].state,
+        BudgetState::Exceeded(_)
+    ));
+    assert!(env.view.toast.is_some());
+    assert_eq!(
+        env.view.budget_title().unwrap(),
+        "daily budget: $6.00 of $5"
+    );
+
+    env.view.start_unblocked_sessions();
+    assert_eq!(env.view.lock_queue, vec![queued]);
+    assert!(env
+        .view
+        .budget_block_reason("test")
+        .unwrap()
+        .contains("new sessions are blocked"));
+}
+
+#[test]
+#[serial]
+fn test_credential_nearing_expiry_warns_and_refreshes_once() {
+    use crate::session::{load_config, save_config};
+
+    let mut env = create_test_env_with_sessions(1);
+    let marker = env._temp.path().join("refreshed");
+    let expires = chrono::Utc::now() + chrono::Duration::hours(50);
+    let mut config = load_config().unwrap().unwrap_or_default();
+    config
+        .session
+        .credential_expiry
+        .insert("claude".to_string(), expires.to_rfc3339());
+    config
+        .session
+        .credential_expiry
+        .insert("codex".to_string(), "2999-01-01".to_string());
+    config.session.credential_refresh_command =
+        Some(format!("echo \"$AOE_AGENT\" >> '{}'", marker.display()));
+    save_config(&config).unwrap();
+
+    assert!(env.view.check_credentials());
+    assert_eq!(env.view.credential_warnings.len(), 1);
+    assert!(env.view.toast.is_some());
+    let (text, expired) = env.view.credential_banner().unwrap();
+    assert!(text.starts_with("claude credentials expire in 2d"));
+    assert!(!expired);
+
+    // The refresh command runs in the background, once per expiry
+    env.view.credentials_checked_at = None;
+    assert!(!env.view.check_credentials());
+    for _ in 0..50 {
+        if marker.exists() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    assert_eq!(std::fs::read_to_string(&marker).unwrap(), "claude\n");
+}
+
+#[test]
+#[serial]
+fn test_merged_session_is_offered_for_archive() {
+    use crate::session::WorktreeInfo;
+
+    let mut env = create_test_env_with_sessions(2);
+    let id = env.view.instances()[0].id.clone();
+    env.view.mutate_instance(&id, |inst| {
+        inst.worktree_info = Some(WorktreeInfo {
+            branch: "feature".to_string(),
+            main_repo_path: "/tmp/repo".to_string(),
+            managed_by_aoe: true,
+            created_at: chrono::Utc::now(),
+            base_commit: None,
+        });
+    });
+    env.view
+        .merged_branches
+        .insert(id.clone(), "PR #7 merged".to_string());
+    env.view.merged_queue.push(id.clone());
+
+    assert!(env.view.apply_merge_results());
+    assert!(env.view.confirm_dialog.is_some());
+    assert_eq!(
+        env.view.pending_merged_cleanup.as_deref(),
+        Some(id.as_str())
+    );
+
+    // Declining keeps the session and doesn't ask again
+    env.view.handle_key(key(KeyCode::Char('n')));
+    assert!(env.view.confirm_dialog.is_none());
+    assert!(env.view.pending_merged_cleanup.is_none());
+    assert!(!env.view.apply_merge_results());
+    assert_ne!(
+        env.view.get_instance(&id).unwrap().status,
+        crate::session::Status::Deleting
+    );
+
+    env.view.merged_queue.push(id.clone());
+    env.view.apply_merge_results();
+    env.view.handle_key(key(KeyCode::Char('y')));
+    assert_eq!(
+        env.view.get_instance(&id).unwrap().status,
+        crate::session::Status::Deleting
+    );
+}
+
+#[test]
+#[serial]
+fn test_conflicted_worktree_is_flagged() {
+    use crate::session::WorktreeInfo;
+    use std::process::Command;
+
+    let git = |dir: &std::path::Path, args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status
+            .success()
+    };
+    let repo = TempDir::new().unwrap();
+    let path = repo.path();
+    assert!(git(path, &["init", "-q", "-b", "main"]));
+    std::fs::write(path.join("a.txt"), "base\n").unwrap();
+    assert!(git(path, &["add", "a.txt"]));
+    assert!(git(path, &["commit", "-q", "-m", "base"]));
+    assert!(git(path, &["checkout", "-q", "-b", "feature"]));
+    std::fs::write(path.join("a.txt"), "feature\n").unwrap();
+    assert!(git(path, &["commit", "-q", "-am", "feature"]));
+    assert!(git(path, &["checkout", "-q", "main"]));
+    std::fs::write(path.join("a.txt"), "main\n").unwrap();
+    assert!(git(path, &["commit", "-q", "-am", "main"]));
+    assert!(!git(path, &["merge", "-q", "feature"]));
+
+    let mut env = create_test_env_with_sessions(1);
+    let id = env.view.instances()[0].id.clone();
+    env.view.mutate_instance(&id, |inst| {
+        inst.project_path = path.to_string_lossy().into_owned();
+        inst.worktree_info = Some(WorktreeInfo {
+            branch: "main".to_string(),
+            main_repo_path: path.to_string_lossy().into_owned(),
+            managed_by_aoe: true,
+            created_at: chrono::Utc::now(),
+            base_commit: None,
+        });
+    });
+
+    env.view.request_conflict_checks();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while !env.view.apply_conflict_results() && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    let conflict = env.view.conflicts.get(&id).expect("conflict flagged");
+    assert_eq!(conflict.operation, "merge");
+    assert_eq!(conflict.files, ["a.txt"]);
+
+    // Once resolved, the resolve action clears the flag instead of asking
+    assert!(git(path, &["merge", "--abort"]));
+    env.view.handle_key(key(KeyCode::Char('F')));
+    assert!(env.view.confirm_dialog.is_none());
+    assert!(!env.view.conflicts.contains_key(&id));
+}
+
+#[test]
+#[serial]
+fn test_permission_prompt_opens_answer_dialog() {
+    use crate::tmux::permission_prompt::{PermissionChoice, PermissionPrompt};
+
+    let mut env = create_test_env_with_sessions(1);
+    let id = env.view.instances()[0].id.clone();
+    env.view.handle_key(key(KeyCode::Char('y')));
+    assert!(env.view.permission_dialog.is_none());
+    assert!(env.view.toast.is_some());
+
+    env.view.mutate_instance(&id, |inst| {
+        inst.permission_prompt = Some(PermissionPrompt {
+            question: "Do you want to proceed?".to_string(),
+            options: vec![
+                (PermissionChoice::Allow, "1".to_string()),
+                (PermissionChoice::Deny, "2".to_string()),
+            ],
+            enter: false,
+            context: vec!["⏺ Bash(cargo test)".to_string()],
+        });
+    });
+    env.view.handle_key(key(KeyCode::Char('y')));
+    assert!(env.view.permission_dialog.is_some());
+    env.view.handle_key(key(KeyCode::Esc));
+    assert!(env.view.permission_dialog.is_none());
+}
+
+#[test]
+#[serial]
+fn test_list_shows_saved_statuses_until_hydrated() {
+    let mut env = create_test_env_with_sessions(2);
+    assert!(!env.view.is_hydrated());
+    assert!(env.view.archives.entries.is_empty());
+    assert!(env.view.logs.entries.is_empty());
+
+    env.view.request_status_refresh();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while !env.view.is_hydrated() && std::time::Instant::now() < deadline {
+        env.view.apply_status_updates();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    assert!(env.view.is_hydrated());
+}
+
+#[test]
+fn test_preview_cache_keeps_unchanged_content_and_backs_off() {
+    let mut cache = PreviewCache::default();
+    assert!(cache.is_stale("a", (80, 24)));
+    assert!(cache.store("a", (80, 24), "line 1\nline 2".to_string()));
+    let first_interval = cache.interval;
+
+    assert!(!cache.store("a", (80, 24), "line 1\nline 2".to_string()));
+    assert!(cache.interval > first_interval);
+    assert!(!cache.is_stale("a", (80, 24)));
+    assert!(cache.is_stale("a", (100, 24)));
+    assert!(cache.is_stale("b", (80, 24)));
+
+    // New output resets to fast polling
+    assert!(cache.store("a", (80, 24), "line 1\nline 2\nline 3".to_string()));
+    assert_eq!(cache.interval, first_interval);
+    assert!(cache.content.ends_with("line 3"));
+}
+
+#[test]
+#[serial]
+fn test_retry_dialog_opens_with_original_prompt() {
+    let mut env = create_test_env_with_sessions(1);
+    let id = env.view.instances[0].id.clone();
+    env.view.mutate_instance(&id, |i| {
+        i.original_prompt = Some("Fix the login bug".to_string())
+    });
+    env.view.selected_session = Some(id.clone());
+
+    env.view.handle_key(key(KeyCode::Char('R')));
+    let dialog = env.view.retry_dialog.as_ref().expect("retry dialog");
+    assert_eq!(dialog.session_id(), id);
+    assert!(env.view.has_dialog());
+
+    env.view.handle_key(key(KeyCode::Esc));
+    assert!(env.view.retry_dialog.is_none());
+}
+
+#[test]
+#[serial]
+fn test_observe_key_attaches_read_only() {
+    let mut env = create_test_env_with_sessions(1);
+    let id = env.view.instances[0].id.clone();
+    env.view.selected_session = Some(id.clone());
+
+    let action = env.view.handle_key(key(KeyCode::Char('O')));
+    assert_eq!(action, Some(Action::ObserveSession(id)));
+}
+
+#[test]
+#[serial]
+fn test_checkpoints_dialog_records_checkpoint() {
+    let mut env = create_test_env_with_sessions(1);
+    let id = env.view.instances[0].id.clone();
+    env.view.selected_session = Some(id.clone());
+
+    env.view.handle_key(key(KeyCode::Char('C')));
+    assert!(env.view.checkpoints_dialog.is_some());
+    env.view.handle_key(key(KeyCode::Char('n')));
+    for c in "plan".chars() {
+        env.view.handle_key(key(KeyCode::Char(c)));
+    }
+    env.view.handle_key(key(KeyCode::Enter));
+
+    let inst = env.view.get_instance(&id).unwrap();
+    assert_eq!(inst.checkpoints.len(), 1);
+    assert_eq!(inst.checkpoints[0].name, "plan");
+    assert!(env.view.checkpoints_dialog.is_some());
+
+    env.view.handle_key(key(KeyCode::Esc));
+    assert!(!env.view.has_dialog());
+}
+
+fn render_to_lines(view: &mut HomeView, width: u16, height: u16) -> Vec<String> {
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    let theme = crate::tui::styles::Theme::default();
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal
+        .draw(|f| view.render(f, f.area(), &theme, None))
+        .unwrap();
+    let buffer = terminal.backend().buffer();
+    (0..height)
+        .map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect())
+        .collect()
+}
+
+#[test]
+#[serial]
+fn test_tiny_terminal_shows_required_size() {
+    let mut env = create_test_env_with_sessions(3);
+    for (width, height) in [(1, 1), (12, 2), (30, 20), (100, 5)] {
+        let screen = render_to_lines(&mut env.view, width, height).join("\n");
+        assert!(!screen.contains("session0"), "{}x{}", width, height);
+    }
+    let screen = render_to_lines(&mut env.view, 30, 6).join("\n");
+    assert!(screen.contains("Terminal too small"));
+    assert!(screen.contains("30 x 6"));
+    assert!(screen.contains(&format!("Need {} x {}", MIN_WIDTH, MIN_HEIGHT)));
+}
+
+#[test]
+#[serial]
+fn test_narrow_terminal_drops_preview() {
+    let mut env = create_test_env_with_sessions(3);
+    let screen = render_to_lines(&mut env.view, 50, 12).join("\n");
+    assert!(screen.contains("session0"));
+    assert!(!screen.contains("Preview"));
+
+    let screen = render_to_lines(&mut env.view, 80, 12).join("\n");
+    assert!(screen.contains("session0"));
+    assert!(screen.contains("Preview"));
+}
+
+#[test]
+#[serial]
+fn test_reload_keeps_auth_required_so_it_notifies_once() {
+    use crate::session::inbox::Reason;
+    use crate::session::Status;
+    use crate::tui::status_poller::StatusUpdate;
+
+    let mut env = create_test_env_with_sessions(1);
+    let id = env.view.instances[0].id.clone();
+    env.view
+        .mutate_instance(&id, |i| i.status = Status::Waiting);
+    let update = || StatusUpdate {
+        id: id.clone(),
+        status: Status::Waiting,
+        last_error: None,
+        auth_required: true,
+        quota_exceeded: false,
+        permission_prompt: None,
+        final_summary: None,
+        cost: None,
+    };
+    let pending_errors = |view: &HomeView| {
+        view.inbox
+            .items
+            .iter()
+            .filter(|i| i.is_pending() && i.reason == Reason::Error)
+            .count()
+    };
+
+    env.view.apply_updates(vec![update()]);
+    assert_eq!(pending_errors(&env.view), 1);
+    env.view.inbox.mark_all_handled();
+
+    // The periodic reload from disk must not forget the session was already
+    // flagged, or the next update notifies all over again
+    env.view.reload().unwrap();
+    assert!(env.view.get_instance(&id).unwrap().auth_required);
+    env.view.apply_updates(vec![update()]);
+    assert_eq!(pending_errors(&env.view), 0);
+}
+
+#[test]
+#[serial]
+fn test_process_signal_only_reaches_the_sessions_tree() {
+    use crate::tui::dialogs::{ProcessAction, ProcessesDialog};
+    use nix::sys::signal::Signal;
+
+    let mut env = create_test_env_empty();
+    let mut session = std::process::Command::new("sleep")
+        .arg("30")
+        .spawn()
+        .unwrap();
+    let mut unrelated = std::process::Command::new("sleep")
+        .arg("30")
+        .spawn()
+        .unwrap();
+    let root = session.id();
+    env.view.processes_dialog = Some(ProcessesDialog::new(
+        "test",
+        root,
+        crate::process::process_tree(root),
+    ));
+
+    // A pid that left the tree, e.g. reused by another process, is refused
+    env.view.apply_process_action(ProcessAction::Signal {
+        pid: unrelated.id(),
+        signal: Signal::SIGTERM,
+    });
+    assert!(env
+        .view
+        .toast
+        .as_ref()
+        .is_some_and(|t| t.message.contains("no longer part of this session")));
+    assert!(unrelated.try_wait().unwrap().is_none());
+
+    env.view.apply_process_action(ProcessAction::Signal {
+        pid: root,
+        signal: Signal::SIGTERM,
+    });
+    assert!(session.wait().unwrap().code().is_none());
+
+    unrelated.kill().unwrap();
+    unrelated.wait().unwrap();
+}
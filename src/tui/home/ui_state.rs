@@ -0,0 +1,148 @@
+//! UI state remembered between runs
+//!
+//! The selection, sort order, view (agent/terminal, board, preview mode,
+//! layout, tab), and session filters are saved per profile to `ui_state.json` in the app directory when
+//! the TUI exits or switches profile, and restored when it opens that
+//! profile again. Collapsed groups are not stored here; they live with the
+//! profile's groups.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::tabs::Tab;
+use super::{HomeView, LayoutMode, ViewMode};
+use crate::session::config::SortOrder;
+use crate::session::filter::SavedFilter;
+use crate::session::{get_app_dir, Item};
+
+/// Key used for the all-profiles view.
+const ALL_PROFILES_KEY: &str = "*";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct UiState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) selected_session: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) selected_group: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) selected_group_profile: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) sort_order: Option<SortOrder>,
+    #[serde(default)]
+    pub(super) view_mode: ViewMode,
+    #[serde(default)]
+    pub(super) tab: Tab,
+    #[serde(default)]
+    pub(super) board: bool,
+    #[serde(default)]
+    pub(super) show_timeline: bool,
+    #[serde(default)]
+    pub(super) show_events: bool,
+    #[serde(default)]
+    pub(super) layout: LayoutMode,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(super) filter: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) saved_filters: Vec<SavedFilter>,
+}
+
+fn state_path() -> Result<PathBuf> {
+    Ok(get_app_dir()?.join("ui_state.json"))
+}
+
+fn load_all() -> Result<HashMap<String, UiState>> {
+    let path = state_path()?;
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Saved state for `profile` (None for the all-profiles view).
+pub(super) fn load(profile: Option<&str>) -> Option<UiState> {
+    match load_all() {
+        Ok(mut all) => all.remove(profile.unwrap_or(ALL_PROFILES_KEY)),
+        Err(e) => {
+            tracing::warn!("Failed to load UI state: {}", e);
+            None
+        }
+    }
+}
+
+fn save(profile: Option<&str>, state: UiState) -> Result<()> {
+    // A corrupt file is replaced rather than blocking the save
+    let mut all = load_all().unwrap_or_default();
+    all.insert(profile.unwrap_or(ALL_PROFILES_KEY).to_string(), state);
+    fs::write(state_path()?, serde_json::to_string_pretty(&all)?)?;
+    Ok(())
+}
+
+impl HomeView {
+    pub(super) fn capture_ui_state(&self) -> UiState {
+        UiState {
+            selected_session: self.selected_session.clone(),
+            selected_group: self.selected_group.clone(),
+            selected_group_profile: self.selected_group_profile.clone(),
+            sort_order: Some(self.sort_order),
+            view_mode: self.view_mode,
+            // Settings opens for the selected session's repo, so it isn't
+            // worth reopening
+            tab: match self.tab {
+                Tab::Settings => Tab::Sessions,
+                tab => tab,
+            },
+            board: self.board.is_some(),
+            show_timeline: self.show_timeline,
+            show_events: self.show_events,
+            layout: self.layout,
+            filter: self.filter_query.clone(),
+            saved_filters: self.saved_filters.clone(),
+        }
+    }
+
+    pub(super) fn restore_ui_state(&mut self, state: UiState) {
+        if let Some(order) = state.sort_order {
+            self.sort_order = order;
+            self.flat_items = self.build_flat_items();
+        }
+        self.view_mode = state.view_mode;
+        self.show_timeline = state.show_timeline;
+        self.show_events = state.show_events;
+        self.layout = state.layout;
+        self.saved_filters = state.saved_filters;
+        if !state.filter.is_empty() {
+            self.set_filter(&state.filter);
+        }
+
+        if let Some(id) = &state.selected_session {
+            self.select_session_by_id(id);
+        } else if let Some(path) = &state.selected_group {
+            let position = (0..self.flat_items.len()).find(|&idx| {
+                matches!(&self.flat_items[idx], Item::Group { path: p, .. } if p == path)
+                    && self.profile_for_cursor(idx) == state.selected_group_profile
+            });
+            if let Some(idx) = position {
+                self.cursor = idx;
+                self.update_selected();
+            }
+        }
+
+        if state.board {
+            self.open_board();
+        }
+        if state.tab != Tab::Sessions {
+            self.switch_tab(state.tab);
+        }
+    }
+
+    /// Remember the current UI state for the active profile.
+    pub fn save_ui_state(&self) {
+        if let Err(e) = save(self.active_profile.as_deref(), self.capture_ui_state()) {
+            tracing::warn!("Failed to save UI state: {}", e);
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! OSC 8 hyperlinks for paths and URLs in the preview
+//!
+//! Ratatui has no notion of hyperlinks, so links are added after a frame is
+//! drawn by rewriting cells: each pair of characters in a link becomes one
+//! cell whose symbol wraps both in the OSC 8 escape, and ratatui skips the
+//! cell after it when writing to the terminal. A link of odd length leaves
+//! its last character unlinked rather than swallowing the cell that follows.
+
+use std::path::{Path, PathBuf};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+
+use crate::session::HyperlinkMode;
+
+/// Characters trimmed from the ends of a candidate link
+const OPENERS: &[char] = &['(', '[', '<', '{', '"', '\'', '`'];
+const CLOSERS: &[char] = &[
+    '.', ',', ';', ':', '!', '?', ')', ']', '>', '}', '"', '\'', '`',
+];
+
+/// A link found in a row of text, as a range of cells
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub start: usize,
+    pub end: usize,
+    pub target: String,
+}
+
+/// Whether hyperlinks should be emitted for `mode` in this terminal.
+pub fn enabled(mode: HyperlinkMode) -> bool {
+    match mode {
+        HyperlinkMode::Enabled => true,
+        HyperlinkMode::Disabled => false,
+        HyperlinkMode::Auto => terminal_supports_hyperlinks(),
+    }
+}
+
+/// Terminals known to handle OSC 8. tmux drops the sequences unless it is
+/// configured to pass them through, so auto is off inside it.
+fn terminal_supports_hyperlinks() -> bool {
+    let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+    if var("TMUX").is_some() {
+        return false;
+    }
+    if matches!(
+        var("TERM_PROGRAM").as_deref(),
+        Some("iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper" | "Tabby" | "rio")
+    ) {
+        return true;
+    }
+    if ["KITTY_WINDOW_ID", "WT_SESSION", "KONSOLE_VERSION"]
+        .iter()
+        .any(|name| var(name).is_some())
+    {
+        return true;
+    }
+    if var("VTE_VERSION").and_then(|v| v.parse::<u32>().ok()) >= Some(5000) {
+        return true;
+    }
+    var("TERM").is_some_and(|term| {
+        ["kitty", "alacritty", "foot", "ghostty", "wezterm"]
+            .iter()
+            .any(|t| term.contains(t))
+    })
+}
+
+/// Find URLs and paths to existing files in `row`, one char per cell.
+/// Relative paths are resolved against `base`.
+pub fn find_links(row: &[char], base: Option<&Path>) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut i = 0;
+    while i < row.len() {
+        if row[i].is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let token_start = i;
+        while i < row.len() && !row[i].is_whitespace() {
+            i += 1;
+        }
+        let (mut start, mut end) = (token_start, i);
+        while start < end && OPENERS.contains(&row[start]) {
+            start += 1;
+        }
+        while end > start && CLOSERS.contains(&row[end - 1]) {
+            end -= 1;
+        }
+        if end - start < 2 {
+            continue;
+        }
+        let token: String = row[start..end].iter().collect();
+        if let Some(target) = link_target(&token, base) {
+            links.push(Link { start, end, target });
+        }
+    }
+    links
+}
+
+fn link_target(token: &str, base: Option<&Path>) -> Option<String> {
+    if !token.is_ascii() {
+        return None;
+    }
+    if token.starts_with("https://") || token.starts_with("http://") {
+        return Some(token.to_string());
+    }
+    if !token.contains('/') {
+        return None;
+    }
+    // Drop a trailing :line or :line:col from compiler and test output
+    let mut path = token;
+    while let Some((head, tail)) = path.rsplit_once(':') {
+        if tail.is_empty() || !tail.chars().all(|c| c.is_ascii_digit()) {
+            break;
+        }
+        path = head;
+    }
+    let resolved = if let Some(rest) = path.strip_prefix("~/") {
+        dirs::home_dir()?.join(rest)
+    } else if path.starts_with('/') {
+        PathBuf::from(path)
+    } else {
+        base?.join(path)
+    };
+    resolved
+        .exists()
+        .then(|| format!("file://{}", resolved.display()))
+}
+
+/// Turn the links in `area` of `buf` into OSC 8 hyperlinks.
+pub fn apply(buf: &mut Buffer, area: Rect, base: Option<&Path>) {
+    let area = area.intersection(buf.area);
+    for y in area.top()..area.bottom() {
+        // Cells holding anything but a single character never match
+        let row: Vec<char> = (area.left()..area.right())
+            .map(|x| {
+                let mut chars = buf[(x, y)].symbol().chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => c,
+                    _ => '\u{0}',
+                }
+            })
+            .collect();
+        for link in find_links(&row, base) {
+            let pairs = (link.end - link.start) / 2;
+            for pair in 0..pairs {
+                let offset = link.start + pair * 2;
+                let text: String = row[offset..offset + 2].iter().collect();
+                let x = area.left() + offset as u16;
+                let symbol = format!("\x1b]8;;{}\x07{}\x1b]8;;\x07", link.target, text);
+                buf[(x, y)].set_symbol(&symbol);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    #[test]
+    fn test_find_links() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "").unwrap();
+
+        let row = chars("see (https://github.com/o/r/pull/12). Error at src/main.rs:4:2, or a/b");
+        let links = find_links(&row, Some(dir.path()));
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].target, "https://github.com/o/r/pull/12");
+        assert_eq!(
+            row[links[0].start..links[0].end].iter().collect::<String>(),
+            "https://github.com/o/r/pull/12"
+        );
+        assert_eq!(
+            links[1].target,
+            format!("file://{}", dir.path().join("src/main.rs").display())
+        );
+        assert_eq!(
+            row[links[1].start..links[1].end].iter().collect::<String>(),
+            "src/main.rs:4:2"
+        );
+
+        assert!(find_links(&row, None).len() == 1);
+    }
+
+    #[test]
+    fn test_apply_pairs_cells_and_leaves_odd_tail() {
+        let area = Rect::new(0, 0, 30, 1);
+        let mut buf = Buffer::empty(area);
+        buf.set_string(
+            0,
+            0,
+            "go https://x.io/abc now",
+            ratatui::style::Style::default(),
+        );
+        apply(&mut buf, area, None);
+
+        // "https://x.io/abc" is 16 chars: 8 linked pairs
+        assert_eq!(
+            buf[(3, 0)].symbol(),
+            "\x1b]8;;https://x.io/abc\x07ht\x1b]8;;\x07"
+        );
+        assert_eq!(buf[(4, 0)].symbol(), "t");
+        assert!(buf[(17, 0)].symbol().ends_with("bc\x1b]8;;\x07"));
+        assert_eq!(buf[(19, 0)].symbol(), " ");
+
+        let mut buf = Buffer::empty(area);
+        buf.set_string(0, 0, "https://x.io/ab x", ratatui::style::Style::default());
+        apply(&mut buf, area, None);
+        // 15 chars: the last one stays plain so the next cell is not skipped
+        assert!(buf[(12, 0)].symbol().starts_with("\x1b]8;;"));
+        assert_eq!(buf[(14, 0)].symbol(), "b");
+    }
+}
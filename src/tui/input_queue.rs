@@ -0,0 +1,147 @@
+//! Buffered terminal input
+//!
+//! Everything the terminal has pending is read in one go and handled in
+//! arrival order, so key repeat and pasted navigation keys are neither
+//! dropped nor reordered, and the screen is drawn once per batch instead of
+//! once per key. Runs of events whose only effect is a redraw (resizes,
+//! mouse moves) collapse to the last one.
+
+use crossterm::event::{self, Event, KeyEventKind, MouseEventKind};
+use std::collections::VecDeque;
+use std::io;
+use std::time::Duration;
+
+/// Upper bound on one batch, so a flood of input can't starve the pollers
+const MAX_BATCH: usize = 256;
+
+#[derive(Default)]
+pub struct InputQueue {
+    events: VecDeque<Event>,
+}
+
+impl InputQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait up to `timeout` for input, then buffer whatever else is already
+    /// pending. Returns whether there is anything to handle.
+    pub fn fill(&mut self, timeout: Duration) -> io::Result<bool> {
+        if self.events.is_empty() && !event::poll(timeout)? {
+            return Ok(false);
+        }
+        while self.events.len() < MAX_BATCH && event::poll(Duration::ZERO)? {
+            self.push(event::read()?);
+        }
+        Ok(!self.events.is_empty())
+    }
+
+    pub fn push(&mut self, event: Event) {
+        // Terminals reporting key releases would otherwise act on every key twice
+        if let Event::Key(key) = &event {
+            if key.kind == KeyEventKind::Release {
+                return;
+            }
+        }
+        if let Some(last) = self.events.back_mut() {
+            if supersedes(last, &event) {
+                *last = event;
+                return;
+            }
+        }
+        self.events.push_back(event);
+    }
+
+    pub fn pop(&mut self) -> Option<Event> {
+        self.events.pop_front()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Drop buffered input, e.g. after leaving the TUI for an attached
+    /// session, when keys typed beforehand no longer apply to the screen
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
+/// Whether `next` makes `prev` redundant: both only trigger a redraw
+fn supersedes(prev: &Event, next: &Event) -> bool {
+    match (prev, next) {
+        (Event::Resize(..), Event::Resize(..)) => true,
+        (Event::Mouse(a), Event::Mouse(b)) => {
+            a.kind == MouseEventKind::Moved && b.kind == MouseEventKind::Moved
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyEventState, KeyModifiers, MouseEvent};
+
+    fn key(c: char, kind: KeyEventKind) -> Event {
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::NONE,
+            kind,
+            state: KeyEventState::NONE,
+        })
+    }
+
+    fn mouse(kind: MouseEventKind) -> Event {
+        Event::Mouse(MouseEvent {
+            kind,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn test_keys_keep_order_and_repeats() {
+        let mut queue = InputQueue::new();
+        for c in ['j', 'j', 'k', 'j'] {
+            queue.push(key(c, KeyEventKind::Press));
+        }
+        queue.push(key('j', KeyEventKind::Repeat));
+        queue.push(key('j', KeyEventKind::Release));
+        queue.push(key('n', KeyEventKind::Press));
+
+        let codes: Vec<_> = std::iter::from_fn(|| queue.pop())
+            .map(|e| match e {
+                Event::Key(k) => k.code,
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        let expected: Vec<_> = "jjkjjn".chars().map(KeyCode::Char).collect();
+        assert_eq!(codes, expected);
+    }
+
+    #[test]
+    fn test_redraw_only_events_collapse() {
+        let mut queue = InputQueue::new();
+        queue.push(Event::Resize(80, 24));
+        queue.push(Event::Resize(100, 30));
+        queue.push(mouse(MouseEventKind::Moved));
+        queue.push(mouse(MouseEventKind::Moved));
+        queue.push(mouse(MouseEventKind::ScrollDown));
+        queue.push(mouse(MouseEventKind::ScrollDown));
+        queue.push(Event::Resize(120, 40));
+
+        assert_eq!(queue.events.len(), 5);
+        assert_eq!(queue.pop(), Some(Event::Resize(100, 30)));
+        assert_eq!(queue.pop(), Some(mouse(MouseEventKind::Moved)));
+        assert_eq!(queue.pop(), Some(mouse(MouseEventKind::ScrollDown)));
+        assert_eq!(queue.pop(), Some(mouse(MouseEventKind::ScrollDown)));
+        assert_eq!(queue.pop(), Some(Event::Resize(120, 40)));
+        assert!(queue.events.is_empty());
+
+        queue.push(Event::Resize(80, 24));
+        queue.clear();
+        assert!(queue.pop().is_none());
+    }
+}
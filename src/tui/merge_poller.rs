@@ -0,0 +1,63 @@
+//! Background check for merged worktree branches
+//!
+//! Like health checks, each probe runs on its own short-lived thread since
+//! asking `gh` about a pull request can take a network round trip. Results
+//! come back over a channel and are applied by
+//! `HomeView::apply_merge_results`.
+
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+
+use chrono::{DateTime, Utc};
+
+use crate::git::merged::branch_merged;
+
+/// Outcome of one merge check
+#[derive(Debug)]
+pub struct MergeCheckResult {
+    pub id: String,
+    /// How the branch was merged, or `None` if it has not been
+    pub merged: Option<String>,
+}
+
+pub struct MergePoller {
+    result_tx: mpsc::Sender<MergeCheckResult>,
+    result_rx: mpsc::Receiver<MergeCheckResult>,
+}
+
+impl MergePoller {
+    pub fn new() -> Self {
+        let (result_tx, result_rx) = mpsc::channel();
+        Self {
+            result_tx,
+            result_rx,
+        }
+    }
+
+    /// Check whether `branch` of session `id` has been merged (non-blocking).
+    pub fn request_check(
+        &self,
+        id: String,
+        repo_path: PathBuf,
+        branch: String,
+        since: DateTime<Utc>,
+    ) {
+        let tx = self.result_tx.clone();
+        thread::spawn(move || {
+            let merged = branch_merged(&repo_path, &branch, since);
+            let _ = tx.send(MergeCheckResult { id, merged });
+        });
+    }
+
+    /// Collect all finished checks without blocking.
+    pub fn try_recv_results(&self) -> Vec<MergeCheckResult> {
+        self.result_rx.try_iter().collect()
+    }
+}
+
+impl Default for MergePoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
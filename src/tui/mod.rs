@@ -1,21 +1,35 @@
 //! Terminal User Interface module
 
 mod app;
+mod clone_poller;
+mod command_poller;
 mod components;
+mod config_watcher;
+mod conflict_poller;
 mod creation_poller;
 mod deletion_poller;
+mod demo;
 pub mod dialogs;
 pub mod diff;
+mod health_poller;
 mod home;
+mod hyperlink;
+mod input_queue;
+mod merge_poller;
+mod replay;
 pub mod settings;
 mod status_poller;
-mod styles;
+pub(crate) mod styles;
+mod terminal_title;
+mod watch_poller;
 
 pub use app::*;
+pub(crate) use home::format_age;
+pub use replay::Replay;
 
 use anyhow::Result;
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
+    event::{DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -26,7 +40,11 @@ use crate::migrations;
 use crate::session::get_update_settings;
 use crate::update::check_for_update;
 
-pub async fn run(profile: &str, startup_warning: Option<String>) -> Result<()> {
+pub async fn run(
+    profile: &str,
+    startup_warning: Option<String>,
+    replay: Option<Replay>,
+) -> Result<()> {
     // Run pending migrations with a spinner so users see progress
     if migrations::has_pending_migrations() {
         const SPINNER_FRAMES: &[char] = &['◐', '◓', '◑', '◒'];
@@ -61,6 +79,10 @@ pub async fn run(profile: &str, startup_warning: Option<String>) -> Result<()> {
         eprintln!("  pacman -S tmux        # Arch");
         std::process::exit(1);
     }
+    if let Err(e) = crate::tmux::version::check_supported() {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
 
     // Check for coding tools
     let available_tools = crate::tmux::AvailableTools::detect();
@@ -91,26 +113,61 @@ pub async fn run(profile: &str, startup_warning: Option<String>) -> Result<()> {
         }
     }
 
+    let mut app = App::new(profile, available_tools)?;
+    if let Some(warning) = startup_warning {
+        app.show_startup_warning(&warning);
+    }
+    if let Some(replay) = replay {
+        app.set_replay(replay);
+    }
+    if let Ok(config) = crate::session::resolve_config(profile) {
+        crate::tray::start_helper(&config.notifications);
+        crate::session::digest::deliver_if_due(&config.notifications);
+    }
+    run_app(app).await
+}
+
+/// Run the TUI on scripted sample sessions (`aoe --demo`). Needs neither
+/// tmux nor an agent, and leaves the user's sessions and config untouched.
+pub async fn run_demo(replay: Option<Replay>) -> Result<()> {
+    let dir = demo::prepare()?;
+    let result = match App::new("", crate::tmux::AvailableTools::all()) {
+        Ok(mut app) => {
+            app.start_demo();
+            if let Some(replay) = replay {
+                app.set_replay(replay);
+            }
+            run_app(app).await
+        }
+        Err(e) => Err(e),
+    };
+    let _ = std::fs::remove_dir_all(&dir);
+    result
+}
+
+async fn run_app(mut app: App) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableFocusChange
+    )?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app and run
-    let mut app = App::new(profile, available_tools)?;
-    if let Some(warning) = startup_warning {
-        app.show_startup_warning(&warning);
-    }
     let result = app.run(&mut terminal).await;
 
     // Restore terminal
+    terminal_title::restore();
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableFocusChange
     )?;
     terminal.show_cursor()?;
 
@@ -0,0 +1,342 @@
+//! Scripted input replay (`aoe --replay <FILE>`)
+//!
+//! Plays a script of keys into the TUI as if they were typed, so a bug can
+//! be reported as a file that reproduces it and e2e tests can drive the TUI
+//! without sleeping between keys. One step per line; blank lines and lines
+//! starting with `#` are ignored:
+//!
+//! ```text
+//! until Agent of Empires   # hold until the screen shows this text
+//! key n                    # one or more keys, tmux style: Enter, C-c, S-Up
+//! type fix the login bug   # literal text
+//! key Enter
+//! wait 500ms               # pause before the next step (ms or s)
+//! capture new-session.txt  # write the screen, relative to the script
+//! ```
+//!
+//! Screen steps (`until`, `capture`) only run once every key before them has
+//! been handled and drawn. Once the last step has played the TUI carries on
+//! taking input from the terminal.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use unicode_width::UnicodeWidthStr;
+
+/// How long an `until` step waits for its text before the replay fails
+const UNTIL_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+    Key(KeyEvent),
+    Wait(Duration),
+    Until(String),
+    Capture(PathBuf),
+}
+
+/// What the replay needs from the app next
+#[derive(Debug, PartialEq)]
+pub enum Cue {
+    /// Handle this key as if it was typed
+    Key(KeyEvent),
+    /// Render the screen and pass its text to [`Replay::screen`]
+    Screen,
+}
+
+#[derive(Debug)]
+pub struct Replay {
+    steps: VecDeque<Step>,
+    /// A `wait` step holds the script until then
+    resume_at: Option<Instant>,
+    /// When the current `until` step started waiting
+    until_since: Option<Instant>,
+}
+
+impl Replay {
+    /// Read and check a script, resolving `capture` paths against its
+    /// directory.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read replay file {}", path.display()))?;
+        let base = path.parent().unwrap_or(Path::new("."));
+        Self::parse(&content, base)
+            .with_context(|| format!("Invalid replay file {}", path.display()))
+    }
+
+    fn parse(content: &str, base: &Path) -> Result<Self> {
+        let mut steps = VecDeque::new();
+        for (number, line) in content.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (command, arg) = line
+                .split_once(char::is_whitespace)
+                .map(|(c, a)| (c, a.trim()))
+                .unwrap_or((line, ""));
+            let at = || format!("line {}", number + 1);
+            if arg.is_empty() {
+                bail!("{}: '{}' needs an argument", at(), command);
+            }
+            match command {
+                "key" => {
+                    for name in arg.split_whitespace() {
+                        let key = parse_key(name).with_context(at)?;
+                        steps.push_back(Step::Key(key));
+                    }
+                }
+                "type" => steps.extend(arg.chars().map(|c| Step::Key(char_key(c)))),
+                "wait" => steps.push_back(Step::Wait(parse_duration(arg).with_context(at)?)),
+                "until" => steps.push_back(Step::Until(arg.to_string())),
+                "capture" => steps.push_back(Step::Capture(base.join(arg))),
+                other => bail!("{}: unknown step '{}'", at(), other),
+            }
+        }
+        Ok(Self {
+            steps,
+            resume_at: None,
+            until_since: None,
+        })
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// The next thing to do, if a step is due. `idle` is whether all input
+    /// handed out so far has been handled; screen steps wait for it.
+    pub fn next(&mut self, idle: bool) -> Option<Cue> {
+        if let Some(at) = self.resume_at {
+            if Instant::now() < at {
+                return None;
+            }
+            self.resume_at = None;
+        }
+        match self.steps.front()? {
+            Step::Key(key) => {
+                let key = *key;
+                self.steps.pop_front();
+                Some(Cue::Key(key))
+            }
+            Step::Wait(duration) => {
+                self.resume_at = Some(Instant::now() + *duration);
+                self.steps.pop_front();
+                None
+            }
+            Step::Until(_) | Step::Capture(_) => idle.then_some(Cue::Screen),
+        }
+    }
+
+    /// Run the pending screen step against the rendered `screen`. Returns
+    /// whether the step is done; an `until` whose text is not showing yet
+    /// is retried on a later frame.
+    pub fn screen(&mut self, screen: &str) -> Result<bool> {
+        match self.steps.front() {
+            Some(Step::Until(text)) => {
+                if screen.contains(text.as_str()) {
+                    self.until_since = None;
+                    self.steps.pop_front();
+                    return Ok(true);
+                }
+                let since = *self.until_since.get_or_insert_with(Instant::now);
+                if since.elapsed() > UNTIL_TIMEOUT {
+                    bail!(
+                        "Replay timed out waiting for {:?} after {:?}",
+                        text,
+                        UNTIL_TIMEOUT
+                    );
+                }
+                Ok(false)
+            }
+            Some(Step::Capture(path)) => {
+                write_capture(path, screen)?;
+                self.steps.pop_front();
+                Ok(true)
+            }
+            _ => Ok(true),
+        }
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    if line.trim_start().starts_with('#') {
+        return "";
+    }
+    match line.find(" #") {
+        Some(i) => &line[..i],
+        None => line,
+    }
+}
+
+/// Write atomically, so a test polling for the file never reads half of it
+fn write_capture(path: &Path, screen: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, screen)
+        .with_context(|| format!("Failed to write capture {}", path.display()))?;
+    std::fs::rename(&tmp, path)
+        .with_context(|| format!("Failed to write capture {}", path.display()))?;
+    Ok(())
+}
+
+/// The buffer as plain text, one line per row without trailing spaces
+pub fn screen_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        let mut skip = 0;
+        for x in area.left()..area.right() {
+            if skip > 0 {
+                // Covered by the wide character before it
+                skip -= 1;
+                continue;
+            }
+            let symbol = buffer[(x, y)].symbol();
+            skip = symbol.width().saturating_sub(1);
+            line.push_str(symbol);
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+fn parse_duration(arg: &str) -> Result<Duration> {
+    let (number, unit) = match arg.strip_suffix("ms") {
+        Some(number) => (number, 1),
+        None => (arg.strip_suffix('s').unwrap_or(arg), 1000),
+    };
+    let number: u64 = number
+        .trim()
+        .parse()
+        .with_context(|| format!("invalid duration '{}' (use e.g. 500ms or 2s)", arg))?;
+    Ok(Duration::from_millis(number * unit))
+}
+
+fn char_key(c: char) -> KeyEvent {
+    // Terminals report capitals with Shift held
+    let modifiers = if c.is_uppercase() {
+        KeyModifiers::SHIFT
+    } else {
+        KeyModifiers::NONE
+    };
+    KeyEvent::new(KeyCode::Char(c), modifiers)
+}
+
+/// A tmux-style key name: `j`, `Enter`, `C-c`, `M-x`, `S-Up`, `F5`
+fn parse_key(name: &str) -> Result<KeyEvent> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = name;
+    while rest.len() > 2 {
+        let modifier = match &rest[..2] {
+            "C-" => KeyModifiers::CONTROL,
+            "M-" => KeyModifiers::ALT,
+            "S-" => KeyModifiers::SHIFT,
+            _ => break,
+        };
+        modifiers |= modifier;
+        rest = &rest[2..];
+    }
+
+    let mut chars = rest.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        let key = char_key(c);
+        return Ok(KeyEvent::new(key.code, key.modifiers | modifiers));
+    }
+
+    let code = match rest {
+        "Enter" => KeyCode::Enter,
+        "Escape" | "Esc" => KeyCode::Esc,
+        "Tab" => KeyCode::Tab,
+        "BTab" => KeyCode::BackTab,
+        "BSpace" | "Backspace" => KeyCode::Backspace,
+        "Space" => KeyCode::Char(' '),
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" | "PPage" | "PgUp" => KeyCode::PageUp,
+        "PageDown" | "NPage" | "PgDn" => KeyCode::PageDown,
+        "Delete" | "DC" => KeyCode::Delete,
+        "Insert" | "IC" => KeyCode::Insert,
+        f if f.starts_with('F') => match f[1..].parse::<u8>() {
+            Ok(n @ 1..=12) => KeyCode::F(n),
+            _ => bail!("unknown key '{}'", name),
+        },
+        _ => bail!("unknown key '{}'", name),
+    };
+    Ok(KeyEvent::new(code, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn test_script_plays_keys_then_waits_for_screen() {
+        let script = "\
+# open the new session dialog
+until Agent of Empires
+key n C-c S-Up
+type Hi # trailing comment
+wait 0ms
+capture out/home.txt
+";
+        let mut replay = Replay::parse(script, Path::new("/tmp/replay")).unwrap();
+
+        // Nothing is typed until the screen shows the title
+        assert_eq!(replay.next(true), Some(Cue::Screen));
+        assert!(!replay.screen("loading").unwrap());
+        assert!(replay.screen("Agent of Empires").unwrap());
+
+        let keys: Vec<_> = std::iter::from_fn(|| match replay.next(true) {
+            Some(Cue::Key(key)) => Some(key),
+            _ => None,
+        })
+        .collect();
+        assert_eq!(
+            keys,
+            vec![
+                KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE),
+                KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+                KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT),
+                KeyEvent::new(KeyCode::Char('H'), KeyModifiers::SHIFT),
+                KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE),
+            ]
+        );
+
+        // The capture waits until the typed keys have been handled
+        assert_eq!(replay.next(false), None);
+        assert_eq!(replay.next(true), Some(Cue::Screen));
+        assert_eq!(
+            replay.steps.front(),
+            Some(&Step::Capture(PathBuf::from("/tmp/replay/out/home.txt")))
+        );
+    }
+
+    #[test]
+    fn test_invalid_scripts_name_the_line() {
+        let err = Replay::parse("key j\nkey Hyper\n", Path::new(".")).unwrap_err();
+        assert!(format!("{:#}", err).contains("line 2"));
+        assert!(Replay::parse("jump 3\n", Path::new(".")).is_err());
+        assert!(Replay::parse("wait soon\n", Path::new(".")).is_err());
+        assert!(Replay::parse("capture\n", Path::new(".")).is_err());
+    }
+
+    #[test]
+    fn test_screen_text_skips_wide_character_padding() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 2));
+        buffer.set_string(0, 0, "a✓界b", ratatui::style::Style::default());
+        assert_eq!(screen_text(&buffer), "a✓界b\n\n");
+    }
+}
@@ -1,8 +1,11 @@
 //! Setting field definitions and config mapping
 
+use crate::session::digest::DigestDelivery;
 use crate::session::{
-    validate_check_interval, Config, ContainerRuntimeName, DefaultTerminalMode, ProfileConfig,
-    TmuxMouseMode, TmuxStatusBarMode,
+    validate_check_interval, validate_health_check_interval, BudgetAction, ColorVision, Config,
+    ContainerRuntimeName, DefaultTerminalMode, HostSandboxProfile, HyperlinkMode, IoPriority,
+    MergedSessionAction, ProfileConfig, ShareDestination, TmuxMouseMode, TmuxStatusBarMode,
+    TmuxWindowSize,
 };
 use crate::sound::{validate_sound_exists, SoundMode};
 use crate::tui::styles::AVAILABLE_THEMES;
@@ -14,11 +17,14 @@ use super::SettingsScope;
 pub enum SettingsCategory {
     Theme,
     Updates,
+    Polling,
     Worktree,
     Sandbox,
     Tmux,
     Session,
     Sound,
+    Notifications,
+    Share,
     Hooks,
 }
 
@@ -27,11 +33,14 @@ impl SettingsCategory {
         match self {
             Self::Theme => "Theme",
             Self::Updates => "Updates",
+            Self::Polling => "Polling",
             Self::Worktree => "Worktree",
             Self::Sandbox => "Sandbox",
             Self::Tmux => "Tmux",
             Self::Session => "Session",
             Self::Sound => "Sound",
+            Self::Notifications => "Notifications",
+            Self::Share => "Share",
             Self::Hooks => "Hooks",
         }
     }
@@ -42,16 +51,41 @@ impl SettingsCategory {
 pub enum FieldKey {
     // Theme
     ThemeName,
+    RowFormat,
+    ColorVision,
+    Hyperlinks,
+    Spellcheck,
+    PreviewMinWidth,
+    RowDetailsMinWidth,
+    TerminalTitle,
     // Updates
     CheckEnabled,
     CheckIntervalHours,
     NotifyInCli,
+    CheckAgents,
+    // Polling
+    PaneCaptureMs,
+    GitCheckSecs,
+    ResourceUsageSecs,
+    EcoMode,
     // Worktree
     PathTemplate,
     BareRepoPathTemplate,
     WorktreeAutoCleanup,
     DeleteBranchOnCleanup,
     WorkspacePathTemplate,
+    WorkspaceRepos,
+    SparsePaths,
+    MergeTestCommand,
+    SyncNotes,
+    OnMerged,
+    BranchTagPattern,
+    BranchTicketPattern,
+    TicketUrlTemplate,
+    WorktreePoolSize,
+    CloneDir,
+    ShallowClone,
+    ConflictPrompt,
     // Sandbox
     SandboxEnabledByDefault,
     YoloModeDefault,
@@ -67,13 +101,38 @@ pub enum FieldKey {
     MountSsh,
     CustomInstruction,
     ContainerRuntime,
+    HostSandboxProfile,
+    HostBlockNetwork,
     // Tmux
     StatusBar,
     Mouse,
+    WindowSize,
     // Session
     DefaultTool,
+    FallbackTool,
     AgentExtraArgs,
     AgentCommandOverride,
+    TrashRetentionDays,
+    TrashMaxEntries,
+    SessionLocks,
+    PromptGuardrails,
+    Nice,
+    IoPriority,
+    SessionMemoryLimit,
+    SessionCpuLimit,
+    SessionProxy,
+    SessionNoProxy,
+    SessionIcon,
+    DailyBudget,
+    WeeklyBudget,
+    BudgetWarnPercent,
+    BudgetAction,
+    ProviderLimits,
+    CredentialExpiry,
+    CredentialWarnDays,
+    CredentialRefreshCommand,
+    AgentShim,
+    ProjectEnv,
     // Sound
     SoundEnabled,
     SoundMode,
@@ -82,9 +141,25 @@ pub enum FieldKey {
     SoundOnWaiting,
     SoundOnIdle,
     SoundOnError,
+    SoundOnFinished,
+    // Notifications
+    NotificationRules,
+    NotificationWebhookUrl,
+    NotificationTray,
+    NotificationTrayFocusCommand,
+    NotificationDigest,
+    NotificationDigestPath,
+    // Share
+    ShareDestination,
+    ShareS3Bucket,
+    ShareS3LinkHours,
+    ShareGistPublic,
     // Hooks
     HookOnCreate,
     HookOnLaunch,
+    HookHealthCheck,
+    HookHealthCheckInterval,
+    HookTestWatch,
 }
 
 /// Resolve a field value from global config and optional profile override.
@@ -168,6 +243,15 @@ fn parse_key_value_list(items: &[String]) -> std::collections::HashMap<String, S
         .collect()
 }
 
+/// `provider=count` items as a `session.provider_limits` map, skipping
+/// counts that are not numbers
+fn parse_limit_list(items: &[String]) -> std::collections::HashMap<String, usize> {
+    parse_key_value_list(items)
+        .into_iter()
+        .filter_map(|(k, v)| Some((k.trim().to_lowercase(), v.trim().parse().ok()?)))
+        .collect()
+}
+
 /// Value types for settings fields
 #[derive(Debug, Clone)]
 pub enum FieldValue {
@@ -203,17 +287,50 @@ impl SettingField {
                 validate_check_interval(*n)?;
                 Ok(())
             }
+            (FieldKey::HookHealthCheckInterval, FieldValue::Number(n)) => {
+                validate_health_check_interval(*n)?;
+                Ok(())
+            }
+            (FieldKey::PaneCaptureMs, FieldValue::Number(n)) => {
+                crate::session::validate_pane_capture_interval(*n)?;
+                Ok(())
+            }
+            (FieldKey::GitCheckSecs | FieldKey::ResourceUsageSecs, FieldValue::Number(n)) => {
+                crate::session::validate_poll_interval(*n)?;
+                Ok(())
+            }
             (FieldKey::MemoryLimit, FieldValue::OptionalText(Some(v))) => {
                 crate::session::validate_memory_limit(v)?;
                 Ok(())
             }
+            (FieldKey::SessionProxy, FieldValue::OptionalText(Some(v))) => {
+                crate::session::proxy::validate_url(v)
+            }
+            (FieldKey::SessionIcon, FieldValue::OptionalText(Some(v))) => {
+                crate::session::validate_icon(v)
+            }
+            (FieldKey::CredentialExpiry, FieldValue::List(items)) => {
+                crate::session::credentials::validate(items)
+            }
+            (FieldKey::ProviderLimits, FieldValue::List(items)) => {
+                crate::session::providers::validate(items)
+            }
+            (
+                FieldKey::BranchTagPattern | FieldKey::BranchTicketPattern,
+                FieldValue::Text(pattern),
+            ) => crate::session::branch_labels::validate_pattern(pattern),
+            (FieldKey::NotificationRules, FieldValue::List(rules)) => {
+                crate::notifications::parse_rules(rules)?;
+                Ok(())
+            }
             // Sound field validation - check if sound file exists
             (
                 FieldKey::SoundOnStart
                 | FieldKey::SoundOnRunning
                 | FieldKey::SoundOnWaiting
                 | FieldKey::SoundOnIdle
-                | FieldKey::SoundOnError,
+                | FieldKey::SoundOnError
+                | FieldKey::SoundOnFinished,
                 FieldValue::OptionalText(Some(name)),
             ) => {
                 if !name.is_empty() {
@@ -239,11 +356,14 @@ pub fn build_fields_for_category(
     match category {
         SettingsCategory::Theme => build_theme_fields(scope, global, profile),
         SettingsCategory::Updates => build_updates_fields(scope, global, profile),
+        SettingsCategory::Polling => build_polling_fields(scope, global, profile),
         SettingsCategory::Worktree => build_worktree_fields(scope, global, profile),
         SettingsCategory::Sandbox => build_sandbox_fields(scope, global, profile),
         SettingsCategory::Tmux => build_tmux_fields(scope, global, profile),
         SettingsCategory::Session => build_session_fields(scope, global, profile),
         SettingsCategory::Sound => build_sound_fields(scope, global, profile),
+        SettingsCategory::Notifications => build_notification_fields(scope, global, profile),
+        SettingsCategory::Share => build_share_fields(scope, global, profile),
         SettingsCategory::Hooks => build_hooks_fields(scope, global, profile),
     }
 }
@@ -276,15 +396,179 @@ fn build_theme_fields(
         },
     );
 
-    vec![SettingField {
-        key: FieldKey::ThemeName,
-        label: "Theme",
-        description: "Color theme for the TUI",
-        value: FieldValue::Select { selected, options },
-        category: SettingsCategory::Theme,
-        has_override,
-        inherited_display: inherited,
-    }]
+    let (row_format, o_row) = resolve_optional(
+        scope,
+        global.theme.row_format.clone(),
+        theme.and_then(|t| t.row_format.clone()),
+        theme.map(|t| t.row_format.is_some()).unwrap_or(false),
+    );
+
+    let (vision, vision_override) = resolve_value(
+        scope,
+        global.theme.color_vision,
+        theme.and_then(|t| t.color_vision),
+    );
+    let (hyperlinks, hyperlinks_override) = resolve_value(
+        scope,
+        global.theme.hyperlinks,
+        theme.and_then(|t| t.hyperlinks),
+    );
+    let (spellcheck, spellcheck_override) = resolve_value(
+        scope,
+        global.theme.spellcheck,
+        theme.and_then(|t| t.spellcheck),
+    );
+    let (preview_min_width, preview_override) = resolve_value(
+        scope,
+        global.theme.preview_min_width,
+        theme.and_then(|t| t.preview_min_width),
+    );
+    let (row_details_min_width, row_details_override) = resolve_value(
+        scope,
+        global.theme.row_details_min_width,
+        theme.and_then(|t| t.row_details_min_width),
+    );
+    let (terminal_title, terminal_title_override) = resolve_value(
+        scope,
+        global.theme.terminal_title.clone(),
+        theme.and_then(|t| t.terminal_title.clone()),
+    );
+    let hyperlink_options: Vec<String> = vec!["Auto".into(), "On".into(), "Off".into()];
+    let vision_options: Vec<String> = vec![
+        "Normal".into(),
+        "Deuteranopia".into(),
+        "Protanopia".into(),
+        "Tritanopia".into(),
+    ];
+
+    vec![
+        SettingField {
+            key: FieldKey::ThemeName,
+            label: "Theme",
+            description: "Color theme for the TUI",
+            value: FieldValue::Select { selected, options },
+            category: SettingsCategory::Theme,
+            has_override,
+            inherited_display: inherited,
+        },
+        SettingField {
+            key: FieldKey::RowFormat,
+            label: "Row Format",
+            description: "Session row template, e.g. \"{status} {name:20} {branch} {age}\" (empty for default)",
+            value: FieldValue::OptionalText(row_format),
+            category: SettingsCategory::Theme,
+            has_override: o_row,
+            inherited_display: inherited_if(
+                o_row,
+                FieldValue::OptionalText(global.theme.row_format.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::ColorVision,
+            label: "Color Vision",
+            description: "Status colors for color vision deficiencies (statuses also differ by glyph)",
+            value: FieldValue::Select {
+                selected: color_vision_index(vision),
+                options: vision_options.clone(),
+            },
+            category: SettingsCategory::Theme,
+            has_override: vision_override,
+            inherited_display: inherited_if(
+                vision_override,
+                FieldValue::Select {
+                    selected: color_vision_index(global.theme.color_vision),
+                    options: vision_options,
+                },
+            ),
+        },
+        SettingField {
+            key: FieldKey::Hyperlinks,
+            label: "Hyperlinks",
+            description: "Clickable paths and URLs in the preview (Auto: supporting terminals, outside tmux)",
+            value: FieldValue::Select {
+                selected: hyperlink_mode_index(hyperlinks),
+                options: hyperlink_options.clone(),
+            },
+            category: SettingsCategory::Theme,
+            has_override: hyperlinks_override,
+            inherited_display: inherited_if(
+                hyperlinks_override,
+                FieldValue::Select {
+                    selected: hyperlink_mode_index(global.theme.hyperlinks),
+                    options: hyperlink_options,
+                },
+            ),
+        },
+        SettingField {
+            key: FieldKey::Spellcheck,
+            label: "Spellcheck",
+            description: "Underline unknown words in the send message and retry editors",
+            value: FieldValue::Bool(spellcheck),
+            category: SettingsCategory::Theme,
+            has_override: spellcheck_override,
+            inherited_display: inherited_if(
+                spellcheck_override,
+                FieldValue::Bool(global.theme.spellcheck),
+            ),
+        },
+        SettingField {
+            key: FieldKey::PreviewMinWidth,
+            label: "Preview Min Width",
+            description: "Hide the preview pane in terminals narrower than this (z cycles auto/full/compact)",
+            value: FieldValue::Number(preview_min_width as u64),
+            category: SettingsCategory::Theme,
+            has_override: preview_override,
+            inherited_display: inherited_if(
+                preview_override,
+                FieldValue::Number(global.theme.preview_min_width as u64),
+            ),
+        },
+        SettingField {
+            key: FieldKey::RowDetailsMinWidth,
+            label: "Row Details Min Width",
+            description: "Leave branch names and tags out of session rows in terminals narrower than this",
+            value: FieldValue::Number(row_details_min_width as u64),
+            category: SettingsCategory::Theme,
+            has_override: row_details_override,
+            inherited_display: inherited_if(
+                row_details_override,
+                FieldValue::Number(global.theme.row_details_min_width as u64),
+            ),
+        },
+        SettingField {
+            key: FieldKey::TerminalTitle,
+            label: "Terminal Title",
+            description: "Window/tab title, e.g. \"aoe - {summary}\" or \"{attention} waiting\" (empty leaves it alone)",
+            value: FieldValue::Text(terminal_title),
+            category: SettingsCategory::Theme,
+            has_override: terminal_title_override,
+            inherited_display: inherited_if(
+                terminal_title_override,
+                FieldValue::Text(global.theme.terminal_title.clone()),
+            ),
+        },
+    ]
+}
+
+const COLOR_VISIONS: [ColorVision; 4] = [
+    ColorVision::Normal,
+    ColorVision::Deuteranopia,
+    ColorVision::Protanopia,
+    ColorVision::Tritanopia,
+];
+
+fn color_vision_index(vision: ColorVision) -> usize {
+    COLOR_VISIONS.iter().position(|v| *v == vision).unwrap_or(0)
+}
+
+const HYPERLINK_MODES: [HyperlinkMode; 3] = [
+    HyperlinkMode::Auto,
+    HyperlinkMode::Enabled,
+    HyperlinkMode::Disabled,
+];
+
+fn hyperlink_mode_index(mode: HyperlinkMode) -> usize {
+    HYPERLINK_MODES.iter().position(|m| *m == mode).unwrap_or(0)
 }
 
 fn build_updates_fields(
@@ -309,6 +593,11 @@ fn build_updates_fields(
         global.updates.notify_in_cli,
         updates.and_then(|u| u.notify_in_cli),
     );
+    let (check_agents, o4) = resolve_value(
+        scope,
+        global.updates.check_agents,
+        updates.and_then(|u| u.check_agents),
+    );
 
     vec![
         SettingField {
@@ -341,6 +630,86 @@ fn build_updates_fields(
             has_override: o3,
             inherited_display: inherited_if(o3, FieldValue::Bool(global.updates.notify_in_cli)),
         },
+        SettingField {
+            key: FieldKey::CheckAgents,
+            label: "Check Agent Versions",
+            description: "Show newer releases of installed agent CLIs on the dashboard",
+            value: FieldValue::Bool(check_agents),
+            category: SettingsCategory::Updates,
+            has_override: o4,
+            inherited_display: inherited_if(o4, FieldValue::Bool(global.updates.check_agents)),
+        },
+    ]
+}
+
+fn build_polling_fields(
+    scope: SettingsScope,
+    global: &Config,
+    profile: &ProfileConfig,
+) -> Vec<SettingField> {
+    let polling = profile.polling.as_ref();
+
+    let (pane_capture_ms, o1) = resolve_value(
+        scope,
+        global.polling.pane_capture_ms,
+        polling.and_then(|p| p.pane_capture_ms),
+    );
+    let (git_check_secs, o2) = resolve_value(
+        scope,
+        global.polling.git_check_secs,
+        polling.and_then(|p| p.git_check_secs),
+    );
+    let (resource_usage_secs, o3) = resolve_value(
+        scope,
+        global.polling.resource_usage_secs,
+        polling.and_then(|p| p.resource_usage_secs),
+    );
+    let (eco_mode, o4) = resolve_value(
+        scope,
+        global.polling.eco_mode,
+        polling.and_then(|p| p.eco_mode),
+    );
+
+    vec![
+        SettingField {
+            key: FieldKey::PaneCaptureMs,
+            label: "Pane Capture (ms)",
+            description: "Fastest session status check; quiet sessions back off to 8x this",
+            value: FieldValue::Number(pane_capture_ms),
+            category: SettingsCategory::Polling,
+            has_override: o1,
+            inherited_display: inherited_if(o1, FieldValue::Number(global.polling.pane_capture_ms)),
+        },
+        SettingField {
+            key: FieldKey::GitCheckSecs,
+            label: "Git Check (seconds)",
+            description: "How often worktree branches are checked for a merge",
+            value: FieldValue::Number(git_check_secs),
+            category: SettingsCategory::Polling,
+            has_override: o2,
+            inherited_display: inherited_if(o2, FieldValue::Number(global.polling.git_check_secs)),
+        },
+        SettingField {
+            key: FieldKey::ResourceUsageSecs,
+            label: "Resource Usage (seconds)",
+            description: "Default refresh of CPU and cost in `aoe top`",
+            value: FieldValue::Number(resource_usage_secs),
+            category: SettingsCategory::Polling,
+            has_override: o3,
+            inherited_display: inherited_if(
+                o3,
+                FieldValue::Number(global.polling.resource_usage_secs),
+            ),
+        },
+        SettingField {
+            key: FieldKey::EcoMode,
+            label: "Eco Mode",
+            description: "Poll 4x less often while the terminal is unfocused",
+            value: FieldValue::Bool(eco_mode),
+            category: SettingsCategory::Polling,
+            has_override: o4,
+            inherited_display: inherited_if(o4, FieldValue::Bool(global.polling.eco_mode)),
+        },
     ]
 }
 
@@ -376,6 +745,68 @@ fn build_worktree_fields(
         global.worktree.workspace_path_template.clone(),
         wt.and_then(|w| w.workspace_path_template.clone()),
     );
+    let (repos, o9) = resolve_value(
+        scope,
+        global.worktree.repos.clone(),
+        wt.and_then(|w| w.repos.clone()),
+    );
+    let (sparse_paths, o14) = resolve_value(
+        scope,
+        global.worktree.sparse_paths.clone(),
+        wt.and_then(|w| w.sparse_paths.clone()),
+    );
+    let (merge_test_command, o6) = resolve_optional(
+        scope,
+        global.worktree.merge_test_command.clone(),
+        wt.and_then(|w| w.merge_test_command.clone()),
+        wt.map(|w| w.merge_test_command.is_some()).unwrap_or(false),
+    );
+    let (sync_notes, o7) = resolve_value(
+        scope,
+        global.worktree.sync_notes,
+        wt.and_then(|w| w.sync_notes),
+    );
+    let (on_merged, o8) = resolve_value(
+        scope,
+        global.worktree.on_merged,
+        wt.and_then(|w| w.on_merged),
+    );
+    let (branch_tag_pattern, o10) = resolve_value(
+        scope,
+        global.worktree.branch_tag_pattern.clone(),
+        wt.and_then(|w| w.branch_tag_pattern.clone()),
+    );
+    let (branch_ticket_pattern, o11) = resolve_value(
+        scope,
+        global.worktree.branch_ticket_pattern.clone(),
+        wt.and_then(|w| w.branch_ticket_pattern.clone()),
+    );
+    let (ticket_url_template, o12) = resolve_value(
+        scope,
+        global.worktree.ticket_url_template.clone(),
+        wt.and_then(|w| w.ticket_url_template.clone()),
+    );
+    let (pool_size, o13) = resolve_value(
+        scope,
+        global.worktree.pool_size,
+        wt.and_then(|w| w.pool_size),
+    );
+    let (clone_dir, o15) = resolve_value(
+        scope,
+        global.worktree.clone_dir.clone(),
+        wt.and_then(|w| w.clone_dir.clone()),
+    );
+    let (shallow_clone, o16) = resolve_value(
+        scope,
+        global.worktree.shallow_clone,
+        wt.and_then(|w| w.shallow_clone),
+    );
+    let (conflict_prompt, o17) = resolve_value(
+        scope,
+        global.worktree.conflict_prompt.clone(),
+        wt.and_then(|w| w.conflict_prompt.clone()),
+    );
+    let merged_options: Vec<String> = vec!["Prompt".into(), "Archive".into(), "Off".into()];
 
     vec![
         SettingField {
@@ -435,9 +866,166 @@ fn build_worktree_fields(
                 FieldValue::Text(global.worktree.workspace_path_template.clone()),
             ),
         },
+        SettingField {
+            key: FieldKey::WorkspaceRepos,
+            label: "Workspace Repos",
+            description: "Other repos or submodules each worktree session spans, relative to the repo root (e.g. ../shared)",
+            value: FieldValue::List(repos),
+            category: SettingsCategory::Worktree,
+            has_override: o9,
+            inherited_display: inherited_if(
+                o9,
+                FieldValue::List(global.worktree.repos.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::SparsePaths,
+            label: "Sparse Checkout Paths",
+            description: "Only check out these paths in new worktrees (e.g. services/api; globs allowed; empty for all)",
+            value: FieldValue::List(sparse_paths),
+            category: SettingsCategory::Worktree,
+            has_override: o14,
+            inherited_display: inherited_if(
+                o14,
+                FieldValue::List(global.worktree.sparse_paths.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::MergeTestCommand,
+            label: "Merge Queue Test Command",
+            description: "Command run in each rebased worktree before the merge queue merges it (e.g. cargo test)",
+            value: FieldValue::OptionalText(merge_test_command),
+            category: SettingsCategory::Worktree,
+            has_override: o6,
+            inherited_display: inherited_if(
+                o6,
+                FieldValue::OptionalText(global.worktree.merge_test_command.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::SyncNotes,
+            label: "Sync Notes to Repo",
+            description: "Mirror session notes to docs/agent-sessions/<name>.md in the worktree",
+            value: FieldValue::Bool(sync_notes),
+            category: SettingsCategory::Worktree,
+            has_override: o7,
+            inherited_display: inherited_if(o7, FieldValue::Bool(global.worktree.sync_notes)),
+        },
+        SettingField {
+            key: FieldKey::OnMerged,
+            label: "When Branch Is Merged",
+            description: "Archive the session and remove its worktree once its branch is merged (Prompt asks first)",
+            value: FieldValue::Select {
+                selected: merged_action_index(on_merged),
+                options: merged_options.clone(),
+            },
+            category: SettingsCategory::Worktree,
+            has_override: o8,
+            inherited_display: inherited_if(
+                o8,
+                FieldValue::Select {
+                    selected: merged_action_index(global.worktree.on_merged),
+                    options: merged_options,
+                },
+            ),
+        },
+        SettingField {
+            key: FieldKey::BranchTagPattern,
+            label: "Branch Tag Pattern",
+            description: "Regex tagging new sessions from their branch name (tag group or whole match; empty for none)",
+            value: FieldValue::Text(branch_tag_pattern),
+            category: SettingsCategory::Worktree,
+            has_override: o10,
+            inherited_display: inherited_if(
+                o10,
+                FieldValue::Text(global.worktree.branch_tag_pattern.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::BranchTicketPattern,
+            label: "Branch Ticket Pattern",
+            description: "Regex finding a ticket ID in new sessions' branch names (ticket group or whole match; empty for none)",
+            value: FieldValue::Text(branch_ticket_pattern),
+            category: SettingsCategory::Worktree,
+            has_override: o11,
+            inherited_display: inherited_if(
+                o11,
+                FieldValue::Text(global.worktree.branch_ticket_pattern.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::TicketUrlTemplate,
+            label: "Ticket URL Template",
+            description: "Link for a branch's ticket, {ticket} replaced by its ID",
+            value: FieldValue::Text(ticket_url_template),
+            category: SettingsCategory::Worktree,
+            has_override: o12,
+            inherited_display: inherited_if(
+                o12,
+                FieldValue::Text(global.worktree.ticket_url_template.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::WorktreePoolSize,
+            label: "Worktree Pool Size",
+            description: "Spare worktrees kept ready per repo so new sessions skip creating one (0 for none)",
+            value: FieldValue::Number(pool_size as u64),
+            category: SettingsCategory::Worktree,
+            has_override: o13,
+            inherited_display: inherited_if(
+                o13,
+                FieldValue::Number(global.worktree.pool_size as u64),
+            ),
+        },
+        SettingField {
+            key: FieldKey::CloneDir,
+            label: "Clone Directory",
+            description: "Where a git URL entered as a session's path is cloned",
+            value: FieldValue::Text(clone_dir),
+            category: SettingsCategory::Worktree,
+            has_override: o15,
+            inherited_display: inherited_if(
+                o15,
+                FieldValue::Text(global.worktree.clone_dir.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::ShallowClone,
+            label: "Shallow Clone",
+            description: "Clone only the latest commit of those repositories",
+            value: FieldValue::Bool(shallow_clone),
+            category: SettingsCategory::Worktree,
+            has_override: o16,
+            inherited_display: inherited_if(o16, FieldValue::Bool(global.worktree.shallow_clone)),
+        },
+        SettingField {
+            key: FieldKey::ConflictPrompt,
+            label: "Conflict Prompt",
+            description: "Prompt asking the agent to resolve merge conflicts ({operation}, {files}, {branch})",
+            value: FieldValue::Text(conflict_prompt),
+            category: SettingsCategory::Worktree,
+            has_override: o17,
+            inherited_display: inherited_if(
+                o17,
+                FieldValue::Text(global.worktree.conflict_prompt.clone()),
+            ),
+        },
     ]
 }
 
+const MERGED_ACTIONS: [MergedSessionAction; 3] = [
+    MergedSessionAction::Prompt,
+    MergedSessionAction::Archive,
+    MergedSessionAction::Off,
+];
+
+fn merged_action_index(action: MergedSessionAction) -> usize {
+    MERGED_ACTIONS
+        .iter()
+        .position(|a| *a == action)
+        .unwrap_or(0)
+}
+
 fn build_sandbox_fields(
     scope: SettingsScope,
     global: &Config,
@@ -519,9 +1107,21 @@ fn build_sandbox_fields(
         DefaultTerminalMode::Container => 1,
     };
 
+    let (host_profile, o_hp) = resolve_value(
+        scope,
+        global.sandbox.host_profile,
+        sb.and_then(|s| s.host_profile),
+    );
+    let (host_block_network, o_hn) = resolve_value(
+        scope,
+        global.sandbox.host_block_network,
+        sb.and_then(|s| s.host_block_network),
+    );
+
     let container_runtime_selected = match container_runtime {
         ContainerRuntimeName::Docker => 0,
         ContainerRuntimeName::AppleContainer => 1,
+        ContainerRuntimeName::Podman => 2,
     };
 
     let global_terminal_mode_selected = match global.sandbox.default_terminal_mode {
@@ -533,8 +1133,12 @@ fn build_sandbox_fields(
     let global_container_runtime_selected = match global.sandbox.container_runtime {
         ContainerRuntimeName::Docker => 0,
         ContainerRuntimeName::AppleContainer => 1,
+        ContainerRuntimeName::Podman => 2,
     };
-    let container_runtime_options = vec!["Docker".into(), "Apple Container".into()];
+    let container_runtime_options =
+        vec!["Docker".into(), "Apple Container".into(), "Podman".into()];
+
+    let host_profile_options = vec!["Off".into(), "Workdir".into(), "Isolated".into()];
 
     vec![
         SettingField {
@@ -684,7 +1288,7 @@ fn build_sandbox_fields(
         SettingField {
             key: FieldKey::ContainerRuntime,
             label: "Container Runtime",
-            description: "Container runtime for sandboxing (Docker or Apple Container on macOS)",
+            description: "Container runtime for sandboxing (Docker, Apple Container on macOS, or Podman)",
             value: FieldValue::Select {
                 selected: container_runtime_selected,
                 options: container_runtime_options.clone(),
@@ -699,9 +1303,55 @@ fn build_sandbox_fields(
                 },
             ),
         },
+        SettingField {
+            key: FieldKey::HostSandboxProfile,
+            label: "Host Sandbox",
+            description: "Restrict non-container sessions with bubblewrap/sandbox-exec: Workdir limits writes to the project, Isolated also hides the home directory",
+            value: FieldValue::Select {
+                selected: host_profile_index(host_profile),
+                options: host_profile_options.clone(),
+            },
+            category: SettingsCategory::Sandbox,
+            has_override: o_hp,
+            inherited_display: inherited_if(
+                o_hp,
+                FieldValue::Select {
+                    selected: host_profile_index(global.sandbox.host_profile),
+                    options: host_profile_options,
+                },
+            ),
+        },
+        SettingField {
+            key: FieldKey::HostBlockNetwork,
+            label: "Host Sandbox: Block Network",
+            description: "Block network access for host-sandboxed sessions",
+            value: FieldValue::Bool(host_block_network),
+            category: SettingsCategory::Sandbox,
+            has_override: o_hn,
+            inherited_display: inherited_if(
+                o_hn,
+                FieldValue::Bool(global.sandbox.host_block_network),
+            ),
+        },
     ]
 }
 
+fn host_profile_index(profile: HostSandboxProfile) -> usize {
+    match profile {
+        HostSandboxProfile::Off => 0,
+        HostSandboxProfile::Workdir => 1,
+        HostSandboxProfile::Isolated => 2,
+    }
+}
+
+fn host_profile_from_index(selected: usize) -> HostSandboxProfile {
+    match selected {
+        1 => HostSandboxProfile::Workdir,
+        2 => HostSandboxProfile::Isolated,
+        _ => HostSandboxProfile::Off,
+    }
+}
+
 fn build_tmux_fields(
     scope: SettingsScope,
     global: &Config,
@@ -742,6 +1392,18 @@ fn build_tmux_fields(
     };
     let tmux_options = vec!["Auto".into(), "Enabled".into(), "Disabled".into()];
 
+    let (window_size, window_size_override) = resolve_value(
+        scope,
+        global.tmux.window_size,
+        tmux.and_then(|t| t.window_size),
+    );
+    let window_size_index = |size: TmuxWindowSize| match size {
+        TmuxWindowSize::Latest => 0,
+        TmuxWindowSize::Largest => 1,
+        TmuxWindowSize::Manual => 2,
+    };
+    let window_size_options = vec!["Latest".into(), "Largest".into(), "Manual".into()];
+
     vec![
         SettingField {
             key: FieldKey::StatusBar,
@@ -779,9 +1441,35 @@ fn build_tmux_fields(
                 },
             ),
         },
+        SettingField {
+            key: FieldKey::WindowSize,
+            label: "Window Size",
+            description: "Which attached client sizes a session's window; it returns to its original size when all detach",
+            value: FieldValue::Select {
+                selected: window_size_index(window_size),
+                options: window_size_options.clone(),
+            },
+            category: SettingsCategory::Tmux,
+            has_override: window_size_override,
+            inherited_display: inherited_if(
+                window_size_override,
+                FieldValue::Select {
+                    selected: window_size_index(global.tmux.window_size),
+                    options: window_size_options,
+                },
+            ),
+        },
     ]
 }
 
+fn window_size_from_index(index: usize) -> TmuxWindowSize {
+    match index {
+        0 => TmuxWindowSize::Latest,
+        1 => TmuxWindowSize::Largest,
+        _ => TmuxWindowSize::Manual,
+    }
+}
+
 fn build_session_fields(
     scope: SettingsScope,
     global: &Config,
@@ -801,6 +1489,18 @@ fn build_session_fields(
     let mut options = vec!["Auto (first available)".to_string()];
     options.extend(crate::agents::agent_names().iter().map(|n| n.to_string()));
 
+    let (fallback_tool, fallback_override) = resolve_optional(
+        scope,
+        global.session.fallback_tool.clone(),
+        session.and_then(|s| s.fallback_tool.clone()),
+        session.map(|s| s.fallback_tool.is_some()).unwrap_or(false),
+    );
+    let fallback_selected = crate::agents::settings_index_from_name(fallback_tool.as_deref());
+    let global_fallback_selected =
+        crate::agents::settings_index_from_name(global.session.fallback_tool.as_deref());
+    let mut fallback_options = vec!["None".to_string()];
+    fallback_options.extend(crate::agents::agent_names().iter().map(|n| n.to_string()));
+
     let (yolo_mode_default, yolo_override) = resolve_value(
         scope,
         global.session.yolo_mode_default,
@@ -837,6 +1537,133 @@ fn build_session_fields(
         items
     };
 
+    let (trash_retention_days, trash_days_override) = resolve_value(
+        scope,
+        global.session.trash_retention_days,
+        session.and_then(|s| s.trash_retention_days),
+    );
+    let (trash_max_entries, trash_max_override) = resolve_value(
+        scope,
+        global.session.trash_max_entries,
+        session.and_then(|s| s.trash_max_entries),
+    );
+    let (locks, locks_override) = resolve_value(
+        scope,
+        global.session.locks.clone(),
+        session.and_then(|s| s.locks.clone()),
+    );
+    let (guardrails, guardrails_override) = resolve_value(
+        scope,
+        global.session.prompt_guardrails.clone(),
+        session.and_then(|s| s.prompt_guardrails.clone()),
+    );
+    let (nice, nice_override) =
+        resolve_value(scope, global.session.nice, session.and_then(|s| s.nice));
+    let (io_priority, io_override) = resolve_value(
+        scope,
+        global.session.io_priority,
+        session.and_then(|s| s.io_priority),
+    );
+    let io_options = vec!["Normal".to_string(), "Low".to_string(), "Idle".to_string()];
+    let (memory_limit, memory_override) = resolve_optional(
+        scope,
+        global.session.memory_limit.clone(),
+        session.and_then(|s| s.memory_limit.clone()),
+        session.map(|s| s.memory_limit.is_some()).unwrap_or(false),
+    );
+    let (cpu_limit, cpu_override) = resolve_optional(
+        scope,
+        global.session.cpu_limit.clone(),
+        session.and_then(|s| s.cpu_limit.clone()),
+        session.map(|s| s.cpu_limit.is_some()).unwrap_or(false),
+    );
+    let (proxy, proxy_override) = resolve_optional(
+        scope,
+        global.session.proxy.clone(),
+        session.and_then(|s| s.proxy.clone()),
+        session.map(|s| s.proxy.is_some()).unwrap_or(false),
+    );
+    let (no_proxy, no_proxy_override) = resolve_optional(
+        scope,
+        global.session.no_proxy.clone(),
+        session.and_then(|s| s.no_proxy.clone()),
+        session.map(|s| s.no_proxy.is_some()).unwrap_or(false),
+    );
+    let (icon, icon_override) = resolve_optional(
+        scope,
+        global.session.icon.clone(),
+        session.and_then(|s| s.icon.clone()),
+        session.map(|s| s.icon.is_some()).unwrap_or(false),
+    );
+
+    let (daily_budget, daily_budget_override) = resolve_value(
+        scope,
+        global.session.daily_budget,
+        session.and_then(|s| s.daily_budget),
+    );
+    let (weekly_budget, weekly_budget_override) = resolve_value(
+        scope,
+        global.session.weekly_budget,
+        session.and_then(|s| s.weekly_budget),
+    );
+    let (budget_warn_percent, budget_warn_override) = resolve_value(
+        scope,
+        global.session.budget_warn_percent,
+        session.and_then(|s| s.budget_warn_percent),
+    );
+    let (budget_action, budget_action_override) = resolve_value(
+        scope,
+        global.session.budget_action,
+        session.and_then(|s| s.budget_action),
+    );
+    let budget_action_options = vec!["Warn".to_string(), "Block".to_string()];
+
+    // Credential expiry: HashMap -> Vec<String> of "agent=date" items
+    let (credential_expiry_map, credential_expiry_override) = resolve_value(
+        scope,
+        global.session.credential_expiry.clone(),
+        session.and_then(|s| s.credential_expiry.clone()),
+    );
+    let key_value_items = |map: &std::collections::HashMap<String, String>| -> Vec<String> {
+        let mut items: Vec<_> = map.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        items.sort();
+        items
+    };
+    // Provider limits: HashMap -> Vec<String> of "provider=count" items
+    let (provider_limits_map, provider_limits_override) = resolve_value(
+        scope,
+        global.session.provider_limits.clone(),
+        session.and_then(|s| s.provider_limits.clone()),
+    );
+    let limit_items = |map: &std::collections::HashMap<String, usize>| -> Vec<String> {
+        let mut items: Vec<_> = map.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        items.sort();
+        items
+    };
+    let (credential_warn_days, credential_warn_override) = resolve_value(
+        scope,
+        global.session.credential_warn_days,
+        session.and_then(|s| s.credential_warn_days),
+    );
+    let (credential_refresh, credential_refresh_override) = resolve_optional(
+        scope,
+        global.session.credential_refresh_command.clone(),
+        session.and_then(|s| s.credential_refresh_command.clone()),
+        session
+            .map(|s| s.credential_refresh_command.is_some())
+            .unwrap_or(false),
+    );
+    let (agent_shim, agent_shim_override) = resolve_value(
+        scope,
+        global.session.agent_shim,
+        session.and_then(|s| s.agent_shim),
+    );
+    let (project_env, project_env_override) = resolve_value(
+        scope,
+        global.session.project_env,
+        session.and_then(|s| s.project_env),
+    );
+
     let global_tool_selected =
         crate::agents::settings_index_from_name(global.session.default_tool.as_deref());
 
@@ -880,6 +1707,24 @@ fn build_session_fields(
                 },
             ),
         },
+        SettingField {
+            key: FieldKey::FallbackTool,
+            label: "Fallback Tool",
+            description: "Agent to switch to when a session's agent fails to start or hits a usage limit",
+            value: FieldValue::Select {
+                selected: fallback_selected,
+                options: fallback_options.clone(),
+            },
+            category: SettingsCategory::Session,
+            has_override: fallback_override,
+            inherited_display: inherited_if(
+                fallback_override,
+                FieldValue::Select {
+                    selected: global_fallback_selected,
+                    options: fallback_options,
+                },
+            ),
+        },
         SettingField {
             key: FieldKey::YoloModeDefault,
             label: "YOLO Mode Default",
@@ -916,9 +1761,291 @@ fn build_session_fields(
                 FieldValue::List(global_cmd_override_list),
             ),
         },
+        SettingField {
+            key: FieldKey::TrashRetentionDays,
+            label: "Trash Retention (days)",
+            description: "Days removed sessions stay restorable with `aoe trash restore` (0 deletes immediately)",
+            value: FieldValue::Number(trash_retention_days),
+            category: SettingsCategory::Session,
+            has_override: trash_days_override,
+            inherited_display: inherited_if(
+                trash_days_override,
+                FieldValue::Number(global.session.trash_retention_days),
+            ),
+        },
+        SettingField {
+            key: FieldKey::TrashMaxEntries,
+            label: "Trash Max Sessions",
+            description: "Maximum removed sessions kept in the trash, oldest purged first (0 for no limit)",
+            value: FieldValue::Number(trash_max_entries),
+            category: SettingsCategory::Session,
+            has_override: trash_max_override,
+            inherited_display: inherited_if(
+                trash_max_override,
+                FieldValue::Number(global.session.trash_max_entries),
+            ),
+        },
+        SettingField {
+            key: FieldKey::SessionLocks,
+            label: "Session Locks",
+            description: "Named locks new sessions hold; sessions sharing a lock run one at a time",
+            value: FieldValue::List(locks),
+            category: SettingsCategory::Session,
+            has_override: locks_override,
+            inherited_display: inherited_if(
+                locks_override,
+                FieldValue::List(global.session.locks.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::PromptGuardrails,
+            label: "Prompt Guardrails",
+            description: "Regex patterns (case-insensitive) checked before a prompt is sent; a match asks for confirmation",
+            value: FieldValue::List(guardrails),
+            category: SettingsCategory::Session,
+            has_override: guardrails_override,
+            inherited_display: inherited_if(
+                guardrails_override,
+                FieldValue::List(global.session.prompt_guardrails.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::Nice,
+            label: "Niceness",
+            description: "CPU niceness (0-19) for host sessions; higher leaves more CPU to other programs",
+            value: FieldValue::Number(u64::from(nice)),
+            category: SettingsCategory::Session,
+            has_override: nice_override,
+            inherited_display: inherited_if(
+                nice_override,
+                FieldValue::Number(u64::from(global.session.nice)),
+            ),
+        },
+        SettingField {
+            key: FieldKey::IoPriority,
+            label: "IO Priority",
+            description: "Disk priority for host sessions (ionice on Linux, taskpolicy on macOS)",
+            value: FieldValue::Select {
+                selected: io_priority_index(io_priority),
+                options: io_options.clone(),
+            },
+            category: SettingsCategory::Session,
+            has_override: io_override,
+            inherited_display: inherited_if(
+                io_override,
+                FieldValue::Select {
+                    selected: io_priority_index(global.session.io_priority),
+                    options: io_options,
+                },
+            ),
+        },
+        SettingField {
+            key: FieldKey::SessionMemoryLimit,
+            label: "Memory Limit",
+            description: "Memory cap for a host session's processes (Linux, e.g. \"4G\")",
+            value: FieldValue::OptionalText(memory_limit),
+            category: SettingsCategory::Session,
+            has_override: memory_override,
+            inherited_display: inherited_if(
+                memory_override,
+                FieldValue::OptionalText(global.session.memory_limit.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::SessionCpuLimit,
+            label: "CPU Limit",
+            description: "CPU cap for a host session's processes in CPUs (Linux, e.g. \"2\")",
+            value: FieldValue::OptionalText(cpu_limit),
+            category: SettingsCategory::Session,
+            has_override: cpu_override,
+            inherited_display: inherited_if(
+                cpu_override,
+                FieldValue::OptionalText(global.session.cpu_limit.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::SessionProxy,
+            label: "Proxy",
+            description: "Proxy for the agent's traffic (e.g. \"http://proxy.corp:3128\"), checked when a session is created",
+            value: FieldValue::OptionalText(proxy),
+            category: SettingsCategory::Session,
+            has_override: proxy_override,
+            inherited_display: inherited_if(
+                proxy_override,
+                FieldValue::OptionalText(global.session.proxy.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::SessionNoProxy,
+            label: "No Proxy",
+            description: "Comma-separated hosts reached without the proxy (e.g. \"localhost,.corp\")",
+            value: FieldValue::OptionalText(no_proxy),
+            category: SettingsCategory::Session,
+            has_override: no_proxy_override,
+            inherited_display: inherited_if(
+                no_proxy_override,
+                FieldValue::OptionalText(global.session.no_proxy.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::SessionIcon,
+            label: "Icon",
+            description: "Emoji or nerd-font glyph shown before new sessions' titles and tmux window names",
+            value: FieldValue::OptionalText(icon),
+            category: SettingsCategory::Session,
+            has_override: icon_override,
+            inherited_display: inherited_if(
+                icon_override,
+                FieldValue::OptionalText(global.session.icon.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::DailyBudget,
+            label: "Daily Budget ($)",
+            description: "Estimated agent spend allowed per day, in dollars (0 for no limit)",
+            value: FieldValue::Number(daily_budget),
+            category: SettingsCategory::Session,
+            has_override: daily_budget_override,
+            inherited_display: inherited_if(
+                daily_budget_override,
+                FieldValue::Number(global.session.daily_budget),
+            ),
+        },
+        SettingField {
+            key: FieldKey::WeeklyBudget,
+            label: "Weekly Budget ($)",
+            description: "Estimated agent spend allowed per week from Monday, in dollars (0 for no limit)",
+            value: FieldValue::Number(weekly_budget),
+            category: SettingsCategory::Session,
+            has_override: weekly_budget_override,
+            inherited_display: inherited_if(
+                weekly_budget_override,
+                FieldValue::Number(global.session.weekly_budget),
+            ),
+        },
+        SettingField {
+            key: FieldKey::BudgetWarnPercent,
+            label: "Budget Warning (%)",
+            description: "Share of a budget spent at which a warning is shown",
+            value: FieldValue::Number(budget_warn_percent),
+            category: SettingsCategory::Session,
+            has_override: budget_warn_override,
+            inherited_display: inherited_if(
+                budget_warn_override,
+                FieldValue::Number(global.session.budget_warn_percent),
+            ),
+        },
+        SettingField {
+            key: FieldKey::BudgetAction,
+            label: "Over Budget",
+            description: "Warn only, or also block new sessions and hold queued ones until the budget resets",
+            value: FieldValue::Select {
+                selected: budget_action_index(budget_action),
+                options: budget_action_options.clone(),
+            },
+            category: SettingsCategory::Session,
+            has_override: budget_action_override,
+            inherited_display: inherited_if(
+                budget_action_override,
+                FieldValue::Select {
+                    selected: budget_action_index(global.session.budget_action),
+                    options: budget_action_options,
+                },
+            ),
+        },
+        SettingField {
+            key: FieldKey::ProviderLimits,
+            label: "Provider Limits",
+            description: "Most sessions of a provider active at once; more are queued (provider=count, e.g. anthropic=3)",
+            value: FieldValue::List(limit_items(&provider_limits_map)),
+            category: SettingsCategory::Session,
+            has_override: provider_limits_override,
+            inherited_display: inherited_if(
+                provider_limits_override,
+                FieldValue::List(limit_items(&global.session.provider_limits)),
+            ),
+        },
+        SettingField {
+            key: FieldKey::CredentialExpiry,
+            label: "Credential Expiry",
+            description: "When each agent's API key expires (agent=date, e.g. claude=2026-03-31)",
+            value: FieldValue::List(key_value_items(&credential_expiry_map)),
+            category: SettingsCategory::Session,
+            has_override: credential_expiry_override,
+            inherited_display: inherited_if(
+                credential_expiry_override,
+                FieldValue::List(key_value_items(&global.session.credential_expiry)),
+            ),
+        },
+        SettingField {
+            key: FieldKey::CredentialWarnDays,
+            label: "Credential Warning (days)",
+            description: "Days before a credential expires that a warning banner is shown",
+            value: FieldValue::Number(credential_warn_days),
+            category: SettingsCategory::Session,
+            has_override: credential_warn_override,
+            inherited_display: inherited_if(
+                credential_warn_override,
+                FieldValue::Number(global.session.credential_warn_days),
+            ),
+        },
+        SettingField {
+            key: FieldKey::CredentialRefreshCommand,
+            label: "Credential Refresh Command",
+            description: "Shell command run once when a credential nears expiry (gets AOE_AGENT, AOE_CREDENTIAL_EXPIRES)",
+            value: FieldValue::OptionalText(credential_refresh),
+            category: SettingsCategory::Session,
+            has_override: credential_refresh_override,
+            inherited_display: inherited_if(
+                credential_refresh_override,
+                FieldValue::OptionalText(global.session.credential_refresh_command.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::AgentShim,
+            label: "Agent Shim",
+            description: "Launch host agents through aoe shim, which detects busy agents and exits without reading the pane",
+            value: FieldValue::Bool(agent_shim),
+            category: SettingsCategory::Session,
+            has_override: agent_shim_override,
+            inherited_display: inherited_if(
+                agent_shim_override,
+                FieldValue::Bool(global.session.agent_shim),
+            ),
+        },
+        SettingField {
+            key: FieldKey::ProjectEnv,
+            label: "Project Environment",
+            description: "Start host agents with the project's direnv (.envrc) or mise environment",
+            value: FieldValue::Bool(project_env),
+            category: SettingsCategory::Session,
+            has_override: project_env_override,
+            inherited_display: inherited_if(
+                project_env_override,
+                FieldValue::Bool(global.session.project_env),
+            ),
+        },
     ]
 }
 
+const BUDGET_ACTIONS: [BudgetAction; 2] = [BudgetAction::Warn, BudgetAction::Block];
+
+fn budget_action_index(action: BudgetAction) -> usize {
+    BUDGET_ACTIONS
+        .iter()
+        .position(|a| *a == action)
+        .unwrap_or(0)
+}
+
+const IO_PRIORITIES: [IoPriority; 3] = [IoPriority::Normal, IoPriority::Low, IoPriority::Idle];
+
+fn io_priority_index(priority: IoPriority) -> usize {
+    IO_PRIORITIES
+        .iter()
+        .position(|p| *p == priority)
+        .unwrap_or(0)
+}
+
 fn build_sound_fields(
     scope: SettingsScope,
     global: &Config,
@@ -969,6 +2096,12 @@ fn build_sound_fields(
         snd.and_then(|s| s.on_error.clone()),
         snd.map(|s| s.on_error.is_some()).unwrap_or(false),
     );
+    let (on_finished, o8) = resolve_optional(
+        scope,
+        global.sound.on_finished.clone(),
+        snd.and_then(|s| s.on_finished.clone()),
+        snd.map(|s| s.on_finished.is_some()).unwrap_or(false),
+    );
 
     let global_mode_selected = match &global.sound.mode {
         SoundMode::Random => 0,
@@ -1064,6 +2197,240 @@ fn build_sound_fields(
                 FieldValue::OptionalText(global.sound.on_error.clone()),
             ),
         },
+        SettingField {
+            key: FieldKey::SoundOnFinished,
+            label: "On Finished",
+            description: "Played when an agent finishes its work; On Idle is used if unset",
+            value: FieldValue::OptionalText(on_finished),
+            category: SettingsCategory::Sound,
+            has_override: o8,
+            inherited_display: inherited_if(
+                o8,
+                FieldValue::OptionalText(global.sound.on_finished.clone()),
+            ),
+        },
+    ]
+}
+
+fn build_notification_fields(
+    scope: SettingsScope,
+    global: &Config,
+    profile: &ProfileConfig,
+) -> Vec<SettingField> {
+    let n = profile.notifications.as_ref();
+
+    let (rules, o1) = resolve_value(
+        scope,
+        global.notifications.rules.clone(),
+        n.and_then(|n| n.rules.clone()),
+    );
+    let (webhook_url, o2) = resolve_optional(
+        scope,
+        global.notifications.webhook_url.clone(),
+        n.and_then(|n| n.webhook_url.clone()),
+        n.is_some_and(|n| n.webhook_url.is_some()),
+    );
+    let (tray, o3) = resolve_value(scope, global.notifications.tray, n.and_then(|n| n.tray));
+    let (tray_focus_command, o4) = resolve_optional(
+        scope,
+        global.notifications.tray_focus_command.clone(),
+        n.and_then(|n| n.tray_focus_command.clone()),
+        n.is_some_and(|n| n.tray_focus_command.is_some()),
+    );
+    let (digest, o5) = resolve_value(scope, global.notifications.digest, n.and_then(|n| n.digest));
+    let (digest_path, o6) = resolve_optional(
+        scope,
+        global.notifications.digest_path.clone(),
+        n.and_then(|n| n.digest_path.clone()),
+        n.is_some_and(|n| n.digest_path.is_some()),
+    );
+    let digest_options = vec!["Off".to_string(), "Webhook".to_string(), "File".to_string()];
+
+    vec![
+        SettingField {
+            key: FieldKey::NotificationRules,
+            label: "Routing Rules",
+            description: "First match wins, e.g. \"error tag:prod -> webhook\" or \"hours:09:00-18:00 days:mon-fri -> desktop\"",
+            value: FieldValue::List(rules),
+            category: SettingsCategory::Notifications,
+            has_override: o1,
+            inherited_display: inherited_if(
+                o1,
+                FieldValue::List(global.notifications.rules.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::NotificationWebhookUrl,
+            label: "Webhook URL",
+            description: "URL the webhook channel POSTs a JSON event to",
+            value: FieldValue::OptionalText(webhook_url),
+            category: SettingsCategory::Notifications,
+            has_override: o2,
+            inherited_display: inherited_if(
+                o2,
+                FieldValue::OptionalText(global.notifications.webhook_url.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::NotificationTray,
+            label: "Tray Icon",
+            description: "Show a tray icon counting sessions that need attention (Linux, needs yad)",
+            value: FieldValue::Bool(tray),
+            category: SettingsCategory::Notifications,
+            has_override: o3,
+            inherited_display: inherited_if(o3, FieldValue::Bool(global.notifications.tray)),
+        },
+        SettingField {
+            key: FieldKey::NotificationTrayFocusCommand,
+            label: "Tray Focus Command",
+            description: "Run when the tray icon is clicked; detected from the terminal if unset",
+            value: FieldValue::OptionalText(tray_focus_command),
+            category: SettingsCategory::Notifications,
+            has_override: o4,
+            inherited_display: inherited_if(
+                o4,
+                FieldValue::OptionalText(global.notifications.tray_focus_command.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::NotificationDigest,
+            label: "Weekly Digest",
+            description: "Deliver last week's sessions, success rate, and spend on startup: to the webhook or a file",
+            value: FieldValue::Select {
+                selected: digest_delivery_index(digest),
+                options: digest_options.clone(),
+            },
+            category: SettingsCategory::Notifications,
+            has_override: o5,
+            inherited_display: inherited_if(
+                o5,
+                FieldValue::Select {
+                    selected: digest_delivery_index(global.notifications.digest),
+                    options: digest_options,
+                },
+            ),
+        },
+        SettingField {
+            key: FieldKey::NotificationDigestPath,
+            label: "Digest File",
+            description: "Where the file digest is written, {week} becomes the week's Monday (default: app dir digests/{week}.md)",
+            value: FieldValue::OptionalText(digest_path),
+            category: SettingsCategory::Notifications,
+            has_override: o6,
+            inherited_display: inherited_if(
+                o6,
+                FieldValue::OptionalText(global.notifications.digest_path.clone()),
+            ),
+        },
+    ]
+}
+
+const DIGEST_DELIVERIES: [DigestDelivery; 3] = [
+    DigestDelivery::Off,
+    DigestDelivery::Webhook,
+    DigestDelivery::File,
+];
+
+fn digest_delivery_index(delivery: DigestDelivery) -> usize {
+    DIGEST_DELIVERIES
+        .iter()
+        .position(|d| *d == delivery)
+        .unwrap_or(0)
+}
+
+const SHARE_DESTINATIONS: [ShareDestination; 3] = [
+    ShareDestination::None,
+    ShareDestination::Gist,
+    ShareDestination::S3,
+];
+
+fn share_destination_index(destination: ShareDestination) -> usize {
+    SHARE_DESTINATIONS
+        .iter()
+        .position(|d| *d == destination)
+        .unwrap_or(0)
+}
+
+fn build_share_fields(
+    scope: SettingsScope,
+    global: &Config,
+    profile: &ProfileConfig,
+) -> Vec<SettingField> {
+    let share = profile.share.as_ref();
+
+    let (destination, o1) = resolve_value(
+        scope,
+        global.share.destination,
+        share.and_then(|s| s.destination),
+    );
+    let (s3_bucket, o2) = resolve_optional(
+        scope,
+        global.share.s3_bucket.clone(),
+        share.and_then(|s| s.s3_bucket.clone()),
+        share.is_some_and(|s| s.s3_bucket.is_some()),
+    );
+    let (s3_link_hours, o3) = resolve_value(
+        scope,
+        global.share.s3_link_hours,
+        share.and_then(|s| s.s3_link_hours),
+    );
+    let (gist_public, o4) = resolve_value(
+        scope,
+        global.share.gist_public,
+        share.and_then(|s| s.gist_public),
+    );
+    let destination_options = vec!["None".to_string(), "Gist".to_string(), "S3".to_string()];
+
+    vec![
+        SettingField {
+            key: FieldKey::ShareDestination,
+            label: "Upload To",
+            description:
+                "Where `aoe share --upload` sends a bundle: a GitHub gist (gh) or S3 (aws)",
+            value: FieldValue::Select {
+                selected: share_destination_index(destination),
+                options: destination_options.clone(),
+            },
+            category: SettingsCategory::Share,
+            has_override: o1,
+            inherited_display: inherited_if(
+                o1,
+                FieldValue::Select {
+                    selected: share_destination_index(global.share.destination),
+                    options: destination_options,
+                },
+            ),
+        },
+        SettingField {
+            key: FieldKey::ShareS3Bucket,
+            label: "S3 Bucket",
+            description: "Bucket and optional key prefix for S3 uploads, e.g. my-bucket/aoe",
+            value: FieldValue::OptionalText(s3_bucket),
+            category: SettingsCategory::Share,
+            has_override: o2,
+            inherited_display: inherited_if(
+                o2,
+                FieldValue::OptionalText(global.share.s3_bucket.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::ShareS3LinkHours,
+            label: "S3 Link Hours",
+            description: "Hours a presigned S3 link stays valid (at most 168)",
+            value: FieldValue::Number(s3_link_hours),
+            category: SettingsCategory::Share,
+            has_override: o3,
+            inherited_display: inherited_if(o3, FieldValue::Number(global.share.s3_link_hours)),
+        },
+        SettingField {
+            key: FieldKey::ShareGistPublic,
+            label: "Public Gists",
+            description: "Create public gists instead of secret ones",
+            value: FieldValue::Bool(gist_public),
+            category: SettingsCategory::Share,
+            has_override: o4,
+            inherited_display: inherited_if(o4, FieldValue::Bool(global.share.gist_public)),
+        },
     ]
 }
 
@@ -1085,6 +2452,24 @@ fn build_hooks_fields(
         hooks.and_then(|h| h.on_launch.clone()),
     );
 
+    let (health_check, o3) = resolve_optional(
+        scope,
+        global.hooks.health_check.clone(),
+        hooks.and_then(|h| h.health_check.clone()),
+        hooks.is_some_and(|h| h.health_check.is_some()),
+    );
+    let (health_check_interval, o4) = resolve_value(
+        scope,
+        global.hooks.health_check_interval_secs,
+        hooks.and_then(|h| h.health_check_interval_secs),
+    );
+    let (test_watch, o5) = resolve_optional(
+        scope,
+        global.hooks.test_watch.clone(),
+        hooks.and_then(|h| h.test_watch.clone()),
+        hooks.is_some_and(|h| h.test_watch.is_some()),
+    );
+
     vec![
         SettingField {
             key: FieldKey::HookOnCreate,
@@ -1110,6 +2495,42 @@ fn build_hooks_fields(
                 FieldValue::List(global.hooks.on_launch.clone()),
             ),
         },
+        SettingField {
+            key: FieldKey::HookHealthCheck,
+            label: "Health Check",
+            description: "Probe run periodically in the session directory (e.g. curl -fs localhost:3000/health). Runs on the host.",
+            value: FieldValue::OptionalText(health_check),
+            category: SettingsCategory::Hooks,
+            has_override: o3,
+            inherited_display: inherited_if(
+                o3,
+                FieldValue::OptionalText(global.hooks.health_check.clone()),
+            ),
+        },
+        SettingField {
+            key: FieldKey::HookHealthCheckInterval,
+            label: "Health Check Interval (secs)",
+            description: "How often to run the health check",
+            value: FieldValue::Number(health_check_interval),
+            category: SettingsCategory::Hooks,
+            has_override: o4,
+            inherited_display: inherited_if(
+                o4,
+                FieldValue::Number(global.hooks.health_check_interval_secs),
+            ),
+        },
+        SettingField {
+            key: FieldKey::HookTestWatch,
+            label: "Test Watch",
+            description: "Test watcher kept running beside each session (e.g. cargo watch -c -x test); its pass/fail counts show in the list",
+            value: FieldValue::OptionalText(test_watch),
+            category: SettingsCategory::Hooks,
+            has_override: o5,
+            inherited_display: inherited_if(
+                o5,
+                FieldValue::OptionalText(global.hooks.test_watch.clone()),
+            ),
+        },
     ]
 }
 
@@ -1135,12 +2556,37 @@ fn apply_field_to_global(field: &SettingField, config: &mut Config) {
         (FieldKey::ThemeName, FieldValue::Select { selected, options }) => {
             config.theme.name = options.get(*selected).cloned().unwrap_or_default();
         }
+        (FieldKey::RowFormat, FieldValue::OptionalText(v)) => {
+            config.theme.row_format = v.clone();
+        }
+        (FieldKey::ColorVision, FieldValue::Select { selected, .. }) => {
+            config.theme.color_vision = COLOR_VISIONS.get(*selected).copied().unwrap_or_default();
+        }
+        (FieldKey::Hyperlinks, FieldValue::Select { selected, .. }) => {
+            config.theme.hyperlinks = HYPERLINK_MODES.get(*selected).copied().unwrap_or_default();
+        }
+        (FieldKey::Spellcheck, FieldValue::Bool(v)) => config.theme.spellcheck = *v,
+        (FieldKey::PreviewMinWidth, FieldValue::Number(v)) => {
+            config.theme.preview_min_width = (*v).min(u16::MAX as u64) as u16
+        }
+        (FieldKey::RowDetailsMinWidth, FieldValue::Number(v)) => {
+            config.theme.row_details_min_width = (*v).min(u16::MAX as u64) as u16
+        }
+        (FieldKey::TerminalTitle, FieldValue::Text(v)) => config.theme.terminal_title = v.clone(),
         // Updates
         (FieldKey::CheckEnabled, FieldValue::Bool(v)) => config.updates.check_enabled = *v,
         (FieldKey::CheckIntervalHours, FieldValue::Number(v)) => {
             config.updates.check_interval_hours = *v
         }
         (FieldKey::NotifyInCli, FieldValue::Bool(v)) => config.updates.notify_in_cli = *v,
+        (FieldKey::CheckAgents, FieldValue::Bool(v)) => config.updates.check_agents = *v,
+        // Polling
+        (FieldKey::PaneCaptureMs, FieldValue::Number(v)) => config.polling.pane_capture_ms = *v,
+        (FieldKey::GitCheckSecs, FieldValue::Number(v)) => config.polling.git_check_secs = *v,
+        (FieldKey::ResourceUsageSecs, FieldValue::Number(v)) => {
+            config.polling.resource_usage_secs = *v
+        }
+        (FieldKey::EcoMode, FieldValue::Bool(v)) => config.polling.eco_mode = *v,
         // Worktree
         (FieldKey::PathTemplate, FieldValue::Text(v)) => config.worktree.path_template = v.clone(),
         (FieldKey::BareRepoPathTemplate, FieldValue::Text(v)) => {
@@ -1153,6 +2599,32 @@ fn apply_field_to_global(field: &SettingField, config: &mut Config) {
         (FieldKey::WorkspacePathTemplate, FieldValue::Text(v)) => {
             config.worktree.workspace_path_template = v.clone()
         }
+        (FieldKey::WorkspaceRepos, FieldValue::List(v)) => config.worktree.repos = v.clone(),
+        (FieldKey::SparsePaths, FieldValue::List(v)) => config.worktree.sparse_paths = v.clone(),
+        (FieldKey::MergeTestCommand, FieldValue::OptionalText(v)) => {
+            config.worktree.merge_test_command = v.clone()
+        }
+        (FieldKey::SyncNotes, FieldValue::Bool(v)) => config.worktree.sync_notes = *v,
+        (FieldKey::OnMerged, FieldValue::Select { selected, .. }) => {
+            config.worktree.on_merged = MERGED_ACTIONS.get(*selected).copied().unwrap_or_default();
+        }
+        (FieldKey::BranchTagPattern, FieldValue::Text(v)) => {
+            config.worktree.branch_tag_pattern = v.clone()
+        }
+        (FieldKey::BranchTicketPattern, FieldValue::Text(v)) => {
+            config.worktree.branch_ticket_pattern = v.clone()
+        }
+        (FieldKey::TicketUrlTemplate, FieldValue::Text(v)) => {
+            config.worktree.ticket_url_template = v.clone()
+        }
+        (FieldKey::WorktreePoolSize, FieldValue::Number(v)) => {
+            config.worktree.pool_size = *v as usize
+        }
+        (FieldKey::CloneDir, FieldValue::Text(v)) => config.worktree.clone_dir = v.clone(),
+        (FieldKey::ShallowClone, FieldValue::Bool(v)) => config.worktree.shallow_clone = *v,
+        (FieldKey::ConflictPrompt, FieldValue::Text(v)) => {
+            config.worktree.conflict_prompt = v.clone()
+        }
         // Sandbox
         (FieldKey::SandboxEnabledByDefault, FieldValue::Bool(v)) => {
             config.sandbox.enabled_by_default = *v
@@ -1183,9 +2655,16 @@ fn apply_field_to_global(field: &SettingField, config: &mut Config) {
         (FieldKey::ContainerRuntime, FieldValue::Select { selected, .. }) => {
             config.sandbox.container_runtime = match selected {
                 0 => ContainerRuntimeName::Docker,
-                _ => ContainerRuntimeName::AppleContainer,
+                1 => ContainerRuntimeName::AppleContainer,
+                _ => ContainerRuntimeName::Podman,
             };
         }
+        (FieldKey::HostSandboxProfile, FieldValue::Select { selected, .. }) => {
+            config.sandbox.host_profile = host_profile_from_index(*selected);
+        }
+        (FieldKey::HostBlockNetwork, FieldValue::Bool(v)) => {
+            config.sandbox.host_block_network = *v;
+        }
         // Tmux
         (FieldKey::StatusBar, FieldValue::Select { selected, .. }) => {
             config.tmux.status_bar = match selected {
@@ -1201,17 +2680,74 @@ fn apply_field_to_global(field: &SettingField, config: &mut Config) {
                 _ => TmuxMouseMode::Disabled,
             };
         }
+        (FieldKey::WindowSize, FieldValue::Select { selected, .. }) => {
+            config.tmux.window_size = window_size_from_index(*selected);
+        }
         // Session
         (FieldKey::DefaultTool, FieldValue::Select { selected, .. }) => {
             config.session.default_tool =
                 crate::agents::name_from_settings_index(*selected).map(|s| s.to_string());
         }
+        (FieldKey::FallbackTool, FieldValue::Select { selected, .. }) => {
+            config.session.fallback_tool =
+                crate::agents::name_from_settings_index(*selected).map(|s| s.to_string());
+        }
         (FieldKey::AgentExtraArgs, FieldValue::List(v)) => {
             config.session.agent_extra_args = parse_key_value_list(v);
         }
         (FieldKey::AgentCommandOverride, FieldValue::List(v)) => {
             config.session.agent_command_override = parse_key_value_list(v);
         }
+        (FieldKey::TrashRetentionDays, FieldValue::Number(v)) => {
+            config.session.trash_retention_days = *v
+        }
+        (FieldKey::TrashMaxEntries, FieldValue::Number(v)) => config.session.trash_max_entries = *v,
+        (FieldKey::SessionLocks, FieldValue::List(v)) => config.session.locks = v.clone(),
+        (FieldKey::PromptGuardrails, FieldValue::List(v)) => {
+            config.session.prompt_guardrails = v.clone()
+        }
+        (FieldKey::Nice, FieldValue::Number(v)) => config.session.nice = (*v).min(19) as u8,
+        (FieldKey::IoPriority, FieldValue::Select { selected, .. }) => {
+            config.session.io_priority = IO_PRIORITIES.get(*selected).copied().unwrap_or_default();
+        }
+        (FieldKey::SessionMemoryLimit, FieldValue::OptionalText(v)) => {
+            config.session.memory_limit = v.clone();
+        }
+        (FieldKey::SessionCpuLimit, FieldValue::OptionalText(v)) => {
+            config.session.cpu_limit = v.clone();
+        }
+        (FieldKey::SessionProxy, FieldValue::OptionalText(v)) => {
+            config.session.proxy = v.clone();
+        }
+        (FieldKey::SessionNoProxy, FieldValue::OptionalText(v)) => {
+            config.session.no_proxy = v.clone();
+        }
+        (FieldKey::SessionIcon, FieldValue::OptionalText(v)) => {
+            config.session.icon = v.clone();
+        }
+        (FieldKey::DailyBudget, FieldValue::Number(v)) => config.session.daily_budget = *v,
+        (FieldKey::WeeklyBudget, FieldValue::Number(v)) => config.session.weekly_budget = *v,
+        (FieldKey::BudgetWarnPercent, FieldValue::Number(v)) => {
+            config.session.budget_warn_percent = *v
+        }
+        (FieldKey::BudgetAction, FieldValue::Select { selected, .. }) => {
+            config.session.budget_action =
+                BUDGET_ACTIONS.get(*selected).copied().unwrap_or_default();
+        }
+        (FieldKey::ProviderLimits, FieldValue::List(v)) => {
+            config.session.provider_limits = parse_limit_list(v);
+        }
+        (FieldKey::CredentialExpiry, FieldValue::List(v)) => {
+            config.session.credential_expiry = parse_key_value_list(v);
+        }
+        (FieldKey::CredentialWarnDays, FieldValue::Number(v)) => {
+            config.session.credential_warn_days = *v
+        }
+        (FieldKey::CredentialRefreshCommand, FieldValue::OptionalText(v)) => {
+            config.session.credential_refresh_command = v.clone();
+        }
+        (FieldKey::AgentShim, FieldValue::Bool(v)) => config.session.agent_shim = *v,
+        (FieldKey::ProjectEnv, FieldValue::Bool(v)) => config.session.project_env = *v,
         // Sound
         (FieldKey::SoundEnabled, FieldValue::Bool(v)) => config.sound.enabled = *v,
         (FieldKey::SoundMode, FieldValue::Select { selected, .. }) => {
@@ -1235,9 +2771,53 @@ fn apply_field_to_global(field: &SettingField, config: &mut Config) {
         (FieldKey::SoundOnError, FieldValue::OptionalText(v)) => {
             config.sound.on_error = v.clone();
         }
+        (FieldKey::SoundOnFinished, FieldValue::OptionalText(v)) => {
+            config.sound.on_finished = v.clone();
+        }
+        // Notifications
+        (FieldKey::NotificationRules, FieldValue::List(v)) => {
+            config.notifications.rules = v.clone();
+        }
+        (FieldKey::NotificationWebhookUrl, FieldValue::OptionalText(v)) => {
+            config.notifications.webhook_url = v.clone();
+        }
+        (FieldKey::NotificationTray, FieldValue::Bool(v)) => config.notifications.tray = *v,
+        (FieldKey::NotificationTrayFocusCommand, FieldValue::OptionalText(v)) => {
+            config.notifications.tray_focus_command = v.clone();
+        }
+        (FieldKey::NotificationDigest, FieldValue::Select { selected, .. }) => {
+            config.notifications.digest = DIGEST_DELIVERIES
+                .get(*selected)
+                .copied()
+                .unwrap_or_default();
+        }
+        (FieldKey::NotificationDigestPath, FieldValue::OptionalText(v)) => {
+            config.notifications.digest_path = v.clone();
+        }
+        // Share
+        (FieldKey::ShareDestination, FieldValue::Select { selected, .. }) => {
+            config.share.destination = SHARE_DESTINATIONS
+                .get(*selected)
+                .copied()
+                .unwrap_or_default();
+        }
+        (FieldKey::ShareS3Bucket, FieldValue::OptionalText(v)) => {
+            config.share.s3_bucket = v.clone();
+        }
+        (FieldKey::ShareS3LinkHours, FieldValue::Number(v)) => config.share.s3_link_hours = *v,
+        (FieldKey::ShareGistPublic, FieldValue::Bool(v)) => config.share.gist_public = *v,
         // Hooks
         (FieldKey::HookOnCreate, FieldValue::List(v)) => config.hooks.on_create = v.clone(),
         (FieldKey::HookOnLaunch, FieldValue::List(v)) => config.hooks.on_launch = v.clone(),
+        (FieldKey::HookHealthCheck, FieldValue::OptionalText(v)) => {
+            config.hooks.health_check = v.clone();
+        }
+        (FieldKey::HookHealthCheckInterval, FieldValue::Number(v)) => {
+            config.hooks.health_check_interval_secs = *v
+        }
+        (FieldKey::HookTestWatch, FieldValue::OptionalText(v)) => {
+            config.hooks.test_watch = v.clone();
+        }
         _ => {}
     }
 }
@@ -1255,6 +2835,39 @@ fn apply_field_to_profile(field: &SettingField, _global: &Config, config: &mut P
                 .get_or_insert_with(ThemeConfigOverride::default);
             t.name = Some(name);
         }
+        (FieldKey::RowFormat, FieldValue::OptionalText(v)) => {
+            use crate::session::ThemeConfigOverride;
+            let t = config
+                .theme
+                .get_or_insert_with(ThemeConfigOverride::default);
+            t.row_format = v.clone();
+        }
+        (FieldKey::ColorVision, FieldValue::Select { selected, .. }) => {
+            let vision = COLOR_VISIONS.get(*selected).copied().unwrap_or_default();
+            set_profile_override(vision, &mut config.theme, |t, val| t.color_vision = val);
+        }
+        (FieldKey::Hyperlinks, FieldValue::Select { selected, .. }) => {
+            let mode = HYPERLINK_MODES.get(*selected).copied().unwrap_or_default();
+            set_profile_override(mode, &mut config.theme, |t, val| t.hyperlinks = val);
+        }
+        (FieldKey::Spellcheck, FieldValue::Bool(v)) => {
+            set_profile_override(*v, &mut config.theme, |t, val| t.spellcheck = val);
+        }
+        (FieldKey::PreviewMinWidth, FieldValue::Number(v)) => {
+            let width = (*v).min(u16::MAX as u64) as u16;
+            set_profile_override(width, &mut config.theme, |t, val| t.preview_min_width = val);
+        }
+        (FieldKey::RowDetailsMinWidth, FieldValue::Number(v)) => {
+            let width = (*v).min(u16::MAX as u64) as u16;
+            set_profile_override(width, &mut config.theme, |t, val| {
+                t.row_details_min_width = val
+            });
+        }
+        (FieldKey::TerminalTitle, FieldValue::Text(v)) => {
+            set_profile_override(v.clone(), &mut config.theme, |t, val| {
+                t.terminal_title = val
+            });
+        }
         // Updates
         (FieldKey::CheckEnabled, FieldValue::Bool(v)) => {
             set_profile_override(*v, &mut config.updates, |s, val| s.check_enabled = val);
@@ -1267,6 +2880,24 @@ fn apply_field_to_profile(field: &SettingField, _global: &Config, config: &mut P
         (FieldKey::NotifyInCli, FieldValue::Bool(v)) => {
             set_profile_override(*v, &mut config.updates, |s, val| s.notify_in_cli = val);
         }
+        (FieldKey::CheckAgents, FieldValue::Bool(v)) => {
+            set_profile_override(*v, &mut config.updates, |s, val| s.check_agents = val);
+        }
+        // Polling
+        (FieldKey::PaneCaptureMs, FieldValue::Number(v)) => {
+            set_profile_override(*v, &mut config.polling, |p, val| p.pane_capture_ms = val);
+        }
+        (FieldKey::GitCheckSecs, FieldValue::Number(v)) => {
+            set_profile_override(*v, &mut config.polling, |p, val| p.git_check_secs = val);
+        }
+        (FieldKey::ResourceUsageSecs, FieldValue::Number(v)) => {
+            set_profile_override(*v, &mut config.polling, |p, val| {
+                p.resource_usage_secs = val
+            });
+        }
+        (FieldKey::EcoMode, FieldValue::Bool(v)) => {
+            set_profile_override(*v, &mut config.polling, |p, val| p.eco_mode = val);
+        }
         // Worktree
         (FieldKey::PathTemplate, FieldValue::Text(v)) => {
             set_profile_override(v.clone(), &mut config.worktree, |s, val| {
@@ -1291,6 +2922,59 @@ fn apply_field_to_profile(field: &SettingField, _global: &Config, config: &mut P
                 s.workspace_path_template = val
             });
         }
+        (FieldKey::WorkspaceRepos, FieldValue::List(v)) => {
+            set_profile_override(v.clone(), &mut config.worktree, |s, val| s.repos = val);
+        }
+        (FieldKey::SparsePaths, FieldValue::List(v)) => {
+            set_profile_override(v.clone(), &mut config.worktree, |s, val| {
+                s.sparse_paths = val
+            });
+        }
+        (FieldKey::MergeTestCommand, FieldValue::OptionalText(v)) => {
+            use crate::session::WorktreeConfigOverride;
+            let w = config
+                .worktree
+                .get_or_insert_with(WorktreeConfigOverride::default);
+            w.merge_test_command = v.clone();
+        }
+        (FieldKey::SyncNotes, FieldValue::Bool(v)) => {
+            set_profile_override(*v, &mut config.worktree, |s, val| s.sync_notes = val);
+        }
+        (FieldKey::OnMerged, FieldValue::Select { selected, .. }) => {
+            let action = MERGED_ACTIONS.get(*selected).copied().unwrap_or_default();
+            set_profile_override(action, &mut config.worktree, |s, val| s.on_merged = val);
+        }
+        (FieldKey::BranchTagPattern, FieldValue::Text(v)) => {
+            set_profile_override(v.clone(), &mut config.worktree, |s, val| {
+                s.branch_tag_pattern = val
+            });
+        }
+        (FieldKey::BranchTicketPattern, FieldValue::Text(v)) => {
+            set_profile_override(v.clone(), &mut config.worktree, |s, val| {
+                s.branch_ticket_pattern = val
+            });
+        }
+        (FieldKey::TicketUrlTemplate, FieldValue::Text(v)) => {
+            set_profile_override(v.clone(), &mut config.worktree, |s, val| {
+                s.ticket_url_template = val
+            });
+        }
+        (FieldKey::WorktreePoolSize, FieldValue::Number(v)) => {
+            set_profile_override(*v as usize, &mut config.worktree, |s, val| {
+                s.pool_size = val
+            });
+        }
+        (FieldKey::CloneDir, FieldValue::Text(v)) => {
+            set_profile_override(v.clone(), &mut config.worktree, |s, val| s.clone_dir = val);
+        }
+        (FieldKey::ShallowClone, FieldValue::Bool(v)) => {
+            set_profile_override(*v, &mut config.worktree, |s, val| s.shallow_clone = val);
+        }
+        (FieldKey::ConflictPrompt, FieldValue::Text(v)) => {
+            set_profile_override(v.clone(), &mut config.worktree, |s, val| {
+                s.conflict_prompt = val
+            });
+        }
         // Sandbox
         (FieldKey::SandboxEnabledByDefault, FieldValue::Bool(v)) => {
             set_profile_override(*v, &mut config.sandbox, |s, val| s.enabled_by_default = val);
@@ -1357,12 +3041,23 @@ fn apply_field_to_profile(field: &SettingField, _global: &Config, config: &mut P
         (FieldKey::ContainerRuntime, FieldValue::Select { selected, .. }) => {
             let runtime = match selected {
                 0 => ContainerRuntimeName::Docker,
-                _ => ContainerRuntimeName::AppleContainer,
+                1 => ContainerRuntimeName::AppleContainer,
+                _ => ContainerRuntimeName::Podman,
             };
             set_profile_override(runtime, &mut config.sandbox, |s, val| {
                 s.container_runtime = val
             });
         }
+        (FieldKey::HostSandboxProfile, FieldValue::Select { selected, .. }) => {
+            set_profile_override(
+                host_profile_from_index(*selected),
+                &mut config.sandbox,
+                |s, val| s.host_profile = val,
+            );
+        }
+        (FieldKey::HostBlockNetwork, FieldValue::Bool(v)) => {
+            set_profile_override(*v, &mut config.sandbox, |s, val| s.host_block_network = val);
+        }
         // Tmux
         (FieldKey::StatusBar, FieldValue::Select { selected, .. }) => {
             let mode = match selected {
@@ -1380,6 +3075,10 @@ fn apply_field_to_profile(field: &SettingField, _global: &Config, config: &mut P
             };
             set_profile_override(mode, &mut config.tmux, |s, val| s.mouse = val);
         }
+        (FieldKey::WindowSize, FieldValue::Select { selected, .. }) => {
+            let size = window_size_from_index(*selected);
+            set_profile_override(size, &mut config.tmux, |s, val| s.window_size = val);
+        }
         // Session
         (FieldKey::DefaultTool, FieldValue::Select { selected, .. }) => {
             let tool = crate::agents::name_from_settings_index(*selected).map(|s| s.to_string());
@@ -1389,6 +3088,14 @@ fn apply_field_to_profile(field: &SettingField, _global: &Config, config: &mut P
                 .get_or_insert_with(SessionConfigOverride::default);
             session.default_tool = tool;
         }
+        (FieldKey::FallbackTool, FieldValue::Select { selected, .. }) => {
+            let tool = crate::agents::name_from_settings_index(*selected).map(|s| s.to_string());
+            use crate::session::SessionConfigOverride;
+            let session = config
+                .session
+                .get_or_insert_with(SessionConfigOverride::default);
+            session.fallback_tool = tool;
+        }
         (FieldKey::YoloModeDefault, FieldValue::Bool(v)) => {
             set_profile_override(*v, &mut config.session, |s, val| s.yolo_mode_default = val);
         }
@@ -1408,6 +3115,114 @@ fn apply_field_to_profile(field: &SettingField, _global: &Config, config: &mut P
                 .get_or_insert_with(SessionConfigOverride::default);
             s.agent_command_override = Some(map);
         }
+        (FieldKey::TrashRetentionDays, FieldValue::Number(v)) => {
+            set_profile_override(*v, &mut config.session, |s, val| {
+                s.trash_retention_days = val
+            });
+        }
+        (FieldKey::TrashMaxEntries, FieldValue::Number(v)) => {
+            set_profile_override(*v, &mut config.session, |s, val| s.trash_max_entries = val);
+        }
+        (FieldKey::SessionLocks, FieldValue::List(v)) => {
+            set_profile_override(v.clone(), &mut config.session, |s, val| s.locks = val);
+        }
+        (FieldKey::PromptGuardrails, FieldValue::List(v)) => {
+            set_profile_override(v.clone(), &mut config.session, |s, val| {
+                s.prompt_guardrails = val
+            });
+        }
+        (FieldKey::Nice, FieldValue::Number(v)) => {
+            let nice = (*v).min(19) as u8;
+            set_profile_override(nice, &mut config.session, |s, val| s.nice = val);
+        }
+        (FieldKey::IoPriority, FieldValue::Select { selected, .. }) => {
+            let priority = IO_PRIORITIES.get(*selected).copied().unwrap_or_default();
+            set_profile_override(priority, &mut config.session, |s, val| s.io_priority = val);
+        }
+        (FieldKey::SessionMemoryLimit, FieldValue::OptionalText(v)) => {
+            use crate::session::SessionConfigOverride;
+            let s = config
+                .session
+                .get_or_insert_with(SessionConfigOverride::default);
+            s.memory_limit = v.clone();
+        }
+        (FieldKey::SessionCpuLimit, FieldValue::OptionalText(v)) => {
+            use crate::session::SessionConfigOverride;
+            let s = config
+                .session
+                .get_or_insert_with(SessionConfigOverride::default);
+            s.cpu_limit = v.clone();
+        }
+        (FieldKey::SessionProxy, FieldValue::OptionalText(v)) => {
+            use crate::session::SessionConfigOverride;
+            let s = config
+                .session
+                .get_or_insert_with(SessionConfigOverride::default);
+            s.proxy = v.clone();
+        }
+        (FieldKey::SessionNoProxy, FieldValue::OptionalText(v)) => {
+            use crate::session::SessionConfigOverride;
+            let s = config
+                .session
+                .get_or_insert_with(SessionConfigOverride::default);
+            s.no_proxy = v.clone();
+        }
+        (FieldKey::SessionIcon, FieldValue::OptionalText(v)) => {
+            use crate::session::SessionConfigOverride;
+            let s = config
+                .session
+                .get_or_insert_with(SessionConfigOverride::default);
+            s.icon = v.clone();
+        }
+        (FieldKey::DailyBudget, FieldValue::Number(v)) => {
+            set_profile_override(*v, &mut config.session, |s, val| s.daily_budget = val);
+        }
+        (FieldKey::WeeklyBudget, FieldValue::Number(v)) => {
+            set_profile_override(*v, &mut config.session, |s, val| s.weekly_budget = val);
+        }
+        (FieldKey::BudgetWarnPercent, FieldValue::Number(v)) => {
+            set_profile_override(*v, &mut config.session, |s, val| {
+                s.budget_warn_percent = val
+            });
+        }
+        (FieldKey::BudgetAction, FieldValue::Select { selected, .. }) => {
+            let action = BUDGET_ACTIONS.get(*selected).copied().unwrap_or_default();
+            set_profile_override(action, &mut config.session, |s, val| s.budget_action = val);
+        }
+        (FieldKey::ProviderLimits, FieldValue::List(v)) => {
+            let map = parse_limit_list(v);
+            use crate::session::SessionConfigOverride;
+            let s = config
+                .session
+                .get_or_insert_with(SessionConfigOverride::default);
+            s.provider_limits = Some(map);
+        }
+        (FieldKey::CredentialExpiry, FieldValue::List(v)) => {
+            let map = parse_key_value_list(v);
+            use crate::session::SessionConfigOverride;
+            let s = config
+                .session
+                .get_or_insert_with(SessionConfigOverride::default);
+            s.credential_expiry = Some(map);
+        }
+        (FieldKey::CredentialWarnDays, FieldValue::Number(v)) => {
+            set_profile_override(*v, &mut config.session, |s, val| {
+                s.credential_warn_days = val
+            });
+        }
+        (FieldKey::CredentialRefreshCommand, FieldValue::OptionalText(v)) => {
+            use crate::session::SessionConfigOverride;
+            let s = config
+                .session
+                .get_or_insert_with(SessionConfigOverride::default);
+            s.credential_refresh_command = v.clone();
+        }
+        (FieldKey::AgentShim, FieldValue::Bool(v)) => {
+            set_profile_override(*v, &mut config.session, |s, val| s.agent_shim = val);
+        }
+        (FieldKey::ProjectEnv, FieldValue::Bool(v)) => {
+            set_profile_override(*v, &mut config.session, |s, val| s.project_env = val);
+        }
         // Sound
         (FieldKey::SoundEnabled, FieldValue::Bool(v)) => {
             set_profile_override(*v, &mut config.sound, |s, val| s.enabled = val);
@@ -1449,6 +3264,64 @@ fn apply_field_to_profile(field: &SettingField, _global: &Config, config: &mut P
                 .get_or_insert_with(crate::sound::SoundConfigOverride::default);
             s.on_error = v.clone();
         }
+        (FieldKey::SoundOnFinished, FieldValue::OptionalText(v)) => {
+            let s = config
+                .sound
+                .get_or_insert_with(crate::sound::SoundConfigOverride::default);
+            s.on_finished = v.clone();
+        }
+        // Notifications
+        (FieldKey::NotificationRules, FieldValue::List(v)) => {
+            set_profile_override(v.clone(), &mut config.notifications, |n, val| n.rules = val);
+        }
+        (FieldKey::NotificationWebhookUrl, FieldValue::OptionalText(v)) => {
+            let n = config
+                .notifications
+                .get_or_insert_with(crate::notifications::NotificationConfigOverride::default);
+            n.webhook_url = v.clone();
+        }
+        (FieldKey::NotificationTray, FieldValue::Bool(v)) => {
+            set_profile_override(*v, &mut config.notifications, |n, val| n.tray = val);
+        }
+        (FieldKey::NotificationTrayFocusCommand, FieldValue::OptionalText(v)) => {
+            let n = config
+                .notifications
+                .get_or_insert_with(crate::notifications::NotificationConfigOverride::default);
+            n.tray_focus_command = v.clone();
+        }
+        (FieldKey::NotificationDigest, FieldValue::Select { selected, .. }) => {
+            let digest = DIGEST_DELIVERIES
+                .get(*selected)
+                .copied()
+                .unwrap_or_default();
+            set_profile_override(digest, &mut config.notifications, |n, val| n.digest = val);
+        }
+        (FieldKey::NotificationDigestPath, FieldValue::OptionalText(v)) => {
+            let n = config
+                .notifications
+                .get_or_insert_with(crate::notifications::NotificationConfigOverride::default);
+            n.digest_path = v.clone();
+        }
+        // Share
+        (FieldKey::ShareDestination, FieldValue::Select { selected, .. }) => {
+            let destination = SHARE_DESTINATIONS
+                .get(*selected)
+                .copied()
+                .unwrap_or_default();
+            set_profile_override(destination, &mut config.share, |s, val| s.destination = val);
+        }
+        (FieldKey::ShareS3Bucket, FieldValue::OptionalText(v)) => {
+            let s = config
+                .share
+                .get_or_insert_with(crate::session::ShareConfigOverride::default);
+            s.s3_bucket = v.clone();
+        }
+        (FieldKey::ShareS3LinkHours, FieldValue::Number(v)) => {
+            set_profile_override(*v, &mut config.share, |s, val| s.s3_link_hours = val);
+        }
+        (FieldKey::ShareGistPublic, FieldValue::Bool(v)) => {
+            set_profile_override(*v, &mut config.share, |s, val| s.gist_public = val);
+        }
         // Hooks
         (FieldKey::HookOnCreate, FieldValue::List(v)) => {
             set_profile_override(v.clone(), &mut config.hooks, |s, val| s.on_create = val);
@@ -1456,6 +3329,25 @@ fn apply_field_to_profile(field: &SettingField, _global: &Config, config: &mut P
         (FieldKey::HookOnLaunch, FieldValue::List(v)) => {
             set_profile_override(v.clone(), &mut config.hooks, |s, val| s.on_launch = val);
         }
+        (FieldKey::HookHealthCheck, FieldValue::OptionalText(v)) => {
+            use crate::session::HooksConfigOverride;
+            let h = config
+                .hooks
+                .get_or_insert_with(HooksConfigOverride::default);
+            h.health_check = v.clone();
+        }
+        (FieldKey::HookHealthCheckInterval, FieldValue::Number(v)) => {
+            set_profile_override(*v, &mut config.hooks, |s, val| {
+                s.health_check_interval_secs = val
+            });
+        }
+        (FieldKey::HookTestWatch, FieldValue::OptionalText(v)) => {
+            use crate::session::HooksConfigOverride;
+            let h = config
+                .hooks
+                .get_or_insert_with(HooksConfigOverride::default);
+            h.test_watch = v.clone();
+        }
         _ => {}
     }
 }
@@ -12,6 +12,8 @@ use super::{FieldKey, FieldValue, ListEditState, SettingsFocus, SettingsScope, S
 pub enum SettingsAction {
     /// Continue showing the settings view
     Continue,
+    /// Changes were written to the config files; keep showing the view
+    Saved,
     /// Close the settings view (with optional unsaved changes warning)
     Close,
     /// Close was cancelled due to unsaved changes
@@ -21,6 +23,15 @@ pub enum SettingsAction {
 }
 
 impl SettingsView {
+    /// Whether keys are going to a text field, list editor, dialog, or the
+    /// help overlay rather than to settings navigation.
+    pub fn is_capturing_input(&self) -> bool {
+        self.editing_input.is_some()
+            || self.list_edit_state.is_some()
+            || self.custom_instruction_dialog.is_some()
+            || self.show_help
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> SettingsAction {
         // Clear transient messages on any key
         self.success_message = None;
@@ -71,12 +82,13 @@ impl SettingsView {
         // Normal mode
         match (key.code, key.modifiers) {
             // Save
-            (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
-                if let Err(e) = self.save() {
+            (KeyCode::Char('s'), KeyModifiers::CONTROL) => match self.save() {
+                Ok(()) => SettingsAction::Saved,
+                Err(e) => {
                     self.error_message = Some(format!("Failed to save: {}", e));
+                    SettingsAction::Continue
                 }
-                SettingsAction::Continue
-            }
+            },
 
             // Close from anywhere
             (KeyCode::Char('q'), _) => {
@@ -512,6 +524,41 @@ impl SettingsView {
                     t.name = None;
                 }
             }
+            FieldKey::RowFormat => {
+                if let Some(ref mut t) = config.theme {
+                    t.row_format = None;
+                }
+            }
+            FieldKey::ColorVision => {
+                if let Some(ref mut t) = config.theme {
+                    t.color_vision = None;
+                }
+            }
+            FieldKey::Hyperlinks => {
+                if let Some(ref mut t) = config.theme {
+                    t.hyperlinks = None;
+                }
+            }
+            FieldKey::Spellcheck => {
+                if let Some(ref mut t) = config.theme {
+                    t.spellcheck = None;
+                }
+            }
+            FieldKey::PreviewMinWidth => {
+                if let Some(ref mut t) = config.theme {
+                    t.preview_min_width = None;
+                }
+            }
+            FieldKey::RowDetailsMinWidth => {
+                if let Some(ref mut t) = config.theme {
+                    t.row_details_min_width = None;
+                }
+            }
+            FieldKey::TerminalTitle => {
+                if let Some(ref mut t) = config.theme {
+                    t.terminal_title = None;
+                }
+            }
             // Updates
             FieldKey::CheckEnabled => {
                 if let Some(ref mut u) = config.updates {
@@ -528,6 +575,32 @@ impl SettingsView {
                     u.notify_in_cli = None;
                 }
             }
+            FieldKey::CheckAgents => {
+                if let Some(ref mut u) = config.updates {
+                    u.check_agents = None;
+                }
+            }
+            // Polling
+            FieldKey::PaneCaptureMs => {
+                if let Some(ref mut p) = config.polling {
+                    p.pane_capture_ms = None;
+                }
+            }
+            FieldKey::GitCheckSecs => {
+                if let Some(ref mut p) = config.polling {
+                    p.git_check_secs = None;
+                }
+            }
+            FieldKey::ResourceUsageSecs => {
+                if let Some(ref mut p) = config.polling {
+                    p.resource_usage_secs = None;
+                }
+            }
+            FieldKey::EcoMode => {
+                if let Some(ref mut p) = config.polling {
+                    p.eco_mode = None;
+                }
+            }
             // Worktree
             FieldKey::PathTemplate => {
                 if let Some(ref mut w) = config.worktree {
@@ -554,6 +627,66 @@ impl SettingsView {
                     w.workspace_path_template = None;
                 }
             }
+            FieldKey::WorkspaceRepos => {
+                if let Some(ref mut w) = config.worktree {
+                    w.repos = None;
+                }
+            }
+            FieldKey::SparsePaths => {
+                if let Some(ref mut w) = config.worktree {
+                    w.sparse_paths = None;
+                }
+            }
+            FieldKey::MergeTestCommand => {
+                if let Some(ref mut w) = config.worktree {
+                    w.merge_test_command = None;
+                }
+            }
+            FieldKey::SyncNotes => {
+                if let Some(ref mut w) = config.worktree {
+                    w.sync_notes = None;
+                }
+            }
+            FieldKey::OnMerged => {
+                if let Some(ref mut w) = config.worktree {
+                    w.on_merged = None;
+                }
+            }
+            FieldKey::BranchTagPattern => {
+                if let Some(ref mut w) = config.worktree {
+                    w.branch_tag_pattern = None;
+                }
+            }
+            FieldKey::BranchTicketPattern => {
+                if let Some(ref mut w) = config.worktree {
+                    w.branch_ticket_pattern = None;
+                }
+            }
+            FieldKey::TicketUrlTemplate => {
+                if let Some(ref mut w) = config.worktree {
+                    w.ticket_url_template = None;
+                }
+            }
+            FieldKey::WorktreePoolSize => {
+                if let Some(ref mut w) = config.worktree {
+                    w.pool_size = None;
+                }
+            }
+            FieldKey::CloneDir => {
+                if let Some(ref mut w) = config.worktree {
+                    w.clone_dir = None;
+                }
+            }
+            FieldKey::ShallowClone => {
+                if let Some(ref mut w) = config.worktree {
+                    w.shallow_clone = None;
+                }
+            }
+            FieldKey::ConflictPrompt => {
+                if let Some(ref mut w) = config.worktree {
+                    w.conflict_prompt = None;
+                }
+            }
             // Sandbox
             FieldKey::DefaultImage => {
                 if let Some(ref mut s) = config.sandbox {
@@ -581,12 +714,22 @@ impl SettingsView {
                     t.mouse = None;
                 }
             }
+            FieldKey::WindowSize => {
+                if let Some(ref mut t) = config.tmux {
+                    t.window_size = None;
+                }
+            }
             // Session
             FieldKey::DefaultTool => {
                 if let Some(ref mut s) = config.session {
                     s.default_tool = None;
                 }
             }
+            FieldKey::FallbackTool => {
+                if let Some(ref mut s) = config.session {
+                    s.fallback_tool = None;
+                }
+            }
             FieldKey::SandboxEnabledByDefault => {
                 if let Some(ref mut s) = config.sandbox {
                     s.enabled_by_default = None;
@@ -607,6 +750,111 @@ impl SettingsView {
                     s.agent_command_override = None;
                 }
             }
+            FieldKey::TrashRetentionDays => {
+                if let Some(ref mut s) = config.session {
+                    s.trash_retention_days = None;
+                }
+            }
+            FieldKey::TrashMaxEntries => {
+                if let Some(ref mut s) = config.session {
+                    s.trash_max_entries = None;
+                }
+            }
+            FieldKey::SessionLocks => {
+                if let Some(ref mut s) = config.session {
+                    s.locks = None;
+                }
+            }
+            FieldKey::PromptGuardrails => {
+                if let Some(ref mut s) = config.session {
+                    s.prompt_guardrails = None;
+                }
+            }
+            FieldKey::Nice => {
+                if let Some(ref mut s) = config.session {
+                    s.nice = None;
+                }
+            }
+            FieldKey::IoPriority => {
+                if let Some(ref mut s) = config.session {
+                    s.io_priority = None;
+                }
+            }
+            FieldKey::SessionMemoryLimit => {
+                if let Some(ref mut s) = config.session {
+                    s.memory_limit = None;
+                }
+            }
+            FieldKey::SessionCpuLimit => {
+                if let Some(ref mut s) = config.session {
+                    s.cpu_limit = None;
+                }
+            }
+            FieldKey::SessionProxy => {
+                if let Some(ref mut s) = config.session {
+                    s.proxy = None;
+                }
+            }
+            FieldKey::SessionNoProxy => {
+                if let Some(ref mut s) = config.session {
+                    s.no_proxy = None;
+                }
+            }
+            FieldKey::SessionIcon => {
+                if let Some(ref mut s) = config.session {
+                    s.icon = None;
+                }
+            }
+            FieldKey::DailyBudget => {
+                if let Some(ref mut s) = config.session {
+                    s.daily_budget = None;
+                }
+            }
+            FieldKey::WeeklyBudget => {
+                if let Some(ref mut s) = config.session {
+                    s.weekly_budget = None;
+                }
+            }
+            FieldKey::BudgetWarnPercent => {
+                if let Some(ref mut s) = config.session {
+                    s.budget_warn_percent = None;
+                }
+            }
+            FieldKey::BudgetAction => {
+                if let Some(ref mut s) = config.session {
+                    s.budget_action = None;
+                }
+            }
+            FieldKey::ProviderLimits => {
+                if let Some(ref mut s) = config.session {
+                    s.provider_limits = None;
+                }
+            }
+            FieldKey::CredentialExpiry => {
+                if let Some(ref mut s) = config.session {
+                    s.credential_expiry = None;
+                }
+            }
+            FieldKey::CredentialWarnDays => {
+                if let Some(ref mut s) = config.session {
+                    s.credential_warn_days = None;
+                }
+            }
+            FieldKey::CredentialRefreshCommand => {
+                if let Some(ref mut s) = config.session {
+                    s.credential_refresh_command = None;
+                }
+            }
+            FieldKey::AgentShim => {
+                if let Some(ref mut s) = config.session {
+                    s.agent_shim = None;
+                }
+            }
+            FieldKey::ProjectEnv => {
+                if let Some(ref mut s) = config.session {
+                    s.project_env = None;
+                }
+            }
             FieldKey::DefaultTerminalMode => {
                 if let Some(ref mut s) = config.sandbox {
                     s.default_terminal_mode = None;
@@ -652,6 +900,16 @@ impl SettingsView {
                     s.container_runtime = None;
                 }
             }
+            FieldKey::HostSandboxProfile => {
+                if let Some(ref mut s) = config.sandbox {
+                    s.host_profile = None;
+                }
+            }
+            FieldKey::HostBlockNetwork => {
+                if let Some(ref mut s) = config.sandbox {
+                    s.host_block_network = None;
+                }
+            }
             // Sound
             FieldKey::SoundEnabled => {
                 if let Some(ref mut s) = config.sound {
@@ -688,6 +946,63 @@ impl SettingsView {
                     s.on_error = None;
                 }
             }
+            FieldKey::SoundOnFinished => {
+                if let Some(ref mut s) = config.sound {
+                    s.on_finished = None;
+                }
+            }
+            // Notifications
+            FieldKey::NotificationRules => {
+                if let Some(ref mut n) = config.notifications {
+                    n.rules = None;
+                }
+            }
+            FieldKey::NotificationWebhookUrl => {
+                if let Some(ref mut n) = config.notifications {
+                    n.webhook_url = None;
+                }
+            }
+            FieldKey::NotificationTray => {
+                if let Some(ref mut n) = config.notifications {
+                    n.tray = None;
+                }
+            }
+            FieldKey::NotificationTrayFocusCommand => {
+                if let Some(ref mut n) = config.notifications {
+                    n.tray_focus_command = None;
+                }
+            }
+            FieldKey::NotificationDigest => {
+                if let Some(ref mut n) = config.notifications {
+                    n.digest = None;
+                }
+            }
+            FieldKey::NotificationDigestPath => {
+                if let Some(ref mut n) = config.notifications {
+                    n.digest_path = None;
+                }
+            }
+            // Share
+            FieldKey::ShareDestination => {
+                if let Some(ref mut s) = config.share {
+                    s.destination = None;
+                }
+            }
+            FieldKey::ShareS3Bucket => {
+                if let Some(ref mut s) = config.share {
+                    s.s3_bucket = None;
+                }
+            }
+            FieldKey::ShareS3LinkHours => {
+                if let Some(ref mut s) = config.share {
+                    s.s3_link_hours = None;
+                }
+            }
+            FieldKey::ShareGistPublic => {
+                if let Some(ref mut s) = config.share {
+                    s.gist_public = None;
+                }
+            }
             // Hooks
             FieldKey::HookOnCreate => {
                 if let Some(ref mut h) = config.hooks {
@@ -699,13 +1014,26 @@ impl SettingsView {
                     h.on_launch = None;
                 }
             }
+            FieldKey::HookHealthCheck => {
+                if let Some(ref mut h) = config.hooks {
+                    h.health_check = None;
+                }
+            }
+            FieldKey::HookHealthCheckInterval => {
+                if let Some(ref mut h) = config.hooks {
+                    h.health_check_interval_secs = None;
+                }
+            }
+            FieldKey::HookTestWatch => {
+                if let Some(ref mut h) = config.hooks {
+                    h.test_watch = None;
+                }
+            }
         }
 
         // Sync repo_config when in Repo scope
         if self.scope == SettingsScope::Repo {
-            self.repo_config = Some(crate::session::profile_to_repo_config(
-                &self.repo_as_profile,
-            ));
+            self.sync_repo_config();
         }
 
         self.has_changes = true;
@@ -721,7 +1049,7 @@ impl SettingsView {
         self.global_config = crate::session::Config::load()?;
         self.profile_config = crate::session::load_profile_config(&self.profile)?;
         self.repo_config = self.project_path.as_ref().and_then(|p| {
-            crate::session::load_repo_config(std::path::Path::new(p))
+            crate::session::load_repo_config_file(std::path::Path::new(p))
                 .ok()
                 .flatten()
         });
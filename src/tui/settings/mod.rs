@@ -7,9 +7,9 @@ mod render;
 use tui_input::Input;
 
 use crate::session::{
-    list_profiles, load_profile_config, load_repo_config, merge_configs, profile_to_repo_config,
-    repo_config_to_profile, save_config, save_profile_config, save_repo_config, Config,
-    ProfileConfig, RepoConfig,
+    list_profiles, load_profile_config, load_repo_config_file, merge_configs,
+    profile_to_repo_config, repo_config_to_profile, save_config, save_profile_config,
+    save_repo_config, Config, ProfileConfig, RepoConfig,
 };
 use crate::tui::dialogs::CustomInstructionDialog;
 
@@ -118,9 +118,11 @@ impl SettingsView {
         let global_config = Config::load()?;
         let profile_config = load_profile_config(profile)?;
 
-        let repo_config = project_path
-            .as_ref()
-            .and_then(|p| load_repo_config(std::path::Path::new(p)).ok().flatten());
+        let repo_config = project_path.as_ref().and_then(|p| {
+            load_repo_config_file(std::path::Path::new(p))
+                .ok()
+                .flatten()
+        });
 
         let resolved_base = merge_configs(global_config.clone(), &profile_config);
         let repo_as_profile = repo_config
@@ -141,8 +143,11 @@ impl SettingsView {
             SettingsCategory::Sandbox,
             SettingsCategory::Worktree,
             SettingsCategory::Updates,
+            SettingsCategory::Polling,
             SettingsCategory::Tmux,
             SettingsCategory::Sound,
+            SettingsCategory::Notifications,
+            SettingsCategory::Share,
         ];
 
         let mut view = Self {
@@ -270,13 +275,29 @@ impl SettingsView {
                     &mut self.resolved_base,
                     &mut self.repo_as_profile,
                 );
-                // Sync back to repo_config
-                self.repo_config = Some(profile_to_repo_config(&self.repo_as_profile));
+                self.sync_repo_config();
             }
         }
         self.has_changes = true;
     }
 
+    /// Rebuild `repo_config` from the edited fields, keeping its `extends`,
+    /// `commands` and `completion_ignores`, which have no settings fields.
+    pub(super) fn sync_repo_config(&mut self) {
+        let extends = self.repo_config.as_ref().and_then(|r| r.extends.clone());
+        let (commands, completion_ignores) = self
+            .repo_config
+            .as_ref()
+            .map(|r| (r.commands.clone(), r.completion_ignores.clone()))
+            .unwrap_or_default();
+        self.repo_config = Some(RepoConfig {
+            extends,
+            commands,
+            completion_ignores,
+            ..profile_to_repo_config(&self.repo_as_profile)
+        });
+    }
+
     /// Save the current configuration
     pub fn save(&mut self) -> anyhow::Result<()> {
         // Validate all fields before saving
@@ -9,6 +9,16 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 use crate::session::{Instance, Status};
+use crate::tmux::permission_prompt::PermissionPrompt;
+
+/// Pane lines searched for the agent's final message
+const FINAL_SUMMARY_LINES: usize = 200;
+
+/// Busy sessions are checked on every request (`polling.pane_capture_ms`
+/// apart); a quiet one waits twice as long after each unchanged check, up to
+/// this many times the request interval, so many idle sessions cost few tmux
+/// calls.
+const MAX_BACKOFF_FACTOR: u32 = 8;
 
 /// Result of a status check for a single session
 #[derive(Debug)]
@@ -16,18 +26,76 @@ pub struct StatusUpdate {
     pub id: String,
     pub status: Status,
     pub last_error: Option<String>,
+    pub auth_required: bool,
+    pub quota_exceeded: bool,
+    /// The tool-permission prompt the agent is waiting on
+    pub permission_prompt: Option<PermissionPrompt>,
+    /// The agent's last message, when it just went from Running to Idle
+    pub final_summary: Option<String>,
+    /// Cost the agent last reported, read alongside `final_summary`
+    pub cost: Option<f64>,
 }
 
 /// Background thread that polls session status without blocking the UI
 pub struct StatusPoller {
-    request_tx: mpsc::Sender<Vec<Instance>>,
+    request_tx: mpsc::Sender<(Vec<Instance>, Duration)>,
     result_rx: mpsc::Receiver<Vec<StatusUpdate>>,
     _handle: thread::JoinHandle<()>,
 }
 
+/// When each session is next due for a status check
+struct PollSchedule {
+    status: Status,
+    interval: Duration,
+    next_due: Instant,
+}
+
+/// Adaptive polling: sessions that are running or just changed are checked
+/// every round, quiet ones back off up to `MAX_BACKOFF_FACTOR` rounds.
+#[derive(Default)]
+struct PollBackoff {
+    sessions: HashMap<String, PollSchedule>,
+}
+
+impl PollBackoff {
+    /// Whether `inst` should be checked now. A session whose status changed
+    /// in the UI since the last check (started, stopped, ...) always is.
+    fn is_due(&self, inst: &Instance, now: Instant) -> bool {
+        self.sessions
+            .get(&inst.id)
+            .map_or(true, |s| s.status != inst.status || now >= s.next_due)
+    }
+
+    /// Schedule `id`'s next check. `round` is how often checks are requested.
+    fn record(&mut self, id: &str, status: Status, now: Instant, round: Duration) {
+        let busy = matches!(status, Status::Running | Status::Starting);
+        let interval = match self.sessions.get(id) {
+            Some(s) if !busy && s.status == status => {
+                (s.interval * 2).clamp(round, round * MAX_BACKOFF_FACTOR)
+            }
+            _ => round,
+        };
+        self.sessions.insert(
+            id.to_string(),
+            PollSchedule {
+                status,
+                interval,
+                // Requests arrive every `round`; allow for jitter
+                next_due: now + interval - round / 2,
+            },
+        );
+    }
+
+    /// Drop schedules of sessions that no longer exist.
+    fn retain(&mut self, instances: &[Instance]) {
+        self.sessions
+            .retain(|id, _| instances.iter().any(|i| &i.id == id));
+    }
+}
+
 impl StatusPoller {
     pub fn new() -> Self {
-        let (request_tx, request_rx) = mpsc::channel::<Vec<Instance>>();
+        let (request_tx, request_rx) = mpsc::channel::<(Vec<Instance>, Duration)>();
         let (result_tx, result_rx) = mpsc::channel::<Vec<StatusUpdate>>();
 
         let handle = thread::spawn(move || {
@@ -42,15 +110,29 @@ impl StatusPoller {
     }
 
     fn polling_loop(
-        request_rx: mpsc::Receiver<Vec<Instance>>,
+        request_rx: mpsc::Receiver<(Vec<Instance>, Duration)>,
         result_tx: mpsc::Sender<Vec<StatusUpdate>>,
     ) {
         let container_check_interval = Duration::from_secs(5);
         // Initialize to the past so the first check runs immediately
         let mut last_container_check = Instant::now() - container_check_interval;
         let mut container_states: HashMap<String, bool> = HashMap::new();
+        let mut backoff = PollBackoff::default();
+
+        while let Ok((instances, round)) = request_rx.recv() {
+            let now = Instant::now();
+            backoff.retain(&instances);
+            let instances: Vec<Instance> = instances
+                .into_iter()
+                .filter(|inst| backoff.is_due(inst, now))
+                .collect();
+            if instances.is_empty() {
+                if result_tx.send(Vec::new()).is_err() {
+                    break;
+                }
+                continue;
+            }
 
-        while let Ok(instances) = request_rx.recv() {
             crate::tmux::refresh_session_cache();
 
             // Refresh container health if any sandboxed session exists and interval elapsed
@@ -78,31 +160,70 @@ impl StatusPoller {
                                         id: inst.id,
                                         status: Status::Error,
                                         last_error: Some("Container is not running".to_string()),
+                                        auth_required: inst.auth_required,
+                                        quota_exceeded: inst.quota_exceeded,
+                                        permission_prompt: None,
+                                        final_summary: None,
+                                        cost: None,
                                     };
                                 }
                             }
                         }
                     }
 
+                    let was_running = inst.status == Status::Running;
                     inst.update_status();
+                    let (final_summary, cost) = if was_running && inst.status == Status::Idle {
+                        Self::capture_finish(&inst)
+                    } else {
+                        (None, None)
+                    };
 
                     StatusUpdate {
                         id: inst.id,
                         status: inst.status,
                         last_error: inst.last_error,
+                        auth_required: inst.auth_required,
+                        quota_exceeded: inst.quota_exceeded,
+                        permission_prompt: inst
+                            .permission_prompt
+                            .filter(|_| inst.status == Status::Waiting),
+                        final_summary,
+                        cost,
                     }
                 })
                 .collect();
 
+            for update in &updates {
+                backoff.record(&update.id, update.status, now, round);
+            }
+
             if result_tx.send(updates).is_err() {
                 break;
             }
         }
     }
 
+    /// The agent's last message and reported cost from the bottom of its
+    /// pane.
+    fn capture_finish(inst: &Instance) -> (Option<String>, Option<f64>) {
+        let Ok(content) = inst
+            .tmux_session()
+            .and_then(|session| session.capture_pane(FINAL_SUMMARY_LINES))
+        else {
+            return (None, None);
+        };
+        (
+            crate::tmux::output_parser::final_message(&content),
+            crate::tmux::output_parser::reported_cost(&content),
+        )
+    }
+
     /// Request a status refresh for all given instances (non-blocking).
-    pub fn request_refresh(&self, instances: Vec<Instance>) {
-        let _ = self.request_tx.send(instances);
+    /// `interval` is how often requests are made, which quiet sessions back
+    /// off from.
+    pub fn request_refresh(&self, instances: Vec<Instance>, interval: Duration) {
+        let _ = self.request_tx.send((instances, interval));
     }
 
     /// Try to receive status updates without blocking.
@@ -117,3 +238,46 @@ impl Default for StatusPoller {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiet_sessions_back_off() {
+        let round = Duration::from_millis(500);
+        let mut backoff = PollBackoff::default();
+        let mut inst = Instance::new("test", "/tmp/test");
+        let start = Instant::now();
+        assert!(backoff.is_due(&inst, start));
+
+        // Each unchanged idle check doubles the wait, up to the maximum
+        let mut now = start;
+        for _ in 0..6 {
+            backoff.record(&inst.id, Status::Idle, now, round);
+            now += round;
+        }
+        assert_eq!(
+            backoff.sessions[&inst.id].interval,
+            round * MAX_BACKOFF_FACTOR
+        );
+        assert!(!backoff.is_due(&inst, now));
+
+        // A status change made in the UI is picked up immediately
+        inst.status = Status::Starting;
+        assert!(backoff.is_due(&inst, now));
+
+        // Busy sessions are polled every round
+        backoff.record(&inst.id, Status::Running, now, round);
+        backoff.record(&inst.id, Status::Running, now, round);
+        assert_eq!(backoff.sessions[&inst.id].interval, round);
+
+        // A slower round (eco mode) raises both bounds
+        let slow = round * 4;
+        backoff.record(&inst.id, Status::Idle, now, slow);
+        assert_eq!(backoff.sessions[&inst.id].interval, slow);
+
+        backoff.retain(&[]);
+        assert!(backoff.sessions.is_empty());
+    }
+}
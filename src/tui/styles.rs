@@ -3,6 +3,8 @@
 use ratatui::style::Color;
 use tracing::warn;
 
+use crate::session::{ColorVision, Status};
+
 pub const AVAILABLE_THEMES: &[&str] = &[
     "phosphor",
     "tokyo-night-storm",
@@ -23,6 +25,43 @@ pub fn load_theme(name: &str) -> Theme {
     }
 }
 
+pub const ICON_RUNNING: &str = "●";
+pub const ICON_WAITING: &str = "◐";
+pub const ICON_IDLE: &str = "○";
+pub const ICON_ERROR: &str = "✕";
+pub const ICON_STARTING: &str = "◌";
+pub const ICON_UNKNOWN: &str = "?";
+pub const ICON_STOPPED: &str = "■";
+pub const ICON_DELETING: &str = "✗";
+
+/// Glyph shown next to every status, so statuses that share a color (or
+/// look alike to a color-blind user) stay distinguishable.
+pub fn status_icon(status: Status) -> &'static str {
+    match status {
+        Status::Running => ICON_RUNNING,
+        Status::Waiting => ICON_WAITING,
+        Status::Idle => ICON_IDLE,
+        Status::Unknown => ICON_UNKNOWN,
+        Status::Stopped => ICON_STOPPED,
+        Status::Error => ICON_ERROR,
+        Status::Starting => ICON_STARTING,
+        Status::Deleting => ICON_DELETING,
+    }
+}
+
+pub fn status_color(status: Status, theme: &Theme) -> Color {
+    match status {
+        Status::Running => theme.running,
+        Status::Waiting => theme.waiting,
+        Status::Idle => theme.idle,
+        Status::Unknown => theme.waiting,
+        Status::Stopped => theme.dimmed,
+        Status::Error => theme.error,
+        Status::Starting => theme.dimmed,
+        Status::Deleting => theme.waiting,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Theme {
     // Background and borders
@@ -69,6 +108,46 @@ impl Default for Theme {
 }
 
 impl Theme {
+    /// Replace the status and diff colors with ones that stay apart for the
+    /// given color vision, based on the Okabe-Ito palette. Backgrounds and
+    /// text keep the theme's colors.
+    pub fn with_color_vision(mut self, vision: ColorVision) -> Self {
+        let light = matches!(self.background, Color::Rgb(r, g, b)
+            if u32::from(r) + u32::from(g) + u32::from(b) > 384);
+        // (good, attention, bad), darker variants on light backgrounds
+        let (good, attention, bad) = match (vision, light) {
+            (ColorVision::Normal, _) => return self,
+            (ColorVision::Deuteranopia | ColorVision::Protanopia, false) => (
+                Color::Rgb(86, 180, 233),
+                Color::Rgb(240, 228, 66),
+                Color::Rgb(213, 94, 0),
+            ),
+            (ColorVision::Deuteranopia | ColorVision::Protanopia, true) => (
+                Color::Rgb(0, 114, 178),
+                Color::Rgb(176, 128, 0),
+                Color::Rgb(170, 60, 0),
+            ),
+            (ColorVision::Tritanopia, false) => (
+                Color::Rgb(0, 200, 190),
+                Color::Rgb(255, 140, 200),
+                Color::Rgb(230, 40, 40),
+            ),
+            (ColorVision::Tritanopia, true) => (
+                Color::Rgb(0, 130, 125),
+                Color::Rgb(200, 60, 140),
+                Color::Rgb(190, 20, 20),
+            ),
+        };
+        self.running = good;
+        self.waiting = attention;
+        self.error = bad;
+        self.diff_add = good;
+        self.diff_modified = attention;
+        self.diff_delete = bad;
+        self.help_key = attention;
+        self
+    }
+
     pub fn phosphor() -> Self {
         Self {
             background: Color::Rgb(16, 20, 18),
@@ -255,6 +334,49 @@ mod tests {
         assert_eq!(theme.background, Color::Rgb(40, 42, 54));
     }
 
+    #[test]
+    fn test_color_vision_palette() {
+        let normal = load_theme("phosphor");
+        assert_eq!(
+            normal
+                .clone()
+                .with_color_vision(ColorVision::Normal)
+                .running,
+            normal.running
+        );
+
+        for name in AVAILABLE_THEMES {
+            for vision in [
+                ColorVision::Deuteranopia,
+                ColorVision::Protanopia,
+                ColorVision::Tritanopia,
+            ] {
+                let theme = load_theme(name).with_color_vision(vision);
+                assert_ne!(theme.running, theme.waiting);
+                assert_ne!(theme.running, theme.error);
+                assert_ne!(theme.waiting, theme.error);
+                assert_eq!(theme.background, load_theme(name).background);
+            }
+        }
+    }
+
+    #[test]
+    fn test_status_icons_are_distinct() {
+        let statuses = [
+            Status::Running,
+            Status::Waiting,
+            Status::Idle,
+            Status::Unknown,
+            Status::Stopped,
+            Status::Error,
+            Status::Starting,
+            Status::Deleting,
+        ];
+        let icons: std::collections::HashSet<_> =
+            statuses.iter().map(|s| status_icon(*s)).collect();
+        assert_eq!(icons.len(), statuses.len());
+    }
+
     #[test]
     fn test_available_themes_count() {
         assert_eq!(AVAILABLE_THEMES.len(), 4);
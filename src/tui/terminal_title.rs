@@ -0,0 +1,137 @@
+//! Terminal window and tab title
+//!
+//! While the TUI runs, the terminal's title, and so its tab, shows how the
+//! sessions are doing, rendered from `theme.terminal_title` (e.g. "aoe - 2
+//! need attention"). The title the user had is pushed onto the terminal's
+//! title stack when aoe first sets one and popped back on exit and while a
+//! session is attached, so the agent or shell can set its own.
+
+use std::io::{self, Write};
+use std::sync::Mutex;
+
+/// Session counts the title is rendered from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub total: usize,
+    pub running: usize,
+    pub waiting: usize,
+    pub error: usize,
+}
+
+impl Counts {
+    /// Sessions waiting on the user or failed
+    pub fn attention(&self) -> usize {
+        self.waiting + self.error
+    }
+
+    /// e.g. "2 need attention", "3 running" or "idle"
+    pub fn summary(&self) -> String {
+        if self.attention() > 0 {
+            format!("{} need attention", self.attention())
+        } else if self.running > 0 {
+            format!("{} running", self.running)
+        } else if self.total == 0 {
+            "no sessions".to_string()
+        } else {
+            "idle".to_string()
+        }
+    }
+}
+
+/// Fill in `template`'s placeholders: `{summary}`, `{attention}`,
+/// `{running}`, `{waiting}`, `{error}`, `{sessions}` and `{profile}`.
+/// Control characters are dropped so the title cannot end its escape early.
+pub fn render(template: &str, profile: &str, counts: &Counts) -> String {
+    let title = template
+        .replace("{summary}", &counts.summary())
+        .replace("{attention}", &counts.attention().to_string())
+        .replace("{running}", &counts.running.to_string())
+        .replace("{waiting}", &counts.waiting.to_string())
+        .replace("{error}", &counts.error.to_string())
+        .replace("{sessions}", &counts.total.to_string())
+        .replace("{profile}", profile);
+    title.chars().filter(|c| !c.is_control()).collect()
+}
+
+/// The title aoe last set, if it has set one since saving the user's
+static SHOWN: Mutex<Option<String>> = Mutex::new(None);
+
+// XTWINOPS: save and restore the window and icon titles
+const PUSH_TITLE: &str = "\x1b[22;0t";
+const POP_TITLE: &str = "\x1b[23;0t";
+
+fn write(sequence: &str) {
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(sequence.as_bytes());
+    let _ = stdout.flush();
+}
+
+fn set_sequence(title: &str) -> String {
+    format!("\x1b]0;{}\x07", title)
+}
+
+/// Show `title`, saving the user's title first if aoe has not set one yet.
+pub fn set(title: &str) {
+    let mut shown = SHOWN.lock().unwrap_or_else(|e| e.into_inner());
+    match shown.as_deref() {
+        Some(current) if current == title => return,
+        Some(_) => write(&set_sequence(title)),
+        None => write(&format!("{}{}", PUSH_TITLE, set_sequence(title))),
+    }
+    *shown = Some(title.to_string());
+}
+
+/// Put the user's title back and stop managing it.
+pub fn restore() {
+    let mut shown = SHOWN.lock().unwrap_or_else(|e| e.into_inner());
+    if shown.take().is_some() {
+        write(POP_TITLE);
+    }
+}
+
+/// Put the user's title back while the TUI is left, e.g. for an attached
+/// session, remembering aoe's for [`resume`].
+pub fn suspend() {
+    let shown = SHOWN.lock().unwrap_or_else(|e| e.into_inner());
+    if shown.is_some() {
+        write(POP_TITLE);
+    }
+}
+
+/// Show aoe's title again after [`suspend`].
+pub fn resume() {
+    let shown = SHOWN.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(title) = shown.as_deref() {
+        write(&format!("{}{}", PUSH_TITLE, set_sequence(title)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_title() {
+        let counts = Counts {
+            total: 5,
+            running: 2,
+            waiting: 1,
+            error: 1,
+        };
+        assert_eq!(
+            render("aoe - {summary}", "work", &counts),
+            "aoe - 2 need attention"
+        );
+        assert_eq!(
+            render("[{profile}] {running}/{sessions}\x07", "work", &counts),
+            "[work] 2/5"
+        );
+        let quiet = Counts {
+            total: 5,
+            running: 2,
+            ..Counts::default()
+        };
+        assert_eq!(quiet.summary(), "2 running");
+        assert_eq!(Counts::default().summary(), "no sessions");
+    }
+}
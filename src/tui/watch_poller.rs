@@ -0,0 +1,54 @@
+//! Background reader for test watchers
+//!
+//! Reading a watcher's pane is a tmux call per session, so it runs on a
+//! short-lived thread like the health checks. The counts come back over a
+//! channel and are applied by `HomeView::apply_watch_results`.
+
+use std::sync::mpsc;
+use std::thread;
+
+use crate::session::test_watch::{self, TestCounts};
+use crate::tmux::CommandSession;
+
+/// What a test watcher's pane showed
+#[derive(Debug)]
+pub struct WatchResult {
+    pub id: String,
+    /// `None` while the screen shows no summary, e.g. mid-run
+    pub counts: Option<TestCounts>,
+}
+
+pub struct WatchPoller {
+    result_tx: mpsc::Sender<WatchResult>,
+    result_rx: mpsc::Receiver<WatchResult>,
+}
+
+impl WatchPoller {
+    pub fn new() -> Self {
+        let (result_tx, result_rx) = mpsc::channel();
+        Self {
+            result_tx,
+            result_rx,
+        }
+    }
+
+    /// Read the counts session `id`'s watcher shows (non-blocking).
+    pub fn request_read(&self, id: String, session: CommandSession) {
+        let tx = self.result_tx.clone();
+        thread::spawn(move || {
+            let counts = test_watch::parse(&session.capture_screen());
+            let _ = tx.send(WatchResult { id, counts });
+        });
+    }
+
+    /// Collect all finished reads without blocking.
+    pub fn try_recv_results(&self) -> Vec<WatchResult> {
+        self.result_rx.try_iter().collect()
+    }
+}
+
+impl Default for WatchPoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,230 @@
+//! Agent CLI version checks
+//!
+//! Compares the version each installed agent CLI reports with `--version`
+//! against the newest release of its npm package, so the dashboard can show
+//! "claude 1.2 → 1.4 available". Results are cached for
+//! `updates.check_interval_hours`, like aoe's own update check. Upgrading
+//! runs `updates.agent_upgrade_commands.<agent>`, or `npm install -g
+//! <package>@latest` when none is configured.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::warn;
+
+use super::is_newer_version;
+use crate::session::config::UpdatesConfig;
+use crate::session::{get_app_dir, get_update_settings};
+
+const NPM_REGISTRY: &str = "https://registry.npmjs.org";
+
+/// How long an agent gets to print its version
+const VERSION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The npm package each agent is released as
+const NPM_PACKAGES: &[(&str, &str)] = &[
+    ("claude", "@anthropic-ai/claude-code"),
+    ("codex", "@openai/codex"),
+    ("gemini", "@google/gemini-cli"),
+    ("opencode", "opencode-ai"),
+    ("copilot", "@github/copilot"),
+];
+
+/// An installed agent's version and its newest release
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentVersion {
+    pub agent: String,
+    pub installed: String,
+    pub latest: String,
+}
+
+impl AgentVersion {
+    pub fn update_available(&self) -> bool {
+        is_newer_version(&self.latest, &self.installed)
+    }
+
+    /// e.g. "claude 1.2 → 1.4 available"
+    pub fn describe(&self) -> String {
+        format!(
+            "{} {} → {} available",
+            self.agent, self.installed, self.latest
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AgentVersionCache {
+    checked_at: chrono::DateTime<chrono::Utc>,
+    agents: Vec<AgentVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NpmRelease {
+    version: String,
+}
+
+fn cache_path() -> Result<PathBuf> {
+    Ok(get_app_dir()?.join("agent_versions_cache.json"))
+}
+
+fn load_cache() -> Option<AgentVersionCache> {
+    let path = cache_path().ok()?;
+    let content = fs::read_to_string(&path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_cache(cache: &AgentVersionCache) -> Result<()> {
+    let path = cache_path()?;
+    let content = serde_json::to_string_pretty(cache)?;
+    fs::write(&path, content)?;
+    Ok(())
+}
+
+fn npm_package(agent: &str) -> Option<&'static str> {
+    NPM_PACKAGES
+        .iter()
+        .find(|(name, _)| *name == agent)
+        .map(|(_, package)| *package)
+}
+
+/// The command that upgrades `agent`: the configured one, else an npm global
+/// install for agents released on npm.
+pub fn upgrade_command(settings: &UpdatesConfig, agent: &str) -> Option<String> {
+    if let Some(command) = settings
+        .agent_upgrade_commands
+        .get(agent)
+        .filter(|c| !c.trim().is_empty())
+    {
+        return Some(command.clone());
+    }
+    npm_package(agent).map(|package| format!("npm install -g {}@latest", package))
+}
+
+/// The first dotted version number in `--version` output, e.g. "1.0.44"
+/// from "1.0.44 (Claude Code)" or "codex-cli 0.1.2".
+fn parse_version(output: &str) -> Option<String> {
+    let re = regex::Regex::new(r"\d+(?:\.\d+)+").ok()?;
+    re.find(output).map(|m| m.as_str().to_string())
+}
+
+async fn installed_version(binary: &str) -> Option<String> {
+    let output = tokio::time::timeout(
+        VERSION_TIMEOUT,
+        tokio::process::Command::new(binary)
+            .arg("--version")
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .ok()?
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_version(&String::from_utf8_lossy(&output.stdout))
+}
+
+async fn latest_version(client: &reqwest::Client, package: &str) -> Result<String> {
+    let url = format!("{}/{}/latest", NPM_REGISTRY, package);
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to fetch {}: HTTP {}", package, response.status());
+    }
+    let release: NpmRelease = response.json().await?;
+    Ok(release.version)
+}
+
+/// Installed agents with a newer release. Uses the cached result unless it
+/// is older than `updates.check_interval_hours` or `force` is set.
+pub async fn check_agent_updates(force: bool) -> Result<Vec<AgentVersion>> {
+    let settings = get_update_settings();
+
+    if !force {
+        if let Some(cache) = load_cache() {
+            let age = chrono::Utc::now() - cache.checked_at;
+            if age < chrono::Duration::hours(settings.check_interval_hours as i64) {
+                return Ok(available(cache.agents));
+            }
+        }
+    }
+
+    let client = reqwest::Client::builder()
+        .user_agent("agent-of-empires")
+        .timeout(Duration::from_secs(5))
+        .build()?;
+
+    let mut agents = Vec::new();
+    for (name, package) in NPM_PACKAGES {
+        let Some(def) = crate::agents::get_agent(name) else {
+            continue;
+        };
+        let Some(installed) = installed_version(def.binary).await else {
+            continue;
+        };
+        match latest_version(&client, package).await {
+            Ok(latest) => agents.push(AgentVersion {
+                agent: name.to_string(),
+                installed,
+                latest,
+            }),
+            Err(e) => warn!("Failed to check for {} updates: {}", name, e),
+        }
+    }
+
+    let cache = AgentVersionCache {
+        checked_at: chrono::Utc::now(),
+        agents: agents.clone(),
+    };
+    if let Err(e) = save_cache(&cache) {
+        warn!("Failed to save agent version cache: {}", e);
+    }
+
+    Ok(available(agents))
+}
+
+fn available(agents: Vec<AgentVersion>) -> Vec<AgentVersion> {
+    agents
+        .into_iter()
+        .filter(|a| a.update_available())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version_and_upgrade_command() {
+        assert_eq!(
+            parse_version("1.0.44 (Claude Code)").as_deref(),
+            Some("1.0.44")
+        );
+        assert_eq!(parse_version("codex-cli 0.1.2\n").as_deref(), Some("0.1.2"));
+        assert_eq!(parse_version("unknown"), None);
+
+        let version = AgentVersion {
+            agent: "claude".to_string(),
+            installed: "1.2".to_string(),
+            latest: "1.4".to_string(),
+        };
+        assert!(version.update_available());
+        assert_eq!(version.describe(), "claude 1.2 → 1.4 available");
+
+        let mut settings = UpdatesConfig::default();
+        assert_eq!(
+            upgrade_command(&settings, "claude").as_deref(),
+            Some("npm install -g @anthropic-ai/claude-code@latest")
+        );
+        assert_eq!(upgrade_command(&settings, "vibe"), None);
+        settings
+            .agent_upgrade_commands
+            .insert("claude".to_string(), "brew upgrade claude-code".to_string());
+        assert_eq!(
+            upgrade_command(&settings, "claude").as_deref(),
+            Some("brew upgrade claude-code")
+        );
+    }
+}
@@ -28,6 +28,7 @@ fn test_delete_dialog_respects_worktree_auto_cleanup_true() {
         DeleteDialogConfig {
             worktree_branch: Some("main".to_string()),
             has_sandbox: false,
+            dirty_worktrees: Vec::new(),
         },
         "default",
     );
@@ -51,6 +52,7 @@ fn test_delete_dialog_respects_worktree_auto_cleanup_false() {
         DeleteDialogConfig {
             worktree_branch: Some("main".to_string()),
             has_sandbox: false,
+            dirty_worktrees: Vec::new(),
         },
         "default",
     );
@@ -74,6 +76,7 @@ fn test_delete_dialog_respects_sandbox_auto_cleanup_true() {
         DeleteDialogConfig {
             worktree_branch: None,
             has_sandbox: true,
+            dirty_worktrees: Vec::new(),
         },
         "default",
     );
@@ -97,6 +100,7 @@ fn test_delete_dialog_respects_sandbox_auto_cleanup_false() {
         DeleteDialogConfig {
             worktree_branch: None,
             has_sandbox: true,
+            dirty_worktrees: Vec::new(),
         },
         "default",
     );
@@ -550,3 +550,240 @@ fn test_cli_rename_preserves_tmux_session() {
         .args(["kill-session", "-t", &new_tmux_name])
         .output();
 }
+
+#[test]
+#[serial]
+fn test_cli_share_writes_html_bundle() {
+    let h = TuiTestHarness::new("cli_share");
+    let project = h.project_path();
+
+    let add_output = h.run_cli(&["add", project.to_str().unwrap(), "-t", "Share <Me>"]);
+    assert!(
+        add_output.status.success(),
+        "aoe add failed: {}",
+        String::from_utf8_lossy(&add_output.stderr)
+    );
+
+    let out = h.home_path().join("bundle.html");
+    let output = h.run_cli(&["share", "Share <Me>", "-o", out.to_str().unwrap()]);
+    assert!(
+        output.status.success(),
+        "aoe share failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let html = std::fs::read_to_string(&out).expect("bundle should be written");
+    assert!(html.contains("<h1>Share &lt;Me&gt;</h1>"), "{}", html);
+    assert!(html.contains("<h2>Transcript</h2>"));
+
+    // Uploading needs a destination
+    let output = h.run_cli(&["share", "Share <Me>", "--upload"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No upload destination"));
+}
+
+#[test]
+#[serial]
+fn test_cli_new_reads_prompt_from_stdin_and_file() {
+    let h = TuiTestHarness::new("cli_new_prompt");
+    let project = h.project_path();
+    let spec = "# Spec\n\nAdd a \"login\" form with $VALIDATION.\n";
+
+    let output = h.run_cli_with_stdin(
+        &[
+            "new",
+            "--path",
+            project.to_str().unwrap(),
+            "-t",
+            "FromStdin",
+            "--prompt",
+            "-",
+        ],
+        spec,
+    );
+    assert!(
+        output.status.success(),
+        "aoe new failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let spec_file = h.home_path().join("spec.md");
+    std::fs::write(&spec_file, spec).unwrap();
+    let output = h.run_cli(&[
+        "add",
+        project.to_str().unwrap(),
+        "-t",
+        "FromFile",
+        "--prompt-file",
+        spec_file.to_str().unwrap(),
+    ]);
+    assert!(
+        output.status.success(),
+        "aoe add failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let sessions = read_sessions_json(&h);
+    for session in sessions.as_array().unwrap() {
+        assert_eq!(
+            session["initial_prompt"].as_str(),
+            Some(spec.trim()),
+            "prompt should be stored verbatim for {}",
+            session["title"]
+        );
+    }
+
+    let output = h.run_cli_with_stdin(
+        &[
+            "add",
+            project.to_str().unwrap(),
+            "-t",
+            "Empty",
+            "--prompt",
+            "-",
+        ],
+        "  \n",
+    );
+    assert!(
+        !output.status.success(),
+        "an empty prompt should be refused"
+    );
+}
+
+#[test]
+#[serial]
+fn test_cli_add_checks_proxy() {
+    let h = TuiTestHarness::new("cli_add_proxy");
+    let project = h.project_path();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+
+    let output = h.run_cli(&[
+        "add",
+        project.to_str().unwrap(),
+        "-t",
+        "Proxied",
+        "--proxy",
+        &url,
+    ]);
+    assert!(
+        output.status.success(),
+        "aoe add failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let sessions = read_sessions_json(&h);
+    assert_eq!(sessions[0]["proxy"]["url"].as_str(), Some(url.as_str()));
+
+    drop(listener);
+    let output = h.run_cli(&[
+        "add",
+        project.to_str().unwrap(),
+        "-t",
+        "Unreachable",
+        "--proxy",
+        &url,
+    ]);
+    assert!(!output.status.success(), "an unreachable proxy should fail");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not reachable"));
+    assert_eq!(read_sessions_json(&h).as_array().unwrap().len(), 1);
+}
+
+#[test]
+#[serial]
+fn test_cli_session_checkpoint_and_rollback() {
+    let h = TuiTestHarness::new("cli_checkpoint");
+    let project = h.project_path();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(&project)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    };
+    git(&["init", "-q"]);
+    std::fs::write(project.join("a.txt"), "one").unwrap();
+    git(&["add", "a.txt"]);
+    git(&["commit", "-qm", "init"]);
+
+    let output = h.run_cli(&["add", project.to_str().unwrap(), "-t", "Milestones"]);
+    assert!(output.status.success());
+
+    std::fs::write(project.join("a.txt"), "two").unwrap();
+    let output = h.run_cli(&[
+        "session",
+        "checkpoint",
+        "Milestones",
+        "--add",
+        "draft",
+        "--note",
+        "first pass",
+    ]);
+    assert!(
+        output.status.success(),
+        "checkpoint failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let sessions = read_sessions_json(&h);
+    let checkpoint = &sessions[0]["checkpoints"][0];
+    assert_eq!(checkpoint["name"].as_str(), Some("draft"));
+    assert_eq!(checkpoint["note"].as_str(), Some("first pass"));
+    assert!(checkpoint["snapshot"].is_string());
+
+    std::fs::write(project.join("a.txt"), "three").unwrap();
+    let output = h.run_cli(&["session", "checkpoint", "Milestones", "--rollback", "draft"]);
+    assert!(
+        output.status.success(),
+        "rollback failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        std::fs::read_to_string(project.join("a.txt")).unwrap(),
+        "two"
+    );
+
+    let output = h.run_cli(&["session", "checkpoint", "Milestones"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("draft"), "{}", stdout);
+    assert!(stdout.contains("before rollback to draft"), "{}", stdout);
+}
+
+#[test]
+#[serial]
+fn test_cli_remove_requires_steal_for_foreign_owner() {
+    let h = TuiTestHarness::new("cli_steal");
+    let project = h.project_path();
+
+    let output = h.run_cli(&["add", project.to_str().unwrap(), "-t", "Shared Run"]);
+    assert!(output.status.success());
+
+    // Hand the session to someone else, as if created on another machine
+    let mut sessions = read_sessions_json(&h);
+    assert!(sessions[0]["owner"].as_str().unwrap().contains('@'));
+    sessions[0]["owner"] = "colleague@devbox".into();
+    let sessions_path = if cfg!(target_os = "linux") {
+        h.home_path()
+            .join(".config/agent-of-empires/profiles/default/sessions.json")
+    } else {
+        h.home_path()
+            .join(".agent-of-empires/profiles/default/sessions.json")
+    };
+    std::fs::write(&sessions_path, sessions.to_string()).unwrap();
+
+    let output = h.run_cli(&["session", "rename", "Shared Run", "-t", "Mine"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("owned by colleague@devbox"), "{}", stderr);
+
+    let output = h.run_cli(&["remove", "Shared Run"]);
+    assert!(!output.status.success());
+    assert_eq!(read_sessions_json(&h).as_array().unwrap().len(), 1);
+
+    let output = h.run_cli(&["remove", "Shared Run", "--steal"]);
+    assert!(
+        output.status.success(),
+        "remove --steal failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(read_sessions_json(&h).as_array().unwrap().is_empty());
+}
@@ -1,6 +1,8 @@
+use std::time::{Duration, Instant};
+
 use serial_test::serial;
 
-use crate::harness::TuiTestHarness;
+use crate::harness::{require_tmux, TuiTestHarness};
 
 #[test]
 #[serial]
@@ -26,3 +28,126 @@ fn test_cli_remove_nonexistent() {
         stderr
     );
 }
+
+/// Read the single session's id from the default profile.
+fn only_session_id(h: &TuiTestHarness) -> String {
+    let output = h.run_cli(&["list", "--json"]);
+    let sessions: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("list --json should be valid JSON");
+    sessions[0]["id"]
+        .as_str()
+        .expect("session should have id")
+        .to_string()
+}
+
+#[test]
+#[serial]
+fn test_agent_exiting_nonzero_is_reported_as_error() {
+    require_tmux!();
+
+    let h = TuiTestHarness::new("agent_exits_nonzero");
+    h.stub_failing_agent("claude", 3, Duration::from_secs(1));
+    let project = h.project_path();
+
+    let add = h.run_cli(&["add", project.to_str().unwrap(), "-t", "Crashes"]);
+    assert!(
+        add.status.success(),
+        "aoe add failed: {}",
+        String::from_utf8_lossy(&add.stderr)
+    );
+    let id = only_session_id(&h);
+
+    let start = h.run_cli_on_server(&["session", "start", &id]);
+    assert!(
+        start.status.success(),
+        "aoe session start failed: {}",
+        String::from_utf8_lossy(&start.stderr)
+    );
+
+    let deadline = Instant::now() + Duration::from_secs(15);
+    loop {
+        let status = h.run_cli_on_server(&["status", "--json"]);
+        let counts: serde_json::Value =
+            serde_json::from_slice(&status.stdout).expect("status --json should be valid JSON");
+        if counts["error"] == 1 {
+            break;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "session never reported the agent's exit as an error: {}",
+            counts
+        );
+        std::thread::sleep(Duration::from_millis(250));
+    }
+}
+
+#[test]
+#[serial]
+fn test_read_only_home_fails_cleanly() {
+    let mut h = TuiTestHarness::new("read_only_home");
+    let project = h.project_path();
+    if !h.make_home_read_only() {
+        eprintln!("Skipping test: permission bits are not enforced for this user");
+        return;
+    }
+
+    let output = h.run_cli(&["add", project.to_str().unwrap(), "-t", "Unsaved"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !output.status.success(),
+        "aoe add should fail when $HOME is read-only"
+    );
+    assert!(
+        !stderr.contains("panicked"),
+        "aoe add should report the error, not panic.\nstderr: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("Permission denied") || stderr.contains("Read-only"),
+        "expected a permission error.\nstderr: {}",
+        stderr
+    );
+}
+
+#[test]
+#[serial]
+fn test_session_start_reports_refused_tmux_connection() {
+    require_tmux!();
+
+    let h = TuiTestHarness::new("tmux_refused_start");
+    let project = h.project_path();
+    let add = h.run_cli(&["add", project.to_str().unwrap(), "-t", "NoServer"]);
+    assert!(
+        add.status.success(),
+        "aoe add failed: {}",
+        String::from_utf8_lossy(&add.stderr)
+    );
+    let id = only_session_id(&h);
+
+    h.refuse_tmux_connections();
+    let start = h.run_cli(&["session", "start", &id]);
+    let stderr = String::from_utf8_lossy(&start.stderr);
+    assert!(
+        !start.status.success(),
+        "aoe session start should fail when tmux refuses connections"
+    );
+    assert!(
+        stderr.contains("Connection refused"),
+        "expected tmux's error to be surfaced.\nstderr: {}",
+        stderr
+    );
+}
+
+#[test]
+#[serial]
+fn test_tui_survives_refused_tmux_connection() {
+    require_tmux!();
+
+    let mut h = TuiTestHarness::new("tmux_refused_tui");
+    h.refuse_tmux_connections();
+    h.spawn_tui();
+
+    h.wait_for("Agent of Empires");
+    h.send_keys("q");
+    h.wait_for_exit(Duration::from_secs(5));
+}
@@ -10,9 +10,29 @@
 //! Set `RECORD_E2E=1` to record each TUI test as an asciinema `.cast` file and
 //! convert it to a GIF via `agg`. Recordings are saved to
 //! `target/e2e-recordings/`. Both `asciinema` and `agg` must be on `$PATH`.
+//!
+//! ## Pooled tmux servers
+//!
+//! By default every harness starts its own tmux server. Set
+//! `AOE_E2E_TMUX_POOL=<n>` to reuse `n` long-lived servers across the tests
+//! in a run instead. This only reuses servers: tests on one server are not
+//! isolated from each other, so they must stay `#[serial]`. On drop the
+//! harness kills the sessions started under its temp `$HOME` (tmux
+//! `session_path`) and fails the test if any are left on the server.
+//!
+//! ## Failure injection
+//!
+//! Error paths get e2e coverage by breaking the environment `aoe` runs in:
+//! `stub_failing_agent` replaces an agent with one that exits non-zero,
+//! `make_home_read_only` takes write access to the isolated `$HOME` away, and
+//! `refuse_tmux_connections` puts a `tmux` on `aoe`'s `PATH` that cannot
+//! reach a server. They only affect this harness's `aoe` processes; the
+//! harness keeps talking to its own tmux server.
 
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
 use tempfile::TempDir;
@@ -99,6 +119,158 @@ fn convert_cast_to_gif(cast_path: &Path) {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Shared tmux server pool
+// ---------------------------------------------------------------------------
+
+const POOL_ENV: &str = "AOE_E2E_TMUX_POOL";
+const POOL_KEEPALIVE_SESSION: &str = "aoe_e2e_pool_keepalive";
+
+struct TmuxServerPool {
+    sockets: Vec<PathBuf>,
+    started: Mutex<Vec<bool>>,
+    next: AtomicUsize,
+}
+
+impl TmuxServerPool {
+    /// Returns the process-wide pool, or `None` when pooling is not enabled.
+    fn get() -> Option<&'static TmuxServerPool> {
+        static POOL: OnceLock<Option<TmuxServerPool>> = OnceLock::new();
+        POOL.get_or_init(|| {
+            let size = std::env::var(POOL_ENV)
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|&n| n > 0)?;
+            // Not a TempDir: statics are never dropped, and the servers remove
+            // themselves once the keepalive session sees this process exit.
+            let dir = std::env::temp_dir().join(format!("aoe-e2e-pool-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).expect("create tmux pool dir");
+            Some(TmuxServerPool {
+                sockets: (0..size)
+                    .map(|i| dir.join(format!("tmux-{}.sock", i)))
+                    .collect(),
+                started: Mutex::new(vec![false; size]),
+                next: AtomicUsize::new(0),
+            })
+        })
+        .as_ref()
+    }
+
+    /// Hand out a server socket round-robin, starting the server on first use.
+    fn lease(&self) -> PathBuf {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.sockets.len();
+        let socket = self.sockets[index].clone();
+        let mut started = self.started.lock().unwrap_or_else(|e| e.into_inner());
+        if !started[index] {
+            Self::start_server(&socket);
+            started[index] = true;
+        }
+        socket
+    }
+
+    /// Start a server held open by a keepalive session. tmux exits once its
+    /// last session is gone, so without this the server would be torn down
+    /// (and restarted) between tests. The keepalive ends when the test
+    /// process does, which lets the server exit on its own.
+    fn start_server(socket: &Path) {
+        let keepalive = format!(
+            "sh -c 'while kill -0 {} 2>/dev/null; do sleep 1; done'",
+            std::process::id()
+        );
+        let output = Command::new("tmux")
+            .arg("-S")
+            .arg(socket)
+            .args(["new-session", "-d", "-s", POOL_KEEPALIVE_SESSION])
+            .arg(&keepalive)
+            .output()
+            .expect("failed to start pooled tmux server");
+        assert!(
+            output.status.success(),
+            "starting pooled tmux server failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
+
+/// Names of sessions on `socket` whose working directory is under `root`.
+fn sessions_under(socket: &Path, root: &Path) -> Vec<String> {
+    let output = Command::new("tmux")
+        .arg("-S")
+        .arg(socket)
+        .args(["list-sessions", "-F", "#{session_name}\t#{session_path}"])
+        .output();
+    let Ok(output) = output else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .filter(|(_, path)| Path::new(path).starts_with(root))
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Stubs and permissions
+// ---------------------------------------------------------------------------
+
+/// Write an executable script `name` into `dir`.
+fn write_stub(dir: &Path, name: &str, script: &str) {
+    let path = dir.join(name);
+    std::fs::write(&path, script).unwrap_or_else(|e| panic!("write {} stub: {}", name, e));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+            .unwrap_or_else(|e| panic!("chmod {} stub: {}", name, e));
+    }
+}
+
+fn which(program: &str) -> Option<PathBuf> {
+    std::env::var_os("PATH").and_then(|path| {
+        std::env::split_paths(&path)
+            .map(|dir| dir.join(program))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+/// Add or remove the owner's write permission on `root` and everything
+/// under it.
+#[cfg(unix)]
+fn set_tree_writable(root: &Path, writable: bool) {
+    use std::os::unix::fs::PermissionsExt;
+    let Ok(meta) = std::fs::symlink_metadata(root) else {
+        return;
+    };
+    if meta.file_type().is_symlink() {
+        return;
+    }
+    let mode = meta.permissions().mode();
+    let mode = if writable {
+        mode | 0o200
+    } else {
+        mode & !0o222
+    };
+    let _ = std::fs::set_permissions(root, std::fs::Permissions::from_mode(mode));
+    if meta.is_dir() {
+        if let Ok(entries) = std::fs::read_dir(root) {
+            for entry in entries.flatten() {
+                set_tree_writable(&entry.path(), writable);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn set_tree_writable(root: &Path, writable: bool) {
+    let Ok(meta) = std::fs::metadata(root) else {
+        return;
+    };
+    let mut permissions = meta.permissions();
+    permissions.set_readonly(!writable);
+    let _ = std::fs::set_permissions(root, permissions);
+}
+
 // ---------------------------------------------------------------------------
 // TuiTestHarness
 // ---------------------------------------------------------------------------
@@ -111,7 +283,11 @@ pub struct TuiTestHarness {
     binary_path: PathBuf,
     stub_path: PathBuf,
     socket_path: PathBuf,
+    /// Whether `socket_path` is a shared server from the pool.
+    pooled: bool,
     spawned: bool,
+    /// Whether `make_home_read_only` was called, so drop can undo it.
+    home_read_only: bool,
     recording: bool,
     cast_path: Option<PathBuf>,
 }
@@ -127,19 +303,18 @@ impl TuiTestHarness {
         // Unique session name to avoid collisions.
         let session_name = format!("aoe_e2e_{}_{}", test_name, std::process::id());
 
-        // Path to unique tmux socket for this test.
-        let socket_path = home_dir.path().join("tmux.sock");
+        // Path to unique tmux socket for this test, or a shared pooled one.
+        // Kept out of $HOME so the server still starts when it is read-only.
+        let pool = TmuxServerPool::get();
+        let pooled = pool.is_some();
+        let socket_path = match pool {
+            Some(pool) => pool.lease(),
+            None => stub_dir.path().join("tmux.sock"),
+        };
 
         // Create a fake `claude` script so `which claude` succeeds.
         let stub_path = stub_dir.path().to_path_buf();
-        let claude_stub = stub_path.join("claude");
-        std::fs::write(&claude_stub, "#!/bin/sh\nexit 0\n").expect("write claude stub");
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            std::fs::set_permissions(&claude_stub, std::fs::Permissions::from_mode(0o755))
-                .expect("chmod claude stub");
-        }
+        write_stub(&stub_path, "claude", "#!/bin/sh\nexit 0\n");
 
         // Pre-seed config.toml to skip the welcome dialog and update checks.
         // On Linux the app uses $XDG_CONFIG_HOME/agent-of-empires/ (set below),
@@ -181,7 +356,9 @@ last_seen_version = "{}"
             binary_path,
             stub_path,
             socket_path,
+            pooled,
             spawned: false,
+            home_read_only: false,
             recording,
             cast_path: None,
         }
@@ -226,8 +403,15 @@ last_seen_version = "{}"
     pub fn spawn(&mut self, args: &[&str]) {
         let cmd_str = self.build_tmux_command(args);
 
-        let output = Command::new("tmux")
-            .arg("-S")
+        let home = self.home_dir.path().to_path_buf();
+        let xdg_config_home = home.join(".config");
+        let env_path = self.env_path();
+
+        // Resolved here: with PATH overridden below, "tmux" would find a stub
+        // left by `refuse_tmux_connections`.
+        let tmux = which("tmux").unwrap_or_else(|| PathBuf::from("tmux"));
+        let mut cmd = Command::new(tmux);
+        cmd.arg("-S")
             .arg(&self.socket_path)
             .arg("new-session")
             .arg("-d")
@@ -236,11 +420,26 @@ last_seen_version = "{}"
             .arg("-x")
             .arg("100")
             .arg("-y")
-            .arg("30")
+            .arg("30");
+        if self.pooled {
+            // A running server ignores the client's environment, so pass the
+            // isolation variables explicitly. The start directory is how the
+            // harness finds this test's sessions again on drop.
+            cmd.arg("-c").arg(&home);
+            for (key, value) in [
+                ("HOME", home.display().to_string()),
+                ("XDG_CONFIG_HOME", xdg_config_home.display().to_string()),
+                ("PATH", env_path.clone()),
+                ("TERM", "xterm-256color".to_string()),
+            ] {
+                cmd.arg("-e").arg(format!("{}={}", key, value));
+            }
+        }
+        let output = cmd
             .arg(&cmd_str)
-            .env("HOME", self.home_dir.path())
-            .env("XDG_CONFIG_HOME", self.home_dir.path().join(".config"))
-            .env("PATH", self.env_path())
+            .env("HOME", &home)
+            .env("XDG_CONFIG_HOME", &xdg_config_home)
+            .env("PATH", &env_path)
             .env("TERM", "xterm-256color")
             .output()
             .expect("failed to run tmux new-session");
@@ -259,6 +458,41 @@ last_seen_version = "{}"
         std::thread::sleep(Duration::from_millis(delay));
     }
 
+    /// Spawn the TUI playing `script` (see `aoe --replay`) followed by
+    /// `args`. Keys go in as soon as the TUI has handled the ones before
+    /// them, so no sleeps are needed between steps; use `read_capture` for
+    /// the screens the script's `capture` steps write.
+    pub fn spawn_replay(&mut self, script: &str, args: &[&str]) {
+        let path = self.home_dir.path().join("replay.txt");
+        std::fs::write(&path, script).expect("write replay script");
+        let path = path.display().to_string();
+        let mut full_args = vec!["--replay", path.as_str()];
+        full_args.extend_from_slice(args);
+        self.spawn(&full_args);
+    }
+
+    /// Wait for the replay script's `capture <name>` to be written and
+    /// return it.
+    pub fn read_capture(&self, name: &str) -> String {
+        let path = self.home_dir.path().join(name);
+        let timeout = Duration::from_secs(10);
+        let start = Instant::now();
+        loop {
+            if let Ok(screen) = std::fs::read_to_string(&path) {
+                return screen;
+            }
+            if start.elapsed() > timeout {
+                panic!(
+                    "Timed out waiting for capture {:?} after {:?}.\n\n--- Screen capture ---\n{}\n--- End screen capture ---",
+                    name,
+                    timeout,
+                    self.capture_screen()
+                );
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+
     /// Send one or more tmux key names (e.g. "Enter", "Escape", "q", "C-c").
     pub fn send_keys(&self, keys: &str) {
         assert!(self.spawned, "must call spawn_tui() or spawn() first");
@@ -391,6 +625,30 @@ last_seen_version = "{}"
             .expect("failed to run aoe CLI")
     }
 
+    /// Run `aoe <args>` like `run_cli`, with `input` piped to its stdin.
+    pub fn run_cli_with_stdin(&self, args: &[&str], input: &str) -> Output {
+        use std::io::Write;
+        use std::process::Stdio;
+
+        let mut child = Command::new(&self.binary_path)
+            .args(args)
+            .env("HOME", self.home_dir.path())
+            .env("XDG_CONFIG_HOME", self.home_dir.path().join(".config"))
+            .env("PATH", self.env_path())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("failed to run aoe CLI");
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(input.as_bytes())
+            .expect("failed to write stdin");
+        child.wait_with_output().expect("failed to run aoe CLI")
+    }
+
     /// Path to the isolated home directory for custom test setup.
     pub fn home_path(&self) -> &Path {
         self.home_dir.path()
@@ -433,22 +691,117 @@ last_seen_version = "{}"
         }
     }
 
+    /// Run `aoe <args>` like `run_cli`, but with its tmux commands sent to
+    /// this harness's server (as they are for `aoe` spawned in the TUI)
+    /// rather than the default one.
+    pub fn run_cli_on_server(&self, args: &[&str]) -> Output {
+        Command::new(&self.binary_path)
+            .args(args)
+            .env("HOME", self.home_dir.path())
+            .env("XDG_CONFIG_HOME", self.home_dir.path().join(".config"))
+            .env("PATH", self.env_path())
+            .env("TMUX", format!("{},0,0", self.socket_path.display()))
+            .output()
+            .expect("failed to run aoe CLI")
+    }
+
+    // -----------------------------------------------------------------------
+    // Failure injection
+    // -----------------------------------------------------------------------
+
+    /// Replace the stub for agent `tool` with one that exits with `code`
+    /// after `after`, like an agent that crashes or fails to authenticate.
+    pub fn stub_failing_agent(&self, tool: &str, code: i32, after: Duration) {
+        let script = format!(
+            "#!/bin/sh\necho \"{} failed\" >&2\nsleep {}\nexit {}\n",
+            tool,
+            after.as_secs_f64(),
+            code
+        );
+        write_stub(&self.stub_path, tool, &script);
+    }
+
+    /// Remove write access to the isolated `$HOME` and everything in it,
+    /// including the config directory. Call after any setup that writes
+    /// there. Returns false when the current user can write anyway (root
+    /// ignores permission bits), in which case the test should be skipped.
+    pub fn make_home_read_only(&mut self) -> bool {
+        set_tree_writable(self.home_dir.path(), false);
+        self.home_read_only = true;
+        let probe = self.home_dir.path().join(".aoe-e2e-write-probe");
+        if std::fs::write(&probe, "").is_ok() {
+            let _ = std::fs::remove_file(&probe);
+            return false;
+        }
+        true
+    }
+
+    /// Put a `tmux` on `aoe`'s `PATH` that reports its version but fails
+    /// every other command the way tmux does when its server refuses the
+    /// connection. Affects `aoe` processes started after this call.
+    pub fn refuse_tmux_connections(&self) {
+        let real_tmux = which("tmux").expect("tmux not found on PATH");
+        let script = format!(
+            "#!/bin/sh\n\
+             if [ \"$1\" = \"-V\" ]; then exec {} -V; fi\n\
+             echo \"error connecting to {} (Connection refused)\" >&2\n\
+             exit 1\n",
+            real_tmux.display(),
+            self.socket_path.display()
+        );
+        write_stub(&self.stub_path, "tmux", &script);
+    }
+
     fn kill_session(&self) {
+        self.kill_named_session(&self.session_name);
+    }
+
+    fn kill_named_session(&self, name: &str) {
         let _ = Command::new("tmux")
             .arg("-S")
             .arg(&self.socket_path)
             .arg("kill-session")
             .arg("-t")
-            .arg(&self.session_name)
+            .arg(format!("={}", name))
             .output();
     }
+
+    /// On a shared server, kill every session this test started (including
+    /// agent sessions created by `aoe`) and check that none survived.
+    fn cleanup_pooled_sessions(&self) {
+        let home = self.home_dir.path();
+        for name in sessions_under(&self.socket_path, home) {
+            self.kill_named_session(&name);
+        }
+        let leaked = sessions_under(&self.socket_path, home);
+        if !leaked.is_empty() && !std::thread::panicking() {
+            panic!(
+                "Test {} left tmux sessions on the pooled server: {:?}",
+                self.test_name, leaked
+            );
+        }
+    }
 }
 
 impl Drop for TuiTestHarness {
     fn drop(&mut self) {
+        if self.home_read_only {
+            // Otherwise the temp dir cannot be removed
+            set_tree_writable(self.home_dir.path(), true);
+        }
         if self.spawned {
             self.kill_session();
         }
+        if self.pooled {
+            self.cleanup_pooled_sessions();
+        } else {
+            // Agent sessions started on this test's own server
+            let _ = Command::new("tmux")
+                .arg("-S")
+                .arg(&self.socket_path)
+                .arg("kill-server")
+                .output();
+        }
 
         // Convert recording to GIF if one was produced.
         if let Some(cast_path) = &self.cast_path {
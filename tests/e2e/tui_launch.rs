@@ -30,3 +30,50 @@ fn test_tui_quit_with_q() {
     h.wait_for_exit(Duration::from_secs(5));
     assert!(!h.session_alive(), "session should have exited after 'q'");
 }
+
+#[test]
+#[serial]
+fn test_demo_mode_shows_sample_sessions_without_touching_real_ones() {
+    require_tmux!();
+
+    let mut h = TuiTestHarness::new("demo");
+    h.spawn(&["--demo"]);
+
+    h.wait_for("fix-login-redirect");
+    h.assert_screen_contains("add-rate-limiter");
+    h.send_keys("q");
+    h.wait_for_exit(Duration::from_secs(5));
+
+    // The sample sessions lived in a throwaway directory
+    let list = h.run_cli(&["list"]);
+    let stdout = String::from_utf8_lossy(&list.stdout);
+    assert!(
+        !stdout.contains("fix-login-redirect"),
+        "demo sessions leaked into the real profile:\n{}",
+        stdout
+    );
+}
+
+#[test]
+#[serial]
+fn test_replay_script_drives_tui_and_writes_captures() {
+    require_tmux!();
+
+    let mut h = TuiTestHarness::new("replay");
+    h.spawn_replay(
+        "until fix-login-redirect\n\
+         key ?\n\
+         capture help.txt\n\
+         key ? q\n",
+        &["--demo"],
+    );
+
+    let help = h.read_capture("help.txt");
+    assert!(
+        help.contains("Keyboard Shortcuts"),
+        "capture should show the help overlay:\n{}",
+        help
+    );
+    h.wait_for_exit(Duration::from_secs(5));
+    assert!(!h.session_alive(), "replayed 'q' should have quit the TUI");
+}
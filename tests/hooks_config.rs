@@ -51,6 +51,7 @@ fn test_repo_hooks_override_global_per_field() -> Result<()> {
         hooks: Some(HooksConfig {
             on_create: vec!["repo_create".to_string()],
             on_launch: vec![],
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -82,6 +83,7 @@ fn test_repo_hooks_override_both_fields() -> Result<()> {
         hooks: Some(HooksConfig {
             on_create: vec!["repo_create".to_string()],
             on_launch: vec!["repo_launch".to_string()],
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -142,6 +144,7 @@ fn test_profile_overrides_on_create_only() -> Result<()> {
         hooks: Some(HooksConfigOverride {
             on_create: Some(vec!["profile_create".to_string()]),
             on_launch: None,
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -170,6 +173,7 @@ fn test_clearing_profile_hooks_restores_global() -> Result<()> {
         hooks: Some(HooksConfigOverride {
             on_create: Some(vec!["profile_create".to_string()]),
             on_launch: Some(vec!["profile_launch".to_string()]),
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -210,6 +214,7 @@ fn test_three_level_resolution() -> Result<()> {
         hooks: Some(HooksConfigOverride {
             on_create: Some(vec!["profile_create".to_string()]),
             on_launch: None,
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -224,6 +229,7 @@ fn test_three_level_resolution() -> Result<()> {
         hooks: Some(HooksConfig {
             on_create: vec![],
             on_launch: vec!["repo_launch".to_string()],
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -251,6 +257,7 @@ fn test_merge_configs_hooks_override() -> Result<()> {
         hooks: Some(HooksConfigOverride {
             on_create: Some(vec!["p1".to_string()]),
             on_launch: None,
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -261,3 +268,39 @@ fn test_merge_configs_hooks_override() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+#[serial]
+fn test_health_check_resolves_across_levels() -> Result<()> {
+    let _temp = setup_temp_home();
+
+    let mut global = Config::default();
+    global.hooks.health_check = Some("global_probe".to_string());
+    save_config(&global)?;
+
+    let profile = ProfileConfig {
+        hooks: Some(HooksConfigOverride {
+            health_check_interval_secs: Some(15),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    save_profile_config("default", &profile)?;
+
+    let resolved = resolve_config("default")?;
+    assert_eq!(resolved.hooks.health_check.as_deref(), Some("global_probe"));
+    assert_eq!(resolved.hooks.health_check_interval_secs, 15);
+
+    let repo = RepoConfig {
+        hooks: Some(HooksConfig {
+            health_check: Some("repo_probe".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let merged = merge_repo_config(resolved, &repo);
+    assert_eq!(merged.hooks.health_check.as_deref(), Some("repo_probe"));
+    assert_eq!(merged.hooks.health_check_interval_secs, 60);
+
+    Ok(())
+}